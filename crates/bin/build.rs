@@ -3,10 +3,39 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// TerminusDB tag built when `TERMINUSDB_VERSION` is not set: the
+/// ParapluOU/terminusdb fork tag rebased onto upstream TerminusDB 12.1
+/// (upstream branch 12.1-rc; no v12.1 release tag yet).
+const DEFAULT_VERSION: &str = "v12.1-rc-paraplu.1";
+
 fn main() {
     println!("cargo:rerun-if-env-changed=TERMINUSDB_VERSION");
     println!("cargo:rerun-if-env-changed=TERMINUSDB_FORCE_REBUILD");
     println!("cargo:rerun-if-env-changed=TERMINUSDB_SOURCE");
+    println!("cargo:rerun-if-env-changed=TERMINUSDB_PREBUILT_URL");
+    println!("cargo:rerun-if-env-changed=TERMINUSDB_PREBUILT_SHA256");
+
+    // Let the library tell the embedded release apart from others it can run
+    // (see `versions.rs`), and name the release assets for this target.
+    let embedded_version = if env::var_os("CARGO_FEATURE_PREBUILT").is_some() {
+        PREBUILT_RELEASE.to_string()
+    } else {
+        env::var("TERMINUSDB_VERSION").unwrap_or_else(|_| DEFAULT_VERSION.to_string())
+    };
+    println!(
+        "cargo:rustc-env=TERMINUSDB_EMBEDDED_VERSION={}",
+        embedded_version
+    );
+    println!(
+        "cargo:rustc-env=TERMINUSDB_TARGET={}",
+        env::var("TARGET").unwrap_or_default()
+    );
+
+    // Without the `embedded` feature there is nothing to build or download:
+    // the library only runs external releases or the Docker image.
+    if env::var_os("CARGO_FEATURE_EMBEDDED").is_none() {
+        return;
+    }
 
     let out_dir = env::var("OUT_DIR").unwrap();
     let binary_path = Path::new(&out_dir).join("terminusdb");
@@ -35,6 +64,20 @@ fn main() {
     let host = env::var("HOST").unwrap_or_default();
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
 
+    // With the `prebuilt` feature, download a released binary instead of
+    // compiling one (no SWI-Prolog or toolchain needed on the build host).
+    if env::var_os("CARGO_FEATURE_PREBUILT").is_some() {
+        let target = if target.is_empty() { &host } else { &target };
+        if let Err(e) = install_prebuilt(target, Path::new(&manifest_dir), Path::new(&out_dir)) {
+            panic!("Failed to install prebuilt TerminusDB binary: {}", e);
+        }
+        println!(
+            "cargo:warning=Successfully installed prebuilt TerminusDB binary at {}",
+            binary_path.display()
+        );
+        return;
+    }
+
     if target != host && !target.is_empty() && !host.is_empty() {
         println!("cargo:warning=Cross-compilation detected: {} -> {}", host, target);
 
@@ -150,12 +193,9 @@ fn main() {
             );
             (dir, false)
         } else {
-            // Fall back to cloning from GitHub.
-            // Pinned to the ParapluOU/terminusdb fork tag rebased onto upstream
-            // TerminusDB 12.1 (upstream branch 12.1-rc; no v12.1 release tag yet).
+            // Fall back to cloning from GitHub, pinned to DEFAULT_VERSION.
             // Override with TERMINUSDB_VERSION to track a moving branch or bump.
-            let version =
-                env::var("TERMINUSDB_VERSION").unwrap_or_else(|_| "v12.1-rc-paraplu.1".to_string());
+            let version = embedded_version;
             println!("cargo:warning=Building TerminusDB version: {}", version);
 
             let temp_dir = env::temp_dir().join("terminusdb-build");
@@ -1118,6 +1158,132 @@ fn verify_md5(path: &Path, expected: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Release tag the `prebuilt` feature downloads; kept in step with the default
+/// source tag so both paths embed the same server.
+const PREBUILT_RELEASE: &str = DEFAULT_VERSION;
+
+/// Download the release archive for `target`, verify it against its pinned
+/// SHA-256 and unpack the embeddable artifacts into `out_dir`.
+///
+/// The archive (`terminusdb-<target>.tar.gz`) holds `terminusdb` plus
+/// `swipl-home.tar.gz` on Linux or `librust.dylib` on macOS, i.e. exactly what
+/// a source build leaves in `OUT_DIR`. `TERMINUSDB_PREBUILT_URL` replaces the
+/// download URL (e.g. a mirror or `file://` path) and
+/// `TERMINUSDB_PREBUILT_SHA256` the checksum pinned in `prebuilt/SHA256SUMS`.
+fn install_prebuilt(target: &str, manifest_dir: &Path, out_dir: &Path) -> Result<(), String> {
+    let asset = format!("terminusdb-{}.tar.gz", target);
+    let url = env::var("TERMINUSDB_PREBUILT_URL").unwrap_or_else(|_| {
+        format!(
+            "https://github.com/ParapluOU/terminusdb/releases/download/{}/{}",
+            PREBUILT_RELEASE, asset
+        )
+    });
+    let sums = manifest_dir.join("prebuilt").join("SHA256SUMS");
+    println!("cargo:rerun-if-changed={}", sums.display());
+    let expected = match env::var("TERMINUSDB_PREBUILT_SHA256") {
+        Ok(sha) => sha.trim().to_lowercase(),
+        Err(_) => pinned_sha256(&sums, &asset)?.ok_or_else(|| {
+            format!(
+                "no checksum pinned for {} in {}; set TERMINUSDB_PREBUILT_SHA256",
+                asset,
+                sums.display()
+            )
+        })?,
+    };
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "checksum for {} is not a SHA-256 hex digest: {:?}",
+            asset, expected
+        ));
+    }
+
+    // Keep the verified archive under .deps so forced rebuilds skip the download.
+    let archive = manifest_dir
+        .join(".deps")
+        .join("prebuilt")
+        .join(PREBUILT_RELEASE)
+        .join(&asset);
+    if !archive.exists() || sha256_file(&archive)? != expected {
+        println!("cargo:warning=Downloading prebuilt TerminusDB from {}", url);
+        download_file(&url, &archive)?;
+    }
+    let digest = sha256_file(&archive)?;
+    if digest != expected {
+        let _ = fs::remove_file(&archive);
+        return Err(format!(
+            "checksum mismatch for {}: expected {}, got {}",
+            url, expected, digest
+        ));
+    }
+
+    let staging = out_dir.join("prebuilt");
+    let _ = fs::remove_dir_all(&staging);
+    let file = fs::File::open(&archive).map_err(|e| e.to_string())?;
+    tar::Archive::new(flate2::read::GzDecoder::new(file))
+        .unpack(&staging)
+        .map_err(|e| format!("unpack {}: {}", archive.display(), e))?;
+
+    let mut artifacts = vec!["terminusdb"];
+    if target.contains("linux") {
+        artifacts.push("swipl-home.tar.gz");
+    } else if target.contains("apple-darwin") {
+        artifacts.push("librust.dylib");
+    }
+    for artifact in artifacts {
+        let src = staging.join(artifact);
+        if !src.exists() {
+            return Err(format!("{} is missing {}", asset, artifact));
+        }
+        fs::copy(&src, out_dir.join(artifact))
+            .map_err(|e| format!("copy {}: {}", artifact, e))?;
+    }
+    let _ = fs::remove_dir_all(&staging);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let binary = out_dir.join("terminusdb");
+        let mut perms = fs::metadata(&binary).map_err(|e| e.to_string())?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&binary, perms).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Look up `asset` in a `sha256sum`-format file (`#` lines are comments).
+fn pinned_sha256(sums: &Path, asset: &str) -> Result<Option<String>, String> {
+    let contents =
+        fs::read_to_string(sums).map_err(|e| format!("read {}: {}", sums.display(), e))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == asset)
+        .map(|(sha, _)| sha.to_lowercase()))
+}
+
+/// SHA-256 of a file via `sha256sum` or `shasum -a 256` (BSD/macOS). Unlike
+/// `verify_md5` this is not best-effort: a missing tool is an error.
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let out = match Command::new("sha256sum").arg(path).output() {
+        Ok(o) if o.status.success() => o,
+        _ => Command::new("shasum")
+            .args(["-a", "256"])
+            .arg(path)
+            .output()
+            .map_err(|e| format!("no sha256sum or shasum available: {}", e))?,
+    };
+    if !out.status.success() {
+        return Err(format!("failed to hash {}", path.display()));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_lowercase())
+}
+
 /// Pack a relocatable swipl root into `out_file` as a `.tar.gz` blob that
 /// `lib.rs` embeds and extracts at runtime (pure-Rust gzip via flate2).
 fn pack_swipl_home(swipl_root: &Path, out_file: &Path) -> Result<(), String> {
@@ -3,7 +3,10 @@
 // Allow hidden lifetime parameters in return types - this is intentional for ergonomic API design
 #![allow(mismatched_lifetime_syntaxes)]
 
-use super::commands::{add_flag, add_option, add_required, execute};
+use super::commands::{
+    add_flag, add_option, add_required, execute, BranchCreate, BranchDelete, DbUpdate, DocDelete,
+    DocGet, DocInsert, DocReplace, Rollup, StoreInit, ToArgs, TriplesDump, TriplesLoad,
+};
 use super::options::*;
 use super::spec::{BranchSpec, DbSpec, GraphSpec};
 use std::process::ExitStatus;
@@ -69,11 +72,15 @@ impl TerminusDB {
     /// Execute a WOQL query.
     pub fn query(
         &self,
-        db_spec: DbSpec,
+        db_spec: impl Into<DbSpec>,
         query: &str,
         options: QueryOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["query".to_string(), db_spec.to_string(), query.to_string()];
+        let mut args = vec![
+            "query".to_string(),
+            db_spec.into().to_string(),
+            query.to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--message", options.message.as_ref());
         add_required(&mut args, "--author", options.author.as_ref());
@@ -119,8 +126,16 @@ pub struct DbCommands<'a> {
 
 impl<'a> DbCommands<'a> {
     /// Create a new database.
-    pub fn create(&self, spec: DbSpec, options: DbCreateOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["db".to_string(), "create".to_string(), spec.to_string()];
+    pub fn create(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DbCreateOptions,
+    ) -> std::io::Result<ExitStatus> {
+        let mut args = vec![
+            "db".to_string(),
+            "create".to_string(),
+            spec.into().to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--organization", &options.organization);
         add_option(&mut args, "--label", &options.label);
@@ -134,8 +149,16 @@ impl<'a> DbCommands<'a> {
     }
 
     /// Delete a database.
-    pub fn delete(&self, spec: DbSpec, options: DbDeleteOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["db".to_string(), "delete".to_string(), spec.to_string()];
+    pub fn delete(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DbDeleteOptions,
+    ) -> std::io::Result<ExitStatus> {
+        let mut args = vec![
+            "db".to_string(),
+            "delete".to_string(),
+            spec.into().to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--organization", &options.organization);
         add_flag(&mut args, "--force", options.force);
@@ -156,21 +179,12 @@ impl<'a> DbCommands<'a> {
     }
 
     /// Update database metadata.
-    pub fn update(&self, spec: DbSpec, options: DbUpdateOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["db".to_string(), "update".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_option(&mut args, "--label", &options.label);
-        add_option(&mut args, "--comment", &options.comment);
-        if let Some(public) = options.public {
-            args.push("--public".to_string());
-            args.push(public.to_string());
-        }
-        if let Some(schema) = options.schema {
-            args.push("--schema".to_string());
-            args.push(schema.to_string());
-        }
-        add_option(&mut args, "--prefixes", &options.prefixes);
-        execute(args)
+    pub fn update(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DbUpdateOptions,
+    ) -> std::io::Result<ExitStatus> {
+        execute(DbUpdate::new(spec).options(options).to_args())
     }
 }
 
@@ -185,87 +199,39 @@ pub struct DocCommands<'a> {
 
 impl<'a> DocCommands<'a> {
     /// Insert documents.
-    pub fn insert(&self, spec: DbSpec, options: DocInsertOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["doc".to_string(), "insert".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--message", options.message.as_ref());
-        add_required(&mut args, "--author", options.author.as_ref());
-        add_required(&mut args, "--graph-type", options.graph_type.as_str());
-        add_flag(&mut args, "--require-migration", options.require_migration);
-        add_flag(
-            &mut args,
-            "--allow-destructive-migration",
-            options.allow_destructive_migration,
-        );
-        add_option(&mut args, "--data", &options.data);
-        add_flag(&mut args, "--raw-json", options.raw_json);
-        add_flag(&mut args, "--merge-repeats", options.merge_repeats);
-        add_flag(&mut args, "--full-replace", options.full_replace);
-        execute(args)
+    pub fn insert(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DocInsertOptions,
+    ) -> std::io::Result<ExitStatus> {
+        execute(DocInsert::new(spec).options(options).to_args())
     }
 
     /// Delete documents.
-    pub fn delete(&self, spec: DbSpec, options: DocDeleteOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["doc".to_string(), "delete".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--message", options.message.as_ref());
-        add_required(&mut args, "--author", options.author.as_ref());
-        add_required(&mut args, "--graph-type", options.graph_type.as_str());
-        add_flag(&mut args, "--require-migration", options.require_migration);
-        add_flag(
-            &mut args,
-            "--allow-destructive-migration",
-            options.allow_destructive_migration,
-        );
-        add_option(&mut args, "--id", &options.id);
-        add_option(&mut args, "--type", &options.doc_type);
-        add_option(&mut args, "--data", &options.data);
-        add_flag(&mut args, "--nuke", options.nuke);
-        execute(args)
+    pub fn delete(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DocDeleteOptions,
+    ) -> std::io::Result<ExitStatus> {
+        execute(DocDelete::new(spec).options(options).to_args())
     }
 
     /// Replace documents.
-    pub fn replace(&self, spec: DbSpec, options: DocReplaceOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["doc".to_string(), "replace".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--message", options.message.as_ref());
-        add_required(&mut args, "--author", options.author.as_ref());
-        add_required(&mut args, "--graph-type", options.graph_type.as_str());
-        add_flag(&mut args, "--require-migration", options.require_migration);
-        add_flag(
-            &mut args,
-            "--allow-destructive-migration",
-            options.allow_destructive_migration,
-        );
-        add_option(&mut args, "--data", &options.data);
-        add_flag(&mut args, "--raw-json", options.raw_json);
-        add_flag(&mut args, "--create", options.create);
-        execute(args)
+    pub fn replace(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DocReplaceOptions,
+    ) -> std::io::Result<ExitStatus> {
+        execute(DocReplace::new(spec).options(options).to_args())
     }
 
     /// Get documents.
-    pub fn get(&self, spec: DbSpec, options: DocGetOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["doc".to_string(), "get".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--graph-type", options.graph_type.as_str());
-        add_required(&mut args, "--skip", options.skip.to_string());
-        if let Some(count) = options.count {
-            add_required(&mut args, "--count", count.to_string());
-        } else {
-            add_required(&mut args, "--count", "unlimited");
-        }
-        add_flag(&mut args, "--minimized", options.minimized);
-        add_flag(&mut args, "--as-list", options.as_list);
-        add_flag(&mut args, "--unfold", options.unfold);
-        add_option(&mut args, "--id", &options.id);
-        if !options.ids.is_empty() {
-            args.push("--ids".to_string());
-            args.push(format!("[{}]", options.ids.join(",")));
-        }
-        add_option(&mut args, "--type", &options.doc_type);
-        add_flag(&mut args, "--compress-ids", options.compress_ids);
-        add_option(&mut args, "--query", &options.query);
-        execute(args)
+    pub fn get(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: DocGetOptions,
+    ) -> std::io::Result<ExitStatus> {
+        execute(DocGet::new(spec).options(options).to_args())
     }
 }
 
@@ -282,28 +248,19 @@ impl<'a> BranchCommands<'a> {
     /// Create a new branch.
     pub fn create(
         &self,
-        spec: BranchSpec,
+        spec: impl Into<BranchSpec>,
         options: BranchCreateOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["branch".to_string(), "create".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        if let Some(origin) = &options.origin {
-            add_required(&mut args, "--origin", origin);
-        } else {
-            add_required(&mut args, "--origin", "false");
-        }
-        execute(args)
+        execute(BranchCreate::new(spec).options(options).to_args())
     }
 
     /// Delete a branch.
     pub fn delete(
         &self,
-        spec: BranchSpec,
+        spec: impl Into<BranchSpec>,
         options: BranchDeleteOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["branch".to_string(), "delete".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        execute(args)
+        execute(BranchDelete::new(spec).options(options).to_args())
     }
 }
 
@@ -318,8 +275,12 @@ pub struct GitCommands<'a> {
 
 impl<'a> GitCommands<'a> {
     /// Push to remote.
-    pub fn push(&self, spec: DbSpec, options: PushOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["push".to_string(), spec.to_string()];
+    pub fn push(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: PushOptions,
+    ) -> std::io::Result<ExitStatus> {
+        let mut args = vec!["push".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--branch", &options.branch);
         add_option(&mut args, "--remote-branch", &options.remote_branch);
@@ -355,8 +316,12 @@ impl<'a> GitCommands<'a> {
     }
 
     /// Pull from remote.
-    pub fn pull(&self, spec: BranchSpec, options: PullOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["pull".to_string(), spec.to_string()];
+    pub fn pull(
+        &self,
+        spec: impl Into<BranchSpec>,
+        options: PullOptions,
+    ) -> std::io::Result<ExitStatus> {
+        let mut args = vec!["pull".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_option(&mut args, "--remote-branch", &options.remote_branch);
         add_required(&mut args, "--remote", &options.remote);
@@ -367,8 +332,12 @@ impl<'a> GitCommands<'a> {
     }
 
     /// Fetch from remote.
-    pub fn fetch(&self, spec: DbSpec, options: FetchOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["fetch".to_string(), spec.to_string()];
+    pub fn fetch(
+        &self,
+        spec: impl Into<DbSpec>,
+        options: FetchOptions,
+    ) -> std::io::Result<ExitStatus> {
+        let mut args = vec!["fetch".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--remote", &options.remote);
         add_option(&mut args, "--token", &options.token);
@@ -380,11 +349,15 @@ impl<'a> GitCommands<'a> {
     /// Rebase branches.
     pub fn rebase(
         &self,
-        to: DbSpec,
-        from: DbSpec,
+        to: impl Into<DbSpec>,
+        from: impl Into<DbSpec>,
         options: RebaseOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["rebase".to_string(), to.to_string(), from.to_string()];
+        let mut args = vec![
+            "rebase".to_string(),
+            to.into().to_string(),
+            from.into().to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_required(&mut args, "--author", options.author.as_ref());
         execute(args)
@@ -670,11 +643,7 @@ pub struct StoreCommands<'a> {
 impl<'a> StoreCommands<'a> {
     /// Initialize the store.
     pub fn init(&self, options: super::options::StoreInitOptions) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["store".to_string(), "init".to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--key", &options.key);
-        add_flag(&mut args, "--force", options.force);
-        execute(args)
+        execute(StoreInit::new().options(options).to_args())
     }
 }
 
@@ -694,14 +663,7 @@ impl<'a> TriplesCommands<'a> {
         graph_spec: GraphSpec,
         options: super::options::TriplesDumpOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec![
-            "triples".to_string(),
-            "dump".to_string(),
-            graph_spec.to_string(),
-        ];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--format", options.format.as_str());
-        execute(args)
+        execute(TriplesDump::new(graph_spec).options(options).to_args())
     }
 
     /// Update triples in a graph from a file.
@@ -731,17 +693,11 @@ impl<'a> TriplesCommands<'a> {
         file: &str,
         options: super::options::TriplesLoadOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec![
-            "triples".to_string(),
-            "load".to_string(),
-            graph_spec.to_string(),
-            file.to_string(),
-        ];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        add_required(&mut args, "--message", options.message.as_ref());
-        add_required(&mut args, "--author", options.author.as_ref());
-        add_required(&mut args, "--format", options.format.as_str());
-        execute(args)
+        execute(
+            TriplesLoad::new(graph_spec, file)
+                .options(options)
+                .to_args(),
+        )
     }
 }
 
@@ -758,7 +714,7 @@ impl<'a> RemoteCommands<'a> {
     /// Add a remote.
     pub fn add(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         remote_name: &str,
         remote_location: &str,
         options: super::options::RemoteAddOptions,
@@ -766,7 +722,7 @@ impl<'a> RemoteCommands<'a> {
         let mut args = vec![
             "remote".to_string(),
             "add".to_string(),
-            spec.to_string(),
+            spec.into().to_string(),
             remote_name.to_string(),
             remote_location.to_string(),
         ];
@@ -777,14 +733,14 @@ impl<'a> RemoteCommands<'a> {
     /// Remove a remote.
     pub fn remove(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         remote_name: &str,
         options: super::options::RemoteRemoveOptions,
     ) -> std::io::Result<ExitStatus> {
         let mut args = vec![
             "remote".to_string(),
             "remove".to_string(),
-            spec.to_string(),
+            spec.into().to_string(),
             remote_name.to_string(),
         ];
         add_required(&mut args, "--impersonate", &options.impersonate);
@@ -794,7 +750,7 @@ impl<'a> RemoteCommands<'a> {
     /// Set the URL of a remote.
     pub fn set_url(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         remote_name: &str,
         remote_location: &str,
         options: super::options::RemoteSetUrlOptions,
@@ -802,7 +758,7 @@ impl<'a> RemoteCommands<'a> {
         let mut args = vec![
             "remote".to_string(),
             "set-url".to_string(),
-            spec.to_string(),
+            spec.into().to_string(),
             remote_name.to_string(),
             remote_location.to_string(),
         ];
@@ -813,14 +769,14 @@ impl<'a> RemoteCommands<'a> {
     /// Get the URL of a remote.
     pub fn get_url(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         remote_name: &str,
         options: super::options::RemoteGetUrlOptions,
     ) -> std::io::Result<ExitStatus> {
         let mut args = vec![
             "remote".to_string(),
             "get-url".to_string(),
-            spec.to_string(),
+            spec.into().to_string(),
             remote_name.to_string(),
         ];
         add_required(&mut args, "--impersonate", &options.impersonate);
@@ -831,10 +787,14 @@ impl<'a> RemoteCommands<'a> {
     /// List all remotes.
     pub fn list(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::RemoteListOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["remote".to_string(), "list".to_string(), spec.to_string()];
+        let mut args = vec![
+            "remote".to_string(),
+            "list".to_string(),
+            spec.into().to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         execute(args)
     }
@@ -883,10 +843,10 @@ impl TerminusDB {
     /// Optimize a database.
     pub fn optimize(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::OptimizeOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["optimize".to_string(), spec.to_string()];
+        let mut args = vec!["optimize".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         execute(args)
     }
@@ -894,10 +854,10 @@ impl TerminusDB {
     /// Squash commits.
     pub fn squash(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::SquashOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["squash".to_string(), spec.to_string()];
+        let mut args = vec!["squash".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_flag(&mut args, "--json", options.json);
         add_required(&mut args, "--message", options.message.as_ref());
@@ -908,21 +868,19 @@ impl TerminusDB {
     /// Rollup commits.
     pub fn rollup(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::RollupOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["rollup".to_string(), spec.to_string()];
-        add_required(&mut args, "--impersonate", &options.impersonate);
-        execute(args)
+        execute(Rollup::new(spec).options(options).to_args())
     }
 
     /// Create a bundle.
     pub fn bundle(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::BundleOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["bundle".to_string(), spec.to_string()];
+        let mut args = vec!["bundle".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_option(&mut args, "--output", &options.output);
         execute(args)
@@ -931,11 +889,15 @@ impl TerminusDB {
     /// Apply a bundle.
     pub fn unbundle(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         file: &str,
         options: super::options::UnbundleOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["unbundle".to_string(), spec.to_string(), file.to_string()];
+        let mut args = vec![
+            "unbundle".to_string(),
+            spec.into().to_string(),
+            file.to_string(),
+        ];
         add_required(&mut args, "--impersonate", &options.impersonate);
         execute(args)
     }
@@ -943,10 +905,10 @@ impl TerminusDB {
     /// View commit log.
     pub fn log(
         &self,
-        spec: DbSpec,
+        spec: impl Into<DbSpec>,
         options: super::options::LogOptions,
     ) -> std::io::Result<ExitStatus> {
-        let mut args = vec!["log".to_string(), spec.to_string()];
+        let mut args = vec!["log".to_string(), spec.into().to_string()];
         add_required(&mut args, "--impersonate", &options.impersonate);
         add_flag(&mut args, "--json", options.json);
         add_required(&mut args, "--start", options.start.to_string());
@@ -958,13 +920,13 @@ impl TerminusDB {
     /// Reset a branch to a specific commit.
     pub fn reset(
         &self,
-        branch_spec: BranchSpec,
+        branch_spec: impl Into<BranchSpec>,
         commit_spec: &str,
         options: super::options::ResetOptions,
     ) -> std::io::Result<ExitStatus> {
         let mut args = vec![
             "reset".to_string(),
-            branch_spec.to_string(),
+            branch_spec.into().to_string(),
             commit_spec.to_string(),
         ];
         add_required(&mut args, "--impersonate", &options.impersonate);
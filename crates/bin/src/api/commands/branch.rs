@@ -0,0 +1,158 @@
+//! Builders for `branch create` and `branch delete`.
+
+use super::{add_required, execute_output, ToArgs};
+use crate::api::options::{BranchCreateOptions, BranchDeleteOptions};
+use crate::api::output::{BranchCreated, BranchDeleted};
+use crate::api::spec::BranchSpec;
+
+/// Typed builder for `branch create`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::{BranchCreate, DbSpec};
+///
+/// let created = BranchCreate::new(DbSpec::new("admin", "mydb").branch("dev"))
+///     .origin("admin/mydb/local/branch/main")
+///     .run()?;
+/// println!("{}", created.message);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BranchCreate {
+    spec: BranchSpec,
+    options: BranchCreateOptions,
+}
+
+impl BranchCreate {
+    /// Create a branch with the default options (branched from `main`).
+    pub fn new(spec: impl Into<BranchSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: BranchCreateOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: BranchCreateOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Branch from the given origin.
+    pub fn origin(mut self, origin: impl Into<String>) -> Self {
+        self.options.origin = Some(origin.into());
+        self
+    }
+
+    /// Create an empty branch with no origin.
+    pub fn no_origin(mut self) -> Self {
+        self.options.origin = None;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<BranchCreated> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(BranchCreated {
+            branch: self.spec.clone(),
+            origin: self.options.origin.clone(),
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for BranchCreate {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "branch".to_string(),
+            "create".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        match &self.options.origin {
+            Some(origin) => add_required(&mut args, "--origin", origin),
+            None => add_required(&mut args, "--origin", "false"),
+        }
+        args
+    }
+}
+
+/// Typed builder for `branch delete`.
+#[derive(Debug, Clone)]
+pub struct BranchDelete {
+    spec: BranchSpec,
+    options: BranchDeleteOptions,
+}
+
+impl BranchDelete {
+    /// Delete a branch with the default options.
+    pub fn new(spec: impl Into<BranchSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: BranchDeleteOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: BranchDeleteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<BranchDeleted> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(BranchDeleted {
+            branch: self.spec.clone(),
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for BranchDelete {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "branch".to_string(),
+            "delete".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::spec::DbSpec;
+
+    #[test]
+    fn test_branch_create_args() {
+        let spec = DbSpec::new("admin", "mydb").branch("dev");
+        assert_eq!(
+            BranchCreate::new(spec.clone()).no_origin().to_args(),
+            vec![
+                "branch",
+                "create",
+                "admin/mydb/local/branch/dev",
+                "--impersonate",
+                "admin",
+                "--origin",
+                "false",
+            ]
+        );
+        let args = BranchCreate::new(spec).origin("admin/mydb").to_args();
+        assert_eq!(args[args.len() - 1], "admin/mydb");
+    }
+}
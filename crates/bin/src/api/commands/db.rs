@@ -0,0 +1,179 @@
+//! Builders for `db update` and `rollup`.
+
+use super::{add_option, add_required, execute_output, ToArgs};
+use crate::api::options::{DbUpdateOptions, RollupOptions};
+use crate::api::output::{DbUpdated, RolledUp};
+use crate::api::spec::DbSpec;
+
+/// Typed builder for `db update`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::{DbSpec, DbUpdate};
+///
+/// DbUpdate::new(DbSpec::new("admin", "mydb"))
+///     .label("My Database")
+///     .public(false)
+///     .run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DbUpdate {
+    spec: DbSpec,
+    options: DbUpdateOptions,
+}
+
+impl DbUpdate {
+    /// Update a database; fields left unset keep their current values.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: DbUpdateOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: DbUpdateOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the database label.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.options.label = Some(label.into());
+        self
+    }
+
+    /// Set the long description.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.options.comment = Some(comment.into());
+        self
+    }
+
+    /// Set whether the database is public.
+    pub fn public(mut self, public: bool) -> Self {
+        self.options.public = Some(public);
+        self
+    }
+
+    /// Set whether the database uses a schema.
+    pub fn schema(mut self, schema: bool) -> Self {
+        self.options.schema = Some(schema);
+        self
+    }
+
+    /// Replace the prefix set (JSON).
+    pub fn prefixes(mut self, prefixes: impl Into<String>) -> Self {
+        self.options.prefixes = Some(prefixes.into());
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<DbUpdated> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(DbUpdated {
+            db: self.spec.clone(),
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for DbUpdate {
+    fn to_args(&self) -> Vec<String> {
+        let options = &self.options;
+        let mut args = vec![
+            "db".to_string(),
+            "update".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &options.impersonate);
+        add_option(&mut args, "--label", &options.label);
+        add_option(&mut args, "--comment", &options.comment);
+        if let Some(public) = options.public {
+            add_required(&mut args, "--public", public.to_string());
+        }
+        if let Some(schema) = options.schema {
+            add_required(&mut args, "--schema", schema.to_string());
+        }
+        add_option(&mut args, "--prefixes", &options.prefixes);
+        args
+    }
+}
+
+/// Typed builder for `rollup`.
+#[derive(Debug, Clone)]
+pub struct Rollup {
+    spec: DbSpec,
+    options: RollupOptions,
+}
+
+impl Rollup {
+    /// Roll up the given database or branch.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: RollupOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: RollupOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<RolledUp> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(RolledUp {
+            db: self.spec.clone(),
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for Rollup {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["rollup".to_string(), self.spec.to_string()];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_db_update_args() {
+        let args = DbUpdate::new(DbSpec::new("admin", "mydb"))
+            .label("Renamed")
+            .public(true)
+            .to_args();
+        assert_eq!(
+            args,
+            vec![
+                "db",
+                "update",
+                "admin/mydb/local/branch/main",
+                "--impersonate",
+                "admin",
+                "--label",
+                "Renamed",
+                "--public",
+                "true",
+            ]
+        );
+    }
+}
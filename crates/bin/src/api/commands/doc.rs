@@ -0,0 +1,450 @@
+//! Builders for the `doc` subcommands.
+
+use super::{add_flag, add_option, add_required, execute_output, ToArgs};
+use crate::api::options::{DocDeleteOptions, DocGetOptions, DocInsertOptions, DocReplaceOptions};
+use crate::api::output::{parse_documents, DocumentsDeleted, DocumentsWritten};
+use crate::api::spec::{DbSpec, GraphType};
+use crate::api::types::{Author, Message};
+use serde_json::Value;
+
+/// Typed builder for `doc insert`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::{DbSpec, DocInsert};
+///
+/// let inserted = DocInsert::new(DbSpec::new("admin", "mydb"))
+///     .data(r#"{"@type": "Person", "name": "Alice"}"#)
+///     .message("add alice")
+///     .run()?;
+/// println!("{:?}", inserted.ids);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocInsert {
+    spec: DbSpec,
+    options: DocInsertOptions,
+}
+
+impl DocInsert {
+    /// Insert documents with the default options.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: DocInsertOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: DocInsertOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the commit message.
+    pub fn message(mut self, message: impl Into<Message>) -> Self {
+        self.options.message = message.into();
+        self
+    }
+
+    /// Set the commit author.
+    pub fn author(mut self, author: impl Into<Author>) -> Self {
+        self.options.author = author.into();
+        self
+    }
+
+    /// Target the instance or schema graph.
+    pub fn graph_type(mut self, graph_type: GraphType) -> Self {
+        self.options.graph_type = graph_type;
+        self
+    }
+
+    /// Documents to insert (JSON object, list or JSON lines).
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.options.data = Some(data.into());
+        self
+    }
+
+    /// Insert as raw JSON.
+    pub fn raw_json(mut self, raw_json: bool) -> Self {
+        self.options.raw_json = raw_json;
+        self
+    }
+
+    /// Replace the whole graph with the given documents.
+    pub fn full_replace(mut self, full_replace: bool) -> Self {
+        self.options.full_replace = full_replace;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<DocumentsWritten> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(DocumentsWritten::from_stdout(&stdout))
+    }
+}
+
+impl ToArgs for DocInsert {
+    fn to_args(&self) -> Vec<String> {
+        let options = &self.options;
+        let mut args = vec![
+            "doc".to_string(),
+            "insert".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &options.impersonate);
+        add_required(&mut args, "--message", options.message.as_ref());
+        add_required(&mut args, "--author", options.author.as_ref());
+        add_required(&mut args, "--graph-type", options.graph_type.as_str());
+        add_flag(&mut args, "--require-migration", options.require_migration);
+        add_flag(
+            &mut args,
+            "--allow-destructive-migration",
+            options.allow_destructive_migration,
+        );
+        add_option(&mut args, "--data", &options.data);
+        add_flag(&mut args, "--raw-json", options.raw_json);
+        add_flag(&mut args, "--merge-repeats", options.merge_repeats);
+        add_flag(&mut args, "--full-replace", options.full_replace);
+        args
+    }
+}
+
+/// Typed builder for `doc replace`.
+#[derive(Debug, Clone)]
+pub struct DocReplace {
+    spec: DbSpec,
+    options: DocReplaceOptions,
+}
+
+impl DocReplace {
+    /// Replace documents with the default options.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: DocReplaceOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: DocReplaceOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the commit message.
+    pub fn message(mut self, message: impl Into<Message>) -> Self {
+        self.options.message = message.into();
+        self
+    }
+
+    /// Set the commit author.
+    pub fn author(mut self, author: impl Into<Author>) -> Self {
+        self.options.author = author.into();
+        self
+    }
+
+    /// Target the instance or schema graph.
+    pub fn graph_type(mut self, graph_type: GraphType) -> Self {
+        self.options.graph_type = graph_type;
+        self
+    }
+
+    /// Replacement documents.
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.options.data = Some(data.into());
+        self
+    }
+
+    /// Create documents that do not exist yet.
+    pub fn create(mut self, create: bool) -> Self {
+        self.options.create = create;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<DocumentsWritten> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(DocumentsWritten::from_stdout(&stdout))
+    }
+}
+
+impl ToArgs for DocReplace {
+    fn to_args(&self) -> Vec<String> {
+        let options = &self.options;
+        let mut args = vec![
+            "doc".to_string(),
+            "replace".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &options.impersonate);
+        add_required(&mut args, "--message", options.message.as_ref());
+        add_required(&mut args, "--author", options.author.as_ref());
+        add_required(&mut args, "--graph-type", options.graph_type.as_str());
+        add_flag(&mut args, "--require-migration", options.require_migration);
+        add_flag(
+            &mut args,
+            "--allow-destructive-migration",
+            options.allow_destructive_migration,
+        );
+        add_option(&mut args, "--data", &options.data);
+        add_flag(&mut args, "--raw-json", options.raw_json);
+        add_flag(&mut args, "--create", options.create);
+        args
+    }
+}
+
+/// Typed builder for `doc delete`.
+#[derive(Debug, Clone)]
+pub struct DocDelete {
+    spec: DbSpec,
+    options: DocDeleteOptions,
+}
+
+impl DocDelete {
+    /// Delete documents with the default options.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: DocDeleteOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: DocDeleteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the commit message.
+    pub fn message(mut self, message: impl Into<Message>) -> Self {
+        self.options.message = message.into();
+        self
+    }
+
+    /// Set the commit author.
+    pub fn author(mut self, author: impl Into<Author>) -> Self {
+        self.options.author = author.into();
+        self
+    }
+
+    /// Target the instance or schema graph.
+    pub fn graph_type(mut self, graph_type: GraphType) -> Self {
+        self.options.graph_type = graph_type;
+        self
+    }
+
+    /// Delete the document with this ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.options.id = Some(id.into());
+        self
+    }
+
+    /// Delete all documents of this type.
+    pub fn doc_type(mut self, doc_type: impl Into<String>) -> Self {
+        self.options.doc_type = Some(doc_type.into());
+        self
+    }
+
+    /// Delete every document in the graph.
+    pub fn nuke(mut self) -> Self {
+        self.options.nuke = true;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<DocumentsDeleted> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(DocumentsDeleted {
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for DocDelete {
+    fn to_args(&self) -> Vec<String> {
+        let options = &self.options;
+        let mut args = vec![
+            "doc".to_string(),
+            "delete".to_string(),
+            self.spec.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &options.impersonate);
+        add_required(&mut args, "--message", options.message.as_ref());
+        add_required(&mut args, "--author", options.author.as_ref());
+        add_required(&mut args, "--graph-type", options.graph_type.as_str());
+        add_flag(&mut args, "--require-migration", options.require_migration);
+        add_flag(
+            &mut args,
+            "--allow-destructive-migration",
+            options.allow_destructive_migration,
+        );
+        add_option(&mut args, "--id", &options.id);
+        add_option(&mut args, "--type", &options.doc_type);
+        add_option(&mut args, "--data", &options.data);
+        add_flag(&mut args, "--nuke", options.nuke);
+        args
+    }
+}
+
+/// Typed builder for `doc get`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::{DbSpec, DocGet};
+///
+/// let people = DocGet::new(DbSpec::new("admin", "mydb"))
+///     .doc_type("Person")
+///     .count(10)
+///     .run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocGet {
+    spec: DbSpec,
+    options: DocGetOptions,
+}
+
+impl DocGet {
+    /// Get documents with the default options.
+    pub fn new(spec: impl Into<DbSpec>) -> Self {
+        Self {
+            spec: spec.into(),
+            options: DocGetOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: DocGetOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Target the instance or schema graph.
+    pub fn graph_type(mut self, graph_type: GraphType) -> Self {
+        self.options.graph_type = graph_type;
+        self
+    }
+
+    /// Skip this many documents.
+    pub fn skip(mut self, skip: usize) -> Self {
+        self.options.skip = skip;
+        self
+    }
+
+    /// Return at most this many documents.
+    pub fn count(mut self, count: usize) -> Self {
+        self.options.count = Some(count);
+        self
+    }
+
+    /// Get the document with this ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.options.id = Some(id.into());
+        self
+    }
+
+    /// Get the documents with these IDs.
+    pub fn ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.ids = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Get documents of this type.
+    pub fn doc_type(mut self, doc_type: impl Into<String>) -> Self {
+        self.options.doc_type = Some(doc_type.into());
+        self
+    }
+
+    /// Get documents matching this query template (JSON).
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.options.query = Some(query.into());
+        self
+    }
+
+    /// Run the command, returning one JSON value per document.
+    pub fn run(&self) -> std::io::Result<Vec<Value>> {
+        let stdout = execute_output(self.to_args())?;
+        parse_documents(&stdout)
+    }
+}
+
+impl ToArgs for DocGet {
+    fn to_args(&self) -> Vec<String> {
+        let options = &self.options;
+        let mut args = vec!["doc".to_string(), "get".to_string(), self.spec.to_string()];
+        add_required(&mut args, "--impersonate", &options.impersonate);
+        add_required(&mut args, "--graph-type", options.graph_type.as_str());
+        add_required(&mut args, "--skip", options.skip.to_string());
+        match options.count {
+            Some(count) => add_required(&mut args, "--count", count.to_string()),
+            None => add_required(&mut args, "--count", "unlimited"),
+        }
+        add_flag(&mut args, "--minimized", options.minimized);
+        add_flag(&mut args, "--as-list", options.as_list);
+        add_flag(&mut args, "--unfold", options.unfold);
+        add_option(&mut args, "--id", &options.id);
+        if !options.ids.is_empty() {
+            add_required(&mut args, "--ids", format!("[{}]", options.ids.join(",")));
+        }
+        add_option(&mut args, "--type", &options.doc_type);
+        add_flag(&mut args, "--compress-ids", options.compress_ids);
+        add_option(&mut args, "--query", &options.query);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_get_args() {
+        let args = DocGet::new(DbSpec::new("admin", "mydb"))
+            .doc_type("Person")
+            .count(5)
+            .to_args();
+        assert_eq!(&args[..3], ["doc", "get", "admin/mydb/local/branch/main"]);
+        let count = args.iter().position(|a| a == "--count").unwrap();
+        assert_eq!(args[count + 1], "5");
+        let doc_type = args.iter().position(|a| a == "--type").unwrap();
+        assert_eq!(args[doc_type + 1], "Person");
+    }
+
+    #[test]
+    fn test_doc_insert_args() {
+        let args = DocInsert::new(DbSpec::new("admin", "mydb"))
+            .message("add alice")
+            .data("{}")
+            .to_args();
+        let message = args.iter().position(|a| a == "--message").unwrap();
+        assert_eq!(args[message + 1], "add alice");
+        assert!(args.ends_with(&["--data".to_string(), "{}".to_string()]));
+    }
+}
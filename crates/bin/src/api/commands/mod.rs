@@ -1,6 +1,22 @@
 //! Command implementations that convert typed options to CLI arguments.
+//!
+//! The submodules hold typed builders for the subcommands whose output is
+//! worth parsing; each builder produces its arguments through [`ToArgs`] and
+//! returns a result type from [`super::output`] when run.
 
-use crate::run_terminusdb;
+mod branch;
+mod db;
+mod doc;
+mod store;
+mod triples;
+
+pub use branch::{BranchCreate, BranchDelete};
+pub use db::{DbUpdate, Rollup};
+pub use doc::{DocDelete, DocGet, DocInsert, DocReplace};
+pub use store::StoreInit;
+pub use triples::{TriplesDump, TriplesLoad};
+
+use crate::{run_terminusdb, run_terminusdb_output};
 use std::process::ExitStatus;
 
 /// Convert options to CLI arguments.
@@ -34,3 +50,21 @@ pub(crate) fn execute(args: Vec<String>) -> std::io::Result<ExitStatus> {
     let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     run_terminusdb(&args_str)
 }
+
+/// Execute a command and return its stdout.
+///
+/// A non-zero exit becomes an error carrying the subcommand and its stderr.
+pub(crate) fn execute_output(args: Vec<String>) -> std::io::Result<String> {
+    let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let output = run_terminusdb_output(&args_str)?;
+    if !output.status.success() {
+        let command: Vec<&str> = args_str.iter().take(2).copied().collect();
+        return Err(std::io::Error::other(format!(
+            "terminusdb {} failed ({}): {}",
+            command.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
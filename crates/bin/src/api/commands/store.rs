@@ -0,0 +1,67 @@
+//! Builder for `store init`.
+
+use super::{add_flag, add_required, execute_output, ToArgs};
+use crate::api::options::StoreInitOptions;
+use crate::api::output::StoreInitialized;
+
+/// Typed builder for `store init`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::StoreInit;
+///
+/// StoreInit::new().key("my-admin-key").force(true).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StoreInit {
+    options: StoreInitOptions,
+}
+
+impl StoreInit {
+    /// Initialize the store with the default admin key (`root`).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: StoreInitOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the admin password.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.options.key = key.into();
+        self
+    }
+
+    /// Re-initialize even if a store already exists.
+    pub fn force(mut self, force: bool) -> Self {
+        self.options.force = force;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<StoreInitialized> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(StoreInitialized {
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for StoreInit {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["store".to_string(), "init".to_string()];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        add_required(&mut args, "--key", &self.options.key);
+        add_flag(&mut args, "--force", self.options.force);
+        args
+    }
+}
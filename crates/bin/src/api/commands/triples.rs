@@ -0,0 +1,146 @@
+//! Builders for `triples dump` and `triples load`.
+
+use super::{add_required, execute_output, ToArgs};
+use crate::api::options::{TriplesDumpOptions, TriplesLoadOptions};
+use crate::api::output::{TriplesDumped, TriplesLoaded};
+use crate::api::spec::GraphSpec;
+use crate::api::types::{Author, Message, RdfFormat};
+
+/// Typed builder for `triples dump`.
+///
+/// ```no_run
+/// use terminusdb_bin::api::{DbSpec, GraphType, TriplesDump};
+///
+/// let dump = TriplesDump::new(DbSpec::new("admin", "mydb").graph(GraphType::Instance)).run()?;
+/// std::fs::write("mydb.ttl", dump.content)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TriplesDump {
+    graph: GraphSpec,
+    options: TriplesDumpOptions,
+}
+
+impl TriplesDump {
+    /// Dump a graph with the default options.
+    pub fn new(graph: GraphSpec) -> Self {
+        Self {
+            graph,
+            options: TriplesDumpOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: TriplesDumpOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the RDF serialization format.
+    pub fn format(mut self, format: RdfFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<TriplesDumped> {
+        let content = execute_output(self.to_args())?;
+        Ok(TriplesDumped {
+            format: self.options.format,
+            content,
+        })
+    }
+}
+
+impl ToArgs for TriplesDump {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "triples".to_string(),
+            "dump".to_string(),
+            self.graph.to_string(),
+        ];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        add_required(&mut args, "--format", self.options.format.as_str());
+        args
+    }
+}
+
+/// Typed builder for `triples load`.
+#[derive(Debug, Clone)]
+pub struct TriplesLoad {
+    graph: GraphSpec,
+    file: String,
+    options: TriplesLoadOptions,
+}
+
+impl TriplesLoad {
+    /// Load the triples in `file` into a graph with the default options.
+    pub fn new(graph: GraphSpec, file: impl Into<String>) -> Self {
+        Self {
+            graph,
+            file: file.into(),
+            options: TriplesLoadOptions::default(),
+        }
+    }
+
+    /// Replace all options at once.
+    pub fn options(mut self, options: TriplesLoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Impersonate a particular user.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.options.impersonate = user.into();
+        self
+    }
+
+    /// Set the commit message.
+    pub fn message(mut self, message: impl Into<Message>) -> Self {
+        self.options.message = message.into();
+        self
+    }
+
+    /// Set the commit author.
+    pub fn author(mut self, author: impl Into<Author>) -> Self {
+        self.options.author = author.into();
+        self
+    }
+
+    /// Set the RDF serialization format of `file`.
+    pub fn format(mut self, format: RdfFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Run the command.
+    pub fn run(&self) -> std::io::Result<TriplesLoaded> {
+        let stdout = execute_output(self.to_args())?;
+        Ok(TriplesLoaded {
+            graph: self.graph.clone(),
+            message: stdout.trim().to_string(),
+        })
+    }
+}
+
+impl ToArgs for TriplesLoad {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "triples".to_string(),
+            "load".to_string(),
+            self.graph.to_string(),
+            self.file.clone(),
+        ];
+        add_required(&mut args, "--impersonate", &self.options.impersonate);
+        add_required(&mut args, "--message", self.options.message.as_ref());
+        add_required(&mut args, "--author", self.options.author.as_ref());
+        add_required(&mut args, "--format", self.options.format.as_str());
+        args
+    }
+}
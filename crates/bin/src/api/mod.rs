@@ -21,15 +21,40 @@
 //! client.db().create(spec, Default::default())?;
 //! # Ok::<(), std::io::Error>(())
 //! ```
+//!
+//! Subcommands whose output is worth parsing also have typed builders that
+//! capture stdout and return a result type instead of an exit status:
+//!
+//! ```no_run
+//! use terminusdb_bin::api::{BranchCreate, DbSpec, DocGet};
+//!
+//! let spec = DbSpec::new("admin", "mydb");
+//! BranchCreate::new(spec.clone().branch("dev")).run()?;
+//! let documents = DocGet::new(spec.branch("dev")).doc_type("Person").run()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Commands also accept the `DatabasePath` and `ResourcePath` types from
+//! `terminusdb-types` that the HTTP client uses, so a path validated for one
+//! can be passed to the other.
 
 mod client;
 mod commands;
 mod options;
+mod output;
 mod spec;
 mod types;
 
 // Re-export public API
 pub use client::TerminusDB;
+pub use commands::{
+    BranchCreate, BranchDelete, DbUpdate, DocDelete, DocGet, DocInsert, DocReplace, Rollup,
+    StoreInit, TriplesDump, TriplesLoad,
+};
 pub use options::*;
+pub use output::{
+    parse_documents, BranchCreated, BranchDeleted, DbUpdated, DocumentsDeleted, DocumentsWritten,
+    RolledUp, StoreInitialized, TriplesDumped, TriplesLoaded,
+};
 pub use spec::{BranchSpec, CommitSpec, DbSpec, GraphSpec, GraphType};
 pub use types::{Author, CommitType, Message, RdfFormat, RoleAction, ScopeType};
@@ -0,0 +1,147 @@
+//! Parsed output of the CLI subcommands run through the command builders.
+//!
+//! The binary reports results as human-readable text on stdout. These types
+//! keep the trimmed message alongside whatever structure can be recovered
+//! from it (document IDs, JSON documents, RDF text).
+
+use super::spec::{BranchSpec, DbSpec, GraphSpec};
+use super::types::RdfFormat;
+use serde_json::Value;
+use std::io;
+
+/// Result of `branch create`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCreated {
+    /// The branch that was created.
+    pub branch: BranchSpec,
+    /// The origin it was branched from, if any.
+    pub origin: Option<String>,
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Result of `branch delete`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDeleted {
+    /// The branch that was deleted.
+    pub branch: BranchSpec,
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Result of `doc insert` and `doc replace`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DocumentsWritten {
+    /// IDs of the written documents, in the order the binary reported them.
+    pub ids: Vec<String>,
+}
+
+impl DocumentsWritten {
+    /// Parse the ID listing printed after `Documents inserted:` / `Documents replaced:`.
+    ///
+    /// Each ID line may carry a `N: ` index prefix, which is stripped.
+    pub fn from_stdout(stdout: &str) -> Self {
+        let ids = stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.ends_with(':'))
+            .map(|line| match line.split_once(':') {
+                Some((index, id)) if index.chars().all(|c| c.is_ascii_digit()) => {
+                    id.trim().to_string()
+                }
+                _ => line.to_string(),
+            })
+            .collect();
+        Self { ids }
+    }
+}
+
+/// Result of `doc delete`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentsDeleted {
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Parse the output of `doc get`, which is either JSON lines or a single JSON
+/// list (`--as-list`), into one value per document.
+pub fn parse_documents(stdout: &str) -> io::Result<Vec<Value>> {
+    let mut documents = Vec::new();
+    for value in serde_json::Deserializer::from_str(stdout).into_iter::<Value>() {
+        match value.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))? {
+            Value::Array(items) => documents.extend(items),
+            document => documents.push(document),
+        }
+    }
+    Ok(documents)
+}
+
+/// Result of `triples dump`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriplesDumped {
+    /// Serialization format of `content`.
+    pub format: RdfFormat,
+    /// The dumped graph.
+    pub content: String,
+}
+
+/// Result of `triples load`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriplesLoaded {
+    /// The graph the triples were loaded into.
+    pub graph: GraphSpec,
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Result of `store init`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreInitialized {
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Result of `db update`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbUpdated {
+    /// The database that was updated.
+    pub db: DbSpec,
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+/// Result of `rollup`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolledUp {
+    /// The database or branch that was rolled up.
+    pub db: DbSpec,
+    /// Message printed by the binary.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_documents_written_strips_index() {
+        let stdout = "Documents inserted:\n 1: terminusdb:///data/Person/alice\n 2: Person/bob\n";
+        assert_eq!(
+            DocumentsWritten::from_stdout(stdout).ids,
+            vec!["terminusdb:///data/Person/alice", "Person/bob"]
+        );
+        assert!(DocumentsWritten::from_stdout("").ids.is_empty());
+    }
+
+    #[test]
+    fn test_parse_documents() {
+        let lines = "{\"@id\":\"Person/a\"}\n{\"@id\":\"Person/b\"}\n";
+        let list = "[{\"@id\":\"Person/a\"},{\"@id\":\"Person/b\"}]";
+        let expected = vec![json!({"@id": "Person/a"}), json!({"@id": "Person/b"})];
+        assert_eq!(parse_documents(lines).unwrap(), expected);
+        assert_eq!(parse_documents(list).unwrap(), expected);
+        assert!(parse_documents("").unwrap().is_empty());
+        assert!(parse_documents("not json").is_err());
+    }
+}
@@ -1,26 +1,27 @@
 //! Database and graph specification types with builder pattern.
 //!
 //! These types provide compile-time safe construction of TerminusDB path specifications.
+//!
+//! Paths can also be parsed, or converted from the `terminusdb-types` path types
+//! used by the HTTP client, so both validate paths the same way:
+//!
+//! ```
+//! use terminusdb_bin::api::DbSpec;
+//! use terminusdb_types::ResourcePath;
+//!
+//! let spec: DbSpec = "admin/mydb/local/branch/dev".parse().unwrap();
+//! assert_eq!(spec, DbSpec::new("admin", "mydb").branch("dev"));
+//!
+//! let path = ResourcePath::parse("admin/mydb/local/branch/dev").unwrap();
+//! assert_eq!(DbSpec::from(path), spec);
+//! ```
 
 use std::fmt;
+use std::str::FromStr;
+use terminusdb_types::{DatabasePath, PathError, ResourcePath, ResourceType};
 
 /// Graph type for operations that target specific graphs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum GraphType {
-    /// Instance data graph
-    Instance,
-    /// Schema graph
-    Schema,
-}
-
-impl GraphType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            GraphType::Instance => "instance",
-            GraphType::Schema => "schema",
-        }
-    }
-}
+pub use terminusdb_types::GraphType;
 
 /// Database specification following the pattern:
 /// `<organization>/<database>/<repository>/branch|commit/<name>`
@@ -44,6 +45,8 @@ enum Location {
     Commits,
     /// Repository metadata
     Meta,
+    /// Remote repository
+    Remote(String),
 }
 
 impl DbSpec {
@@ -117,6 +120,41 @@ impl DbSpec {
         self
     }
 
+    /// Parse a path such as `admin/mydb`, `admin/mydb/local/branch/dev` or
+    /// `_system`.
+    ///
+    /// Database paths are validated as [`DatabasePath`]s and longer paths as
+    /// [`ResourcePath`]s, so the same paths are accepted as by the HTTP client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminusdb_bin::api::DbSpec;
+    ///
+    /// let spec = DbSpec::parse("admin/mydb").unwrap();
+    /// assert_eq!(spec.to_string(), "admin/mydb/local/branch/main");
+    ///
+    /// assert!(DbSpec::parse("admin/mydb/local/tag/v1").is_err());
+    /// ```
+    pub fn parse(path: &str) -> Result<Self, PathError> {
+        if path == "_system" {
+            return Ok(Self::system());
+        }
+        if path.split('/').count() <= 2 {
+            return DatabasePath::parse(path).map(Self::from);
+        }
+        ResourcePath::parse(path).map(Self::from)
+    }
+
+    /// The `organization/database` part of this spec, `None` for the system
+    /// database.
+    pub fn database_path(&self) -> Option<DatabasePath> {
+        if self.organization == "_system" {
+            return None;
+        }
+        DatabasePath::parse(&format!("{}/{}", self.organization, self.database)).ok()
+    }
+
     /// Convert to a graph specification for a specific graph type.
     pub fn graph(self, graph_type: GraphType) -> GraphSpec {
         GraphSpec {
@@ -144,6 +182,50 @@ impl fmt::Display for DbSpec {
             Location::Commit(id) => write!(f, "/commit/{}", id),
             Location::Commits => write!(f, "/_commits"),
             Location::Meta => write!(f, "/_meta"),
+            Location::Remote(name) => write!(f, "/remote/{}", name),
+        }
+    }
+}
+
+impl FromStr for DbSpec {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The main branch of the database
+impl From<DatabasePath> for DbSpec {
+    fn from(path: DatabasePath) -> Self {
+        Self::new(path.organization(), path.database_name())
+    }
+}
+
+impl From<&DatabasePath> for DbSpec {
+    fn from(path: &DatabasePath) -> Self {
+        Self::new(path.organization(), path.database_name())
+    }
+}
+
+impl From<ResourcePath> for DbSpec {
+    fn from(path: ResourcePath) -> Self {
+        Self::from(&path)
+    }
+}
+
+impl From<&ResourcePath> for DbSpec {
+    fn from(path: &ResourcePath) -> Self {
+        let spec = Self::from(path.database_path()).repository(path.location().to_string());
+        match path.resource() {
+            ResourceType::Branch(name) => spec.branch(name),
+            ResourceType::Commit(id) => spec.commit(id),
+            ResourceType::Meta => spec.meta(),
+            ResourceType::Commits => spec.commits(),
+            ResourceType::Remote(name) => Self {
+                location: Location::Remote(name.clone()),
+                ..spec
+            },
         }
     }
 }
@@ -172,6 +254,18 @@ impl From<DbSpec> for BranchSpec {
     }
 }
 
+impl From<DatabasePath> for BranchSpec {
+    fn from(path: DatabasePath) -> Self {
+        Self::new(path.into())
+    }
+}
+
+impl From<ResourcePath> for BranchSpec {
+    fn from(path: ResourcePath) -> Self {
+        Self::new(path.into())
+    }
+}
+
 impl fmt::Display for BranchSpec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.db_spec)
@@ -202,6 +296,18 @@ impl From<DbSpec> for CommitSpec {
     }
 }
 
+impl From<DatabasePath> for CommitSpec {
+    fn from(path: DatabasePath) -> Self {
+        Self::new(path.into())
+    }
+}
+
+impl From<ResourcePath> for CommitSpec {
+    fn from(path: ResourcePath) -> Self {
+        Self::new(path.into())
+    }
+}
+
 impl fmt::Display for CommitSpec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.db_spec)
@@ -241,6 +347,30 @@ impl fmt::Display for GraphSpec {
     }
 }
 
+/// Fails for resource paths that do not name a graph.
+impl TryFrom<ResourcePath> for GraphSpec {
+    type Error = PathError;
+
+    fn try_from(path: ResourcePath) -> Result<Self, Self::Error> {
+        match path.graph() {
+            Some(graph_type) => Ok(DbSpec::from(&path).graph(graph_type)),
+            None => Err(PathError::InvalidResourcePath(format!(
+                "'{}' does not name an instance or schema graph",
+                path
+            ))),
+        }
+    }
+}
+
+/// Parses paths such as `admin/mydb/local/branch/main/instance`.
+impl FromStr for GraphSpec {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ResourcePath::parse(s)?.try_into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,6 +411,42 @@ mod tests {
         assert_eq!(spec.to_string(), "admin/mydb/local/_commits");
     }
 
+    #[test]
+    fn test_db_spec_parse() {
+        assert_eq!(DbSpec::parse("_system").unwrap(), DbSpec::system());
+        assert_eq!(
+            DbSpec::parse("admin/mydb").unwrap(),
+            DbSpec::new("admin", "mydb")
+        );
+        assert_eq!(
+            "admin/mydb/local/commit/abc123".parse::<DbSpec>().unwrap(),
+            DbSpec::new("admin", "mydb").commit("abc123")
+        );
+        for path in [
+            "admin/mydb/local/branch/release/1.0",
+            "admin/mydb/local/_meta",
+            "admin/mydb/local/_commits",
+            "admin/mydb/remote/remote/origin",
+        ] {
+            assert_eq!(DbSpec::parse(path).unwrap().to_string(), path);
+        }
+
+        assert!(DbSpec::parse("admin").is_err());
+        assert!(DbSpec::parse("admin/").is_err());
+        assert!(DbSpec::parse("admin/mydb/elsewhere/branch/main").is_err());
+    }
+
+    #[test]
+    fn test_db_spec_from_types() {
+        let path = DatabasePath::parse("admin/mydb").unwrap();
+        assert_eq!(DbSpec::from(&path), DbSpec::new("admin", "mydb"));
+        assert_eq!(DbSpec::from(&path).database_path(), Some(path));
+        assert_eq!(DbSpec::system().database_path(), None);
+
+        let branch = BranchSpec::from(ResourcePath::parse("admin/mydb/local/branch/dev").unwrap());
+        assert_eq!(branch.to_string(), "admin/mydb/local/branch/dev");
+    }
+
     #[test]
     fn test_graph_spec() {
         let spec = DbSpec::new("admin", "mydb").graph(GraphType::Instance);
@@ -291,4 +457,16 @@ mod tests {
             .graph(GraphType::Schema);
         assert_eq!(spec.to_string(), "admin/mydb/local/branch/dev/schema");
     }
+
+    #[test]
+    fn test_graph_spec_parse() {
+        let spec: GraphSpec = "admin/mydb/local/commit/abc123/schema".parse().unwrap();
+        assert_eq!(
+            spec,
+            DbSpec::new("admin", "mydb")
+                .commit("abc123")
+                .graph(GraphType::Schema)
+        );
+        assert!("admin/mydb/local/branch/main".parse::<GraphSpec>().is_err());
+    }
 }
@@ -0,0 +1,214 @@
+//! Docker backend for [`TerminusDBServer`](crate::TerminusDBServer).
+//!
+//! With the `docker` feature, setting
+//! [`ServerOptions::backend`](crate::ServerOptions::backend) to
+//! [`ServerBackend::Docker`](crate::ServerBackend::Docker) runs the official
+//! `terminusdb/terminusdb-server` image through the local Docker daemon instead
+//! of the embedded binary, for CI machines that cannot build or run it. The
+//! rest of the API — `client()`, `stop_graceful()`, `restart()`, drop
+//! behaviour — is unchanged.
+//!
+//! The image tag is [`ServerOptions::version`](crate::ServerOptions::version)
+//! (default `latest`) and is pulled on first use. In persistent mode the store
+//! directory is bind-mounted into the container; in memory mode the store lives
+//! in the container and disappears with it. Container output is not forwarded.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use bollard::container::{
+    Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
+    StopContainerOptions,
+};
+use bollard::image::CreateImageOptions;
+use bollard::models::{HostConfig, PortBinding};
+use bollard::Docker;
+use futures::StreamExt;
+
+use crate::server::ServerOptions;
+
+/// Image the Docker backend runs.
+pub const DOCKER_IMAGE: &str = "terminusdb/terminusdb-server";
+
+/// Port the server listens on inside the container.
+const CONTAINER_PORT: &str = "6363/tcp";
+
+/// Store directory inside the container.
+const CONTAINER_STORAGE: &str = "/app/terminusdb/storage";
+
+/// A running TerminusDB container.
+#[derive(Debug)]
+pub(crate) struct Container {
+    id: String,
+}
+
+impl Container {
+    /// Short form of the container ID, for log messages.
+    pub(crate) fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+
+    /// Stop the container, waiting up to `timeout` before Docker kills it,
+    /// and remove it.
+    pub(crate) async fn stop(self, timeout: Duration) -> anyhow::Result<()> {
+        let docker = Docker::connect_with_local_defaults()?;
+        docker
+            .stop_container(
+                &self.id,
+                Some(StopContainerOptions {
+                    t: timeout.as_secs() as i64,
+                }),
+            )
+            .await?;
+        remove(&docker, &self.id).await
+    }
+
+    /// Force-remove the container from synchronous code such as `Drop`.
+    ///
+    /// Runs on its own thread and runtime, so it works whether or not the
+    /// caller is inside a tokio runtime.
+    pub(crate) fn remove_blocking(self) {
+        let id = self.id;
+        let _ = std::thread::spawn(move || -> anyhow::Result<()> {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(async {
+                let docker = Docker::connect_with_local_defaults()?;
+                remove(&docker, &id).await
+            })
+        })
+        .join();
+    }
+}
+
+async fn remove(docker: &Docker, id: &str) -> anyhow::Result<()> {
+    docker
+        .remove_container(
+            id,
+            Some(RemoveContainerOptions {
+                force: true,
+                v: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Pull the image if needed and start a container publishing the server on
+/// `127.0.0.1:port`.
+pub(crate) async fn start_container(
+    opts: &ServerOptions,
+    port: u16,
+    password: &str,
+    workers: Option<u8>,
+    db_path: Option<&Path>,
+) -> anyhow::Result<Container> {
+    let docker = Docker::connect_with_local_defaults()?;
+    let tag = opts.version.as_deref().unwrap_or("latest");
+    let image = format!("{}:{}", DOCKER_IMAGE, tag);
+
+    if docker.inspect_image(&image).await.is_err() {
+        eprintln!("[terminusdb-bin] Pulling {}...", image);
+        let mut pull = docker.create_image(
+            Some(CreateImageOptions {
+                from_image: DOCKER_IMAGE,
+                tag,
+                ..Default::default()
+            }),
+            None,
+            None,
+        );
+        while let Some(progress) = pull.next().await {
+            progress?;
+        }
+    }
+
+    let host_config = HostConfig {
+        port_bindings: Some(HashMap::from([(
+            CONTAINER_PORT.to_string(),
+            Some(vec![PortBinding {
+                host_ip: Some("127.0.0.1".to_string()),
+                host_port: Some(port.to_string()),
+            }]),
+        )])),
+        binds: db_path.map(|path| vec![format!("{}:{}", path.display(), CONTAINER_STORAGE)]),
+        ..Default::default()
+    };
+    let config = Config {
+        image: Some(image.clone()),
+        env: Some(container_env(opts, password, workers)),
+        exposed_ports: Some(HashMap::from([(
+            CONTAINER_PORT.to_string(),
+            HashMap::new(),
+        )])),
+        host_config: Some(host_config),
+        ..Default::default()
+    };
+
+    let name = format!("terminusdb-bin-{}", uuid::Uuid::new_v4().simple());
+    let created = docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: name.as_str(),
+                platform: None,
+            }),
+            config,
+        )
+        .await?;
+    let container = Container { id: created.id };
+    if let Err(e) = docker
+        .start_container(&container.id, None::<StartContainerOptions<String>>)
+        .await
+    {
+        let _ = remove(&docker, &container.id).await;
+        return Err(e.into());
+    }
+
+    eprintln!(
+        "[terminusdb-bin] Started container {} ({}) on port {}",
+        container.short_id(),
+        image,
+        port
+    );
+    Ok(container)
+}
+
+/// `KEY=VALUE` environment for the container: the typed/generic server config
+/// first, then the admin password and worker count.
+fn container_env(opts: &ServerOptions, password: &str, workers: Option<u8>) -> Vec<String> {
+    // Reuse the config's own env mapping rather than duplicating it.
+    let mut cmd = Command::new("terminusdb");
+    opts.config.apply_env(&mut cmd);
+    cmd.env("TERMINUSDB_ADMIN_PASS", password);
+    if let Some(w) = workers {
+        cmd.env("TERMINUSDB_SERVER_WORKERS", w.to_string());
+    }
+    cmd.get_envs()
+        .filter_map(|(k, v)| Some(format!("{}={}", k.to_str()?, v?.to_str()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{LogLevel, ServerConfig};
+
+    #[test]
+    fn container_env_includes_config_and_password() {
+        let opts = ServerOptions {
+            config: ServerConfig {
+                log_level: Some(LogLevel::Debug),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let env = container_env(&opts, "secret", Some(2));
+        assert!(env.contains(&"TERMINUSDB_ADMIN_PASS=secret".to_string()));
+        assert!(env.contains(&"TERMINUSDB_SERVER_WORKERS=2".to_string()));
+        assert!(env.iter().any(|e| e.starts_with("TERMINUSDB_LOG_LEVEL=")));
+    }
+}
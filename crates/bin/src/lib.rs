@@ -4,6 +4,10 @@
 //! the compiled binary. The binary can be accessed through the `TERMINUSDB_BINARY`
 //! constant or executed via the wrapper functions.
 //!
+//! The embed is the default `embedded` feature. Without it (e.g. a Docker-only
+//! build) [`extract_binary`] fails and servers run external releases or the
+//! Docker image instead.
+//!
 //! ## Environment Variables
 //!
 //! - `TERMINUSDB_VERSION`: Git branch or tag to build (default: "v12.1-rc-paraplu.1")
@@ -55,26 +59,34 @@
 // Allow dead code since this is a library with CLI builder patterns where not all methods are always used
 #![allow(dead_code)]
 
-use std::fs;
-use std::io::Write;
-use std::path::{Path, PathBuf};
-use std::process::{Command, ExitStatus};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Output};
+#[cfg(feature = "embedded")]
+use std::{fs, io::Write};
 
 pub mod api;
+#[cfg(feature = "docker")]
+pub mod docker;
+pub mod pool;
 pub mod server;
+pub mod versions;
 
 // Re-export server API for convenience
+pub use pool::{PooledServer, ServerPool};
 pub use server::{
-    start_server, with_server, LogFormat, LogLevel, ServerConfig, ServerOptions, TerminusDBServer,
+    start_server, with_server, DropBehavior, LogFormat, LogLevel, ServerBackend, ServerConfig,
+    ServerOptions, StoreStats, TerminusDBServer,
 };
+pub use versions::{TerminusDBBinary, EMBEDDED_VERSION};
 
 /// The embedded TerminusDB binary.
 /// This is compiled during the build process and embedded into this crate.
+#[cfg(feature = "embedded")]
 pub static TERMINUSDB_BINARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/terminusdb"));
 
 /// The embedded librust.dylib (macOS only).
 /// On macOS, the dev build needs this library at runtime.
-#[cfg(target_os = "macos")]
+#[cfg(all(feature = "embedded", target_os = "macos"))]
 pub static LIBRUST_DYLIB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/librust.dylib"));
 
 /// The embedded, relocatable SWI-Prolog runtime home (Linux only).
@@ -83,7 +95,7 @@ pub static LIBRUST_DYLIB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/libr
 /// (boot file, library predicates, foreign `.so`s) at runtime. We ship it as a
 /// `.tar.zst` blob and extract it on first use so the server runs on a machine
 /// with no swipl installed.
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "embedded", target_os = "linux"))]
 pub static SWIPL_HOME_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/swipl-home.tar.gz"));
 
 /// Extract the embedded SWI-Prolog home (Linux) into the cache dir and return
@@ -91,7 +103,7 @@ pub static SWIPL_HOME_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sw
 ///
 /// The tree is extracted once; a stamp file keyed on the blob length avoids
 /// re-extracting on every call and refreshes it when the embedded blob changes.
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "embedded", target_os = "linux"))]
 pub fn extract_swipl_home() -> std::io::Result<PathBuf> {
     let cache_dir = std::env::temp_dir().join("terminusdb-bin-cache");
     let home_root = cache_dir.join("swipl-home");
@@ -117,6 +129,23 @@ pub fn extract_swipl_home() -> std::io::Result<PathBuf> {
     Ok(home_root)
 }
 
+/// Without the `embedded` feature there is no SWI-Prolog home to extract.
+#[cfg(all(not(feature = "embedded"), target_os = "linux"))]
+pub fn extract_swipl_home() -> std::io::Result<PathBuf> {
+    Err(not_embedded())
+}
+
+/// The error returned by the extract functions when the crate was built
+/// without the `embedded` feature.
+#[cfg(not(feature = "embedded"))]
+fn not_embedded() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "terminusdb-bin was built without the `embedded` feature; \
+         run a specific release (ServerOptions::version) or the Docker backend",
+    )
+}
+
 /// SWI-Prolog per-arch library subdir (PLARCH), e.g. `x86_64-linux`.
 #[cfg(target_os = "linux")]
 fn plarch() -> String {
@@ -129,23 +158,7 @@ fn plarch() -> String {
 /// (macOS handles relocation via the extracted librust.dylib).
 pub fn apply_runtime_env(cmd: &mut Command) -> std::io::Result<()> {
     #[cfg(target_os = "linux")]
-    {
-        let home_root = extract_swipl_home()?;
-        let swipl_home = home_root.join("lib").join("swipl");
-        cmd.env("SWI_HOME_DIR", &swipl_home);
-
-        let mut ld_dirs = vec![home_root.join("lib")];
-        let arch_lib = swipl_home.join("lib").join(plarch());
-        if arch_lib.exists() {
-            ld_dirs.push(arch_lib);
-        }
-        if let Some(existing) = std::env::var_os("LD_LIBRARY_PATH") {
-            ld_dirs.extend(std::env::split_paths(&existing));
-        }
-        if let Ok(joined) = std::env::join_paths(&ld_dirs) {
-            cmd.env("LD_LIBRARY_PATH", joined);
-        }
-    }
+    apply_swipl_home(cmd, &extract_swipl_home()?);
     #[cfg(not(target_os = "linux"))]
     {
         let _ = cmd;
@@ -153,6 +166,26 @@ pub fn apply_runtime_env(cmd: &mut Command) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Point `cmd` at the relocatable SWI-Prolog home extracted to `home_root`
+/// (the tree containing `bin/` and `lib/swipl/`).
+#[cfg(target_os = "linux")]
+fn apply_swipl_home(cmd: &mut Command, home_root: &std::path::Path) {
+    let swipl_home = home_root.join("lib").join("swipl");
+    cmd.env("SWI_HOME_DIR", &swipl_home);
+
+    let mut ld_dirs = vec![home_root.join("lib")];
+    let arch_lib = swipl_home.join("lib").join(plarch());
+    if arch_lib.exists() {
+        ld_dirs.push(arch_lib);
+    }
+    if let Some(existing) = std::env::var_os("LD_LIBRARY_PATH") {
+        ld_dirs.extend(std::env::split_paths(&existing));
+    }
+    if let Ok(joined) = std::env::join_paths(&ld_dirs) {
+        cmd.env("LD_LIBRARY_PATH", joined);
+    }
+}
+
 /// Extracts the embedded TerminusDB binary to a temporary location and returns the path.
 ///
 /// The binary is cached in a temporary directory to avoid repeated extractions.
@@ -168,6 +201,8 @@ pub fn apply_runtime_env(cmd: &mut Command) -> std::io::Result<()> {
 /// - The temporary directory cannot be created
 /// - The binary cannot be written to disk
 /// - (Unix only) Permissions cannot be set
+/// - The crate was built without the `embedded` feature
+#[cfg(feature = "embedded")]
 pub fn extract_binary() -> std::io::Result<PathBuf> {
     let cache_dir = std::env::temp_dir().join("terminusdb-bin-cache");
     fs::create_dir_all(&cache_dir)?;
@@ -209,8 +244,15 @@ pub fn extract_binary() -> std::io::Result<PathBuf> {
     Ok(binary_path)
 }
 
+/// Without the `embedded` feature there is no binary to extract.
+#[cfg(not(feature = "embedded"))]
+pub fn extract_binary() -> std::io::Result<PathBuf> {
+    Err(not_embedded())
+}
+
 /// Checks if the extracted binary is outdated compared to the embedded one.
-fn is_outdated(path: &Path) -> std::io::Result<bool> {
+#[cfg(feature = "embedded")]
+fn is_outdated(path: &std::path::Path) -> std::io::Result<bool> {
     let metadata = fs::metadata(path)?;
     let file_size = metadata.len() as usize;
 
@@ -255,6 +297,19 @@ pub fn run_terminusdb(args: &[&str]) -> std::io::Result<ExitStatus> {
     cmd.status()
 }
 
+/// Runs TerminusDB with the given arguments and captures its output.
+///
+/// Unlike `run_terminusdb`, standard output and error are collected instead of
+/// inherited, so callers can parse what the command printed.
+pub fn run_terminusdb_output(args: &[&str]) -> std::io::Result<Output> {
+    let binary_path = extract_binary()?;
+
+    let mut cmd = Command::new(binary_path);
+    cmd.args(args);
+    apply_runtime_env(&mut cmd)?;
+    cmd.output()
+}
+
 /// Runs TerminusDB with the given arguments and inherits all I/O streams.
 ///
 /// This is similar to `run_terminusdb` but provides more explicit control.
@@ -267,6 +322,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "embedded")]
     fn test_binary_embedded() {
         assert!(!TERMINUSDB_BINARY.is_empty(), "Binary should be embedded");
         assert!(
@@ -276,6 +332,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "embedded")]
     fn test_extract_binary() {
         let path = extract_binary().expect("Should extract binary successfully");
         assert!(path.exists(), "Extracted binary should exist");
@@ -0,0 +1,291 @@
+//! Pool of isolated in-memory test servers.
+//!
+//! [`TerminusDBServer::test_instance`] shares one server between every test in
+//! the process, so global state — the system database, users, organizations,
+//! prefixes — leaks from one test into the next. A [`ServerPool`] instead keeps
+//! several in-memory servers and leases each to a single test at a time. When
+//! a lease is dropped the server goes back to the pool, and it is restarted
+//! (which empties a memory server) before it is leased again.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use terminusdb_bin::ServerPool;
+//!
+//! #[tokio::test]
+//! async fn test_users() -> anyhow::Result<()> {
+//!     let server = ServerPool::global().await?.lease().await?;
+//!     let client = server.client().await?;
+//!     // Nothing another test did is visible here.
+//!     Ok(())
+//! }
+//! ```
+
+use std::ops::Deref;
+use std::sync::{Mutex, Once};
+use tokio::sync::{OnceCell, Semaphore, SemaphorePermit};
+
+use crate::server::{start_server, ServerOptions, TerminusDBServer};
+
+/// Environment variable overriding the size of [`ServerPool::global`].
+pub const POOL_SIZE_ENV: &str = "TERMINUSDB_TEST_POOL_SIZE";
+
+/// Size of [`ServerPool::global`] when [`POOL_SIZE_ENV`] is not set.
+const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Process-wide pool returned by [`ServerPool::global`].
+static GLOBAL_POOL: OnceCell<ServerPool> = OnceCell::const_new();
+
+/// PIDs of the global pool's servers, killed at process exit because the
+/// static pool is never dropped.
+static EXIT_PIDS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// A fixed-size set of in-memory servers, each leased to one test at a time.
+pub struct ServerPool {
+    options: ServerOptions,
+    size: usize,
+    /// Servers that have not been used since they were (re)started.
+    idle: Mutex<Vec<TerminusDBServer>>,
+    /// Returned servers that must be restarted before the next lease.
+    dirty: Mutex<Vec<TerminusDBServer>>,
+    /// One permit per server not currently leased.
+    permits: Semaphore,
+    /// Whether server PIDs are registered for killing at process exit.
+    kill_at_exit: bool,
+}
+
+impl ServerPool {
+    /// Start `size` in-memory test servers (quiet, test mode).
+    pub async fn new(size: usize) -> anyhow::Result<Self> {
+        Self::with_options(
+            size,
+            ServerOptions {
+                memory: true,
+                quiet: true,
+                test_mode: true,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Start `size` servers with the given options.
+    ///
+    /// The servers always run in memory on an OS-assigned port, whatever
+    /// `options` says about `memory`, `port` and `db_path`, so they cannot
+    /// share a store or collide on a port.
+    pub async fn with_options(size: usize, options: ServerOptions) -> anyhow::Result<Self> {
+        Self::start(size, options, false).await
+    }
+
+    /// Get or create the process-wide pool.
+    ///
+    /// Its size is read from `TERMINUSDB_TEST_POOL_SIZE` (default 4) when the
+    /// first test asks for it. The servers run until the process exits.
+    pub async fn global() -> anyhow::Result<&'static Self> {
+        GLOBAL_POOL
+            .get_or_try_init(|| async {
+                let size = match std::env::var(POOL_SIZE_ENV) {
+                    Ok(value) => value.parse().map_err(|_| {
+                        anyhow::anyhow!(
+                            "{} must be a positive integer, got {:?}",
+                            POOL_SIZE_ENV,
+                            value
+                        )
+                    })?,
+                    Err(_) => DEFAULT_POOL_SIZE,
+                };
+                let options = ServerOptions {
+                    memory: true,
+                    quiet: true,
+                    test_mode: true,
+                    ..Default::default()
+                };
+                Self::start(size, options, true).await
+            })
+            .await
+    }
+
+    async fn start(
+        size: usize,
+        mut options: ServerOptions,
+        kill_at_exit: bool,
+    ) -> anyhow::Result<Self> {
+        if size == 0 {
+            anyhow::bail!("A server pool needs at least one server");
+        }
+        options.memory = true;
+        options.port = None;
+        options.db_path = None;
+
+        if kill_at_exit {
+            register_exit_handler();
+        }
+        let mut servers = Vec::with_capacity(size);
+        for _ in 0..size {
+            let server = start_server(options.clone()).await?;
+            if kill_at_exit {
+                track_pid(server.pid(), None);
+            }
+            servers.push(server);
+        }
+        eprintln!("[terminusdb-bin] Server pool ready with {} servers", size);
+
+        Ok(Self {
+            options,
+            size,
+            idle: Mutex::new(servers),
+            dirty: Mutex::new(Vec::new()),
+            permits: Semaphore::new(size),
+            kill_at_exit,
+        })
+    }
+
+    /// Number of servers in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Lease a server for exclusive use, waiting until one is free.
+    ///
+    /// The server has not been used since it was last started. It returns to
+    /// the pool when the [`PooledServer`] is dropped.
+    pub async fn lease(&self) -> anyhow::Result<PooledServer<'_>> {
+        let permit = self.permits.acquire().await?;
+        let clean = self.idle.lock().unwrap().pop();
+        let server = match clean {
+            Some(server) => server,
+            None => {
+                let server = self
+                    .dirty
+                    .lock()
+                    .unwrap()
+                    .pop()
+                    .expect("a free permit implies a returned server");
+                match self.recycle(server).await {
+                    Ok(server) => server,
+                    Err(e) => {
+                        // The slot is gone for good; keep permits in step with servers.
+                        permit.forget();
+                        return Err(e);
+                    }
+                }
+            }
+        };
+        Ok(PooledServer {
+            server: Some(server),
+            pool: self,
+            _permit: permit,
+        })
+    }
+
+    /// Restart a returned server so it comes back empty, replacing it with a
+    /// fresh one if the restart fails.
+    async fn recycle(&self, mut server: TerminusDBServer) -> anyhow::Result<TerminusDBServer> {
+        let old_pid = server.pid();
+        let server = match server.restart().await {
+            Ok(()) => server,
+            Err(e) => {
+                eprintln!(
+                    "[terminusdb-bin] Failed to recycle pooled server on port {}: {}",
+                    server.port(),
+                    e
+                );
+                drop(server);
+                start_server(self.options.clone()).await?
+            }
+        };
+        if self.kill_at_exit {
+            track_pid(server.pid(), old_pid);
+        }
+        Ok(server)
+    }
+}
+
+/// A server leased from a [`ServerPool`]; dereferences to [`TerminusDBServer`].
+///
+/// Dropping it hands the server back to the pool for recycling.
+pub struct PooledServer<'a> {
+    server: Option<TerminusDBServer>,
+    pool: &'a ServerPool,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Deref for PooledServer<'_> {
+    type Target = TerminusDBServer;
+
+    fn deref(&self) -> &TerminusDBServer {
+        self.server.as_ref().expect("server is present until drop")
+    }
+}
+
+impl Drop for PooledServer<'_> {
+    fn drop(&mut self) {
+        if let Some(server) = self.server.take() {
+            // Returned before the permit is released, so a waiting lease
+            // always finds it.
+            self.pool.dirty.lock().unwrap().push(server);
+        }
+    }
+}
+
+/// Record `pid` for killing at exit, forgetting `replaced` if given.
+fn track_pid(pid: Option<u32>, replaced: Option<u32>) {
+    let mut pids = EXIT_PIDS.lock().unwrap();
+    if let Some(old) = replaced {
+        pids.retain(|&p| p != old);
+    }
+    pids.extend(pid);
+}
+
+/// Register, once, an atexit handler killing every tracked pool server.
+fn register_exit_handler() {
+    static REGISTER: Once = Once::new();
+
+    extern "C" fn cleanup() {
+        let pids = match EXIT_PIDS.lock() {
+            Ok(pids) => pids.clone(),
+            Err(_) => return,
+        };
+        for pid in pids {
+            eprintln!("[terminusdb-bin] atexit: Killing pooled server PID {}", pid);
+            #[cfg(unix)]
+            unsafe {
+                libc::kill(pid as i32, libc::SIGKILL);
+            }
+        }
+    }
+
+    REGISTER.call_once(|| unsafe {
+        libc::atexit(cleanup);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Leases are exclusive, and a recycled server has lost the previous
+    /// lease's databases.
+    #[tokio::test]
+    async fn test_pool_isolates_leases() -> anyhow::Result<()> {
+        let pool = ServerPool::new(2).await?;
+        assert_eq!(pool.size(), 2);
+
+        let first = pool.lease().await?;
+        let second = pool.lease().await?;
+        assert_ne!(first.port(), second.port());
+
+        first.client().await?.ensure_database("leased").await?;
+        let port = first.port();
+        drop(first);
+
+        let recycled = pool.lease().await?;
+        assert_eq!(recycled.port(), port);
+        let dbs = recycled.client().await?.list_databases_simple().await?;
+        assert!(!dbs
+            .iter()
+            .any(|db| db.path.as_deref().is_some_and(|p| p.ends_with("/leased"))));
+        Ok(())
+    }
+}
@@ -23,11 +23,14 @@
 //! ```
 
 use std::collections::BTreeMap;
-use std::net::TcpListener;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 use terminusdb_client::TerminusDBHttpClient;
+
+use crate::TerminusDBBinary;
 use tokio::sync::OnceCell;
 
 /// Store the PID of the shared test server so we can kill it on exit.
@@ -234,7 +237,7 @@ impl ServerConfig {
     /// Only `Some`/non-empty fields are set, so unset fields fall through to the
     /// server's own defaults. The generic [`env`](Self::env) map is applied last
     /// and therefore overrides any typed field it collides with.
-    fn apply_env(&self, cmd: &mut Command) {
+    pub(crate) fn apply_env(&self, cmd: &mut Command) {
         // Booleans are emitted as the atoms the server compares against.
         fn b(v: bool) -> &'static str {
             if v {
@@ -352,14 +355,19 @@ impl ServerConfig {
 pub struct ServerOptions {
     /// Run server in-memory (no persistence). Good for testing.
     pub memory: bool,
-    /// Admin password (defaults to "root" if not set).
+    /// Admin password (defaults to "root" if not set). Used for `--memory` in
+    /// memory mode and as the admin key when initializing a persistent store.
+    /// Clients created via [`TerminusDBServer::client`] log in with it.
     pub password: Option<String>,
     /// Suppress stdout/stderr output.
     pub quiet: bool,
-    /// Custom database path. If not set, a temp directory is created.
+    /// Custom database path for persistent mode. If not set, a temp directory
+    /// is created. Ignored in memory mode.
     pub db_path: Option<std::path::PathBuf>,
-    /// Port to listen on. If None, auto-allocates a unique port in memory mode,
-    /// or uses 6363 in persistent mode.
+    /// Port to listen on. `Some(0)` lets the OS assign a free port in either
+    /// mode. If None, auto-allocates a unique port in memory mode, or uses
+    /// 6363 in persistent mode. The effective port is available from
+    /// [`TerminusDBServer::port`] / [`TerminusDBServer::addr`] after startup.
     pub port: Option<u16>,
     /// Enable test mode optimizations (longer timeouts, fewer workers).
     /// When true, automatically sets sensible defaults for parallel test execution.
@@ -376,6 +384,45 @@ pub struct ServerOptions {
     /// settings (logging, auth, caching, paths, …). Applied per-process, so the
     /// server can be fully configured without setting real environment vars.
     pub config: ServerConfig,
+    /// TerminusDB release to run (e.g. `"v11.1.0"`). If None, runs the
+    /// embedded binary; other versions are resolved as described in
+    /// [`versions`](crate::versions).
+    pub version: Option<String>,
+    /// What dropping the [`TerminusDBServer`] handle does to the process.
+    pub on_drop: DropBehavior,
+    /// Where the server runs: the native binary (default) or, with the
+    /// `docker` feature, the official container image.
+    pub backend: ServerBackend,
+}
+
+/// Where a [`TerminusDBServer`] runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ServerBackend {
+    /// The embedded binary, or the release named by
+    /// [`ServerOptions::version`].
+    #[default]
+    Binary,
+    /// The official `terminusdb/terminusdb-server` image, tagged
+    /// [`ServerOptions::version`] (default `latest`), run through the local
+    /// Docker daemon. See [`docker`](crate::docker).
+    #[cfg(feature = "docker")]
+    Docker,
+}
+
+/// What happens to the server process when its [`TerminusDBServer`] handle is
+/// dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DropBehavior {
+    /// Kill the process (the default).
+    #[default]
+    Kill,
+    /// Leave the process running, e.g. for a long-lived dev environment.
+    ///
+    /// The server's stderr then goes to `server.log` in its store directory
+    /// (or `<tmp>/terminusdb-server-<port>.log` in memory mode) rather than a
+    /// pipe, so it keeps logging after this process exits.
+    LeaveRunning,
 }
 
 /// Default worker count for test mode (reduced to minimize resource contention).
@@ -384,14 +431,46 @@ const TEST_MODE_WORKERS: u8 = 2;
 /// Default request timeout for test mode (15 minutes).
 const TEST_MODE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
 
+/// Admin password used when `ServerOptions::password` is not set.
+const DEFAULT_PASSWORD: &str = "root";
+
+/// How long `restart()` waits for the old process to exit gracefully.
+const RESTART_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Storage statistics of a persistent server, from
+/// [`TerminusDBServer::store_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StoreStats {
+    /// User databases in the store (the system database is not counted).
+    pub databases: usize,
+    /// Layers on disk. Every commit adds layers until the database is
+    /// optimized or rolled up.
+    pub layers: usize,
+    /// Label files, each pointing a graph at its current layer.
+    pub labels: usize,
+    /// Total size of the store directory in bytes.
+    pub disk_usage: u64,
+}
+
 /// A running TerminusDB server instance.
 ///
 /// The server is automatically stopped when this handle is dropped.
 pub struct TerminusDBServer {
     child: Option<Child>,
     port: u16,
+    /// Admin password clients created via `client()` log in with.
+    password: String,
+    /// Store directory of a persistent server (None in memory mode).
+    db_path: Option<PathBuf>,
+    /// Release tag of the running binary.
+    version: String,
+    /// Options the server was started with, reused by `restart()`.
+    options: ServerOptions,
     /// Request timeout for clients created via `client()`.
     request_timeout: Option<Duration>,
+    /// The container running the server, with the Docker backend.
+    #[cfg(feature = "docker")]
+    container: Option<crate::docker::Container>,
 }
 
 /// Shared test server instance (per-process)
@@ -403,6 +482,330 @@ impl TerminusDBServer {
         self.port
     }
 
+    /// The address the server is bound to, as resolved at startup (so an
+    /// OS-assigned port is reported as the actual port).
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, self.port))
+    }
+
+    /// The base URL clients connect to, e.g. `http://localhost:6363`.
+    pub fn url(&self) -> url::Url {
+        server_url(self.port)
+    }
+
+    /// The admin password clients created via `client()` log in with.
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// The TerminusDB release this server runs.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The PID of the server process, or None once it has been stopped.
+    pub fn pid(&self) -> Option<u32> {
+        self.child.as_ref().map(Child::id)
+    }
+
+    /// The file the server writes its log to, with
+    /// [`DropBehavior::LeaveRunning`]. None when its stderr is piped to this
+    /// process instead, or when it runs in a container.
+    pub fn log_file(&self) -> Option<PathBuf> {
+        #[cfg(feature = "docker")]
+        if self.container.is_some() {
+            return None;
+        }
+        log_file_path(&self.options, self.db_path.as_deref(), self.port)
+    }
+
+    /// Whether the server currently answers API requests.
+    pub async fn is_healthy(&self) -> bool {
+        match create_test_client(self.port, &self.password, None).await {
+            Ok(client) => client.try_info().await.is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Poll [`is_healthy`](Self::is_healthy) until the server answers, or fail
+    /// after `timeout`.
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> anyhow::Result<()> {
+        let start = std::time::Instant::now();
+        while !self.is_healthy().await {
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Server on port {} not healthy within {:?}",
+                    self.port,
+                    timeout
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        Ok(())
+    }
+
+    /// Ask the server to shut down (SIGTERM) and wait up to `timeout` for it
+    /// to exit, killing it if it does not. A no-op if already stopped.
+    pub async fn stop_graceful(&mut self, timeout: Duration) -> anyhow::Result<()> {
+        #[cfg(feature = "docker")]
+        if let Some(container) = self.container.take() {
+            eprintln!(
+                "[terminusdb-bin] Stopping container {} on port {}",
+                container.short_id(),
+                self.port
+            );
+            return container.stop(timeout).await;
+        }
+        let Some(mut child) = self.child.take() else {
+            return Ok(());
+        };
+        eprintln!(
+            "[terminusdb-bin] Stopping server PID {} on port {}",
+            child.id(),
+            self.port
+        );
+        #[cfg(unix)]
+        unsafe {
+            libc::kill(child.id() as i32, libc::SIGTERM);
+        }
+        #[cfg(not(unix))]
+        child.kill()?;
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < timeout {
+            if let Some(status) = child.try_wait()? {
+                eprintln!("[terminusdb-bin] Server exited with status: {}", status);
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        eprintln!(
+            "[terminusdb-bin] Server did not stop within {:?}, killing it",
+            timeout
+        );
+        child.kill()?;
+        child.wait()?;
+        Ok(())
+    }
+
+    /// Stop the server gracefully and start it again on the same port with the
+    /// same options. A persistent store keeps its data; a memory server comes
+    /// back empty.
+    pub async fn restart(&mut self) -> anyhow::Result<()> {
+        self.stop_graceful(RESTART_STOP_TIMEOUT).await?;
+        self.respawn().await
+    }
+
+    /// Start a stopped server again on the same port with the same options.
+    async fn respawn(&mut self) -> anyhow::Result<()> {
+        let mut options = self.options.clone();
+        options.port = Some(self.port);
+        if let Some(ref path) = self.db_path {
+            options.db_path = Some(path.clone());
+        }
+        let mut restarted = start_server_attempt(&options).await?;
+        self.child = restarted.child.take();
+        #[cfg(feature = "docker")]
+        {
+            self.container = restarted.container.take();
+        }
+        Ok(())
+    }
+
+    /// Back up the store to the directory `path` and verify the copy.
+    ///
+    /// The server is stopped gracefully while the store is copied, so no write
+    /// lands half-way, and started again on the same port afterwards. Besides
+    /// the store files the backup holds a `backup.manifest` with the size and
+    /// checksum of each file; the copy is checked against it before returning.
+    /// `path` must not exist yet or be empty. Needs a persistent server
+    /// (`memory: false`).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut server = start_server(ServerOptions { memory: false, ..Default::default() }).await?;
+    /// server.backup("/var/backups/terminusdb/2026-10-16").await?;
+    ///
+    /// // ... later, after something went wrong ...
+    /// server.restore_backup("/var/backups/terminusdb/2026-10-16").await?;
+    /// ```
+    pub async fn backup(&mut self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let target = path.as_ref();
+        let db_path = self.persistent_store("back up")?.to_path_buf();
+        if target.exists() && std::fs::read_dir(target)?.next().is_some() {
+            anyhow::bail!("Backup directory {:?} is not empty", target);
+        }
+
+        self.stop_graceful(RESTART_STOP_TIMEOUT).await?;
+        let copied = backup_store(&db_path, target);
+        self.respawn().await?;
+        copied?;
+
+        eprintln!("[terminusdb-bin] Backed up store to {:?}", target);
+        Ok(())
+    }
+
+    /// Replace the store with a backup taken by [`backup`](Self::backup).
+    ///
+    /// The backup is verified against its manifest before anything is touched,
+    /// so a truncated or corrupted backup is rejected with the store intact.
+    /// The server is stopped for the copy, the restored store is verified
+    /// again, and the server is restarted and polled until healthy.
+    pub async fn restore_backup(&mut self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let source = path.as_ref();
+        let db_path = self.persistent_store("restore")?.to_path_buf();
+        let manifest = read_manifest(source)?;
+        verify_manifest(source, &manifest)?;
+
+        self.stop_graceful(RESTART_STOP_TIMEOUT).await?;
+        let copied = restore_store(source, &db_path, &manifest);
+        self.respawn().await?;
+        copied?;
+        self.wait_until_healthy(RESTART_STOP_TIMEOUT).await?;
+
+        eprintln!("[terminusdb-bin] Restored store from {:?}", source);
+        Ok(())
+    }
+
+    /// Save the current contents of the store as snapshot `name`, replacing
+    /// any earlier snapshot of that name.
+    ///
+    /// Seed expensive fixture data once, snapshot it, and [`restore`](Self::restore)
+    /// it between tests instead of re-inserting everything. Snapshots copy the
+    /// store directory, so they need a persistent server (`memory: false`);
+    /// take them while no writes are in flight.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let server = start_server(ServerOptions { memory: false, port: Some(0), ..Default::default() }).await?;
+    /// seed(&server.client().await?).await?;
+    /// server.snapshot("seeded")?;
+    ///
+    /// // ... a test mutates the data ...
+    /// server.restore("seeded")?;
+    /// ```
+    pub fn snapshot(&self, name: &str) -> anyhow::Result<()> {
+        let db_path = self.persistent_store("snapshot")?;
+        let dir = self.snapshot_dir(name)?;
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        copy_tree(db_path, &dir)?;
+        eprintln!("[terminusdb-bin] Saved snapshot '{}' to {:?}", name, dir);
+        Ok(())
+    }
+
+    /// Reset the store to snapshot `name`, taken earlier with
+    /// [`snapshot`](Self::snapshot).
+    ///
+    /// The server keeps running: layer files are immutable, so only files that
+    /// changed since the snapshot are rewritten and anything created after it
+    /// (new databases, commits) is removed. Restore while no requests are in
+    /// flight.
+    pub fn restore(&self, name: &str) -> anyhow::Result<()> {
+        let db_path = self.persistent_store("restore")?;
+        let dir = self.snapshot_dir(name)?;
+        if !dir.is_dir() {
+            anyhow::bail!("No snapshot named '{}' at {:?}", name, dir);
+        }
+        sync_tree(&dir, db_path)?;
+        eprintln!("[terminusdb-bin] Restored snapshot '{}'", name);
+        Ok(())
+    }
+
+    /// Optimize the system database and every user database by running the
+    /// binary's `optimize` subcommand against the store, returning the paths
+    /// that were optimized.
+    ///
+    /// The server keeps running. Needs a persistent server (`memory: false`)
+    /// whose store the binary can open.
+    pub fn optimize_all(&self) -> anyhow::Result<Vec<String>> {
+        let mut paths = vec!["_system".to_string()];
+        paths.extend(self.list_database_paths()?);
+        for path in &paths {
+            self.run_store_cli(&["optimize", path])?;
+        }
+        eprintln!("[terminusdb-bin] Optimized {} databases", paths.len());
+        Ok(paths)
+    }
+
+    /// Storage statistics for the store: database count (from `db list`),
+    /// layer and label counts, and disk usage.
+    ///
+    /// Take stats before and after a workload to assert on storage growth, or
+    /// around [`optimize_all`](Self::optimize_all) to see what it reclaimed.
+    /// Needs a persistent server (`memory: false`).
+    pub fn store_stats(&self) -> anyhow::Result<StoreStats> {
+        let db_path = self.persistent_store("take stats of")?;
+        let mut stats = StoreStats {
+            databases: self.list_database_paths()?.len(),
+            ..Default::default()
+        };
+        tally_store(db_path, &mut stats)?;
+        Ok(stats)
+    }
+
+    /// Paths (`org/db`) of the user databases, from `db list --json`.
+    fn list_database_paths(&self) -> anyhow::Result<Vec<String>> {
+        let stdout = self.run_store_cli(&["db", "list", "--json"])?;
+        let listing: serde_json::Value = serde_json::from_str(stdout.trim())
+            .map_err(|e| anyhow::anyhow!("Unexpected `db list` output {:?}: {}", stdout, e))?;
+        Ok(listing
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|db| db.get("path").and_then(|path| path.as_str()))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Run a CLI subcommand of this server's binary against its store and
+    /// return stdout.
+    fn run_store_cli(&self, args: &[&str]) -> anyhow::Result<String> {
+        let db_path = self.persistent_store("inspect the store of")?;
+        let binary = resolve_binary(&self.options)?;
+        let mut cmd = binary.command()?;
+        cmd.args(args).current_dir(db_path);
+        self.options.config.apply_env(&mut cmd);
+        cmd.env("TERMINUSDB_SERVER_DB_PATH", db_path);
+        let output = cmd.output()?;
+        // Like `store init`, the binary may exit non-zero after succeeding,
+        // so only an error report on stderr counts as failure.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() && stderr.contains("Error") {
+            anyhow::bail!(
+                "terminusdb {} failed: status={:?}, stderr={:?}",
+                args.join(" "),
+                output.status,
+                stderr
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn persistent_store(&self, action: &str) -> anyhow::Result<&Path> {
+        self.db_path.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot {} an in-memory server; start it with `memory: false`",
+                action
+            )
+        })
+    }
+
+    /// Snapshots live next to the store, in `<db_path>.snapshots/<name>`.
+    fn snapshot_dir(&self, name: &str) -> anyhow::Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            anyhow::bail!("Invalid snapshot name: {:?}", name);
+        }
+        let db_path = self.persistent_store("snapshot")?;
+        let mut root = db_path.as_os_str().to_owned();
+        root.push(".snapshots");
+        Ok(PathBuf::from(root).join(name))
+    }
+
     /// Start a new test server with default test settings.
     ///
     /// Equivalent to `start_server(ServerOptions { memory: true, quiet: true, test_mode: true, .. })`.
@@ -434,6 +837,34 @@ impl TerminusDBServer {
         .await
     }
 
+    /// Start a new test server running TerminusDB release `version` instead
+    /// of the embedded binary, so client compatibility tests can cover several
+    /// releases in one suite. See [`versions`](crate::versions) for how the
+    /// binary is obtained.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use terminusdb_bin::TerminusDBServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let server = TerminusDBServer::with_version("v11.1.0").await?;
+    ///     assert_eq!(server.version(), "v11.1.0");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn with_version(version: &str) -> anyhow::Result<Self> {
+        start_server(ServerOptions {
+            memory: true,
+            quiet: true,
+            test_mode: true,
+            version: Some(version.to_string()),
+            ..Default::default()
+        })
+        .await
+    }
+
     /// Get or create a shared test server instance for this process.
     ///
     /// The server is started on the first call and kept running for the
@@ -475,14 +906,14 @@ impl TerminusDBServer {
 
     /// Get a configured HTTP client for this server.
     ///
-    /// Uses explicit "root" password instead of `local_node()` which reads from
-    /// environment variables. Memory mode servers always use "root" password.
+    /// Uses the server's admin password (see [`ServerOptions::password`])
+    /// instead of `local_node()` which reads from environment variables.
     /// Verifies the server is responding before returning.
     ///
     /// If the server was started with `test_mode: true`, the client will have
     /// a 15-minute request timeout to handle resource contention during parallel tests.
     pub async fn client(&self) -> anyhow::Result<TerminusDBHttpClient> {
-        let client = create_test_client(self.port, self.request_timeout).await?;
+        let client = create_test_client(self.port, &self.password, self.request_timeout).await?;
         // Verify server is responding
         client.info().await?;
         Ok(client)
@@ -630,6 +1061,34 @@ impl TerminusDBServer {
 
 impl Drop for TerminusDBServer {
     fn drop(&mut self) {
+        #[cfg(feature = "docker")]
+        if let Some(container) = self.container.take() {
+            if self.options.on_drop == DropBehavior::LeaveRunning {
+                eprintln!(
+                    "[terminusdb-bin] Drop: Leaving container {} running on port {}",
+                    container.short_id(),
+                    self.port
+                );
+            } else {
+                eprintln!(
+                    "[terminusdb-bin] Drop: Removing container {} on port {}",
+                    container.short_id(),
+                    self.port
+                );
+                container.remove_blocking();
+            }
+            return;
+        }
+        if self.options.on_drop == DropBehavior::LeaveRunning {
+            if let Some(ref child) = self.child {
+                eprintln!(
+                    "[terminusdb-bin] Drop: Leaving server PID {} running on port {}",
+                    child.id(),
+                    self.port
+                );
+            }
+            return;
+        }
         if let Some(ref mut child) = self.child {
             eprintln!(
                 "[terminusdb-bin] Drop: Killing server PID {} on port {}",
@@ -709,11 +1168,10 @@ pub async fn start_server(opts: ServerOptions) -> anyhow::Result<TerminusDBServe
 
 /// Internal: Single attempt to start the server.
 async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDBServer> {
-    let binary_path = crate::extract_binary()?;
-    eprintln!("[terminusdb-bin] Binary path: {:?}", binary_path);
-
-    // Determine port: auto-allocate for memory mode, use 6363 for persistent mode
+    // Determine port: explicit, OS-assigned for 0, auto-allocate for memory
+    // mode, 6363 for persistent mode
     let port = match opts.port {
+        Some(0) => find_available_port()?,
         Some(p) => p,
         None if opts.memory => find_available_port()?,
         None => 6363,
@@ -730,6 +1188,8 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
         eprintln!("[terminusdb-bin] Using {} workers", w);
     }
 
+    let password = opts.password.as_deref().unwrap_or(DEFAULT_PASSWORD);
+
     // Determine request timeout for clients
     let request_timeout = opts.request_timeout.or(if opts.test_mode {
         Some(TEST_MODE_TIMEOUT)
@@ -743,6 +1203,18 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
         );
     }
 
+    #[cfg(feature = "docker")]
+    if opts.backend == ServerBackend::Docker {
+        return start_container_attempt(opts, port, workers, password, request_timeout).await;
+    }
+
+    let binary = resolve_binary(opts)?;
+    eprintln!(
+        "[terminusdb-bin] Binary path: {:?} ({})",
+        binary.path(),
+        binary.version_name()
+    );
+
     // Only set up db_path for persistent (non-memory) mode
     // Memory mode should NOT have TERMINUSDB_SERVER_DB_PATH set, as it would
     // override --memory and cause the server to try opening a disk store
@@ -761,13 +1233,14 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
         };
 
         eprintln!("[terminusdb-bin] Initializing store in {:?}...", path);
-        let mut init_cmd = Command::new(&binary_path);
-        init_cmd.args(["store", "init"]).current_dir(&path);
+        let mut init_cmd = binary.command()?;
+        init_cmd
+            .args(["store", "init", "--key", password])
+            .current_dir(&path);
         // Apply typed/generic config first; the db path is set last so it stays
         // authoritative even if a caller put a value in `config.env`.
         opts.config.apply_env(&mut init_cmd);
         init_cmd.env("TERMINUSDB_SERVER_DB_PATH", &path);
-        crate::apply_runtime_env(&mut init_cmd)?;
         let init_output = init_cmd.output()?;
         // The terminusdb binary always exits non-zero due to a SWI-Prolog
         // `unwind(halt(N))` quirk, even when `store init` succeeds. Trust
@@ -795,7 +1268,6 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
 
     let mut args = vec!["serve".to_string()];
     if opts.memory {
-        let password = opts.password.as_deref().unwrap_or("root");
         args.push("--memory".to_string());
         args.push(password.to_string());
     }
@@ -810,9 +1282,17 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
 
     eprintln!("[terminusdb-bin] Spawning server with args: {:?}", args);
 
-    // Build command
-    let mut cmd = Command::new(&binary_path);
-    cmd.args(&args).stdout(stdout).stderr(Stdio::piped());
+    // A server outliving this process must not write to a pipe nobody reads,
+    // so it logs to a file instead (at the cost of early stderr diagnostics).
+    let log_file = log_file_path(opts, db_path.as_deref(), port);
+    let stderr = match log_file {
+        Some(ref path) => Stdio::from(std::fs::File::create(path)?),
+        None => Stdio::piped(),
+    };
+
+    // Build command (with the binary's SWI-Prolog runtime env already applied)
+    let mut cmd = binary.command()?;
+    cmd.args(&args).stdout(stdout).stderr(stderr);
 
     // Apply the caller's typed/generic server config first...
     opts.config.apply_env(&mut cmd);
@@ -828,9 +1308,6 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
         cmd.env("TERMINUSDB_SERVER_DB_PATH", path);
     }
 
-    // Point the binary at its bundled SWI-Prolog runtime (Linux self-contained embed).
-    crate::apply_runtime_env(&mut cmd)?;
-
     let mut child = cmd.spawn()?;
 
     eprintln!(
@@ -840,13 +1317,88 @@ async fn start_server_attempt(opts: &ServerOptions) -> anyhow::Result<TerminusDB
     );
 
     // Wait for server to be ready, checking for early process exit
-    wait_for_ready(&mut child, port, Duration::from_secs(30)).await?;
+    let ready = wait_for_ready(&mut child, port, password, Duration::from_secs(30)).await;
+    if let (Err(e), Some(path)) = (&ready, &log_file) {
+        anyhow::bail!("{} (server log: {})", e, path.display());
+    }
+    ready?;
 
     let server = TerminusDBServer {
         child: Some(child),
         port,
+        password: password.to_string(),
+        db_path,
+        version: binary.version_name().to_string(),
+        options: opts.clone(),
+        request_timeout,
+        #[cfg(feature = "docker")]
+        container: None,
+    };
+    eprintln!("[terminusdb-bin] Server listening on {}", server.addr());
+
+    Ok(server)
+}
+
+/// The file a native server logs to: only one left running on drop writes
+/// its stderr to a file, in the store directory or (in memory mode) the temp
+/// directory.
+fn log_file_path(opts: &ServerOptions, db_path: Option<&Path>, port: u16) -> Option<PathBuf> {
+    match opts.on_drop {
+        DropBehavior::Kill => None,
+        DropBehavior::LeaveRunning => Some(match db_path {
+            Some(path) => path.join("server.log"),
+            None => std::env::temp_dir().join(format!("terminusdb-server-{}.log", port)),
+        }),
+    }
+}
+
+/// The binary `opts` runs: the release named by `version`, or the embedded one.
+fn resolve_binary(opts: &ServerOptions) -> std::io::Result<TerminusDBBinary> {
+    match opts.version {
+        Some(ref version) => TerminusDBBinary::version(version),
+        None => TerminusDBBinary::embedded(),
+    }
+}
+
+/// How long a freshly started container may take to answer requests.
+#[cfg(feature = "docker")]
+const CONTAINER_READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Internal: Single attempt to start the server in a Docker container.
+#[cfg(feature = "docker")]
+async fn start_container_attempt(
+    opts: &ServerOptions,
+    port: u16,
+    workers: Option<u8>,
+    password: &str,
+    request_timeout: Option<Duration>,
+) -> anyhow::Result<TerminusDBServer> {
+    // The container initializes its own store, so persistent mode only needs
+    // the directory to mount.
+    let db_path = if opts.memory {
+        None
+    } else {
+        let path = opts.db_path.clone().unwrap_or_else(|| {
+            std::env::temp_dir().join(format!("terminusdb-server-{}", std::process::id()))
+        });
+        std::fs::create_dir_all(&path)?;
+        Some(path)
+    };
+
+    let container =
+        crate::docker::start_container(opts, port, password, workers, db_path.as_deref()).await?;
+    let server = TerminusDBServer {
+        child: None,
+        port,
+        password: password.to_string(),
+        db_path,
+        version: opts.version.clone().unwrap_or_else(|| "latest".to_string()),
+        options: opts.clone(),
         request_timeout,
+        container: Some(container),
     };
+    server.wait_until_healthy(CONTAINER_READY_TIMEOUT).await?;
+    eprintln!("[terminusdb-bin] Server listening on {}", server.addr());
 
     Ok(server)
 }
@@ -886,7 +1438,7 @@ async fn start_test_server() -> anyhow::Result<TerminusDBServer> {
     );
 
     // --memory mode self-initializes, no store init needed
-    let args = vec!["serve", "--memory", "root"];
+    let args = vec!["serve", "--memory", DEFAULT_PASSWORD];
 
     eprintln!(
         "[terminusdb-bin] test_instance: Spawning with args: {:?}",
@@ -915,31 +1467,242 @@ async fn start_test_server() -> anyhow::Result<TerminusDBServer> {
     TEST_SERVER_PID.store(pid, Ordering::SeqCst);
 
     // Wait for server to be ready, checking for early process exit
-    wait_for_ready(&mut child, port, Duration::from_secs(30)).await?;
+    wait_for_ready(&mut child, port, DEFAULT_PASSWORD, Duration::from_secs(30)).await?;
 
     Ok(TerminusDBServer {
         child: Some(child),
         port,
+        password: DEFAULT_PASSWORD.to_string(),
+        db_path: None,
+        version: crate::EMBEDDED_VERSION.to_string(),
+        options: ServerOptions {
+            memory: true,
+            quiet: true,
+            test_mode: true,
+            port: Some(port),
+            ..Default::default()
+        },
         request_timeout: Some(request_timeout),
+        #[cfg(feature = "docker")]
+        container: None,
     })
 }
 
-/// Create a client for the local test server with hardcoded "root" password.
-/// This is used instead of `local_node()` which reads from environment variables,
-/// since memory mode servers always use "root" password.
+/// Recursively copy the directory `src` to `dst`.
+fn copy_tree(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_tree(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Make the directory `dst` identical to `src`, removing whatever `src` lacks.
+///
+/// Layer files are content-addressed and never modified, so a file already
+/// present with the same size is left alone. Label files (`*.label`) point at
+/// the current layer of a database and are always rewritten.
+fn sync_tree(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(dst)? {
+        let entry = entry?;
+        if !src.join(entry.file_name()).exists() {
+            if entry.file_type()?.is_dir() {
+                std::fs::remove_dir_all(entry.path())?;
+            } else {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+    }
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            sync_tree(&entry.path(), &target)?;
+            continue;
+        }
+        let len = entry.metadata()?.len();
+        let unchanged = entry.path().extension().is_none_or(|ext| ext != "label")
+            && std::fs::metadata(&target).is_ok_and(|md| md.len() == len);
+        if !unchanged {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Add the layers, labels and bytes under `dir` to `stats`.
+///
+/// A layer is either a `.larch` archive or a directory named by its 40-digit
+/// hex ID; labels are `.label` files.
+fn tally_store(dir: &Path, stats: &mut StoreStats) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.len() == 40 && name.chars().all(|c| c.is_ascii_hexdigit()) {
+                stats.layers += 1;
+            }
+            tally_store(&path, stats)?;
+            continue;
+        }
+        stats.disk_usage += entry.metadata()?.len();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("larch") => stats.layers += 1,
+            Some("label") => stats.labels += 1,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Per-file `(size, checksum)` of a store tree, keyed by `/`-separated
+/// relative path.
+type Manifest = BTreeMap<String, (u64, u64)>;
+
+/// File written into every backup directory by [`TerminusDBServer::backup`].
+const BACKUP_MANIFEST: &str = "backup.manifest";
+
+/// Build the manifest of every file under `root`, except a top-level
+/// [`BACKUP_MANIFEST`].
+fn tree_manifest(root: &Path) -> std::io::Result<Manifest> {
+    fn walk(dir: &Path, prefix: &str, manifest: &mut Manifest) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            if entry.file_type()?.is_dir() {
+                walk(&entry.path(), &relative, manifest)?;
+            } else if relative != BACKUP_MANIFEST {
+                let len = entry.metadata()?.len();
+                manifest.insert(relative, (len, file_checksum(&entry.path())?));
+            }
+        }
+        Ok(())
+    }
+
+    let mut manifest = Manifest::new();
+    walk(root, "", &mut manifest)?;
+    Ok(manifest)
+}
+
+/// 64-bit FNV-1a hash of a file's contents.
+fn file_checksum(path: &Path) -> std::io::Result<u64> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut buf = [0u8; 64 * 1024];
+    let mut hash: u64 = 0xcbf29ce484222325;
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            return Ok(hash);
+        }
+        for &byte in &buf[..n] {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// Write `manifest` to `dir`, one `<checksum> <size> <path>` line per file.
+fn write_manifest(dir: &Path, manifest: &Manifest) -> std::io::Result<()> {
+    let contents: String = manifest
+        .iter()
+        .map(|(path, (len, sum))| format!("{:016x} {} {}\n", sum, len, path))
+        .collect();
+    std::fs::write(dir.join(BACKUP_MANIFEST), contents)
+}
+
+/// Read the manifest written by [`write_manifest`].
+fn read_manifest(dir: &Path) -> anyhow::Result<Manifest> {
+    let file = dir.join(BACKUP_MANIFEST);
+    let contents = std::fs::read_to_string(&file)
+        .map_err(|e| anyhow::anyhow!("Not a backup directory: cannot read {:?}: {}", file, e))?;
+    contents
+        .lines()
+        .map(|line| {
+            parse_manifest_line(line)
+                .ok_or_else(|| anyhow::anyhow!("Malformed line in {:?}: {:?}", file, line))
+        })
+        .collect()
+}
+
+fn parse_manifest_line(line: &str) -> Option<(String, (u64, u64))> {
+    let mut parts = line.splitn(3, ' ');
+    let sum = u64::from_str_radix(parts.next()?, 16).ok()?;
+    let len = parts.next()?.parse().ok()?;
+    Some((parts.next()?.to_string(), (len, sum)))
+}
+
+/// Copy the (stopped) store at `db_path` to `target` with a manifest, and
+/// verify the copy against it.
+fn backup_store(db_path: &Path, target: &Path) -> anyhow::Result<()> {
+    let manifest = tree_manifest(db_path)?;
+    copy_tree(db_path, target)?;
+    write_manifest(target, &manifest)?;
+    verify_manifest(target, &manifest)
+}
+
+/// Make the (stopped) store at `db_path` identical to the backup at `source`
+/// and verify the result against the backup's manifest.
+fn restore_store(source: &Path, db_path: &Path, manifest: &Manifest) -> anyhow::Result<()> {
+    sync_tree(source, db_path)?;
+    std::fs::remove_file(db_path.join(BACKUP_MANIFEST))?;
+    verify_manifest(db_path, manifest)
+}
+
+/// Check that the files under `root` match `manifest` exactly.
+fn verify_manifest(root: &Path, manifest: &Manifest) -> anyhow::Result<()> {
+    let actual = tree_manifest(root)?;
+    if let Some(path) = manifest
+        .keys()
+        .chain(actual.keys())
+        .find(|path| manifest.get(*path) != actual.get(*path))
+    {
+        anyhow::bail!(
+            "Integrity check failed for {:?}: {} is missing or differs from the manifest",
+            root,
+            path
+        );
+    }
+    Ok(())
+}
+
+/// Base URL of a local server on `port`.
+fn server_url(port: u16) -> url::Url {
+    url::Url::parse(&format!("http://localhost:{}", port)).unwrap()
+}
+
+/// Create an admin client for the local test server with the password it was
+/// started with. This is used instead of `local_node()` which reads from
+/// environment variables.
 ///
 /// # Arguments
 /// * `port` - The port the server is listening on
+/// * `password` - The admin password
 /// * `request_timeout` - Optional request timeout. If None, uses the default (60 seconds).
 async fn create_test_client(
     port: u16,
+    password: &str,
     request_timeout: Option<Duration>,
 ) -> anyhow::Result<TerminusDBHttpClient> {
-    let url = format!("http://localhost:{}", port);
     TerminusDBHttpClient::new_with_timeout(
-        url::Url::parse(&url).unwrap(),
+        server_url(port),
         "admin",
-        "root",
+        password,
         "admin",
         request_timeout,
     )
@@ -948,7 +1711,12 @@ async fn create_test_client(
 
 /// Wait for the server to respond using TerminusDBHttpClient.
 /// Also checks if the process has exited early or logged fatal errors.
-async fn wait_for_ready(child: &mut Child, port: u16, max_wait: Duration) -> anyhow::Result<()> {
+async fn wait_for_ready(
+    child: &mut Child,
+    port: u16,
+    password: &str,
+    max_wait: Duration,
+) -> anyhow::Result<()> {
     use std::io::Read;
 
     let start = std::time::Instant::now();
@@ -1014,9 +1782,8 @@ async fn wait_for_ready(child: &mut Child, port: u16, max_wait: Duration) -> any
             }
         }
 
-        // Use create_test_client() for health check (hardcoded "root" password)
         // Use default timeout for health checks - we want them to be fast
-        let client = match create_test_client(port, None).await {
+        let client = match create_test_client(port, password, None).await {
             Ok(c) => c,
             Err(_) => {
                 tokio::time::sleep(Duration::from_millis(100)).await;
@@ -1190,6 +1957,194 @@ mod tests {
         Ok(())
     }
 
+    /// An OS-assigned port (`Some(0)`) and a custom admin password are
+    /// reported back by the server handle and used by its clients.
+    #[tokio::test]
+    async fn test_server_reports_effective_address() -> anyhow::Result<()> {
+        let server = start_server(ServerOptions {
+            memory: true,
+            quiet: true,
+            test_mode: true,
+            port: Some(0),
+            password: Some("secret".into()),
+            ..Default::default()
+        })
+        .await?;
+
+        assert_ne!(server.port(), 0);
+        assert_eq!(server.addr().port(), server.port());
+        assert_eq!(server.url().port(), Some(server.port()));
+        assert_eq!(server.password(), "secret");
+        assert_eq!(server.version(), crate::EMBEDDED_VERSION);
+
+        let client = server.client().await?;
+        client.info().await?;
+        Ok(())
+    }
+
+    /// Restoring a snapshot drops databases created after it and keeps the
+    /// ones it captured; memory servers refuse to snapshot.
+    #[tokio::test]
+    async fn test_snapshot_and_restore() -> anyhow::Result<()> {
+        let db_path = std::env::temp_dir().join(format!(
+            "terminusdb-snapshot-test-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let server = start_server(ServerOptions {
+            quiet: true,
+            test_mode: true,
+            port: Some(0),
+            db_path: Some(db_path.clone()),
+            ..Default::default()
+        })
+        .await?;
+        let client = server.client().await?;
+
+        client.ensure_database("seeded").await?;
+        server.snapshot("seeded")?;
+
+        client.ensure_database("scratch").await?;
+        server.restore("seeded")?;
+
+        let names: Vec<String> = client
+            .list_databases_simple()
+            .await?
+            .into_iter()
+            .filter_map(|db| db.path)
+            .collect();
+        assert!(names.iter().any(|p| p.ends_with("/seeded")));
+        assert!(!names.iter().any(|p| p.ends_with("/scratch")));
+        assert!(server.restore("missing").is_err());
+
+        drop(server);
+        let snapshots = PathBuf::from(format!("{}.snapshots", db_path.display()));
+        let _ = std::fs::remove_dir_all(&db_path);
+        let _ = std::fs::remove_dir_all(&snapshots);
+
+        let memory = TerminusDBServer::test().await?;
+        assert!(memory.snapshot("seeded").is_err());
+        Ok(())
+    }
+
+    /// A backup restores the databases it captured, and a tampered backup is
+    /// rejected without touching the store.
+    #[tokio::test]
+    async fn test_backup_and_restore() -> anyhow::Result<()> {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let db_path = std::env::temp_dir().join(format!("terminusdb-backup-test-{}", id));
+        let backup_path = std::env::temp_dir().join(format!("terminusdb-backup-{}", id));
+        let mut server = start_server(ServerOptions {
+            quiet: true,
+            test_mode: true,
+            port: Some(0),
+            db_path: Some(db_path.clone()),
+            ..Default::default()
+        })
+        .await?;
+
+        server.client().await?.ensure_database("kept").await?;
+        server.backup(&backup_path).await?;
+        assert!(server.backup(&backup_path).await.is_err());
+
+        server.client().await?.ensure_database("later").await?;
+        server.restore_backup(&backup_path).await?;
+        let names: Vec<String> = server
+            .client()
+            .await?
+            .list_databases_simple()
+            .await?
+            .into_iter()
+            .filter_map(|db| db.path)
+            .collect();
+        assert!(names.iter().any(|p| p.ends_with("/kept")));
+        assert!(!names.iter().any(|p| p.ends_with("/later")));
+
+        let manifest = read_manifest(&backup_path)?;
+        let (victim, _) = manifest.iter().next().expect("backup has files");
+        std::fs::write(backup_path.join(victim), b"corrupted")?;
+        assert!(server.restore_backup(&backup_path).await.is_err());
+        assert!(server.is_healthy().await);
+
+        drop(server);
+        let _ = std::fs::remove_dir_all(&db_path);
+        let _ = std::fs::remove_dir_all(&backup_path);
+
+        let mut memory = TerminusDBServer::test().await?;
+        assert!(memory.backup(&backup_path).await.is_err());
+        Ok(())
+    }
+
+    /// Stats count the databases and grow with writes; optimizing covers the
+    /// system database and every user database.
+    #[tokio::test]
+    async fn test_store_stats_and_optimize() -> anyhow::Result<()> {
+        let db_path = std::env::temp_dir().join(format!(
+            "terminusdb-stats-test-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let server = start_server(ServerOptions {
+            quiet: true,
+            test_mode: true,
+            port: Some(0),
+            db_path: Some(db_path.clone()),
+            ..Default::default()
+        })
+        .await?;
+
+        let before = server.store_stats()?;
+        server.client().await?.ensure_database("stats").await?;
+        let after = server.store_stats()?;
+        assert_eq!(after.databases, before.databases + 1);
+        assert!(after.layers > before.layers);
+        assert!(after.disk_usage > before.disk_usage);
+
+        let optimized = server.optimize_all()?;
+        assert_eq!(optimized[0], "_system");
+        assert!(optimized.iter().any(|p| p.ends_with("/stats")));
+
+        drop(server);
+        let _ = std::fs::remove_dir_all(&db_path);
+
+        let memory = TerminusDBServer::test().await?;
+        assert!(memory.store_stats().is_err());
+        Ok(())
+    }
+
+    /// A persistent server keeps its data across a graceful restart, and stops
+    /// answering once stopped.
+    #[tokio::test]
+    async fn test_stop_and_restart() -> anyhow::Result<()> {
+        let db_path = std::env::temp_dir().join(format!(
+            "terminusdb-restart-test-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let mut server = start_server(ServerOptions {
+            quiet: true,
+            test_mode: true,
+            port: Some(0),
+            db_path: Some(db_path.clone()),
+            ..Default::default()
+        })
+        .await?;
+        server.client().await?.ensure_database("survivor").await?;
+
+        let pid = server.pid();
+        server.restart().await?;
+        assert_ne!(server.pid(), pid);
+        server.wait_until_healthy(Duration::from_secs(30)).await?;
+        let dbs = server.client().await?.list_databases_simple().await?;
+        assert!(dbs
+            .iter()
+            .any(|db| db.path.as_deref().is_some_and(|p| p.ends_with("/survivor"))));
+
+        server.stop_graceful(Duration::from_secs(10)).await?;
+        assert_eq!(server.pid(), None);
+        assert!(!server.is_healthy().await);
+
+        let _ = std::fs::remove_dir_all(&db_path);
+        Ok(())
+    }
+
     /// Test that memory mode via TerminusDBServer::test() doesn't write files to disk.
     #[tokio::test]
     async fn test_memory_mode_no_disk_writes() -> anyhow::Result<()> {
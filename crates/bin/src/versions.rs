@@ -0,0 +1,248 @@
+//! Running TerminusDB releases other than the embedded one.
+//!
+//! The crate embeds a single server binary ([`EMBEDDED_VERSION`]). Client
+//! compatibility tests often need to run the same suite against several
+//! releases, so [`TerminusDBBinary::version`] resolves any other release tag
+//! to a runnable binary, in order:
+//!
+//! 1. The embedded binary, when the tag is [`EMBEDDED_VERSION`]
+//! 2. A binary provisioned under `$TERMINUSDB_VERSIONS_DIR/<version>/`
+//! 3. The extraction cache (`<tmp>/terminusdb-bin-cache/versions/<version>/`)
+//! 4. The `terminusdb-<target>.tar.gz` asset of the GitHub release for the
+//!    tag, downloaded with `curl` and unpacked into the cache
+//!
+//! A version directory holds the same artifacts a release archive does:
+//! `terminusdb`, plus `swipl-home.tar.gz` (or an extracted `swipl-home/`) on
+//! Linux and `librust.dylib` on macOS.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use terminusdb_bin::TerminusDBServer;
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     for version in ["v11.1.0", terminusdb_bin::EMBEDDED_VERSION] {
+//!         let server = TerminusDBServer::with_version(version).await?;
+//!         let client = server.client().await?;
+//!         // ... run the compatibility checks against `client` ...
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The TerminusDB release tag the embedded binary was built from.
+pub const EMBEDDED_VERSION: &str = env!("TERMINUSDB_EMBEDDED_VERSION");
+
+/// Target triple the release assets are published under.
+const TARGET: &str = env!("TERMINUSDB_TARGET");
+
+/// Where release archives are downloaded from; `{version}` and `{target}` are
+/// substituted. Override with `TERMINUSDB_RELEASE_URL`.
+const RELEASE_URL: &str =
+    "https://github.com/ParapluOU/terminusdb/releases/download/{version}/terminusdb-{target}.tar.gz";
+
+/// A runnable TerminusDB server binary of a known version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminusDBBinary {
+    version: String,
+    path: PathBuf,
+    /// Extracted SWI-Prolog home of a non-embedded Linux release.
+    swipl_home: Option<PathBuf>,
+}
+
+impl TerminusDBBinary {
+    /// The embedded binary, extracted to the cache dir.
+    pub fn embedded() -> std::io::Result<Self> {
+        Ok(Self {
+            version: EMBEDDED_VERSION.to_string(),
+            path: crate::extract_binary()?,
+            swipl_home: None,
+        })
+    }
+
+    /// The binary of release `version` (e.g. `"v11.1.0"`); see the
+    /// [module documentation](self) for where it is looked up.
+    pub fn version(version: &str) -> std::io::Result<Self> {
+        // Without the `embedded` feature this tag is downloaded like any other.
+        if cfg!(feature = "embedded") && version == EMBEDDED_VERSION {
+            return Self::embedded();
+        }
+        if version.is_empty()
+            || !version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid TerminusDB version: {:?}", version),
+            ));
+        }
+
+        let provisioned = std::env::var_os("TERMINUSDB_VERSIONS_DIR")
+            .map(|dir| PathBuf::from(dir).join(version))
+            .filter(|dir| dir.join("terminusdb").exists());
+        let dir = match provisioned {
+            Some(dir) => dir,
+            None => {
+                let dir = std::env::temp_dir()
+                    .join("terminusdb-bin-cache")
+                    .join("versions")
+                    .join(version);
+                if !dir.join("terminusdb").exists() {
+                    download_release(version, &dir)?;
+                }
+                dir
+            }
+        };
+        Self::from_dir(version, &dir)
+    }
+
+    /// The release tag of this binary.
+    pub fn version_name(&self) -> &str {
+        &self.version
+    }
+
+    /// Path of the executable.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A command running this binary with the runtime environment it needs
+    /// (see [`apply_runtime_env`](crate::apply_runtime_env)).
+    pub fn command(&self) -> std::io::Result<Command> {
+        let mut cmd = Command::new(&self.path);
+        match self.swipl_home {
+            #[cfg(target_os = "linux")]
+            Some(ref home) => crate::apply_swipl_home(&mut cmd, home),
+            _ => crate::apply_runtime_env(&mut cmd)?,
+        }
+        Ok(cmd)
+    }
+
+    fn from_dir(version: &str, dir: &Path) -> std::io::Result<Self> {
+        let path = dir.join("terminusdb");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&path)?.permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&path, perms)?;
+        }
+
+        #[cfg(target_os = "linux")]
+        let swipl_home = extract_release_home(dir)?;
+        #[cfg(not(target_os = "linux"))]
+        let swipl_home = None;
+
+        // The macOS dev build loads librust.dylib from the absolute path it was
+        // built at, which all versions share (see `extract_binary`).
+        #[cfg(target_os = "macos")]
+        {
+            let dylib = dir.join("librust.dylib");
+            if dylib.exists() {
+                let src_rust_dir = std::env::temp_dir()
+                    .join("terminusdb-build")
+                    .join("src")
+                    .join("rust");
+                fs::create_dir_all(&src_rust_dir)?;
+                fs::copy(&dylib, src_rust_dir.join("librust.dylib"))?;
+            }
+        }
+
+        Ok(Self {
+            version: version.to_string(),
+            path,
+            swipl_home,
+        })
+    }
+}
+
+/// The SWI-Prolog home a Linux release ships as `swipl-home.tar.gz`, extracted
+/// once to `swipl-home/` next to it.
+#[cfg(target_os = "linux")]
+fn extract_release_home(dir: &Path) -> std::io::Result<Option<PathBuf>> {
+    let home = dir.join("swipl-home");
+    let blob = dir.join("swipl-home.tar.gz");
+    if !home.join("lib").join("swipl").exists() && blob.exists() {
+        let decoder = flate2::read::GzDecoder::new(fs::File::open(&blob)?);
+        let mut archive = tar::Archive::new(decoder);
+        archive.set_preserve_permissions(true);
+        archive.unpack(&home)?;
+    }
+    Ok(Some(home).filter(|home| home.join("lib").join("swipl").exists()))
+}
+
+/// Download and unpack the release archive of `version` into `dir`.
+fn download_release(version: &str, dir: &Path) -> std::io::Result<()> {
+    let url = std::env::var("TERMINUSDB_RELEASE_URL")
+        .unwrap_or_else(|_| RELEASE_URL.to_string())
+        .replace("{version}", version)
+        .replace("{target}", TARGET);
+    eprintln!(
+        "[terminusdb-bin] Downloading TerminusDB {} from {}",
+        version, url
+    );
+
+    // Unpack next to the final location and rename, so an interrupted
+    // download never leaves a half-populated version dir behind.
+    let parent = dir.parent().unwrap_or(dir);
+    fs::create_dir_all(parent)?;
+    let staging = parent.join(format!(".{}-{}", version, std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)?;
+
+    let archive = staging.join("release.tar.gz");
+    let status = Command::new("curl")
+        .args(["-fsSL", "--retry", "3", "-o"])
+        .arg(&archive)
+        .arg(&url)
+        .status()?;
+    if !status.success() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(std::io::Error::other(format!(
+            "failed to download TerminusDB {} from {}",
+            version, url
+        )));
+    }
+
+    let decoder = flate2::read::GzDecoder::new(fs::File::open(&archive)?);
+    tar::Archive::new(decoder).unpack(&staging)?;
+    fs::remove_file(&archive)?;
+    if !staging.join("terminusdb").exists() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(std::io::Error::other(format!(
+            "release archive {} has no terminusdb binary",
+            url
+        )));
+    }
+
+    // Another process may have finished the same download first.
+    if fs::rename(&staging, dir).is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "embedded")]
+    fn test_embedded_version_resolves_to_embedded_binary() {
+        let binary = TerminusDBBinary::version(EMBEDDED_VERSION).unwrap();
+        assert_eq!(binary.version_name(), EMBEDDED_VERSION);
+        assert_eq!(binary.path(), crate::extract_binary().unwrap());
+    }
+
+    #[test]
+    fn test_rejects_path_like_versions() {
+        assert!(TerminusDBBinary::version("../v11").is_err());
+        assert!(TerminusDBBinary::version("").is_err());
+    }
+}
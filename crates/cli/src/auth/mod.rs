@@ -53,6 +53,38 @@ pub fn get_active_credentials() -> Result<ResolvedCredentials> {
     get_profile_credentials(&profile_name)
 }
 
+/// Connection defaults a profile supplies to every command, keyed by the
+/// environment variable of the argument they fill in. Uses the active profile
+/// unless `profile_name` is given; a profile that does not exist (or whose
+/// password is not in the keyring) supplies nothing (or no password).
+pub fn profile_defaults(profile_name: Option<&str>) -> Result<Vec<(&'static str, String)>> {
+    let config = load_config()?;
+    let profile_name = profile_name.unwrap_or(&config.settings.active_profile);
+    let Some(profile) = config.get_profile(profile_name) else {
+        return Ok(Vec::new());
+    };
+
+    let mut defaults = vec![
+        ("TERMINUSDB_HOST", profile.host.clone()),
+        ("TERMINUSDB_USER", profile.user.clone()),
+        ("TERMINUSDB_ORG", profile.org.clone()),
+    ];
+    let service = profile.keyring_service();
+    let username = profile.keyring_username(profile_name);
+    match get_password(&service, &username) {
+        Ok(password) => defaults.push(("TERMINUSDB_PASS", password)),
+        Err(err) => tracing::debug!("no password for profile '{}': {:#}", profile_name, err),
+    }
+    if let Some(ref database) = profile.database {
+        defaults.push(("TERMINUSDB_DB", database.clone()));
+    }
+    if let Some(ref branch) = profile.branch {
+        defaults.push(("TERMINUSDB_BRANCH", branch.clone()));
+    }
+
+    Ok(defaults)
+}
+
 /// Save a profile with password in keyring
 pub fn save_profile_with_password(
     profile_name: &str,
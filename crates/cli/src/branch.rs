@@ -0,0 +1,323 @@
+//! Branch commands: create, list, delete, reset, switch and merge.
+//!
+//! Every command prints a short confirmation, or with `--output json|yaml`
+//! (or `--json`) the server response (the branch names for `list`) for
+//! scripts. `delete` and `reset` preview what they drop and ask first (see
+//! [`confirm`](crate::confirm)). `switch` records the branch as the default
+//! branch of a profile; `merge` rebases the target branch onto the source,
+//! which is how TerminusDB merges branches.
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use terminusdb_client::{BranchSpec, TerminusDBHttpClient};
+use terminusdb_types::{
+    BranchName, BranchRef, BranchSpec as Spec, CommitId, DatabaseName, DatabasePath, PathError,
+};
+use url::Url;
+
+use crate::confirm::{Confirm, Preview};
+use crate::output::Output;
+
+/// Resource path of a branch, e.g. `admin/mydb/local/branch/main`
+fn branch_path(org: &str, database: &str, branch: &str) -> Result<String> {
+    Ok(Spec::new(database_path(org, database)?, BranchName::new(branch)?).to_string())
+}
+
+/// Resource path for `--from`: a branch name (`dev`, `commit/<id>`), or a full
+/// spec such as `admin/mydb/local/commit/<id>`
+fn origin_path(org: &str, database: &str, from: &str) -> Result<String> {
+    absolute_spec(org, database, from, BranchRef::parse)
+}
+
+/// Resource path for `reset --to`: a commit id, or a full spec
+fn commit_path(org: &str, database: &str, commit: &str) -> Result<String> {
+    absolute_spec(org, database, commit, |commit| {
+        if commit.starts_with("branch/") || commit.starts_with("commit/") {
+            BranchRef::parse(commit)
+        } else {
+            CommitId::new(commit).map(BranchRef::Commit)
+        }
+    })
+}
+
+fn database_path(org: &str, database: &str) -> Result<DatabasePath> {
+    Ok(DatabasePath::new(org, DatabaseName::new(database)?)?)
+}
+
+/// Parse `value` with the shared spec grammar: a full spec when it names a
+/// repository (`.../local/...`), otherwise a ref in `org/database` read with
+/// `parse_ref`. Relative refs are rejected: the server cannot resolve them.
+fn absolute_spec(
+    org: &str,
+    database: &str,
+    value: &str,
+    parse_ref: impl Fn(&str) -> Result<BranchRef, PathError>,
+) -> Result<String> {
+    let spec = if value.contains("/local/") || value.contains("/remote/") {
+        Spec::parse(value)?
+    } else {
+        Spec::with_ref(database_path(org, database)?, parse_ref(value)?)
+    };
+    if spec.is_relative() {
+        anyhow::bail!("'{}' is a relative ref; pass a commit id instead", value);
+    }
+    Ok(spec.to_string())
+}
+
+/// What resetting a branch with `history` (newest first) to `commit` drops
+fn reset_effect(history: &[String], commit: &str) -> String {
+    match history.iter().position(|id| id == commit) {
+        Some(0) => "the branch is already at this commit; nothing is dropped".to_string(),
+        Some(newer) => format!(
+            "the {} newest of its {} commits are dropped from the branch",
+            newer,
+            history.len()
+        ),
+        None => format!(
+            "the commit is not in the history of the branch; all {} of its commits are dropped from it",
+            history.len()
+        ),
+    }
+}
+
+pub(crate) async fn run_branch_create(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    name: String,
+    from: String,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let result = client
+        .create_branch(
+            &branch_path(&org, &database, &name)?,
+            &origin_path(&org, &database, &from)?,
+        )
+        .await?;
+
+    output.print(&result, || {
+        format!("Created branch '{}' from '{}'\n", name, from)
+    })
+}
+
+pub(crate) async fn run_branch_list(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let mut branches = client.list_branches(&database).await?;
+    branches.sort();
+
+    output.print(&branches, || {
+        branches
+            .iter()
+            .map(|branch| format!("{}\n", branch))
+            .collect()
+    })
+}
+
+pub(crate) async fn run_branch_delete(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    name: String,
+    confirm: Confirm,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let path = branch_path(&org, &database, &name)?;
+    if confirm.asks() {
+        let spec = BranchSpec::with_branch(&database, &name);
+        let commits = client.commit_history(&spec, None).await?.len();
+        let preview = Preview::new(format!("delete branch {}", path)).effect(format!(
+            "its {} commits are lost, except those also on another branch",
+            commits
+        ));
+        if !confirm.proceed(&preview, output)? {
+            return Ok(());
+        }
+    }
+
+    let result = client.delete_branch(&path).await?;
+
+    output.print(&result, || format!("Deleted branch '{}'\n", name))
+}
+
+pub(crate) async fn run_branch_reset(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    name: String,
+    to: String,
+    confirm: Confirm,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let path = branch_path(&org, &database, &name)?;
+    let target = commit_path(&org, &database, &to)?;
+    if confirm.asks() {
+        let spec = BranchSpec::with_branch(&database, &name);
+        let history: Vec<String> = client
+            .commit_history(&spec, None)
+            .await?
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let commit = target.rsplit('/').next().unwrap_or(&target);
+        let preview = Preview::new(format!("reset branch {} to {}", path, target))
+            .effect(reset_effect(&history, commit));
+        if !confirm.proceed(&preview, output)? {
+            return Ok(());
+        }
+    }
+
+    let result = client.reset(&path, &target).await?;
+
+    output.print(&result, || format!("Reset branch '{}' to '{}'\n", name, to))
+}
+
+pub(crate) async fn run_branch_switch(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    name: String,
+    profile: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    if !client.branch_exists(&database, &name).await? {
+        anyhow::bail!(
+            "Branch '{}' not found in {}/{}. Use 'tdb branch list' to see available branches.",
+            name,
+            org,
+            database
+        );
+    }
+
+    let mut config = crate::auth::load_config()?;
+    let profile_name = profile.unwrap_or_else(|| config.settings.active_profile.clone());
+    let mut updated = config
+        .get_profile(&profile_name)
+        .cloned()
+        .with_context(|| {
+            format!(
+                "Profile '{}' not found. Use 'tdb login' to create it.",
+                profile_name
+            )
+        })?;
+    updated.branch = Some(name.clone());
+    config.set_profile(profile_name.clone(), updated);
+    crate::auth::save_config(&config)?;
+
+    output.print(&json!({ "profile": profile_name, "branch": name }), || {
+        format!("Switched profile '{}' to branch '{}'\n", profile_name, name)
+    })
+}
+
+pub(crate) async fn run_branch_merge(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    from: String,
+    to: String,
+    author: String,
+    message: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let message = message.unwrap_or_else(|| format!("Merge {} into {}", from, to));
+    let result = client
+        .rebase(
+            &branch_path(&org, &database, &to)?,
+            &origin_path(&org, &database, &from)?,
+            &author,
+            &message,
+        )
+        .await?;
+
+    output.print(&result, || format!("Merged '{}' into '{}'\n", from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_path() {
+        assert_eq!(
+            origin_path("admin", "mydb", "main").unwrap(),
+            "admin/mydb/local/branch/main"
+        );
+        assert_eq!(
+            origin_path("admin", "mydb", "release/1.0").unwrap(),
+            "admin/mydb/local/branch/release/1.0"
+        );
+        assert_eq!(
+            origin_path("admin", "mydb", "commit/abc123").unwrap(),
+            "admin/mydb/local/commit/abc123"
+        );
+        assert_eq!(
+            origin_path("admin", "mydb", "admin/other/local/commit/abc123").unwrap(),
+            "admin/other/local/commit/abc123"
+        );
+        assert!(origin_path("admin", "mydb", "main~2").is_err());
+        assert!(origin_path("admin", "mydb", "bad branch").is_err());
+    }
+
+    #[test]
+    fn test_commit_path() {
+        assert_eq!(
+            commit_path("admin", "mydb", "abc123").unwrap(),
+            "admin/mydb/local/commit/abc123"
+        );
+        assert_eq!(
+            commit_path("admin", "mydb", "admin/mydb/local/commit/abc123").unwrap(),
+            "admin/mydb/local/commit/abc123"
+        );
+        assert!(commit_path("admin", "mydb", "ABC!").is_err());
+    }
+
+    #[test]
+    fn test_reset_effect() {
+        let history: Vec<String> = ["c3", "c2", "c1"].iter().map(|id| id.to_string()).collect();
+        assert_eq!(
+            reset_effect(&history, "c1"),
+            "the 2 newest of its 3 commits are dropped from the branch"
+        );
+        assert_eq!(
+            reset_effect(&history, "c3"),
+            "the branch is already at this commit; nothing is dropped"
+        );
+        assert_eq!(
+            reset_effect(&history, "other"),
+            "the commit is not in the history of the branch; all 3 of its commits are dropped from it"
+        );
+    }
+}
@@ -1,17 +1,29 @@
-//! Changestream (SSE) event types and the `changestream` command runner.
+//! Changestream (SSE) event types and the `watch` command runner.
+//!
+//! Events can be narrowed to some document types and actions, preceded by a
+//! replay of the commits made since a given one (rebuilt from the commit log
+//! and the diff endpoint), and projected through a jq expression. With
+//! `--output json|yaml` every event is printed as data (a JSON line, or a YAML
+//! document), whatever the `--format`.
 
 use crate::formatter::{self, ColorMode, OutputFormat};
-use anyhow::{anyhow, Context, Result};
+use crate::jq::Jq;
+use crate::output::{yaml, Output};
+use anyhow::{anyhow, bail, Context, Result};
 use futures_util::stream::StreamExt;
 use reqwest::Client;
 use reqwest_eventsource::{Event, EventSource};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::HashSet;
 use std::time::Duration;
-use terminusdb_client::TerminusDBHttpClient;
+use terminusdb_client::{BranchSpec, DiffOptions, LogEntry, LogOpts, TerminusDBHttpClient};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+/// Commit log entries fetched per request while looking for `--since`
+const LOG_PAGE_SIZE: usize = 100;
+
 /// SSE event data from TerminusDB changeset plugin
 #[derive(Debug, Clone, Deserialize)]
 struct ChangesetEvent {
@@ -37,8 +49,9 @@ struct ChangesetCommitInfo {
 
 #[derive(Debug, Clone, Deserialize)]
 struct MetadataInfo {
-    inserts_count: u64,
-    deletes_count: u64,
+    /// Triple counts; unknown for replayed commits
+    inserts_count: Option<u64>,
+    deletes_count: Option<u64>,
     documents_added: u64,
     documents_deleted: u64,
     documents_updated: u64,
@@ -50,7 +63,272 @@ struct DocumentChange {
     action: String,
 }
 
-pub(crate) async fn run_changestream(
+/// The action name events use for `--action` values (`add` and `added` both
+/// select added documents)
+fn parse_action(action: &str) -> Result<&'static str> {
+    match action.to_lowercase().as_str() {
+        "add" | "added" | "insert" => Ok("added"),
+        "update" | "updated" => Ok("updated"),
+        "delete" | "deleted" => Ok("deleted"),
+        _ => bail!(
+            "unknown action '{}', expected added, updated or deleted",
+            action
+        ),
+    }
+}
+
+/// Which document changes `tdb watch` passes on
+#[derive(Debug, Default)]
+struct EventFilter {
+    types: Vec<String>,
+    actions: Vec<&'static str>,
+}
+
+impl EventFilter {
+    fn new(types: Vec<String>, actions: &[String]) -> Result<Self> {
+        let actions = actions
+            .iter()
+            .map(|action| parse_action(action))
+            .collect::<Result<_>>()?;
+        Ok(EventFilter { types, actions })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.actions.is_empty()
+    }
+
+    fn matches(&self, change: &DocumentChange) -> bool {
+        let ty = crate::diff::type_from_id(&change.id);
+        (self.types.is_empty() || self.types.iter().any(|t| t == ty))
+            && (self.actions.is_empty() || self.actions.contains(&change.action.as_str()))
+    }
+
+    /// `event` with only the matching changes and document counts to match,
+    /// or None when no change matches
+    fn apply(&self, mut event: ChangesetEvent) -> Option<ChangesetEvent> {
+        if self.is_empty() {
+            return Some(event);
+        }
+        event.changes.retain(|change| self.matches(change));
+        if event.changes.is_empty() {
+            return None;
+        }
+        let count = |action: &str| event.changes.iter().filter(|c| c.action == action).count();
+        event.metadata.documents_added = count("added") as u64;
+        event.metadata.documents_updated = count("updated") as u64;
+        event.metadata.documents_deleted = count("deleted") as u64;
+        Some(event)
+    }
+}
+
+/// The identifier of a commit given as `ValidCommit/<id>` or `<id>`
+fn commit_identifier(commit: &str) -> &str {
+    commit.rsplit('/').next().unwrap_or(commit)
+}
+
+/// The commits on the branch of `spec` made after `since`, oldest first
+async fn commits_since(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    since: &str,
+) -> Result<Vec<LogEntry>> {
+    let since = commit_identifier(since);
+    let mut commits = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = client
+            .log(
+                spec,
+                LogOpts {
+                    offset: Some(offset),
+                    count: Some(LOG_PAGE_SIZE),
+                    verbose: false,
+                },
+            )
+            .await?;
+        if page.is_empty() {
+            bail!(
+                "commit '{}' is not in the history of branch '{}'",
+                since,
+                spec.branch.as_deref().unwrap_or("main")
+            );
+        }
+        offset += page.len();
+        for entry in page {
+            if commit_identifier(&entry.identifier) == since {
+                commits.reverse();
+                return Ok(commits);
+            }
+            commits.push(entry);
+        }
+    }
+}
+
+/// A changeset event for a commit from the log, with the changes diffed
+/// against its parent
+async fn replayed_event(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    resource: &str,
+    branch: &str,
+    entry: LogEntry,
+) -> Result<ChangesetEvent> {
+    let id = commit_identifier(&entry.identifier).to_string();
+    let actions = match entry.parent {
+        Some(ref parent) => {
+            let diff = client
+                .diff_data_versions(
+                    spec,
+                    commit_identifier(parent),
+                    &id,
+                    None,
+                    DiffOptions::default(),
+                )
+                .await
+                .with_context(|| format!("failed to diff commit {}", id))?;
+            crate::diff::document_actions(&crate::diff::as_list(diff))
+        }
+        None => Vec::new(),
+    };
+    let count = |action: &str| actions.iter().filter(|(_, a)| *a == action).count() as u64;
+
+    Ok(ChangesetEvent {
+        resource: resource.to_string(),
+        branch: branch.to_string(),
+        metadata: MetadataInfo {
+            inserts_count: None,
+            deletes_count: None,
+            documents_added: count("added"),
+            documents_deleted: count("deleted"),
+            documents_updated: count("updated"),
+        },
+        changes: actions
+            .into_iter()
+            .map(|(id, action)| DocumentChange {
+                id,
+                action: action.to_string(),
+            })
+            .collect(),
+        commit: ChangesetCommitInfo {
+            id,
+            author: entry.author,
+            message: entry.message,
+            timestamp: entry.timestamp,
+        },
+    })
+}
+
+/// The JSON form of an event, as printed by `--format json` and fed to `--jq`
+fn event_json(event: &ChangesetEvent) -> Value {
+    json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "resource": event.resource,
+        "branch": event.branch,
+        "commit": {
+            "id": event.commit.id,
+            "author": event.commit.author,
+            "message": event.commit.message,
+            "timestamp": event.commit.timestamp,
+        },
+        "metadata": {
+            "inserts_count": event.metadata.inserts_count,
+            "deletes_count": event.metadata.deletes_count,
+            "documents_added": event.metadata.documents_added,
+            "documents_deleted": event.metadata.documents_deleted,
+            "documents_updated": event.metadata.documents_updated,
+        },
+        "changes": event.changes,
+    })
+}
+
+/// How events are printed
+struct Printer {
+    format: OutputFormat,
+    output: Output,
+    colorize: bool,
+    jq: Option<Jq>,
+}
+
+impl Printer {
+    fn print(&self, event: &ChangesetEvent) -> Result<()> {
+        if let Some(ref jq) = self.jq {
+            for output in jq.run(event_json(event))? {
+                println!("{}", serde_json::to_string(&output)?);
+            }
+            return Ok(());
+        }
+
+        match (self.output, self.format) {
+            (Output::Yaml, _) => {
+                print!("---\n{}", yaml(&event_json(event))?);
+            }
+            (Output::Json, _) | (Output::Table, OutputFormat::Json) => {
+                println!("{}", serde_json::to_string(&event_json(event))?);
+            }
+            (Output::Table, OutputFormat::Compact) => {
+                // Compact one-line format
+                let metadata_str = formatter::format_metadata(
+                    event.metadata.documents_added,
+                    event.metadata.documents_updated,
+                    event.metadata.documents_deleted,
+                    self.colorize,
+                );
+                println!(
+                    "{} | {} | {} | {}",
+                    event.commit.id, event.commit.author, event.commit.message, metadata_str
+                );
+            }
+            (Output::Table, OutputFormat::Pretty) => {
+                // Pretty formatted output with colors
+                let header = formatter::format_commit_header(
+                    &event.commit.id,
+                    &event.commit.author,
+                    &event.commit.message,
+                    event.commit.timestamp,
+                    self.colorize,
+                );
+                println!("\n{}", header);
+
+                let metadata_str = formatter::format_metadata(
+                    event.metadata.documents_added,
+                    event.metadata.documents_updated,
+                    event.metadata.documents_deleted,
+                    self.colorize,
+                );
+                println!("Changes: {}\n", metadata_str);
+
+                // Print each document change
+                for change in &event.changes {
+                    // For updated documents, we currently don't have field-level changes
+                    // from the SSE event. In future, this could be enhanced.
+                    let change_output = formatter::format_document_change(
+                        &change.id,
+                        &change.action,
+                        None, // changed_fields would go here if available
+                        self.colorize,
+                    );
+                    println!("{}", change_output);
+                }
+                println!(); // Extra newline between events
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Log the likely causes of a failing SSE stream and turn it into an error
+fn stream_error(e: impl std::fmt::Display, sse_url: &str) -> anyhow::Error {
+    error!("SSE stream error: {}", e);
+    error!("Failed URL: {}", sse_url);
+    error!("This could mean:");
+    error!("  1. The TerminusDB server doesn't have the changeset plugin enabled");
+    error!("  2. The /changesets/stream endpoint is not available");
+    error!("  3. Authentication failed (check credentials)");
+    error!("  4. Network connectivity issues");
+    anyhow!("SSE stream error: {}. See logs above for details.", e)
+}
+
+pub(crate) async fn run_watch(
     host: String,
     user: String,
     password: String,
@@ -59,11 +337,20 @@ pub(crate) async fn run_changestream(
     branch: String,
     format: String,
     color: String,
+    types: Vec<String>,
+    actions: Vec<String>,
+    since: Option<String>,
+    jq: Option<String>,
+    output: Output,
 ) -> Result<()> {
     // Validate database is provided
     let db = database
         .context("Database name is required. Provide via --database or TERMINUSDB_DB env var")?;
 
+    // Check the filters and projection before connecting
+    let filter = EventFilter::new(types, &actions)?;
+    let jq = jq.as_deref().map(Jq::compile).transpose()?;
+
     // Parse the host URL
     let url = Url::parse(&host).context(format!("Invalid TerminusDB host URL: {}", host))?;
 
@@ -98,9 +385,13 @@ pub(crate) async fn run_changestream(
     info!("Filtering events for resource: {}", resource_path);
 
     // Parse output options
-    let output_format = OutputFormat::from_str(&format);
     let color_mode = ColorMode::from_str(&color);
-    let colorize = color_mode.should_colorize();
+    let printer = Printer {
+        format: OutputFormat::from_str(&format),
+        output,
+        colorize: color_mode.should_colorize(),
+        jq,
+    };
 
     // Build HTTP client
     let http_client = Client::builder()
@@ -123,6 +414,29 @@ pub(crate) async fn run_changestream(
     let mut event_source = EventSource::new(request)
         .map_err(|e| anyhow!("Failed to create EventSource: {}. Check that the TerminusDB server has the changeset plugin enabled and is accessible at {}", e, sse_url))?;
 
+    // Replay the commits since --since once the stream is open, so that a
+    // commit made during the replay arrives as a live event; commits seen in
+    // the replay are skipped when they arrive live too.
+    let mut replayed = HashSet::new();
+    if let Some(since) = since {
+        match event_source.next().await {
+            Some(Ok(_)) => info!("SSE connection opened successfully!"),
+            Some(Err(e)) => return Err(stream_error(e, &sse_url)),
+            None => bail!("SSE stream ended before it opened"),
+        }
+
+        let spec = BranchSpec::with_branch(&db, &branch);
+        let commits = commits_since(&client, &spec, &since).await?;
+        info!("Replaying {} commits since {}", commits.len(), since);
+        for entry in commits {
+            let event = replayed_event(&client, &spec, &resource_path, &branch, entry).await?;
+            replayed.insert(event.commit.id.clone());
+            if let Some(event) = filter.apply(event) {
+                printer.print(&event)?;
+            }
+        }
+    }
+
     info!("SSE connection established, waiting for events...");
 
     // Process events from the stream
@@ -146,84 +460,12 @@ pub(crate) async fn run_changestream(
                             match serde_json::from_str::<ChangesetEvent>(&message.data) {
                                 Ok(changeset_event) => {
                                     // Filter events by resource path
-                                    if changeset_event.resource == resource_path {
-                                        // Format and print the event
-                                        match output_format {
-                                            OutputFormat::Compact => {
-                                                // Compact one-line format
-                                                let metadata_str = formatter::format_metadata(
-                                                    changeset_event.metadata.documents_added,
-                                                    changeset_event.metadata.documents_updated,
-                                                    changeset_event.metadata.documents_deleted,
-                                                    colorize,
-                                                );
-                                                println!("{} | {} | {} | {}",
-                                                    changeset_event.commit.id,
-                                                    changeset_event.commit.author,
-                                                    changeset_event.commit.message,
-                                                    metadata_str
-                                                );
-                                            }
-                                            OutputFormat::Json => {
-                                                // JSON format
-                                                let output = json!({
-                                                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                                                    "resource": changeset_event.resource,
-                                                    "branch": changeset_event.branch,
-                                                    "commit": {
-                                                        "id": changeset_event.commit.id,
-                                                        "author": changeset_event.commit.author,
-                                                        "message": changeset_event.commit.message,
-                                                        "timestamp": changeset_event.commit.timestamp,
-                                                    },
-                                                    "metadata": {
-                                                        "inserts_count": changeset_event.metadata.inserts_count,
-                                                        "deletes_count": changeset_event.metadata.deletes_count,
-                                                        "documents_added": changeset_event.metadata.documents_added,
-                                                        "documents_deleted": changeset_event.metadata.documents_deleted,
-                                                        "documents_updated": changeset_event.metadata.documents_updated,
-                                                    },
-                                                    "changes": changeset_event.changes,
-                                                });
-
-                                                println!("{}", serde_json::to_string(&output)?);
-                                            }
-                                            OutputFormat::Pretty => {
-                                                // Pretty formatted output with colors
-                                                let header = formatter::format_commit_header(
-                                                    &changeset_event.commit.id,
-                                                    &changeset_event.commit.author,
-                                                    &changeset_event.commit.message,
-                                                    changeset_event.commit.timestamp,
-                                                    colorize,
-                                                );
-                                                println!("\n{}", header);
-
-                                                let metadata_str = formatter::format_metadata(
-                                                    changeset_event.metadata.documents_added,
-                                                    changeset_event.metadata.documents_updated,
-                                                    changeset_event.metadata.documents_deleted,
-                                                    colorize,
-                                                );
-                                                println!("Changes: {}\n", metadata_str);
-
-                                                // Print each document change
-                                                for change in &changeset_event.changes {
-                                                    // For updated documents, we currently don't have field-level changes
-                                                    // from the SSE event. In future, this could be enhanced.
-                                                    let change_output = formatter::format_document_change(
-                                                        &change.id,
-                                                        &change.action,
-                                                        None, // changed_fields would go here if available
-                                                        colorize,
-                                                    );
-                                                    println!("{}", change_output);
-                                                }
-                                                println!(); // Extra newline between events
-                                            }
-                                        }
-                                    } else {
+                                    if changeset_event.resource != resource_path {
                                         debug!("Ignoring event for different resource: {}", changeset_event.resource);
+                                    } else if replayed.contains(&changeset_event.commit.id) {
+                                        debug!("Ignoring replayed commit: {}", changeset_event.commit.id);
+                                    } else if let Some(event) = filter.apply(changeset_event) {
+                                        printer.print(&event)?;
                                     }
                                 }
                                 Err(e) => {
@@ -238,16 +480,7 @@ pub(crate) async fn run_changestream(
                             debug!("Received unknown event type: {}", message.event);
                         }
                     }
-                    Some(Err(e)) => {
-                        error!("SSE stream error: {}", e);
-                        error!("Failed URL: {}", sse_url);
-                        error!("This could mean:");
-                        error!("  1. The TerminusDB server doesn't have the changeset plugin enabled");
-                        error!("  2. The /changesets/stream endpoint is not available");
-                        error!("  3. Authentication failed (check credentials)");
-                        error!("  4. Network connectivity issues");
-                        return Err(anyhow!("SSE stream error: {}. See logs above for details.", e));
-                    }
+                    Some(Err(e)) => return Err(stream_error(e, &sse_url)),
                     None => {
                         warn!("SSE stream ended");
                         break;
@@ -259,3 +492,60 @@ pub(crate) async fn run_changestream(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> ChangesetEvent {
+        serde_json::from_value(json!({
+            "resource": "admin/shop/local/branch/main",
+            "branch": "main",
+            "commit": { "id": "c1", "author": "admin", "message": "m", "timestamp": 0.0 },
+            "metadata": {
+                "inserts_count": 9,
+                "deletes_count": 3,
+                "documents_added": 2,
+                "documents_deleted": 0,
+                "documents_updated": 1
+            },
+            "changes": [
+                { "id": "Person/alice", "action": "added" },
+                { "id": "Order/1", "action": "added" },
+                { "id": "Person/bob", "action": "updated" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn ids(event: &ChangesetEvent) -> Vec<&str> {
+        event.changes.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter() {
+        let filter = EventFilter::new(vec!["Person".to_string()], &[]).unwrap();
+        let filtered = filter.apply(event()).unwrap();
+        assert_eq!(ids(&filtered), vec!["Person/alice", "Person/bob"]);
+        assert_eq!(filtered.metadata.documents_added, 1);
+        assert_eq!(filtered.metadata.documents_updated, 1);
+
+        let filter = EventFilter::new(vec!["Person".to_string()], &["update".to_string()]).unwrap();
+        assert_eq!(ids(&filter.apply(event()).unwrap()), vec!["Person/bob"]);
+
+        let filter = EventFilter::new(vec![], &["deleted".to_string()]).unwrap();
+        assert!(filter.apply(event()).is_none());
+
+        assert_eq!(
+            ids(&EventFilter::default().apply(event()).unwrap()).len(),
+            3
+        );
+        assert!(EventFilter::new(vec![], &["renamed".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_commit_identifier() {
+        assert_eq!(commit_identifier("ValidCommit/abc123"), "abc123");
+        assert_eq!(commit_identifier("abc123"), "abc123");
+    }
+}
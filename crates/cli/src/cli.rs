@@ -1,6 +1,10 @@
 //! Command-line interface definitions (clap `Parser`/`Subcommand` types).
 
-use clap::{Parser, Subcommand};
+use clap::{Command, Parser, Subcommand};
+use std::ffi::OsString;
+
+use crate::confirm::{Confirm, DryRun};
+use crate::output::Output;
 
 #[derive(Parser)]
 #[command(name = "tdb")]
@@ -9,7 +13,17 @@ use clap::{Parser, Subcommand};
 pub(crate) struct Cli {
     /// Profile name to use for credentials (uses active profile if not specified)
     #[arg(long, global = true)]
-    profile: Option<String>,
+    pub(crate) profile: Option<String>,
+
+    /// Print results as a table or text (default), or as JSON or YAML for scripts
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        env = "TERMINUSDB_OUTPUT",
+        default_value_t = Output::Table
+    )]
+    pub(crate) output: Output,
 
     #[command(subcommand)]
     pub(crate) command: Commands,
@@ -17,8 +31,9 @@ pub(crate) struct Cli {
 
 #[derive(Subcommand)]
 pub(crate) enum Commands {
-    /// Stream changeset events from TerminusDB SSE endpoint
-    Changestream {
+    /// Stream changeset events from TerminusDB SSE endpoint, optionally filtered and projected
+    #[command(visible_alias = "changestream")]
+    Watch {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -50,6 +65,22 @@ pub(crate) enum Commands {
         /// Color output: auto (default), always, or never
         #[arg(long, default_value = "auto")]
         color: String,
+
+        /// Only changes to documents of this class (repeatable)
+        #[arg(long = "type", value_name = "CLASS")]
+        types: Vec<String>,
+
+        /// Only changes of this kind: added, updated or deleted (repeatable)
+        #[arg(long = "action")]
+        actions: Vec<String>,
+
+        /// Replay the commits made after this one before following new ones
+        #[arg(long, value_name = "COMMIT")]
+        since: Option<String>,
+
+        /// jq expression applied to each event's JSON form; its results are printed one per line
+        #[arg(long, value_name = "EXPR")]
+        jq: Option<String>,
     },
 
     /// Remote repository management commands
@@ -321,6 +352,9 @@ pub(crate) enum Commands {
         /// Commit message
         #[arg(long, default_value = "Squash commits")]
         message: String,
+
+        #[command(flatten)]
+        confirm: Confirm,
     },
 
     /// Deploy a database from source to target (reverse branch cloning)
@@ -382,37 +416,8 @@ pub(crate) enum Commands {
         skip_create: bool,
     },
 
-    /// Database management commands
-    Database {
-        #[command(subcommand)]
-        command: DatabaseCommands,
-    },
-
-    /// Login and store credentials for a profile
-    Login {
-        /// Profile name (default: "default")
-        #[arg(long, default_value = "default")]
-        profile: String,
-    },
-
-    /// Logout and remove stored credentials
-    Logout {
-        /// Profile name (default: active profile)
-        #[arg(long)]
-        profile: Option<String>,
-    },
-
-    /// Manage profiles
-    Profile {
-        #[command(subcommand)]
-        command: ProfileCommands,
-    },
-}
-
-#[derive(Subcommand)]
-pub(crate) enum DatabaseCommands {
-    /// Create a new database
-    Create {
+    /// List class names, one per line (for shell completion and pickers)
+    Classes {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -426,28 +431,27 @@ pub(crate) enum DatabaseCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Optional label for the database
-        #[arg(long)]
-        label: Option<String>,
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
 
-        /// Optional comment/description for the database
+        /// Only list classes starting with this prefix
         #[arg(long)]
-        comment: Option<String>,
-
-        /// Create with schema graph (default: true)
-        #[arg(long, default_value = "true")]
-        schema: bool,
+        prefix: Option<String>,
     },
 
-    /// Get information about a database
-    Info {
+    /// List the document IDs of a class, one per line (for shell completion and pickers)
+    Ids {
+        /// Class whose document IDs to list
+        class: String,
+
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -461,16 +465,27 @@ pub(crate) enum DatabaseCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Only list IDs starting with this prefix (with or without "<class>/")
+        #[arg(long)]
+        prefix: Option<String>,
     },
 
-    /// List all databases in an organization
-    List {
+    /// Run a WOQL query and print its bindings
+    Query {
+        /// Query in WOQL DSL or JSON-LD, @<path> to read it from a file, or - (or nothing) to read stdin
+        query: Option<String>,
+
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -484,12 +499,27 @@ pub(crate) enum DatabaseCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Output format: table (default), json, or csv
+        #[arg(long, default_value = "table")]
+        format: String,
     },
 
-    /// Delete a database
-    Delete {
+    /// Run a WOQL query and print where the time went instead of the results
+    QueryProfile {
+        /// Query in WOQL JS syntax or JSON-LD, or @<path> to read it from a file
+        query: String,
+
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -503,20 +533,20 @@ pub(crate) enum DatabaseCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Force deletion without confirmation
-        #[arg(long)]
-        force: bool,
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
     },
 
-    /// Get commit log for a database
-    Log {
+    /// Interactive WOQL session with history, paging and schema inspection
+    Repl {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -530,51 +560,40 @@ pub(crate) enum DatabaseCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Limit number of commits to show
-        #[arg(long, default_value = "10")]
-        limit: usize,
-    },
-}
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
 
-#[derive(Subcommand)]
-pub(crate) enum ProfileCommands {
-    /// List all profiles
-    List,
+        /// Result format: table (default), json, or csv
+        #[arg(long, default_value = "table")]
+        format: String,
 
-    /// Set the active profile
-    Set {
-        /// Profile name to make active
-        name: String,
+        /// Lines shown before pausing for Enter (0 to turn paging off)
+        #[arg(long, default_value = "40")]
+        page_size: usize,
     },
 
-    /// Show profile configuration
-    Show {
-        /// Profile name (default: active profile)
-        name: Option<String>,
+    /// Database management commands
+    Database {
+        #[command(subcommand)]
+        command: DatabaseCommands,
     },
 
-    /// Delete a profile
-    Delete {
-        /// Profile name to delete
-        name: String,
+    /// Show the documents added, deleted and updated between two branches or commits
+    Diff {
+        /// Branch name or commit id to compare from
+        ref_a: String,
 
-        /// Force deletion without confirmation
-        #[arg(long)]
-        force: bool,
-    },
-}
+        /// Branch name or commit id to compare to
+        ref_b: String,
 
-#[derive(Subcommand)]
-pub(crate) enum RemoteCommands {
-    /// Add a new remote repository
-    Add {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -588,24 +607,44 @@ pub(crate) enum RemoteCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Name for the remote (e.g., "origin")
-        #[arg(long)]
-        name: String,
+        /// Output format: summary (default), patch, or json
+        #[arg(long, default_value = "summary")]
+        format: String,
+    },
 
-        /// URL of the remote repository
-        #[arg(long)]
-        url: String,
+    /// Branch management commands
+    Branch {
+        #[command(subcommand)]
+        command: BranchCommands,
     },
 
-    /// List all remotes for a database
-    List {
+    /// Schema management commands
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+
+    /// Inspect the GraphQL schema of a branch and run GraphQL queries
+    Gql {
+        #[command(subcommand)]
+        command: GqlCommands,
+    },
+
+    /// Apply and revert versioned schema and data migrations
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommands,
+    },
+
+    /// Export documents as JSONL or CSV, or triples as Turtle
+    Export {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -619,16 +658,39 @@ pub(crate) enum RemoteCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Only export documents of this class (required for CSV)
+        #[arg(long = "type")]
+        ty: Option<String>,
+
+        /// Output format: jsonl, csv, or turtle (default: from the --out extension, else jsonl)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// File to write (default: stdout)
+        #[arg(long)]
+        out: Option<String>,
+
+        /// Number of documents fetched per request
+        #[arg(long, default_value = "1000")]
+        page_size: usize,
     },
 
-    /// Get information about a specific remote
-    Get {
+    /// Import documents from JSONL or CSV, or triples from Turtle
+    Import {
+        /// File to read, or - for stdin
+        file: String,
+
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -642,20 +704,131 @@ pub(crate) enum RemoteCommands {
         password: String,
 
         /// Organization name
-        #[arg(long, env = "TERMINUSDB_ORG")]
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
         org: String,
 
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Name of the remote
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Class of the imported documents (required for CSV; fills in a missing @type in JSONL)
+        #[arg(long = "type")]
+        ty: Option<String>,
+
+        /// Input format: jsonl, csv, or turtle (default: from the file extension, else jsonl)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Write a CSV column to another field (column=field), or skip it (column=-)
+        #[arg(long, value_name = "COLUMN=FIELD")]
+        map: Vec<String>,
+
+        /// Number of documents (or Turtle statements) written per commit
+        #[arg(long, default_value = "1000")]
+        batch_size: usize,
+
+        /// Commit author
+        #[arg(long, default_value = "admin")]
+        author: String,
+
+        /// Commit message (default: "Import from <file>")
+        #[arg(long)]
+        message: Option<String>,
+    },
+
+    /// Run a local TerminusDB server in the foreground, for development
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value = "6363")]
+        port: u16,
+
+        /// Store directory (default: the tdb data directory)
+        #[arg(long, conflicts_with = "memory")]
+        data_dir: Option<std::path::PathBuf>,
+
+        /// Keep everything in memory; nothing survives a restart
+        #[arg(long)]
+        memory: bool,
+
+        /// Password of the admin user, set when the store is created
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Server log level: error, warning, notice, info or debug
+        #[arg(long, default_value = "info")]
+        log_level: String,
+
+        /// Server log format: text or json
+        #[arg(long, default_value = "text")]
+        log_format: String,
+
+        /// Don't echo the server log (it is still written to the log file)
         #[arg(long)]
+        quiet: bool,
+    },
+
+    /// Scaffold a new cargo project wired up to TerminusDB
+    Init {
+        /// Package name of the new project
         name: String,
+
+        /// Directory to create (default: ./<name>)
+        #[arg(long)]
+        path: Option<std::path::PathBuf>,
+
+        /// Database the generated boot script creates (default: <name> with '-' as '_')
+        #[arg(long)]
+        database: Option<String>,
+
+        /// Use a local terminusdb-rs checkout instead of the git repository
+        #[arg(long)]
+        terminusdb_path: Option<std::path::PathBuf>,
     },
 
-    /// Update a remote repository URL
-    Update {
+    /// Login and store credentials for a profile
+    Login {
+        /// Profile name (default: "default")
+        #[arg(long, default_value = "default")]
+        profile: String,
+    },
+
+    /// Logout and remove stored credentials
+    Logout {
+        /// Profile name (default: active profile)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+
+    /// Manage profiles
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to complete for: bash, zsh, fish, powershell or elvish
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Print the tdb man page, or write one page per command to a directory
+    Man {
+        /// Directory to write tdb.1 and a page per subcommand to
+        #[arg(long)]
+        out_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum DatabaseCommands {
+    /// Create a new database
+    Create {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -676,17 +849,21 @@ pub(crate) enum RemoteCommands {
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
 
-        /// Name of the remote
+        /// Optional label for the database
         #[arg(long)]
-        name: String,
+        label: Option<String>,
 
-        /// New URL for the remote repository
+        /// Optional comment/description for the database
         #[arg(long)]
-        url: String,
+        comment: Option<String>,
+
+        /// Create with schema graph (default: true)
+        #[arg(long, default_value = "true")]
+        schema: bool,
     },
 
-    /// Delete a remote repository
-    Delete {
+    /// Get information about a database
+    Info {
         /// TerminusDB server URL
         #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
         host: String,
@@ -706,9 +883,984 @@ pub(crate) enum RemoteCommands {
         /// Database name
         #[arg(long, env = "TERMINUSDB_DB")]
         database: String,
-
-        /// Name of the remote to delete
-        #[arg(long)]
-        name: String,
     },
+
+    /// List all databases in an organization
+    List {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+    },
+
+    /// Delete a database
+    Delete {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        #[command(flatten)]
+        confirm: Confirm,
+    },
+
+    /// Get commit log for a database
+    Log {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Limit number of commits to show
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Show document counts, commits, branches and storage size of a database
+    Stats {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch to count documents and commits on
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum BranchCommands {
+    /// Create a branch
+    Create {
+        /// Name of the new branch
+        name: String,
+
+        /// Branch to start from, commit/<id>, or a resource path such as admin/mydb/local/commit/<id>
+        #[arg(long, default_value = "main")]
+        from: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the branches of a database
+    List {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Delete a branch
+    Delete {
+        /// Name of the branch to delete
+        name: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        #[command(flatten)]
+        confirm: Confirm,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move a branch back to an earlier commit
+    Reset {
+        /// Name of the branch to reset
+        name: String,
+
+        /// Commit id, or a resource path such as admin/mydb/local/commit/<id>
+        #[arg(long)]
+        to: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        #[command(flatten)]
+        confirm: Confirm,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Make a branch the default branch of the profile
+    Switch {
+        /// Name of the branch
+        name: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Merge one branch into another by rebasing the target onto the source
+    Merge {
+        /// Branch to merge from, commit/<id>, or a resource path such as admin/mydb/local/commit/<id>
+        #[arg(long)]
+        from: String,
+
+        /// Branch to merge into
+        #[arg(long, default_value = "main")]
+        to: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Commit author
+        #[arg(long, default_value = "admin")]
+        author: String,
+
+        /// Commit message (default: "Merge <from> into <to>")
+        #[arg(long)]
+        message: Option<String>,
+
+        /// Print the result as JSON (same as `--output json`)
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SchemaCommands {
+    /// Print the schema classes, enums and context
+    Dump {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Output format: json (default) or dsl
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+
+    /// Insert or update the schema documents in a file, showing a diff first
+    Apply {
+        /// Schema file, as JSON (like `schema dump`) or DSL
+        file: String,
+
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Show the diff without applying it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Commit author
+        #[arg(long, default_value = "admin")]
+        author: String,
+
+        /// Commit message
+        #[arg(long, default_value = "Apply schema")]
+        message: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum GqlCommands {
+    /// Print the GraphQL schema of a branch
+    Introspect {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Output format: sdl (default) or json (the introspection result)
+        #[arg(long, default_value = "sdl")]
+        format: String,
+
+        /// File to write (default: stdout)
+        #[arg(long)]
+        out: Option<String>,
+    },
+
+    /// Run a GraphQL query against a branch
+    Exec {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// File holding the query, or - for stdin
+        #[arg(long)]
+        query: String,
+
+        /// JSON file holding the query variables
+        #[arg(long)]
+        variables: Option<String>,
+
+        /// Operation to run, when the query defines several
+        #[arg(long)]
+        operation_name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum MigrateCommands {
+    /// Apply the pending migrations, oldest first
+    Up {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Directory holding the migration files
+        #[arg(long, default_value = "migrations")]
+        dir: String,
+
+        /// Stop after this version
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Commit author
+        #[arg(long, default_value = "admin")]
+        author: String,
+
+        #[command(flatten)]
+        dry_run: DryRun,
+    },
+
+    /// Revert the newest migration, or those after --to
+    Down {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Directory holding the migration files
+        #[arg(long, default_value = "migrations")]
+        dir: String,
+
+        /// Revert the migrations after this version (0 reverts all)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Commit author
+        #[arg(long, default_value = "admin")]
+        author: String,
+
+        #[command(flatten)]
+        confirm: Confirm,
+    },
+
+    /// Show which migrations are applied and which are pending
+    Status {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG", default_value = "admin")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Branch name
+        #[arg(long, env = "TERMINUSDB_BRANCH", default_value = "main")]
+        branch: String,
+
+        /// Directory holding the migration files
+        #[arg(long, default_value = "migrations")]
+        dir: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ProfileCommands {
+    /// List all profiles
+    List,
+
+    /// Set the active profile
+    Set {
+        /// Profile name to make active
+        name: String,
+    },
+
+    /// Show profile configuration
+    Show {
+        /// Profile name (default: active profile)
+        name: Option<String>,
+    },
+
+    /// Delete a profile
+    Delete {
+        /// Profile name to delete
+        name: String,
+
+        /// Force deletion without confirmation
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Set the database commands use when --database is not given
+    SetDefaultDb {
+        /// Database name
+        database: String,
+
+        /// Also set the default branch
+        #[arg(long)]
+        branch: Option<String>,
+
+        /// Also set the default organization
+        #[arg(long)]
+        org: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum RemoteCommands {
+    /// Add a new remote repository
+    Add {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Name for the remote (e.g., "origin")
+        #[arg(long)]
+        name: String,
+
+        /// URL of the remote repository
+        #[arg(long)]
+        url: String,
+    },
+
+    /// List all remotes for a database
+    List {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+    },
+
+    /// Get information about a specific remote
+    Get {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Name of the remote
+        #[arg(long)]
+        name: String,
+    },
+
+    /// Update a remote repository URL
+    Update {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Name of the remote
+        #[arg(long)]
+        name: String,
+
+        /// New URL for the remote repository
+        #[arg(long)]
+        url: String,
+    },
+
+    /// Delete a remote repository
+    Delete {
+        /// TerminusDB server URL
+        #[arg(long, env = "TERMINUSDB_HOST", default_value = "http://localhost:6363")]
+        host: String,
+
+        /// Username for authentication
+        #[arg(long, env = "TERMINUSDB_USER", default_value = "admin")]
+        user: String,
+
+        /// Password for authentication
+        #[arg(long, env = "TERMINUSDB_PASS", default_value = "root")]
+        password: String,
+
+        /// Organization name
+        #[arg(long, env = "TERMINUSDB_ORG")]
+        org: String,
+
+        /// Database name
+        #[arg(long, env = "TERMINUSDB_DB")]
+        database: String,
+
+        /// Name of the remote to delete
+        #[arg(long)]
+        name: String,
+    },
+}
+
+/// The global `--profile` value, picked out of the raw arguments so that the
+/// profile's defaults can be installed before clap parses them.
+pub(crate) fn profile_arg(args: &[OsString]) -> Option<String> {
+    let mut args = args.iter().skip(1).map(|arg| arg.to_string_lossy());
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--profile" {
+            return args.next().map(|value| value.into_owned());
+        }
+        if let Some(value) = arg.strip_prefix("--profile=") {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// `command` with the connection defaults of a profile: every argument that
+/// reads one of the variables in `defaults` falls back to the profile's value
+/// when neither the flag nor the variable is set.
+pub(crate) fn with_defaults(command: Command, defaults: &[(&str, String)]) -> Command {
+    command
+        .mut_args(|arg| {
+            let default = arg
+                .get_env()
+                .and_then(|env| defaults.iter().find(|(var, _)| env == *var));
+            match default {
+                Some((var, value)) => arg
+                    .default_value(value.clone())
+                    .required(false)
+                    // Keep a stored password out of --help.
+                    .hide_default_value(*var == "TERMINUSDB_PASS"),
+                None => arg,
+            }
+        })
+        .mut_subcommands(|subcommand| with_defaults(subcommand, defaults))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn profile_arg_is_found_anywhere_before_a_double_dash() {
+        assert_eq!(
+            profile_arg(&args(&["tdb", "--profile", "prod", "query"])),
+            Some("prod".to_string())
+        );
+        assert_eq!(
+            profile_arg(&args(&["tdb", "query", "--profile=staging"])),
+            Some("staging".to_string())
+        );
+        assert_eq!(
+            profile_arg(&args(&["tdb", "query", "--", "--profile"])),
+            None
+        );
+    }
+
+    /// Building the command tree needs more than a test thread's default stack
+    /// in debug builds, so the tests run on a thread of their own.
+    fn with_stack(test: impl FnOnce() + Send + 'static) {
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(test)
+            .expect("Failed to spawn test thread")
+            .join()
+            .expect("Test thread panicked");
+    }
+
+    #[test]
+    fn profile_defaults_fill_in_missing_connection_args() {
+        with_stack(profile_defaults_fill_in_missing_connection_args_inner);
+    }
+
+    fn profile_defaults_fill_in_missing_connection_args_inner() {
+        let defaults = [
+            ("TERMINUSDB_DB", "mydb".to_string()),
+            ("TERMINUSDB_BRANCH", "dev".to_string()),
+        ];
+        let command = with_defaults(Cli::command(), &defaults);
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["tdb", "classes"])
+            .unwrap();
+        match Cli::from_arg_matches(&matches).unwrap().command {
+            Commands::Classes {
+                database, branch, ..
+            } => {
+                assert_eq!(database, "mydb");
+                assert_eq!(branch, "dev");
+            }
+            _ => panic!("expected classes"),
+        }
+
+        let matches = command
+            .try_get_matches_from(["tdb", "classes", "--database", "other"])
+            .unwrap();
+        match Cli::from_arg_matches(&matches).unwrap().command {
+            Commands::Classes { database, .. } => assert_eq!(database, "other"),
+            _ => panic!("expected classes"),
+        }
+    }
+
+    #[test]
+    fn output_is_accepted_before_or_after_the_subcommand() {
+        with_stack(|| {
+            let parse =
+                |args: &[&str]| Cli::try_parse_from(args.iter().copied()).map(|cli| cli.output);
+            assert_eq!(
+                parse(&["tdb", "--output", "yaml", "classes", "--database", "db"]).unwrap(),
+                Output::Yaml
+            );
+            assert_eq!(
+                parse(&["tdb", "classes", "--database", "db", "--output=json"]).unwrap(),
+                Output::Json
+            );
+            let err = parse(&["tdb", "classes", "--database", "db", "--output", "xml"])
+                .err()
+                .unwrap();
+            assert_eq!(err.exit_code(), 2);
+        });
+    }
+
+    #[test]
+    fn destructive_commands_share_dry_run_and_yes() {
+        with_stack(|| {
+            let parse = |args: &[&str]| Cli::try_parse_from(args.iter().copied()).unwrap();
+            match parse(&[
+                "tdb",
+                "database",
+                "delete",
+                "--org",
+                "a",
+                "--database",
+                "db",
+                "--force",
+            ])
+            .command
+            {
+                Commands::Database {
+                    command: DatabaseCommands::Delete { confirm, .. },
+                } => assert!(confirm.yes && !confirm.dry_run),
+                _ => panic!("expected database delete"),
+            }
+            match parse(&[
+                "tdb",
+                "squash-and-reset",
+                "--org",
+                "a",
+                "--database",
+                "db",
+                "--dry-run",
+            ])
+            .command
+            {
+                Commands::SquashAndReset { confirm, .. } => {
+                    assert!(confirm.dry_run && !confirm.yes)
+                }
+                _ => panic!("expected squash-and-reset"),
+            }
+            match parse(&[
+                "tdb",
+                "branch",
+                "reset",
+                "dev",
+                "--to",
+                "abc123",
+                "--database",
+                "db",
+                "-y",
+            ])
+            .command
+            {
+                Commands::Branch {
+                    command: BranchCommands::Reset { to, confirm, .. },
+                } => {
+                    assert_eq!(to, "abc123");
+                    assert!(confirm.yes);
+                }
+                _ => panic!("expected branch reset"),
+            }
+            match parse(&[
+                "tdb",
+                "migrate",
+                "down",
+                "--to",
+                "0",
+                "--database",
+                "db",
+                "--dry-run",
+            ])
+            .command
+            {
+                Commands::Migrate {
+                    command: MigrateCommands::Down { to, confirm, .. },
+                } => {
+                    assert_eq!(to.as_deref(), Some("0"));
+                    assert!(confirm.dry_run && !confirm.yes);
+                }
+                _ => panic!("expected migrate down"),
+            }
+            match parse(&["tdb", "migrate", "up", "--database", "db", "--dry-run"]).command {
+                Commands::Migrate {
+                    command: MigrateCommands::Up { dry_run, .. },
+                } => assert!(dry_run.dry_run),
+                _ => panic!("expected migrate up"),
+            }
+        });
+    }
 }
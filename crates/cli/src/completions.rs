@@ -0,0 +1,67 @@
+//! `tdb completions` and `tdb man`: shell completion scripts and man pages,
+//! generated from the clap definitions in `cli.rs`.
+
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::path::PathBuf;
+
+use crate::cli::Cli;
+
+pub(crate) async fn run_completions(shell: Shell) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
+
+/// Print the page for `tdb` itself, or with `out_dir` write one page per
+/// command (`tdb.1`, `tdb-query.1`, `tdb-branch-create.1`, ...) there.
+pub(crate) async fn run_man(out_dir: Option<PathBuf>) -> Result<()> {
+    let command = Cli::command();
+    match out_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+            clap_mangen::generate_to(command, &dir)
+                .with_context(|| format!("failed to write man pages to {}", dir.display()))?;
+            eprintln!("Wrote man pages to {}", dir.display());
+        }
+        None => clap_mangen::Man::new(command).render(&mut std::io::stdout())?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Building the command tree needs more than a test thread's default stack
+    /// in debug builds, so the tests run on a thread of their own.
+    fn with_stack(test: impl FnOnce() + Send + 'static) {
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(test)
+            .expect("Failed to spawn test thread")
+            .join()
+            .expect("Test thread panicked");
+    }
+
+    #[test]
+    fn cli_definitions_are_consistent() {
+        with_stack(|| Cli::command().debug_assert());
+    }
+
+    #[test]
+    fn man_page_lists_subcommands() {
+        with_stack(|| {
+            let mut page = Vec::new();
+            clap_mangen::Man::new(Cli::command())
+                .render(&mut page)
+                .unwrap();
+            let page = String::from_utf8(page).unwrap();
+            assert!(page.contains("completions"));
+            assert!(page.contains("query"));
+        });
+    }
+}
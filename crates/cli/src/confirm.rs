@@ -0,0 +1,150 @@
+//! `--dry-run` and `--yes` for commands that delete or rewrite data.
+//!
+//! A destructive command builds a [`Preview`] of what it is about to do and
+//! hands it to [`Confirm::proceed`] before touching anything. With `--dry-run`
+//! the preview is printed (as data with `--output json|yaml`) and nothing
+//! changes; otherwise it is shown on stderr and the user has to type `yes`,
+//! unless `--yes` was given. Without a terminal to ask on, the command fails
+//! instead of guessing. Commands that change data without asking, like
+//! `migrate up`, take the same `--dry-run` flag as [`DryRun`].
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde_json::json;
+
+use crate::output::Output;
+
+/// The `--dry-run` and `--yes` flags of a destructive command
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub(crate) struct Confirm {
+    /// Show what would change without changing anything
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+
+    /// Don't ask for confirmation
+    #[arg(long, short = 'y', alias = "force")]
+    pub(crate) yes: bool,
+}
+
+/// The `--dry-run` flag of a command that doesn't ask for confirmation
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub(crate) struct DryRun {
+    /// Show what would change without changing anything
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+/// What a destructive command is about to do
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Preview {
+    /// What the command does, e.g. `delete database admin/mydb`
+    pub(crate) action: String,
+    /// What is lost or changed, one item per line
+    pub(crate) effects: Vec<String>,
+}
+
+impl Preview {
+    pub(crate) fn new(action: impl Into<String>) -> Self {
+        Preview {
+            action: action.into(),
+            effects: Vec::new(),
+        }
+    }
+
+    pub(crate) fn effect(mut self, effect: impl Into<String>) -> Self {
+        self.effects.push(effect.into());
+        self
+    }
+
+    /// The preview as text, headed by `lead` (`Would` or `About to`)
+    fn render(&self, lead: &str) -> String {
+        let mut text = format!("{} {}\n", lead, self.action);
+        for effect in &self.effects {
+            text += &format!("  - {}\n", effect);
+        }
+        text
+    }
+}
+
+impl Confirm {
+    /// Whether the command needs a [`Preview`], i.e. is not just told to go ahead
+    pub(crate) fn asks(self) -> bool {
+        self.dry_run || !self.yes
+    }
+
+    /// Whether to go ahead with `preview`: never for `--dry-run` (the preview
+    /// is printed instead), always with `--yes`, otherwise when the user
+    /// confirms.
+    pub(crate) fn proceed(self, preview: &Preview, output: Output) -> Result<bool> {
+        if self.dry_run {
+            let data = json!({
+                "dry_run": true,
+                "action": preview.action,
+                "effects": preview.effects,
+            });
+            output.print(&data, || preview.render("Would"))?;
+            eprintln!("Dry run: nothing was changed.");
+            return Ok(false);
+        }
+        if self.yes {
+            return Ok(true);
+        }
+        if !atty::is(atty::Stream::Stdin) {
+            bail!(
+                "not asking to {} without a terminal; pass --yes to go ahead",
+                preview.action
+            );
+        }
+
+        eprint!("{}", preview.render("About to"));
+        eprintln!("This action cannot be undone!");
+        eprint!("Type 'yes' to confirm: ");
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        if input.trim() != "yes" {
+            eprintln!("Cancelled.");
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let preview = Preview::new("delete database admin/mydb")
+            .effect("branch dev: 10 documents, 3 commits")
+            .effect("branch main: 1204 documents, 42 commits");
+        assert_eq!(
+            preview.render("Would"),
+            "Would delete database admin/mydb\n\
+             \x20 - branch dev: 10 documents, 3 commits\n\
+             \x20 - branch main: 1204 documents, 42 commits\n"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_and_yes() {
+        let preview = Preview::new("reset branch admin/mydb/main");
+        let dry_run = Confirm {
+            dry_run: true,
+            yes: true,
+        };
+        assert!(dry_run.asks());
+        assert!(!dry_run.proceed(&preview, Output::Table).unwrap());
+
+        let yes = Confirm {
+            dry_run: false,
+            yes: true,
+        };
+        assert!(!yes.asks());
+        assert!(yes.proceed(&preview, Output::Table).unwrap());
+        assert!(Confirm::default().asks());
+    }
+}
@@ -1,10 +1,15 @@
-//! Database-level commands: optimize, squash, database CRUD, and deploy.
+//! Database-level commands: optimize, squash, database CRUD, stats, and deploy.
+
+use std::collections::BTreeMap;
 
 use anyhow::Result;
-use serde_json::json;
-use terminusdb_client::TerminusDBHttpClient;
+use serde_json::{json, Value};
+use terminusdb_client::{BranchSpec, LogEntry, LogOpts, TerminusDBHttpClient};
 use url::Url;
 
+use crate::confirm::{Confirm, Preview};
+use crate::output::{Output, StatusError};
+
 pub(crate) async fn run_optimize(
     host: String,
     user: String,
@@ -13,6 +18,7 @@ pub(crate) async fn run_optimize(
     database: String,
     branch: String,
     meta: bool,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -25,8 +31,7 @@ pub(crate) async fn run_optimize(
 
     let result = client.optimize(&path, None).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_squash(
@@ -38,6 +43,7 @@ pub(crate) async fn run_squash(
     branch: String,
     author: String,
     message: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -45,8 +51,7 @@ pub(crate) async fn run_squash(
     let path = format!("{}/{}/local/branch/{}", org, database, branch);
     let result = client.squash(&path, &author, &message, None).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_squash_and_reset(
@@ -58,15 +63,32 @@ pub(crate) async fn run_squash_and_reset(
     branch: String,
     author: String,
     message: String,
+    confirm: Confirm,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
 
     let path = format!("{}/{}/local/branch/{}", org, database, branch);
+    if confirm.asks() {
+        let spec = BranchSpec::with_branch(&database, &branch);
+        let commits = client.commit_history(&spec, None).await?.len();
+        let preview = Preview::new(format!(
+            "squash the history of branch {} into one commit",
+            path
+        ))
+        .effect(format!(
+            "the {} commits on the branch are replaced by one with the same content",
+            commits
+        ))
+        .effect("commit ids change, so clones of the branch no longer share its history");
+        if !confirm.proceed(&preview, output)? {
+            return Ok(());
+        }
+    }
     let result = client.squash_and_reset(&path, &author, &message).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_database_create(
@@ -78,6 +100,7 @@ pub(crate) async fn run_database_create(
     label: Option<String>,
     comment: Option<String>,
     schema: bool,
+    output: Output,
 ) -> Result<()> {
     let label_str = label.as_deref().unwrap_or(&database);
     let comment_str = comment.as_deref().unwrap_or("");
@@ -102,13 +125,17 @@ pub(crate) async fn run_database_create(
         .await?;
 
     if !res.status().is_success() {
+        let status = res.status();
         let error_text = res.text().await?;
-        anyhow::bail!("Failed to create database: {}", error_text);
+        return Err(StatusError {
+            status,
+            message: format!("Failed to create database: {}", error_text),
+        }
+        .into());
     }
 
     let result: serde_json::Value = res.json().await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_database_info(
@@ -117,6 +144,7 @@ pub(crate) async fn run_database_info(
     password: String,
     org: String,
     database: String,
+    output: Output,
 ) -> Result<()> {
     let http_client = reqwest::Client::new();
     let api_url = format!("{}/api/db/{}/{}", host, org, database);
@@ -130,16 +158,18 @@ pub(crate) async fn run_database_info(
     if !res.status().is_success() {
         let status = res.status();
         let error_text = res.text().await?;
-        anyhow::bail!(
-            "Failed to get database info (status {}): {}",
+        return Err(StatusError {
             status,
-            error_text
-        );
+            message: format!(
+                "Failed to get database info (status {}): {}",
+                status, error_text
+            ),
+        }
+        .into());
     }
 
     let result: serde_json::Value = res.json().await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_database_list(
@@ -147,6 +177,7 @@ pub(crate) async fn run_database_list(
     user: String,
     password: String,
     org: String,
+    output: Output,
 ) -> Result<()> {
     let http_client = reqwest::Client::new();
     let api_url = format!("{}/api/db/{}", host, org);
@@ -160,16 +191,18 @@ pub(crate) async fn run_database_list(
     if !res.status().is_success() {
         let status = res.status();
         let error_text = res.text().await?;
-        anyhow::bail!(
-            "Failed to list databases (status {}): {}",
+        return Err(StatusError {
             status,
-            error_text
-        );
+            message: format!(
+                "Failed to list databases (status {}): {}",
+                status, error_text
+            ),
+        }
+        .into());
     }
 
     let result: serde_json::Value = res.json().await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_database_delete(
@@ -178,18 +211,14 @@ pub(crate) async fn run_database_delete(
     password: String,
     org: String,
     database: String,
-    force: bool,
+    confirm: Confirm,
+    output: Output,
 ) -> Result<()> {
-    if !force {
-        eprintln!("About to delete database: {}/{}", org, database);
-        eprintln!("This action cannot be undone!");
-        eprint!("Type 'yes' to confirm: ");
-        use std::io::{self, BufRead};
-        let stdin = io::stdin();
-        let mut input = String::new();
-        stdin.lock().read_line(&mut input)?;
-        if input.trim() != "yes" {
-            eprintln!("Deletion cancelled.");
+    if confirm.asks() {
+        let parsed_url = Url::parse(&host)?;
+        let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+        let preview = delete_preview(&client, &org, &database).await?;
+        if !confirm.proceed(&preview, output)? {
             return Ok(());
         }
     }
@@ -206,16 +235,42 @@ pub(crate) async fn run_database_delete(
     if !res.status().is_success() {
         let status = res.status();
         let error_text = res.text().await?;
-        anyhow::bail!(
-            "Failed to delete database (status {}): {}",
+        return Err(StatusError {
             status,
-            error_text
-        );
+            message: format!(
+                "Failed to delete database (status {}): {}",
+                status, error_text
+            ),
+        }
+        .into());
     }
 
     let result: serde_json::Value = res.json().await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
+}
+
+/// The branches of a database with their documents and commits
+async fn delete_preview(
+    client: &TerminusDBHttpClient,
+    org: &str,
+    database: &str,
+) -> Result<Preview> {
+    let mut preview = Preview::new(format!("delete database {}/{}", org, database));
+    let mut branches = client.list_branches(database).await?;
+    branches.sort();
+    for branch in branches {
+        let documents: u64 = document_counts(client, org, database, &branch)
+            .await?
+            .values()
+            .sum();
+        let spec = BranchSpec::with_branch(database, &branch);
+        let commits = client.commit_history(&spec, None).await?.len();
+        preview = preview.effect(format!(
+            "branch {}: {} documents, {} commits",
+            branch, documents, commits
+        ));
+    }
+    Ok(preview)
 }
 
 pub(crate) async fn run_database_log(
@@ -225,6 +280,7 @@ pub(crate) async fn run_database_log(
     org: String,
     database: String,
     limit: usize,
+    output: Output,
 ) -> Result<()> {
     let http_client = reqwest::Client::new();
     let api_url = format!("{}/api/log/{}/{}", host, org, database);
@@ -238,11 +294,14 @@ pub(crate) async fn run_database_log(
     if !res.status().is_success() {
         let status = res.status();
         let error_text = res.text().await?;
-        anyhow::bail!(
-            "Failed to get commit log (status {}): {}",
+        return Err(StatusError {
             status,
-            error_text
-        );
+            message: format!(
+                "Failed to get commit log (status {}): {}",
+                status, error_text
+            ),
+        }
+        .into());
     }
 
     let mut result: Vec<serde_json::Value> = res.json().await?;
@@ -252,8 +311,248 @@ pub(crate) async fn run_database_log(
         result.truncate(limit);
     }
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
+}
+
+pub(crate) async fn run_database_stats(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+    let spec = BranchSpec::with_branch(&database, &branch);
+
+    let classes = document_counts(&client, &org, &database, &branch).await?;
+    let size = client
+        .query_raw::<Value>(Some(spec.clone()), size_query(&org, &database), None)
+        .await?;
+
+    let last_commit = client
+        .log(
+            &spec,
+            LogOpts {
+                offset: Some(0),
+                count: Some(1),
+                verbose: false,
+            },
+        )
+        .await?
+        .into_iter()
+        .next();
+
+    let stats = DatabaseStats {
+        database: format!("{}/{}", org, database),
+        branch,
+        branches: client.list_branches(&database).await?,
+        commits: client.commit_history(&spec, None).await?.len(),
+        last_commit,
+        size: size
+            .bindings
+            .first()
+            .and_then(|binding| bound_number(binding, "size")),
+        classes,
+    };
+
+    let now = chrono::Utc::now().timestamp_millis() as f64 / 1000.0;
+    output.print(&stats.data(now), || stats.render(now))
+}
+
+/// Documents per class on a branch, see [`class_counts`]
+async fn document_counts(
+    client: &TerminusDBHttpClient,
+    org: &str,
+    database: &str,
+    branch: &str,
+) -> Result<BTreeMap<String, u64>> {
+    let spec = BranchSpec::with_branch(database, branch);
+    let schema = client.get_schema_documents(&spec).await?;
+    let counts = client
+        .query_raw::<Value>(Some(spec), class_count_query(org, database, branch), None)
+        .await?;
+    Ok(class_counts(&schema, &counts.bindings))
+}
+
+/// What `tdb database stats` reports
+struct DatabaseStats {
+    database: String,
+    branch: String,
+    branches: Vec<String>,
+    commits: usize,
+    /// Newest commit on the branch, if it has any
+    last_commit: Option<LogEntry>,
+    /// Bytes the database takes up in the store
+    size: Option<u64>,
+    /// Documents per class, including classes without any
+    classes: BTreeMap<String, u64>,
+}
+
+impl DatabaseStats {
+    fn documents(&self) -> u64 {
+        self.classes.values().sum()
+    }
+
+    /// Seconds from the last commit to `now`
+    fn age(&self, now: f64) -> Option<u64> {
+        let commit = self.last_commit.as_ref()?;
+        Some((now - commit.timestamp).max(0.0) as u64)
+    }
+
+    fn data(&self, now: f64) -> Value {
+        json!({
+            "database": self.database,
+            "branch": self.branch,
+            "branches": self.branches,
+            "commits": self.commits,
+            "last_commit": self.last_commit.as_ref().map(|commit| json!({
+                "id": commit.identifier,
+                "author": commit.author,
+                "message": commit.message,
+                "timestamp": commit.timestamp,
+                "age_seconds": self.age(now),
+            })),
+            "size_bytes": self.size,
+            "documents": self.documents(),
+            "classes": self.classes,
+        })
+    }
+
+    fn render(&self, now: f64) -> String {
+        let last_commit = match (&self.last_commit, self.age(now)) {
+            (Some(commit), Some(age)) => format!(
+                "{} by {}, {} ago: {}",
+                commit.identifier,
+                commit.author,
+                format_age(age),
+                commit.message.lines().next().unwrap_or("")
+            ),
+            _ => "none".to_string(),
+        };
+        let mut rows = vec![
+            ("Database".to_string(), self.database.clone()),
+            ("Branch".to_string(), self.branch.clone()),
+            ("Branches".to_string(), self.branches.join(", ")),
+            ("Commits".to_string(), self.commits.to_string()),
+            ("Last commit".to_string(), last_commit),
+            (
+                "Size".to_string(),
+                self.size.map_or("unknown".to_string(), format_size),
+            ),
+            ("Documents".to_string(), self.documents().to_string()),
+        ];
+        rows.extend(
+            self.classes
+                .iter()
+                .map(|(class, count)| (format!("  {}", class), count.to_string())),
+        );
+
+        let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        rows.iter()
+            .map(|(key, value)| format!("{:<width$}  {}\n", key, value, width = width))
+            .collect()
+    }
+}
+
+/// Count the documents of every type in the instance graph, in one query
+fn class_count_query(org: &str, database: &str, branch: &str) -> Value {
+    json!({
+        "@type": "Using",
+        "collection": format!("{}/{}/local/branch/{}", org, database, branch),
+        "query": {
+            "@type": "And",
+            "and": [
+                {
+                    "@type": "GroupBy",
+                    "group_by": ["type"],
+                    "template": { "@type": "Value", "variable": "doc" },
+                    "grouped": { "@type": "Value", "variable": "docs" },
+                    "query": {
+                        "@type": "Triple",
+                        "subject": { "@type": "NodeValue", "variable": "doc" },
+                        "predicate": { "@type": "NodeValue", "node": "rdf:type" },
+                        "object": { "@type": "Value", "variable": "type" },
+                        "graph": "instance"
+                    }
+                },
+                {
+                    "@type": "Length",
+                    "list": { "@type": "DataValue", "variable": "docs" },
+                    "length": { "@type": "DataValue", "variable": "count" }
+                }
+            ]
+        }
+    })
+}
+
+/// The storage size of a database, in bytes
+fn size_query(org: &str, database: &str) -> Value {
+    json!({
+        "@type": "Size",
+        "resource": format!("{}/{}", org, database),
+        "size": { "@type": "DataValue", "variable": "size" }
+    })
+}
+
+/// Documents per class: every class in the schema that can have documents of
+/// its own (not abstract, not a subdocument), with the counts from
+/// [`class_count_query`]
+fn class_counts(schema: &[Value], bindings: &[Value]) -> BTreeMap<String, u64> {
+    let mut classes: BTreeMap<String, u64> = schema
+        .iter()
+        .filter(|doc| doc.get("@type").and_then(Value::as_str) == Some("Class"))
+        .filter(|doc| doc.get("@abstract").is_none() && doc.get("@subdocument").is_none())
+        .filter_map(|doc| doc.get("@id")?.as_str())
+        .map(|class| (class.to_string(), 0))
+        .collect();
+
+    for binding in bindings {
+        let class = binding
+            .get("type")
+            .and_then(Value::as_str)
+            .map(|ty| ty.strip_prefix("@schema:").unwrap_or(ty));
+        if let (Some(class), Some(count)) = (class, bound_number(binding, "count")) {
+            if let Some(total) = classes.get_mut(class) {
+                *total = count;
+            }
+        }
+    }
+    classes
+}
+
+/// A number bound to `variable`, as a typed literal or a plain number
+fn bound_number(binding: &Value, variable: &str) -> Option<u64> {
+    let value = binding.get(variable)?;
+    let value = value.get("@value").unwrap_or(value);
+    value.as_u64().or_else(|| value.as_f64().map(|f| f as u64))
+}
+
+/// `seconds` as the largest whole unit, e.g. `3h`
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86399 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/// `bytes` in binary units, e.g. `1.5 MiB`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
 }
 
 pub(crate) async fn run_deploy(
@@ -271,6 +570,7 @@ pub(crate) async fn run_deploy(
     target_label: Option<String>,
     target_comment: Option<String>,
     skip_create: bool,
+    output: Output,
 ) -> Result<()> {
     eprintln!(
         "Starting deployment from {}:{}/{} to {}:{}/{}",
@@ -310,5 +610,99 @@ pub(crate) async fn run_deploy(
     );
     eprintln!("   Target: {}:{}/{}", target_host, target_org, target_db);
 
+    let summary = json!({
+        "source": {
+            "host": source_host,
+            "org": source_org,
+            "database": source_db,
+            "branch": source_branch,
+        },
+        "target": {
+            "host": target_host,
+            "org": target_org,
+            "database": target_db,
+        },
+    });
+    if let Some(text) = output.render_data(&summary)? {
+        print!("{}", text);
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> DatabaseStats {
+        let last_commit: LogEntry = serde_json::from_value(json!({
+            "@id": "ValidCommit/abc123",
+            "@type": "ValidCommit",
+            "author": "admin",
+            "identifier": "abc123",
+            "message": "Add people\n\nImported from the CRM",
+            "schema": "layer_data:Layer_0",
+            "timestamp": 1_700_000_000.5
+        }))
+        .unwrap();
+        DatabaseStats {
+            database: "admin/mydb".to_string(),
+            branch: "main".to_string(),
+            branches: vec!["dev".to_string(), "main".to_string()],
+            commits: 42,
+            last_commit: Some(last_commit),
+            size: Some(1536),
+            classes: BTreeMap::from([("Person".to_string(), 10), ("Team".to_string(), 0)]),
+        }
+    }
+
+    #[test]
+    fn test_class_counts() {
+        let schema = vec![
+            json!({ "@type": "@context", "@schema": "terminusdb:///schema#" }),
+            json!({ "@type": "Class", "@id": "Person", "name": "xsd:string" }),
+            json!({ "@type": "Class", "@id": "Team" }),
+            json!({ "@type": "Class", "@id": "Entity", "@abstract": [] }),
+            json!({ "@type": "Class", "@id": "Address", "@subdocument": [], "@key": { "@type": "Random" } }),
+            json!({ "@type": "Enum", "@id": "Color", "@value": ["red"] }),
+        ];
+        let bindings = vec![
+            json!({ "type": "@schema:Person", "docs": [], "count": { "@type": "xsd:decimal", "@value": 10 } }),
+            json!({ "type": "@schema:Address", "docs": [], "count": { "@type": "xsd:decimal", "@value": 7 } }),
+        ];
+        assert_eq!(
+            class_counts(&schema, &bindings),
+            BTreeMap::from([("Person".to_string(), 10), ("Team".to_string(), 0)])
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let now = 1_700_000_000.5 + 3.0 * 3600.0 + 120.0;
+        assert_eq!(
+            stats().render(now),
+            "Database     admin/mydb\n\
+             Branch       main\n\
+             Branches     dev, main\n\
+             Commits      42\n\
+             Last commit  abc123 by admin, 3h ago: Add people\n\
+             Size         1.5 KiB\n\
+             Documents    10\n\
+             \x20 Person     10\n\
+             \x20 Team       0\n"
+        );
+        let data = stats().data(now);
+        assert_eq!(data["last_commit"]["age_seconds"], json!(10920));
+        assert_eq!(data["documents"], json!(10));
+        assert_eq!(data["size_bytes"], json!(1536));
+    }
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(format_age(59), "59s");
+        assert_eq!(format_age(61), "1m");
+        assert_eq!(format_age(2 * 86400 + 5), "2d");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(5 * 1024 * 1024 + 1), "5.0 MiB");
+        assert_eq!(bound_number(&json!({ "size": 2048 }), "size"), Some(2048));
+    }
+}
@@ -0,0 +1,349 @@
+//! `tdb diff`: documents added, deleted and updated between two refs.
+//!
+//! The refs are branch names or commit ids, diffed with the data-version form
+//! of the diff endpoint. The result is printed as per-type statistics
+//! (`summary`), a readable patch listing each document and changed field
+//! (`patch`), or the raw diff with the statistics (`json`, or YAML with
+//! `--output yaml`).
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use terminusdb_client::{BranchSpec, DiffOptions, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+
+/// Output format for `tdb diff`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DiffFormat {
+    Summary,
+    Patch,
+    Json,
+}
+
+impl DiffFormat {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "summary" => Ok(Self::Summary),
+            "patch" => Ok(Self::Patch),
+            "json" => Ok(Self::Json),
+            _ => bail!("unknown format '{}', expected summary, patch or json", s),
+        }
+    }
+}
+
+pub(crate) async fn run_diff(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    ref_a: String,
+    ref_b: String,
+    format: String,
+    output: Output,
+) -> Result<()> {
+    let format = DiffFormat::parse(&format)?;
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::new(&database);
+    let diffs = as_list(
+        client
+            .diff_data_versions(&spec, &ref_a, &ref_b, None, DiffOptions::default())
+            .await?,
+    );
+    let changes: Vec<Change> = diffs.iter().map(Change::from_diff).collect();
+
+    let output = match (output, format) {
+        (Output::Table, DiffFormat::Json) => Output::Json,
+        (output, _) => output,
+    };
+    let data = json!({ "stats": stats(&changes), "diffs": diffs });
+    output.print(&data, || match format {
+        DiffFormat::Patch => render_patch(&changes),
+        _ => render_summary(&changes),
+    })
+}
+
+/// The per-document diffs of a diff endpoint response
+pub(crate) fn as_list(diff: Value) -> Vec<Value> {
+    match diff {
+        Value::Array(diffs) => diffs,
+        Value::Null => Vec::new(),
+        diff => vec![diff],
+    }
+}
+
+/// The documents `diffs` touch, as changeset events report them: the id
+/// relative to the data prefix and `added`, `deleted` or `updated`
+pub(crate) fn document_actions(diffs: &[Value]) -> Vec<(String, &'static str)> {
+    diffs
+        .iter()
+        .map(Change::from_diff)
+        .map(|change| {
+            let action = match change.kind {
+                ChangeKind::Added => "added",
+                ChangeKind::Deleted => "deleted",
+                ChangeKind::Updated => "updated",
+            };
+            (local_id(change.id).to_string(), action)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChangeKind {
+    Added,
+    Deleted,
+    Updated,
+}
+
+/// One document's entry in a diff
+#[derive(Debug)]
+struct Change<'a> {
+    kind: ChangeKind,
+    id: &'a str,
+    ty: String,
+    /// The field patches of an update
+    patch: Option<&'a Value>,
+}
+
+impl<'a> Change<'a> {
+    fn from_diff(diff: &'a Value) -> Self {
+        let (kind, doc) = match diff.get("@op").and_then(Value::as_str) {
+            Some("Insert") => (ChangeKind::Added, diff.get("@insert")),
+            Some("Delete") => (ChangeKind::Deleted, diff.get("@delete")),
+            _ => (ChangeKind::Updated, None),
+        };
+        let id = doc
+            .unwrap_or(diff)
+            .get("@id")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let ty = doc
+            .and_then(|doc| doc.get("@type"))
+            .and_then(Value::as_str)
+            .map(short_name)
+            .unwrap_or_else(|| type_from_id(id));
+        Change {
+            kind,
+            id,
+            ty: ty.to_string(),
+            patch: (kind == ChangeKind::Updated).then_some(diff),
+        }
+    }
+}
+
+/// The local name of an IRI: `http://example.com/schema#Person` -> `Person`
+fn short_name(iri: &str) -> &str {
+    iri.rsplit('#').next().unwrap_or(iri)
+}
+
+/// The class of a document from its id (`terminusdb:///data/Order/42` ->
+/// `Order`), since update patches do not carry the `@type`
+pub(crate) fn type_from_id(id: &str) -> &str {
+    match local_id(id).split_once('/') {
+        Some((ty, _)) if !ty.is_empty() => ty,
+        _ => "(unknown)",
+    }
+}
+
+/// A document id without the data prefix: `terminusdb:///data/Order/42` ->
+/// `Order/42`
+fn local_id(id: &str) -> &str {
+    id.split_once("/data/").map_or(id, |(_, local)| local)
+}
+
+/// Document counts of one type
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+struct TypeStats {
+    added: usize,
+    deleted: usize,
+    updated: usize,
+}
+
+impl TypeStats {
+    fn count(&mut self, kind: ChangeKind) {
+        match kind {
+            ChangeKind::Added => self.added += 1,
+            ChangeKind::Deleted => self.deleted += 1,
+            ChangeKind::Updated => self.updated += 1,
+        }
+    }
+}
+
+fn stats(changes: &[Change]) -> BTreeMap<String, TypeStats> {
+    let mut stats: BTreeMap<String, TypeStats> = BTreeMap::new();
+    for change in changes {
+        stats
+            .entry(change.ty.clone())
+            .or_default()
+            .count(change.kind);
+    }
+    stats
+}
+
+fn totals(changes: &[Change]) -> TypeStats {
+    let mut totals = TypeStats::default();
+    for change in changes {
+        totals.count(change.kind);
+    }
+    totals
+}
+
+fn render_summary(changes: &[Change]) -> String {
+    if changes.is_empty() {
+        return "No differences\n".to_string();
+    }
+
+    let stats = stats(changes);
+    let mut rows: Vec<(&str, TypeStats)> = stats.iter().map(|(ty, s)| (ty.as_str(), *s)).collect();
+    rows.push(("Total", totals(changes)));
+
+    let width = rows
+        .iter()
+        .map(|(ty, _)| ty.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Type".len());
+    let mut out = format!(
+        "{:<width$}  {:>7}  {:>7}  {:>7}\n",
+        "Type", "Added", "Deleted", "Updated"
+    );
+    for (ty, s) in rows {
+        out.push_str(&format!(
+            "{:<width$}  {:>7}  {:>7}  {:>7}\n",
+            ty, s.added, s.deleted, s.updated
+        ));
+    }
+    out
+}
+
+fn render_patch(changes: &[Change]) -> String {
+    let mut out = String::new();
+    for change in changes {
+        match change.kind {
+            ChangeKind::Added => out.push_str(&format!("+ {} ({})\n", change.id, change.ty)),
+            ChangeKind::Deleted => out.push_str(&format!("- {} ({})\n", change.id, change.ty)),
+            ChangeKind::Updated => {
+                out.push_str(&format!("~ {} ({})\n", change.id, change.ty));
+                if let Some(patch) = change.patch {
+                    push_field_changes(&mut out, "", patch);
+                }
+            }
+        }
+    }
+    let totals = totals(changes);
+    out.push_str(&format!(
+        "{} added, {} deleted, {} updated\n",
+        totals.added, totals.deleted, totals.updated
+    ));
+    out
+}
+
+/// One line per changed field; subdocument patches are followed with dotted
+/// paths, and values are shown when the operation records both sides
+fn push_field_changes(out: &mut String, prefix: &str, patch: &Value) {
+    let Some(fields) = patch.as_object() else {
+        return;
+    };
+    let mut fields: Vec<(&String, &Value)> = fields
+        .iter()
+        .filter(|(field, _)| !field.starts_with('@'))
+        .collect();
+    fields.sort_by_key(|(field, _)| *field);
+    for (field, op) in fields {
+        let path = format!("{}{}", prefix, field);
+        match (
+            op.get("@op").and_then(Value::as_str),
+            op.get("@before"),
+            op.get("@after"),
+        ) {
+            (Some(_), Some(before), Some(after)) => {
+                out.push_str(&format!("    {}: {} -> {}\n", path, before, after))
+            }
+            (Some(name), _, _) => out.push_str(&format!("    {}: {}\n", path, name)),
+            (None, _, _) => push_field_changes(out, &format!("{}.", path), op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diffs() -> Vec<Value> {
+        vec![
+            json!({
+                "@op": "Insert",
+                "@insert": { "@id": "Product/Gadget", "@type": "Product", "name": "Gadget" }
+            }),
+            json!({
+                "@id": "terminusdb:///data/Order/ORD-0002",
+                "status": { "@op": "SwapValue", "@before": "processing", "@after": "shipped" },
+                "address": {
+                    "city": { "@op": "SwapValue", "@before": "Delft", "@after": "Leiden" }
+                },
+                "tags": { "@op": "CopyList", "@to": 2, "@rest": { "@op": "KeepList" } }
+            }),
+            json!({
+                "@op": "Delete",
+                "@delete": { "@id": "Product/Widget", "@type": "http://example.com/schema#Product" }
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_summary() {
+        let diffs = diffs();
+        let changes: Vec<Change> = diffs.iter().map(Change::from_diff).collect();
+        assert_eq!(
+            render_summary(&changes),
+            "Type       Added  Deleted  Updated\n\
+             Order          0        0        1\n\
+             Product        1        1        0\n\
+             Total          1        1        1\n"
+        );
+        assert_eq!(render_summary(&[]), "No differences\n");
+    }
+
+    #[test]
+    fn test_patch() {
+        let diffs = diffs();
+        let changes: Vec<Change> = diffs.iter().map(Change::from_diff).collect();
+        assert_eq!(
+            render_patch(&changes),
+            "+ Product/Gadget (Product)\n\
+             ~ terminusdb:///data/Order/ORD-0002 (Order)\n    \
+             address.city: \"Delft\" -> \"Leiden\"\n    \
+             status: \"processing\" -> \"shipped\"\n    \
+             tags: CopyList\n\
+             - Product/Widget (Product)\n\
+             1 added, 1 deleted, 1 updated\n"
+        );
+    }
+
+    #[test]
+    fn test_document_actions() {
+        assert_eq!(
+            document_actions(&diffs()),
+            vec![
+                ("Product/Gadget".to_string(), "added"),
+                ("Order/ORD-0002".to_string(), "updated"),
+                ("Product/Widget".to_string(), "deleted"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_from_id() {
+        assert_eq!(type_from_id("terminusdb:///data/Order/ORD-1"), "Order");
+        assert_eq!(type_from_id("Person/alice/home"), "Person");
+        assert_eq!(type_from_id("alice"), "(unknown)");
+    }
+}
@@ -0,0 +1,272 @@
+//! `tdb gql introspect` and `tdb gql exec`: the GraphQL endpoint of a branch.
+//!
+//! `introspect` prints the schema the server generates for the branch as SDL,
+//! or as the introspection result (`{"__schema": ...}`) that code generators
+//! read. `exec` runs a query from a file, with variables from a JSON file, and
+//! prints the response. It exits with an error when the response has GraphQL
+//! errors, after printing them.
+
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use terminusdb_client::graphql::GraphQLPathSegment;
+use terminusdb_client::{GraphQLError, GraphQLRequest, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+
+pub(crate) async fn run_gql_introspect(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    format: String,
+    out: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let output = match format.to_lowercase().as_str() {
+        "json" if output == Output::Table => Output::Json,
+        "json" | "sdl" => output,
+        _ => bail!("unknown format '{}', expected sdl or json", format),
+    };
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+    let introspection = client
+        .introspect_schema(&database, Some(&branch), None)
+        .await?;
+
+    let text = match output.render_data(&introspection)? {
+        Some(text) => text,
+        None => render_sdl(&introspection)?,
+    };
+    match out.as_deref() {
+        None | Some("-") => print!("{}", text),
+        Some(path) => {
+            std::fs::write(path, text)
+                .with_context(|| format!("failed to write schema to {}", path))?;
+            eprintln!(
+                "Wrote the GraphQL schema of {}/{} to {}",
+                database, branch, path
+            );
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run_gql_exec(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    query: String,
+    variables: Option<String>,
+    operation_name: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let request = GraphQLRequest {
+        query: read_source(&query)
+            .with_context(|| format!("failed to read query from {}", query))?,
+        variables: variables.as_deref().map(read_variables).transpose()?,
+        operation_name,
+    };
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+    let response = client
+        .execute_graphql::<Value>(&database, Some(&branch), request, None)
+        .await?;
+    let errors = response.errors.unwrap_or_default();
+
+    if output.is_data() {
+        let mut result = json!({ "data": response.data });
+        if !errors.is_empty() {
+            result["errors"] = serde_json::to_value(&errors)?;
+        }
+        output.print_value(&result)?;
+    } else {
+        if let Some(data) = &response.data {
+            println!("{}", serde_json::to_string_pretty(data)?);
+        }
+        for error in &errors {
+            eprintln!("{}", describe_error(error));
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("the query returned {} GraphQL error(s)", errors.len());
+    }
+    Ok(())
+}
+
+/// The SDL of an introspection result
+fn render_sdl(introspection: &Value) -> Result<String> {
+    let response: cynic_introspection::IntrospectionQuery =
+        serde_json::from_value(introspection.clone())
+            .context("unexpected introspection response")?;
+    Ok(response.into_schema()?.to_sdl())
+}
+
+/// The contents of `path`, or stdin for `-`
+fn read_source(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        return Ok(text);
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Query variables from a JSON file
+fn read_variables(path: &str) -> Result<Value> {
+    let text =
+        read_source(path).with_context(|| format!("failed to read variables from {}", path))?;
+    parse_variables(&text).with_context(|| format!("invalid variables file {}", path))
+}
+
+/// Query variables: a JSON object of variable names to values
+fn parse_variables(text: &str) -> Result<Value> {
+    let variables: Value = serde_json::from_str(text)?;
+    if !variables.is_object() {
+        bail!("expected a JSON object of variable names to values");
+    }
+    Ok(variables)
+}
+
+/// A GraphQL error as `error at <line>:<column> in <path>: <message>`
+fn describe_error(error: &GraphQLError) -> String {
+    let mut prefix = "error".to_string();
+    if let Some(location) = error.locations.as_ref().and_then(|l| l.first()) {
+        prefix += &format!(" at {}:{}", location.line, location.column);
+    }
+    if let Some(path) = error.path.as_ref().filter(|p| !p.is_empty()) {
+        let path: Vec<String> = path
+            .iter()
+            .map(|segment| match segment {
+                GraphQLPathSegment::Field(name) => name.clone(),
+                GraphQLPathSegment::Index(index) => index.to_string(),
+            })
+            .collect();
+        prefix += &format!(" in {}", path.join("."));
+    }
+    format!("{}: {}", prefix, error.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_error() {
+        let error: GraphQLError = serde_json::from_value(json!({
+            "message": "Unknown field \"nmae\" on type \"Person\"",
+            "locations": [{ "line": 3, "column": 5 }],
+            "path": ["Person", 0, "nmae"]
+        }))
+        .unwrap();
+        assert_eq!(
+            describe_error(&error),
+            "error at 3:5 in Person.0.nmae: Unknown field \"nmae\" on type \"Person\""
+        );
+
+        let error: GraphQLError =
+            serde_json::from_value(json!({ "message": "Syntax error" })).unwrap();
+        assert_eq!(describe_error(&error), "error: Syntax error");
+    }
+
+    #[test]
+    fn test_render_sdl() {
+        let introspection = json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": null,
+                "subscriptionType": null,
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "description": null,
+                        "fields": [{
+                            "name": "Person",
+                            "description": null,
+                            "args": [],
+                            "type": {
+                                "kind": "NON_NULL", "name": null,
+                                "ofType": {
+                                    "kind": "LIST", "name": null,
+                                    "ofType": {
+                                        "kind": "NON_NULL", "name": null,
+                                        "ofType": { "kind": "OBJECT", "name": "Person", "ofType": null }
+                                    }
+                                }
+                            },
+                            "isDeprecated": false,
+                            "deprecationReason": null
+                        }],
+                        "inputFields": null,
+                        "interfaces": [],
+                        "enumValues": null,
+                        "possibleTypes": null
+                    },
+                    {
+                        "kind": "OBJECT",
+                        "name": "Person",
+                        "description": null,
+                        "fields": [{
+                            "name": "name",
+                            "description": null,
+                            "args": [],
+                            "type": {
+                                "kind": "NON_NULL", "name": null,
+                                "ofType": { "kind": "SCALAR", "name": "String", "ofType": null }
+                            },
+                            "isDeprecated": false,
+                            "deprecationReason": null
+                        }],
+                        "inputFields": null,
+                        "interfaces": [],
+                        "enumValues": null,
+                        "possibleTypes": null
+                    },
+                    {
+                        "kind": "SCALAR",
+                        "name": "String",
+                        "description": null,
+                        "fields": null,
+                        "inputFields": null,
+                        "interfaces": null,
+                        "enumValues": null,
+                        "possibleTypes": null
+                    }
+                ],
+                "directives": []
+            }
+        });
+        let sdl = render_sdl(&introspection).unwrap();
+        assert!(sdl.contains("type Person {"), "{}", sdl);
+        assert!(sdl.contains("name: String!"), "{}", sdl);
+        assert!(sdl.contains("Person: [Person!]!"), "{}", sdl);
+
+        assert!(render_sdl(&json!({ "__schema": { "types": 1 } })).is_err());
+    }
+
+    #[test]
+    fn test_parse_variables() {
+        assert_eq!(
+            parse_variables(r#"{ "name": "Alice", "limit": 10 }"#).unwrap(),
+            json!({ "name": "Alice", "limit": 10 })
+        );
+        let err = parse_variables("[1, 2]").unwrap_err();
+        assert!(
+            err.to_string().contains("expected a JSON object"),
+            "{}",
+            err
+        );
+        assert!(parse_variables("{ name: 1 }").is_err());
+    }
+}
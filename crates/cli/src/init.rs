@@ -0,0 +1,202 @@
+//! `tdb init`: scaffold a cargo project wired up to TerminusDB.
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::output::Output;
+
+const REPOSITORY: &str = "https://github.com/ParapluOU/terminusdb-rs";
+
+const CARGO_TOML: &str = include_str!("../templates/init/Cargo.toml.tmpl");
+const TOOLCHAIN: &str = include_str!("../templates/init/rust-toolchain.toml.tmpl");
+const GITIGNORE: &str = include_str!("../templates/init/gitignore.tmpl");
+const README: &str = include_str!("../templates/init/README.md.tmpl");
+const LIB_RS: &str = include_str!("../templates/init/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/init/main.rs.tmpl");
+const TEST_RS: &str = include_str!("../templates/init/test.rs.tmpl");
+
+/// Where the generated project pulls the terminusdb crates (and the `rug`
+/// shim it patches in) from.
+#[derive(Debug, Clone, PartialEq)]
+enum DependencySource {
+    /// The upstream git repository
+    Git,
+    /// A local checkout of this workspace
+    Path(PathBuf),
+}
+
+impl DependencySource {
+    fn spec(&self, crate_dir: &str) -> String {
+        match self {
+            DependencySource::Git => format!("{{ git = \"{}\" }}", REPOSITORY),
+            DependencySource::Path(root) => format!(
+                "{{ path = \"{}\" }}",
+                root.join("crates").join(crate_dir).display()
+            ),
+        }
+    }
+}
+
+/// Check `name` is usable as a cargo package name.
+fn validate_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    match chars.next() {
+        None => bail!("project name must not be empty"),
+        Some(c) if !c.is_ascii_alphabetic() => {
+            bail!("project name '{}' must start with a letter", name)
+        }
+        _ => {}
+    }
+    if let Some(c) = chars.find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_')) {
+        bail!("project name '{}' contains invalid character '{}'", name, c);
+    }
+    Ok(())
+}
+
+/// Relative path and rendered contents of every generated file.
+fn render(name: &str, database: &str, source: &DependencySource) -> Vec<(&'static str, String)> {
+    let crate_ident = name.replace('-', "_");
+    let fill = |template: &str| {
+        template
+            .replace("{{name}}", name)
+            .replace("{{crate_ident}}", &crate_ident)
+            .replace("{{database}}", database)
+            .replace("{{terminusdb_dep}}", &source.spec("terminusdb"))
+            .replace("{{schema_dep}}", &source.spec("schema"))
+            .replace("{{derive_dep}}", &source.spec("schema/derive"))
+            .replace("{{test_dep}}", &source.spec("test"))
+            .replace("{{rug_dep}}", &source.spec("rug-shim"))
+    };
+
+    vec![
+        ("Cargo.toml", fill(CARGO_TOML)),
+        ("rust-toolchain.toml", fill(TOOLCHAIN)),
+        (".gitignore", fill(GITIGNORE)),
+        ("README.md", fill(README)),
+        ("src/lib.rs", fill(LIB_RS)),
+        ("src/main.rs", fill(MAIN_RS)),
+        ("tests/person.rs", fill(TEST_RS)),
+    ]
+}
+
+fn write_project(dir: &Path, files: &[(&str, String)]) -> Result<()> {
+    for (relative, contents) in files {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        fs::write(&path, contents).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn run_init(
+    name: String,
+    path: Option<PathBuf>,
+    database: Option<String>,
+    terminusdb_path: Option<PathBuf>,
+    output: Output,
+) -> Result<()> {
+    validate_name(&name)?;
+
+    let dir = path.unwrap_or_else(|| PathBuf::from(&name));
+    if dir.exists() && fs::read_dir(&dir)?.next().is_some() {
+        bail!(
+            "destination '{}' already exists and is not empty",
+            dir.display()
+        );
+    }
+
+    let source = match terminusdb_path {
+        Some(root) => DependencySource::Path(
+            fs::canonicalize(&root)
+                .with_context(|| format!("resolving --terminusdb-path {}", root.display()))?,
+        ),
+        None => DependencySource::Git,
+    };
+    let database = database.unwrap_or_else(|| name.replace('-', "_"));
+
+    write_project(&dir, &render(&name, &database, &source))?;
+
+    let summary = json!({ "name": name, "path": dir, "database": database });
+    if let Some(text) = output.render_data(&summary)? {
+        print!("{}", text);
+        return Ok(());
+    }
+    println!("Created TerminusDB project '{}' in {}", name, dir.display());
+    println!();
+    println!("  cd {}", dir.display());
+    println!("  cargo test   # runs against an embedded server");
+    println!(
+        "  cargo run    # boots database '{}' via TERMINUSDB_* settings",
+        database
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("my-app").is_ok());
+        assert!(validate_name("my_app2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("2app").is_err());
+        assert!(validate_name("my app").is_err());
+    }
+
+    #[test]
+    fn test_render_fills_every_placeholder() {
+        let files = render(
+            "my-app",
+            "my_db",
+            &DependencySource::Path(PathBuf::from("/src/terminusdb-rs")),
+        );
+
+        for (file, contents) in &files {
+            assert!(!contents.contains("{{"), "unfilled placeholder in {}", file);
+        }
+
+        let cargo = &files.iter().find(|(f, _)| *f == "Cargo.toml").unwrap().1;
+        assert!(cargo.contains("name = \"my-app\""));
+        assert!(cargo.contains(
+            "terminusdb-schema-derive = { path = \"/src/terminusdb-rs/crates/schema/derive\" }"
+        ));
+
+        let main = &files.iter().find(|(f, _)| *f == "src/main.rs").unwrap().1;
+        assert!(main.contains("use my_app::{boot, DEFAULT_DATABASE};"));
+
+        let lib = &files.iter().find(|(f, _)| *f == "src/lib.rs").unwrap().1;
+        assert!(lib.contains("pub const DEFAULT_DATABASE: &str = \"my_db\";"));
+    }
+
+    #[test]
+    fn test_cargo_toml_patches_match_workspace() {
+        let workspace = include_str!("../../../Cargo.toml");
+        let patches = CARGO_TOML
+            .split_once("[patch.crates-io]")
+            .expect("template patches crates-io")
+            .1;
+        for line in patches.lines().filter(|line| !line.trim().is_empty()) {
+            if !line.starts_with("rug = ") {
+                assert!(workspace.contains(line), "patch not in workspace: {}", line);
+            }
+        }
+
+        let files = render("my-app", "my_db", &DependencySource::Git);
+        let cargo = &files.iter().find(|(f, _)| *f == "Cargo.toml").unwrap().1;
+        assert!(cargo.contains(&format!("rug = {{ git = \"{}\" }}", REPOSITORY)));
+    }
+
+    #[test]
+    fn test_git_dependency_spec() {
+        assert_eq!(
+            DependencySource::Git.spec("terminusdb"),
+            format!("{{ git = \"{}\" }}", REPOSITORY)
+        );
+    }
+}
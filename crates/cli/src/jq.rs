@@ -0,0 +1,127 @@
+//! jq expressions over JSON values (`--jq`), evaluated with jaq.
+
+use anyhow::{anyhow, Result};
+use jaq_core::load::{self, Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, Filter, Native, RcIter};
+use jaq_json::Val;
+use serde_json::Value;
+
+/// A compiled jq expression
+pub(crate) struct Jq {
+    filter: Filter<Native<Val>>,
+}
+
+/// Where in the expression an error was found, from the text left after it
+fn position(rest: &str) -> String {
+    if rest.is_empty() {
+        "at the end".to_string()
+    } else {
+        format!("at '{}'", rest)
+    }
+}
+
+impl Jq {
+    /// Compile `expr`, with the jq standard library available to it.
+    pub(crate) fn compile(expr: &str) -> Result<Self> {
+        let program = File {
+            code: expr,
+            path: (),
+        };
+        let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+        let arena = Arena::default();
+        let modules = loader.load(&arena, program).map_err(|errs| {
+            let messages: Vec<String> = errs
+                .into_iter()
+                .flat_map(|(_, err)| match err {
+                    load::Error::Io(errs) => errs
+                        .into_iter()
+                        .map(|(path, err)| format!("{}: {}", path, err))
+                        .collect::<Vec<_>>(),
+                    load::Error::Lex(errs) => errs
+                        .into_iter()
+                        .map(|(expect, rest)| {
+                            format!("expected {} {}", expect.as_str(), position(rest))
+                        })
+                        .collect(),
+                    load::Error::Parse(errs) => errs
+                        .into_iter()
+                        .map(|(expect, rest)| {
+                            format!("expected {} {}", expect.as_str(), position(rest))
+                        })
+                        .collect(),
+                })
+                .collect();
+            anyhow!("invalid jq expression '{}': {}", expr, messages.join("; "))
+        })?;
+
+        let filter = Compiler::default()
+            .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+            .compile(modules)
+            .map_err(|errs| {
+                let messages: Vec<String> = errs
+                    .into_iter()
+                    .flat_map(|(_, errs)| errs)
+                    .map(|(name, undefined)| format!("undefined {} '{}'", undefined.as_str(), name))
+                    .collect();
+                anyhow!("invalid jq expression '{}': {}", expr, messages.join("; "))
+            })?;
+
+        Ok(Jq { filter })
+    }
+
+    /// The outputs of the expression for `input`, in order.
+    pub(crate) fn run(&self, input: Value) -> Result<Vec<Value>> {
+        let inputs = RcIter::new(core::iter::empty());
+        self.filter
+            .run((Ctx::new([], &inputs), Val::from(input)))
+            .map(|output| {
+                output
+                    .map(Value::from)
+                    .map_err(|err| anyhow!("jq: {}", err))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_run() {
+        let jq = Jq::compile(
+            "{commit: .commit.id, ids: [.changes[] | select(.action == \"added\") | .id]}",
+        )
+        .unwrap();
+        let event = json!({
+            "commit": { "id": "abc" },
+            "changes": [
+                { "id": "Person/alice", "action": "added" },
+                { "id": "Person/bob", "action": "deleted" }
+            ]
+        });
+        assert_eq!(
+            jq.run(event.clone()).unwrap(),
+            vec![json!({ "commit": "abc", "ids": ["Person/alice"] })]
+        );
+
+        let jq = Jq::compile(".changes[].id").unwrap();
+        assert_eq!(
+            jq.run(event).unwrap(),
+            vec![json!("Person/alice"), json!("Person/bob")]
+        );
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        let err = Jq::compile(".changes[").err().unwrap().to_string();
+        assert!(
+            err.starts_with("invalid jq expression '.changes['"),
+            "{}",
+            err
+        );
+        let err = Jq::compile("nosuchfn(1)").err().unwrap().to_string();
+        assert!(err.contains("undefined filter 'nosuchfn'"), "{}", err);
+    }
+}
@@ -0,0 +1,154 @@
+//! Listing commands for shell completion and pickers: `tdb classes` and `tdb ids`.
+//!
+//! Output is one name per line, sorted, with nothing else on stdout, so it can
+//! be piped straight into `fzf`, `grep` or a completion function. With
+//! `--output json|yaml` the names are printed as a list instead.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use terminusdb_client::{BranchSpec, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+
+pub(crate) async fn run_classes(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    prefix: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let docs = client.get_schema_documents(&spec).await?;
+
+    print_names(class_names(&docs), prefix.as_deref(), output)
+}
+
+pub(crate) async fn run_ids(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    class: String,
+    prefix: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    // Only the ids are fetched, not the documents
+    let query = json!({
+        "@type": "Using",
+        "collection": format!("{}/{}/local/branch/{}", org, database, branch),
+        "query": {
+            "@type": "Triple",
+            "subject": { "@type": "NodeValue", "variable": "id" },
+            "predicate": { "@type": "NodeValue", "node": "rdf:type" },
+            "object": { "@type": "Value", "node": format!("@schema:{}", class) },
+            "graph": "instance"
+        }
+    });
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let res = client.query_raw::<Value>(Some(spec), query, None).await?;
+
+    let ids = res
+        .bindings
+        .iter()
+        .filter_map(|binding| binding.get("id")?.as_str())
+        .map(str::to_string)
+        .collect();
+
+    print_names(ids, id_prefix(&class, prefix).as_deref(), output)
+}
+
+/// Ids are `Class/key`; a prefix may be given with or without the class
+fn id_prefix(class: &str, prefix: Option<String>) -> Option<String> {
+    let class_prefix = format!("{}/", class);
+    prefix.map(|prefix| {
+        if prefix.starts_with(&class_prefix) {
+            prefix
+        } else {
+            class_prefix + &prefix
+        }
+    })
+}
+
+/// `@id` of every class in the schema documents, skipping the `@context`
+fn class_names(docs: &[Value]) -> Vec<String> {
+    docs.iter()
+        .filter(|doc| doc.get("@type").and_then(Value::as_str) != Some("@context"))
+        .filter_map(|doc| doc.get("@id")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Sorted, deduplicated names starting with `prefix`
+fn filter_names(mut names: Vec<String>, prefix: Option<&str>) -> Vec<String> {
+    if let Some(prefix) = prefix {
+        names.retain(|name| name.starts_with(prefix));
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn print_names(names: Vec<String>, prefix: Option<&str>, output: Output) -> Result<()> {
+    let names = filter_names(names, prefix);
+    output.print(&names, || {
+        names.iter().map(|name| format!("{}\n", name)).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_names_skip_context() {
+        let docs = vec![
+            json!({ "@type": "@context", "@base": "terminusdb:///data/" }),
+            json!({ "@type": "Class", "@id": "Person", "name": "xsd:string" }),
+            json!({ "@type": "Enum", "@id": "Color", "@value": ["red"] }),
+        ];
+
+        assert_eq!(class_names(&docs), vec!["Person", "Color"]);
+    }
+
+    #[test]
+    fn test_filter_names() {
+        let names = vec![
+            "Person/bob".to_string(),
+            "Person/alice".to_string(),
+            "Pet/rex".to_string(),
+            "Person/alice".to_string(),
+        ];
+
+        assert_eq!(
+            filter_names(names.clone(), Some("Person/")),
+            vec!["Person/alice", "Person/bob"]
+        );
+        assert_eq!(filter_names(names, None).len(), 3);
+    }
+
+    #[test]
+    fn test_id_prefix_adds_class() {
+        assert_eq!(
+            id_prefix("Person", Some("al".to_string())).as_deref(),
+            Some("Person/al")
+        );
+        assert_eq!(
+            id_prefix("Person", Some("Person/al".to_string())).as_deref(),
+            Some("Person/al")
+        );
+        assert_eq!(id_prefix("Person", None), None);
+    }
+}
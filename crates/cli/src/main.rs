@@ -1,32 +1,86 @@
 mod auth;
+mod branch;
 mod changestream;
 mod cli;
+mod completions;
+mod confirm;
 mod database;
+mod diff;
 mod formatter;
+mod gql;
+mod init;
+mod jq;
+mod listing;
+mod migrate;
+mod output;
 mod profile_cmds;
+mod query;
+mod query_profile;
 mod remote;
+mod repl;
+mod schema;
+mod schema_dsl;
+#[cfg(feature = "serve")]
+mod serve;
+mod transfer;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
+use std::ffi::OsString;
 
-use changestream::run_changestream;
-use cli::{Cli, Commands, DatabaseCommands, ProfileCommands, RemoteCommands};
+use branch::*;
+use changestream::run_watch;
+use cli::{
+    BranchCommands, Cli, Commands, DatabaseCommands, GqlCommands, MigrateCommands, ProfileCommands,
+    RemoteCommands, SchemaCommands,
+};
+use completions::{run_completions, run_man};
 use database::*;
+use diff::run_diff;
+use gql::{run_gql_exec, run_gql_introspect};
+use init::run_init;
+use listing::{run_classes, run_ids};
+use migrate::{run_migrate_down, run_migrate_status, run_migrate_up};
+use output::Output;
 use profile_cmds::*;
+use query::run_query;
+use query_profile::run_query_profile;
 use remote::*;
+use repl::run_repl;
+use schema::{run_schema_apply, run_schema_dump};
+#[cfg(feature = "serve")]
+use serve::run_serve;
+use transfer::{run_export, run_import};
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize tracing (logs to stderr, keeping stdout clean for data)
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
+async fn main() {
+    // Initialize tracing (logs to stderr, keeping stdout clean for data).
+    // TERMINUSDB_LOG / RUST_LOG adjust levels per subsystem.
+    terminusdb_log::LogConfig::new()
+        .level("tdb", tracing::Level::INFO)
         .with_target(false)
         .init();
 
-    let cli = Cli::parse();
+    // The profile's host, org, database etc. stand in for flags and
+    // variables that are not given.
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let defaults = auth::profile_defaults(cli::profile_arg(&args).as_deref())
+        .unwrap_or_else(|err| Output::default().fail(err));
+    let matches = cli::with_defaults(Cli::command(), &defaults).get_matches_from(args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    let output = cli.output;
+    if let Err(err) = run(cli).await {
+        output.fail(err);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let profile = cli.profile;
+    let output = cli.output;
 
     match cli.command {
-        Commands::Changestream {
+        Commands::Watch {
             host,
             user,
             password,
@@ -35,7 +89,17 @@ async fn main() -> Result<()> {
             branch,
             format,
             color,
-        } => run_changestream(host, user, password, org, database, branch, format, color).await,
+            types,
+            actions,
+            since,
+            jq,
+        } => {
+            run_watch(
+                host, user, password, org, database, branch, format, color, types, actions, since,
+                jq, output,
+            )
+            .await
+        }
         Commands::Remote { command } => match command {
             RemoteCommands::Add {
                 host,
@@ -45,14 +109,14 @@ async fn main() -> Result<()> {
                 database,
                 name,
                 url,
-            } => run_remote_add(host, user, password, org, database, name, url).await,
+            } => run_remote_add(host, user, password, org, database, name, url, output).await,
             RemoteCommands::List {
                 host,
                 user,
                 password,
                 org,
                 database,
-            } => run_remote_list(host, user, password, org, database).await,
+            } => run_remote_list(host, user, password, org, database, output).await,
             RemoteCommands::Get {
                 host,
                 user,
@@ -60,7 +124,7 @@ async fn main() -> Result<()> {
                 org,
                 database,
                 name,
-            } => run_remote_get(host, user, password, org, database, name).await,
+            } => run_remote_get(host, user, password, org, database, name, output).await,
             RemoteCommands::Update {
                 host,
                 user,
@@ -69,7 +133,7 @@ async fn main() -> Result<()> {
                 database,
                 name,
                 url,
-            } => run_remote_update(host, user, password, org, database, name, url).await,
+            } => run_remote_update(host, user, password, org, database, name, url, output).await,
             RemoteCommands::Delete {
                 host,
                 user,
@@ -77,7 +141,7 @@ async fn main() -> Result<()> {
                 org,
                 database,
                 name,
-            } => run_remote_delete(host, user, password, org, database, name).await,
+            } => run_remote_delete(host, user, password, org, database, name, output).await,
         },
         Commands::Clone {
             host,
@@ -100,6 +164,7 @@ async fn main() -> Result<()> {
                 label,
                 comment,
                 remote_auth,
+                output,
             )
             .await
         }
@@ -124,6 +189,7 @@ async fn main() -> Result<()> {
                 remote_url,
                 remote_branch,
                 remote_auth,
+                output,
             )
             .await
         }
@@ -152,6 +218,7 @@ async fn main() -> Result<()> {
                 author,
                 message,
                 remote_auth,
+                output,
             )
             .await
         }
@@ -176,6 +243,7 @@ async fn main() -> Result<()> {
                 remote_url,
                 remote_branch,
                 remote_auth,
+                output,
             )
             .await
         }
@@ -187,7 +255,7 @@ async fn main() -> Result<()> {
             database,
             branch,
             meta,
-        } => run_optimize(host, user, password, org, database, branch, meta).await,
+        } => run_optimize(host, user, password, org, database, branch, meta, output).await,
         Commands::Squash {
             host,
             user,
@@ -197,7 +265,12 @@ async fn main() -> Result<()> {
             branch,
             author,
             message,
-        } => run_squash(host, user, password, org, database, branch, author, message).await,
+        } => {
+            run_squash(
+                host, user, password, org, database, branch, author, message, output,
+            )
+            .await
+        }
         Commands::SquashAndReset {
             host,
             user,
@@ -207,8 +280,12 @@ async fn main() -> Result<()> {
             branch,
             author,
             message,
+            confirm,
         } => {
-            run_squash_and_reset(host, user, password, org, database, branch, author, message).await
+            run_squash_and_reset(
+                host, user, password, org, database, branch, author, message, confirm, output,
+            )
+            .await
         }
         Commands::Deploy {
             source_host,
@@ -241,6 +318,70 @@ async fn main() -> Result<()> {
                 target_label,
                 target_comment,
                 skip_create,
+                output,
+            )
+            .await
+        }
+        Commands::Classes {
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            prefix,
+        } => run_classes(host, user, password, org, database, branch, prefix, output).await,
+        Commands::Ids {
+            class,
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            prefix,
+        } => {
+            run_ids(
+                host, user, password, org, database, branch, class, prefix, output,
+            )
+            .await
+        }
+        Commands::Query {
+            query,
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            format,
+        } => {
+            run_query(
+                host, user, password, org, database, branch, query, format, output,
+            )
+            .await
+        }
+        Commands::QueryProfile {
+            query,
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+        } => run_query_profile(host, user, password, org, database, branch, query, output).await,
+        Commands::Repl {
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            format,
+            page_size,
+        } => {
+            run_repl(
+                host, user, password, org, database, branch, format, page_size,
             )
             .await
         }
@@ -255,8 +396,10 @@ async fn main() -> Result<()> {
                 comment,
                 schema,
             } => {
-                run_database_create(host, user, password, org, database, label, comment, schema)
-                    .await
+                run_database_create(
+                    host, user, password, org, database, label, comment, schema, output,
+                )
+                .await
             }
             DatabaseCommands::Info {
                 host,
@@ -264,21 +407,21 @@ async fn main() -> Result<()> {
                 password,
                 org,
                 database,
-            } => run_database_info(host, user, password, org, database).await,
+            } => run_database_info(host, user, password, org, database, output).await,
             DatabaseCommands::List {
                 host,
                 user,
                 password,
                 org,
-            } => run_database_list(host, user, password, org).await,
+            } => run_database_list(host, user, password, org, output).await,
             DatabaseCommands::Delete {
                 host,
                 user,
                 password,
                 org,
                 database,
-                force,
-            } => run_database_delete(host, user, password, org, database, force).await,
+                confirm,
+            } => run_database_delete(host, user, password, org, database, confirm, output).await,
             DatabaseCommands::Log {
                 host,
                 user,
@@ -286,15 +429,347 @@ async fn main() -> Result<()> {
                 org,
                 database,
                 limit,
-            } => run_database_log(host, user, password, org, database, limit).await,
+            } => run_database_log(host, user, password, org, database, limit, output).await,
+            DatabaseCommands::Stats {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+            } => run_database_stats(host, user, password, org, database, branch, output).await,
+        },
+        Commands::Diff {
+            ref_a,
+            ref_b,
+            host,
+            user,
+            password,
+            org,
+            database,
+            format,
+        } => {
+            run_diff(
+                host, user, password, org, database, ref_a, ref_b, format, output,
+            )
+            .await
+        }
+        Commands::Branch { command } => match command {
+            BranchCommands::Create {
+                name,
+                from,
+                host,
+                user,
+                password,
+                org,
+                database,
+                json,
+            } => {
+                run_branch_create(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    name,
+                    from,
+                    output.or_json(json),
+                )
+                .await
+            }
+            BranchCommands::List {
+                host,
+                user,
+                password,
+                org,
+                database,
+                json,
+            } => run_branch_list(host, user, password, org, database, output.or_json(json)).await,
+            BranchCommands::Delete {
+                name,
+                host,
+                user,
+                password,
+                org,
+                database,
+                confirm,
+                json,
+            } => {
+                run_branch_delete(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    name,
+                    confirm,
+                    output.or_json(json),
+                )
+                .await
+            }
+            BranchCommands::Reset {
+                name,
+                to,
+                host,
+                user,
+                password,
+                org,
+                database,
+                confirm,
+                json,
+            } => {
+                run_branch_reset(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    name,
+                    to,
+                    confirm,
+                    output.or_json(json),
+                )
+                .await
+            }
+            BranchCommands::Switch {
+                name,
+                host,
+                user,
+                password,
+                org,
+                database,
+                json,
+            } => {
+                run_branch_switch(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    name,
+                    profile,
+                    output.or_json(json),
+                )
+                .await
+            }
+            BranchCommands::Merge {
+                from,
+                to,
+                host,
+                user,
+                password,
+                org,
+                database,
+                author,
+                message,
+                json,
+            } => {
+                run_branch_merge(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    from,
+                    to,
+                    author,
+                    message,
+                    output.or_json(json),
+                )
+                .await
+            }
+        },
+        Commands::Schema { command } => match command {
+            SchemaCommands::Dump {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                format,
+            } => run_schema_dump(host, user, password, org, database, branch, format, output).await,
+            SchemaCommands::Apply {
+                file,
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                dry_run,
+                author,
+                message,
+            } => {
+                run_schema_apply(
+                    host, user, password, org, database, branch, file, dry_run, author, message,
+                    output,
+                )
+                .await
+            }
         },
+        Commands::Gql { command } => match command {
+            GqlCommands::Introspect {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                format,
+                out,
+            } => {
+                run_gql_introspect(
+                    host, user, password, org, database, branch, format, out, output,
+                )
+                .await
+            }
+            GqlCommands::Exec {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                query,
+                variables,
+                operation_name,
+            } => {
+                run_gql_exec(
+                    host,
+                    user,
+                    password,
+                    org,
+                    database,
+                    branch,
+                    query,
+                    variables,
+                    operation_name,
+                    output,
+                )
+                .await
+            }
+        },
+        Commands::Migrate { command } => match command {
+            MigrateCommands::Up {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                dir,
+                to,
+                dry_run,
+                author,
+            } => {
+                run_migrate_up(
+                    host, user, password, org, database, branch, dir, to, dry_run, author, output,
+                )
+                .await
+            }
+            MigrateCommands::Down {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                dir,
+                to,
+                author,
+                confirm,
+            } => {
+                run_migrate_down(
+                    host, user, password, org, database, branch, dir, to, author, confirm, output,
+                )
+                .await
+            }
+            MigrateCommands::Status {
+                host,
+                user,
+                password,
+                org,
+                database,
+                branch,
+                dir,
+            } => run_migrate_status(host, user, password, org, database, branch, dir, output).await,
+        },
+        Commands::Export {
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            ty,
+            format,
+            out,
+            page_size,
+        } => {
+            run_export(
+                host, user, password, org, database, branch, ty, format, out, page_size, output,
+            )
+            .await
+        }
+        Commands::Import {
+            file,
+            host,
+            user,
+            password,
+            org,
+            database,
+            branch,
+            ty,
+            format,
+            map,
+            batch_size,
+            author,
+            message,
+        } => {
+            run_import(
+                host, user, password, org, database, branch, file, ty, format, map, batch_size,
+                author, message, output,
+            )
+            .await
+        }
+        #[cfg(feature = "serve")]
+        Commands::Serve {
+            port,
+            data_dir,
+            memory,
+            password,
+            log_level,
+            log_format,
+            quiet,
+        } => {
+            run_serve(
+                port, data_dir, memory, password, log_level, log_format, quiet, output,
+            )
+            .await
+        }
+        Commands::Init {
+            name,
+            path,
+            database,
+            terminusdb_path,
+        } => run_init(name, path, database, terminusdb_path, output).await,
         Commands::Login { profile } => run_login(&profile).await,
-        Commands::Logout { profile } => run_logout(profile.as_deref()).await,
+        Commands::Logout { profile } => run_logout(profile.as_deref(), output).await,
         Commands::Profile { command } => match command {
-            ProfileCommands::List => run_profile_list().await,
-            ProfileCommands::Set { name } => run_profile_set(&name).await,
-            ProfileCommands::Show { name } => run_profile_show(name.as_deref()).await,
-            ProfileCommands::Delete { name, force } => run_profile_delete(&name, force).await,
+            ProfileCommands::List => run_profile_list(output).await,
+            ProfileCommands::Set { name } => run_profile_set(&name, output).await,
+            ProfileCommands::Show { name } => run_profile_show(name.as_deref(), output).await,
+            ProfileCommands::Delete { name, force } => {
+                run_profile_delete(&name, force, output).await
+            }
+            ProfileCommands::SetDefaultDb {
+                database,
+                branch,
+                org,
+            } => run_profile_set_default_db(profile, database, branch, org, output).await,
         },
+        Commands::Completions { shell } => run_completions(shell).await,
+        Commands::Man { out_dir } => run_man(out_dir).await,
     }
 }
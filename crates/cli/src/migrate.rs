@@ -0,0 +1,839 @@
+//! `tdb migrate up`, `down` and `status`: versioned migrations from a directory.
+//!
+//! A migration is a pair of files named `<version>_<name>.up.<kind>` and
+//! (optionally) `<version>_<name>.down.<kind>`, where the version is a number
+//! and the kind is `schema` (schema documents, as JSON or DSL, written like
+//! `tdb schema apply`) or `woql` (one WOQL query, as DSL or JSON-LD). Files not
+//! starting with a digit are ignored.
+//!
+//! Each applied migration is recorded on the branch as a `TdbMigration`
+//! document, whose class is added to the schema on the first run. A run is all
+//! or nothing: the migrations are applied on a scratch branch, one commit each,
+//! and the branch is only moved to the result when all of them succeeded and
+//! nothing else was committed to it in the meantime.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use terminusdb_client::{
+    BranchSpec, DeleteOpts, DocumentInsertArgs, GetOpts, TerminusDBHttpClient,
+};
+use url::Url;
+
+use crate::confirm::{Confirm, DryRun, Preview};
+use crate::output::{Output, StatusError};
+use crate::schema::{apply_schema, parse_schema_file};
+
+/// Class of the documents recording applied migrations
+const TRACKING_CLASS: &str = "TdbMigration";
+
+/// Whether a script applies or reverts its migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+}
+
+/// What a migration script holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptKind {
+    Schema,
+    Woql,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Script {
+    path: PathBuf,
+    kind: ScriptKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Migration {
+    /// The version as written in the file name, e.g. `0001`
+    version: String,
+    number: u64,
+    name: String,
+    up: Script,
+    down: Option<Script>,
+}
+
+impl Migration {
+    /// `0001_add_people`
+    fn id(&self) -> String {
+        format!("{}_{}", self.version, self.name)
+    }
+}
+
+/// A `TdbMigration` document
+#[derive(Debug, Clone, PartialEq)]
+struct Applied {
+    version: String,
+    name: String,
+    applied_at: String,
+}
+
+/// The parts of a migration file name, or `None` for files that are not
+/// migrations (not starting with a digit)
+fn parse_file_name(file_name: &str) -> Result<Option<(String, String, Direction, ScriptKind)>> {
+    if !file_name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+    let invalid = || {
+        anyhow!(
+            "invalid migration file name '{}', expected <version>_<name>.<up|down>.<schema|woql>",
+            file_name
+        )
+    };
+    let mut parts = file_name.split('.');
+    let (Some(stem), Some(direction), Some(kind), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let (version, name) = stem.split_once('_').ok_or_else(invalid)?;
+    if name.is_empty() || !version.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let direction = match direction {
+        "up" => Direction::Up,
+        "down" => Direction::Down,
+        _ => return Err(invalid()),
+    };
+    let kind = match kind {
+        "schema" => ScriptKind::Schema,
+        "woql" => ScriptKind::Woql,
+        _ => return Err(invalid()),
+    };
+    Ok(Some((
+        version.to_string(),
+        name.to_string(),
+        direction,
+        kind,
+    )))
+}
+
+/// The migrations in `dir`, oldest first
+fn load_migrations(dir: &Path) -> Result<Vec<Migration>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read migrations from {}", dir.display()))?;
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    migrations_from_names(dir, names)
+}
+
+/// Pair up the `up` and `down` files among `names`, oldest version first
+fn migrations_from_names(
+    dir: &Path,
+    names: impl IntoIterator<Item = String>,
+) -> Result<Vec<Migration>> {
+    let mut ups: BTreeMap<(u64, String), (String, Script)> = BTreeMap::new();
+    let mut downs: BTreeMap<(u64, String), (String, Script)> = BTreeMap::new();
+    for file_name in names {
+        let Some((version, name, direction, kind)) = parse_file_name(&file_name)? else {
+            continue;
+        };
+        let number = version
+            .parse()
+            .with_context(|| format!("version of {} is too large", file_name))?;
+        let script = Script {
+            path: dir.join(&file_name),
+            kind,
+        };
+        let scripts = match direction {
+            Direction::Up => &mut ups,
+            Direction::Down => &mut downs,
+        };
+        if let Some((_, other)) = scripts.insert((number, version.clone()), (name, script)) {
+            bail!(
+                "{} and {} are both migration {}",
+                other.path.display(),
+                file_name,
+                version
+            );
+        }
+    }
+
+    if let Some(((_, version), (_, script))) = downs.iter().find(|(key, _)| !ups.contains_key(key))
+    {
+        bail!(
+            "{} has no up file for migration {}",
+            script.path.display(),
+            version
+        );
+    }
+
+    let mut migrations: Vec<Migration> = Vec::new();
+    for ((number, version), (name, up)) in ups {
+        let down = match downs.remove(&(number, version.clone())) {
+            Some((down_name, _)) if down_name != name => {
+                bail!(
+                    "the up and down files of migration {} have different names: {} and {}",
+                    version,
+                    name,
+                    down_name
+                )
+            }
+            Some((_, down)) => Some(down),
+            None => None,
+        };
+        if let Some(previous) = migrations.last() {
+            if previous.number == number {
+                bail!(
+                    "migrations {} and {} have the same version",
+                    previous.id(),
+                    version
+                );
+            }
+        }
+        migrations.push(Migration {
+            version,
+            number,
+            name,
+            up,
+            down,
+        });
+    }
+    Ok(migrations)
+}
+
+/// Migrations that have not been applied, up to `to` (a version) if given
+fn pending<'a>(
+    migrations: &'a [Migration],
+    applied: &[Applied],
+    to: Option<&str>,
+) -> Result<Vec<&'a Migration>> {
+    let last = match to {
+        Some(to) => find(migrations, to)?.number,
+        None => u64::MAX,
+    };
+    Ok(migrations
+        .iter()
+        .filter(|m| m.number <= last)
+        .filter(|m| !applied.iter().any(|a| a.version == m.version))
+        .collect())
+}
+
+/// Applied migrations to revert, newest first: those after `to` (a version),
+/// or only the newest one
+fn to_revert<'a>(
+    migrations: &'a [Migration],
+    applied: &[Applied],
+    to: Option<&str>,
+) -> Result<Vec<&'a Migration>> {
+    let mut reverted: Vec<&Migration> = Vec::new();
+    for record in applied {
+        let migration = migrations
+            .iter()
+            .find(|m| m.version == record.version)
+            .ok_or_else(|| {
+                anyhow!(
+                    "migration {}_{} was applied, but its files are missing",
+                    record.version,
+                    record.name
+                )
+            })?;
+        reverted.push(migration);
+    }
+    reverted.sort_by_key(|m| std::cmp::Reverse(m.number));
+
+    match to {
+        Some(to) => {
+            let last_kept = if to.chars().all(|c| c == '0') {
+                0
+            } else {
+                find(migrations, to)?.number
+            };
+            reverted.retain(|m| m.number > last_kept);
+        }
+        None => reverted.truncate(1),
+    }
+
+    if let Some(migration) = reverted.iter().find(|m| m.down.is_none()) {
+        bail!(
+            "migration {} has no down file, so it cannot be reverted",
+            migration.id()
+        );
+    }
+    Ok(reverted)
+}
+
+fn find<'a>(migrations: &'a [Migration], version: &str) -> Result<&'a Migration> {
+    let number: u64 = version
+        .parse()
+        .with_context(|| format!("'{}' is not a migration version", version))?;
+    migrations
+        .iter()
+        .find(|m| m.number == number)
+        .ok_or_else(|| anyhow!("there is no migration {}", version))
+}
+
+/// One line of `tdb migrate status`
+fn status_rows(migrations: &[Migration], applied: &[Applied]) -> Vec<Value> {
+    let mut rows: Vec<(u64, Value)> = migrations
+        .iter()
+        .map(|m| {
+            let record = applied.iter().find(|a| a.version == m.version);
+            let row = json!({
+                "version": m.version,
+                "name": m.name,
+                "state": if record.is_some() { "applied" } else { "pending" },
+                "applied_at": record.map(|a| a.applied_at.clone()),
+            });
+            (m.number, row)
+        })
+        .collect();
+    for record in applied {
+        if !migrations.iter().any(|m| m.version == record.version) {
+            let row = json!({
+                "version": record.version,
+                "name": record.name,
+                "state": "missing",
+                "applied_at": record.applied_at,
+            });
+            rows.push((record.version.parse().unwrap_or(u64::MAX), row));
+        }
+    }
+    rows.sort_by_key(|(number, _)| *number);
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+fn render_status(rows: &[Value]) -> String {
+    let field = |row: &Value, key: &str| row[key].as_str().unwrap_or_default().to_string();
+    let ids: Vec<String> = rows
+        .iter()
+        .map(|row| format!("{}_{}", field(row, "version"), field(row, "name")))
+        .collect();
+    let width = ids.iter().map(String::len).max().unwrap_or(0);
+    ids.iter()
+        .zip(rows)
+        .map(|(id, row)| {
+            let line = format!(
+                "{:<width$}  {:<7}  {}",
+                id,
+                field(row, "state"),
+                field(row, "applied_at"),
+                width = width
+            );
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// The schema of the tracking documents
+fn tracking_class() -> Value {
+    json!({
+        "@type": "Class",
+        "@id": TRACKING_CLASS,
+        "@key": { "@type": "Lexical", "@fields": ["version"] },
+        "version": "xsd:string",
+        "name": "xsd:string",
+        "applied_at": "xsd:dateTime"
+    })
+}
+
+/// A WOQL query as JSON-LD, from JSON-LD or the WOQL DSL
+fn woql_json(text: &str) -> Result<Value> {
+    match serde_json::from_str::<Value>(text) {
+        Ok(json_ld) => Ok(json_ld),
+        Err(_) => Ok(terminusdb_woql_dsl::parse_woql_dsl(text)
+            .map_err(|e| anyhow!("invalid WOQL DSL: {}", e))?
+            .to_woql_json()),
+    }
+}
+
+/// A database to migrate, and who the commits are by
+struct Migrator {
+    client: TerminusDBHttpClient,
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    author: String,
+}
+
+impl Migrator {
+    async fn new(
+        host: String,
+        user: String,
+        password: String,
+        org: String,
+        database: String,
+        author: String,
+    ) -> Result<Self> {
+        let parsed_url = Url::parse(&host)?;
+        let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+        Ok(Migrator {
+            client,
+            host,
+            user,
+            password,
+            org,
+            database,
+            author,
+        })
+    }
+
+    fn spec(&self, branch: &str) -> BranchSpec {
+        BranchSpec::with_branch(&self.database, branch)
+    }
+
+    fn branch_path(&self, branch: &str) -> String {
+        format!("{}/{}/local/branch/{}", self.org, self.database, branch)
+    }
+
+    /// The migrations recorded on `branch`
+    async fn applied(&self, branch: &str) -> Result<Vec<Applied>> {
+        let spec = self.spec(branch);
+        let schema = self.client.get_schema_documents(&spec).await?;
+        if !schema
+            .iter()
+            .any(|doc| doc.get("@id").and_then(Value::as_str) == Some(TRACKING_CLASS))
+        {
+            return Ok(Vec::new());
+        }
+        let opts = GetOpts::default().with_type_filter_string(TRACKING_CLASS);
+        let docs = self.client.get_documents(Vec::new(), &spec, opts).await?;
+        Ok(docs
+            .iter()
+            .map(|doc| {
+                let field = |key: &str| doc[key].as_str().unwrap_or_default().to_string();
+                Applied {
+                    version: field("version"),
+                    name: field("name"),
+                    applied_at: field("applied_at"),
+                }
+            })
+            .collect())
+    }
+
+    /// Run `migrations` in `direction` on `branch` as a whole: on a scratch
+    /// branch first, then moving `branch` to the result
+    async fn run(
+        &self,
+        branch: &str,
+        migrations: &[&Migration],
+        direction: Direction,
+    ) -> Result<()> {
+        let head = self.client.get_latest_commit_id(&self.spec(branch)).await?;
+        let scratch = format!("tdb_migrate_{}", chrono::Utc::now().timestamp_millis());
+        self.client
+            .create_branch(&self.branch_path(&scratch), &self.branch_path(branch))
+            .await?;
+
+        let mut result = self.run_all(&scratch, migrations, direction).await;
+        if result.is_ok() {
+            result = self.fast_forward(branch, &scratch, &head.to_string()).await;
+        }
+        if let Err(err) = self.client.delete_branch(&self.branch_path(&scratch)).await {
+            eprintln!("Warning: failed to delete branch {}: {:#}", scratch, err);
+        }
+        result.with_context(|| format!("branch {} was left unchanged", branch))
+    }
+
+    async fn run_all(
+        &self,
+        branch: &str,
+        migrations: &[&Migration],
+        direction: Direction,
+    ) -> Result<()> {
+        if direction == Direction::Up {
+            let message = format!("Add the {} class", TRACKING_CLASS);
+            apply_schema(
+                &self.client,
+                &self.spec(branch),
+                &[tracking_class()],
+                &self.author,
+                &message,
+            )
+            .await?;
+        }
+
+        for migration in migrations {
+            let (verb, script) = match (direction, &migration.down) {
+                (Direction::Up, _) => ("Apply", &migration.up),
+                (Direction::Down, Some(down)) => ("Revert", down),
+                (Direction::Down, None) => bail!("migration {} has no down file", migration.id()),
+            };
+            eprintln!("{} {}", verb, script.path.display());
+            let message = format!("{} migration {}", verb, migration.id());
+            self.run_script(branch, script, &message)
+                .await
+                .with_context(|| format!("migration {} failed", migration.id()))?;
+            match direction {
+                Direction::Up => self.record(branch, migration).await?,
+                Direction::Down => self.forget(branch, migration).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_script(&self, branch: &str, script: &Script, message: &str) -> Result<()> {
+        let text = std::fs::read_to_string(&script.path)
+            .with_context(|| format!("failed to read {}", script.path.display()))?;
+        match script.kind {
+            ScriptKind::Schema => {
+                let docs = parse_schema_file(&text)
+                    .with_context(|| format!("invalid schema in {}", script.path.display()))?;
+                apply_schema(
+                    &self.client,
+                    &self.spec(branch),
+                    &docs,
+                    &self.author,
+                    message,
+                )
+                .await?;
+            }
+            ScriptKind::Woql => {
+                let query = woql_json(&text)
+                    .with_context(|| format!("invalid query in {}", script.path.display()))?;
+                self.woql(branch, query, message).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a WOQL query that writes to `branch`, as one commit
+    async fn woql(&self, branch: &str, query: Value, message: &str) -> Result<()> {
+        let api_url = format!(
+            "{}/api/woql/{}",
+            self.host.trim_end_matches('/'),
+            self.branch_path(branch)
+        );
+        let body = json!({
+            "query": query,
+            "commit_info": { "author": self.author, "message": message }
+        });
+
+        let res = reqwest::Client::new()
+            .post(&api_url)
+            .basic_auth(&self.user, Some(&self.password))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let status = res.status();
+            let error_text = res.text().await?;
+            return Err(StatusError {
+                status,
+                message: format!("WOQL query failed (status {}): {}", status, error_text),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    async fn record(&self, branch: &str, migration: &Migration) -> Result<()> {
+        let doc = json!({
+            "@type": TRACKING_CLASS,
+            "version": migration.version,
+            "name": migration.name,
+            "applied_at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        });
+        let args = DocumentInsertArgs::from(self.spec(branch))
+            .with_author(self.author.as_str())
+            .with_message(format!("Record migration {}", migration.id()));
+        self.client.insert_documents(vec![&doc], args).await?;
+        Ok(())
+    }
+
+    async fn forget(&self, branch: &str, migration: &Migration) -> Result<()> {
+        let id = format!("{}/{}", TRACKING_CLASS, migration.version);
+        let message = format!("Forget migration {}", migration.id());
+        self.client
+            .delete_document(
+                Some(&id),
+                &self.spec(branch),
+                &self.author,
+                &message,
+                "instance",
+                DeleteOpts::new(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Move `branch` to the head of `scratch`, unless it moved away from `head`
+    async fn fast_forward(&self, branch: &str, scratch: &str, head: &str) -> Result<()> {
+        let current = self.client.get_latest_commit_id(&self.spec(branch)).await?;
+        if current.to_string() != head {
+            bail!("{} received other commits while migrating", branch);
+        }
+        let result = self
+            .client
+            .get_latest_commit_id(&self.spec(scratch))
+            .await?;
+        let commit = format!("{}/{}/local/commit/{}", self.org, self.database, result);
+        self.client
+            .reset(&self.branch_path(branch), &commit)
+            .await?;
+        Ok(())
+    }
+}
+
+pub(crate) async fn run_migrate_up(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    dir: String,
+    to: Option<String>,
+    DryRun { dry_run }: DryRun,
+    author: String,
+    output: Output,
+) -> Result<()> {
+    let migrations = load_migrations(Path::new(&dir))?;
+    let migrator = Migrator::new(host, user, password, org, database, author).await?;
+    let applied = migrator.applied(&branch).await?;
+    let pending = pending(&migrations, &applied, to.as_deref())?;
+
+    let ids: Vec<String> = pending.iter().map(|m| m.id()).collect();
+    let run = !pending.is_empty() && !dry_run;
+    if pending.is_empty() {
+        eprintln!("Nothing to migrate: {} is up to date.", branch);
+    } else if dry_run {
+        eprintln!("Dry run: nothing was changed.");
+    } else {
+        migrator.run(&branch, &pending, Direction::Up).await?;
+    }
+
+    let data = json!({ "branch": branch, "migrations": ids, "applied": run });
+    output.print(&data, || {
+        let lead = if run { "Applied" } else { "Would apply" };
+        ids.iter().map(|id| format!("{} {}\n", lead, id)).collect()
+    })
+}
+
+pub(crate) async fn run_migrate_down(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    dir: String,
+    to: Option<String>,
+    author: String,
+    confirm: Confirm,
+    output: Output,
+) -> Result<()> {
+    let migrations = load_migrations(Path::new(&dir))?;
+    let migrator = Migrator::new(host, user, password, org, database, author).await?;
+    let applied = migrator.applied(&branch).await?;
+    let reverted = to_revert(&migrations, &applied, to.as_deref())?;
+    if reverted.is_empty() {
+        eprintln!("Nothing to revert on {}.", branch);
+        return output.print(&json!({ "branch": branch, "reverted": [] }), String::new);
+    }
+
+    let ids: Vec<String> = reverted.iter().map(|m| m.id()).collect();
+    if confirm.asks() {
+        let mut preview = Preview::new(format!(
+            "revert {} migration(s) on {}",
+            reverted.len(),
+            migrator.branch_path(&branch)
+        ));
+        for migration in &reverted {
+            if let Some(down) = &migration.down {
+                preview = preview.effect(format!("run {}", down.path.display()));
+            }
+        }
+        if !confirm.proceed(&preview, output)? {
+            return Ok(());
+        }
+    }
+    migrator.run(&branch, &reverted, Direction::Down).await?;
+
+    output.print(&json!({ "branch": branch, "reverted": ids }), || {
+        ids.iter().map(|id| format!("Reverted {}\n", id)).collect()
+    })
+}
+
+pub(crate) async fn run_migrate_status(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    dir: String,
+    output: Output,
+) -> Result<()> {
+    let migrations = load_migrations(Path::new(&dir))?;
+    let migrator = Migrator::new(host, user, password, org, database, String::new()).await?;
+    let applied = migrator.applied(&branch).await?;
+
+    let rows = status_rows(&migrations, &applied);
+    output.print(&rows, || render_status(&rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn migrations() -> Vec<Migration> {
+        migrations_from_names(
+            Path::new("migrations"),
+            names(&[
+                "README.md",
+                "0002_backfill_names.up.woql",
+                "0001_people.up.schema",
+                "0001_people.down.woql",
+                "0010_teams.up.schema",
+                "0010_teams.down.woql",
+            ]),
+        )
+        .unwrap()
+    }
+
+    fn applied(versions: &[&str]) -> Vec<Applied> {
+        let migrations = migrations();
+        versions
+            .iter()
+            .map(|version| Applied {
+                version: version.to_string(),
+                name: migrations
+                    .iter()
+                    .find(|m| m.version == *version)
+                    .map_or("gone".to_string(), |m| m.name.clone()),
+                applied_at: "2026-10-16T12:00:00Z".to_string(),
+            })
+            .collect()
+    }
+
+    fn ids(migrations: &[&Migration]) -> Vec<String> {
+        migrations.iter().map(|m| m.id()).collect()
+    }
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(
+            parse_file_name("0001_add_people.up.woql").unwrap(),
+            Some((
+                "0001".to_string(),
+                "add_people".to_string(),
+                Direction::Up,
+                ScriptKind::Woql
+            ))
+        );
+        assert_eq!(parse_file_name("README.md").unwrap(), None);
+        for name in [
+            "0001.up.woql",
+            "0001_x.sideways.woql",
+            "0001_x.up.sql",
+            "01a_x.up.woql",
+        ] {
+            let err = parse_file_name(name).unwrap_err().to_string();
+            assert!(err.starts_with("invalid migration file name"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_migrations_from_names() {
+        let migrations = migrations();
+        assert_eq!(
+            migrations.iter().map(Migration::id).collect::<Vec<_>>(),
+            ["0001_people", "0002_backfill_names", "0010_teams"]
+        );
+        assert_eq!(
+            migrations[0].up,
+            Script {
+                path: Path::new("migrations").join("0001_people.up.schema"),
+                kind: ScriptKind::Schema
+            }
+        );
+        assert!(migrations[1].down.is_none());
+
+        let err = migrations_from_names(Path::new("m"), names(&["0003_x.down.woql"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("has no up file"), "{}", err);
+        let err = migrations_from_names(
+            Path::new("m"),
+            names(&["0003_x.up.woql", "0003_x.up.schema"]),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("are both migration 0003"), "{}", err);
+        let err = migrations_from_names(Path::new("m"), names(&["1_x.up.woql", "01_y.up.woql"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("have the same version"), "{}", err);
+    }
+
+    #[test]
+    fn test_pending() {
+        let migrations = migrations();
+        assert_eq!(
+            ids(&pending(&migrations, &applied(&["0001"]), None).unwrap()),
+            ["0002_backfill_names", "0010_teams"]
+        );
+        assert_eq!(
+            ids(&pending(&migrations, &[], Some("2")).unwrap()),
+            ["0001_people", "0002_backfill_names"]
+        );
+        assert!(pending(&migrations, &[], Some("3")).is_err());
+    }
+
+    #[test]
+    fn test_to_revert() {
+        let migrations = migrations();
+        let all = applied(&["0001", "0010"]);
+        assert_eq!(
+            ids(&to_revert(&migrations, &all, None).unwrap()),
+            ["0010_teams"]
+        );
+        assert_eq!(
+            ids(&to_revert(&migrations, &all, Some("0")).unwrap()),
+            ["0010_teams", "0001_people"]
+        );
+
+        let err = to_revert(&migrations, &applied(&["0001", "0002"]), None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("0002_backfill_names has no down file"),
+            "{}",
+            err
+        );
+        let err = to_revert(&migrations, &applied(&["0007"]), None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("its files are missing"), "{}", err);
+    }
+
+    #[test]
+    fn test_status() {
+        let migrations = migrations();
+        let rows = status_rows(&migrations, &applied(&["0001", "0007"]));
+        assert_eq!(
+            render_status(&rows),
+            "0001_people          applied  2026-10-16T12:00:00Z\n\
+             0002_backfill_names  pending\n\
+             0007_gone            missing  2026-10-16T12:00:00Z\n\
+             0010_teams           pending\n"
+        );
+        assert_eq!(rows[1]["applied_at"], Value::Null);
+    }
+
+    #[test]
+    fn test_woql_json() {
+        let query = woql_json(r#"{"@type": "True"}"#).unwrap();
+        assert_eq!(query, json!({ "@type": "True" }));
+        assert!(woql_json("triple(").is_err());
+    }
+}
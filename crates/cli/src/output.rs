@@ -0,0 +1,364 @@
+//! The global `--output` flag and the exit codes `tdb` fails with.
+//!
+//! Table output is for people: confirmations, aligned tables and the
+//! per-command formats (`query --format`, `diff --format`, ...). JSON and YAML
+//! print the command's result as data on stdout, so scripts can parse it; in
+//! those modes errors are printed to stderr in the same format.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::{Display, Formatter};
+use terminusdb_client::err::{ErrorResponse, ServerNotReadyError, TypedErrorResponse};
+use terminusdb_client::{TerminusAPIStatus, TerminusDBAdapterError};
+
+use crate::query::{self, Binding};
+
+/// What commands print their results as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum Output {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+}
+
+impl Output {
+    /// The output of a command that also takes `--json`
+    pub(crate) fn or_json(self, json: bool) -> Self {
+        if json {
+            Output::Json
+        } else {
+            self
+        }
+    }
+
+    /// Whether results are printed as data (JSON or YAML) rather than text
+    pub(crate) fn is_data(self) -> bool {
+        self != Output::Table
+    }
+
+    /// `value` as JSON or YAML text, or `None` for table output
+    pub(crate) fn render_data<T: Serialize + ?Sized>(self, value: &T) -> Result<Option<String>> {
+        Ok(match self {
+            Output::Table => None,
+            Output::Json => Some(format!("{}\n", serde_json::to_string_pretty(value)?)),
+            Output::Yaml => Some(yaml(&serde_json::to_value(value)?)?),
+        })
+    }
+
+    /// Print `value` as JSON or YAML, or the text from `table` for table output
+    pub(crate) fn print<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+        table: impl FnOnce() -> String,
+    ) -> Result<()> {
+        match self.render_data(value)? {
+            Some(text) => print!("{}", text),
+            None => print!("{}", table()),
+        }
+        Ok(())
+    }
+
+    /// Print a server response, rendered as a table for table output
+    pub(crate) fn print_value<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        let value = serde_json::to_value(value)?;
+        self.print(&value, || render_table(&value))
+    }
+
+    /// Report `err` on stderr and exit with its exit code.
+    pub(crate) fn fail(self, err: anyhow::Error) -> ! {
+        let failure = Failure::of(&err);
+        let report = json!({
+            "error": {
+                "message": format!("{:#}", err),
+                "code": failure.code(),
+                "kind": failure.kind(),
+            }
+        });
+        match self.render_data(&report) {
+            Ok(Some(text)) => eprint!("{}", text),
+            _ => eprintln!("Error: {:?}", err),
+        }
+        std::process::exit(failure.code())
+    }
+}
+
+/// `value` as a YAML document.
+///
+/// Numbers are converted by hand: with serde_json's `arbitrary_precision`
+/// they serialize as a private wrapper type rather than as numbers.
+pub(crate) fn yaml(value: &Value) -> Result<String> {
+    Ok(serde_norway::to_string(&yaml_value(value))?)
+}
+
+fn yaml_value(value: &Value) -> serde_norway::Value {
+    match value {
+        Value::Null => serde_norway::Value::Null,
+        Value::Bool(b) => serde_norway::Value::Bool(*b),
+        Value::Number(n) => yaml_number(n),
+        Value::String(s) => serde_norway::Value::String(s.clone()),
+        Value::Array(items) => {
+            serde_norway::Value::Sequence(items.iter().map(yaml_value).collect())
+        }
+        Value::Object(fields) => serde_norway::Value::Mapping(
+            fields
+                .iter()
+                .map(|(key, value)| (serde_norway::Value::String(key.clone()), yaml_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// A number as a YAML number, or as a string when a float would lose digits
+fn yaml_number(n: &serde_json::Number) -> serde_norway::Value {
+    if let Some(i) = n.as_i64() {
+        return serde_norway::Value::Number(i.into());
+    }
+    if let Some(u) = n.as_u64() {
+        return serde_norway::Value::Number(u.into());
+    }
+    let literal = n.to_string();
+    match literal.parse::<f64>() {
+        Ok(f) if f.to_string() == literal => serde_norway::Value::Number(f.into()),
+        _ => serde_norway::Value::String(literal),
+    }
+}
+
+/// A JSON value as text: arrays of objects as a table with a column per
+/// field, other arrays one item per line, objects one field per line
+pub(crate) fn render_table(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            let rows: Vec<Binding> = items
+                .iter()
+                .filter_map(|item| item.as_object().cloned())
+                .collect();
+            query::render_table(&query::columns(&[], &rows), &rows)
+        }
+        Value::Array(items) => items
+            .iter()
+            .map(|item| format!("{}\n", query::cell(Some(item))))
+            .collect(),
+        Value::Object(fields) => {
+            let width = fields
+                .keys()
+                .map(|key| key.chars().count())
+                .max()
+                .unwrap_or(0);
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let line = format!(
+                        "{:<width$}  {}",
+                        key,
+                        query::cell(Some(value)).replace('\n', "\\n"),
+                        width = width
+                    );
+                    format!("{}\n", line.trim_end())
+                })
+                .collect()
+        }
+        other => format!("{}\n", query::cell(Some(other))),
+    }
+}
+
+/// A request the server answered with an error status
+#[derive(Debug)]
+pub(crate) struct StatusError {
+    pub(crate) status: reqwest::StatusCode,
+    pub(crate) message: String,
+}
+
+impl Display for StatusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// How a command failed, which decides the exit code. Clap exits with 2 on
+/// invalid arguments, before any command runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Failure {
+    /// Anything not covered below
+    Error,
+    /// The server could not be reached, or is still starting up
+    Unavailable,
+    /// The database, branch, document or other resource does not exist
+    NotFound,
+    /// The server rejected the credentials
+    Unauthorized,
+}
+
+impl Failure {
+    pub(crate) fn of(err: &anyhow::Error) -> Self {
+        err.chain()
+            .find_map(Self::of_cause)
+            .unwrap_or(Failure::Error)
+    }
+
+    pub(crate) fn code(self) -> i32 {
+        match self {
+            Failure::Error => 1,
+            Failure::Unavailable => 3,
+            Failure::NotFound => 4,
+            Failure::Unauthorized => 5,
+        }
+    }
+
+    fn kind(self) -> &'static str {
+        match self {
+            Failure::Error => "error",
+            Failure::Unavailable => "unavailable",
+            Failure::NotFound => "not_found",
+            Failure::Unauthorized => "unauthorized",
+        }
+    }
+
+    fn of_cause(cause: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            if err.is_connect() || err.is_timeout() {
+                return Some(Failure::Unavailable);
+            }
+            return err.status().and_then(Self::of_status);
+        }
+        // The GraphQL calls wrap HTTP errors without exposing them as a source
+        if let Some(TerminusDBAdapterError::HTTP(err)) =
+            cause.downcast_ref::<TerminusDBAdapterError>()
+        {
+            return Self::of_cause(err);
+        }
+        if cause.is::<ServerNotReadyError>() {
+            return Some(Failure::Unavailable);
+        }
+        if let Some(err) = cause.downcast_ref::<StatusError>() {
+            return Self::of_status(err.status);
+        }
+        if let Some(err) = cause.downcast_ref::<TypedErrorResponse>() {
+            return Self::of_response(error_response(err));
+        }
+        if let Some(err) = cause.downcast_ref::<ErrorResponse>() {
+            return Self::of_response(err);
+        }
+        None
+    }
+
+    fn of_status(status: reqwest::StatusCode) -> Option<Self> {
+        match status.as_u16() {
+            401 | 403 => Some(Failure::Unauthorized),
+            404 => Some(Failure::NotFound),
+            502..=504 => Some(Failure::Unavailable),
+            _ => None,
+        }
+    }
+
+    fn of_response(response: &ErrorResponse) -> Option<Self> {
+        use terminusdb_client::err::ApiResponseError;
+
+        match (&response.api_error, &response.api_status) {
+            (Some(ApiResponseError::IncorrectAuthentication(_)), _) => Some(Failure::Unauthorized),
+            (Some(ApiResponseError::UnknownDatabase(_)), _)
+            | (Some(ApiResponseError::DocumentNotFound(_)), _)
+            | (_, TerminusAPIStatus::NotFound) => Some(Failure::NotFound),
+            _ => None,
+        }
+    }
+}
+
+fn error_response(err: &TypedErrorResponse) -> &ErrorResponse {
+    match err {
+        TypedErrorResponse::DocumentError { error, .. }
+        | TypedErrorResponse::ReplaceDocumentError { error, .. }
+        | TypedErrorResponse::WoqlError { error, .. }
+        | TypedErrorResponse::InsertDocumentError { error, .. }
+        | TypedErrorResponse::DeleteDocumentError { error, .. }
+        | TypedErrorResponse::LogError { error, .. }
+        | TypedErrorResponse::GenericError(error) => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_render_table() {
+        let databases = json!([
+            { "name": "mydb", "label": "My DB" },
+            { "name": "other", "comment": "spare" }
+        ]);
+        assert_eq!(
+            render_table(&databases),
+            "comment | label | name\n\
+             --------+-------+------\n\
+             \x20       | My DB | mydb\n\
+             spare   |       | other\n"
+        );
+        assert_eq!(render_table(&json!(["main", "dev"])), "main\ndev\n");
+        assert_eq!(
+            render_table(&json!({ "name": "mydb", "branches": ["main"], "label": null })),
+            "branches  [\"main\"]\nlabel\nname      mydb\n"
+        );
+        assert_eq!(render_table(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_yaml_numbers() {
+        let value: Value = serde_json::from_str(
+            r#"{"count": 42, "ratio": 1.5, "precise": 0.1000000000000000000001}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            yaml(&value).unwrap(),
+            "count: 42\nprecise: '0.1000000000000000000001'\nratio: 1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_render_data() {
+        let value = json!({ "branch": "main" });
+        assert_eq!(Output::Table.render_data(&value).unwrap(), None);
+        assert_eq!(
+            Output::Json.render_data(&value).unwrap().unwrap(),
+            "{\n  \"branch\": \"main\"\n}\n"
+        );
+        assert_eq!(
+            Output::Yaml.render_data(&value).unwrap().unwrap(),
+            "branch: main\n"
+        );
+        assert_eq!(Output::Table.or_json(true), Output::Json);
+        assert_eq!(Output::Yaml.or_json(false), Output::Yaml);
+    }
+
+    #[test]
+    fn test_failure() {
+        let not_found = anyhow::Error::new(StatusError {
+            status: reqwest::StatusCode::NOT_FOUND,
+            message: "Failed to get database info".to_string(),
+        })
+        .context("database info");
+        assert_eq!(Failure::of(&not_found), Failure::NotFound);
+
+        let response: TypedErrorResponse = serde_json::from_value(json!({
+            "@type": "api:GetDocumentErrorResponse",
+            "api:error": {
+                "@type": "api:DocumentNotFound",
+                "api:document_id": "Person/alice"
+            },
+            "api:message": "Document not found: 'Person/alice'",
+            "api:status": "api:not_found"
+        }))
+        .unwrap();
+        assert_eq!(Failure::of(&response.into()), Failure::NotFound);
+
+        let unreachable: Result<()> = Err(ServerNotReadyError.into());
+        let unreachable = unreachable.context("failed to connect").unwrap_err();
+        assert_eq!(Failure::of(&unreachable), Failure::Unavailable);
+        assert_eq!(Failure::of(&anyhow::anyhow!("bad query")).code(), 1);
+    }
+}
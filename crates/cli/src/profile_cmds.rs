@@ -1,6 +1,12 @@
 //! Profile and authentication management commands (login/logout/profile).
+//!
+//! `login` prompts for its settings and ignores `--output`; the other
+//! commands print their result as data with `--output json|yaml`.
 
 use anyhow::{Context, Result};
+use serde_json::json;
+
+use crate::output::Output;
 
 // Profile and authentication management functions
 
@@ -89,7 +95,7 @@ pub(crate) async fn run_login(profile_name: &str) -> Result<()> {
     Ok(())
 }
 
-pub(crate) async fn run_logout(profile_name: Option<&str>) -> Result<()> {
+pub(crate) async fn run_logout(profile_name: Option<&str>, output: Output) -> Result<()> {
     let config = crate::auth::load_config()?;
 
     let profile_to_logout = match profile_name {
@@ -100,18 +106,37 @@ pub(crate) async fn run_logout(profile_name: Option<&str>) -> Result<()> {
     // Delete the profile
     crate::auth::delete_profile(&profile_to_logout)?;
 
-    println!(
-        "Successfully logged out from profile '{}'",
-        profile_to_logout
-    );
-    println!("Credentials removed from system keyring");
-
-    Ok(())
+    output.print(&json!({ "logged_out": profile_to_logout }), || {
+        format!(
+            "Successfully logged out from profile '{}'\n\
+             Credentials removed from system keyring\n",
+            profile_to_logout
+        )
+    })
 }
 
-pub(crate) async fn run_profile_list() -> Result<()> {
+pub(crate) async fn run_profile_list(output: Output) -> Result<()> {
     let config = crate::auth::load_config()?;
 
+    let mut profile_names: Vec<_> = config.profiles.keys().collect();
+    profile_names.sort();
+
+    if output.is_data() {
+        let profiles: Vec<_> = profile_names
+            .iter()
+            .map(|name| {
+                let mut profile = json!(config.profiles[*name]);
+                profile["name"] = json!(name);
+                profile["active"] = json!(*name == &config.settings.active_profile);
+                profile
+            })
+            .collect();
+        if let Some(text) = output.render_data(&profiles)? {
+            print!("{}", text);
+        }
+        return Ok(());
+    }
+
     if config.profiles.is_empty() {
         println!("No profiles configured. Use 'tdb login' to create one.");
         return Ok(());
@@ -120,9 +145,6 @@ pub(crate) async fn run_profile_list() -> Result<()> {
     println!("Available profiles:");
     println!();
 
-    let mut profile_names: Vec<_> = config.profiles.keys().collect();
-    profile_names.sort();
-
     for name in profile_names {
         let profile = &config.profiles[name];
         let is_active = name == &config.settings.active_profile;
@@ -147,7 +169,7 @@ pub(crate) async fn run_profile_list() -> Result<()> {
     Ok(())
 }
 
-pub(crate) async fn run_profile_set(name: &str) -> Result<()> {
+pub(crate) async fn run_profile_set(name: &str, output: Output) -> Result<()> {
     let mut config = crate::auth::load_config()?;
 
     // Verify profile exists
@@ -161,12 +183,12 @@ pub(crate) async fn run_profile_set(name: &str) -> Result<()> {
     config.set_active(name.to_string());
     crate::auth::save_config(&config)?;
 
-    println!("Set '{}' as active profile", name);
-
-    Ok(())
+    output.print(&json!({ "active_profile": name }), || {
+        format!("Set '{}' as active profile\n", name)
+    })
 }
 
-pub(crate) async fn run_profile_show(name: Option<&str>) -> Result<()> {
+pub(crate) async fn run_profile_show(name: Option<&str>, output: Output) -> Result<()> {
     let config = crate::auth::load_config()?;
 
     let profile_name = match name {
@@ -178,6 +200,13 @@ pub(crate) async fn run_profile_show(name: Option<&str>) -> Result<()> {
         .get_profile(profile_name)
         .with_context(|| format!("Profile '{}' not found", profile_name))?;
 
+    let mut data = json!(profile);
+    data["name"] = json!(profile_name);
+    if let Some(text) = output.render_data(&data)? {
+        print!("{}", text);
+        return Ok(());
+    }
+
     println!("Profile: {}", profile_name);
     println!("  Host: {}", profile.host);
     println!("  User: {}", profile.user);
@@ -196,7 +225,53 @@ pub(crate) async fn run_profile_show(name: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-pub(crate) async fn run_profile_delete(name: &str, force: bool) -> Result<()> {
+pub(crate) async fn run_profile_set_default_db(
+    profile: Option<String>,
+    database: String,
+    branch: Option<String>,
+    org: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let mut config = crate::auth::load_config()?;
+    let profile_name = profile.unwrap_or_else(|| config.settings.active_profile.clone());
+    let mut updated = config
+        .get_profile(&profile_name)
+        .cloned()
+        .with_context(|| {
+            format!(
+                "Profile '{}' not found. Use 'tdb login' to create it.",
+                profile_name
+            )
+        })?;
+
+    updated.database = Some(database.clone());
+    if let Some(ref branch) = branch {
+        updated.branch = Some(branch.clone());
+    }
+    if let Some(ref org) = org {
+        updated.org = org.clone();
+    }
+    config.set_profile(profile_name.clone(), updated.clone());
+    crate::auth::save_config(&config)?;
+
+    let mut data = json!(updated);
+    data["name"] = json!(profile_name);
+    output.print(&data, || {
+        let mut text = format!(
+            "Set default database of profile '{}' to '{}'\n",
+            profile_name, database
+        );
+        if let Some(branch) = branch {
+            text.push_str(&format!("  Default Branch: {}\n", branch));
+        }
+        if let Some(org) = org {
+            text.push_str(&format!("  Organization: {}\n", org));
+        }
+        text
+    })
+}
+
+pub(crate) async fn run_profile_delete(name: &str, force: bool, output: Output) -> Result<()> {
     use std::io::{self, Write};
 
     let config = crate::auth::load_config()?;
@@ -208,112 +283,28 @@ pub(crate) async fn run_profile_delete(name: &str, force: bool) -> Result<()> {
 
     // Check if it's the active profile
     if name == config.settings.active_profile {
-        println!("Warning: '{}' is currently the active profile", name);
+        eprintln!("Warning: '{}' is currently the active profile", name);
     }
 
     // Confirm deletion unless --force
     if !force {
-        print!(
+        eprint!(
             "Are you sure you want to delete profile '{}'? (y/N): ",
             name
         );
-        io::stdout().flush()?;
+        io::stderr().flush()?;
         let mut response = String::new();
         io::stdin().read_line(&mut response)?;
 
         if !response.trim().eq_ignore_ascii_case("y") {
-            println!("Deletion cancelled");
+            eprintln!("Deletion cancelled");
             return Ok(());
         }
     }
 
     crate::auth::delete_profile(name)?;
 
-    println!("Profile '{}' deleted", name);
-
-    Ok(())
-}
-
-/// Helper function to resolve credentials from multiple sources
-/// Priority: CLI args > Environment variables > Active profile > Error
-fn resolve_credentials(
-    cli_host: Option<String>,
-    cli_user: Option<String>,
-    cli_password: Option<String>,
-    cli_org: Option<String>,
-    cli_database: Option<String>,
-    cli_branch: Option<String>,
-    profile_name: Option<String>,
-) -> Result<crate::auth::ResolvedCredentials> {
-    // If profile is specified, load from that profile
-    if let Some(profile) = profile_name {
-        let mut creds = crate::auth::get_profile_credentials(&profile)?;
-
-        // CLI args override profile values
-        if let Some(h) = cli_host {
-            creds.host = h;
-        }
-        if let Some(u) = cli_user {
-            creds.user = u;
-        }
-        if let Some(p) = cli_password {
-            creds.password = p;
-        }
-        if let Some(o) = cli_org {
-            creds.org = o;
-        }
-        if let Some(db) = cli_database {
-            creds.database = Some(db);
-        }
-        if let Some(br) = cli_branch {
-            creds.branch = Some(br);
-        }
-
-        return Ok(creds);
-    }
-
-    // Try to load from active profile if no explicit credentials provided
-    if cli_host.is_none() || cli_user.is_none() || cli_password.is_none() || cli_org.is_none() {
-        if let Ok(mut creds) = crate::auth::get_active_credentials() {
-            // CLI args override profile values
-            if let Some(h) = cli_host {
-                creds.host = h;
-            }
-            if let Some(u) = cli_user {
-                creds.user = u;
-            }
-            if let Some(p) = cli_password {
-                creds.password = p;
-            }
-            if let Some(o) = cli_org {
-                creds.org = o;
-            }
-            if let Some(db) = cli_database {
-                creds.database = Some(db);
-            }
-            if let Some(br) = cli_branch {
-                creds.branch = Some(br);
-            }
-
-            return Ok(creds);
-        }
-    }
-
-    // Fall back to CLI args only (must have all required fields)
-    match (cli_host, cli_user, cli_password, cli_org) {
-        (Some(host), Some(user), Some(password), Some(org)) => Ok(crate::auth::ResolvedCredentials {
-            host,
-            user,
-            password,
-            org,
-            database: cli_database,
-            branch: cli_branch,
-        }),
-        _ => {
-            anyhow::bail!(
-                "Missing required credentials. Please provide --host, --user, --password, and --org, \
-                or use 'tdb login' to save credentials in a profile."
-            )
-        }
-    }
+    output.print(&json!({ "deleted": name }), || {
+        format!("Profile '{}' deleted\n", name)
+    })
 }
@@ -0,0 +1,271 @@
+//! `tdb query`: run a WOQL query and print its bindings.
+//!
+//! The query is read from the argument, from a file (`@<path>`) or from stdin
+//! (no argument, or `-`). Text that parses as JSON is sent as JSON-LD; anything
+//! else is parsed as WOQL DSL. Bindings go to stdout with one column per query
+//! variable, as an aligned table, a JSON array or CSV; `--output json|yaml`
+//! takes precedence over `--format`.
+
+use std::collections::BTreeSet;
+use std::io::Read;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Value};
+use terminusdb_client::{BranchSpec, TerminusDBHttpClient, WOQLResult};
+use url::Url;
+
+use crate::output::Output;
+
+/// One row of query results: variable name to bound value
+pub(crate) type Binding = Map<String, Value>;
+
+/// Output format for `tdb query`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum QueryFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl QueryFormat {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("unknown format '{}', expected table, json or csv", s),
+        }
+    }
+}
+
+pub(crate) async fn run_query(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    query: Option<String>,
+    format: String,
+    output: Output,
+) -> Result<()> {
+    let format = QueryFormat::parse(&format)?;
+    let query = read_query(query)?;
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let result = execute(&client, spec, &query).await?;
+
+    if let Some(text) = output.render_data(&result.bindings)? {
+        print!("{}", text);
+        return Ok(());
+    }
+    print!("{}", render(format, &result)?);
+    if format == QueryFormat::Table {
+        eprintln!("({} rows)", result.bindings.len());
+    }
+    Ok(())
+}
+
+/// The bindings of a result in `format`
+pub(crate) fn render(format: QueryFormat, result: &WOQLResult<Binding>) -> Result<String> {
+    let columns = columns(&result.api_variable_names, &result.bindings);
+    Ok(match format {
+        QueryFormat::Table => render_table(&columns, &result.bindings),
+        QueryFormat::Json => format!("{}\n", serde_json::to_string_pretty(&result.bindings)?),
+        QueryFormat::Csv => render_csv(&columns, &result.bindings),
+    })
+}
+
+/// Run a query written as JSON-LD (anything that parses as JSON) or in the
+/// WOQL DSL
+pub(crate) async fn execute(
+    client: &TerminusDBHttpClient,
+    spec: BranchSpec,
+    query: &str,
+) -> Result<WOQLResult<Binding>> {
+    match serde_json::from_str::<Value>(query) {
+        Ok(json_ld) => client.query_raw(Some(spec), json_ld, None).await,
+        Err(_) => {
+            let query = terminusdb_woql_dsl::parse_woql_dsl(query)
+                .map_err(|e| anyhow!("invalid WOQL DSL: {}", e))?;
+            client.query(Some(spec), query).await
+        }
+    }
+}
+
+/// The query text from the argument, `@<path>` or stdin
+fn read_query(query: Option<String>) -> Result<String> {
+    let query = match query.as_deref() {
+        None | Some("-") => {
+            if atty::is(atty::Stream::Stdin) {
+                eprintln!("Reading query from stdin (end with Ctrl+D)");
+            }
+            let mut query = String::new();
+            std::io::stdin()
+                .read_to_string(&mut query)
+                .context("failed to read query from stdin")?;
+            query
+        }
+        Some(query) => match query.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read query from {}", path))?,
+            None => query.to_string(),
+        },
+    };
+
+    if query.trim().is_empty() {
+        bail!("empty query");
+    }
+    Ok(query)
+}
+
+/// Column names: the query's variables, or the variables bound in the
+/// results (sorted) when the server does not list them
+pub(crate) fn columns(variable_names: &[String], bindings: &[Binding]) -> Vec<String> {
+    if !variable_names.is_empty() {
+        return variable_names.to_vec();
+    }
+    let columns: BTreeSet<&String> = bindings.iter().flat_map(|binding| binding.keys()).collect();
+    columns.into_iter().cloned().collect()
+}
+
+/// A bound value as text: typed literals by their value, nodes by their id,
+/// unbound variables as the empty string
+pub(crate) fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Object(obj)) if obj.contains_key("@value") => cell(obj.get("@value")),
+        Some(other) => other.to_string(),
+    }
+}
+
+pub(crate) fn render_table(columns: &[String], bindings: &[Binding]) -> String {
+    let rows: Vec<Vec<String>> = bindings
+        .iter()
+        .map(|binding| {
+            columns
+                .iter()
+                .map(|column| cell(binding.get(column)).replace('\n', "\\n"))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(column.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", padded.join(" | ").trim_end())
+    };
+
+    let mut output = line(columns);
+    let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    output.push_str(&format!("{}\n", rule.join("-+-")));
+    for row in &rows {
+        output.push_str(&line(row));
+    }
+    output
+}
+
+fn render_csv(columns: &[String], bindings: &[Binding]) -> String {
+    let line = |cells: Vec<String>| {
+        let escaped: Vec<String> = cells.iter().map(|cell| csv_field(cell)).collect();
+        format!("{}\n", escaped.join(","))
+    };
+
+    let mut output = line(columns.to_vec());
+    for binding in bindings {
+        output.push_str(&line(
+            columns
+                .iter()
+                .map(|column| cell(binding.get(column)))
+                .collect(),
+        ));
+    }
+    output
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bindings() -> Vec<Binding> {
+        vec![
+            json!({
+                "Person": "Person/alice",
+                "Name": { "@type": "xsd:string", "@value": "Alice, A." },
+                "Age": { "@type": "xsd:decimal", "@value": 42 }
+            }),
+            json!({ "Person": "Person/bob", "Name": null, "Age": null }),
+        ]
+        .into_iter()
+        .map(|binding| binding.as_object().unwrap().clone())
+        .collect()
+    }
+
+    fn person_columns() -> Vec<String> {
+        vec!["Person".to_string(), "Name".to_string(), "Age".to_string()]
+    }
+
+    #[test]
+    fn test_render_table() {
+        assert_eq!(
+            render_table(&person_columns(), &bindings()),
+            "Person       | Name      | Age\n\
+             -------------+-----------+----\n\
+             Person/alice | Alice, A. | 42\n\
+             Person/bob   |           |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            render_csv(&person_columns(), &bindings()),
+            "Person,Name,Age\nPerson/alice,\"Alice, A.\",42\nPerson/bob,,\n"
+        );
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+    }
+
+    #[test]
+    fn test_columns_fall_back_to_bound_variables() {
+        assert_eq!(columns(&[], &bindings()), vec!["Age", "Name", "Person"]);
+        assert_eq!(
+            columns(&["Name".to_string()], &bindings()),
+            vec!["Name".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(QueryFormat::parse("CSV").unwrap(), QueryFormat::Csv);
+        assert!(QueryFormat::parse("yaml").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! `tdb query-profile`: run a query and print its timing breakdown.
+//!
+//! The report goes to stdout, as a table or with `--output json|yaml` as data
+//! with durations in milliseconds; the query results themselves are discarded.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use terminusdb_client::{BranchSpec, ProfileReport, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+
+pub(crate) async fn run_query_profile(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    query: String,
+    output: Output,
+) -> Result<()> {
+    let query = match query.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read query from {}", path))?,
+        None => query,
+    };
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let report = client.profile_query_string(Some(spec), &query).await?;
+
+    output.print(&report_data(&report), || report.to_string())
+}
+
+fn report_data(report: &ProfileReport) -> Value {
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let server: Vec<Value> = report
+        .server_timing
+        .iter()
+        .map(|timing| {
+            json!({
+                "name": timing.name,
+                "duration_ms": timing.duration.map(ms),
+                "description": timing.description,
+            })
+        })
+        .collect();
+    let bound: Map<String, Value> = report
+        .bound
+        .iter()
+        .map(|(var, count)| (var.clone(), json!(count)))
+        .collect();
+
+    json!({
+        "timing": {
+            "queued_ms": ms(report.queued),
+            "request_ms": ms(report.request),
+            "server": server,
+            "network_ms": report.network().map(ms),
+            "download_ms": ms(report.download),
+            "deserialize_ms": ms(report.deserialize),
+            "total_ms": ms(report.total),
+        },
+        "bindings": report.bindings,
+        "bytes": report.response_bytes,
+        "inserts": report.inserts,
+        "deletes": report.deletes,
+        "retries": report.transaction_retry_count,
+        "bound": bound,
+    })
+}
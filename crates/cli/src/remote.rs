@@ -5,6 +5,8 @@ use anyhow::Result;
 use terminusdb_client::TerminusDBHttpClient;
 use url::Url;
 
+use crate::output::Output;
+
 /// Helper function to parse remote authentication string
 fn parse_remote_auth(auth_str: &str) -> Result<(String, String)> {
     let parts: Vec<&str> = auth_str.splitn(2, ':').collect();
@@ -22,6 +24,7 @@ pub(crate) async fn run_remote_add(
     database: String,
     name: String,
     url: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -29,8 +32,7 @@ pub(crate) async fn run_remote_add(
     let path = format!("{}/{}", org, database);
     let result = client.add_remote(&path, &name, &url).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_remote_list(
@@ -39,6 +41,7 @@ pub(crate) async fn run_remote_list(
     password: String,
     org: String,
     database: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -46,8 +49,7 @@ pub(crate) async fn run_remote_list(
     let path = format!("{}/{}", org, database);
     let remotes = client.list_remotes(&path).await?;
 
-    println!("{}", serde_json::to_string_pretty(&remotes)?);
-    Ok(())
+    output.print_value(&remotes)
 }
 
 pub(crate) async fn run_remote_get(
@@ -57,6 +59,7 @@ pub(crate) async fn run_remote_get(
     org: String,
     database: String,
     name: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -64,8 +67,7 @@ pub(crate) async fn run_remote_get(
     let path = format!("{}/{}", org, database);
     let remote = client.get_remote(&path, &name).await?;
 
-    println!("{}", serde_json::to_string_pretty(&remote)?);
-    Ok(())
+    output.print_value(&remote)
 }
 
 pub(crate) async fn run_remote_update(
@@ -76,6 +78,7 @@ pub(crate) async fn run_remote_update(
     database: String,
     name: String,
     url: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -83,8 +86,7 @@ pub(crate) async fn run_remote_update(
     let path = format!("{}/{}", org, database);
     let result = client.update_remote(&path, &name, &url).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_remote_delete(
@@ -94,6 +96,7 @@ pub(crate) async fn run_remote_delete(
     org: String,
     database: String,
     name: String,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -101,8 +104,7 @@ pub(crate) async fn run_remote_delete(
     let path = format!("{}/{}", org, database);
     let result = client.delete_remote(&path, &name).await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_clone(
@@ -115,6 +117,7 @@ pub(crate) async fn run_clone(
     label: Option<String>,
     comment: Option<String>,
     remote_auth: Option<String>,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -137,8 +140,7 @@ pub(crate) async fn run_clone(
         )
         .await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_fetch(
@@ -151,6 +153,7 @@ pub(crate) async fn run_fetch(
     remote_url: String,
     remote_branch: String,
     remote_auth: Option<String>,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -166,8 +169,7 @@ pub(crate) async fn run_fetch(
         .fetch(&path, &remote_url, Some(&remote_branch), auth, None)
         .await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_pull(
@@ -182,6 +184,7 @@ pub(crate) async fn run_pull(
     author: String,
     message: String,
     remote_auth: Option<String>,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -205,8 +208,7 @@ pub(crate) async fn run_pull(
         )
         .await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
 
 pub(crate) async fn run_push(
@@ -219,6 +221,7 @@ pub(crate) async fn run_push(
     remote_url: String,
     remote_branch: Option<String>,
     remote_auth: Option<String>,
+    output: Output,
 ) -> Result<()> {
     let parsed_url = Url::parse(&host)?;
     let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
@@ -234,6 +237,5 @@ pub(crate) async fn run_push(
         .push(&path, &remote_url, remote_branch.as_deref(), auth, None)
         .await?;
 
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    Ok(())
+    output.print_value(&result)
 }
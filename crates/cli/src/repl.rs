@@ -0,0 +1,431 @@
+//! `tdb repl`: an interactive WOQL session.
+//!
+//! Queries are written in the WOQL DSL (or as JSON-LD) and may span several
+//! lines: input is read until its brackets balance. Results are shown in the
+//! session's format and paged when they are longer than the page size.
+//! Backslash commands inspect the schema and change the session; `\?` lists
+//! them. Query history and the prefixes defined with `\prefix` are kept in the
+//! config directory between sessions.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{bail, Context, Result};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use serde_json::Value;
+use terminusdb_client::{BranchSpec, TerminusDBHttpClient};
+use url::Url;
+
+use crate::query::{self, QueryFormat};
+use crate::schema_dsl;
+
+const HELP: &str = "\
+Enter a WOQL query (DSL or JSON-LD); it runs once its brackets balance.
+
+  \\d                   List the classes and enums of the schema
+  \\d <name>            Show a class or enum
+  \\prefix              List prefixes
+  \\prefix <name> <iri> Define a prefix: \"<name>:x\" in a query becomes \"<iri>x\"
+  \\unprefix <name>     Remove a prefix
+  \\branch <name>       Query another branch
+  \\format <format>     Show results as table, json or csv
+  \\page <lines>        Page results longer than this (0 to turn paging off)
+  \\?                   Show this help
+  \\q                   Quit (or Ctrl+D)
+
+Ctrl+C discards the query being typed.";
+
+/// A backslash command
+#[derive(Debug, PartialEq)]
+enum Command<'a> {
+    Quit,
+    Help,
+    Describe(Option<&'a str>),
+    Prefixes,
+    Prefix(&'a str, &'a str),
+    Unprefix(&'a str),
+    Branch(&'a str),
+    Format(QueryFormat),
+    Page(usize),
+}
+
+impl<'a> Command<'a> {
+    fn parse(line: &'a str) -> Result<Self> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        let command = match (name, args.as_slice()) {
+            ("\\q" | "\\quit", []) => Command::Quit,
+            ("\\?" | "\\h" | "\\help", []) => Command::Help,
+            ("\\d", []) => Command::Describe(None),
+            ("\\d", [name]) => Command::Describe(Some(*name)),
+            ("\\prefix", []) => Command::Prefixes,
+            ("\\prefix", [name, iri]) => Command::Prefix(name.trim_end_matches(':'), *iri),
+            ("\\unprefix", [name]) => Command::Unprefix(name.trim_end_matches(':')),
+            ("\\branch", [name]) => Command::Branch(*name),
+            ("\\format", [format]) => Command::Format(QueryFormat::parse(format)?),
+            ("\\page", [lines]) => Command::Page(
+                lines
+                    .parse()
+                    .with_context(|| format!("invalid page size '{}'", lines))?,
+            ),
+            _ => bail!(
+                "unknown command or wrong arguments: {} (\\? for help)",
+                line
+            ),
+        };
+        Ok(command)
+    }
+}
+
+/// Whether the text so far is a whole query: no string left open and every
+/// bracket closed
+fn is_complete(text: &str) -> bool {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    !in_string && depth <= 0
+}
+
+/// Expand `"<prefix>:rest"` string literals with the session's prefixes
+fn expand_prefixes(query: &str, prefixes: &BTreeMap<String, String>) -> String {
+    if prefixes.is_empty() {
+        return query.to_string();
+    }
+    let mut out = String::with_capacity(query.len());
+    let mut rest = query;
+    while let Some(start) = rest.find('"') {
+        out.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        // Find the closing quote, skipping escapes
+        let mut end = None;
+        let mut escaped = false;
+        for (i, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            break;
+        };
+        let literal = &rest[..end];
+        let expanded = literal.split_once(':').and_then(|(prefix, local)| {
+            prefixes.get(prefix).map(|iri| format!("{}{}", iri, local))
+        });
+        out.push_str(expanded.as_deref().unwrap_or(literal));
+        out.push('"');
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Where the session keeps its history and prefixes
+fn state_file(name: &str) -> Result<PathBuf> {
+    let dir = crate::auth::config_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create config directory: {:?}", dir))?;
+    Ok(dir.join(name))
+}
+
+fn load_prefixes() -> Result<BTreeMap<String, String>> {
+    let path = state_file("repl_prefixes.toml")?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read prefixes: {:?}", path))?;
+    toml::from_str(&contents).with_context(|| format!("Failed to parse prefixes: {:?}", path))
+}
+
+fn save_prefixes(prefixes: &BTreeMap<String, String>) -> Result<()> {
+    let path = state_file("repl_prefixes.toml")?;
+    let contents = toml::to_string_pretty(prefixes).context("Failed to serialize prefixes")?;
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write prefixes: {:?}", path))
+}
+
+struct Session {
+    client: TerminusDBHttpClient,
+    database: String,
+    branch: String,
+    format: QueryFormat,
+    page_size: usize,
+    prefixes: BTreeMap<String, String>,
+}
+
+impl Session {
+    fn prompt(&self) -> String {
+        format!("{}:{}> ", self.database, self.branch)
+    }
+
+    /// Run a backslash command; returns false to end the session
+    async fn command(&mut self, line: &str) -> Result<bool> {
+        match Command::parse(line)? {
+            Command::Quit => return Ok(false),
+            Command::Help => println!("{}", HELP),
+            Command::Describe(name) => self.describe(name).await?,
+            Command::Prefixes => {
+                for (name, iri) in &self.prefixes {
+                    println!("{}: {}", name, iri);
+                }
+            }
+            Command::Prefix(name, iri) => {
+                self.prefixes.insert(name.to_string(), iri.to_string());
+                save_prefixes(&self.prefixes)?;
+            }
+            Command::Unprefix(name) => {
+                if self.prefixes.remove(name).is_none() {
+                    bail!("no prefix '{}'", name);
+                }
+                save_prefixes(&self.prefixes)?;
+            }
+            Command::Branch(name) => {
+                if !self.client.branch_exists(&self.database, name).await? {
+                    bail!("branch '{}' not found", name);
+                }
+                self.branch = name.to_string();
+            }
+            Command::Format(format) => self.format = format,
+            Command::Page(lines) => self.page_size = lines,
+        }
+        Ok(true)
+    }
+
+    /// `\d`: one line per class and enum, or one document in schema DSL
+    async fn describe(&self, name: Option<&str>) -> Result<()> {
+        let spec = BranchSpec::with_branch(&self.database, &self.branch);
+        let docs = self.client.get_schema_documents(&spec).await?;
+        let id = |doc: &Value| doc.get("@id").and_then(Value::as_str).map(str::to_string);
+
+        let Some(name) = name else {
+            let mut rows: Vec<(String, &str)> = docs
+                .iter()
+                .filter_map(|doc| {
+                    let ty = doc.get("@type").and_then(Value::as_str)?;
+                    Some((id(doc)?, schema_dsl::keyword(ty)))
+                })
+                .collect();
+            rows.sort();
+            for (id, keyword) in rows {
+                println!("{:<7} {}", keyword, id);
+            }
+            return Ok(());
+        };
+
+        match docs.iter().find(|doc| id(doc).as_deref() == Some(name)) {
+            Some(doc) => print!("{}", schema_dsl::render(std::slice::from_ref(doc))),
+            None => bail!("'{}' is not in the schema", name),
+        }
+        Ok(())
+    }
+
+    async fn query(&self, text: &str) -> Result<()> {
+        let spec = BranchSpec::with_branch(&self.database, &self.branch);
+        let started = Instant::now();
+        let result =
+            query::execute(&self.client, spec, &expand_prefixes(text, &self.prefixes)).await?;
+        let elapsed = started.elapsed();
+
+        page(&query::render(self.format, &result)?, self.page_size)?;
+        eprintln!(
+            "({} rows, {} ms)",
+            result.bindings.len(),
+            elapsed.as_millis()
+        );
+        Ok(())
+    }
+}
+
+/// Print `text`, pausing after every `page_size` lines until Enter is
+/// pressed; `q` stops the output. Output that is not a terminal is not paged.
+fn page(text: &str, page_size: usize) -> Result<()> {
+    if page_size == 0 || !atty::is(atty::Stream::Stdout) {
+        print!("{}", text);
+        return Ok(());
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let mut stdout = io::stdout().lock();
+    let mut shown = 0;
+    for chunk in lines.chunks(page_size) {
+        for line in chunk {
+            writeln!(stdout, "{}", line)?;
+        }
+        shown += chunk.len();
+        if shown == lines.len() {
+            break;
+        }
+        stdout.flush()?;
+        eprint!(
+            "-- {} more lines: Enter for more, q to stop -- ",
+            lines.len() - shown
+        );
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        if answer.trim().eq_ignore_ascii_case("q") {
+            break;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run_repl(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    format: String,
+    page_size: usize,
+) -> Result<()> {
+    let format = QueryFormat::parse(&format)?;
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let mut session = Session {
+        client,
+        database,
+        branch,
+        format,
+        page_size,
+        prefixes: load_prefixes()?,
+    };
+
+    let history = state_file("repl_history")?;
+    let mut editor = DefaultEditor::new()?;
+    // A missing history file is normal on the first run
+    let _ = editor.load_history(&history);
+
+    eprintln!(
+        "Connected to {}/{} ({}). \\? for help.",
+        org, session.database, host
+    );
+    let mut lines: Vec<String> = Vec::new();
+    loop {
+        let prompt = if lines.is_empty() {
+            session.prompt()
+        } else {
+            format!("{:>width$}", "...> ", width = session.prompt().len())
+        };
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                lines.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if lines.is_empty() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed.starts_with('\\') {
+                editor.add_history_entry(trimmed)?;
+                match session.command(trimmed).await {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(e) => eprintln!("Error: {:#}", e),
+                }
+                continue;
+            }
+        }
+
+        lines.push(line);
+        let text = lines.join("\n");
+        if !is_complete(&text) {
+            continue;
+        }
+        lines.clear();
+
+        let entry: Vec<&str> = text.lines().map(str::trim).collect();
+        editor.add_history_entry(entry.join(" "))?;
+        if let Err(e) = session.query(&text).await {
+            eprintln!("Error: {:#}", e);
+        }
+    }
+
+    editor.save_history(&history)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Command::parse("\\q").unwrap(), Command::Quit);
+        assert_eq!(Command::parse("\\d").unwrap(), Command::Describe(None));
+        assert_eq!(
+            Command::parse("\\d  Person").unwrap(),
+            Command::Describe(Some("Person"))
+        );
+        assert_eq!(
+            Command::parse("\\prefix ex: http://example.com/").unwrap(),
+            Command::Prefix("ex", "http://example.com/")
+        );
+        assert_eq!(
+            Command::parse("\\format CSV").unwrap(),
+            Command::Format(QueryFormat::Csv)
+        );
+        assert_eq!(Command::parse("\\page 0").unwrap(), Command::Page(0));
+        assert!(Command::parse("\\page many").is_err());
+        assert!(Command::parse("\\d a b").is_err());
+        assert!(Command::parse("\\x").is_err());
+    }
+
+    #[test]
+    fn test_is_complete() {
+        assert!(is_complete("triple($S, $P, $O)"));
+        assert!(!is_complete("and(\n  triple($S, $P, $O),"));
+        assert!(is_complete(
+            "and(\n  triple($S, $P, $O),\n  eq($S, \"a)\")\n)"
+        ));
+        assert!(!is_complete("eq($S, \"open ("));
+        assert!(is_complete("eq($S, \"quote \\\" (\")"));
+    }
+
+    #[test]
+    fn test_expand_prefixes() {
+        let prefixes = BTreeMap::from([("ex".to_string(), "http://example.com/".to_string())]);
+        assert_eq!(
+            expand_prefixes(
+                r#"triple("ex:alice", "@schema:name", $N) "ex\"x" "#,
+                &prefixes
+            ),
+            r#"triple("http://example.com/alice", "@schema:name", $N) "ex\"x" "#
+        );
+        assert_eq!(expand_prefixes("eq($A, \"ex:", &prefixes), "eq($A, \"ex:");
+    }
+}
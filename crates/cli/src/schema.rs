@@ -0,0 +1,368 @@
+//! `tdb schema dump` and `tdb schema apply`: schema as code.
+//!
+//! `dump` prints the schema graph (classes, enums and the `@context`) as JSON
+//! (or YAML with `--output yaml`) or in the [`schema_dsl`](crate::schema_dsl)
+//! text form. `apply` reads a file
+//! in either form, compares it with the schema on the branch and previews the
+//! classes it adds or changes before writing them. Documents that exist only
+//! on the branch are left alone.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+use terminusdb_client::{BranchSpec, DocumentInsertArgs, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+use crate::schema_dsl;
+
+pub(crate) async fn run_schema_dump(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    format: String,
+    output: Output,
+) -> Result<()> {
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let docs = client.get_schema_documents(&spec).await?;
+
+    let output = match format.to_lowercase().as_str() {
+        "json" if output == Output::Table => Output::Json,
+        "json" | "dsl" => output,
+        _ => bail!("unknown format '{}', expected json or dsl", format),
+    };
+    output.print(&docs, || schema_dsl::render(&docs))
+}
+
+pub(crate) async fn run_schema_apply(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    file: String,
+    dry_run: bool,
+    author: String,
+    message: String,
+    output: Output,
+) -> Result<()> {
+    let text = std::fs::read_to_string(&file)
+        .with_context(|| format!("failed to read schema from {}", file))?;
+    let desired =
+        parse_schema_file(&text).with_context(|| format!("invalid schema in {}", file))?;
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let current = client.get_schema_documents(&spec).await?;
+
+    let plan = SchemaPlan::new(&current, &desired)?;
+    if !output.is_data() {
+        print!("{}", plan);
+    }
+
+    let applied = !plan.is_empty() && !dry_run;
+    if plan.is_empty() {
+        eprintln!("Schema is up to date.");
+    } else if dry_run {
+        eprintln!("Dry run: no changes applied.");
+    } else {
+        let docs = plan.documents();
+        let args = DocumentInsertArgs::from(spec)
+            .with_author(author)
+            .with_message(message)
+            .as_schema();
+        client.insert_documents(docs, args).await?;
+        eprintln!(
+            "Applied {} added and {} changed schema documents.",
+            plan.added.len(),
+            plan.changed.len()
+        );
+    }
+
+    if let Some(text) = output.render_data(&plan.data(applied))? {
+        print!("{}", text);
+    }
+    Ok(())
+}
+
+/// Write the documents of `desired` that are new or changed on the branch, in
+/// one commit. Returns how many were written.
+pub(crate) async fn apply_schema(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    desired: &[Value],
+    author: &str,
+    message: &str,
+) -> Result<usize> {
+    let current = client.get_schema_documents(spec).await?;
+    let docs = SchemaPlan::new(&current, desired)?.documents();
+    let written = docs.len();
+    if written > 0 {
+        let args = DocumentInsertArgs::from(spec.clone())
+            .with_author(author)
+            .with_message(message)
+            .as_schema();
+        client.insert_documents(docs, args).await?;
+    }
+    Ok(written)
+}
+
+/// Schema documents from a file: a JSON array (or single document), or DSL
+pub(crate) fn parse_schema_file(text: &str) -> Result<Vec<Value>> {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(docs)) => Ok(docs),
+        Ok(doc @ Value::Object(_)) => Ok(vec![doc]),
+        Ok(_) => bail!("expected a JSON array of schema documents"),
+        Err(_) => schema_dsl::parse(text),
+    }
+}
+
+/// The identity of a schema document: its `@id`, or `@context`
+fn document_id(doc: &Value) -> Result<&str> {
+    if doc.get("@type").and_then(Value::as_str) == Some("@context") {
+        return Ok("@context");
+    }
+    doc.get("@id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("schema document without an @id: {}", doc))
+}
+
+/// A document with equivalent spellings made equal, for comparison
+fn normalize(doc: &Value) -> Value {
+    let mut doc = doc.clone();
+    if let Some(Value::String(parent)) = doc.get("@inherits") {
+        doc["@inherits"] = Value::Array(vec![Value::String(parent.clone())]);
+    }
+    doc
+}
+
+/// What applying a schema file changes on the branch
+#[derive(Debug)]
+struct SchemaPlan<'a> {
+    added: Vec<&'a Value>,
+    /// (current, desired)
+    changed: Vec<(&'a Value, &'a Value)>,
+    unchanged: usize,
+    /// Documents on the branch that the file does not mention
+    untouched: Vec<&'a str>,
+}
+
+impl<'a> SchemaPlan<'a> {
+    fn new(current: &'a [Value], desired: &'a [Value]) -> Result<Self> {
+        let mut by_id: HashMap<&str, &Value> = HashMap::new();
+        for doc in current {
+            by_id.insert(document_id(doc)?, doc);
+        }
+
+        let mut plan = SchemaPlan {
+            added: Vec::new(),
+            changed: Vec::new(),
+            unchanged: 0,
+            untouched: Vec::new(),
+        };
+        for doc in desired {
+            match by_id.remove(document_id(doc)?) {
+                None => plan.added.push(doc),
+                Some(old) if normalize(old) == normalize(doc) => plan.unchanged += 1,
+                Some(old) => plan.changed.push((old, doc)),
+            }
+        }
+        plan.untouched = by_id.into_keys().collect();
+        plan.untouched.sort();
+        Ok(plan)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty()
+    }
+
+    /// Documents to write, the `@context` first
+    fn documents(&self) -> Vec<&'a Value> {
+        let mut docs: Vec<&Value> = self
+            .added
+            .iter()
+            .copied()
+            .chain(self.changed.iter().map(|(_, new)| *new))
+            .collect();
+        docs.sort_by_key(|doc| document_id(doc).ok() != Some("@context"));
+        docs
+    }
+
+    /// The plan as data, by document id, for `--output json|yaml`
+    fn data(&self, applied: bool) -> Value {
+        let id = |doc: &Value| document_id(doc).unwrap_or_default().to_string();
+        json!({
+            "added": self.added.iter().map(|doc| id(doc)).collect::<Vec<_>>(),
+            "changed": self.changed.iter().map(|(_, new)| id(new)).collect::<Vec<_>>(),
+            "unchanged": self.unchanged,
+            "untouched": self.untouched,
+            "applied": applied,
+        })
+    }
+}
+
+/// `class Person`, `enum Color`, `context`
+fn describe(doc: &Value) -> String {
+    let ty = doc.get("@type").and_then(Value::as_str).unwrap_or_default();
+    if ty == "@context" {
+        return "context".to_string();
+    }
+    let id = doc.get("@id").and_then(Value::as_str).unwrap_or_default();
+    format!("{} {}", schema_dsl::keyword(ty), id)
+}
+
+/// Field-level differences between two versions of a document
+fn field_changes(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<String> {
+    let show = |name: &str, value: &Value| {
+        if name.starts_with('@') {
+            value.to_string()
+        } else {
+            schema_dsl::render_type(value)
+        }
+    };
+
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| match (old.get(name), new.get(name)) {
+            (None, Some(value)) => Some(format!("+ {}: {}", name, show(name, value))),
+            (Some(value), None) => Some(format!("- {}: {}", name, show(name, value))),
+            (Some(before), Some(after)) if before != after => Some(format!(
+                "~ {}: {} -> {}",
+                name,
+                show(name, before),
+                show(name, after)
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+impl std::fmt::Display for SchemaPlan<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for doc in &self.added {
+            writeln!(f, "+ {}", describe(doc))?;
+        }
+        for (old, new) in &self.changed {
+            writeln!(f, "~ {}", describe(new))?;
+            let (old, new) = (normalize(old), normalize(new));
+            if let (Some(old), Some(new)) = (old.as_object(), new.as_object()) {
+                for change in field_changes(old, new) {
+                    writeln!(f, "    {}", change)?;
+                }
+            }
+        }
+        writeln!(
+            f,
+            "{} added, {} changed, {} unchanged, {} only on the branch",
+            self.added.len(),
+            self.changed.len(),
+            self.unchanged,
+            self.untouched.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_preview() {
+        let current = vec![
+            json!({ "@type": "@context", "@base": "terminusdb:///data/", "@schema": "terminusdb:///schema#" }),
+            json!({
+                "@type": "Class",
+                "@id": "Person",
+                "@inherits": "Named",
+                "name": "xsd:string",
+                "nick": "xsd:string"
+            }),
+            json!({ "@type": "Class", "@id": "Named", "@abstract": [] }),
+            json!({ "@type": "Enum", "@id": "Legacy", "@value": ["a"] }),
+        ];
+        let desired = schema_dsl::parse(
+            r#"
+context {
+  @base "terminusdb:///data/"
+  @schema "terminusdb:///schema#"
+}
+
+@key Random
+class Person : Named {
+  name: xsd:string
+  nick: Optional<xsd:string>
+  age: xsd:integer
+}
+
+@abstract
+class Named {
+}
+
+enum Color {
+  red
+}
+"#,
+        )
+        .unwrap();
+
+        let plan = SchemaPlan::new(&current, &desired).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "+ enum Color\n\
+             ~ class Person\n    \
+             + @key: {\"@type\":\"Random\"}\n    \
+             + age: xsd:integer\n    \
+             ~ nick: xsd:string -> Optional<xsd:string>\n\
+             1 added, 1 changed, 2 unchanged, 1 only on the branch\n"
+        );
+        assert_eq!(plan.untouched, vec!["Legacy"]);
+        assert_eq!(
+            plan.data(false),
+            json!({
+                "added": ["Color"],
+                "changed": ["Person"],
+                "unchanged": 2,
+                "untouched": ["Legacy"],
+                "applied": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_documents_put_context_first() {
+        let current = vec![json!({ "@type": "@context", "@schema": "a#" })];
+        let desired = vec![
+            json!({ "@type": "Class", "@id": "Person" }),
+            json!({ "@type": "@context", "@schema": "b#" }),
+        ];
+        let plan = SchemaPlan::new(&current, &desired).unwrap();
+        let ids: Vec<&str> = plan
+            .documents()
+            .into_iter()
+            .map(|doc| document_id(doc).unwrap())
+            .collect();
+        assert_eq!(ids, vec!["@context", "Person"]);
+    }
+
+    #[test]
+    fn test_parse_schema_file() {
+        let docs = parse_schema_file(r#"{"@type": "Class", "@id": "Person"}"#).unwrap();
+        assert_eq!(docs.len(), 1);
+        let docs = parse_schema_file("class Person {\n}\n").unwrap();
+        assert_eq!(docs, vec![json!({ "@type": "Class", "@id": "Person" })]);
+        assert!(parse_schema_file("42").is_err());
+    }
+}
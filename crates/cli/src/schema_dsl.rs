@@ -0,0 +1,598 @@
+//! A compact text form of TerminusDB schema documents, for `tdb schema dump`
+//! and `tdb schema apply`.
+//!
+//! ```text
+//! context {
+//!   @base "terminusdb:///data/"
+//!   @schema "terminusdb:///schema#"
+//! }
+//!
+//! /// Someone we know
+//! @key Lexical(name)
+//! class Person : Named {
+//!   /// Nickname, if any
+//!   nick: Optional<xsd:string>
+//!   friends: Set<Person>
+//! }
+//!
+//! enum Color {
+//!   red
+//!   green
+//! }
+//! ```
+//!
+//! Each document is a block whose keyword gives its `@type` (`class`, `enum`,
+//! `union` for tagged unions, `foreign`). `@`-lines before the block are the
+//! document's own `@` fields: bare for `[]` flags such as `@subdocument`,
+//! `Lexical(a, b)` style for keys, compact JSON for anything else. `///` lines
+//! hold documentation. Property types use `Optional<T>`, `List<T>`, `Set<T>`,
+//! `Array<T, dimensions>` and `Cardinality<T, min, max>` (`_` for no bound);
+//! any other type is written as JSON. Lines starting with `#` are comments.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+
+/// Block keywords and the `@type` they stand for
+const KEYWORDS: &[(&str, &str)] = &[
+    ("class", "Class"),
+    ("enum", "Enum"),
+    ("union", "TaggedUnion"),
+    ("foreign", "Foreign"),
+];
+
+/// Collection types written as `Name<T>`
+const WRAPPERS: &[&str] = &["Optional", "List", "Set"];
+
+/// The DSL keyword for a schema document `@type`
+pub(crate) fn keyword(ty: &str) -> &str {
+    KEYWORDS
+        .iter()
+        .find(|(_, t)| *t == ty)
+        .map_or(ty, |(keyword, _)| keyword)
+}
+
+/// Render schema documents, one block per document
+pub(crate) fn render(docs: &[Value]) -> String {
+    let blocks: Vec<String> = docs.iter().map(render_doc).collect();
+    blocks.join("\n")
+}
+
+fn render_doc(doc: &Value) -> String {
+    let Some(obj) = doc.as_object() else {
+        return format!("# not a schema document: {}\n", doc);
+    };
+    let ty = obj.get("@type").and_then(Value::as_str).unwrap_or_default();
+
+    let mut out = String::new();
+    if ty == "@context" {
+        out.push_str("context {\n");
+        for name in sorted_keys(obj).filter(|name| *name != "@type") {
+            out.push_str(&format!("  {} {}\n", name, obj[name]));
+        }
+        out.push_str("}\n");
+        return out;
+    }
+
+    let is_enum = ty == "Enum";
+    let members: Vec<&str> = if is_enum {
+        obj.get("@value")
+            .and_then(Value::as_array)
+            .map(|values| values.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    } else {
+        obj.keys()
+            .filter(|name| !name.starts_with('@'))
+            .map(String::as_str)
+            .collect()
+    };
+    let docs_key = if is_enum { "@values" } else { "@properties" };
+    let documentation = obj
+        .get("@documentation")
+        .filter(|documentation| is_simple_documentation(documentation, docs_key, &members));
+
+    if let Some(comment) = documentation.and_then(|d| d.get("@comment")) {
+        push_doc_comment(&mut out, "", comment);
+    }
+    for name in sorted_keys(obj) {
+        let skip = matches!(name, "@id" | "@type" | "@inherits")
+            || (name == "@documentation" && documentation.is_some())
+            || (name == "@value" && is_enum)
+            || !name.starts_with('@');
+        if !skip {
+            out.push_str(&render_annotation(name, &obj[name]));
+            out.push('\n');
+        }
+    }
+
+    let id = obj.get("@id").and_then(Value::as_str).unwrap_or_default();
+    out.push_str(&format!("{} {}", keyword(ty), id));
+    let parents: Vec<&str> = match obj.get("@inherits") {
+        Some(Value::String(parent)) => vec![parent],
+        Some(Value::Array(parents)) => parents.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    if !parents.is_empty() {
+        out.push_str(&format!(" : {}", parents.join(", ")));
+    }
+    out.push_str(" {\n");
+
+    let member_docs = documentation.and_then(|d| d.get(docs_key));
+    for member in members {
+        if let Some(comment) = member_docs.and_then(|docs| docs.get(member)) {
+            push_doc_comment(&mut out, "  ", comment);
+        }
+        if is_enum {
+            out.push_str(&format!("  {}\n", render_enum_value(member)));
+        } else {
+            out.push_str(&format!("  {}: {}\n", member, render_type(&obj[member])));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Keys in a fixed order (`@key` first), whatever the map's own order
+fn sorted_keys(obj: &Map<String, Value>) -> impl Iterator<Item = &str> {
+    let mut keys: Vec<&str> = obj.keys().map(String::as_str).collect();
+    keys.sort_by_key(|key| (*key != "@key", *key));
+    keys.into_iter()
+}
+
+/// Whether `@documentation` fits in `///` comments: a `@comment` and
+/// per-member comments for members the document has
+fn is_simple_documentation(documentation: &Value, docs_key: &str, members: &[&str]) -> bool {
+    let Some(documentation) = documentation.as_object() else {
+        return false;
+    };
+    documentation.iter().all(|(key, value)| match key.as_str() {
+        "@comment" => value.is_string(),
+        key if key == docs_key => value.as_object().is_some_and(|docs| {
+            docs.iter()
+                .all(|(member, comment)| comment.is_string() && members.contains(&member.as_str()))
+        }),
+        _ => false,
+    })
+}
+
+fn push_doc_comment(out: &mut String, indent: &str, comment: &Value) {
+    for line in comment.as_str().unwrap_or_default().lines() {
+        out.push_str(&format!("{}/// {}\n", indent, line).replace("/// \n", "///\n"));
+    }
+}
+
+fn render_annotation(name: &str, value: &Value) -> String {
+    if value.as_array().is_some_and(Vec::is_empty) {
+        return name.to_string();
+    }
+    if name == "@key" {
+        if let Some(key) = render_key(value) {
+            return format!("@key {}", key);
+        }
+    }
+    format!("{} {}", name, value)
+}
+
+/// `Lexical(a, b)` for keys with fields, the bare key type otherwise
+fn render_key(key: &Value) -> Option<String> {
+    let obj = key.as_object()?;
+    let ty = obj.get("@type")?.as_str()?;
+    match obj.get("@fields") {
+        None if obj.len() == 1 => Some(ty.to_string()),
+        Some(Value::Array(fields)) if obj.len() == 2 => {
+            let fields: Option<Vec<&str>> = fields.iter().map(Value::as_str).collect();
+            Some(format!("{}({})", ty, fields?.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+fn render_enum_value(value: &str) -> String {
+    let bare = !value.is_empty()
+        && !value.starts_with(['#', '/', '"'])
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    if bare {
+        value.to_string()
+    } else {
+        Value::String(value.to_string()).to_string()
+    }
+}
+
+/// A property type in DSL form
+pub(crate) fn render_type(ty: &Value) -> String {
+    if let Some(class) = ty.as_str() {
+        return class.to_string();
+    }
+    render_collection_type(ty).unwrap_or_else(|| ty.to_string())
+}
+
+fn render_collection_type(ty: &Value) -> Option<String> {
+    let obj = ty.as_object()?;
+    let kind = obj.get("@type")?.as_str()?;
+    let class = render_type(obj.get("@class")?);
+    let bound = |name: &str| match obj.get(name) {
+        Some(Value::Number(n)) => Some(n.to_string()),
+        None => Some("_".to_string()),
+        _ => None,
+    };
+    match kind {
+        _ if WRAPPERS.contains(&kind) && obj.len() == 2 => Some(format!("{}<{}>", kind, class)),
+        "Array" if obj.len() == 2 => Some(format!("Array<{}>", class)),
+        "Array" if obj.len() == 3 => {
+            let dimensions = obj.get("@dimensions")?.as_u64()?;
+            Some(format!("Array<{}, {}>", class, dimensions))
+        }
+        "Cardinality" => {
+            let known = ["@type", "@class", "@min_cardinality", "@max_cardinality"];
+            if !obj.keys().all(|key| known.contains(&key.as_str())) {
+                return None;
+            }
+            Some(format!(
+                "Cardinality<{}, {}, {}>",
+                class,
+                bound("@min_cardinality")?,
+                bound("@max_cardinality")?
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Parse DSL text into schema documents
+pub(crate) fn parse(text: &str) -> Result<Vec<Value>> {
+    let mut docs = Vec::new();
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let mut comment: Vec<&str> = Vec::new();
+    let mut annotations = Map::new();
+    while let Some((number, line)) = lines.next() {
+        let result = if let Some(text) = doc_comment(line) {
+            comment.push(text);
+            Ok(())
+        } else if line.starts_with('@') {
+            parse_annotation(line).map(|(name, value)| {
+                annotations.insert(name, value);
+            })
+        } else if line.ends_with('{') {
+            let doc = parse_block(
+                number,
+                line,
+                &mut lines,
+                std::mem::take(&mut comment),
+                std::mem::take(&mut annotations),
+            )?;
+            docs.push(doc);
+            Ok(())
+        } else {
+            Err(anyhow!("expected a schema block, found '{}'", line))
+        };
+        result.map_err(|err| at_line(number, err))?;
+    }
+
+    if !comment.is_empty() || !annotations.is_empty() {
+        bail!("documentation or annotations at the end of the schema without a block");
+    }
+    Ok(docs)
+}
+
+fn at_line(number: usize, err: anyhow::Error) -> anyhow::Error {
+    anyhow!("line {}: {:#}", number, err)
+}
+
+fn doc_comment(line: &str) -> Option<&str> {
+    line.strip_prefix("///")
+        .map(|text| text.strip_prefix(' ').unwrap_or(text))
+}
+
+fn parse_annotation(line: &str) -> Result<(String, Value)> {
+    let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let value = value.trim();
+    let value = if value.is_empty() {
+        json!([])
+    } else if name == "@key" && !value.starts_with('{') {
+        parse_key(value)?
+    } else {
+        serde_json::from_str(value)
+            .with_context(|| format!("invalid JSON value for {}: {}", name, value))?
+    };
+    Ok((name.to_string(), value))
+}
+
+fn parse_key(key: &str) -> Result<Value> {
+    let Some((ty, fields)) = key.split_once('(') else {
+        return Ok(json!({ "@type": key }));
+    };
+    let fields = fields
+        .strip_suffix(')')
+        .ok_or_else(|| anyhow!("unterminated key fields in '{}'", key))?;
+    let fields: Vec<&str> = fields
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .collect();
+    Ok(json!({ "@type": ty.trim(), "@fields": fields }))
+}
+
+fn parse_block<'a>(
+    number: usize,
+    header: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    comment: Vec<&str>,
+    mut doc: Map<String, Value>,
+) -> Result<Value> {
+    let header = header.trim_end_matches('{').trim();
+    let (keyword, rest) = header
+        .split_once(char::is_whitespace)
+        .unwrap_or((header, ""));
+
+    if keyword == "context" {
+        if !rest.is_empty() || !comment.is_empty() || !doc.is_empty() {
+            bail!(
+                "line {}: the context block takes no name, annotations or documentation",
+                number
+            );
+        }
+        doc.insert("@type".to_string(), json!("@context"));
+        for (number, line) in lines.by_ref() {
+            if line == "}" {
+                return Ok(Value::Object(doc));
+            }
+            let (name, value) = parse_annotation(line).map_err(|err| at_line(number, err))?;
+            doc.insert(name, value);
+        }
+        bail!("line {}: unterminated context block", number);
+    }
+
+    let ty = KEYWORDS
+        .iter()
+        .find(|(k, _)| *k == keyword)
+        .map_or(keyword, |(_, ty)| ty);
+    let (id, parents) = match rest.split_once(':') {
+        Some((id, parents)) => (id.trim(), parents.split(',').map(str::trim).collect()),
+        None => (rest.trim(), Vec::new()),
+    };
+    if id.is_empty() {
+        bail!("line {}: missing name after '{}'", number, keyword);
+    }
+    doc.insert("@type".to_string(), json!(ty));
+    doc.insert("@id".to_string(), json!(id));
+    if !parents.is_empty() {
+        doc.insert("@inherits".to_string(), json!(parents));
+    }
+
+    let is_enum = ty == "Enum";
+    let mut values = Vec::new();
+    let mut member_docs = Map::new();
+    let mut member_comment: Vec<&str> = Vec::new();
+    let mut closed = false;
+    for (number, line) in lines.by_ref() {
+        if line == "}" {
+            closed = true;
+            break;
+        }
+        if let Some(text) = doc_comment(line) {
+            member_comment.push(text);
+            continue;
+        }
+        let member = if is_enum {
+            let value = if line.starts_with('"') {
+                serde_json::from_str::<String>(line)
+                    .with_context(|| format!("line {}: invalid enum value {}", number, line))?
+            } else {
+                line.to_string()
+            };
+            values.push(json!(value));
+            value
+        } else {
+            let (name, ty) = line
+                .split_once(':')
+                .map(|(name, ty)| (name.trim(), ty))
+                .filter(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+                .ok_or_else(|| {
+                    anyhow!("line {}: expected 'name: Type', found '{}'", number, line)
+                })?;
+            let name = name.to_string();
+            let ty = parse_type(ty.trim()).map_err(|err| at_line(number, err))?;
+            doc.insert(name.clone(), ty);
+            name
+        };
+        if !member_comment.is_empty() {
+            member_docs.insert(member, json!(member_comment.join("\n")));
+            member_comment.clear();
+        }
+    }
+    if !closed {
+        bail!("line {}: unterminated {} block", number, keyword);
+    }
+    if is_enum {
+        doc.insert("@value".to_string(), Value::Array(values));
+    }
+
+    if !comment.is_empty() || !member_docs.is_empty() {
+        let mut documentation = Map::new();
+        if !comment.is_empty() {
+            documentation.insert("@comment".to_string(), json!(comment.join("\n")));
+        }
+        if !member_docs.is_empty() {
+            let key = if is_enum { "@values" } else { "@properties" };
+            documentation.insert(key.to_string(), Value::Object(member_docs));
+        }
+        doc.insert("@documentation".to_string(), Value::Object(documentation));
+    }
+    Ok(Value::Object(doc))
+}
+
+/// Parse a property type in DSL form
+fn parse_type(ty: &str) -> Result<Value> {
+    if ty.starts_with('{') {
+        return serde_json::from_str(ty).with_context(|| format!("invalid JSON type {}", ty));
+    }
+    let Some((kind, args)) = ty.split_once('<') else {
+        if ty.is_empty() || ty.contains(['>', ',']) {
+            bail!("invalid type '{}'", ty);
+        }
+        return Ok(json!(ty));
+    };
+    let args = args
+        .strip_suffix('>')
+        .ok_or_else(|| anyhow!("unterminated type '{}'", ty))?;
+    let args = split_type_args(args);
+    let class = parse_type(args[0])?;
+    let number = |arg: &str| -> Result<Option<u64>> {
+        match arg {
+            "_" => Ok(None),
+            arg => arg
+                .parse()
+                .map(Some)
+                .map_err(|_| anyhow!("expected a number or _ in '{}', found '{}'", ty, arg)),
+        }
+    };
+    match (kind, args.len()) {
+        (kind, 1) if WRAPPERS.contains(&kind) || kind == "Array" => {
+            Ok(json!({ "@type": kind, "@class": class }))
+        }
+        ("Array", 2) => {
+            let dimensions = number(args[1])?
+                .ok_or_else(|| anyhow!("Array dimensions cannot be _ in '{}'", ty))?;
+            Ok(json!({ "@type": "Array", "@class": class, "@dimensions": dimensions }))
+        }
+        ("Cardinality", 3) => {
+            let mut cardinality = json!({ "@type": "Cardinality", "@class": class });
+            if let Some(min) = number(args[1])? {
+                cardinality["@min_cardinality"] = json!(min);
+            }
+            if let Some(max) = number(args[2])? {
+                cardinality["@max_cardinality"] = json!(max);
+            }
+            Ok(cardinality)
+        }
+        _ => bail!("unknown type constructor '{}'", ty),
+    }
+}
+
+/// Split type arguments at the commas that are not inside a nested `<...>`
+fn split_type_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Vec<Value> {
+        vec![
+            json!({
+                "@type": "@context",
+                "@base": "terminusdb:///data/",
+                "@schema": "terminusdb:///schema#"
+            }),
+            json!({
+                "@type": "Class",
+                "@id": "Person",
+                "@inherits": ["Named"],
+                "@key": { "@type": "Lexical", "@fields": ["name", "born"] },
+                "@unfoldable": [],
+                "@metadata": { "source": "crm" },
+                "@documentation": {
+                    "@comment": "Someone we know\nor knew",
+                    "@properties": { "nick": "Nickname, if any" }
+                },
+                "born": "xsd:date",
+                "nick": { "@type": "Optional", "@class": "xsd:string" },
+                "friends": { "@type": "Set", "@class": "Person" },
+                "grid": { "@type": "Array", "@class": "xsd:integer", "@dimensions": 2 },
+                "parents": { "@type": "Cardinality", "@class": "Person", "@max_cardinality": 2 }
+            }),
+            json!({
+                "@type": "Enum",
+                "@id": "Status",
+                "@value": ["active", "on hold"],
+                "@documentation": { "@values": { "on hold": "Paused by support" } }
+            }),
+            json!({
+                "@type": "TaggedUnion",
+                "@id": "Shape",
+                "@subdocument": [],
+                "@key": { "@type": "ValueHash" },
+                "circle": "xsd:decimal",
+                "square": "sys:Unit"
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_render() {
+        let text = render(&schema());
+        assert!(text.starts_with("context {\n  @base \"terminusdb:///data/\"\n"));
+        assert!(text.contains(
+            "/// Someone we know\n/// or knew\n@key Lexical(name, born)\n@metadata {\"source\":\"crm\"}\n@unfoldable\nclass Person : Named {\n"
+        ));
+        assert!(text.contains("  /// Nickname, if any\n  nick: Optional<xsd:string>\n"));
+        assert!(text.contains("  grid: Array<xsd:integer, 2>\n"));
+        assert!(text.contains("  parents: Cardinality<Person, _, 2>\n"));
+        assert!(
+            text.contains("enum Status {\n  active\n  /// Paused by support\n  \"on hold\"\n}\n")
+        );
+        assert!(text.contains("@key ValueHash\n@subdocument\nunion Shape {\n"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let docs = schema();
+        assert_eq!(parse(&render(&docs)).unwrap(), docs);
+    }
+
+    #[test]
+    fn test_unexpressible_documentation_stays_json() {
+        let docs = vec![json!({
+            "@type": "Class",
+            "@id": "Note",
+            "@documentation": [{ "@comment": "A note" }],
+            "text": "xsd:string"
+        })];
+        let text = render(&docs);
+        assert!(text.starts_with("@documentation [{\"@comment\":\"A note\"}]\n"));
+        assert_eq!(parse(&text).unwrap(), docs);
+    }
+
+    #[test]
+    fn test_parse_errors_have_line_numbers() {
+        let err = parse("class Person {\n  name xsd:string\n}\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected 'name: Type', found 'name xsd:string'"
+        );
+
+        let err = parse("\n@key Lexical(name\nclass Person {\n}\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: unterminated key fields in 'Lexical(name'"
+        );
+
+        let err = parse("class Person {\n  pets: Bag<Pet>\n}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: unknown type constructor 'Bag<Pet>'"
+        );
+    }
+}
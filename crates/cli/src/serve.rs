@@ -0,0 +1,202 @@
+//! `tdb serve`: run the embedded TerminusDB server (from `terminusdb-bin`) in
+//! the foreground as a local development database.
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use terminusdb_bin::{DropBehavior, LogFormat, LogLevel, ServerConfig, ServerOptions};
+
+use crate::output::Output;
+
+/// How often the server is checked while it runs.
+const HEALTH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Failed checks in a row after which the server is considered gone.
+const HEALTH_FAILURES: u32 = 3;
+
+/// How long the server gets to shut down after Ctrl+C.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn parse_log_level(level: &str) -> Result<LogLevel> {
+    match level.to_ascii_lowercase().as_str() {
+        "error" => Ok(LogLevel::Error),
+        "warning" | "warn" => Ok(LogLevel::Warning),
+        "notice" => Ok(LogLevel::Notice),
+        "info" => Ok(LogLevel::Info),
+        "debug" => Ok(LogLevel::Debug),
+        _ => bail!(
+            "unknown log level '{}' (expected error, warning, notice, info or debug)",
+            level
+        ),
+    }
+}
+
+fn parse_log_format(format: &str) -> Result<LogFormat> {
+    match format.to_ascii_lowercase().as_str() {
+        "text" => Ok(LogFormat::Text),
+        "json" => Ok(LogFormat::Json),
+        _ => bail!("unknown log format '{}' (expected text or json)", format),
+    }
+}
+
+/// Where the store lives when `--data-dir` is not given.
+fn default_data_dir() -> Result<PathBuf> {
+    let data_dir = directories::ProjectDirs::from("com", "terminusdb", "tdb")
+        .context("Could not determine data directory")?
+        .data_dir()
+        .join("store");
+
+    Ok(data_dir)
+}
+
+/// Copy what the server appends to its log file to stderr, from a background
+/// thread that lives as long as the process.
+fn follow_log(path: &Path) -> Result<()> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open server log {}", path.display()))?;
+
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => std::thread::sleep(Duration::from_millis(200)),
+                Ok(_) => {
+                    let _ = std::io::stderr().write_all(line.as_bytes());
+                }
+                Err(_) => return,
+            }
+        }
+    });
+
+    Ok(())
+}
+
+pub(crate) async fn run_serve(
+    port: u16,
+    data_dir: Option<PathBuf>,
+    memory: bool,
+    password: String,
+    log_level: String,
+    log_format: String,
+    quiet: bool,
+    output: Output,
+) -> Result<()> {
+    let config = ServerConfig {
+        log_level: Some(parse_log_level(&log_level)?),
+        log_format: Some(parse_log_format(&log_format)?),
+        ..Default::default()
+    };
+    let db_path = match (memory, data_dir) {
+        (true, _) => None,
+        (false, Some(dir)) => Some(dir),
+        (false, None) => Some(default_data_dir()?),
+    };
+
+    // Left running on drop so the server logs to a file rather than a pipe
+    // nobody reads; it is stopped explicitly below.
+    let options = ServerOptions {
+        memory,
+        password: Some(password.clone()),
+        quiet,
+        db_path: db_path.clone(),
+        port: Some(port),
+        config,
+        on_drop: DropBehavior::LeaveRunning,
+        ..Default::default()
+    };
+    let mut server = terminusdb_bin::start_server(options)
+        .await
+        .context("failed to start the TerminusDB server")?;
+
+    let log_file = server.log_file();
+    if let (false, Some(path)) = (quiet, &log_file) {
+        follow_log(path)?;
+    }
+
+    let host = server.url().as_str().trim_end_matches('/').to_string();
+    let banner = json!({
+        "version": server.version(),
+        "url": host,
+        "user": "admin",
+        "password": password,
+        "store": db_path,
+        "log": log_file,
+    });
+    output.print(&banner, || {
+        let mut text = format!(
+            "TerminusDB {} listening on {}\n",
+            server.version(),
+            server.url()
+        );
+        text.push_str("  user:     admin\n");
+        text.push_str(&format!("  password: {}\n", password));
+        match db_path {
+            Some(ref path) => text.push_str(&format!("  store:    {}\n", path.display())),
+            None => text.push_str("  store:    in memory\n"),
+        }
+        if let Some(ref path) = log_file {
+            text.push_str(&format!("  log:      {}\n", path.display()));
+        }
+        text.push('\n');
+        text.push_str(&format!(
+            "Connect with: export TERMINUSDB_HOST={} TERMINUSDB_PASS={}\n",
+            host, password
+        ));
+        text.push_str("Press Ctrl+C to stop.\n");
+        text
+    })?;
+
+    let mut interval = tokio::time::interval(HEALTH_INTERVAL);
+    interval.tick().await;
+    let mut failures = 0;
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                eprintln!("Stopping server...");
+                break;
+            }
+
+            _ = interval.tick() => {
+                if server.is_healthy().await {
+                    failures = 0;
+                    continue;
+                }
+                failures += 1;
+                if failures >= HEALTH_FAILURES {
+                    let _ = server.stop_graceful(STOP_TIMEOUT).await;
+                    match log_file {
+                        Some(ref path) => bail!(
+                            "server stopped responding (see {})",
+                            path.display()
+                        ),
+                        None => bail!("server stopped responding"),
+                    }
+                }
+            }
+        }
+    }
+
+    server
+        .stop_graceful(STOP_TIMEOUT)
+        .await
+        .context("failed to stop the server")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_levels_are_case_insensitive() {
+        assert_eq!(parse_log_level("DEBUG").unwrap(), LogLevel::Debug);
+        assert_eq!(parse_log_level("warn").unwrap(), LogLevel::Warning);
+        assert_eq!(parse_log_format("Json").unwrap(), LogFormat::Json);
+        assert!(parse_log_level("verbose").is_err());
+        assert!(parse_log_format("yaml").is_err());
+    }
+}
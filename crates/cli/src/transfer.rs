@@ -0,0 +1,999 @@
+//! `tdb export` and `tdb import`: move instance data in and out of a branch.
+//!
+//! Three formats are supported: JSONL (one document per line), CSV (one class,
+//! one column per property) and Turtle. Neither direction holds the data set
+//! in memory. Exports read documents a page at a time from the commit the
+//! branch was at when the export started, or stream the triples of a WOQL
+//! query. Imports read the input line by line and write it in batches, one
+//! commit per batch. Progress goes to stderr; with `--output json|yaml` a
+//! summary is printed to stdout as well, unless the export itself goes there.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::StreamExt;
+use serde_json::{json, Map, Value};
+use terminusdb_client::{BranchSpec, DocumentInsertArgs, GetOpts, TerminusDBHttpClient};
+use url::Url;
+
+use crate::output::Output;
+
+/// File format for `tdb export` and `tdb import`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TransferFormat {
+    Jsonl,
+    Csv,
+    Turtle,
+}
+
+impl TransferFormat {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jsonl" | "ndjson" => Ok(Self::Jsonl),
+            "csv" => Ok(Self::Csv),
+            "turtle" | "ttl" => Ok(Self::Turtle),
+            _ => bail!("unknown format '{}', expected jsonl, csv or turtle", s),
+        }
+    }
+
+    /// The `--format` if given, else the format named by the file extension,
+    /// else JSONL
+    fn resolve(format: Option<&str>, path: Option<&str>) -> Result<Self> {
+        if let Some(format) = format {
+            return Self::parse(format);
+        }
+        Ok(path
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| Self::parse(ext).ok())
+            .unwrap_or(Self::Jsonl))
+    }
+}
+
+pub(crate) async fn run_export(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    ty: Option<String>,
+    format: Option<String>,
+    out: Option<String>,
+    page_size: usize,
+    output: Output,
+) -> Result<()> {
+    let format = TransferFormat::resolve(format.as_deref(), out.as_deref())?;
+    if format == TransferFormat::Csv && ty.is_none() {
+        bail!("CSV export needs --type: the columns are the properties of one class");
+    }
+    if page_size == 0 {
+        bail!("--page-size must be at least 1");
+    }
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let mut sink = open_output(out.as_deref())?;
+    let ty = ty.as_deref();
+    let (count, unit) = match format {
+        TransferFormat::Jsonl => {
+            let count = for_each_document(&client, &spec, ty, page_size, |doc| {
+                serde_json::to_writer(&mut sink, doc)?;
+                writeln!(sink)?;
+                Ok(())
+            })
+            .await?;
+            (count, "documents")
+        }
+        TransferFormat::Csv => {
+            let ty = ty.unwrap_or_default();
+            let schema = client.get_schema_documents(&spec).await?;
+            let properties = class_properties(&schema, ty)?;
+            let mut writer = csv::Writer::from_writer(&mut sink);
+            writer.write_record(
+                std::iter::once("@id").chain(properties.keys().map(String::as_str)),
+            )?;
+            let count = for_each_document(&client, &spec, Some(ty), page_size, |doc| {
+                let record = std::iter::once("@id")
+                    .chain(properties.keys().map(String::as_str))
+                    .map(|field| csv_cell(doc.get(field)));
+                writer.write_record(record)?;
+                Ok(())
+            })
+            .await?;
+            writer.flush()?;
+            (count, "documents")
+        }
+        TransferFormat::Turtle => {
+            let schema = client.get_schema_documents(&spec).await?;
+            let namespaces = Namespaces::from_schema(&schema);
+            let query = triples_query(&org, &database, &branch, ty)?;
+            let query = terminusdb_woql_dsl::parse_woql_dsl(&query)
+                .map_err(|e| anyhow!("invalid WOQL DSL: {}", e))?;
+            let stream = client.query_stream(None, query).await?;
+            futures_util::pin_mut!(stream);
+            let mut count = 0;
+            while let Some(binding) = stream.next().await {
+                writeln!(sink, "{}", namespaces.triple(&binding?)?)?;
+                count += 1;
+            }
+            (count, "triples")
+        }
+    };
+    sink.flush()?;
+
+    let destination = out.as_deref().unwrap_or("stdout");
+    eprintln!("Exported {} {} to {}", count, unit, destination);
+    if !matches!(out.as_deref(), None | Some("-")) {
+        let summary = json!({ "exported": count, "unit": unit, "out": destination });
+        if let Some(text) = output.render_data(&summary)? {
+            print!("{}", text);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run_import(
+    host: String,
+    user: String,
+    password: String,
+    org: String,
+    database: String,
+    branch: String,
+    file: String,
+    ty: Option<String>,
+    format: Option<String>,
+    map: Vec<String>,
+    batch_size: usize,
+    author: String,
+    message: Option<String>,
+    output: Output,
+) -> Result<()> {
+    let format = TransferFormat::resolve(format.as_deref(), Some(&file))?;
+    let mapping = parse_mappings(&map)?;
+    if !mapping.is_empty() && format != TransferFormat::Csv {
+        bail!("--map only applies to CSV imports");
+    }
+    if format == TransferFormat::Csv && ty.is_none() {
+        bail!("CSV import needs --type: every row becomes a document of that class");
+    }
+    if batch_size == 0 {
+        bail!("--batch-size must be at least 1");
+    }
+    let input = open_input(&file)?;
+
+    let parsed_url = Url::parse(&host)?;
+    let client = TerminusDBHttpClient::new(parsed_url, &user, &password, &org).await?;
+
+    let spec = BranchSpec::with_branch(&database, &branch);
+    let source = if file == "-" { "stdin" } else { file.as_str() };
+    let message = message.unwrap_or_else(|| format!("Import from {}", source));
+
+    let (count, unit) = match format {
+        TransferFormat::Jsonl => {
+            let args = DocumentInsertArgs::from(spec)
+                .with_author(author)
+                .with_message(message);
+            let mut writer = DocumentWriter::new(&client, args, batch_size);
+            for (number, line) in input.lines().enumerate() {
+                let line = line.with_context(|| format!("failed to read {}", source))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let mut doc: Value = serde_json::from_str(&line)
+                    .with_context(|| format!("line {}: invalid JSON", number + 1))?;
+                if let (Some(ty), Some(doc)) = (&ty, doc.as_object_mut()) {
+                    doc.entry("@type")
+                        .or_insert_with(|| Value::String(ty.clone()));
+                }
+                writer.push(doc).await?;
+            }
+            (writer.finish().await?, "documents")
+        }
+        TransferFormat::Csv => {
+            let ty = ty.unwrap_or_default();
+            let schema = client.get_schema_documents(&spec).await?;
+            let properties = class_properties(&schema, &ty)?;
+            let args = DocumentInsertArgs::from(spec)
+                .with_author(author)
+                .with_message(message);
+            let mut writer = DocumentWriter::new(&client, args, batch_size);
+
+            let mut reader = csv::Reader::from_reader(input);
+            let headers = reader.headers()?.clone();
+            let fields = column_fields(&headers, &mapping, &properties, &ty)?;
+            for record in reader.records() {
+                let record = record?;
+                let line = record.position().map_or(0, |pos| pos.line());
+                let doc = csv_document(&record, &fields, &properties, &ty)
+                    .with_context(|| format!("line {}", line))?;
+                writer.push(doc).await?;
+            }
+            (writer.finish().await?, "documents")
+        }
+        TransferFormat::Turtle => {
+            let mut writer = TripleWriter {
+                client: &client,
+                spec: &spec,
+                author: &author,
+                message: &message,
+                directives: String::new(),
+                batch: Vec::new(),
+                batch_size,
+                written: 0,
+            };
+            let mut splitter = TurtleSplitter::default();
+            for line in input.lines() {
+                let line = line.with_context(|| format!("failed to read {}", source))?;
+                for statement in splitter.push_line(&line) {
+                    writer.push(statement).await?;
+                }
+            }
+            splitter.finish()?;
+            writer.flush().await?;
+            (writer.written, "statements")
+        }
+    };
+
+    let summary = json!({ "imported": count, "unit": unit, "source": source });
+    if let Some(text) = output.render_data(&summary)? {
+        print!("{}", text);
+    }
+    Ok(())
+}
+
+/// `--out` as a buffered writer; stdout when absent or `-`
+fn open_output(path: Option<&str>) -> Result<Box<dyn Write>> {
+    match path {
+        None | Some("-") => Ok(Box::new(BufWriter::new(io::stdout().lock()))),
+        Some(path) => {
+            let file = File::create(path).with_context(|| format!("failed to create {}", path))?;
+            Ok(Box::new(BufWriter::new(file)))
+        }
+    }
+}
+
+/// The import file as a buffered reader; stdin for `-`
+fn open_input(path: &str) -> Result<Box<dyn BufRead>> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).with_context(|| format!("failed to open {}", path))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Call `f` on every document (of `ty`, if given) on the branch, fetched
+/// `page_size` at a time. All pages are read from the commit the branch is at
+/// when the first one is fetched, so concurrent writes cannot shift them.
+/// Returns the number of documents.
+async fn for_each_document(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    ty: Option<&str>,
+    page_size: usize,
+    mut f: impl FnMut(&Value) -> Result<()>,
+) -> Result<usize> {
+    let head = client.get_latest_commit_id(spec).await?;
+    let pinned = spec.clone().ref_commit(head);
+
+    let mut count = 0;
+    loop {
+        let mut opts = GetOpts::default().with_skip(count).with_count(page_size);
+        if let Some(ty) = ty {
+            opts = opts.with_type_filter_string(ty);
+        }
+        let page = client.get_documents(Vec::new(), &pinned, opts).await?;
+        for doc in &page {
+            f(doc)?;
+        }
+        count += page.len();
+        if page.len() < page_size {
+            return Ok(count);
+        }
+    }
+}
+
+/// Writes documents in batches of `batch_size`, one commit per batch
+struct DocumentWriter<'a> {
+    client: &'a TerminusDBHttpClient,
+    args: DocumentInsertArgs,
+    batch: Vec<Value>,
+    batch_size: usize,
+    written: usize,
+}
+
+impl<'a> DocumentWriter<'a> {
+    fn new(client: &'a TerminusDBHttpClient, args: DocumentInsertArgs, batch_size: usize) -> Self {
+        DocumentWriter {
+            client,
+            args,
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+            written: 0,
+        }
+    }
+
+    async fn push(&mut self, doc: Value) -> Result<()> {
+        self.batch.push(doc);
+        if self.batch.len() >= self.batch_size {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let docs: Vec<&Value> = self.batch.iter().collect();
+        self.client
+            .insert_documents(docs, self.args.clone())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to write documents {} to {}",
+                    self.written + 1,
+                    self.written + self.batch.len()
+                )
+            })?;
+        self.written += self.batch.len();
+        self.batch.clear();
+        eprintln!("Imported {} documents", self.written);
+        Ok(())
+    }
+
+    /// Write the last batch; returns the number of documents written
+    async fn finish(mut self) -> Result<usize> {
+        self.flush().await?;
+        if self.written == 0 {
+            eprintln!("Nothing to import.");
+        }
+        Ok(self.written)
+    }
+}
+
+/// Writes Turtle statements in batches of `batch_size`, one commit per batch.
+/// Every batch starts with the `@prefix` and `@base` directives read so far.
+struct TripleWriter<'a> {
+    client: &'a TerminusDBHttpClient,
+    spec: &'a BranchSpec,
+    author: &'a str,
+    message: &'a str,
+    directives: String,
+    batch: Vec<String>,
+    batch_size: usize,
+    written: usize,
+}
+
+impl TripleWriter<'_> {
+    async fn push(&mut self, statement: String) -> Result<()> {
+        if is_directive(&statement) {
+            // Statements before a redefined prefix must be sent without it
+            self.flush().await?;
+            self.directives.push_str(&statement);
+            self.directives.push('\n');
+            return Ok(());
+        }
+        self.batch.push(statement);
+        if self.batch.len() >= self.batch_size {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let turtle = format!("{}{}\n", self.directives, self.batch.join("\n"));
+        self.client
+            .insert_triples(self.spec, &turtle, self.author, self.message)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to write statements {} to {}",
+                    self.written + 1,
+                    self.written + self.batch.len()
+                )
+            })?;
+        self.written += self.batch.len();
+        self.batch.clear();
+        eprintln!("Imported {} statements", self.written);
+        Ok(())
+    }
+}
+
+/// `--map column=field` arguments; `column=-` skips the column
+fn parse_mappings(map: &[String]) -> Result<HashMap<String, Option<String>>> {
+    map.iter()
+        .map(|arg| {
+            let (column, field) = arg
+                .split_once('=')
+                .filter(|(column, field)| !column.is_empty() && !field.is_empty())
+                .ok_or_else(|| anyhow!("invalid --map '{}', expected column=field", arg))?;
+            let field = (field != "-").then(|| field.to_string());
+            Ok((column.to_string(), field))
+        })
+        .collect()
+}
+
+/// The properties of a class, including inherited ones, by name
+fn class_properties(schema: &[Value], class: &str) -> Result<BTreeMap<String, Value>> {
+    fn collect(
+        by_id: &HashMap<&str, &Value>,
+        class: &str,
+        seen: &mut HashSet<String>,
+        properties: &mut BTreeMap<String, Value>,
+    ) -> Result<()> {
+        if !seen.insert(class.to_string()) {
+            return Ok(());
+        }
+        let doc = by_id
+            .get(class)
+            .ok_or_else(|| anyhow!("class '{}' not found in the schema", class))?;
+        match doc.get("@type").and_then(Value::as_str) {
+            Some("Class") => {}
+            Some(ty) => bail!("'{}' is a {}, not a class", class, ty),
+            None => bail!("'{}' has no @type in the schema", class),
+        }
+        let parents = match doc.get("@inherits") {
+            Some(Value::String(parent)) => vec![parent.as_str()],
+            Some(Value::Array(parents)) => parents.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        for parent in parents {
+            collect(by_id, parent, seen, properties)?;
+        }
+        if let Some(fields) = doc.as_object() {
+            for (name, ty) in fields.iter().filter(|(name, _)| !name.starts_with('@')) {
+                properties.insert(name.clone(), ty.clone());
+            }
+        }
+        Ok(())
+    }
+
+    let by_id: HashMap<&str, &Value> = schema
+        .iter()
+        .filter_map(|doc| Some((doc.get("@id")?.as_str()?, doc)))
+        .collect();
+    let mut properties = BTreeMap::new();
+    collect(&by_id, class, &mut HashSet::new(), &mut properties)?;
+    Ok(properties)
+}
+
+/// A field as a CSV cell: strings as they are, other values as JSON, missing
+/// fields empty
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// The field each CSV column is written to (`None` to skip it): the one given
+/// with `--map`, else the property of the same name
+fn column_fields(
+    headers: &csv::StringRecord,
+    mapping: &HashMap<String, Option<String>>,
+    properties: &BTreeMap<String, Value>,
+    class: &str,
+) -> Result<Vec<Option<String>>> {
+    for column in mapping.keys() {
+        if !headers.iter().any(|header| header == column) {
+            bail!(
+                "--map names column '{}', which is not in the CSV header",
+                column
+            );
+        }
+    }
+    headers
+        .iter()
+        .map(|column| {
+            let field = match mapping.get(column) {
+                Some(field) => field.clone(),
+                None => Some(column.to_string()),
+            };
+            if let Some(field) = &field {
+                if field != "@id" && !properties.contains_key(field) {
+                    bail!(
+                        "column '{}' maps to '{}', which is not a property of {} \
+                         (skip it with --map '{}=-')",
+                        column,
+                        field,
+                        class,
+                        column
+                    );
+                }
+            }
+            Ok(field)
+        })
+        .collect()
+}
+
+/// A CSV row as a document of `class`; empty cells are left out
+fn csv_document(
+    record: &csv::StringRecord,
+    fields: &[Option<String>],
+    properties: &BTreeMap<String, Value>,
+    class: &str,
+) -> Result<Value> {
+    let mut doc = Map::new();
+    doc.insert("@type".to_string(), Value::String(class.to_string()));
+    for (cell, field) in record.iter().zip(fields) {
+        let Some(field) = field else {
+            continue;
+        };
+        if cell.is_empty() {
+            continue;
+        }
+        let value = match properties.get(field) {
+            Some(ty) => cell_value(cell, ty).with_context(|| format!("field '{}'", field))?,
+            None => Value::String(cell.to_string()),
+        };
+        doc.insert(field.clone(), value);
+    }
+    Ok(Value::Object(doc))
+}
+
+/// The JSON value of a cell for a property of type `ty`
+fn cell_value(cell: &str, ty: &Value) -> Result<Value> {
+    let Some(family) = ty.get("@type").and_then(Value::as_str) else {
+        return scalar_value(cell, ty.as_str().unwrap_or_default());
+    };
+    let class = ty.get("@class").unwrap_or(&Value::Null);
+    match family {
+        "Optional" => cell_value(cell, class),
+        // Set, List, Array, Cardinality: a JSON array, or a single element
+        _ if cell.trim_start().starts_with('[') => {
+            serde_json::from_str(cell).with_context(|| format!("'{}' is not a JSON array", cell))
+        }
+        _ => Ok(Value::Array(vec![cell_value(cell, class)?])),
+    }
+}
+
+const NUMERIC_TYPES: &[&str] = &[
+    "xsd:decimal",
+    "xsd:double",
+    "xsd:float",
+    "xsd:integer",
+    "xsd:long",
+    "xsd:int",
+    "xsd:short",
+    "xsd:byte",
+    "xsd:nonNegativeInteger",
+    "xsd:positiveInteger",
+    "xsd:nonPositiveInteger",
+    "xsd:negativeInteger",
+    "xsd:unsignedLong",
+    "xsd:unsignedInt",
+    "xsd:unsignedShort",
+    "xsd:unsignedByte",
+];
+
+fn scalar_value(cell: &str, ty: &str) -> Result<Value> {
+    match ty {
+        "xsd:boolean" => match cell.to_lowercase().as_str() {
+            "true" | "1" => Ok(Value::Bool(true)),
+            "false" | "0" => Ok(Value::Bool(false)),
+            _ => bail!("'{}' is not a boolean", cell),
+        },
+        _ if NUMERIC_TYPES.contains(&ty) => serde_json::from_str(cell.trim())
+            .map(Value::Number)
+            .map_err(|_| anyhow!("'{}' is not a number ({})", cell, ty)),
+        "sys:JSON" => serde_json::from_str(cell).with_context(|| format!("'{}' is not JSON", cell)),
+        // A subdocument, written as JSON by `tdb export`
+        _ if !ty.starts_with("xsd:") && cell.starts_with('{') => {
+            Ok(serde_json::from_str(cell).unwrap_or_else(|_| Value::String(cell.to_string())))
+        }
+        _ => Ok(Value::String(cell.to_string())),
+    }
+}
+
+/// `using(<branch>, triple($Subject, $Predicate, $Object))` in WOQL DSL,
+/// limited to the subjects of `ty` when given
+fn triples_query(org: &str, database: &str, branch: &str, ty: Option<&str>) -> Result<String> {
+    let mut query = "triple($Subject, $Predicate, $Object)".to_string();
+    if let Some(ty) = ty {
+        query = format!(
+            "and(triple($Subject, \"rdf:type\", iri({})), {})",
+            serde_json::to_string(&format!("@schema:{}", ty))?,
+            query
+        );
+    }
+    let collection = format!("{}/{}/local/branch/{}", org, database, branch);
+    Ok(format!(
+        "using({}, {})",
+        serde_json::to_string(&collection)?,
+        query
+    ))
+}
+
+/// Expands the prefixed names of query results to full IRIs, for Turtle
+/// output that does not depend on the database's context
+struct Namespaces {
+    base: String,
+    schema: String,
+}
+
+impl Namespaces {
+    fn from_schema(schema: &[Value]) -> Self {
+        let context = schema
+            .iter()
+            .find(|doc| doc.get("@type").and_then(Value::as_str) == Some("@context"));
+        let get = |key: &str, default: &str| {
+            context
+                .and_then(|context| context.get(key))
+                .and_then(Value::as_str)
+                .unwrap_or(default)
+                .to_string()
+        };
+        Namespaces {
+            base: get("@base", "terminusdb:///data/"),
+            schema: get("@schema", "terminusdb:///schema#"),
+        }
+    }
+
+    fn expand(&self, name: &str) -> String {
+        if let Some(local) = name.strip_prefix("@schema:") {
+            return format!("{}{}", self.schema, local);
+        }
+        if let Some((prefix, local)) = name.split_once(':') {
+            let namespace = match prefix {
+                "rdf" => Some("http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+                "rdfs" => Some("http://www.w3.org/2000/01/rdf-schema#"),
+                "xsd" => Some("http://www.w3.org/2001/XMLSchema#"),
+                "owl" => Some("http://www.w3.org/2002/07/owl#"),
+                "sys" => Some("http://terminusdb.com/schema/sys#"),
+                _ => None,
+            };
+            if let Some(namespace) = namespace {
+                return format!("{}{}", namespace, local);
+            }
+            let is_scheme = prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+                && prefix
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+            if is_scheme {
+                return name.to_string();
+            }
+        }
+        format!("{}{}", self.base, name)
+    }
+
+    fn iri(&self, name: &str) -> String {
+        let mut out = String::from("<");
+        for c in self.expand(name).chars() {
+            if c <= ' ' || "<>\"{}|^`\\".contains(c) {
+                out.push_str(&format!("\\u{:04X}", c as u32));
+            } else {
+                out.push(c);
+            }
+        }
+        out.push('>');
+        out
+    }
+
+    /// A node as an IRI, a typed value as a literal
+    fn term(&self, value: &Value) -> Result<String> {
+        match value {
+            Value::String(node) => Ok(self.iri(node)),
+            Value::Object(literal) => {
+                let lexical = match literal.get("@value") {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => bail!("unexpected value in query result: {}", value),
+                };
+                let quoted = turtle_string(&lexical);
+                if let Some(lang) = literal.get("@language").and_then(Value::as_str) {
+                    return Ok(format!("{}@{}", quoted, lang));
+                }
+                match literal.get("@type").and_then(Value::as_str) {
+                    Some(ty) => Ok(format!("{}^^{}", quoted, self.iri(ty))),
+                    None => Ok(quoted),
+                }
+            }
+            other => Ok(turtle_string(&other.to_string())),
+        }
+    }
+
+    /// A `Subject`/`Predicate`/`Object` binding as one Turtle statement
+    fn triple(&self, binding: &Map<String, Value>) -> Result<String> {
+        let get = |var: &str| {
+            binding
+                .get(var)
+                .filter(|value| !value.is_null())
+                .ok_or_else(|| anyhow!("query result without a {}", var))
+        };
+        Ok(format!(
+            "{} {} {} .",
+            self.term(get("Subject")?)?,
+            self.term(get("Predicate")?)?,
+            self.term(get("Object")?)?
+        ))
+    }
+}
+
+fn turtle_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// `@prefix`/`@base`, or their SPARQL-style forms
+fn is_directive(statement: &str) -> bool {
+    let keyword = statement
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    matches!(keyword.as_str(), "@prefix" | "@base" | "prefix" | "base")
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum TurtleState {
+    #[default]
+    Code,
+    Iri,
+    Comment,
+    Text {
+        quote: char,
+        long: bool,
+    },
+}
+
+/// Splits Turtle into statements without parsing them, so that large files
+/// can be sent in batches. IRIs, strings and comments are tracked so that a
+/// `.` inside them does not end a statement; comments are dropped.
+#[derive(Debug, Default)]
+struct TurtleSplitter {
+    statement: String,
+    state: TurtleState,
+}
+
+impl TurtleSplitter {
+    /// Feed one line; returns the statements it completes
+    fn push_line(&mut self, line: &str) -> Vec<String> {
+        let chars: Vec<char> = line.chars().chain(std::iter::once('\n')).collect();
+        let mut statements = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            match self.state {
+                TurtleState::Comment => {
+                    if c == '\n' {
+                        self.state = TurtleState::Code;
+                        self.statement.push(c);
+                    }
+                }
+                TurtleState::Iri => {
+                    self.statement.push(c);
+                    if c == '>' {
+                        self.state = TurtleState::Code;
+                        // SPARQL-style directives have no closing `.`
+                        if is_directive(&self.statement) && !self.statement.trim().starts_with('@')
+                        {
+                            self.end_statement(&mut statements);
+                        }
+                    }
+                }
+                TurtleState::Text { quote, long } => {
+                    self.statement.push(c);
+                    if c == '\\' && i + 1 < chars.len() {
+                        self.statement.push(chars[i + 1]);
+                        i += 1;
+                    } else if c == quote && !long {
+                        self.state = TurtleState::Code;
+                    } else if c == quote && chars.get(i + 1..i + 3) == Some(&[quote, quote][..]) {
+                        self.statement.push_str(&format!("{}{}", quote, quote));
+                        i += 2;
+                        self.state = TurtleState::Code;
+                    }
+                }
+                TurtleState::Code => match c {
+                    '#' => self.state = TurtleState::Comment,
+                    '<' => {
+                        self.statement.push(c);
+                        self.state = TurtleState::Iri;
+                    }
+                    '"' | '\'' => {
+                        let long = chars.get(i + 1..i + 3) == Some(&[c, c][..]);
+                        self.statement.push(c);
+                        if long {
+                            self.statement.push_str(&format!("{}{}", c, c));
+                            i += 2;
+                        }
+                        self.state = TurtleState::Text { quote: c, long };
+                    }
+                    '.' if chars.get(i + 1).is_none_or(|next| next.is_whitespace()) => {
+                        self.statement.push(c);
+                        self.end_statement(&mut statements);
+                    }
+                    c => self.statement.push(c),
+                },
+            }
+            i += 1;
+        }
+        statements
+    }
+
+    fn end_statement(&mut self, statements: &mut Vec<String>) {
+        let statement = self.statement.trim();
+        if !statement.is_empty() {
+            statements.push(statement.to_string());
+        }
+        self.statement.clear();
+    }
+
+    /// Check that the input did not end inside a statement
+    fn finish(self) -> Result<()> {
+        let rest = self.statement.trim();
+        if matches!(self.state, TurtleState::Iri | TurtleState::Text { .. }) || !rest.is_empty() {
+            let start: String = rest.chars().take(60).collect();
+            bail!("unterminated Turtle statement at end of input: {}", start);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Vec<Value> {
+        vec![
+            json!({ "@type": "@context", "@base": "http://example.com/data/", "@schema": "http://example.com/schema#" }),
+            json!({ "@type": "Class", "@id": "Named", "@abstract": [], "name": "xsd:string" }),
+            json!({
+                "@type": "Class",
+                "@id": "Person",
+                "@inherits": "Named",
+                "age": { "@type": "Optional", "@class": "xsd:integer" },
+                "active": "xsd:boolean",
+                "tags": { "@type": "Set", "@class": "xsd:string" },
+                "address": "Address"
+            }),
+            json!({ "@type": "Enum", "@id": "Color", "@value": ["red"] }),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_format() {
+        let resolve = TransferFormat::resolve;
+        assert_eq!(
+            resolve(None, Some("people.csv")).unwrap(),
+            TransferFormat::Csv
+        );
+        assert_eq!(
+            resolve(None, Some("dump.ttl")).unwrap(),
+            TransferFormat::Turtle
+        );
+        assert_eq!(resolve(None, Some("-")).unwrap(), TransferFormat::Jsonl);
+        assert_eq!(
+            resolve(Some("turtle"), Some("x.csv")).unwrap(),
+            TransferFormat::Turtle
+        );
+        assert!(resolve(Some("xml"), None).is_err());
+    }
+
+    #[test]
+    fn test_class_properties() {
+        let properties = class_properties(&schema(), "Person").unwrap();
+        let names: Vec<&str> = properties.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["active", "address", "age", "name", "tags"]);
+        assert!(class_properties(&schema(), "Color").is_err());
+        assert!(class_properties(&schema(), "Missing").is_err());
+    }
+
+    #[test]
+    fn test_csv_document() {
+        let properties = class_properties(&schema(), "Person").unwrap();
+        let headers =
+            csv::StringRecord::from(vec!["id", "full_name", "age", "active", "tags", "note"]);
+        let mapping = parse_mappings(&[
+            "id=@id".to_string(),
+            "full_name=name".to_string(),
+            "note=-".to_string(),
+        ])
+        .unwrap();
+        let fields = column_fields(&headers, &mapping, &properties, "Person").unwrap();
+
+        let record = csv::StringRecord::from(vec!["Person/ada", "Ada", "36", "true", "math", "x"]);
+        assert_eq!(
+            csv_document(&record, &fields, &properties, "Person").unwrap(),
+            json!({
+                "@type": "Person",
+                "@id": "Person/ada",
+                "name": "Ada",
+                "age": 36,
+                "active": true,
+                "tags": ["math"]
+            })
+        );
+
+        let record = csv::StringRecord::from(vec!["", "Bob", "", "0", "[\"a\",\"b\"]", ""]);
+        assert_eq!(
+            csv_document(&record, &fields, &properties, "Person").unwrap(),
+            json!({ "@type": "Person", "name": "Bob", "active": false, "tags": ["a", "b"] })
+        );
+
+        let record = csv::StringRecord::from(vec!["", "Eve", "old", "", "", ""]);
+        assert!(csv_document(&record, &fields, &properties, "Person").is_err());
+
+        let unmapped = parse_mappings(&["id=@id".to_string()]).unwrap();
+        assert!(column_fields(&headers, &unmapped, &properties, "Person").is_err());
+        assert!(parse_mappings(&["name".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_turtle_terms() {
+        let namespaces = Namespaces::from_schema(&schema());
+        let binding = json!({
+            "Subject": "Person/ada",
+            "Predicate": "@schema:name",
+            "Object": { "@type": "xsd:string", "@value": "Ada \"the\" first" }
+        });
+        assert_eq!(
+            namespaces.triple(binding.as_object().unwrap()).unwrap(),
+            "<http://example.com/data/Person/ada> <http://example.com/schema#name> \
+             \"Ada \\\"the\\\" first\"^^<http://www.w3.org/2001/XMLSchema#string> ."
+        );
+        assert_eq!(
+            namespaces.iri("rdf:type"),
+            "<http://www.w3.org/1999/02/22-rdf-syntax-ns#type>"
+        );
+        assert_eq!(namespaces.iri("urn:isbn:1"), "<urn:isbn:1>");
+        assert_eq!(
+            namespaces.iri("Note/a b"),
+            "<http://example.com/data/Note/a\\u0020b>"
+        );
+    }
+
+    #[test]
+    fn test_turtle_splitter() {
+        let mut splitter = TurtleSplitter::default();
+        let mut statements = Vec::new();
+        for line in [
+            "@prefix ex: <http://example.com/a.b#> .",
+            "PREFIX s: <http://example.com/s#>",
+            "# a comment . with dots",
+            "ex:a s:p \"x. y\" ; s:q 1.5 .",
+            "ex:b s:p \"\"\"multi",
+            "line. text\"\"\" .",
+        ] {
+            statements.extend(splitter.push_line(line));
+        }
+        splitter.finish().unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                "@prefix ex: <http://example.com/a.b#> .",
+                "PREFIX s: <http://example.com/s#>",
+                "ex:a s:p \"x. y\" ; s:q 1.5 .",
+                "ex:b s:p \"\"\"multi\nline. text\"\"\" .",
+            ]
+        );
+        assert!(is_directive(&statements[1]));
+        assert!(!is_directive(&statements[2]));
+
+        let mut splitter = TurtleSplitter::default();
+        assert!(splitter.push_line("ex:a ex:p \"open").is_empty());
+        assert!(splitter.finish().is_err());
+    }
+}
@@ -16,10 +16,24 @@ pub struct GetOpts {
     /// TerminusDB 12 `raw_json`: read the document as unstructured
     /// `sys:JSONDocument`. Required to GET a document inserted with `raw_json`.
     pub raw_json: bool,
+    /// Shorten document ids relative to the `@base` of the schema context
+    /// (server default: true). Set to false to receive full IRIs.
+    pub compress_ids: bool,
+    /// Shorten property and type names using the context prefixes
+    /// (server default: true). Set to false to receive full IRIs.
+    pub prefixed: bool,
     /// Optional request timeout
     pub timeout: Option<Duration>,
+    /// Maximum number of ids sent in one request; longer id lists are split
+    pub chunk_size: usize,
+    /// Maximum number of chunk requests in flight at once
+    pub chunk_concurrency: usize,
 }
 
+/// Upper bound on the JSON size of the ids sent in one request, well below
+/// the request body limits of TerminusDB and common reverse proxies
+const MAX_CHUNK_BYTES: usize = 256 * 1024;
+
 impl Default for GetOpts {
     fn default() -> Self {
         Self {
@@ -30,7 +44,11 @@ impl Default for GetOpts {
             type_filter: None,
             minimized: true,
             raw_json: false,
+            compress_ids: true,
+            prefixed: true,
             timeout: None,
+            chunk_size: 1000,
+            chunk_concurrency: 4,
         }
     }
 }
@@ -101,9 +119,131 @@ impl GetOpts {
         self
     }
 
+    /// Set compress_ids for chaining
+    pub fn with_compress_ids(mut self, compress_ids: bool) -> Self {
+        self.compress_ids = compress_ids;
+        self
+    }
+
+    /// Set prefixed for chaining
+    pub fn with_prefixed(mut self, prefixed: bool) -> Self {
+        self.prefixed = prefixed;
+        self
+    }
+
+    /// Return full IRIs for ids, properties and types (no compression at all)
+    pub fn with_full_iris(self) -> Self {
+        self.with_compress_ids(false).with_prefixed(false)
+    }
+
     /// Set timeout for chaining
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Set the maximum number of ids per request for chaining
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Set the maximum number of concurrent chunk requests for chaining
+    pub fn with_chunk_concurrency(mut self, chunk_concurrency: usize) -> Self {
+        self.chunk_concurrency = chunk_concurrency;
+        self
+    }
+
+    /// Split `ids` into consecutive chunks of at most `chunk_size` ids and
+    /// [`MAX_CHUNK_BYTES`] of JSON each. An empty list (meaning "all
+    /// documents") is a single empty chunk.
+    pub(crate) fn id_chunks<'a>(&self, ids: &'a [String]) -> Vec<&'a [String]> {
+        let max_ids = self.chunk_size.max(1);
+        let mut chunks = Vec::new();
+        let (mut start, mut bytes) = (0, 0);
+        for (i, id) in ids.iter().enumerate() {
+            // Quotes and separator; escapes are rare enough to ignore
+            let size = id.len() + 3;
+            if i > start && (i - start == max_ids || bytes + size > MAX_CHUNK_BYTES) {
+                chunks.push(&ids[start..i]);
+                (start, bytes) = (i, 0);
+            }
+            bytes += size;
+        }
+        chunks.push(&ids[start..]);
+        chunks
+    }
+
+    /// Boolean document-API flags whose value differs from the server default.
+    ///
+    /// Only these are sent, so requests made with default options are unchanged.
+    pub(crate) fn non_default_flags(&self) -> Vec<(&'static str, bool)> {
+        let mut flags = Vec::new();
+        if !self.compress_ids {
+            flags.push(("compress_ids", false));
+        }
+        if !self.prefixed {
+            flags.push(("prefixed", false));
+        }
+        if self.raw_json {
+            flags.push(("raw_json", true));
+        }
+        flags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_opts_send_no_extra_flags() {
+        assert!(GetOpts::default().non_default_flags().is_empty());
+    }
+
+    fn ids(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("Person/{}", i)).collect()
+    }
+
+    #[test]
+    fn test_id_chunks_by_count() {
+        let ids = ids(25);
+        let chunks = GetOpts::default().with_chunk_size(10).id_chunks(&ids);
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+        assert_eq!(chunks.concat(), ids);
+
+        assert_eq!(GetOpts::default().id_chunks(&ids).len(), 1);
+        assert_eq!(GetOpts::default().id_chunks(&[]), vec![&[] as &[String]]);
+    }
+
+    #[test]
+    fn test_id_chunks_by_size() {
+        // 1000 ids of ~1KiB each must not share a single request body
+        let ids: Vec<String> = (0..1000)
+            .map(|i| format!("{}/{}", "x".repeat(1024), i))
+            .collect();
+        let chunks = GetOpts::default().id_chunks(&ids);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            let bytes: usize = chunk.iter().map(|id| id.len() + 3).sum();
+            assert!(bytes <= MAX_CHUNK_BYTES);
+        }
+        assert_eq!(chunks.concat(), ids);
+    }
+
+    #[test]
+    fn test_non_default_flags() {
+        let opts = GetOpts::default().with_full_iris().with_raw_json(true);
+        assert_eq!(
+            opts.non_default_flags(),
+            vec![
+                ("compress_ids", false),
+                ("prefixed", false),
+                ("raw_json", true)
+            ]
+        );
+    }
 }
@@ -50,6 +50,19 @@ impl DocumentInsertArgs {
         })
     }
 
+    /// Commit author recorded by the server. Together with [`Self::with_message`]
+    /// this is the only commit metadata a client controls; timestamps and commit
+    /// ids are server-assigned (normalize them with [`crate::CommitNormalizer`]).
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
     pub fn with_force(mut self, force: bool) -> Self {
         self.force = force;
         self
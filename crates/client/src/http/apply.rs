@@ -50,6 +50,8 @@ impl TerminusDBHttpClient {
         message: &str,
         options: ApplyOptions,
     ) -> anyhow::Result<Value> {
+        self.check_protected_commit(spec.branch.as_deref(), author, message)?;
+
         let uri = self
             .build_url()
             .endpoint("apply")
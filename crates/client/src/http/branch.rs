@@ -1,6 +1,7 @@
 //! Branch management operations
 
 use {
+    super::branch_protection::ForceOperation,
     crate::{
         debug::{OperationEntry, OperationType, QueryLogEntry},
         CommitInfo, SquashResponse,
@@ -298,6 +299,8 @@ impl super::client::TerminusDBHttpClient {
         branch_path: &str,
         commit_descriptor: &str,
     ) -> anyhow::Result<serde_json::Value> {
+        self.check_protected_force_at(branch_path, ForceOperation::Reset)?;
+
         let start_time = Instant::now();
         let uri = self
             .build_url()
@@ -524,6 +527,8 @@ impl super::client::TerminusDBHttpClient {
         err
     )]
     pub async fn delete_branch(&self, branch_path: &str) -> anyhow::Result<serde_json::Value> {
+        self.check_protected_force_at(branch_path, ForceOperation::DeleteBranch)?;
+
         let start_time = Instant::now();
         let uri = self
             .build_url()
@@ -618,6 +623,8 @@ impl super::client::TerminusDBHttpClient {
         author: &str,
         message: &str,
     ) -> anyhow::Result<serde_json::Value> {
+        self.check_protected_commit_at(branch_path, author, message)?;
+
         let start_time = Instant::now();
         let uri = self
             .build_url()
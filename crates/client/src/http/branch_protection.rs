@@ -0,0 +1,361 @@
+//! Client-side branch protection
+//!
+//! TerminusDB has no server-side branch policies, so this client can enforce a
+//! few of them itself before a request is sent: requiring a commit message or a
+//! named author for commits on a branch, and refusing history-rewriting
+//! operations such as `reset`. Rules only apply to requests made through a
+//! client they were configured on; other clients can still modify the branch.
+//!
+//! ```rust,ignore
+//! use terminusdb_client::http::{BranchProtection, BranchProtectionError};
+//!
+//! let client = TerminusDBHttpClient::local_node()
+//!     .await
+//!     .with_branch_protection("main", BranchProtection::strict());
+//!
+//! let err = client
+//!     .reset("admin/mydb/local/branch/main", "admin/mydb/local/commit/abc123")
+//!     .await
+//!     .unwrap_err();
+//! assert!(matches!(
+//!     err.downcast_ref::<BranchProtectionError>(),
+//!     Some(BranchProtectionError::ForceOperation { .. })
+//! ));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Branch that requests without an explicit branch are made against
+const DEFAULT_BRANCH: &str = "main";
+
+/// Authors that don't identify anyone: the default of
+/// [`DocumentInsertArgs`](crate::DocumentInsertArgs) and its usual stand-ins
+const ANONYMOUS_AUTHORS: &[&str] = &["system", "anonymous", "unknown"];
+
+/// Rules enforced for commits and operations on a protected branch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BranchProtection {
+    /// commits must carry a non-blank message
+    pub require_message: bool,
+    /// commits must name an author; blank and placeholder authors such as
+    /// `"system"` are rejected
+    pub require_named_author: bool,
+    /// refuse operations that rewrite or discard the branch history, see
+    /// [`ForceOperation`]
+    pub block_force: bool,
+}
+
+impl BranchProtection {
+    /// No rules; enable them with the `with_*` methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All rules enabled
+    pub fn strict() -> Self {
+        Self {
+            require_message: true,
+            require_named_author: true,
+            block_force: true,
+        }
+    }
+
+    pub fn with_require_message(mut self, require: bool) -> Self {
+        self.require_message = require;
+        self
+    }
+
+    pub fn with_require_named_author(mut self, require: bool) -> Self {
+        self.require_named_author = require;
+        self
+    }
+
+    pub fn with_block_force(mut self, block: bool) -> Self {
+        self.block_force = block;
+        self
+    }
+
+    /// Check the commit metadata of a commit made on `branch`
+    pub fn check_commit(
+        &self,
+        branch: &str,
+        author: &str,
+        message: &str,
+    ) -> Result<(), BranchProtectionError> {
+        if self.require_message && message.trim().is_empty() {
+            return Err(BranchProtectionError::MissingMessage {
+                branch: branch.to_string(),
+            });
+        }
+        if self.require_named_author && !is_named_author(author) {
+            return Err(BranchProtectionError::AnonymousAuthor {
+                branch: branch.to_string(),
+                author: author.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check whether `operation` may be performed on `branch`
+    pub fn check_force(
+        &self,
+        branch: &str,
+        operation: ForceOperation,
+    ) -> Result<(), BranchProtectionError> {
+        if self.block_force {
+            return Err(BranchProtectionError::ForceOperation {
+                branch: branch.to_string(),
+                operation,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn is_named_author(author: &str) -> bool {
+    let author = author.trim();
+    !author.is_empty()
+        && !ANONYMOUS_AUTHORS
+            .iter()
+            .any(|anonymous| author.eq_ignore_ascii_case(anonymous))
+}
+
+/// Operations that rewrite or discard a branch's history
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ForceOperation {
+    /// pointing the branch at another commit
+    Reset,
+    /// deleting the branch
+    DeleteBranch,
+    /// deleting every document in a graph at once
+    Nuke,
+}
+
+impl fmt::Display for ForceOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ForceOperation::Reset => "reset",
+            ForceOperation::DeleteBranch => "branch deletion",
+            ForceOperation::Nuke => "deleting all documents",
+        })
+    }
+}
+
+/// A request refused by a [`BranchProtection`] rule before it was sent.
+///
+/// Client methods return it wrapped in an `anyhow::Error`; recover it with
+/// `err.downcast_ref::<BranchProtectionError>()`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BranchProtectionError {
+    #[error("branch '{branch}' is protected: commits require a message")]
+    MissingMessage { branch: String },
+
+    #[error("branch '{branch}' is protected: commits require a named author, got '{author}'")]
+    AnonymousAuthor { branch: String, author: String },
+
+    #[error("branch '{branch}' is protected: {operation} is not allowed")]
+    ForceOperation {
+        branch: String,
+        operation: ForceOperation,
+    },
+}
+
+/// Protection rules by branch name. A rule applies to branches of that name in
+/// every database.
+#[derive(Clone, Debug, Default)]
+pub struct BranchProtectionRules {
+    rules: HashMap<String, BranchProtection>,
+}
+
+impl BranchProtectionRules {
+    pub fn insert(&mut self, branch: impl Into<String>, protection: BranchProtection) {
+        self.rules.insert(branch.into(), protection);
+    }
+
+    pub fn remove(&mut self, branch: &str) -> Option<BranchProtection> {
+        self.rules.remove(branch)
+    }
+
+    pub fn get(&self, branch: &str) -> Option<&BranchProtection> {
+        self.rules.get(branch)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check a commit on `branch`, where `None` is the default branch
+    pub fn check_commit(
+        &self,
+        branch: Option<&str>,
+        author: &str,
+        message: &str,
+    ) -> Result<(), BranchProtectionError> {
+        let branch = branch.unwrap_or(DEFAULT_BRANCH);
+        match self.get(branch) {
+            Some(protection) => protection.check_commit(branch, author, message),
+            None => Ok(()),
+        }
+    }
+
+    /// Check an operation on `branch`, where `None` is the default branch
+    pub fn check_force(
+        &self,
+        branch: Option<&str>,
+        operation: ForceOperation,
+    ) -> Result<(), BranchProtectionError> {
+        let branch = branch.unwrap_or(DEFAULT_BRANCH);
+        match self.get(branch) {
+            Some(protection) => protection.check_force(branch, operation),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The branch name of a resource path such as `admin/mydb/local/branch/main`,
+/// or the default branch for a bare `admin/mydb`. `None` for other resources,
+/// such as commit paths, which are not subject to branch protection.
+pub(crate) fn branch_of_path(path: &str) -> Option<&str> {
+    let path = path.trim_matches('/');
+    if let Some((_, branch)) = path.split_once("/local/branch/") {
+        return Some(branch);
+    }
+    (path.split('/').count() == 2).then_some(DEFAULT_BRANCH)
+}
+
+/// Enforcement hooks called by the client's write operations
+impl super::client::TerminusDBHttpClient {
+    /// Protect `branch` with the given rules, replacing any earlier rules for
+    /// it. Clones made afterwards inherit the rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use terminusdb_client::http::BranchProtection;
+    ///
+    /// let client = TerminusDBHttpClient::new(url, "admin", "root", "admin")
+    ///     .await?
+    ///     .with_branch_protection(
+    ///         "main",
+    ///         BranchProtection::new()
+    ///             .with_require_message(true)
+    ///             .with_block_force(true),
+    ///     );
+    /// ```
+    pub fn with_branch_protection(
+        mut self,
+        branch: impl Into<String>,
+        protection: BranchProtection,
+    ) -> Self {
+        self.branch_protection.insert(branch, protection);
+        self
+    }
+
+    /// The protection rules configured for `branch`, if any
+    pub fn branch_protection(&self, branch: &str) -> Option<&BranchProtection> {
+        self.branch_protection.get(branch)
+    }
+
+    pub(crate) fn check_protected_commit(
+        &self,
+        branch: Option<&str>,
+        author: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        Ok(self
+            .branch_protection
+            .check_commit(branch, author, message)?)
+    }
+
+    /// Like [`Self::check_protected_commit`], for a resource path
+    pub(crate) fn check_protected_commit_at(
+        &self,
+        path: &str,
+        author: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        match branch_of_path(path) {
+            Some(branch) => self.check_protected_commit(Some(branch), author, message),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_protected_force(
+        &self,
+        branch: Option<&str>,
+        operation: ForceOperation,
+    ) -> anyhow::Result<()> {
+        Ok(self.branch_protection.check_force(branch, operation)?)
+    }
+
+    /// Like [`Self::check_protected_force`], for a resource path
+    pub(crate) fn check_protected_force_at(
+        &self,
+        path: &str,
+        operation: ForceOperation,
+    ) -> anyhow::Result<()> {
+        match branch_of_path(path) {
+            Some(branch) => self.check_protected_force(Some(branch), operation),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_rules() {
+        let mut rules = BranchProtectionRules::default();
+        rules.insert("main", BranchProtection::strict());
+
+        assert_eq!(
+            rules.check_commit(None, "alice", "  "),
+            Err(BranchProtectionError::MissingMessage {
+                branch: "main".to_string()
+            })
+        );
+        assert_eq!(
+            rules.check_commit(Some("main"), "System", "fix typo"),
+            Err(BranchProtectionError::AnonymousAuthor {
+                branch: "main".to_string(),
+                author: "System".to_string(),
+            })
+        );
+        assert!(rules
+            .check_commit(Some("main"), "alice", "fix typo")
+            .is_ok());
+        assert!(rules.check_commit(Some("feature"), "", "").is_ok());
+    }
+
+    #[test]
+    fn test_force_rules() {
+        let mut rules = BranchProtectionRules::default();
+        rules.insert("main", BranchProtection::new().with_block_force(true));
+        rules.insert("dev", BranchProtection::new().with_require_message(true));
+
+        let err = rules
+            .check_force(Some("main"), ForceOperation::Reset)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "branch 'main' is protected: reset is not allowed"
+        );
+        assert!(rules
+            .check_force(Some("dev"), ForceOperation::Reset)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_branch_of_path() {
+        assert_eq!(branch_of_path("admin/mydb/local/branch/main"), Some("main"));
+        assert_eq!(
+            branch_of_path("admin/branch/local/branch/release/1.0"),
+            Some("release/1.0")
+        );
+        assert_eq!(branch_of_path("admin/mydb"), Some("main"));
+        assert_eq!(branch_of_path("admin/mydb/local/commit/abc123"), None);
+    }
+}
@@ -818,6 +818,21 @@ impl ChangeListenerInner {
         )
     }
 
+    /// Latest commit on this listener's branch
+    pub(crate) async fn branch_head(&self) -> anyhow::Result<Option<String>> {
+        self.client.branch_head(&self.spec).await
+    }
+
+    /// Changeset events for the commits on this listener's branch after `since`
+    ///
+    /// This is called by the SseManager when it polls instead of using SSE
+    pub(crate) async fn poll_changesets(
+        &self,
+        since: Option<&str>,
+    ) -> anyhow::Result<super::change_transport::ChangesetPoll> {
+        self.client.poll_changesets(&self.spec, since).await
+    }
+
     /// Dispatch a changeset event to this listener's registered handlers
     ///
     /// This is called by the SseManager when an event matches this listener's resource path
@@ -0,0 +1,560 @@
+//! Transport selection for the changeset stream
+//!
+//! Change listeners normally receive changesets over TerminusDB's SSE endpoint
+//! (`/changesets/stream`). Some proxies buffer or cut long-lived responses, in
+//! which case SSE never delivers anything. As an alternative, listeners can
+//! poll the commit log of their branch and reconstruct the same
+//! [`ChangesetEvent`]s from the triples each new commit added and deleted.
+//!
+//! TerminusDB has no long-poll or websocket endpoint, so polling happens on the
+//! client: each round fetches the new commits since the last one seen. The
+//! handlers registered on a [`ChangeListener`](super::ChangeListener) are the
+//! same for every transport.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! export TERMINUSDB_CHANGE_TRANSPORT=poll           # auto (default), sse or poll
+//! export TERMINUSDB_CHANGE_POLL_INTERVAL_MS=2000    # polling interval, default 5000
+//! ```
+//!
+//! ```rust,ignore
+//! use terminusdb_client::http::ChangeTransport;
+//!
+//! let client = TerminusDBHttpClient::new(url, "admin", "root", "admin")
+//!     .await?
+//!     .with_change_transport(ChangeTransport::Polling {
+//!         interval: Duration::from_secs(2),
+//!     });
+//! ```
+
+use super::changeset::{ChangesetCommitInfo, ChangesetEvent, DocumentChange, MetadataInfo};
+use crate::log::LogEntry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::time::Duration;
+
+/// Default interval between two polls of the commit log
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How change listeners receive changesets from the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeTransport {
+    /// Use SSE, falling back to polling when the SSE stream cannot be opened
+    #[default]
+    Auto,
+    /// Only use the SSE stream, retrying on failure
+    Sse,
+    /// Poll the commit log of each listened branch
+    Polling { interval: Duration },
+}
+
+impl ChangeTransport {
+    /// Polling with the default interval
+    pub fn polling() -> Self {
+        ChangeTransport::Polling {
+            interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Read the transport from environment variables
+    ///
+    /// - `TERMINUSDB_CHANGE_TRANSPORT`: `auto`, `sse` or `poll`
+    /// - `TERMINUSDB_CHANGE_POLL_INTERVAL_MS`: polling interval in milliseconds
+    ///
+    /// Unset or unrecognized values give [`ChangeTransport::Auto`].
+    pub fn from_env() -> Self {
+        Self::parse(
+            env::var("TERMINUSDB_CHANGE_TRANSPORT").ok().as_deref(),
+            env_poll_interval(),
+        )
+    }
+
+    fn parse(transport: Option<&str>, interval: Option<Duration>) -> Self {
+        match transport.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("sse") => ChangeTransport::Sse,
+            Some("poll" | "polling") => ChangeTransport::Polling {
+                interval: interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+            },
+            _ => ChangeTransport::Auto,
+        }
+    }
+
+    /// Interval used once this transport polls
+    pub(crate) fn poll_interval(&self) -> Duration {
+        match self {
+            ChangeTransport::Polling { interval } => *interval,
+            _ => env_poll_interval().unwrap_or(DEFAULT_POLL_INTERVAL),
+        }
+    }
+}
+
+fn env_poll_interval() -> Option<Duration> {
+    env::var("TERMINUSDB_CHANGE_POLL_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_millis)
+}
+
+/// The id of a triple subject, without the instance graph's base IRI
+fn subject_id(subject: &str) -> &str {
+    subject
+        .strip_prefix("terminusdb:///data/")
+        .unwrap_or(subject)
+}
+
+/// The document id a triple subject belongs to: subdocuments such as
+/// `Person/alice/address/Address/1` belong to `Person/alice`
+fn root_document(subject: &str) -> String {
+    subject_id(subject)
+        .splitn(3, '/')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_type_predicate(predicate: &str) -> bool {
+    predicate == "rdf:type" || predicate == "http://www.w3.org/1999/02/22-rdf-syntax-ns#type"
+}
+
+/// Build the changeset event for `commit` from the `(subject, predicate)` pairs
+/// of the triples it added and deleted
+///
+/// A document whose own `rdf:type` triple was added is reported as added, one
+/// whose `rdf:type` was deleted as deleted, and any other document with a
+/// changed triple (including in its subdocuments) as updated.
+pub(crate) fn changeset_from_triples(
+    resource: &str,
+    branch: &str,
+    commit: &LogEntry,
+    added: &[(String, String)],
+    deleted: &[(String, String)],
+) -> ChangesetEvent {
+    let typed = |triples: &[(String, String)]| -> BTreeSet<String> {
+        triples
+            .iter()
+            .filter(|(s, p)| is_type_predicate(p) && root_document(s) == subject_id(s))
+            .map(|(s, _)| root_document(s))
+            .collect()
+    };
+    let added_docs = typed(added);
+    let deleted_docs = typed(deleted);
+
+    let mut actions = BTreeMap::new();
+    for (subject, _) in added.iter().chain(deleted) {
+        let id = root_document(subject);
+        // A document both deleted and re-added in one commit was replaced
+        let action = match (added_docs.contains(&id), deleted_docs.contains(&id)) {
+            (true, false) => "added",
+            (false, true) => "deleted",
+            _ => "updated",
+        };
+        actions.insert(id, action);
+    }
+
+    let count = |action: &str| actions.values().filter(|a| **a == action).count() as u64;
+    let metadata = MetadataInfo {
+        inserts_count: added.len() as u64,
+        deletes_count: deleted.len() as u64,
+        documents_added: count("added"),
+        documents_deleted: count("deleted"),
+        documents_updated: count("updated"),
+    };
+
+    ChangesetEvent {
+        resource: resource.to_string(),
+        branch: branch.to_string(),
+        commit: ChangesetCommitInfo {
+            id: commit.identifier.clone(),
+            author: commit.author.clone(),
+            message: commit.message.clone(),
+            timestamp: commit.timestamp,
+        },
+        metadata,
+        changes: actions
+            .into_iter()
+            .map(|(id, action)| DocumentChange {
+                id,
+                action: action.to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod polling {
+    use super::changeset_from_triples;
+    use crate::http::changeset::ChangesetEvent;
+    use crate::log::LogEntry;
+    use crate::spec::BranchSpec;
+    use anyhow::Context;
+    use serde_json::Value as JsonValue;
+    use std::collections::HashMap;
+    use terminusdb_schema::{GraphType, ToJson, ToTDBInstance, XSDAnySimpleType};
+    use terminusdb_woql2::prelude::{
+        AddedTriple, And, DeletedTriple, Equals, NodeValue, Or, Query as Woql2Query, Select, Using,
+        Value,
+    };
+    use tracing::{debug, warn};
+
+    /// Commits fetched per log request while looking for the last seen commit
+    const LOG_PAGE_SIZE: usize = 50;
+    /// Give up looking for the last seen commit after this many commits
+    const MAX_LOG_SCAN: usize = 1000;
+
+    const ADDED: &str = "added";
+    const DELETED: &str = "deleted";
+
+    /// `(subject, predicate)` pairs a commit added and deleted
+    type CommitTriples = (Vec<(String, String)>, Vec<(String, String)>);
+
+    /// Result of one poll of a branch
+    pub(crate) struct ChangesetPoll {
+        /// Latest commit seen, to pass as `since` to the next poll
+        pub head: Option<String>,
+        /// Events for the commits after `since`, oldest first
+        pub events: Vec<ChangesetEvent>,
+    }
+
+    impl crate::http::client::TerminusDBHttpClient {
+        /// Latest commit on `spec`'s branch, if it has any
+        pub(crate) async fn branch_head(
+            &self,
+            spec: &BranchSpec,
+        ) -> anyhow::Result<Option<String>> {
+            Ok(self
+                .branch_log(spec, 0, 1)
+                .await?
+                .into_iter()
+                .next()
+                .map(|entry| entry.identifier))
+        }
+
+        /// Changeset events for the commits on `spec`'s branch after `since`,
+        /// or for all of its commits if `since` is `None`
+        pub(crate) async fn poll_changesets(
+            &self,
+            spec: &BranchSpec,
+            since: Option<&str>,
+        ) -> anyhow::Result<ChangesetPoll> {
+            let branch = spec.branch.as_deref().unwrap_or("main");
+            let resource = format!("{}/{}/local/branch/{}", self.org, spec.db, branch);
+
+            let mut new_commits = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = self.branch_log(spec, offset, LOG_PAGE_SIZE).await?;
+                let page_len = page.len();
+                let mut found = false;
+                for entry in page {
+                    if since == Some(entry.identifier.as_str()) {
+                        found = true;
+                        break;
+                    }
+                    new_commits.push(entry);
+                }
+                offset += page_len;
+
+                let end_of_log = page_len < LOG_PAGE_SIZE;
+                if found || (since.is_none() && end_of_log) {
+                    break;
+                }
+                if end_of_log || offset >= MAX_LOG_SCAN {
+                    // The branch was reset or rebased past the last seen commit
+                    let head = new_commits.first().map(|entry| entry.identifier.clone());
+                    warn!(
+                        "Commit {:?} is no longer in the recent log of {}; resuming from {:?}",
+                        since, resource, head
+                    );
+                    return Ok(ChangesetPoll {
+                        head,
+                        events: vec![],
+                    });
+                }
+            }
+
+            let head = new_commits
+                .first()
+                .map(|entry| entry.identifier.clone())
+                .or_else(|| since.map(str::to_string));
+
+            let mut triples = self.commit_triples(spec, &new_commits).await?;
+            let mut events = Vec::with_capacity(new_commits.len());
+            for commit in new_commits.iter().rev() {
+                let (added, deleted) = triples.remove(&commit.identifier).unwrap_or_default();
+                debug!(
+                    "Commit {} on {}: {} triple(s) added, {} deleted",
+                    commit.identifier,
+                    resource,
+                    added.len(),
+                    deleted.len()
+                );
+                events.push(changeset_from_triples(
+                    &resource, branch, commit, &added, &deleted,
+                ));
+            }
+
+            Ok(ChangesetPoll { head, events })
+        }
+
+        /// Commit log of the branch in `spec`, newest first
+        async fn branch_log(
+            &self,
+            spec: &BranchSpec,
+            offset: usize,
+            count: usize,
+        ) -> anyhow::Result<Vec<LogEntry>> {
+            let uri = self
+                .build_url()
+                .endpoint("log")
+                .database_with_branch(spec)
+                .log_params(offset, count, false)
+                .build();
+
+            let res = self
+                .http
+                .get(uri)
+                .basic_auth(&self.user, Some(&self.pass))
+                .send()
+                .await?;
+
+            self.parse_response(res).await
+        }
+
+        /// `(subject, predicate)` of the instance triples each of `commits`
+        /// added and deleted, by commit id, read in a single query
+        async fn commit_triples(
+            &self,
+            spec: &BranchSpec,
+            commits: &[LogEntry],
+        ) -> anyhow::Result<HashMap<String, CommitTriples>> {
+            if commits.is_empty() {
+                return Ok(HashMap::new());
+            }
+
+            let var = |name: &str| Value::Variable(name.to_string());
+            let string = |s: &str| Value::Data(XSDAnySimpleType::String(s.to_string()));
+            let branches = commits
+                .iter()
+                .flat_map(|commit| [(commit, ADDED), (commit, DELETED)])
+                .map(|(commit, change)| {
+                    let subject = NodeValue::Variable("s".to_string());
+                    let predicate = NodeValue::Variable("p".to_string());
+                    let object = var("o");
+                    let graph = Some(GraphType::Instance);
+                    let triples = if change == DELETED {
+                        Woql2Query::DeletedTriple(DeletedTriple {
+                            subject,
+                            predicate,
+                            object,
+                            graph,
+                        })
+                    } else {
+                        Woql2Query::AddedTriple(AddedTriple {
+                            subject,
+                            predicate,
+                            object,
+                            graph,
+                        })
+                    };
+                    Woql2Query::And(And {
+                        and: vec![
+                            Woql2Query::Equals(Equals {
+                                left: var("commit"),
+                                right: string(&commit.identifier),
+                            }),
+                            Woql2Query::Equals(Equals {
+                                left: var("change"),
+                                right: string(change),
+                            }),
+                            Woql2Query::Using(Using {
+                                collection: format!("commit/{}", commit.identifier),
+                                query: Box::new(triples),
+                            }),
+                        ],
+                    })
+                })
+                .collect();
+
+            let query = Woql2Query::Using(Using {
+                collection: format!("{}/{}", self.org, spec.db),
+                query: Box::new(Woql2Query::Select(Select {
+                    variables: ["commit", "change", "s", "p"].map(str::to_string).to_vec(),
+                    query: Box::new(Woql2Query::Or(Or { or: branches })),
+                })),
+            });
+
+            let res = self
+                .query_raw::<JsonValue>(Some(spec.clone()), query.to_instance(None).to_json(), None)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to read the changes of commits {}..{}",
+                        commits[commits.len() - 1].identifier,
+                        commits[0].identifier
+                    )
+                })?;
+
+            let mut triples: HashMap<String, CommitTriples> = HashMap::new();
+            for binding in res.bindings {
+                let (Some(commit), Some(change), Some(s), Some(p)) = (
+                    binding.get("commit").and_then(binding_str),
+                    binding.get("change").and_then(binding_str),
+                    binding.get("s").and_then(binding_str),
+                    binding.get("p").and_then(binding_str),
+                ) else {
+                    continue;
+                };
+                let (added, deleted) = triples.entry(commit.to_string()).or_default();
+                let list = if change == DELETED { deleted } else { added };
+                list.push((s.to_string(), p.to_string()));
+            }
+            Ok(triples)
+        }
+    }
+
+    /// A bound IRI, or the value of a bound string literal
+    fn binding_str(value: &JsonValue) -> Option<&str> {
+        value
+            .as_str()
+            .or_else(|| value.get("@value").and_then(JsonValue::as_str))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use polling::ChangesetPoll;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit() -> LogEntry {
+        serde_json::from_value(serde_json::json!({
+            "@id": "ValidCommit/abc",
+            "@type": "ValidCommit",
+            "author": "admin",
+            "identifier": "abc",
+            "message": "update people",
+            "schema": "",
+            "timestamp": 1700000000.0,
+        }))
+        .unwrap()
+    }
+
+    fn triples(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(s, p)| (s.to_string(), p.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_transport() {
+        assert_eq!(ChangeTransport::parse(None, None), ChangeTransport::Auto);
+        assert_eq!(
+            ChangeTransport::parse(Some("SSE"), None),
+            ChangeTransport::Sse
+        );
+        assert_eq!(
+            ChangeTransport::parse(Some("poll"), Some(Duration::from_millis(250))),
+            ChangeTransport::Polling {
+                interval: Duration::from_millis(250)
+            }
+        );
+        assert_eq!(
+            ChangeTransport::parse(Some("poll"), None),
+            ChangeTransport::polling()
+        );
+        assert_eq!(
+            ChangeTransport::parse(Some("carrier-pigeon"), None),
+            ChangeTransport::Auto
+        );
+    }
+
+    #[test]
+    fn test_root_document() {
+        assert_eq!(root_document("Person/alice"), "Person/alice");
+        assert_eq!(
+            root_document("Person/alice/address/Address/1"),
+            "Person/alice"
+        );
+        assert_eq!(
+            root_document("terminusdb:///data/Person/alice"),
+            "Person/alice"
+        );
+    }
+
+    #[test]
+    fn test_changeset_from_triples() {
+        let added = triples(&[
+            ("Person/bob", "rdf:type"),
+            ("Person/bob", "@schema:name"),
+            ("Person/alice", "@schema:age"),
+            ("Person/carol/address/Address/1", "rdf:type"),
+        ]);
+        let deleted = triples(&[
+            ("Person/alice", "@schema:age"),
+            ("Person/dave", "rdf:type"),
+            ("Person/dave", "@schema:name"),
+        ]);
+
+        let event = changeset_from_triples(
+            "admin/db/local/branch/main",
+            "main",
+            &commit(),
+            &added,
+            &deleted,
+        );
+
+        let changes: Vec<_> = event
+            .changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.action.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("Person/alice", "updated"),
+                ("Person/bob", "added"),
+                // A new subdocument updates its parent
+                ("Person/carol", "updated"),
+                ("Person/dave", "deleted"),
+            ]
+        );
+
+        assert_eq!(event.commit.id, "abc");
+        assert_eq!(event.resource, "admin/db/local/branch/main");
+        assert_eq!(event.metadata.inserts_count, 4);
+        assert_eq!(event.metadata.deletes_count, 3);
+        assert_eq!(event.metadata.documents_added, 1);
+        assert_eq!(event.metadata.documents_deleted, 1);
+        assert_eq!(event.metadata.documents_updated, 2);
+    }
+
+    #[test]
+    fn test_changeset_from_iri_triples() {
+        let added = triples(&[
+            ("terminusdb:///data/Person/bob", "rdf:type"),
+            (
+                "terminusdb:///data/Person/bob/address/Address/1",
+                "rdf:type",
+            ),
+        ]);
+        let deleted = triples(&[(
+            "terminusdb:///data/Person/dave",
+            "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+        )]);
+
+        let event = changeset_from_triples("admin/db", "main", &commit(), &added, &deleted);
+
+        let changes: Vec<_> = event
+            .changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.action.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![("Person/bob", "added"), ("Person/dave", "deleted")]
+        );
+    }
+}
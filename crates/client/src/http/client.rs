@@ -59,9 +59,14 @@ pub struct TerminusDBHttpClient {
     pub(crate) debug_config: Arc<RwLock<DebugConfig>>,
     /// Cache of ensured databases to avoid repeated ensure_database calls
     pub(crate) ensured_databases: Arc<Mutex<HashSet<String>>>,
+    /// Client-side protection rules for branches
+    pub(crate) branch_protection: super::branch_protection::BranchProtectionRules,
     /// Centralized SSE manager for change listeners (lazily initialized)
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) sse_manager: Arc<RwLock<Option<Arc<super::sse_manager::SseManager>>>>,
+    /// Transport used by change listeners (SSE, polling, or negotiated)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) change_transport: super::change_transport::ChangeTransport,
     /// Semaphore for limiting concurrent read operations (GET requests)
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) read_semaphore: Option<Arc<tokio::sync::Semaphore>>,
@@ -243,7 +248,9 @@ impl TerminusDBHttpClient {
             query_logger: Arc::new(RwLock::new(None)),
             debug_config: Arc::new(RwLock::new(DebugConfig::default())),
             ensured_databases: Arc::new(Mutex::new(HashSet::new())),
+            branch_protection: Default::default(),
             sse_manager: Arc::new(RwLock::new(None)),
+            change_transport: super::change_transport::ChangeTransport::from_env(),
             read_semaphore: None,
             write_semaphore: None,
         };
@@ -585,6 +592,32 @@ impl TerminusDBHttpClient {
         self
     }
 
+    /// Set how change listeners receive changesets
+    ///
+    /// Defaults to [`ChangeTransport::Auto`](super::ChangeTransport::Auto), or to
+    /// the transport set in `TERMINUSDB_CHANGE_TRANSPORT`. Use polling when SSE
+    /// is blocked by a proxy. Listeners created afterwards by this client (and
+    /// its clones) use the new transport.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use terminusdb_client::http::ChangeTransport;
+    ///
+    /// let client = TerminusDBHttpClient::new(url, "admin", "root", "admin")
+    ///     .await?
+    ///     .with_change_transport(ChangeTransport::polling());
+    /// ```
+    pub fn with_change_transport(
+        mut self,
+        transport: super::change_transport::ChangeTransport,
+    ) -> Self {
+        self.change_transport = transport;
+        // Listeners on the previous transport keep their own manager
+        self.sse_manager = Arc::new(RwLock::new(None));
+        self
+    }
+
     /// Acquire a permit for a read operation
     ///
     /// This is called internally before GET requests. If concurrency limiting is not
@@ -735,6 +768,8 @@ impl TerminusDBHttpClient {
     ///
     /// All listeners for the same client share a single SSE connection, which is
     /// automatically managed and routes events based on the resource path.
+    /// Behind proxies that break SSE, listeners poll the commit log instead; see
+    /// [`with_change_transport`](Self::with_change_transport).
     ///
     /// # Arguments
     /// * `spec` - Branch specification indicating which database and branch to monitor
@@ -822,6 +857,7 @@ impl TerminusDBHttpClient {
                     self.endpoint.to_string(),
                     self.user.clone(),
                     self.pass.clone(),
+                    self.change_transport,
                 ));
                 *manager_lock = Some(manager.clone());
                 manager
@@ -874,6 +910,7 @@ impl TerminusDBHttpClient {
             query_logger: Arc::new(RwLock::new(None)),
             debug_config: Arc::new(RwLock::new(DebugConfig::default())),
             ensured_databases: Arc::new(Mutex::new(HashSet::new())),
+            branch_protection: Default::default(),
         })
     }
 
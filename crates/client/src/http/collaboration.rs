@@ -311,6 +311,8 @@ impl super::client::TerminusDBHttpClient {
         remote_auth: Option<(&str, &str)>,
         timeout: Option<std::time::Duration>,
     ) -> anyhow::Result<serde_json::Value> {
+        self.check_protected_commit_at(path, author, message)?;
+
         let start_time = Instant::now();
         let uri = self.build_url().endpoint("pull").add_path(path).build();
 
@@ -0,0 +1,238 @@
+//! Database-scoped client for single-database applications
+//!
+//! `DatabaseClient` wraps a `TerminusDBHttpClient` together with a default
+//! `BranchSpec`, so everyday calls can drop the spec argument:
+//!
+//! ```rust,ignore
+//! let db = TerminusDBHttpClient::local_node().await.for_database("mydb");
+//! db.save(&person).await?;
+//! let person: Person = db.get(&id).await?;
+//! ```
+//!
+//! Like [`BranchClient`](super::BranchClient) it does NOT implement `Deref`:
+//! its spec-less `get`/`save`/`query`/... would otherwise shadow the client's
+//! aliased methods of the same name. Per-call overrides go through
+//! [`client`](DatabaseClient::client)
+//! (`db.client().get_instance(id, &other_spec, &mut deserializer)`).
+
+use {
+    super::{
+        client::TerminusDBHttpClient, document::DeleteOpts, InsertInstanceResult,
+        TDBInsertInstanceResult, TerminusDBModel,
+    },
+    crate::{
+        document::{DocumentInsertArgs, GetOpts},
+        result::ResponseWithHeaders,
+        spec::BranchSpec,
+        DefaultTDBDeserializer,
+    },
+    std::{collections::HashMap, fmt::Debug},
+    terminusdb_schema::{InstanceFromJson, ToTDBSchema},
+    terminusdb_woql2::prelude::Query as Woql2Query,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::log::{LogEntry, LogOpts};
+
+/// A client bound to a default database (and branch) within its organization.
+///
+/// Created with [`TerminusDBHttpClient::for_database`]. Operations use
+/// [`spec`](Self::spec); use [`client`](Self::client) to target another spec.
+#[derive(Clone)]
+pub struct DatabaseClient {
+    /// The underlying HTTP client (owned clone)
+    client: TerminusDBHttpClient,
+    /// The default branch spec for all spec-less calls
+    spec: BranchSpec,
+}
+
+impl TerminusDBHttpClient {
+    /// Scope a clone of this client to `db` (on `main`) within the client's organization.
+    ///
+    /// Combine with [`for_org`](Self::for_org) to pick a different organization:
+    /// `client.for_org("acme").for_database("crm")`.
+    pub fn for_database(&self, db: &str) -> DatabaseClient {
+        self.for_branch_spec(BranchSpec::new(db))
+    }
+
+    /// Scope a clone of this client to an explicit branch or commit spec.
+    pub fn for_branch_spec(&self, spec: BranchSpec) -> DatabaseClient {
+        DatabaseClient {
+            client: self.clone(),
+            spec,
+        }
+    }
+}
+
+impl DatabaseClient {
+    /// The default branch spec for this client
+    pub fn spec(&self) -> &BranchSpec {
+        &self.spec
+    }
+
+    /// The underlying unscoped client
+    pub fn client(&self) -> &TerminusDBHttpClient {
+        &self.client
+    }
+
+    /// The same database on another branch
+    pub fn on_branch(&self, branch: &str) -> Self {
+        Self {
+            client: self.client.clone(),
+            spec: BranchSpec::with_branch(self.spec.db.clone(), branch),
+        }
+    }
+
+    /// Default insert arguments (author, message, ...) targeting this client's spec
+    pub fn args(&self) -> DocumentInsertArgs {
+        DocumentInsertArgs::from(self.spec.clone())
+    }
+
+    /// Make sure the default database exists
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn ensure(&self) -> anyhow::Result<()> {
+        self.client.ensure_database(&self.spec.db).await.map(|_| ())
+    }
+
+    // =========================================================================
+    // Instance Operations - Read
+    // =========================================================================
+
+    /// Get a strongly-typed model instance by ID
+    pub async fn get<T: TerminusDBModel>(&self, id: &str) -> anyhow::Result<T> {
+        self.client
+            .get_instance(id, &self.spec, &mut DefaultTDBDeserializer)
+            .await
+    }
+
+    /// Get an instance if it exists, return None otherwise
+    pub async fn get_if_exists<T: TerminusDBModel>(&self, id: &str) -> anyhow::Result<Option<T>> {
+        self.client
+            .get_instance_if_exists(id, &self.spec, &mut DefaultTDBDeserializer)
+            .await
+    }
+
+    /// Get multiple instances by IDs
+    pub async fn get_many<T: TerminusDBModel>(&self, ids: Vec<String>) -> anyhow::Result<Vec<T>> {
+        self.client
+            .get_instances(
+                ids,
+                &self.spec,
+                GetOpts::default(),
+                &mut DefaultTDBDeserializer,
+            )
+            .await
+    }
+
+    /// Check if an instance exists by ID
+    pub async fn has<T: TerminusDBModel>(&self, id: &str) -> bool {
+        self.client.has_instance_id::<T>(id, &self.spec).await
+    }
+
+    /// List instances of a type using WOQL
+    pub async fn list<T: TerminusDBModel + InstanceFromJson>(
+        &self,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> anyhow::Result<Vec<T>> {
+        self.client.list_instances(&self.spec, limit, offset).await
+    }
+
+    /// Count instances of a type
+    pub async fn count<T: ToTDBSchema>(&self) -> anyhow::Result<usize> {
+        self.client.count_instances::<T>(&self.spec).await
+    }
+
+    // =========================================================================
+    // Instance Operations - Write
+    // =========================================================================
+
+    /// Save (insert or update) an instance with default commit arguments
+    pub async fn save<I: TerminusDBModel>(
+        &self,
+        model: &I,
+    ) -> anyhow::Result<InsertInstanceResult> {
+        self.client.save_instance(model, self.args()).await
+    }
+
+    /// Create a new instance (fails if it already exists)
+    pub async fn create<I: TerminusDBModel>(
+        &self,
+        model: &I,
+    ) -> anyhow::Result<InsertInstanceResult> {
+        self.client.create_instance(model, self.args()).await
+    }
+
+    /// Update an existing instance
+    pub async fn update<I: TerminusDBModel>(
+        &self,
+        model: &I,
+    ) -> anyhow::Result<InsertInstanceResult> {
+        self.client.update_instance(model, self.args()).await
+    }
+
+    /// Insert multiple instances
+    pub async fn insert_many<M: crate::IntoBoxedTDBInstances>(
+        &self,
+        models: M,
+    ) -> anyhow::Result<ResponseWithHeaders<HashMap<String, TDBInsertInstanceResult>>> {
+        self.client.insert_instances(models, self.args()).await
+    }
+
+    /// Delete an instance by ID
+    pub async fn delete<T: TerminusDBModel>(&self, id: &str) -> anyhow::Result<()> {
+        self.client
+            .delete_instance_by_id::<T>(id, self.args(), DeleteOpts::default())
+            .await
+            .map(|_| ())
+    }
+
+    // =========================================================================
+    // Query Operations
+    // =========================================================================
+
+    /// Execute a WOQL query against the default spec
+    pub async fn query<T: Debug + serde::de::DeserializeOwned>(
+        &self,
+        query: Woql2Query,
+    ) -> anyhow::Result<crate::WOQLResult<T>> {
+        self.client.query(Some(self.spec.clone()), query).await
+    }
+
+    // =========================================================================
+    // Log Operations (non-WASM only)
+    // =========================================================================
+
+    /// Get the commit log of the default spec
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn log(&self, opts: LogOpts) -> anyhow::Result<Vec<LogEntry>> {
+        self.client.log(&self.spec, opts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_for_database_scopes_spec() {
+        let client = TerminusDBHttpClient::new(
+            url::Url::parse("http://localhost:6363").unwrap(),
+            "admin",
+            "root",
+            "acme",
+        )
+        .await
+        .unwrap();
+
+        let db = client.for_database("crm");
+        assert_eq!(db.spec().db, "crm");
+        assert_eq!(db.args().spec.db, "crm");
+        // The unscoped API (and its org) stays reachable via client().
+        assert_eq!(db.client().org(), "acme");
+
+        let dev = db.on_branch("dev");
+        assert_eq!(dev.spec().branch.as_deref(), Some("dev"));
+        assert_eq!(db.spec().branch, None);
+    }
+}
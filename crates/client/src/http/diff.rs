@@ -229,6 +229,8 @@ impl super::client::TerminusDBHttpClient {
         author: &str,
         message: &str,
     ) -> anyhow::Result<serde_json::Value> {
+        self.check_protected_commit_at(branch_path, author, message)?;
+
         let start_time = Instant::now();
         let uri = self
             .build_url()
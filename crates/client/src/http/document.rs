@@ -6,6 +6,7 @@ use tap::TapFallible;
 use crate::ErrorResponse;
 
 use {
+    super::branch_protection::ForceOperation,
     crate::{
         document::{CommitHistoryEntry, DocumentHistoryParams, DocumentInsertArgs, GetOpts},
         err::TypedErrorResponse,
@@ -15,6 +16,7 @@ use {
     },
     ::tracing::{debug, error, instrument, trace},
     anyhow::{anyhow, Context},
+    futures_util::{stream, StreamExt, TryStreamExt},
     serde_json::Value,
     std::{
         collections::{HashMap, HashSet},
@@ -263,8 +265,8 @@ impl super::client::TerminusDBHttpClient {
             .endpoint("document")
             .database(spec)
             .document_get_params(id, opts.unfold, opts.as_list, opts.minimized);
-        if opts.raw_json {
-            url_builder = url_builder.query("raw_json", "true");
+        for (flag, value) in opts.non_default_flags() {
+            url_builder = url_builder.query(flag, &value.to_string());
         }
         let uri = url_builder.build();
 
@@ -342,6 +344,9 @@ impl super::client::TerminusDBHttpClient {
             "minimized".to_string(),
             serde_json::Value::Bool(opts.minimized),
         );
+        for (flag, value) in opts.non_default_flags() {
+            query_doc.insert(flag.to_string(), serde_json::Value::Bool(value));
+        }
         let body = serde_json::to_string(&query_doc)?;
 
         debug!("retrieving document #{} (POST body form)...", id);
@@ -415,6 +420,8 @@ impl super::client::TerminusDBHttpClient {
             return Ok(ResponseWithHeaders::without_headers(HashMap::new()));
         }
 
+        self.check_protected_commit(args.spec.branch.as_deref(), &args.author, &args.message)?;
+
         self.ensure_database(&args.spec.db)
             .await
             .context("ensuring database")?;
@@ -797,6 +804,8 @@ impl super::client::TerminusDBHttpClient {
         model: &impl ToJson,
         args: DocumentInsertArgs,
     ) -> anyhow::Result<Self> {
+        self.check_protected_commit(args.spec.branch.as_deref(), &args.author, &args.message)?;
+
         let json = model.to_json();
 
         let ty = args.ty.to_string().to_lowercase();
@@ -1031,8 +1040,10 @@ impl super::client::TerminusDBHttpClient {
     ///
     /// This function retrieves multiple documents by their IDs and returns them
     /// as untyped `serde_json::Value` objects. It provides no type safety or automatic
-    /// deserialization. For large ID lists, it automatically uses POST with
-    /// `X-HTTP-Method-Override: GET` to avoid URL length limits.
+    /// deserialization. The ids are sent in a POST body (with
+    /// `X-HTTP-Method-Override: GET`) to avoid URL length limits, and long
+    /// lists are split into several requests (see [`GetOpts::with_chunk_size`])
+    /// whose results are merged back in order.
     ///
     /// # Arguments
     /// * `ids` - Vector of document IDs to retrieve
@@ -1084,102 +1095,8 @@ impl super::client::TerminusDBHttpClient {
     ) -> anyhow::Result<Vec<serde_json::Value>> {
         debug!("Retrieving {} documents", ids.len());
 
-        // Build the URL for the document endpoint
-        let uri = self
-            .build_url()
-            .endpoint("document")
-            .database(spec)
-            .document_get_multiple_params(&ids, &opts)
-            .build();
-
-        // Always POST (with `X-HTTP-Method-Override: GET`): the ids travel as a
-        // raw JSON list in the body, never through the URL query string. Ids
-        // generated by `lexical`/`hash` keys are url-encoded (e.g.
-        // `Type/hello%20world`, or a full IRI for a key over a relation field)
-        // and can't be round-tripped through a query param — the document API
-        // won't match them. The body form passes them verbatim. (See the
-        // OpenAPI note recommending the body form for the `ids` field.)
-        let use_post = true;
-
-        debug!(
-            "Fetching documents from: {} (using {})",
-            &uri,
-            if use_post { "POST" } else { "GET" }
-        );
-
-        let start = Instant::now();
-
-        let res = if use_post {
-            // Use POST with X-HTTP-Method-Override: GET for large requests
-            let base_uri = self.build_url().endpoint("document").database(spec).build();
-
-            // Create query document as JSON
-            let mut query_doc = serde_json::Map::new();
-            if !ids.is_empty() {
-                query_doc.insert("ids".to_string(), serde_json::to_value(&ids)?);
-            }
-            query_doc.insert("as_list".to_string(), serde_json::Value::Bool(true));
-            query_doc.insert("unfold".to_string(), serde_json::Value::Bool(opts.unfold));
-            query_doc.insert(
-                "minimized".to_string(),
-                serde_json::Value::Bool(opts.minimized),
-            );
-
-            if let Some(skip) = opts.skip {
-                query_doc.insert("skip".to_string(), serde_json::Value::Number(skip.into()));
-            }
-            if let Some(count) = opts.count {
-                query_doc.insert("count".to_string(), serde_json::Value::Number(count.into()));
-            }
-            if let Some(ref type_filter) = opts.type_filter {
-                query_doc.insert(
-                    "type".to_string(),
-                    serde_json::Value::String(type_filter.clone()),
-                );
-            }
-
-            let query_json = serde_json::to_string(&query_doc)?;
-
-            let mut request = self
-                .http
-                .post(base_uri)
-                .basic_auth(&self.user, Some(&self.pass))
-                .header("Content-Type", "application/json")
-                .header("X-HTTP-Method-Override", "GET")
-                .body(query_json);
-
-            // Apply timeout if provided
-            if let Some(timeout) = opts.timeout {
-                request = request.timeout(timeout);
-            }
-
-            request.send().await?
-        } else {
-            // Use GET for smaller requests
-            let mut request = self.http.get(uri).basic_auth(&self.user, Some(&self.pass));
-
-            // Apply timeout if provided
-            if let Some(timeout) = opts.timeout {
-                request = request.timeout(timeout);
-            }
-
-            request.send().await?
-        };
-
-        // Extract status before consuming response
-        let status = res.status();
-
-        // Parse response as array of JSON values
-        let docs = self.parse_response::<Vec<serde_json::Value>>(res).await?;
-
-        debug!(
-            "Retrieved {} documents with status code: {} in {:?}",
-            docs.len(),
-            status,
-            start.elapsed()
-        );
-
-        Ok(docs)
+        let docs = self.get_documents_with_headers(ids, spec, opts).await?;
+        Ok(docs.into_inner())
     }
 
     /// Retrieve the authored **schema** documents (classes, enums, the
@@ -1256,14 +1173,69 @@ impl super::client::TerminusDBHttpClient {
     ) -> anyhow::Result<ResponseWithHeaders<Vec<serde_json::Value>>> {
         debug!("Retrieving {} documents with headers", ids.len());
 
-        // Build the URL for the document endpoint
-        let uri = self
-            .build_url()
-            .endpoint("document")
-            .database(spec)
-            .document_get_multiple_params(&ids, &opts)
-            .build();
+        let chunks = opts.id_chunks(&ids);
+        if chunks.len() <= 1 {
+            return self.get_documents_chunk(&ids, spec, &opts).await;
+        }
 
+        // Paginate over the merged result rather than within each chunk
+        let (skip, count) = (opts.skip.unwrap_or(0), opts.count);
+        let chunk_opts = GetOpts {
+            skip: None,
+            count: None,
+            ..opts.clone()
+        };
+
+        debug!(
+            "Splitting {} ids into {} requests ({} at a time)",
+            ids.len(),
+            chunks.len(),
+            opts.chunk_concurrency
+        );
+
+        let start = Instant::now();
+
+        // Read the remaining chunks from the commit the first one saw, so a
+        // concurrent write can't leave the merged result half old, half new
+        let first = self
+            .get_documents_chunk(chunks[0], spec, &chunk_opts)
+            .await?;
+        let commit_id = first.commit_id.clone();
+        let pinned = match first.extract_commit_id() {
+            Some(commit) if !spec.is_commit_ref() => spec.clone().ref_commit(commit),
+            _ => spec.clone(),
+        };
+
+        let rest: Vec<_> = stream::iter(&chunks[1..])
+            .map(|chunk| self.get_documents_chunk(chunk, &pinned, &chunk_opts))
+            .buffered(opts.chunk_concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let docs: Vec<serde_json::Value> = std::iter::once(first)
+            .chain(rest)
+            .flat_map(ResponseWithHeaders::into_inner)
+            .skip(skip)
+            .take(count.unwrap_or(usize::MAX))
+            .collect();
+
+        debug!(
+            "Retrieved {} documents in {} requests in {:?}",
+            docs.len(),
+            chunks.len(),
+            start.elapsed()
+        );
+
+        Ok(ResponseWithHeaders::new(docs, commit_id))
+    }
+
+    /// Fetch the documents for one chunk of ids in a single request.
+    async fn get_documents_chunk(
+        &self,
+        ids: &[String],
+        spec: &BranchSpec,
+        opts: &GetOpts,
+    ) -> anyhow::Result<ResponseWithHeaders<Vec<serde_json::Value>>> {
         // Always POST (with `X-HTTP-Method-Override: GET`): the ids travel as a
         // raw JSON list in the body, never through the URL query string. Ids
         // generated by `lexical`/`hash` keys are url-encoded (e.g.
@@ -1271,72 +1243,60 @@ impl super::client::TerminusDBHttpClient {
         // and can't be round-tripped through a query param — the document API
         // won't match them. The body form passes them verbatim. (See the
         // OpenAPI note recommending the body form for the `ids` field.)
-        let use_post = true;
+        let uri = self.build_url().endpoint("document").database(spec).build();
 
         debug!(
-            "Fetching documents from: {} (using {})",
-            &uri,
-            if use_post { "POST" } else { "GET" }
+            "Fetching {} documents from: {} (using POST)",
+            ids.len(),
+            &uri
         );
 
         let start = Instant::now();
 
-        let res = if use_post {
-            // Use POST with X-HTTP-Method-Override: GET for large requests
-            let base_uri = self.build_url().endpoint("document").database(spec).build();
+        // Create query document as JSON
+        let mut query_doc = serde_json::Map::new();
+        if !ids.is_empty() {
+            query_doc.insert("ids".to_string(), serde_json::to_value(ids)?);
+        }
+        query_doc.insert("as_list".to_string(), serde_json::Value::Bool(true));
+        query_doc.insert("unfold".to_string(), serde_json::Value::Bool(opts.unfold));
+        query_doc.insert(
+            "minimized".to_string(),
+            serde_json::Value::Bool(opts.minimized),
+        );
 
-            // Create query document as JSON
-            let mut query_doc = serde_json::Map::new();
-            if !ids.is_empty() {
-                query_doc.insert("ids".to_string(), serde_json::to_value(&ids)?);
-            }
-            query_doc.insert("as_list".to_string(), serde_json::Value::Bool(true));
-            query_doc.insert("unfold".to_string(), serde_json::Value::Bool(opts.unfold));
+        if let Some(skip) = opts.skip {
+            query_doc.insert("skip".to_string(), serde_json::Value::Number(skip.into()));
+        }
+        if let Some(count) = opts.count {
+            query_doc.insert("count".to_string(), serde_json::Value::Number(count.into()));
+        }
+        if let Some(ref type_filter) = opts.type_filter {
             query_doc.insert(
-                "minimized".to_string(),
-                serde_json::Value::Bool(opts.minimized),
+                "type".to_string(),
+                serde_json::Value::String(type_filter.clone()),
             );
+        }
+        for (flag, value) in opts.non_default_flags() {
+            query_doc.insert(flag.to_string(), serde_json::Value::Bool(value));
+        }
 
-            if let Some(skip) = opts.skip {
-                query_doc.insert("skip".to_string(), serde_json::Value::Number(skip.into()));
-            }
-            if let Some(count) = opts.count {
-                query_doc.insert("count".to_string(), serde_json::Value::Number(count.into()));
-            }
-            if let Some(ref type_filter) = opts.type_filter {
-                query_doc.insert(
-                    "type".to_string(),
-                    serde_json::Value::String(type_filter.clone()),
-                );
-            }
-
-            let query_json = serde_json::to_string(&query_doc)?;
-
-            let mut request = self
-                .http
-                .post(base_uri)
-                .basic_auth(&self.user, Some(&self.pass))
-                .header("Content-Type", "application/json")
-                .header("X-HTTP-Method-Override", "GET")
-                .body(query_json);
-
-            // Apply timeout if provided
-            if let Some(timeout) = opts.timeout {
-                request = request.timeout(timeout);
-            }
+        let query_json = serde_json::to_string(&query_doc)?;
 
-            request.send().await?
-        } else {
-            // Use GET for smaller requests
-            let mut request = self.http.get(uri).basic_auth(&self.user, Some(&self.pass));
+        let mut request = self
+            .http
+            .post(uri)
+            .basic_auth(&self.user, Some(&self.pass))
+            .header("Content-Type", "application/json")
+            .header("X-HTTP-Method-Override", "GET")
+            .body(query_json);
 
-            // Apply timeout if provided
-            if let Some(timeout) = opts.timeout {
-                request = request.timeout(timeout);
-            }
+        // Apply timeout if provided
+        if let Some(timeout) = opts.timeout {
+            request = request.timeout(timeout);
+        }
 
-            request.send().await?
-        };
+        let res = request.send().await?;
 
         debug!("Retrieved documents with status code: {}", res.status());
 
@@ -1418,6 +1378,11 @@ impl super::client::TerminusDBHttpClient {
         graph_type: &str,
         opts: DeleteOpts,
     ) -> anyhow::Result<Self> {
+        if opts.is_nuke() {
+            self.check_protected_force(spec.branch.as_deref(), ForceOperation::Nuke)?;
+        }
+        self.check_protected_commit(spec.branch.as_deref(), author, message)?;
+
         let uri = self
             .build_url()
             .endpoint("document")
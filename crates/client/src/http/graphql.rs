@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
-use crate::{TerminusDBAdapterError, TerminusDBHttpClient};
+use crate::{spec::BranchSpec, TerminusDBAdapterError, TerminusDBHttpClient};
 use std::time::{Duration, Instant};
 
 /// A GraphQL request following the standard GraphQL over HTTP specification
@@ -228,17 +228,65 @@ impl TerminusDBHttpClient {
         branch: Option<&str>,
         request: GraphQLRequest,
         timeout: Option<Duration>,
+    ) -> Result<GraphQLResponse<T>, TerminusDBAdapterError> {
+        let branch = branch.unwrap_or("main");
+        self.execute_graphql_on(
+            database,
+            branch,
+            &format!("branch/{}", branch),
+            request,
+            timeout,
+        )
+        .await
+    }
+
+    /// Execute a GraphQL query against the branch or commit `spec` points at.
+    ///
+    /// Unlike [`execute_graphql`](Self::execute_graphql), this honours a commit
+    /// reference on `spec`, so the query reads the database as of that commit.
+    pub async fn execute_graphql_at<T: serde::de::DeserializeOwned>(
+        &self,
+        spec: &BranchSpec,
+        request: GraphQLRequest,
+        timeout: Option<Duration>,
+    ) -> Result<GraphQLResponse<T>, TerminusDBAdapterError> {
+        match spec.commit_id() {
+            Some(commit) => {
+                self.execute_graphql_on(
+                    &spec.db,
+                    commit.as_str(),
+                    &format!("commit/{}", commit),
+                    request,
+                    timeout,
+                )
+                .await
+            }
+            None => {
+                self.execute_graphql(&spec.db, spec.branch.as_deref(), request, timeout)
+                    .await
+            }
+        }
+    }
+
+    /// Execute a GraphQL query against `ref_path` (`branch/<name>` or
+    /// `commit/<id>`), logging it under `branch`.
+    async fn execute_graphql_on<T: serde::de::DeserializeOwned>(
+        &self,
+        database: &str,
+        branch: &str,
+        ref_path: &str,
+        request: GraphQLRequest,
+        timeout: Option<Duration>,
     ) -> Result<GraphQLResponse<T>, TerminusDBAdapterError> {
         use crate::debug::{OperationEntry, OperationType, QueryLogEntry};
 
         let start_time = Instant::now();
-        let branch = branch.unwrap_or("main");
-        let url = self.build_graphql_url(database, branch);
+        let url = self.build_graphql_url(database, ref_path);
 
         // Create operation entry with the GraphQL query stored in context
         let mut operation = OperationEntry::new(
             OperationType::GraphQL,
-            format!("/graphql/{}/{}/local/branch/{}", self.org, database, branch),
+            format!("/graphql/{}/{}/local/{}", self.org, database, ref_path),
         )
         .with_context(Some(database.to_string()), Some(branch.to_string()))
         .with_additional_context(request.query.clone());
@@ -447,14 +495,15 @@ impl TerminusDBHttpClient {
         })
     }
 
-    /// Build the GraphQL endpoint URL for a database
-    fn build_graphql_url(&self, database: &str, branch: &str) -> String {
+    /// Build the GraphQL endpoint URL for a database at `ref_path`
+    /// (`branch/<name>` or `commit/<id>`)
+    fn build_graphql_url(&self, database: &str, ref_path: &str) -> String {
         format!(
-            "{}/graphql/{}/{}/local/branch/{}",
+            "{}/graphql/{}/{}/local/{}",
             self.endpoint.as_str().trim_end_matches('/'),
             self.org,
             database,
-            branch
+            ref_path
         )
     }
 }
@@ -194,7 +194,7 @@ mod tests {
         );
 
         let mut result = InsertInstanceResult::new(results, "Person/123".to_string()).unwrap();
-        result.commit_id = Some(CommitId::new("branch:abc123"));
+        result.commit_id = Some(CommitId::from("branch:abc123"));
 
         let versioned_ref: VersionedEntityIDFor<Person> = result.root_versioned_ref().unwrap();
 
@@ -234,7 +234,7 @@ mod tests {
         );
 
         let mut result = InsertInstanceResult::new(results, "Person/123".to_string()).unwrap();
-        result.commit_id = Some(CommitId::new("branch:abc123"));
+        result.commit_id = Some(CommitId::from("branch:abc123"));
 
         // Get only Person refs
         let person_refs: Vec<VersionedEntityIDFor<Person>> = result.all_versioned_refs().unwrap();
@@ -555,6 +555,52 @@ impl super::client::TerminusDBHttpClient {
         Self::process_operation_result::<I>(res)
     }
 
+    /// Updates only the given fields of an existing instance, leaving every other
+    /// property of the stored document as it is.
+    ///
+    /// The selected properties are sent as a `ForceValue` JSON patch (see
+    /// [`ToTDBInstance::to_instance_partial`]), so concurrent writers touching other
+    /// fields are not overwritten the way a full [`update_instance`](Self::update_instance) would.
+    ///
+    /// # Arguments
+    /// * `model` - The instance carrying the new values; must have an ID
+    /// * `fields` - TDB property names to update (after any `#[tdb(name)]` rename)
+    /// * `args` - Database/branch plus commit author and message
+    ///
+    /// # Example
+    /// ```rust
+    /// user.age = 32;
+    /// client.patch_instance_fields(&user, &["age"], args).await?;
+    /// ```
+    #[instrument(
+        name = "terminus.instance.patch_fields",
+        skip(self, model, args),
+        fields(
+            db = %args.spec.db,
+            branch = ?args.spec.branch,
+            entity_type = %I::schema_name(),
+            fields = ?fields
+        ),
+        err
+    )]
+    pub async fn patch_instance_fields<I: TerminusDBModel>(
+        &self,
+        model: &I,
+        fields: &[&str],
+        args: DocumentInsertArgs,
+    ) -> anyhow::Result<serde_json::Value> {
+        let patch = model.to_instance_partial(fields).to_force_patch()?;
+        let branch_path = format!(
+            "{}/{}/local/branch/{}",
+            self.org,
+            args.spec.db,
+            args.spec.branch.as_deref().unwrap_or("main")
+        );
+
+        self.patch(&branch_path, &[patch], &args.author, &args.message)
+            .await
+    }
+
     /// Saves an instance to the database, creating it if it doesn't exist or updating if it does.
     ///
     /// This method first attempts to create the instance using POST. If that fails because
@@ -1954,7 +2000,7 @@ impl super::client::TerminusDBHttpClient {
         opts: DeleteOpts,
     ) -> anyhow::Result<Self> {
         let instance_id = instance
-            .instance_id()
+            .known_id()
             .ok_or_else(|| anyhow!("Instance has no ID - cannot delete"))?;
         let id = instance_id.to_string();
         let full_id = format_id::<T>(&id);
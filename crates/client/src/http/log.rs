@@ -43,7 +43,7 @@ impl super::client::TerminusDBHttpClient {
         let uri = self
             .build_url()
             .endpoint("log")
-            .simple_database(&spec.db)
+            .database_with_branch(spec)
             .log_params(offset.unwrap_or_default(), count.unwrap_or(10), verbose)
             .build();
 
@@ -103,7 +103,7 @@ impl super::client::TerminusDBHttpClient {
         entries
             .into_iter()
             .next()
-            .map(|entry| crate::CommitId::new(entry.identifier))
+            .map(|entry| crate::CommitId::from(entry.identifier))
             .ok_or_else(|| anyhow::anyhow!("No commits found in branch"))
     }
 
@@ -117,6 +117,103 @@ impl super::client::TerminusDBHttpClient {
         ))
     }
 
+    /// Get the commit IDs of a branch, newest first
+    ///
+    /// Pages through the branch log until `depth` commits have been read, or
+    /// the whole history if `depth` is `None`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn commit_history(
+        &self,
+        spec: &BranchSpec,
+        depth: Option<usize>,
+    ) -> anyhow::Result<Vec<terminusdb_types::CommitId>> {
+        const PAGE_SIZE: usize = 100;
+
+        let mut history = Vec::new();
+        loop {
+            let count = depth.map_or(PAGE_SIZE, |d| (d - history.len()).min(PAGE_SIZE));
+            if count == 0 {
+                break;
+            }
+            let opts = LogOpts {
+                offset: Some(history.len()),
+                count: Some(count),
+                verbose: false,
+            };
+            let entries = self.log(spec, opts).await?;
+            let fetched = entries.len();
+            for entry in entries {
+                history.push(terminusdb_types::CommitId::new(entry.identifier)?);
+            }
+            if fetched < count {
+                break;
+            }
+        }
+        Ok(history)
+    }
+
+    /// Resolve a [`Ref`](terminusdb_types::Ref) such as `HEAD~3`, `dev~1` or
+    /// `FIRST`; `HEAD` and `FIRST` refer to the branch in `spec`
+    ///
+    /// Returns a spec for the same database pointing at the resolved branch
+    /// head or commit, ready for time-travel reads.
+    ///
+    /// # Example
+    /// ```rust
+    /// let reference: Ref = "HEAD~2".parse()?;
+    /// let two_back = client.resolve_ref(&branch_spec, &reference).await?;
+    /// let dev_parent = client.resolve_ref(&branch_spec, &"dev~1".parse()?).await?;
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    #[instrument(
+        name = "terminus.log.resolve_ref",
+        skip(self),
+        fields(
+            db = %spec.db,
+            branch = ?spec.branch,
+            reference = %reference
+        ),
+        err
+    )]
+    pub async fn resolve_ref(
+        &self,
+        spec: &BranchSpec,
+        reference: &terminusdb_types::Ref,
+    ) -> anyhow::Result<BranchSpec> {
+        let branch = match &spec.branch {
+            Some(branch) => terminusdb_types::BranchName::new(branch.as_str())?,
+            None => terminusdb_types::BranchName::main(),
+        };
+        let history = if reference.needs_history() {
+            if spec.is_commit_ref() && !matches!(reference, terminusdb_types::Ref::Named(_)) {
+                anyhow::bail!("'{}' is relative to a branch, not a commit", reference);
+            }
+            let target = reference.history_branch(&branch);
+            let target_spec = BranchSpec::with_branch(&spec.db, target.as_str());
+            self.commit_history(&target_spec, reference.history_depth()).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(match reference.resolve(&branch, &history)? {
+            terminusdb_types::BranchRef::Branch { name, .. } => {
+                BranchSpec::with_branch(&spec.db, name.as_str())
+            }
+            terminusdb_types::BranchRef::Commit(id) => {
+                BranchSpec::with_commit(&spec.db, id.as_str())
+            }
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub async fn resolve_ref(
+        &self,
+        _spec: &BranchSpec,
+        _reference: &terminusdb_types::Ref,
+    ) -> anyhow::Result<BranchSpec> {
+        Err(anyhow::anyhow!("resolve_ref not implemented for WASM"))
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[instrument(
         name = "terminus.log.iterate",
@@ -94,6 +94,13 @@ impl TerminusDBHttpClient {
         F: FnOnce(BranchClient) -> Fut,
         Fut: Future<Output = anyhow::Result<T>>,
     {
+        // Fail before creating the temporary branch if the final rebase would be
+        // refused; the rebase below retries on errors
+        let merge_msg = options
+            .merge_message
+            .unwrap_or_else(|| "Merge from temporary branch".to_string());
+        self.check_protected_commit(spec.branch.as_deref(), &options.author, &merge_msg)?;
+
         // 1. Generate temporary branch name
         let temp_branch_name = format!("_merge_branch_{}", uuid::Uuid::new_v4());
 
@@ -153,10 +160,6 @@ impl TerminusDBHttpClient {
                 }
 
                 // Rebase onto target
-                let merge_msg = options
-                    .merge_message
-                    .unwrap_or_else(|| "Merge from temporary branch".to_string());
-
                 debug!("Rebasing {} onto {}", temp_branch_path, origin_path);
 
                 // Serialize the rebase if a shared merge lock was supplied:
@@ -137,6 +137,10 @@ impl TerminusDBHttpClient {
         operations: Vec<MigrationOperation>,
         options: MigrationOptions,
     ) -> anyhow::Result<MigrationResponse> {
+        if !options.dry_run {
+            self.check_protected_commit(spec.branch.as_deref(), author, message)?;
+        }
+
         let uri = self
             .build_url()
             .endpoint("migration")
@@ -7,6 +7,7 @@
 //!
 //! - `client`: Core client struct and constructors
 //! - `branch`: Branch management operations (squash, reset, rebase)
+//! - `branch_protection`: Client-side protection rules for branches
 //! - `collaboration`: Collaboration operations (fetch, push, pull, clone)
 //! - `database`: Database administration operations
 //! - `diff`: Diff and patch operations
@@ -14,27 +15,33 @@
 //! - `document`: Untyped document CRUD operations
 //! - `instance`: Strongly-typed instance operations
 //! - `query`: Query execution and WOQL operations
+//! - `profile`: Query profiling with timing breakdown and binding counts
 //! - `log`: Log and commit tracking operations
 //! - `organization`: Organization management operations
 //! - `remote`: Remote repository management
 //! - `response`: Response parsing utilities
 //! - `role`: Role management operations
+//! - `triples`: Turtle writes to the instance graph
 //! - `url_builder`: URL construction utilities
 //! - `user`: User management operations
 //! - `helpers`: Helper functions
 //! - `graphql`: GraphQL query execution and introspection
 //! - `changeset`: SSE changeset event types and streaming
 //! - `change_listener`: Type-safe change listener API
+//! - `change_transport`: SSE or polling transport for change listeners
 
 // Public modules
 pub mod apply;
 pub mod branch;
 pub mod branch_client;
+pub mod branch_protection;
 pub mod change_listener;
+pub mod change_transport;
 pub mod changeset;
 pub mod client;
 pub mod collaboration;
 pub mod database;
+pub mod database_client;
 pub mod diff;
 pub mod document;
 pub mod graphql;
@@ -46,12 +53,14 @@ pub mod merge_branch;
 pub mod migration;
 pub mod prefix;
 pub mod open;
+pub mod profile;
 pub mod organization;
 pub mod query;
 pub mod remote;
 pub mod response;
 pub mod role;
 pub mod schema;
+pub mod triples;
 pub mod url_builder;
 pub mod user;
 pub mod versions;
@@ -66,9 +75,14 @@ pub mod concurrency_limiter;
 
 // Re-export main types and traits
 pub use branch_client::BranchClient;
+pub use branch_protection::{
+    BranchProtection, BranchProtectionError, BranchProtectionRules, ForceOperation,
+};
 pub use change_listener::ChangeListener;
+pub use change_transport::ChangeTransport;
 pub use changeset::{ChangesetCommitInfo, ChangesetEvent, DocumentChange, MetadataInfo};
 pub use client::TerminusDBHttpClient;
+pub use database_client::DatabaseClient;
 #[cfg(not(target_arch = "wasm32"))]
 pub use concurrency_limiter::ConcurrencyLimitConfig;
 pub use document::DeleteOpts;
@@ -76,6 +90,7 @@ pub use apply::ApplyOptions;
 pub use diff::{DiffOptions, DiffResponse};
 pub use migration::{MigrationOperation, MigrationOptions, MigrationResponse};
 pub use prefix::PrefixResponse;
+pub use profile::{ProfileReport, ServerTiming};
 pub use graphql::{GraphQLError, GraphQLRequest, GraphQLResponse};
 pub use helpers::{
     dedup_documents_by_id, dedup_instances_by_id, dump_failed_payload, dump_json, dump_schema,
@@ -0,0 +1,467 @@
+//! Query profiling
+//!
+//! [`profile_query`](TerminusDBHttpClient::profile_query) runs a WOQL query
+//! like [`query`](TerminusDBHttpClient::query) does, but returns a
+//! [`ProfileReport`] instead of the results: where the time went (waiting for
+//! a concurrency permit, the server answering, reading the body,
+//! deserializing it), how many bindings came back and how often each variable
+//! was bound, plus any `Server-Timing` metrics the server sent along.
+//!
+//! The report's `Display` implementation is the table printed by
+//! `tdb query-profile`.
+
+use {
+    super::client::TerminusDBHttpClient,
+    crate::{
+        debug::{OperationEntry, OperationType},
+        spec::BranchSpec,
+        ApiResponse, WOQLResult,
+    },
+    anyhow::Context,
+    serde_json::{json, Map, Value},
+    std::{
+        fmt,
+        time::{Duration, Instant},
+    },
+    terminusdb_schema::{FromTDBInstance, ToJson, ToTDBInstance},
+    terminusdb_woql2::{json::normalize_woql_json, prelude::Query as Woql2Query},
+    tracing::instrument,
+};
+
+/// Timing breakdown and result statistics of a profiled query
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileReport {
+    /// time spent waiting for a read permit from the concurrency limiter
+    pub queued: Duration,
+    /// time from sending the request until the response headers arrived
+    pub request: Duration,
+    /// time spent reading the response body
+    pub download: Duration,
+    /// time spent deserializing the response body into bindings
+    pub deserialize: Duration,
+    /// wall-clock time of the whole call
+    pub total: Duration,
+    /// size of the response body
+    pub response_bytes: usize,
+    /// metrics from the `Server-Timing` response headers, if the server sent any
+    pub server_timing: Vec<ServerTiming>,
+    /// variable names reported by the server, in order
+    pub variables: Vec<String>,
+    /// number of bindings (result rows)
+    pub bindings: usize,
+    /// for each variable, the number of bindings in which it is bound
+    pub bound: Vec<(String, usize)>,
+    pub inserts: usize,
+    pub deletes: usize,
+    pub transaction_retry_count: usize,
+}
+
+impl ProfileReport {
+    /// The time not accounted for by the server: `request` minus the
+    /// `Server-Timing` durations, when the server reported any
+    pub fn network(&self) -> Option<Duration> {
+        let server: Duration = self
+            .server_timing
+            .iter()
+            .filter_map(|timing| timing.duration)
+            .sum();
+        (!server.is_zero()).then(|| self.request.saturating_sub(server))
+    }
+
+    /// Fill in the result statistics from a deserialized response
+    fn count(&mut self, result: &WOQLResult<Map<String, Value>>) {
+        self.variables = result.api_variable_names.clone();
+        self.bindings = result.bindings.len();
+        self.bound = result
+            .api_variable_names
+            .iter()
+            .map(|var| {
+                let count = result
+                    .bindings
+                    .iter()
+                    .filter(|binding| binding.get(var).is_some_and(|value| !value.is_null()))
+                    .count();
+                (var.clone(), count)
+            })
+            .collect();
+        self.inserts = result.inserts;
+        self.deletes = result.deletes;
+        self.transaction_retry_count = result.transaction_retry_count;
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "timing")?;
+        writeln!(f, "  {:<14}{:>12}", "queued", millis(self.queued))?;
+        writeln!(f, "  {:<14}{:>12}", "request", millis(self.request))?;
+        for timing in &self.server_timing {
+            let duration = timing.duration.map(millis).unwrap_or_default();
+            write!(f, "    {:<12}{:>12}", timing.name, duration)?;
+            match &timing.description {
+                Some(description) => writeln!(f, "  {}", description)?,
+                None => writeln!(f)?,
+            }
+        }
+        if let Some(network) = self.network() {
+            writeln!(f, "    {:<12}{:>12}", "(network)", millis(network))?;
+        }
+        writeln!(f, "  {:<14}{:>12}", "download", millis(self.download))?;
+        writeln!(f, "  {:<14}{:>12}", "deserialize", millis(self.deserialize))?;
+        writeln!(f, "  {:<14}{:>12}", "total", millis(self.total))?;
+
+        writeln!(f, "result")?;
+        writeln!(f, "  {:<14}{:>12}", "bindings", self.bindings)?;
+        writeln!(f, "  {:<14}{:>12}", "bytes", self.response_bytes)?;
+        if self.inserts + self.deletes > 0 {
+            writeln!(f, "  {:<14}{:>12}", "inserts", self.inserts)?;
+            writeln!(f, "  {:<14}{:>12}", "deletes", self.deletes)?;
+        }
+        if self.transaction_retry_count > 0 {
+            writeln!(f, "  {:<14}{:>12}", "retries", self.transaction_retry_count)?;
+        }
+
+        if !self.bound.is_empty() {
+            writeln!(f, "bound")?;
+            for (var, count) in &self.bound {
+                writeln!(f, "  {:<14}{:>12}", var, count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// One metric of a `Server-Timing` header, such as `db;dur=53.2;desc="query"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerTiming {
+    pub name: String,
+    pub duration: Option<Duration>,
+    pub description: Option<String>,
+}
+
+impl ServerTiming {
+    /// Parse the comma-separated metrics of a `Server-Timing` header value.
+    /// Malformed parameters are ignored rather than rejected.
+    pub fn parse_header(value: &str) -> Vec<ServerTiming> {
+        split_unquoted(value, ',')
+            .into_iter()
+            .filter_map(|metric| {
+                let mut params = split_unquoted(metric, ';').into_iter();
+                let name = params.next()?.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                let mut timing = ServerTiming {
+                    name: name.to_string(),
+                    duration: None,
+                    description: None,
+                };
+                for param in params {
+                    let Some((key, value)) = param.split_once('=') else {
+                        continue;
+                    };
+                    let value = value.trim();
+                    match key.trim().to_ascii_lowercase().as_str() {
+                        "dur" => {
+                            timing.duration = value
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|ms| ms.is_finite() && *ms >= 0.0)
+                                .map(|ms| Duration::from_nanos((ms * 1e6).round() as u64));
+                        }
+                        "desc" => timing.description = Some(unquote(value)),
+                        _ => {}
+                    }
+                }
+                Some(timing)
+            })
+            .collect()
+    }
+}
+
+/// Split on `separator` outside double-quoted strings
+fn split_unquoted(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => {
+            let mut out = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => out.extend(chars.next()),
+                    c => out.push(c),
+                }
+            }
+            out
+        }
+        None => value.to_string(),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TerminusDBHttpClient {
+    /// Execute a WOQL query and report how long each stage took instead of
+    /// returning the results.
+    ///
+    /// The query is sent exactly as [`query`](Self::query) would send it. The
+    /// bindings are deserialized as JSON maps and only counted, so the
+    /// deserialization time is a lower bound for typed results.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use terminusdb_client::*;
+    /// # use terminusdb_woql2::prelude::*;
+    /// # async fn example(client: TerminusDBHttpClient, query: Query) -> anyhow::Result<()> {
+    /// let report = client
+    ///     .profile_query(Some(BranchSpec::new("mydb")), query)
+    ///     .await?;
+    /// println!("{}", report);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn profile_query(
+        &self,
+        spec: Option<BranchSpec>,
+        query: Woql2Query,
+    ) -> anyhow::Result<ProfileReport> {
+        let json_query = query.to_instance(None).to_json();
+        self.profile_query_raw(spec, json_query).await
+    }
+
+    /// Profile a query given as a string, either WOQL JS syntax or JSON-LD,
+    /// like [`query_string`](Self::query_string) accepts
+    pub async fn profile_query_string(
+        &self,
+        spec: Option<BranchSpec>,
+        query_string: &str,
+    ) -> anyhow::Result<ProfileReport> {
+        let json_query = match serde_json::from_str::<Value>(query_string) {
+            Ok(json_value) => json_value,
+            Err(_) => terminusdb_woql_js::parse_js_woql(query_string)?,
+        };
+        self.profile_query_raw(spec, json_query).await
+    }
+
+    /// Profile a query given as JSON-LD
+    #[instrument(
+        name = "terminus.query.profile",
+        skip(self, query),
+        fields(
+            db = spec.as_ref().map(|s| s.db.as_str()).unwrap_or("default"),
+            branch = ?spec.as_ref().and_then(|s| s.branch.as_ref())
+        ),
+        err
+    )]
+    pub async fn profile_query_raw(
+        &self,
+        spec: Option<BranchSpec>,
+        mut query: Value,
+    ) -> anyhow::Result<ProfileReport> {
+        let start_time = Instant::now();
+
+        // See query_raw: ensure default graphs before sending.
+        normalize_woql_json(&mut query);
+
+        let uri = match &spec {
+            None => self.build_url().endpoint("woql").build(),
+            Some(spc) => self
+                .build_url()
+                .endpoint("woql")
+                .simple_database(&spc.db)
+                .build(),
+        };
+        let body = serde_json::to_string(&json!({ "query": query }))?;
+
+        let mut operation = OperationEntry::new(
+            OperationType::Query,
+            format!(
+                "/api/woql/{}",
+                spec.as_ref().map(|s| s.db.as_str()).unwrap_or("default")
+            ),
+        )
+        .with_context(
+            spec.as_ref().map(|s| s.db.clone()),
+            spec.as_ref().and_then(|s| s.branch.clone()),
+        );
+        if let Ok(parsed) = Woql2Query::from_json(query) {
+            operation = operation.with_query(parsed);
+        }
+
+        let result = self.profile_request(uri, body, start_time).await;
+
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        operation = match &result {
+            Ok(report) => operation.success(Some(report.bindings), duration_ms),
+            Err(e) => operation.failure(e.to_string(), duration_ms),
+        };
+        self.operation_log.push(operation);
+
+        result
+    }
+
+    async fn profile_request(
+        &self,
+        uri: String,
+        body: String,
+        start_time: Instant,
+    ) -> anyhow::Result<ProfileReport> {
+        let _permit = self.acquire_read_permit().await;
+        let queued = start_time.elapsed();
+
+        let sent = Instant::now();
+        let res = self
+            .http
+            .post(uri)
+            .basic_auth(&self.user, Some(&self.pass))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .context("failed to send WOQL query")?;
+        let request = sent.elapsed();
+
+        let server_timing = res
+            .headers()
+            .get_all("Server-Timing")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(ServerTiming::parse_header)
+            .collect();
+
+        let received = Instant::now();
+        let text = res.text().await.context("failed to read response text")?;
+        let download = received.elapsed();
+
+        let parsing = Instant::now();
+        let response: ApiResponse<WOQLResult<Map<String, Value>>> =
+            serde_json::from_str(&text).context("failed to deserialize query response")?;
+        let deserialize = parsing.elapsed();
+
+        let result = match response {
+            ApiResponse::Success(result) => result,
+            ApiResponse::Error(err) => return Err(err.into()),
+        };
+
+        let mut report = ProfileReport {
+            queued,
+            request,
+            download,
+            deserialize,
+            total: start_time.elapsed(),
+            response_bytes: text.len(),
+            server_timing,
+            variables: Vec::new(),
+            bindings: 0,
+            bound: Vec::new(),
+            inserts: 0,
+            deletes: 0,
+            transaction_retry_count: 0,
+        };
+        report.count(&result);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_timing() {
+        let timings = ServerTiming::parse_header(
+            r#"db;dur=53.5;desc="query, \"fast\"", cache;desc=miss, total;dur=60"#,
+        );
+        assert_eq!(
+            timings,
+            vec![
+                ServerTiming {
+                    name: "db".to_string(),
+                    duration: Some(Duration::from_micros(53_500)),
+                    description: Some(r#"query, "fast""#.to_string()),
+                },
+                ServerTiming {
+                    name: "cache".to_string(),
+                    duration: None,
+                    description: Some("miss".to_string()),
+                },
+                ServerTiming {
+                    name: "total".to_string(),
+                    duration: Some(Duration::from_millis(60)),
+                    description: None,
+                },
+            ]
+        );
+        assert!(ServerTiming::parse_header("").is_empty());
+        assert_eq!(ServerTiming::parse_header("a;dur=-1")[0].duration, None);
+    }
+
+    #[test]
+    fn test_report_counts_bound_variables() {
+        let result: WOQLResult<Map<String, Value>> = serde_json::from_value(json!({
+            "api:status": "api:success",
+            "api:variable_names": ["Person", "Name"],
+            "bindings": [
+                { "Person": "Person/1", "Name": "Ann" },
+                { "Person": "Person/2", "Name": null },
+            ],
+            "deletes": 0,
+            "inserts": 0,
+            "transaction_retry_count": 0
+        }))
+        .unwrap();
+
+        let mut report = ProfileReport {
+            queued: Duration::ZERO,
+            request: Duration::from_millis(10),
+            download: Duration::from_millis(1),
+            deserialize: Duration::from_millis(2),
+            total: Duration::from_millis(13),
+            response_bytes: 120,
+            server_timing: ServerTiming::parse_header("woql;dur=8"),
+            variables: Vec::new(),
+            bindings: 0,
+            bound: Vec::new(),
+            inserts: 0,
+            deletes: 0,
+            transaction_retry_count: 0,
+        };
+        report.count(&result);
+
+        assert_eq!(report.bindings, 2);
+        assert_eq!(
+            report.bound,
+            vec![("Person".to_string(), 2), ("Name".to_string(), 1)]
+        );
+        assert_eq!(report.network(), Some(Duration::from_millis(2)));
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("    woql            8.000 ms"));
+        assert!(rendered.contains("  bindings                 2"));
+        assert!(rendered.contains("  Name                     1"));
+        assert!(!rendered.contains("inserts"));
+    }
+}
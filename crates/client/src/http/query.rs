@@ -464,6 +464,11 @@ impl super::client::TerminusDBHttpClient {
         message: &str,
         timeout: Option<Duration>,
     ) -> anyhow::Result<WOQLResult<T>> {
+        // The request goes to the database path, so it commits on the default branch
+        if spec.is_some() {
+            self.check_protected_commit(None, author, message)?;
+        }
+
         // See query_raw: ensure default graphs before sending.
         normalize_woql_json(&mut query);
 
@@ -2,8 +2,11 @@
 //!
 //! This module provides a centralized manager for SSE connections that routes
 //! changeset events to registered listeners based on their resource paths.
+//! Depending on its [`ChangeTransport`], the manager polls the commit log of
+//! each listened branch instead of, or after failing to open, the SSE stream.
 
 use super::change_listener::ChangeListenerInner;
+use super::change_transport::ChangeTransport;
 use super::changeset::ChangesetEvent;
 use anyhow::{anyhow, Context};
 use futures_util::stream::StreamExt;
@@ -12,9 +15,15 @@ use reqwest_eventsource::{Event, EventSource};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, Weak};
 use std::time::Duration;
+use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
+/// Consecutive SSE attempts that fail to open before `Auto` switches to polling
+const SSE_ATTEMPTS_BEFORE_POLLING: u32 = 3;
+/// How long to wait for the SSE stream to open, e.g. behind a buffering proxy
+const SSE_OPEN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Centralized SSE manager that maintains one connection and routes to multiple listeners
 pub struct SseManager {
     inner: Arc<SseManagerInner>,
@@ -24,6 +33,10 @@ struct SseManagerInner {
     endpoint: String,
     user: String,
     pass: String,
+    /// How changesets are received
+    transport: ChangeTransport,
+    /// Wakes the polling loop when a listener registers
+    registered: Notify,
     /// Registry of listeners by resource path (e.g., "admin/dev/local/branch/main")
     listeners: RwLock<HashMap<String, Vec<Weak<ChangeListenerInner>>>>,
     /// Handle to the background SSE processing task
@@ -32,12 +45,14 @@ struct SseManagerInner {
 
 impl SseManager {
     /// Create a new SSE manager
-    pub fn new(endpoint: String, user: String, pass: String) -> Self {
+    pub fn new(endpoint: String, user: String, pass: String, transport: ChangeTransport) -> Self {
         Self {
             inner: Arc::new(SseManagerInner {
                 endpoint,
                 user,
                 pass,
+                transport,
+                registered: Notify::new(),
                 listeners: RwLock::new(HashMap::new()),
                 task_handle: RwLock::new(None),
             }),
@@ -60,6 +75,7 @@ impl SseManager {
 
         // Start the SSE connection if not already running
         drop(listeners); // Release lock before starting
+        self.inner.registered.notify_one();
         self.ensure_running()?;
 
         Ok(())
@@ -76,12 +92,15 @@ impl SseManager {
             }
         }
 
-        info!("Starting centralized SSE connection");
+        info!(
+            "Starting centralized changeset stream ({:?})",
+            self.inner.transport
+        );
 
         // Start new background task
         let manager = self.inner.clone();
         let handle = tokio::spawn(async move {
-            manager.run_sse_loop_with_retry().await;
+            manager.run().await;
         });
 
         *handle_lock = Some(handle);
@@ -108,10 +127,29 @@ impl SseManager {
 }
 
 impl SseManagerInner {
+    /// Receive changesets over the configured transport
+    async fn run(&self) {
+        match self.transport {
+            ChangeTransport::Polling { interval } => self.run_poll_loop(interval).await,
+            ChangeTransport::Sse | ChangeTransport::Auto => {
+                // Only returns when `Auto` gives up on SSE
+                self.run_sse_loop_with_retry().await;
+                let interval = self.transport.poll_interval();
+                warn!(
+                    "SSE stream failed to open {} times, falling back to polling every {:?}",
+                    SSE_ATTEMPTS_BEFORE_POLLING, interval
+                );
+                self.run_poll_loop(interval).await
+            }
+        }
+    }
+
     /// Main SSE processing loop with automatic retry
     async fn run_sse_loop_with_retry(&self) {
+        let mut failed_attempts = 0;
         loop {
-            match self.run_sse_loop().await {
+            let mut opened = false;
+            match self.run_sse_loop(&mut opened).await {
                 Ok(()) => {
                     warn!("SSE connection closed, reconnecting in 5 seconds...");
                 }
@@ -119,12 +157,25 @@ impl SseManagerInner {
                     error!("SSE connection error: {}, reconnecting in 5 seconds...", e);
                 }
             }
+
+            if opened {
+                failed_attempts = 0;
+            } else {
+                failed_attempts += 1;
+            }
+            if self.transport == ChangeTransport::Auto
+                && failed_attempts >= SSE_ATTEMPTS_BEFORE_POLLING
+            {
+                return;
+            }
             tokio::time::sleep(Duration::from_secs(5)).await;
         }
     }
 
     /// Main SSE processing loop using reqwest-eventsource
-    async fn run_sse_loop(&self) -> anyhow::Result<()> {
+    ///
+    /// Sets `opened` once the server has accepted the stream.
+    async fn run_sse_loop(&self, opened: &mut bool) -> anyhow::Result<()> {
         // Ensure proper path joining - strip trailing slash from endpoint, then add our path
         let url = format!("{}/changesets/stream", self.endpoint.trim_end_matches('/'));
 
@@ -152,9 +203,21 @@ impl SseManagerInner {
         info!("SSE connection established, processing events...");
 
         // Process events from the stream
-        while let Some(event_result) = event_source.next().await {
+        loop {
+            let next = if *opened {
+                event_source.next().await
+            } else {
+                tokio::time::timeout(SSE_OPEN_TIMEOUT, event_source.next())
+                    .await
+                    .map_err(|_| anyhow!("SSE stream did not open within {:?}", SSE_OPEN_TIMEOUT))?
+            };
+            let Some(event_result) = next else {
+                break;
+            };
+
             match event_result {
                 Ok(Event::Open) => {
+                    *opened = true;
                     info!("SSE connection opened successfully");
                 }
                 Ok(Event::Message(message)) => {
@@ -187,6 +250,58 @@ impl SseManagerInner {
         Ok(())
     }
 
+    /// Polling loop: reports the commits made on each listened branch since
+    /// the previous round
+    async fn run_poll_loop(&self, interval: Duration) {
+        info!("Polling the commit log for changes every {:?}", interval);
+
+        // Last commit seen per resource; `None` if the branch had no commits
+        let mut heads: HashMap<String, Option<String>> = HashMap::new();
+        loop {
+            let targets = self.poll_targets();
+            heads.retain(|resource, _| targets.iter().any(|(r, _)| r == resource));
+
+            for (resource, listener) in targets {
+                let Some(since) = heads.get(&resource) else {
+                    // New listener: only report commits made from now on
+                    match listener.branch_head().await {
+                        Ok(head) => {
+                            heads.insert(resource, head);
+                        }
+                        Err(e) => warn!("Failed to read the head of {}: {}", resource, e),
+                    }
+                    continue;
+                };
+
+                match listener.poll_changesets(since.as_deref()).await {
+                    Ok(poll) => {
+                        for event in poll.events {
+                            self.route_event(event).await;
+                        }
+                        heads.insert(resource, poll.head);
+                    }
+                    Err(e) => warn!("Failed to poll changes of {}: {}", resource, e),
+                }
+            }
+
+            // Poll right away when a listener registers so it gets its baseline
+            let _ = tokio::time::timeout(interval, self.registered.notified()).await;
+        }
+    }
+
+    /// One live listener per resource, used to query its branch
+    fn poll_targets(&self) -> Vec<(String, Arc<ChangeListenerInner>)> {
+        let listeners = self.listeners.read().unwrap();
+        listeners
+            .iter()
+            .filter_map(|(resource, list)| {
+                list.iter()
+                    .find_map(Weak::upgrade)
+                    .map(|listener| (resource.clone(), listener))
+            })
+            .collect()
+    }
+
     /// Route an event to all listeners for the matching resource
     async fn route_event(&self, event: ChangesetEvent) {
         let resource = event.resource.clone();
@@ -0,0 +1,61 @@
+//! The triples endpoint (`/api/triples/{resource}/{graph}`): write a graph as
+//! Turtle rather than as documents. Used to load RDF that was exported from
+//! another database or produced by other tools.
+
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::spec::BranchSpec;
+use crate::TerminusDBHttpClient;
+
+#[derive(Debug, Clone, Serialize)]
+struct CommitInfo<'a> {
+    author: &'a str,
+    message: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TriplesRequest<'a> {
+    turtle: &'a str,
+    commit_info: CommitInfo<'a>,
+}
+
+impl TerminusDBHttpClient {
+    /// Add the triples of a Turtle document to the instance graph of the
+    /// branch in `spec`, as one commit. Existing triples are kept; the result
+    /// must still satisfy the schema.
+    pub async fn insert_triples(
+        &self,
+        spec: &BranchSpec,
+        turtle: &str,
+        author: &str,
+        message: &str,
+    ) -> anyhow::Result<Value> {
+        self.check_protected_commit(spec.branch.as_deref(), author, message)?;
+
+        let uri = self
+            .build_url()
+            .endpoint("triples")
+            .database_with_branch(spec)
+            .add_path("instance")
+            .build();
+
+        let body = TriplesRequest {
+            turtle,
+            commit_info: CommitInfo { author, message },
+        };
+
+        let _permit = self.acquire_write_permit().await;
+        let res = self
+            .http
+            .post(uri)
+            .basic_auth(&self.user, Some(&self.pass))
+            .json(&body)
+            .send()
+            .await
+            .context("failed to send triples request")?;
+
+        self.parse_response(res).await
+    }
+}
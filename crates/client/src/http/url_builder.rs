@@ -146,6 +146,11 @@ impl<'a> UrlBuilder<'a> {
                 .push(("type".to_string(), type_filter.clone()));
         }
 
+        for (flag, value) in opts.non_default_flags() {
+            self.query_params
+                .push((flag.to_string(), value.to_string()));
+        }
+
         self
     }
 
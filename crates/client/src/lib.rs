@@ -11,13 +11,6 @@
 use terminusdb_schema::*;
 use terminusdb_schema_derive::*;
 
-// Import for primitive value conversion
-use terminusdb_schema::json::InstancePropertyFromJson;
-use terminusdb_schema::{
-    FromInstanceProperty, InstanceProperty, Primitive, PrimitiveValue, Schema, ToInstanceProperty,
-    ToMaybeTDBSchema, ToSchemaClass, STRING,
-};
-
 pub use {
     deserialize::*, document::*, err::*, info::*, r#trait::*, result::*, spec::*, versioned_id::*,
 };
@@ -56,6 +49,8 @@ pub mod versioned_id;
 mod woql_helpers;
 #[cfg(not(target_arch = "wasm32"))]
 pub use query::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use woql_helpers::data_value_to_object;
 
 use serde::{Deserialize, Serialize};
 use std::convert::{From, Into};
@@ -88,111 +83,8 @@ pub enum TerminusAPIStatus {
 
 pub type TerminusDBResult<T> = Result<T, TerminusDBAdapterError>;
 
-/// A strongly typed commit identifier.
-///
-/// This newtype wrapper ensures type safety when working with commit IDs
-/// throughout the TerminusDB client API.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct CommitId(String);
-
-impl CommitId {
-    /// Create a new CommitId from a string
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
-    }
-
-    /// Get the commit ID as a string slice
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-
-    /// Consume the CommitId and return the inner String
-    pub fn into_inner(self) -> String {
-        self.0
-    }
-}
-
-impl From<String> for CommitId {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
-
-impl From<&str> for CommitId {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
-    }
-}
-
-impl std::fmt::Display for CommitId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl AsRef<str> for CommitId {
-    fn as_ref(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::ops::Deref for CommitId {
-    type Target = str;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-// Implement ToSchemaClass to mark CommitId as a string primitive
-impl ToSchemaClass for CommitId {
-    fn to_class() -> String {
-        STRING.to_string()
-    }
-}
-
-// Mark CommitId as a primitive type
-impl Primitive for CommitId {}
-
-// Implement ToMaybeTDBSchema (default impl for primitives)
-impl ToMaybeTDBSchema for CommitId {}
-
-// Implement conversion to PrimitiveValue
-impl From<CommitId> for PrimitiveValue {
-    fn from(commit_id: CommitId) -> Self {
-        PrimitiveValue::String(commit_id.0)
-    }
-}
-
-// Implement ToInstanceProperty with generic parent type
-impl<Parent> ToInstanceProperty<Parent> for CommitId {
-    fn to_property(self, _field_name: &str, _parent: &Schema) -> InstanceProperty {
-        InstanceProperty::Primitive(self.into())
-    }
-}
-
-// Implement FromInstanceProperty to enable deserialization
-impl FromInstanceProperty for CommitId {
-    fn from_property(prop: &InstanceProperty) -> anyhow::Result<Self> {
-        match prop {
-            InstanceProperty::Primitive(PrimitiveValue::String(s)) => Ok(CommitId::new(s.clone())),
-            _ => anyhow::bail!("Expected string for CommitId, got {:?}", prop),
-        }
-    }
-}
-
-// Implement InstancePropertyFromJson for JSON deserialization
-impl<Parent> InstancePropertyFromJson<Parent> for CommitId {
-    fn property_from_json(json: serde_json::Value) -> anyhow::Result<InstanceProperty> {
-        match json {
-            serde_json::Value::String(s) => {
-                Ok(InstanceProperty::Primitive(PrimitiveValue::String(s)))
-            }
-            _ => anyhow::bail!("Expected string for CommitId, got {:?}", json),
-        }
-    }
-}
+/// Commit identifier, shared with the CLI and the path types.
+pub use terminusdb_types::CommitId;
 
 #[derive(Default)]
 pub struct CommitMeta {
@@ -200,45 +92,14 @@ pub struct CommitMeta {
     message: Option<String>,
 }
 
-#[test]
-fn test_commit_id_conversions() {
-    // Test creating a CommitId
-    let commit_id = CommitId::new("abc123");
-    assert_eq!(commit_id.as_str(), "abc123");
-
-    // Test conversion to PrimitiveValue
-    let primitive_value: PrimitiveValue = commit_id.clone().into();
-    assert!(matches!(primitive_value, PrimitiveValue::String(s) if s == "abc123"));
-
-    // Test ToInstanceProperty
-    let instance_prop = <CommitId as ToInstanceProperty<()>>::to_property(
-        commit_id.clone(),
-        "commit",
-        &Schema::empty_class("Test"),
-    );
-    assert!(
-        matches!(instance_prop, InstanceProperty::Primitive(PrimitiveValue::String(s)) if s == "abc123")
-    );
-
-    // Test FromInstanceProperty
-    let prop = InstanceProperty::Primitive(PrimitiveValue::String("def456".to_string()));
-    let restored = CommitId::from_property(&prop).unwrap();
-    assert_eq!(restored.as_str(), "def456");
-
-    // Test InstancePropertyFromJson
-    let json_val = serde_json::Value::String("xyz789".to_string());
-    let prop_from_json =
-        <CommitId as InstancePropertyFromJson<()>>::property_from_json(json_val).unwrap();
-    assert!(
-        matches!(prop_from_json, InstanceProperty::Primitive(PrimitiveValue::String(s)) if s == "xyz789")
-    );
-}
-
 pub use self::document::GetOpts;
 // `log` is a native-only module (it drives the HTTP commit-log endpoints); its
 // re-export must be gated to match, or wasm builds fail on the missing module.
 #[cfg(not(target_arch = "wasm32"))]
-pub use self::log::{CommitLogEntry, CommitLogIterator, EntityIterator, LogEntry, LogOpts};
+pub use self::log::{
+    CommitClock, CommitLogEntry, CommitLogIterator, CommitNormalizer, EntityIterator, FixedClock,
+    LogEntry, LogOpts, SteppingClock,
+};
 
 // Re-export streams trait for convenience
 pub use futures_util::Stream;
@@ -3,9 +3,10 @@ mod entity;
 mod entry;
 mod iter;
 mod migration;
+mod normalize;
 mod opts;
 
-pub use {commit::*, entity::*, entry::*, iter::*, migration::*, opts::*};
+pub use {commit::*, entity::*, entry::*, iter::*, migration::*, normalize::*, opts::*};
 
 // NOTE: Log functionality is tested in client/tests/http_client_tests.rs
 // (test_commit_added_entities_query uses client.log() internally).
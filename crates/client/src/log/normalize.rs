@@ -0,0 +1,268 @@
+//! Deterministic commit metadata for snapshot and replay tests.
+//!
+//! TerminusDB stamps every commit with a server-side wall-clock `timestamp` and a
+//! random `identifier`; neither can be set by the client (only `author` and
+//! `message` travel with an insert, see [`DocumentInsertArgs`](crate::DocumentInsertArgs)).
+//! A [`CommitNormalizer`] rewrites those volatile values after the fact so two runs
+//! of the same test produce byte-identical log entries, history entries, changeset
+//! events or raw JSON:
+//!
+//! - each distinct commit id becomes `commit-0`, `commit-1`, … in order of first
+//!   appearance (so parent links stay consistent);
+//! - each distinct timestamp is replaced by the next value of a pluggable
+//!   [`CommitClock`] (again keyed on first appearance);
+//! - optionally, every author is replaced by a fixed name.
+//!
+//! ```rust,ignore
+//! let mut norm = CommitNormalizer::new(SteppingClock::new(1_700_000_000.0, 1.0));
+//! let log = client.log(&spec, LogOpts::default()).await?;
+//! let stable: Vec<_> = log.into_iter().map(|e| norm.normalize_log_entry(e)).collect();
+//! assert_eq!(stable[0].identifier, "commit-0");
+//! ```
+
+use crate::document::CommitHistoryEntry;
+use crate::log::LogEntry;
+use crate::{ChangesetCommitInfo, CommitId};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Source of replacement timestamps (Unix seconds) for [`CommitNormalizer`].
+pub trait CommitClock: Send {
+    /// The timestamp to hand out for the next previously-unseen commit time.
+    fn next_timestamp(&mut self) -> f64;
+}
+
+/// Clock that always returns the same instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock(pub f64);
+
+impl CommitClock for FixedClock {
+    fn next_timestamp(&mut self) -> f64 {
+        self.0
+    }
+}
+
+/// Clock that starts at `start` and advances by `step` on every call, preserving
+/// the relative order of the original commits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SteppingClock {
+    next: f64,
+    step: f64,
+}
+
+impl SteppingClock {
+    pub fn new(start: f64, step: f64) -> Self {
+        Self { next: start, step }
+    }
+}
+
+impl Default for SteppingClock {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl CommitClock for SteppingClock {
+    fn next_timestamp(&mut self) -> f64 {
+        let ts = self.next;
+        self.next += self.step;
+        ts
+    }
+}
+
+/// Rewrites commit identifiers, timestamps and (optionally) authors into stable
+/// placeholders. See the [module docs](self).
+pub struct CommitNormalizer<C: CommitClock = SteppingClock> {
+    clock: C,
+    author: Option<String>,
+    ids: HashMap<String, String>,
+    timestamps: HashMap<u64, f64>,
+}
+
+impl Default for CommitNormalizer<SteppingClock> {
+    fn default() -> Self {
+        Self::new(SteppingClock::default())
+    }
+}
+
+impl<C: CommitClock> CommitNormalizer<C> {
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            author: None,
+            ids: HashMap::new(),
+            timestamps: HashMap::new(),
+        }
+    }
+
+    /// Replace every author with `author` as well.
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Stable placeholder for a commit id. Accepts bare ids as well as the
+    /// `ValidCommit/<id>` / `InitialCommit/<id>` document ids and
+    /// `branch:<id>` data-version headers, keeping the prefix intact.
+    pub fn normalize_commit_id(&mut self, id: &str) -> String {
+        if let Some((prefix, raw)) = split_commit_prefix(id) {
+            return format!("{}{}", prefix, self.placeholder(raw));
+        }
+        self.placeholder(id)
+    }
+
+    /// Deterministic replacement for a server timestamp. The same input always
+    /// maps to the same output within one normalizer.
+    pub fn normalize_timestamp(&mut self, timestamp: f64) -> f64 {
+        let clock = &mut self.clock;
+        *self
+            .timestamps
+            .entry(timestamp.to_bits())
+            .or_insert_with(|| clock.next_timestamp())
+    }
+
+    fn normalize_author(&self, author: String) -> String {
+        self.author.clone().unwrap_or(author)
+    }
+
+    fn placeholder(&mut self, raw: &str) -> String {
+        let next = self.ids.len();
+        self.ids
+            .entry(raw.to_string())
+            .or_insert_with(|| format!("commit-{}", next))
+            .clone()
+    }
+
+    pub fn normalize_log_entry(&mut self, mut entry: LogEntry) -> LogEntry {
+        entry.id = self.normalize_commit_id(&entry.id);
+        entry.identifier = self.normalize_commit_id(&entry.identifier);
+        entry.parent = entry.parent.map(|p| self.normalize_commit_id(&p));
+        entry.timestamp = self.normalize_timestamp(entry.timestamp);
+        entry.author = self.normalize_author(entry.author);
+        entry
+    }
+
+    pub fn normalize_history_entry(&mut self, mut entry: CommitHistoryEntry) -> CommitHistoryEntry {
+        entry.identifier = CommitId::from(self.normalize_commit_id(entry.identifier.as_str()));
+        entry.timestamp = self.normalize_timestamp(entry.timestamp);
+        entry.author = self.normalize_author(entry.author);
+        entry
+    }
+
+    pub fn normalize_changeset_commit(
+        &mut self,
+        mut commit: ChangesetCommitInfo,
+    ) -> ChangesetCommitInfo {
+        commit.id = self.normalize_commit_id(&commit.id);
+        commit.timestamp = self.normalize_timestamp(commit.timestamp);
+        commit.author = self.normalize_author(commit.author);
+        commit
+    }
+
+    /// Normalize raw JSON in place (e.g. a `log` response or a snapshot file):
+    /// rewrites `identifier`/`parent`/`commit` strings, commit-typed `@id`s,
+    /// numeric `timestamp`s and, if configured, `author` anywhere in the tree.
+    pub fn normalize_json(&mut self, value: &mut Value) {
+        match value {
+            Value::Array(items) => items.iter_mut().for_each(|v| self.normalize_json(v)),
+            Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    let replacement = match (key.as_str(), &*v) {
+                        ("identifier" | "parent" | "commit", Value::String(s)) => {
+                            Some(Value::String(self.normalize_commit_id(s)))
+                        }
+                        ("@id", Value::String(s)) if split_commit_doc_prefix(s).is_some() => {
+                            Some(Value::String(self.normalize_commit_id(s)))
+                        }
+                        ("timestamp", Value::Number(n)) => n
+                            .as_f64()
+                            .map(|ts| self.normalize_timestamp(ts))
+                            .and_then(serde_json::Number::from_f64)
+                            .map(Value::Number),
+                        ("author", Value::String(_)) => self.author.clone().map(Value::String),
+                        _ => None,
+                    };
+                    match replacement {
+                        Some(new_value) => *v = new_value,
+                        None => self.normalize_json(v),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Split a commit reference into its prefix (`ValidCommit/`, `InitialCommit/`,
+/// `<branch>:`) and the raw id, if it carries one.
+fn split_commit_prefix(id: &str) -> Option<(&str, &str)> {
+    split_commit_doc_prefix(id).or_else(|| id.find(':').map(|pos| id.split_at(pos + 1)))
+}
+
+/// Split a commit *document* id (`ValidCommit/<id>`, `InitialCommit/<id>`).
+fn split_commit_doc_prefix(id: &str) -> Option<(&str, &str)> {
+    ["ValidCommit/", "InitialCommit/"]
+        .into_iter()
+        .find_map(|prefix| id.strip_prefix(prefix).map(|raw| (prefix, raw)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(id: &str, parent: Option<&str>, timestamp: f64) -> LogEntry {
+        LogEntry {
+            id: format!("ValidCommit/{}", id),
+            ty: "ValidCommit".to_string(),
+            author: "alice".to_string(),
+            identifier: id.to_string(),
+            parent: parent.map(|p| format!("ValidCommit/{}", p)),
+            instance: None,
+            message: "msg".to_string(),
+            migration: vec![],
+            schema: "layer".to_string(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_log_entries_normalize_consistently() {
+        let mut norm = CommitNormalizer::new(SteppingClock::new(100.0, 10.0)).with_author("tester");
+
+        let newest = norm.normalize_log_entry(entry("zzz", Some("aaa"), 1719865624.97));
+        let oldest = norm.normalize_log_entry(entry("aaa", None, 1719865600.12));
+
+        assert_eq!(newest.id, "ValidCommit/commit-0");
+        assert_eq!(newest.identifier, "commit-0");
+        assert_eq!(newest.parent.as_deref(), Some("ValidCommit/commit-1"));
+        assert_eq!(oldest.identifier, "commit-1");
+        assert_eq!(newest.timestamp, 100.0);
+        assert_eq!(oldest.timestamp, 110.0);
+        assert_eq!(newest.author, "tester");
+    }
+
+    #[test]
+    fn test_normalize_json_is_repeatable() {
+        let raw = json!([
+            {"@id": "ValidCommit/abc", "identifier": "abc", "timestamp": 1719865624.5, "author": "bob"},
+            {"@id": "Person/abc", "commit": "main:abc", "timestamp": 1719865624.5}
+        ]);
+
+        let run = |mut value: Value| {
+            let mut norm = CommitNormalizer::new(FixedClock(0.0));
+            norm.normalize_json(&mut value);
+            value
+        };
+        let once = run(raw.clone());
+
+        assert_eq!(once, run(raw));
+        assert_eq!(once[0]["@id"], "ValidCommit/commit-0");
+        assert_eq!(once[0]["identifier"], "commit-0");
+        assert_eq!(once[0]["author"], "bob");
+        // Non-commit ids are untouched; `branch:<id>` keeps its prefix.
+        assert_eq!(once[1]["@id"], "Person/abc");
+        assert_eq!(once[1]["commit"], "main:commit-0");
+        assert_eq!(once[1]["timestamp"].as_f64(), Some(0.0));
+    }
+}
@@ -1,11 +1,9 @@
-use crate::woql_helpers::isa_model;
+use crate::woql_helpers::{data_value_to_object, isa_model};
 use crate::{BranchSpec, TerminusDBHttpClient};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::marker::PhantomData;
-use terminusdb_schema::{
-    FromTDBInstance, GraphType, InstanceFromJson, TerminusDBModel, XSDAnySimpleType,
-};
+use terminusdb_schema::{FromTDBInstance, GraphType, InstanceFromJson, TerminusDBModel};
 use terminusdb_woql2::macros::IntoNodeValue;
 use terminusdb_woql2::misc::{Count, Limit, Start};
 use terminusdb_woql2::prelude::{
@@ -213,39 +211,13 @@ impl<T: TerminusDBModel + InstanceFromJson> InstanceQueryable for FilteredListMo
     type Model = T;
 
     fn build(&self, subject: &Value) -> Query {
-        // One triple pattern per filter condition. The DataValue -> object
-        // mapping mirrors the previous builder conversion exactly (URI ->
-        // Node, HexBinary -> String, UnsignedInt -> Integer, Float -> Decimal,
-        // everything else -> the same xsd variant).
+        // One triple pattern per filter condition; see `data_value_to_object`
+        // for how each value maps onto the triple object.
         let triples: Vec<Query> = self
             .filters
             .iter()
             .map(|(field, value)| {
-                let object: Value = match value {
-                    DataValue::Variable(v) => Value::Variable(v.clone()),
-                    DataValue::Data(d) => match d {
-                        // URIs are represented as nodes
-                        XSDAnySimpleType::URI(uri) => Value::Node(uri.clone()),
-                        // Store hex binary as string
-                        XSDAnySimpleType::HexBinary(hex) => {
-                            Value::Data(XSDAnySimpleType::String(hex.clone()))
-                        }
-                        // Unsigned integers are emitted as (signed) integers
-                        XSDAnySimpleType::UnsignedInt(u) => {
-                            Value::Data(XSDAnySimpleType::Integer(*u as i64))
-                        }
-                        // Floats are emitted as decimals via their string form
-                        XSDAnySimpleType::Float(f) => Value::Data(XSDAnySimpleType::Decimal(
-                            f.to_string().parse().expect("Invalid decimal string format"),
-                        )),
-                        // String/Boolean/Integer/Decimal/DateTime/Date/Time pass
-                        // through unchanged.
-                        other => Value::Data(other.clone()),
-                    },
-                    DataValue::List(_) => {
-                        panic!("List values are not supported in filters")
-                    }
-                };
+                let object = data_value_to_object(value);
 
                 // Properties need to be prefixed with @schema: for property lookups
                 Query::Triple(Triple {
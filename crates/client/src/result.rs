@@ -380,7 +380,7 @@ mod tests {
             TDBInsertInstanceResult::Inserted("Person/789".to_string()),
         );
 
-        let response = ResponseWithHeaders::new(results, Some(CommitId::new("branch:abc123")));
+        let response = ResponseWithHeaders::new(results, Some(CommitId::from("branch:abc123")));
 
         // Extract only Person refs
         let person_refs: Vec<VersionedEntityIDFor<Person>> =
@@ -410,7 +410,7 @@ mod tests {
     #[test]
     fn test_into_versioned_refs_empty_results() {
         let results = HashMap::new();
-        let response = ResponseWithHeaders::new(results, Some(CommitId::new("branch:abc123")));
+        let response = ResponseWithHeaders::new(results, Some(CommitId::from("branch:abc123")));
 
         let refs: Vec<VersionedEntityIDFor<Person>> = response.into_versioned_refs().unwrap();
         assert_eq!(refs.len(), 0);
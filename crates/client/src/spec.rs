@@ -1,5 +1,7 @@
 use crate::CommitId;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use terminusdb_types::{BranchName, BranchRef, DatabaseName, DatabasePath, Location, PathError};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BranchSpec {
@@ -94,4 +96,101 @@ impl BranchSpec {
     pub fn commit_id(&self) -> Option<&CommitId> {
         self.ref_commit.as_ref()
     }
+
+    /// Convert a spec parsed with the shared [`terminusdb_types::BranchSpec`]
+    /// grammar.
+    ///
+    /// The organization is not kept: requests always go to the client's
+    /// organization. A client spec always addresses the local repository, so
+    /// `remote` specs are rejected, as are relative refs such as `main~3`,
+    /// which have no server-side equivalent; resolve them to a commit first.
+    pub fn from_spec(spec: &terminusdb_types::BranchSpec) -> Result<Self, PathError> {
+        if spec.repository() != Location::Local {
+            return Err(PathError::InvalidBranchSpec(format!(
+                "'{}' is not in the local repository",
+                spec
+            )));
+        }
+        if spec.is_relative() {
+            return Err(PathError::InvalidBranchSpec(format!(
+                "relative ref '{}' must be resolved to a commit first",
+                spec
+            )));
+        }
+        let db = spec.database_path().database_name();
+        Ok(match spec.reference() {
+            BranchRef::Branch { name, .. } => Self::with_branch(db, name.as_str()),
+            BranchRef::Commit(id) => Self::with_commit(db, id.as_str()),
+        })
+    }
+
+    /// This spec in the shared [`terminusdb_types::BranchSpec`] grammar, in
+    /// organization `org`. Fails if the names or commit id are invalid.
+    pub fn to_spec(&self, org: &str) -> Result<terminusdb_types::BranchSpec, PathError> {
+        let database_path = DatabasePath::new(org, DatabaseName::new(self.db.as_str())?)?;
+        Ok(match (&self.ref_commit, &self.branch) {
+            (Some(id), _) => terminusdb_types::BranchSpec::commit(
+                database_path,
+                terminusdb_types::CommitId::new(id.as_str())?,
+            ),
+            (None, Some(branch)) => {
+                terminusdb_types::BranchSpec::new(database_path, BranchName::new(branch.as_str())?)
+            }
+            (None, None) => database_path.into(),
+        })
+    }
+}
+
+/// Parses `org/db`, `org/db/local/branch/<name>` or `org/db/local/commit/<id>`
+/// (see [`BranchSpec::from_spec`]).
+impl FromStr for BranchSpec {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_spec(&terminusdb_types::BranchSpec::parse(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_spec_keeps_local_refs() {
+        let spec: BranchSpec = "admin/mydb/local/branch/dev".parse().unwrap();
+        assert_eq!(spec.db, "mydb");
+        assert_eq!(spec.branch.as_deref(), Some("dev"));
+
+        let spec: BranchSpec = "admin/mydb/local/commit/abc123".parse().unwrap();
+        assert_eq!(spec.commit_id().map(|id| id.as_str()), Some("abc123"));
+
+        assert!("admin/mydb/remote/branch/main"
+            .parse::<BranchSpec>()
+            .is_err());
+        assert!("admin/mydb/local/branch/main~2"
+            .parse::<BranchSpec>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_to_spec_round_trip() {
+        for path in [
+            "admin/mydb/local/branch/main",
+            "admin/mydb/local/branch/release/1.0",
+            "admin/mydb/local/commit/abc123",
+        ] {
+            let spec: BranchSpec = path.parse().unwrap();
+            assert_eq!(spec.to_spec("admin").unwrap().to_string(), path);
+        }
+        assert_eq!(
+            BranchSpec::new("mydb")
+                .to_spec("admin")
+                .unwrap()
+                .to_string(),
+            "admin/mydb/local/branch/main"
+        );
+        assert!(BranchSpec::with_branch("mydb", "bad branch")
+            .to_spec("admin")
+            .is_err());
+    }
 }
@@ -16,7 +16,7 @@ use crate::CommitId;
 ///
 /// ```rust,ignore
 /// let id = EntityIDFor::<User>::random();
-/// let commit = CommitId::new("abc123");
+/// let commit = CommitId::from("abc123");
 /// let versioned = VersionedEntityIDFor::new(id, commit);
 ///
 /// // Access EntityIDFor methods via Deref
@@ -92,7 +92,7 @@ mod tests {
     #[test]
     fn test_new_and_deref() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned = VersionedEntityIDFor::new(id.clone(), commit.clone());
 
         // Test deref access
@@ -103,7 +103,7 @@ mod tests {
     #[test]
     fn test_partial_eq_entity_id() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned = VersionedEntityIDFor::new(id.clone(), commit);
 
         assert_eq!(versioned, id);
@@ -112,7 +112,7 @@ mod tests {
     #[test]
     fn test_partial_eq_string() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned = VersionedEntityIDFor::new(id.clone(), commit);
 
         assert_eq!(versioned, id.typed());
@@ -122,7 +122,7 @@ mod tests {
     #[test]
     fn test_partial_eq_tdb_iri() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned = VersionedEntityIDFor::new(id.clone(), commit);
 
         assert_eq!(versioned, *id.get_iri());
@@ -131,7 +131,7 @@ mod tests {
     #[test]
     fn test_serialize() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned = VersionedEntityIDFor::new(id, commit);
 
         let json = serde_json::to_value(&versioned).unwrap();
@@ -142,7 +142,7 @@ mod tests {
     #[test]
     fn test_clone_and_equality() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit = CommitId::new("commit456");
+        let commit = CommitId::from("commit456");
         let versioned1 = VersionedEntityIDFor::new(id.clone(), commit.clone());
         let versioned2 = versioned1.clone();
 
@@ -152,8 +152,8 @@ mod tests {
     #[test]
     fn test_different_versions_not_equal() {
         let id = EntityIDFor::<TestEntity>::new_untyped("TestEntity/test123").unwrap();
-        let commit1 = CommitId::new("commit456");
-        let commit2 = CommitId::new("commit789");
+        let commit1 = CommitId::from("commit456");
+        let commit2 = CommitId::from("commit789");
         let versioned1 = VersionedEntityIDFor::new(id.clone(), commit1);
         let versioned2 = VersionedEntityIDFor::new(id, commit2);
 
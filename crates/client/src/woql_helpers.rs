@@ -3,10 +3,10 @@
 //! These replace the (now-removed) `terminusdb-woql-builder` shortcuts with
 //! direct `terminusdb-woql2` AST construction.
 
-use terminusdb_schema::{GraphType, ToTDBSchema};
+use terminusdb_schema::{GraphType, ToTDBSchema, XSDAnySimpleType};
 use terminusdb_woql2::macros::IntoNodeValue;
 use terminusdb_woql2::prelude::{Query, Triple};
-use terminusdb_woql2::value::{NodeValue, Value};
+use terminusdb_woql2::value::{DataValue, NodeValue, Value};
 
 /// Build an `rdf:type` triple constraining `subject` to be an instance of `T`.
 ///
@@ -20,3 +20,36 @@ pub(crate) fn isa_model<T: ToTDBSchema>(subject: &Value) -> Query {
         graph: Some(GraphType::Instance),
     })
 }
+
+/// Convert a filter `DataValue` into the triple object used to match it.
+///
+/// The mapping is: URI -> Node, HexBinary -> String, UnsignedInt -> Integer,
+/// Float -> Decimal, everything else -> the same xsd variant.
+///
+/// # Panics
+/// Panics on `DataValue::List`, which has no single-object representation.
+pub fn data_value_to_object(value: &DataValue) -> Value {
+    match value {
+        DataValue::Variable(v) => Value::Variable(v.clone()),
+        DataValue::Data(d) => match d {
+            // URIs are represented as nodes
+            XSDAnySimpleType::URI(uri) => Value::Node(uri.clone()),
+            // Store hex binary as string
+            XSDAnySimpleType::HexBinary(hex) => Value::Data(XSDAnySimpleType::String(hex.clone())),
+            // Unsigned integers are emitted as (signed) integers
+            XSDAnySimpleType::UnsignedInt(u) => Value::Data(XSDAnySimpleType::Integer(*u as i64)),
+            // Floats are emitted as decimals via their string form
+            XSDAnySimpleType::Float(f) => Value::Data(XSDAnySimpleType::Decimal(
+                f.to_string()
+                    .parse()
+                    .expect("Invalid decimal string format"),
+            )),
+            // String/Boolean/Integer/Decimal/DateTime/Date/Time pass
+            // through unchanged.
+            other => Value::Data(other.clone()),
+        },
+        DataValue::List(_) => {
+            panic!("List values are not supported in filters")
+        }
+    }
+}
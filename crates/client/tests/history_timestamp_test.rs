@@ -10,7 +10,7 @@ mod tests {
         // Unix timestamp 1701423000 = 2023-12-01T09:30:00Z
         let entry = CommitHistoryEntry {
             author: "test_user".to_string(),
-            identifier: CommitId::new("abc123"),
+            identifier: CommitId::from("abc123"),
             message: "Test commit".to_string(),
             timestamp: 1701423000.0,
         };
@@ -33,7 +33,7 @@ mod tests {
         // 1701423000 seconds + 123 milliseconds
         let entry = CommitHistoryEntry {
             author: "test_user".to_string(),
-            identifier: CommitId::new("abc123"),
+            identifier: CommitId::from("abc123"),
             message: "Test commit".to_string(),
             timestamp: 1701423000.0 + 0.123,
         };
@@ -57,7 +57,7 @@ mod tests {
         // -86400.0 = 1969-12-31T00:00:00Z (one day before epoch)
         let entry = CommitHistoryEntry {
             author: "test_user".to_string(),
-            identifier: CommitId::new("abc123"),
+            identifier: CommitId::from("abc123"),
             message: "Test commit".to_string(),
             timestamp: -86400.0,
         };
@@ -0,0 +1,87 @@
+//! Branch protection is enforced before any request is sent, so these tests
+//! need no running server.
+
+use serde_json::json;
+use terminusdb_client::*;
+use url::Url;
+
+async fn protected_client() -> TerminusDBHttpClient {
+    TerminusDBHttpClient::new(
+        Url::parse("http://localhost:6363").unwrap(),
+        "admin",
+        "root",
+        "admin",
+    )
+    .await
+    .unwrap()
+    .with_branch_protection("main", BranchProtection::strict())
+}
+
+#[tokio::test]
+async fn test_reset_of_protected_branch_is_refused() {
+    let client = protected_client().await;
+
+    let err = client
+        .reset(
+            "admin/mydb/local/branch/main",
+            "admin/mydb/local/commit/abc123",
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<BranchProtectionError>(),
+        Some(&BranchProtectionError::ForceOperation {
+            branch: "main".to_string(),
+            operation: ForceOperation::Reset,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_commit_metadata_on_protected_branch() {
+    let client = protected_client().await;
+    let doc = json!({"@type": "Person", "name": "Alice"});
+
+    // The default author is the "system" placeholder
+    let args = DocumentInsertArgs::from(BranchSpec::with_branch("mydb", "main"));
+    let err = client.insert_document(&doc, args).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BranchProtectionError>(),
+        Some(BranchProtectionError::AnonymousAuthor { author, .. }) if author == "system"
+    ));
+
+    // Requests without a branch go to main
+    let args = DocumentInsertArgs::from(BranchSpec::new("mydb"))
+        .with_author("alice")
+        .with_message("");
+    let err = client.insert_document(&doc, args).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BranchProtectionError>(),
+        Some(BranchProtectionError::MissingMessage { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_nuke_of_protected_branch_is_refused() {
+    let client = protected_client().await;
+
+    let err = client
+        .delete_document(
+            None,
+            &BranchSpec::with_branch("mydb", "main"),
+            "alice",
+            "start over",
+            "instance",
+            DeleteOpts::nuke_all_data(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BranchProtectionError>(),
+        Some(BranchProtectionError::ForceOperation {
+            operation: ForceOperation::Nuke,
+            ..
+        })
+    ));
+}
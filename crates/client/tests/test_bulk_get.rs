@@ -60,6 +60,21 @@ mod tests {
         assert!(opts.minimized, "GetOpts should default to minimized=true");
     }
 
+    #[test]
+    fn test_get_opts_iri_compression() {
+        let defaults = GetOpts::default();
+        assert!(defaults.compress_ids);
+        assert!(defaults.prefixed);
+
+        let opts = GetOpts::default().with_compress_ids(false);
+        assert!(!opts.compress_ids);
+        assert!(opts.prefixed);
+
+        let full = GetOpts::default().with_full_iris();
+        assert!(!full.compress_ids);
+        assert!(!full.prefixed);
+    }
+
     #[test]
     fn test_get_opts_minimized_builder() {
         // Test the builder pattern for minimized
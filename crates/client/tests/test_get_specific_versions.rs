@@ -205,7 +205,7 @@ mod tests {
 
                 // Try to get versions with non-existent commit IDs
                 // TerminusDB returns an error when accessing non-existent commits
-                let fake_commits = vec![CommitId::new("fake_commit_id_123")];
+                let fake_commits = vec![CommitId::from("fake_commit_id_123")];
 
                 let mut deserializer = terminusdb_client::deserialize::DefaultTDBDeserializer;
                 let result = client
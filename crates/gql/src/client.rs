@@ -0,0 +1,547 @@
+//! Code generation for a typed client of a remote GraphQL API.
+//!
+//! [`generate_client`] takes the introspection envelope of a TerminusDB
+//! GraphQL endpoint (as returned by `TerminusDBHttpClient::introspect_schema`)
+//! and generates, for a consumer that has no access to the Rust models:
+//!
+//! - the enums and input types, as [`generate_filter_types`](crate::generate_filter_types) does
+//! - a struct per object type, with a `SELECTION` constant listing its
+//!   scalar and enum fields, the selection set queries use by default
+//! - per root field, an `{Field}Args` struct and a function building a
+//!   `TdbGqlQuery` typed with the field's result
+//!
+//! Link fields are `Option<Box<T>>` or `Option<Vec<T>>` and stay `None`
+//! unless selected with `with_selection`.
+//!
+//! The generated code expects `serde`, `serde_json`, `bon`, `anyhow` and
+//! `terminusdb-client` as dependencies of the crate including it.
+//!
+//! # Usage in build.rs
+//!
+//! ```ignore
+//! let introspection = serde_json::from_str(&fs::read_to_string("schema.json")?)?;
+//! let client = generate_client(&introspection)?;
+//! fs::write(format!("{}/tdb_client.rs", out_dir), client)?;
+//! ```
+//!
+//! and in the consumer:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/tdb_client.rs"));
+//!
+//! let tickets: Vec<Ticket> = ticket(TicketArgs::builder().limit(10).build())
+//!     .with_selection(format!("{} project {{ {} }}", Ticket::SELECTION, Project::SELECTION))
+//!     .execute(&client, &spec)
+//!     .await?;
+//! ```
+
+use graphql_parser::schema::{
+    parse_schema, Definition, Document, Field, InputValue, ObjectType, SchemaDefinition, Type,
+    TypeDefinition,
+};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::codegen::{
+    generate_from_document, graphql_scalar_to_rust, sanitize_field_name_with_conflicts,
+    sanitize_type_name, to_pascal_case,
+};
+
+/// Generate a typed client from a GraphQL introspection envelope.
+///
+/// See the [module documentation](self) for what is generated.
+pub fn generate_client(introspection: &Value) -> Result<String, String> {
+    let sdl = crate::render_introspection_to_sdl(introspection)
+        .map_err(|e| format!("Failed to read introspection: {}", e))?;
+    generate_client_from_sdl(&sdl)
+}
+
+/// Generate a typed client from SDL; see [`generate_client`].
+pub fn generate_client_from_sdl(sdl: &str) -> Result<String, String> {
+    let doc = parse_schema::<String>(sdl).map_err(|e| format!("Failed to parse SDL: {}", e))?;
+    let types = SchemaTypes::new(&doc);
+
+    let query_root = types
+        .objects
+        .iter()
+        .find(|object| object.name == types.query_root)
+        .ok_or_else(|| format!("SDL has no query root type '{}'", types.query_root))?;
+
+    let objects: Vec<TokenStream> = types
+        .objects
+        .iter()
+        .filter(|object| !types.is_root(&object.name))
+        .map(|object| generate_object(object, &types))
+        .collect();
+    let root_fields: Vec<TokenStream> = query_root
+        .fields
+        .iter()
+        .map(|field| generate_root_field(field, &types))
+        .collect();
+    let inputs = generate_from_document(&doc);
+
+    let tokens = quote! {
+        #inputs
+
+        /// A query against one root field, deserializing to `T`.
+        #[derive(Clone, Debug)]
+        pub struct TdbGqlQuery<T> {
+            field: &'static str,
+            declarations: &'static str,
+            arguments: &'static str,
+            selection: Option<String>,
+            variables: serde_json::Value,
+            _result: std::marker::PhantomData<T>,
+        }
+
+        impl<T: serde::de::DeserializeOwned> TdbGqlQuery<T> {
+            /// Replace the selection set of the root field, e.g. to select links
+            pub fn with_selection(mut self, selection: impl Into<String>) -> Self {
+                self.selection = Some(selection.into());
+                self
+            }
+
+            /// The GraphQL query text
+            pub fn query(&self) -> String {
+                let selection = match &self.selection {
+                    Some(selection) => format!(" {{ {} }}", selection),
+                    None => String::new(),
+                };
+                format!(
+                    "query {}{} {{ {}{}{} }}",
+                    self.field, self.declarations, self.field, self.arguments, selection
+                )
+            }
+
+            /// The variables holding the arguments
+            pub fn variables(&self) -> &serde_json::Value {
+                &self.variables
+            }
+
+            pub fn request(&self) -> terminusdb_client::GraphQLRequest {
+                terminusdb_client::GraphQLRequest::with_variables(
+                    self.query(),
+                    self.variables.clone(),
+                )
+            }
+
+            /// Read the result from the `data` of a response
+            pub fn parse(&self, data: serde_json::Value) -> Result<T, serde_json::Error> {
+                serde_json::from_value(data.get(self.field).cloned().unwrap_or_default())
+            }
+
+            pub async fn execute(
+                &self,
+                client: &terminusdb_client::TerminusDBHttpClient,
+                spec: &terminusdb_client::BranchSpec,
+            ) -> anyhow::Result<T> {
+                let response = client
+                    .execute_graphql_at::<serde_json::Value>(spec, self.request(), None)
+                    .await?;
+                if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+                    anyhow::bail!("GraphQL errors: {:?}", errors);
+                }
+                let data = response
+                    .data
+                    .ok_or_else(|| anyhow::anyhow!("no data in GraphQL response"))?;
+                Ok(self.parse(data)?)
+            }
+        }
+
+        #(#objects)*
+
+        #(#root_fields)*
+    };
+
+    Ok(tokens.to_string())
+}
+
+/// The named types of a schema, by kind.
+struct SchemaTypes<'a> {
+    objects: Vec<&'a ObjectType<'a, String>>,
+    object_names: HashSet<String>,
+    inputs: HashSet<String>,
+    enums: HashSet<String>,
+    query_root: String,
+    roots: HashSet<String>,
+}
+
+impl<'a> SchemaTypes<'a> {
+    fn new(doc: &'a Document<'a, String>) -> Self {
+        let mut types = SchemaTypes {
+            objects: Vec::new(),
+            object_names: HashSet::new(),
+            inputs: HashSet::new(),
+            enums: HashSet::new(),
+            query_root: "Query".to_string(),
+            roots: ["Query", "Mutation", "Subscription"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        };
+        for def in &doc.definitions {
+            match def {
+                Definition::TypeDefinition(TypeDefinition::Object(object))
+                    if !object.name.starts_with("__") =>
+                {
+                    types.objects.push(object);
+                    types.object_names.insert(object.name.clone());
+                }
+                Definition::TypeDefinition(TypeDefinition::InputObject(input)) => {
+                    types.inputs.insert(input.name.clone());
+                }
+                Definition::TypeDefinition(TypeDefinition::Enum(e)) => {
+                    types.enums.insert(e.name.clone());
+                }
+                Definition::SchemaDefinition(SchemaDefinition {
+                    query,
+                    mutation,
+                    subscription,
+                    ..
+                }) => {
+                    types.roots = [query, mutation, subscription]
+                        .into_iter()
+                        .flatten()
+                        .cloned()
+                        .collect();
+                    if let Some(query) = query {
+                        types.query_root = query.clone();
+                    }
+                }
+                _ => {}
+            }
+        }
+        types
+    }
+
+    fn is_root(&self, name: &str) -> bool {
+        self.roots.contains(name)
+    }
+
+    fn is_object(&self, ty: &Type<String>) -> bool {
+        self.object_names.contains(named_type(ty))
+    }
+
+    /// Rust type of a named type; objects map to their generated struct.
+    fn rust_name(&self, name: &str) -> TokenStream {
+        if self.object_names.contains(name) {
+            let ident = Ident::new(&sanitize_type_name(name), Span::call_site());
+            quote! { #ident }
+        } else {
+            graphql_scalar_to_rust(name, &self.inputs, &self.enums, "")
+        }
+    }
+
+    /// Rust type of an output or argument type, following its nullability.
+    fn rust_type(&self, ty: &Type<String>) -> TokenStream {
+        match ty {
+            Type::NonNullType(inner) => self.rust_type_non_null(inner),
+            inner => {
+                let rust = self.rust_type_non_null(inner);
+                quote! { Option<#rust> }
+            }
+        }
+    }
+
+    fn rust_type_non_null(&self, ty: &Type<String>) -> TokenStream {
+        match ty {
+            Type::NamedType(name) => self.rust_name(name),
+            Type::ListType(inner) => {
+                let inner = self.rust_type(inner);
+                quote! { Vec<#inner> }
+            }
+            Type::NonNullType(inner) => self.rust_type_non_null(inner),
+        }
+    }
+}
+
+/// Struct for an object type, with its default selection.
+fn generate_object(object: &ObjectType<String>, types: &SchemaTypes) -> TokenStream {
+    let struct_ident = Ident::new(&sanitize_type_name(&object.name), Span::call_site());
+    let names: HashSet<String> = object
+        .fields
+        .iter()
+        .filter(|f| !f.name.starts_with('_'))
+        .map(|f| f.name.clone())
+        .collect();
+
+    let fields: Vec<TokenStream> = object
+        .fields
+        .iter()
+        .map(|field| {
+            let name = &field.name;
+            let ident = field_ident(&sanitize_field_name_with_conflicts(name, &names));
+            let rename = quote! { rename = #name };
+
+            if types.is_object(&field.field_type) {
+                let target = types.rust_name(named_type(&field.field_type));
+                let ty = if is_list(&field.field_type) {
+                    quote! { Option<Vec<#target>> }
+                } else {
+                    quote! { Option<Box<#target>> }
+                };
+                quote! {
+                    #[serde(#rename, default, skip_serializing_if = "Option::is_none")]
+                    pub #ident: #ty,
+                }
+            } else {
+                let ty = types.rust_type(&field.field_type);
+                quote! {
+                    #[serde(#rename, default)]
+                    pub #ident: #ty,
+                }
+            }
+        })
+        .collect();
+
+    let selection = object
+        .fields
+        .iter()
+        .filter(|field| !types.is_object(&field.field_type))
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    quote! {
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        pub struct #struct_ident {
+            #(#fields)*
+        }
+
+        impl #struct_ident {
+            /// The scalar and enum fields, selected by default
+            pub const SELECTION: &'static str = #selection;
+        }
+    }
+}
+
+/// Arguments struct and query builder function for a root field.
+fn generate_root_field(field: &Field<String>, types: &SchemaTypes) -> TokenStream {
+    let field_name = &field.name;
+    let fn_ident = field_ident(&to_snake_case(field_name));
+    let args_ident = Ident::new(
+        &format!("{}Args", to_pascal_case(field_name.trim_start_matches('_'))),
+        Span::call_site(),
+    );
+    let result = types.rust_type(&field.field_type);
+
+    let names: HashSet<String> = field.arguments.iter().map(|a| a.name.clone()).collect();
+    let args: Vec<TokenStream> = field
+        .arguments
+        .iter()
+        .map(|arg| generate_argument(arg, &names, types))
+        .collect();
+
+    let declarations = if field.arguments.is_empty() {
+        String::new()
+    } else {
+        let declarations: Vec<String> = field
+            .arguments
+            .iter()
+            .map(|arg| format!("${}: {}", arg.name, arg.value_type))
+            .collect();
+        format!("({})", declarations.join(", "))
+    };
+    let arguments = if field.arguments.is_empty() {
+        String::new()
+    } else {
+        let arguments: Vec<String> = field
+            .arguments
+            .iter()
+            .map(|arg| format!("{}: ${}", arg.name, arg.name))
+            .collect();
+        format!("({})", arguments.join(", "))
+    };
+    let selection = if types.is_object(&field.field_type) {
+        let object = types.rust_name(named_type(&field.field_type));
+        quote! { Some(#object::SELECTION.to_string()) }
+    } else {
+        quote! { None }
+    };
+    let doc = format!("Query the `{}` root field.", field_name);
+
+    quote! {
+        #[derive(Default, Clone, Debug, Serialize, bon::Builder)]
+        #[builder(on(_, into), finish_fn = build)]
+        pub struct #args_ident {
+            #(#args)*
+        }
+
+        #[doc = #doc]
+        pub fn #fn_ident(args: #args_ident) -> TdbGqlQuery<#result> {
+            TdbGqlQuery {
+                field: #field_name,
+                declarations: #declarations,
+                arguments: #arguments,
+                selection: #selection,
+                variables: serde_json::to_value(args).unwrap_or_default(),
+                _result: std::marker::PhantomData,
+            }
+        }
+    }
+}
+
+fn generate_argument(
+    arg: &InputValue<String>,
+    names: &HashSet<String>,
+    types: &SchemaTypes,
+) -> TokenStream {
+    let name = &arg.name;
+    let ident = field_ident(&to_snake_case(&sanitize_field_name_with_conflicts(
+        name, names,
+    )));
+    let ty = types.rust_type(&arg.value_type);
+    if matches!(arg.value_type, Type::NonNullType(_)) {
+        quote! {
+            #[serde(rename = #name)]
+            pub #ident: #ty,
+        }
+    } else {
+        quote! {
+            #[serde(rename = #name, skip_serializing_if = "Option::is_none")]
+            pub #ident: #ty,
+        }
+    }
+}
+
+fn named_type<'a>(ty: &'a Type<'_, String>) -> &'a str {
+    match ty {
+        Type::NamedType(name) => name,
+        Type::NonNullType(inner) | Type::ListType(inner) => named_type(inner),
+    }
+}
+
+fn is_list(ty: &Type<String>) -> bool {
+    match ty {
+        Type::ListType(_) => true,
+        Type::NonNullType(inner) => is_list(inner),
+        Type::NamedType(_) => false,
+    }
+}
+
+/// Identifier for a field, raw when it is a keyword (`_type` becomes `r#type`).
+fn field_ident(name: &str) -> Ident {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe",
+        "use", "where", "while",
+    ];
+    if KEYWORDS.contains(&name) {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    }
+}
+
+/// `orderBy` becomes `order_by`, `_getDocument` becomes `get_document`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for c in name.trim_start_matches('_').chars() {
+        if c.is_uppercase() {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = r#"
+        scalar JSON
+
+        enum Status {
+            Open
+            Closed
+        }
+
+        input StringFilter {
+            eq: String
+        }
+
+        input Ticket_Filter {
+            title: StringFilter
+        }
+
+        type Project {
+            _id: ID!
+            _type: ID!
+            name: String!
+            budget: Int
+            tickets: [Ticket!]!
+        }
+
+        type Ticket {
+            _id: ID!
+            title: String!
+            status: Status
+            labels: [String!]!
+            project: Project!
+        }
+
+        type Query {
+            _getDocument(id: String!): JSON
+            Ticket(id: ID, limit: Int, filter: Ticket_Filter): [Ticket!]!
+        }
+    "#;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("Ticket"), "ticket");
+        assert_eq!(to_snake_case("orderBy"), "order_by");
+        assert_eq!(to_snake_case("_getDocument"), "get_document");
+        assert_eq!(to_snake_case("include_children"), "include_children");
+    }
+
+    #[test]
+    fn test_generate_client_objects() {
+        let code = generate_client_from_sdl(SDL).unwrap();
+
+        assert!(code.contains("pub struct Project"));
+        assert!(code.contains("# [serde (rename = \"_id\" , default)] pub id : String"));
+        assert!(code.contains("pub r#type : String"));
+        assert!(code.contains("pub budget : Option < i32 >"));
+        assert!(code.contains("pub status : Option < Status >"));
+        assert!(code.contains("pub labels : Vec < String >"));
+        assert!(code.contains("pub project : Option < Box < Project >>"));
+        assert!(code.contains("pub tickets : Option < Vec < Ticket >>"));
+        assert!(code.contains("pub const SELECTION : & 'static str = \"_id title status labels\""));
+        assert!(!code.contains("pub struct Query"));
+
+        // Inputs and enums come from the filter codegen
+        assert!(code.contains("pub struct TicketFilter"));
+        assert!(code.contains("pub enum Status"));
+    }
+
+    #[test]
+    fn test_generate_client_root_fields() {
+        let code = generate_client_from_sdl(SDL).unwrap();
+
+        assert!(
+            code.contains("pub fn ticket (args : TicketArgs) -> TdbGqlQuery < Vec < Ticket > >")
+        );
+        assert!(code.contains("declarations : \"($id: ID, $limit: Int, $filter: Ticket_Filter)\""));
+        assert!(code.contains("arguments : \"(id: $id, limit: $limit, filter: $filter)\""));
+        assert!(code.contains("pub filter : Option < TicketFilter >"));
+
+        assert!(code.contains(
+            "pub fn get_document (args : GetDocumentArgs) -> TdbGqlQuery < Option < serde_json :: Value > >"
+        ));
+        assert!(code.contains("# [serde (rename = \"id\")] pub id : String"));
+    }
+
+    #[test]
+    fn test_generate_client_without_query_root() {
+        let err = generate_client_from_sdl("type Project { name: String }").unwrap_err();
+        assert!(err.contains("no query root"));
+    }
+}
@@ -1,6 +1,8 @@
 //! Code generation for GraphQL filter types.
 //!
-//! This module parses GraphQL SDL and generates Rust structs for filter types.
+//! This module parses GraphQL SDL and generates Rust structs for filter types,
+//! including the nested filters on document links (`project: ProjectFilter`)
+//! and the `someHave`/`allHave` filters on collections of links.
 //!
 //! # Usage in build.rs
 //!
@@ -33,7 +35,7 @@ use graphql_parser::schema::{
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Generate Rust code for filter types from GraphQL SDL.
 ///
@@ -60,7 +62,7 @@ pub fn generate_filter_types(sdl: &str) -> Result<String, String> {
 }
 
 /// Generate TokenStream from a parsed GraphQL document.
-fn generate_from_document(doc: &Document<String>) -> TokenStream {
+pub(crate) fn generate_from_document(doc: &Document<String>) -> TokenStream {
     let mut generated = Vec::new();
 
     // Collect all type names for reference resolution
@@ -95,11 +97,42 @@ fn generate_from_document(doc: &Document<String>) -> TokenStream {
         }
     }
 
+    // Relation filters reference each other (`Ticket_Filter.project` and
+    // `Project_Filter.tickets`), so find the references that close a cycle
+    let inputs: Vec<&InputObjectType<String>> = doc
+        .definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(GqlTypeDefinition::InputObject(input)) => Some(input),
+            _ => None,
+        })
+        .collect();
+    let direct_refs: HashMap<&str, HashSet<&str>> = inputs
+        .iter()
+        .map(|input| {
+            let refs = input
+                .fields
+                .iter()
+                .filter_map(|field| direct_type_name(&field.value_type))
+                .filter(|name| input_type_names.contains(*name))
+                .collect();
+            (input.name.as_str(), refs)
+        })
+        .collect();
+
     // Generate structs for input types (filters and ordering)
-    for def in &doc.definitions {
-        if let Definition::TypeDefinition(GqlTypeDefinition::InputObject(input)) = def {
-            generated.push(generate_input_object(input, &input_type_names, &enum_names));
-        }
+    for input in inputs {
+        let recursive: HashSet<String> = direct_refs[input.name.as_str()]
+            .iter()
+            .filter(|target| reaches(&direct_refs, target, &input.name))
+            .map(|target| target.to_string())
+            .collect();
+        generated.push(generate_input_object(
+            input,
+            &input_type_names,
+            &enum_names,
+            &recursive,
+        ));
     }
 
     quote! {
@@ -110,6 +143,30 @@ fn generate_from_document(doc: &Document<String>) -> TokenStream {
     }
 }
 
+/// The named type of a field held inline, i.e. not through a list.
+fn direct_type_name<'a>(ty: &'a Type<'_, String>) -> Option<&'a str> {
+    match ty {
+        Type::NamedType(name) => Some(name),
+        Type::NonNullType(inner) => direct_type_name(inner),
+        Type::ListType(_) => None,
+    }
+}
+
+/// Whether input type `to` is held inline, directly or transitively, by `from`.
+fn reaches(refs: &HashMap<&str, HashSet<&str>>, from: &str, to: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(name) = stack.pop() {
+        if name == to {
+            return true;
+        }
+        if seen.insert(name) {
+            stack.extend(refs.get(name).into_iter().flatten().copied());
+        }
+    }
+    false
+}
+
 /// Generate a Rust enum for a GraphQL enum type.
 fn generate_enum(e: &EnumType<String>) -> TokenStream {
     let enum_name = sanitize_type_name(&e.name);
@@ -183,7 +240,7 @@ fn sanitize_enum_variant(name: &str) -> String {
 }
 
 /// Convert a string to PascalCase.
-fn to_pascal_case(s: &str) -> String {
+pub(crate) fn to_pascal_case(s: &str) -> String {
     // Handle already PascalCase names
     if s.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) && !s.contains('_') {
         return s.to_string();
@@ -202,10 +259,14 @@ fn to_pascal_case(s: &str) -> String {
 }
 
 /// Generate a Rust struct for a GraphQL input object type.
+///
+/// Fields whose input type is in `recursive` hold it in a `Box`, since the
+/// struct would otherwise contain itself.
 fn generate_input_object(
     input: &InputObjectType<String>,
     known_inputs: &HashSet<String>,
     known_enums: &HashSet<String>,
+    recursive: &HashSet<String>,
 ) -> TokenStream {
     let struct_name = sanitize_type_name(&input.name);
     let struct_ident = Ident::new(&struct_name, Span::call_site());
@@ -226,8 +287,17 @@ fn generate_input_object(
             let rust_field_name = sanitize_field_name_with_conflicts(field_name, &sanitized_names);
             let field_ident = Ident::new(&rust_field_name, Span::call_site());
 
-            let field_type =
-                graphql_type_to_rust(&field.value_type, known_inputs, known_enums, &struct_name);
+            let field_type = match direct_type_name(&field.value_type) {
+                Some(name)
+                    if recursive.contains(name) && sanitize_type_name(name) != struct_name =>
+                {
+                    let type_ident = Ident::new(&sanitize_type_name(name), Span::call_site());
+                    quote! { Option<Box<#type_ident>> }
+                }
+                _ => {
+                    graphql_type_to_rust(&field.value_type, known_inputs, known_enums, &struct_name)
+                }
+            };
 
             // Add serde rename if the field name was sanitized or starts with underscore
             let serde_attr = if rust_field_name != *field_name {
@@ -243,15 +313,55 @@ fn generate_input_object(
         })
         .collect();
 
+    let collection_impl = generate_collection_impl(input, &struct_ident, recursive);
+
     quote! {
         #[derive(Default, Clone, Debug, Serialize, Deserialize, bon::Builder)]
         #[builder(on(_, into), finish_fn = build)]
         pub struct #struct_ident {
             #(#fields)*
         }
+
+        #collection_impl
     }
 }
 
+/// Constructors for a collection filter (`someHave`/`allHave`), which match
+/// when some or all of the collection's elements match the inner filter.
+fn generate_collection_impl(
+    input: &InputObjectType<String>,
+    struct_ident: &Ident,
+    recursive: &HashSet<String>,
+) -> Option<TokenStream> {
+    let mut names: Vec<&str> = input.fields.iter().map(|f| f.name.as_str()).collect();
+    names.sort_unstable();
+    if names != ["allHave", "someHave"] {
+        return None;
+    }
+
+    let inner = direct_type_name(&input.fields[0].value_type)?;
+    let inner_ident = Ident::new(&sanitize_type_name(inner), Span::call_site());
+    let wrap = if recursive.contains(inner) {
+        quote! { Some(Box::new(filter)) }
+    } else {
+        quote! { Some(filter) }
+    };
+
+    Some(quote! {
+        impl #struct_ident {
+            /// Matches when at least one element matches `filter`.
+            pub fn some_have(filter: #inner_ident) -> Self {
+                Self { someHave: #wrap, ..Default::default() }
+            }
+
+            /// Matches when every element matches `filter`.
+            pub fn all_have(filter: #inner_ident) -> Self {
+                Self { allHave: #wrap, ..Default::default() }
+            }
+        }
+    })
+}
+
 /// Convert a GraphQL type to a Rust type.
 fn graphql_type_to_rust(
     ty: &Type<String>,
@@ -301,7 +411,7 @@ fn graphql_type_to_rust_unwrapped(
 }
 
 /// Convert a GraphQL scalar/type name to Rust type.
-fn graphql_scalar_to_rust(
+pub(crate) fn graphql_scalar_to_rust(
     name: &str,
     known_inputs: &HashSet<String>,
     known_enums: &HashSet<String>,
@@ -337,7 +447,7 @@ fn graphql_scalar_to_rust(
 
 /// Sanitize a GraphQL type name to be a valid Rust identifier.
 /// Converts underscores to CamelCase style.
-fn sanitize_type_name(name: &str) -> String {
+pub(crate) fn sanitize_type_name(name: &str) -> String {
     // Convert names like "Project_Filter" to "ProjectFilter"
     name.split('_')
         .map(|part| {
@@ -363,7 +473,10 @@ fn sanitize_field_name(name: &str) -> String {
 
 /// Sanitize a field name while avoiding conflicts with existing field names.
 /// If removing the underscore would cause a conflict, prefix with "tdb_".
-fn sanitize_field_name_with_conflicts(name: &str, existing_names: &HashSet<String>) -> String {
+pub(crate) fn sanitize_field_name_with_conflicts(
+    name: &str,
+    existing_names: &HashSet<String>,
+) -> String {
     if name.starts_with('_') {
         let stripped = name.trim_start_matches('_');
         // If the stripped name conflicts with an existing field, use "tdb_" prefix
@@ -599,6 +712,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_mutually_recursive_relation_filters() {
+        let sdl = r#"
+            input StringFilter {
+                eq: String
+            }
+
+            input Project_Filter {
+                name: StringFilter
+                tickets: Ticket_Collection_Filter
+            }
+
+            input Ticket_Filter {
+                title: StringFilter
+                project: Project_Filter
+            }
+
+            input Ticket_Collection_Filter {
+                someHave: Ticket_Filter
+                allHave: Ticket_Filter
+            }
+        "#;
+
+        let code = generate_filter_types(sdl).unwrap();
+
+        // References closing the Project -> Ticket -> Project cycle are boxed
+        assert!(code.contains("pub tickets : Option < Box < TicketCollectionFilter >>"));
+        assert!(code.contains("pub project : Option < Box < ProjectFilter >>"));
+        // Base filters are not part of a cycle
+        assert!(code.contains("pub name : Option < StringFilter >"));
+
+        // Collection filters get someHave/allHave constructors
+        assert!(code.contains("pub fn some_have (filter : TicketFilter) -> Self"));
+        assert!(code.contains("someHave : Some (Box :: new (filter))"));
+        assert!(code.contains("pub fn all_have (filter : TicketFilter) -> Self"));
+    }
+
     #[test]
     fn test_generate_filter_impls() {
         let impls = generate_filter_impls(&[
@@ -0,0 +1,648 @@
+//! Apollo Federation support for the generated schema.
+//!
+//! [`generate_federated_gql_schema`] emits the same SDL as
+//! [`generate_gql_schema`](crate::generate_gql_schema), plus the
+//! `@link` schema extension and an `@key` directive on every entity type, so
+//! the schema can be composed into a federated supergraph.
+//!
+//! Keys follow the TerminusDB key strategy of each model. Every document can
+//! be resolved by its `_id`; models with a `Lexical` or `Hash` key can also be
+//! resolved by their key fields, since those determine the id:
+//!
+//! ```graphql
+//! type Project @key(fields: "_id") @key(fields: "name") {
+//!   _id: ID!
+//!   name: String!
+//! }
+//! ```
+//!
+//! Subdocuments and abstract classes are not entities and get no `@key`.
+//! Subdocuments are value types, which other subgraphs may define as well, so
+//! they are marked `@shareable`; [`EntityKeys::with_shareable`] marks more.
+//!
+//! TerminusDB does not serve the `_entities` field itself, so a subgraph in
+//! front of it resolves entity representations with [`EntityKeys::lookup`],
+//! which turns a representation into a TerminusDB GraphQL query.
+//! [`generate_subgraph_gql_schema`] emits the schema such a subgraph serves,
+//! with the `_entities` and `_service` fields and their types, and
+//! [`generate_resolvers`](crate::generate_resolvers) generates `_id`
+//! reference resolvers for the entities of a federated SDL.
+//!
+//! ```ignore
+//! use terminusdb_gql::{generate_federated_gql_schema, EntityKeys};
+//!
+//! let sdl = generate_federated_gql_schema::<(Project, Ticket)>();
+//!
+//! let keys = EntityKeys::for_models::<(Project, Ticket)>();
+//! let lookup = keys.lookup(&json!({"__typename": "Project", "name": "apollo"}))?;
+//! let query = lookup.to_query("_id name");
+//! // query { Project(filter: {name: {eq: "apollo"}}, limit: 1) { _id name } }
+//! ```
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use terminusdb_community::graphql::frame::AllFrames;
+use terminusdb_schema::{Key, Property, Schema, ToTDBSchemas};
+
+use crate::frames::schemas_vec_to_allframes;
+use crate::schema::{render_sdl, validate_sdl_or_panic};
+
+/// The Federation specification the generated schema links to
+pub const FEDERATION_SPEC_URL: &str = "https://specs.apollo.dev/federation/v2.3";
+
+/// Definitions of the federation directives, which a federation-aware
+/// composer provides itself. Only used to validate the generated SDL.
+const FEDERATION_DEFINITIONS: &str = r#"scalar federation__FieldSet
+scalar link__Import
+directive @link(url: String!, import: [link__Import]) repeatable on SCHEMA
+directive @key(fields: federation__FieldSet!, resolvable: Boolean = true) repeatable on OBJECT | INTERFACE
+directive @shareable repeatable on OBJECT | FIELD_DEFINITION
+
+"#;
+
+/// Generate a GraphQL schema (SDL) for use as an Apollo Federation subgraph.
+///
+/// The schema is that of [`generate_gql_schema`](crate::generate_gql_schema),
+/// with `@key` directives derived from the key strategy of each model; see the
+/// [module documentation](self).
+pub fn generate_federated_gql_schema<T: ToTDBSchemas>() -> String {
+    let schemas = T::to_schemas();
+    let keys = EntityKeys::from_schemas(&schemas);
+    let body = render_sdl(&schemas_vec_to_allframes(&schemas), Some(&keys), false);
+    validate_sdl_or_panic(&format!("{}{}", FEDERATION_DEFINITIONS, body));
+    format!("{}{}", link_extension(), body)
+}
+
+/// Same as `generate_federated_gql_schema` but skips the apollo-compiler
+/// validation pass; see `generate_gql_schema_unchecked`.
+pub fn generate_federated_gql_schema_unchecked<T: ToTDBSchemas>() -> String {
+    let schemas = T::to_schemas();
+    allframes_to_federated_sdl(
+        &schemas_vec_to_allframes(&schemas),
+        &EntityKeys::from_schemas(&schemas),
+    )
+}
+
+/// Generate federated SDL from AllFrames and the keys of its entity types.
+pub fn allframes_to_federated_sdl(frames: &AllFrames, keys: &EntityKeys) -> String {
+    format!(
+        "{}{}",
+        link_extension(),
+        render_sdl(frames, Some(keys), false)
+    )
+}
+
+/// Generate the schema (SDL) served by a subgraph in front of TerminusDB:
+/// the federated schema plus the `_entities` and `_service` query fields
+/// with the `_Any`, `_Entity` and `_Service` types they use.
+pub fn generate_subgraph_gql_schema<T: ToTDBSchemas>() -> String {
+    let schemas = T::to_schemas();
+    let keys = EntityKeys::from_schemas(&schemas);
+    let body = format!(
+        "{}{}",
+        render_sdl(&schemas_vec_to_allframes(&schemas), Some(&keys), false),
+        entities_sdl(&keys)
+    );
+    validate_sdl_or_panic(&format!("{}{}", FEDERATION_DEFINITIONS, body));
+    format!("{}{}", link_extension(), body)
+}
+
+/// Same as `generate_subgraph_gql_schema` but skips the apollo-compiler
+/// validation pass; see `generate_gql_schema_unchecked`.
+pub fn generate_subgraph_gql_schema_unchecked<T: ToTDBSchemas>() -> String {
+    let schemas = T::to_schemas();
+    allframes_to_subgraph_sdl(
+        &schemas_vec_to_allframes(&schemas),
+        &EntityKeys::from_schemas(&schemas),
+    )
+}
+
+/// Generate subgraph SDL from AllFrames and the keys of its entity types.
+pub fn allframes_to_subgraph_sdl(frames: &AllFrames, keys: &EntityKeys) -> String {
+    format!(
+        "{}{}",
+        allframes_to_federated_sdl(frames, keys),
+        entities_sdl(keys)
+    )
+}
+
+/// `sdl` without the leading `@link` schema extension of a federated schema,
+/// which SDL parsers without federation support reject.
+pub(crate) fn strip_link_extension(sdl: &str) -> &str {
+    sdl.strip_prefix(link_extension().as_str()).unwrap_or(sdl)
+}
+
+fn link_extension() -> String {
+    format!(
+        "extend schema\n  @link(url: \"{}\", import: [\"@key\", \"@shareable\"])\n\n",
+        FEDERATION_SPEC_URL
+    )
+}
+
+/// The types and query fields of the federation subgraph protocol. With no
+/// entities there is no `_Entity` union and no `_entities` field.
+fn entities_sdl(keys: &EntityKeys) -> String {
+    let mut output = String::from("# Federation\n");
+    output.push_str("scalar _Any\n\n");
+    output.push_str("type _Service {\n  sdl: String\n}\n\n");
+
+    let entities: Vec<&str> = keys.iter().map(|(name, _)| name).collect();
+    if !entities.is_empty() {
+        output.push_str(&format!("union _Entity = {}\n\n", entities.join(" | ")));
+    }
+
+    output.push_str("extend type Query {\n");
+    if !entities.is_empty() {
+        output.push_str("  _entities(representations: [_Any!]!): [_Entity]!\n");
+    }
+    output.push_str("  _service: _Service!\n");
+    output.push_str("}\n\n");
+    output
+}
+
+/// A field of an entity key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyField {
+    /// the document id, `_id`
+    Id,
+    /// a field with a scalar value
+    Scalar(String),
+    /// a field with an enum value
+    Enum(String),
+    /// a field referencing another document, identified by its `_id`
+    Reference(String),
+}
+
+impl KeyField {
+    /// Field name as it appears in the schema and in entity representations
+    pub fn name(&self) -> &str {
+        match self {
+            KeyField::Id => "_id",
+            KeyField::Scalar(name) | KeyField::Enum(name) | KeyField::Reference(name) => name,
+        }
+    }
+
+    fn selection(&self) -> String {
+        match self {
+            KeyField::Reference(name) => format!("{} {{ _id }}", name),
+            field => field.name().to_string(),
+        }
+    }
+}
+
+/// A set of fields that identifies an entity, rendered as one `@key`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityKey {
+    pub fields: Vec<KeyField>,
+}
+
+impl EntityKey {
+    /// The key on the document id
+    pub fn id() -> Self {
+        Self {
+            fields: vec![KeyField::Id],
+        }
+    }
+
+    /// The field set of the `@key(fields: ...)` directive, e.g. `"name"` or
+    /// `"project { _id } number"`
+    pub fn field_set(&self) -> String {
+        self.fields
+            .iter()
+            .map(KeyField::selection)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Why an entity representation could not be turned into a lookup
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FederationError {
+    #[error("entity representation has no __typename")]
+    MissingTypename,
+
+    #[error("'{0}' is not an entity type")]
+    UnknownEntity(String),
+
+    #[error("representation of '{0}' has the fields of none of its keys")]
+    NoMatchingKey(String),
+
+    #[error("key field '{field}' of '{type_name}' has an invalid value: {value}")]
+    InvalidKeyValue {
+        type_name: String,
+        field: String,
+        value: Value,
+    },
+}
+
+/// The entity types of a schema with their keys, in order of preference,
+/// and the value types marked `@shareable`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntityKeys {
+    entities: BTreeMap<String, Vec<EntityKey>>,
+    shareable: BTreeSet<String>,
+}
+
+impl EntityKeys {
+    /// Keys of the entity types among the given models
+    pub fn for_models<T: ToTDBSchemas>() -> Self {
+        Self::from_schemas(&T::to_schemas())
+    }
+
+    /// Keys of the entity types among the given schemas. Only documents are
+    /// entities: subdocuments, abstract classes and enums are skipped.
+    /// Subdocuments are shareable.
+    pub fn from_schemas(schemas: &[Schema]) -> Self {
+        let entities = schemas
+            .iter()
+            .filter(|schema| !schema.is_enum() && !schema.is_subdocument() && !schema.is_abstract())
+            .map(|schema| {
+                let mut keys = vec![EntityKey::id()];
+                if let Some(Key::Lexical(fields) | Key::Hash(fields)) = schema.key() {
+                    if let Some(key) = field_key(schemas, schema, &fields) {
+                        keys.push(key);
+                    }
+                }
+                (schema.class_name().clone(), keys)
+            })
+            .collect();
+        let shareable = schemas
+            .iter()
+            .filter(|schema| schema.is_subdocument() && !schema.is_abstract())
+            .map(|schema| schema.class_name().clone())
+            .collect();
+        Self {
+            entities,
+            shareable,
+        }
+    }
+
+    /// Also mark `type_name` `@shareable`, for a type other subgraphs resolve
+    /// as well
+    pub fn with_shareable(mut self, type_name: impl Into<String>) -> Self {
+        self.shareable.insert(type_name.into());
+        self
+    }
+
+    pub fn is_shareable(&self, type_name: &str) -> bool {
+        self.shareable.contains(type_name)
+    }
+
+    /// The keys of an entity type, `None` if it is not an entity
+    pub fn get(&self, type_name: &str) -> Option<&[EntityKey]> {
+        self.entities.get(type_name).map(Vec::as_slice)
+    }
+
+    pub fn is_entity(&self, type_name: &str) -> bool {
+        self.entities.contains_key(type_name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[EntityKey])> {
+        self.entities
+            .iter()
+            .map(|(name, keys)| (name.as_str(), keys.as_slice()))
+    }
+
+    /// The `@key` and `@shareable` directives of a type, each preceded by a
+    /// space
+    pub(crate) fn directives(&self, type_name: &str) -> String {
+        let mut directives: String = self
+            .get(type_name)
+            .unwrap_or_default()
+            .iter()
+            .map(|key| format!(" @key(fields: \"{}\")", key.field_set()))
+            .collect();
+        if self.is_shareable(type_name) {
+            directives.push_str(" @shareable");
+        }
+        directives
+    }
+
+    /// Resolve an entity representation, as passed to a subgraph's
+    /// `_entities` field, to a lookup of the document in TerminusDB. The first
+    /// key whose fields are all present in the representation is used.
+    pub fn lookup(&self, representation: &Value) -> Result<EntityLookup, FederationError> {
+        let type_name = representation
+            .get("__typename")
+            .and_then(Value::as_str)
+            .ok_or(FederationError::MissingTypename)?;
+        let keys = self
+            .get(type_name)
+            .ok_or_else(|| FederationError::UnknownEntity(type_name.to_string()))?;
+
+        let key = keys
+            .iter()
+            .find(|key| {
+                key.fields
+                    .iter()
+                    .all(|field| representation.get(field.name()).is_some())
+            })
+            .ok_or_else(|| FederationError::NoMatchingKey(type_name.to_string()))?;
+
+        let invalid = |field: &KeyField, value: &Value| FederationError::InvalidKeyValue {
+            type_name: type_name.to_string(),
+            field: field.name().to_string(),
+            value: value.clone(),
+        };
+
+        if key.fields == [KeyField::Id] {
+            let id = &representation["_id"];
+            let id = id.as_str().ok_or_else(|| invalid(&KeyField::Id, id))?;
+            return Ok(EntityLookup {
+                type_name: type_name.to_string(),
+                arguments: format!("id: {}", string_literal(id)),
+            });
+        }
+
+        let mut filters = Vec::new();
+        for field in &key.fields {
+            let value = &representation[field.name()];
+            let filter = match field {
+                KeyField::Id => value.as_str().map(string_literal),
+                KeyField::Scalar(_) => scalar_literal(value).map(|v| format!("{{eq: {}}}", v)),
+                KeyField::Enum(_) => value
+                    .as_str()
+                    .filter(|v| is_graphql_name(v))
+                    .map(|v| format!("{{eq: {}}}", v)),
+                KeyField::Reference(_) => value
+                    .get("_id")
+                    .and_then(Value::as_str)
+                    .map(|id| format!("{{_id: {}}}", string_literal(id))),
+            }
+            .ok_or_else(|| invalid(field, value))?;
+            filters.push(format!("{}: {}", field.name(), filter));
+        }
+
+        Ok(EntityLookup {
+            type_name: type_name.to_string(),
+            arguments: format!("filter: {{{}}}, limit: 1", filters.join(", ")),
+        })
+    }
+}
+
+/// A query for a single entity against TerminusDB's GraphQL endpoint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityLookup {
+    /// the type's query field, which has the type's name
+    pub type_name: String,
+    /// arguments of the query field, e.g. `id: "Project/apollo"`
+    pub arguments: String,
+}
+
+impl EntityLookup {
+    /// The GraphQL query fetching the entity's `selection`, e.g. `"_id name"`.
+    /// The entity is the first element of the result list, if it exists.
+    pub fn to_query(&self, selection: &str) -> String {
+        format!(
+            "query {{ {}({}) {{ {} }} }}",
+            self.type_name, self.arguments, selection
+        )
+    }
+}
+
+/// The key made of the given key fields of `schema`, if all of them can be
+/// used in a `@key`
+fn field_key(schemas: &[Schema], schema: &Schema, fields: &[String]) -> Option<EntityKey> {
+    if fields.is_empty() {
+        return None;
+    }
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let property = find_property(schemas, schema, field)?;
+            // Optional and collection fields don't identify a document
+            if property.r#type.is_some() {
+                return None;
+            }
+            let class = property.class.as_str();
+            Some(if class.starts_with("xsd:") || class.starts_with("sys:") {
+                KeyField::Scalar(field.clone())
+            } else if schemas
+                .iter()
+                .any(|s| s.is_enum() && s.class_name() == class)
+            {
+                KeyField::Enum(field.clone())
+            } else {
+                KeyField::Reference(field.clone())
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(EntityKey { fields })
+}
+
+/// A property of `schema` or of one of the classes it inherits from
+fn find_property<'a>(
+    schemas: &'a [Schema],
+    schema: &'a Schema,
+    name: &str,
+) -> Option<&'a Property> {
+    if let Some(property) = schema
+        .own_properties()
+        .into_iter()
+        .find(|property| property.name == name)
+    {
+        return Some(property);
+    }
+    let inherits = match schema {
+        Schema::Class { inherits, .. } | Schema::OneOfClass { inherits, .. } => inherits,
+        _ => return None,
+    };
+    inherits.iter().find_map(|parent| {
+        let parent = schemas.iter().find(|s| s.class_name() == parent)?;
+        find_property(schemas, parent, name)
+    })
+}
+
+/// A JSON string is a valid GraphQL string literal
+fn string_literal(s: &str) -> String {
+    Value::from(s).to_string()
+}
+
+fn scalar_literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(string_literal(s)),
+        Value::Number(_) | Value::Bool(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn is_graphql_name(s: &str) -> bool {
+    s.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic())
+        && s.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use terminusdb_schema::TypeFamily;
+
+    fn class(id: &str, key: Key, subdocument: bool, properties: Vec<(&str, &str)>) -> Schema {
+        Schema::Class {
+            id: id.to_string(),
+            base: None,
+            key,
+            documentation: None,
+            subdocument,
+            r#abstract: false,
+            inherits: vec![],
+            unfoldable: false,
+            properties: properties
+                .into_iter()
+                .map(|(name, class)| Property {
+                    name: name.to_string(),
+                    r#type: None,
+                    class: class.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn schemas() -> Vec<Schema> {
+        vec![
+            class(
+                "Project",
+                Key::Lexical(vec!["name".to_string()]),
+                false,
+                vec![("name", "xsd:string")],
+            ),
+            class(
+                "Ticket",
+                Key::Hash(vec!["project".to_string(), "number".to_string()]),
+                false,
+                vec![
+                    ("project", "Project"),
+                    ("number", "xsd:integer"),
+                    ("state", "State"),
+                ],
+            ),
+            class("Comment", Key::Random, false, vec![("text", "xsd:string")]),
+            class(
+                "Address",
+                Key::ValueHash,
+                true,
+                vec![("street", "xsd:string")],
+            ),
+            Schema::Enum {
+                id: "State".to_string(),
+                base: None,
+                values: vec!["Open".to_string(), "Closed".to_string()],
+                documentation: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_keys_follow_key_strategy() {
+        let keys = EntityKeys::from_schemas(&schemas());
+
+        let field_sets = |name: &str| {
+            keys.get(name)
+                .unwrap()
+                .iter()
+                .map(EntityKey::field_set)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(field_sets("Project"), vec!["_id", "name"]);
+        assert_eq!(field_sets("Ticket"), vec!["_id", "project { _id } number"]);
+        assert_eq!(field_sets("Comment"), vec!["_id"]);
+        assert!(!keys.is_entity("Address"));
+        assert!(!keys.is_entity("State"));
+        assert_eq!(
+            keys.directives("Project"),
+            r#" @key(fields: "_id") @key(fields: "name")"#
+        );
+    }
+
+    #[test]
+    fn test_federated_sdl() {
+        let schemas = schemas();
+        let sdl = allframes_to_federated_sdl(
+            &schemas_vec_to_allframes(&schemas),
+            &EntityKeys::from_schemas(&schemas),
+        );
+
+        assert!(sdl.starts_with(
+            "extend schema\n  @link(url: \"https://specs.apollo.dev/federation/v2.3\""
+        ));
+        assert!(sdl.contains("type Project @key(fields: \"_id\") @key(fields: \"name\") {\n"));
+        assert!(sdl.contains("type Comment @key(fields: \"_id\") {\n"));
+        assert!(sdl.contains("type Address @shareable {\n"));
+        assert!(!sdl.contains("_entities"));
+    }
+
+    #[test]
+    fn test_subgraph_sdl() {
+        let schemas = schemas();
+        let frames = schemas_vec_to_allframes(&schemas);
+        let keys = EntityKeys::from_schemas(&schemas).with_shareable("Comment");
+        let sdl = allframes_to_subgraph_sdl(&frames, &keys);
+
+        validate_sdl_or_panic(&format!(
+            "{}{}",
+            FEDERATION_DEFINITIONS,
+            strip_link_extension(&sdl)
+        ));
+        assert!(sdl.contains("type Comment @key(fields: \"_id\") @shareable {\n"));
+        assert!(sdl.contains("union _Entity = Comment | Project | Ticket\n"));
+        assert!(sdl.contains("  _entities(representations: [_Any!]!): [_Entity]!\n"));
+        assert!(sdl.contains("  _service: _Service!\n"));
+
+        let no_entities = allframes_to_subgraph_sdl(&frames, &EntityKeys::default());
+        assert!(!no_entities.contains("_Entity"));
+        assert!(no_entities.contains("_service: _Service!"));
+    }
+
+    #[test]
+    fn test_optional_key_field_is_not_a_key() {
+        let mut schema = class(
+            "Person",
+            Key::Lexical(vec!["email".to_string()]),
+            false,
+            vec![("email", "xsd:string")],
+        );
+        if let Schema::Class { properties, .. } = &mut schema {
+            properties[0].r#type = Some(TypeFamily::Optional);
+        }
+        let keys = EntityKeys::from_schemas(&[schema]);
+        assert_eq!(keys.get("Person").unwrap(), &[EntityKey::id()]);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let keys = EntityKeys::from_schemas(&schemas());
+
+        let lookup = keys
+            .lookup(&json!({"__typename": "Project", "_id": "Project/apollo"}))
+            .unwrap();
+        assert_eq!(
+            lookup.to_query("name"),
+            r#"query { Project(id: "Project/apollo") { name } }"#
+        );
+
+        let lookup = keys
+            .lookup(&json!({
+                "__typename": "Ticket",
+                "project": {"_id": "Project/apollo"},
+                "number": 7
+            }))
+            .unwrap();
+        assert_eq!(
+            lookup.arguments,
+            r#"filter: {project: {_id: "Project/apollo"}, number: {eq: 7}}, limit: 1"#
+        );
+
+        assert_eq!(
+            keys.lookup(&json!({"__typename": "Ticket", "number": 7})),
+            Err(FederationError::NoMatchingKey("Ticket".to_string()))
+        );
+        assert_eq!(
+            keys.lookup(&json!({"__typename": "Address", "_id": "x"})),
+            Err(FederationError::UnknownEntity("Address".to_string()))
+        );
+        assert!(matches!(
+            keys.lookup(&json!({"__typename": "Project", "name": ["a"]})),
+            Err(FederationError::InvalidKeyValue { .. })
+        ));
+    }
+}
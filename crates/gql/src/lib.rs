@@ -19,20 +19,57 @@
 //! let sdl = generate_gql_schema::<(Project, Ticket)>();
 //! println!("{}", sdl);
 //! ```
+//!
+//! For an Apollo Federation subgraph, `generate_federated_gql_schema` adds
+//! `@key` directives derived from the models' key strategies and marks value
+//! types `@shareable`; `generate_subgraph_gql_schema` also declares the
+//! `_entities` and `_service` fields the subgraph serves.
+//!
+//! `generate_relay_gql_schema` adds Relay connection types and fields, with
+//! `ConnectionArgs` mapping their cursors onto TerminusDB's `offset`/`limit`.
+//!
+//! `WoqlTranslator` turns GraphQL queries against the generated schema into
+//! WOQL, for servers whose GraphQL endpoint is unavailable.
+//!
+//! `generate_resolvers` emits async-graphql resolvers for the generated
+//! schema, so a Rust service can serve it over its TerminusDB models, with
+//! per-model change subscriptions fed by the client's change stream.
+//!
+//! `generate_client` emits a typed client from an introspection envelope, for
+//! consumers of a remote TerminusDB GraphQL API without access to the models.
 
+mod client;
 pub mod codegen;
+mod federation;
 mod frames;
 #[cfg(feature = "live")]
 mod live;
+mod relay;
 mod render;
+mod resolvers;
 mod schema;
+mod woql;
 
+pub use client::{generate_client, generate_client_from_sdl};
 pub use codegen::{generate_all, generate_filter_impls, generate_filter_types, ModelConfig};
+pub use federation::{
+    allframes_to_federated_sdl, allframes_to_subgraph_sdl, generate_federated_gql_schema,
+    generate_federated_gql_schema_unchecked, generate_subgraph_gql_schema,
+    generate_subgraph_gql_schema_unchecked, EntityKey, EntityKeys, EntityLookup, FederationError,
+    KeyField, FEDERATION_SPEC_URL,
+};
 pub use frames::{schemas_to_allframes, schemas_vec_to_allframes};
 #[cfg(feature = "live")]
 pub use live::{introspect_schema_for, introspect_schema_sdl_for, with_introspected_schema};
+pub use relay::{
+    allframes_to_relay_sdl, decode_cursor, encode_cursor, generate_relay_gql_schema,
+    generate_relay_gql_schema_unchecked, Connection, ConnectionArgs, ConnectionError, Edge, Page,
+    PageInfo,
+};
 pub use render::render_introspection_to_sdl;
+pub use resolvers::generate_resolvers;
 pub use schema::{allframes_to_sdl, generate_gql_schema};
+pub use woql::{graphql_to_woql, TranslateError, WoqlTranslator};
 
 // Re-export key types from terminusdb-community
 pub use terminusdb_community::graphql::filter;
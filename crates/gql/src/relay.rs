@@ -0,0 +1,323 @@
+//! Relay-style connection pagination for the generated schema.
+//!
+//! [`generate_relay_gql_schema`] emits the same SDL as
+//! [`generate_gql_schema`](crate::generate_gql_schema), plus the
+//! [connection types](https://relay.dev/graphql/connections.htm) Relay and
+//! Apollo clients paginate with:
+//!
+//! ```graphql
+//! type ProjectEdge {
+//!   cursor: String!
+//!   node: Project!
+//! }
+//!
+//! type ProjectConnection {
+//!   edges: [ProjectEdge!]!
+//!   pageInfo: PageInfo!
+//! }
+//!
+//! type Query {
+//!   ProjectConnection(first: Int, after: String, last: Int, before: String,
+//!     filter: Project_Filter, orderBy: Project_Ordering): ProjectConnection!
+//! }
+//! ```
+//!
+//! TerminusDB itself pages with `offset` and `limit`, so a cursor is the
+//! position of a document in the ordered result. [`ConnectionArgs::page`]
+//! turns the connection arguments into the [`Page`] to query, and
+//! [`Page::connection`] turns the documents returned into the connection:
+//!
+//! ```ignore
+//! use terminusdb_gql::ConnectionArgs;
+//!
+//! let args = ConnectionArgs { first: Some(10), after: Some(cursor), ..Default::default() };
+//! let page = args.page(None)?;
+//! // query { Project(offset: page.offset, limit: page.query_limit()) { ... } }
+//! let connection = page.connection(documents);
+//! ```
+
+use serde::Serialize;
+use terminusdb_community::graphql::frame::AllFrames;
+use terminusdb_schema::ToTDBSchemas;
+
+use crate::frames::schemas_to_allframes;
+use crate::schema::{render_sdl, validate_sdl_or_panic};
+
+const CURSOR_PREFIX: &str = "cursor:";
+
+/// Generate a GraphQL schema (SDL) with a Relay connection type and a
+/// `{Type}Connection` query field for every class; see the
+/// [module documentation](self).
+pub fn generate_relay_gql_schema<T: ToTDBSchemas>() -> String {
+    let sdl = generate_relay_gql_schema_unchecked::<T>();
+    validate_sdl_or_panic(&sdl);
+    sdl
+}
+
+/// Same as `generate_relay_gql_schema` but skips the apollo-compiler
+/// validation pass; see `generate_gql_schema_unchecked`.
+pub fn generate_relay_gql_schema_unchecked<T: ToTDBSchemas>() -> String {
+    allframes_to_relay_sdl(&schemas_to_allframes::<T>())
+}
+
+/// Generate SDL with Relay connections from AllFrames.
+pub fn allframes_to_relay_sdl(frames: &AllFrames) -> String {
+    render_sdl(frames, None, true)
+}
+
+/// Why connection arguments could not be turned into a page
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConnectionError {
+    #[error("invalid cursor '{0}'")]
+    InvalidCursor(String),
+
+    #[error("'last' needs a 'before' cursor or the total count")]
+    UnboundedLast,
+}
+
+/// The pagination arguments of a `{Type}Connection` field
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionArgs {
+    pub first: Option<usize>,
+    pub after: Option<String>,
+    pub last: Option<usize>,
+    pub before: Option<String>,
+}
+
+impl ConnectionArgs {
+    /// The window of results these arguments select. `total` is the number
+    /// of results, which is only needed for `last` without `before`.
+    pub fn page(&self, total: Option<usize>) -> Result<Page, ConnectionError> {
+        let mut start = match &self.after {
+            Some(cursor) => decode_cursor(cursor)? + 1,
+            None => 0,
+        };
+        let mut end = match &self.before {
+            Some(cursor) => Some(decode_cursor(cursor)?),
+            None => total,
+        };
+        if let Some(first) = self.first {
+            end = Some(end.map_or(start + first, |end| end.min(start + first)));
+        }
+        if let Some(last) = self.last {
+            let end = end.ok_or(ConnectionError::UnboundedLast)?;
+            start = start.max(end.saturating_sub(last));
+        }
+        let end = end.map(|end| end.max(start));
+
+        Ok(Page {
+            offset: start,
+            limit: end.map(|end| end - start),
+            total,
+        })
+    }
+}
+
+/// A window of results, as TerminusDB's `offset` and `limit`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Page {
+    pub offset: usize,
+    /// `None` when the page runs to the end of the results
+    pub limit: Option<usize>,
+    total: Option<usize>,
+}
+
+impl Page {
+    /// The `limit` to query with: one more than the page, so that
+    /// [`connection`](Self::connection) can tell whether a next page exists
+    pub fn query_limit(&self) -> Option<usize> {
+        self.limit.map(|limit| limit + 1)
+    }
+
+    /// The connection over the results queried with `offset` and
+    /// [`query_limit`](Self::query_limit).
+    pub fn connection<T>(&self, mut nodes: Vec<T>) -> Connection<T> {
+        let more = match self.limit {
+            Some(limit) if nodes.len() > limit => {
+                nodes.truncate(limit);
+                true
+            }
+            _ => false,
+        };
+        let end = self.offset + nodes.len();
+        let edges: Vec<Edge<T>> = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| Edge {
+                cursor: encode_cursor(self.offset + i),
+                node,
+            })
+            .collect();
+
+        Connection {
+            page_info: PageInfo {
+                has_next_page: more || self.total.is_some_and(|total| end < total),
+                has_previous_page: self.offset > 0,
+                start_cursor: edges.first().map(|edge| edge.cursor.clone()),
+                end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+            },
+            edges,
+        }
+    }
+}
+
+/// A `{Type}Connection`, serializing to the shape of the generated SDL
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection<T> {
+    pub edges: Vec<Edge<T>>,
+    pub page_info: PageInfo,
+}
+
+/// A `{Type}Edge`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Edge<T> {
+    pub cursor: String,
+    pub node: T,
+}
+
+/// The Relay `PageInfo` type
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+/// The cursor of the result at `position`
+pub fn encode_cursor(position: usize) -> String {
+    format!("{}{}", CURSOR_PREFIX, position)
+}
+
+/// The position of the result a cursor points at
+pub fn decode_cursor(cursor: &str) -> Result<usize, ConnectionError> {
+    cursor
+        .strip_prefix(CURSOR_PREFIX)
+        .and_then(|position| position.parse().ok())
+        .ok_or_else(|| ConnectionError::InvalidCursor(cursor.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frames::schemas_vec_to_allframes;
+    use terminusdb_schema::{Key, Property, Schema};
+
+    fn args(
+        first: Option<usize>,
+        after: Option<usize>,
+        last: Option<usize>,
+        before: Option<usize>,
+    ) -> ConnectionArgs {
+        ConnectionArgs {
+            first,
+            after: after.map(encode_cursor),
+            last,
+            before: before.map(encode_cursor),
+        }
+    }
+
+    #[test]
+    fn test_relay_sdl_validates() {
+        let schemas = vec![Schema::Class {
+            id: "Project".to_string(),
+            base: None,
+            key: Key::Lexical(vec!["name".to_string()]),
+            documentation: None,
+            subdocument: false,
+            r#abstract: false,
+            inherits: vec![],
+            unfoldable: false,
+            properties: vec![Property {
+                name: "name".to_string(),
+                r#type: None,
+                class: "xsd:string".to_string(),
+            }],
+        }];
+
+        let sdl = allframes_to_relay_sdl(&schemas_vec_to_allframes(&schemas));
+        validate_sdl_or_panic(&sdl);
+
+        assert!(sdl.contains("type ProjectEdge {\n  cursor: String!\n  node: Project!\n}"));
+        assert!(sdl.contains("type ProjectConnection {\n  edges: [ProjectEdge!]!"));
+        assert!(sdl.contains("type PageInfo {"));
+        assert!(sdl.contains("ProjectConnection(first: Int, after: String, last: Int, before: String, filter: Project_Filter, orderBy: Project_Ordering): ProjectConnection!"));
+
+        let plain = crate::allframes_to_sdl(&schemas_vec_to_allframes(&schemas));
+        assert!(!plain.contains("Connection"));
+    }
+
+    #[test]
+    fn test_page_from_args() {
+        let page = |args: ConnectionArgs, total| {
+            let page = args.page(total).unwrap();
+            (page.offset, page.limit)
+        };
+
+        assert_eq!(page(args(None, None, None, None), None), (0, None));
+        assert_eq!(page(args(Some(10), None, None, None), None), (0, Some(10)));
+        assert_eq!(
+            page(args(Some(10), Some(9), None, None), None),
+            (10, Some(10))
+        );
+        assert_eq!(
+            page(args(None, None, Some(5), Some(20)), None),
+            (15, Some(5))
+        );
+        assert_eq!(
+            page(args(None, None, Some(5), None), Some(12)),
+            (7, Some(5))
+        );
+        assert_eq!(
+            page(args(Some(10), Some(4), None, Some(8)), None),
+            (5, Some(3))
+        );
+        assert_eq!(
+            page(args(None, None, Some(50), Some(3)), None),
+            (0, Some(3))
+        );
+
+        assert_eq!(
+            args(None, None, Some(5), None).page(None),
+            Err(ConnectionError::UnboundedLast)
+        );
+        let bad = ConnectionArgs {
+            after: Some("42".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            bad.page(None),
+            Err(ConnectionError::InvalidCursor("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_connection_page_info() {
+        let page = args(Some(2), Some(0), None, None).page(None).unwrap();
+        assert_eq!(page.query_limit(), Some(3));
+
+        let connection = page.connection(vec!["b", "c", "d"]);
+        assert_eq!(
+            connection.edges.iter().map(|e| e.node).collect::<Vec<_>>(),
+            vec!["b", "c"]
+        );
+        assert_eq!(
+            connection.page_info,
+            PageInfo {
+                has_next_page: true,
+                has_previous_page: true,
+                start_cursor: Some(encode_cursor(1)),
+                end_cursor: Some(encode_cursor(2)),
+            }
+        );
+
+        let last = page.connection(vec!["b"]);
+        assert!(!last.page_info.has_next_page);
+
+        let json = serde_json::to_value(&last).unwrap();
+        assert_eq!(json["pageInfo"]["endCursor"], "cursor:1");
+        assert_eq!(json["edges"][0]["node"], "b");
+    }
+}
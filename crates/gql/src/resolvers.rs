@@ -0,0 +1,958 @@
+//! Code generation for async-graphql server resolvers.
+//!
+//! [`generate_resolvers`] turns the SDL of [`generate_gql_schema`](crate::generate_gql_schema)
+//! into resolvers for [async-graphql](https://docs.rs/async-graphql), so a Rust
+//! service can serve a GraphQL API over its TerminusDB models:
+//!
+//! - one `{Type}Node` per object type: a `SimpleObject` holding `_id`, with a
+//!   `ComplexObject` impl resolving every other field from the stored document
+//! - `TdbQuery`, a query root with one field per model, taking `id`, `ids`,
+//!   `offset` and `limit` and running a [`ModelQuery`](terminusdb_orm::ModelQuery)
+//! - `TdbDocumentLoader`, an async-graphql `DataLoader` backed by the ORM's
+//!   batch fetch, so the links of all nodes in a response load in one request
+//! - `TdbSubscription`, a subscription root with an `on{Type}Changed` field
+//!   per model, streaming `{Type}Change` payloads from a
+//!   [`ChangeListener`](terminusdb_orm::TerminusDBHttpClient::change_listener)
+//!   on the served branch
+//! - `tdb_schema_builder`, which wires the above into a schema builder
+//!
+//! Given a federated SDL (see
+//! [`generate_federated_gql_schema`](crate::generate_federated_gql_schema)),
+//! models whose type has a `@key` also get a reference resolver on `_id`,
+//! and the schema builder enables federation, so async-graphql serves
+//! `_entities` and `_service`.
+//!
+//! The generated code expects `async-graphql` 7 with the `dataloader` feature,
+//! `terminusdb-orm`, `anyhow`, `serde_json` and `tokio` as dependencies of the
+//! crate including it. Subscriptions only receive events when the client's
+//! change stream is enabled with `TERMINUSDB_SSE=true`.
+//!
+//! # Usage in build.rs
+//!
+//! ```ignore
+//! let sdl = generate_gql_schema::<(Project, Ticket)>();
+//! let resolvers = generate_resolvers(&sdl, &[
+//!     ("Project", "my_domain::Project"),
+//!     ("Ticket", "my_domain::Ticket"),
+//! ])?;
+//! fs::write(format!("{}/resolvers.rs", out_dir), resolvers)?;
+//! ```
+//!
+//! and in the service:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/resolvers.rs"));
+//!
+//! let schema = tdb_schema_builder(client, spec).finish();
+//! let response = schema.execute("{ Ticket(limit: 10) { title project { name } } }").await;
+//! let changes = schema.execute_stream("subscription { onTicketChanged { kind _id node { title } } }");
+//! ```
+
+use graphql_parser::schema::{parse_schema, Definition, ObjectType, Type, TypeDefinition};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use std::collections::HashSet;
+
+use crate::codegen::sanitize_type_name;
+
+/// Generate async-graphql resolvers for the object types in `sdl`.
+///
+/// Every object type gets a node type; `models` lists the `(name, path)` of
+/// the types to expose on the query root, as for
+/// [`generate_filter_impls`](crate::generate_filter_impls). Their nodes also
+/// get a `model()` accessor returning the typed model.
+pub fn generate_resolvers(sdl: &str, models: &[(&str, &str)]) -> Result<String, String> {
+    let sdl = crate::federation::strip_link_extension(sdl);
+    let doc = parse_schema::<String>(sdl).map_err(|e| format!("Failed to parse SDL: {}", e))?;
+
+    let objects: Vec<&ObjectType<String>> = doc
+        .definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.name != "Query" =>
+            {
+                Some(object)
+            }
+            _ => None,
+        })
+        .collect();
+    let object_names: HashSet<&str> = objects.iter().map(|o| o.name.as_str()).collect();
+
+    for (name, _) in models {
+        if !object_names.contains(name) {
+            return Err(format!("Model {} has no object type in the SDL", name));
+        }
+    }
+
+    let nodes: Vec<TokenStream> = objects
+        .iter()
+        .map(|object| {
+            let path = models
+                .iter()
+                .find(|(name, _)| *name == object.name)
+                .map(|(_, path)| parse_path(path));
+            generate_node(object, &object_names, path)
+        })
+        .collect();
+
+    let root_fields: Vec<TokenStream> = models
+        .iter()
+        .map(|(name, path)| generate_root_field(name, &parse_path(path)))
+        .collect();
+
+    let entities: Vec<TokenStream> = objects
+        .iter()
+        .filter(|object| {
+            models.iter().any(|(name, _)| *name == object.name)
+                && object.directives.iter().any(|d| d.name == "key")
+        })
+        .map(|object| generate_reference_resolver(&object.name))
+        .collect();
+
+    let (subscription_fields, change_types): (Vec<TokenStream>, Vec<TokenStream>) = models
+        .iter()
+        .map(|(name, path)| generate_subscription(name, &parse_path(path)))
+        .unzip();
+
+    let used = used_helpers(&quote! {
+        #(#nodes)*
+        #(#root_fields)*
+        #(#entities)*
+        #(#subscription_fields)*
+        #(#change_types)*
+    });
+    let support = generate_support(!entities.is_empty(), &used);
+
+    let tokens = quote! {
+        #support
+
+        /// Query root with one field per model.
+        #[derive(Default)]
+        pub struct TdbQuery;
+
+        #[async_graphql::Object(name = "Query")]
+        impl TdbQuery {
+            #(#root_fields)*
+
+            #(#entities)*
+        }
+
+        /// Subscription root with an `on{Model}Changed` field per model.
+        #[derive(Default)]
+        pub struct TdbSubscription;
+
+        #[async_graphql::Subscription(name = "Subscription")]
+        impl TdbSubscription {
+            #(#subscription_fields)*
+        }
+
+        #(#nodes)*
+
+        #(#change_types)*
+    };
+
+    Ok(tokens.to_string())
+}
+
+/// Node type for one object type, resolving its fields from the document.
+fn generate_node(
+    object: &ObjectType<String>,
+    object_names: &HashSet<&str>,
+    path: Option<TokenStream>,
+) -> TokenStream {
+    let gql_name = &object.name;
+    let node = node_ident(gql_name);
+
+    let fields: Vec<TokenStream> = object
+        .fields
+        .iter()
+        // `_id` is a plain field of the node; `_type` and `_json` are below.
+        .filter(|field| !field.name.starts_with('_'))
+        .map(|field| generate_field(&field.name, &field.field_type, object_names))
+        .collect();
+
+    let model = path.map(|path| {
+        quote! {
+            /// The document as the model it was generated from.
+            pub fn model(&self) -> anyhow::Result<#path> {
+                <#path as terminusdb_orm::InstanceFromJson>::from_json(self.document.clone())
+            }
+        }
+    });
+
+    quote! {
+        #[derive(Clone, Debug, async_graphql::SimpleObject)]
+        #[graphql(complex, name = #gql_name)]
+        pub struct #node {
+            #[graphql(name = "_id")]
+            pub id: async_graphql::ID,
+            #[graphql(skip)]
+            pub document: serde_json::Value,
+        }
+
+        impl #node {
+            /// Wrap a document as returned by the document API.
+            pub fn from_document(document: serde_json::Value) -> Self {
+                let id = document
+                    .get("@id")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                Self {
+                    id: async_graphql::ID::from(id),
+                    document,
+                }
+            }
+
+            #model
+        }
+
+        #[async_graphql::ComplexObject]
+        impl #node {
+            #[graphql(name = "_type")]
+            async fn resolve_tdb_type(&self) -> async_graphql::Result<async_graphql::ID> {
+                tdb_required(
+                    tdb_field(&self.document, "@type").and_then(tdb_string),
+                    "@type",
+                )
+                .map(async_graphql::ID::from)
+            }
+
+            #[graphql(name = "_json")]
+            async fn resolve_tdb_json(&self) -> Option<async_graphql::Json<serde_json::Value>> {
+                Some(async_graphql::Json(self.document.clone()))
+            }
+
+            #(#fields)*
+        }
+    }
+}
+
+/// Resolver for one field of a node.
+fn generate_field(name: &str, ty: &Type<String>, object_names: &HashSet<&str>) -> TokenStream {
+    let method = Ident::new(&format!("resolve_{}", name), Span::call_site());
+    let (base, shape) = field_shape(ty);
+
+    if object_names.contains(base.as_str()) {
+        let node = node_ident(&base);
+        let (output, body) = match shape {
+            Shape::List => (
+                quote! { Vec<#node> },
+                quote! { Ok(documents.into_iter().map(#node::from_document).collect()) },
+            ),
+            Shape::Required => (
+                quote! { #node },
+                quote! {
+                    tdb_required(documents.into_iter().next(), #name).map(#node::from_document)
+                },
+            ),
+            Shape::Optional => (
+                quote! { Option<#node> },
+                quote! { Ok(documents.into_iter().next().map(#node::from_document)) },
+            ),
+        };
+        return quote! {
+            #[graphql(name = #name)]
+            async fn #method(
+                &self,
+                ctx: &async_graphql::Context<'_>,
+            ) -> async_graphql::Result<#output> {
+                let documents = tdb_documents(ctx, tdb_values(&self.document, #name)).await?;
+                #body
+            }
+        };
+    }
+
+    let (rust_type, convert) = scalar(&base);
+    let (output, body) = match shape {
+        Shape::List => (
+            quote! { Vec<#rust_type> },
+            quote! {
+                Ok(tdb_values(&self.document, #name)
+                    .into_iter()
+                    .filter_map(#convert)
+                    .collect())
+            },
+        ),
+        Shape::Required => (
+            quote! { #rust_type },
+            quote! { tdb_required(tdb_field(&self.document, #name).and_then(#convert), #name) },
+        ),
+        Shape::Optional => (
+            quote! { Option<#rust_type> },
+            quote! { Ok(tdb_field(&self.document, #name).and_then(#convert)) },
+        ),
+    };
+    quote! {
+        #[graphql(name = #name)]
+        async fn #method(&self) -> async_graphql::Result<#output> {
+            #body
+        }
+    }
+}
+
+/// Subscription field streaming the changes to one model, and its payload type.
+fn generate_subscription(name: &str, path: &TokenStream) -> (TokenStream, TokenStream) {
+    let field_name = format!("on{}Changed", sanitize_type_name(name));
+    let method = Ident::new(
+        &format!("on_{}_changed", name.to_lowercase()),
+        Span::call_site(),
+    );
+    let node = node_ident(name);
+    let change = Ident::new(
+        &format!("{}Change", sanitize_type_name(name)),
+        Span::call_site(),
+    );
+    let change_name = change.to_string();
+
+    let field = quote! {
+        #[graphql(name = #field_name)]
+        async fn #method(
+            &self,
+            ctx: &async_graphql::Context<'_>,
+        ) -> async_graphql::Result<impl async_graphql::futures_util::Stream<Item = #change>> {
+            use async_graphql::futures_util::StreamExt;
+
+            Ok(tdb_changes::<#path>(ctx)?.map(|(kind, id, changed_fields)| #change {
+                kind,
+                id: async_graphql::ID::from(id),
+                changed_fields: async_graphql::Json(changed_fields),
+            }))
+        }
+    };
+
+    let payload = quote! {
+        /// A change to one document, as reported by the changeset stream.
+        #[derive(Clone, Debug, async_graphql::SimpleObject)]
+        #[graphql(complex, name = #change_name)]
+        pub struct #change {
+            pub kind: TdbChangeKind,
+            #[graphql(name = "_id")]
+            pub id: async_graphql::ID,
+            #[graphql(name = "changedFields")]
+            pub changed_fields:
+                async_graphql::Json<std::collections::HashMap<String, serde_json::Value>>,
+        }
+
+        #[async_graphql::ComplexObject]
+        impl #change {
+            /// The document as currently stored; null once it is deleted.
+            async fn node(
+                &self,
+                ctx: &async_graphql::Context<'_>,
+            ) -> async_graphql::Result<Option<#node>> {
+                if self.kind == TdbChangeKind::Deleted {
+                    return Ok(None);
+                }
+                let id = serde_json::Value::String(self.id.to_string());
+                let documents = tdb_documents(ctx, vec![&id]).await?;
+                Ok(documents.into_iter().next().map(#node::from_document))
+            }
+        }
+    };
+
+    (field, payload)
+}
+
+/// Query root field listing the instances of one model.
+fn generate_root_field(name: &str, path: &TokenStream) -> TokenStream {
+    let method = Ident::new(&format!("list_{}", name.to_lowercase()), Span::call_site());
+    let node = node_ident(name);
+
+    quote! {
+        #[graphql(name = #name)]
+        async fn #method(
+            &self,
+            ctx: &async_graphql::Context<'_>,
+            id: Option<async_graphql::ID>,
+            ids: Option<Vec<async_graphql::ID>>,
+            offset: Option<i32>,
+            limit: Option<i32>,
+        ) -> async_graphql::Result<Vec<#node>> {
+            let ids: Vec<String> = id
+                .into_iter()
+                .chain(ids.into_iter().flatten())
+                .map(|id| id.to_string())
+                .collect();
+            let query = if ids.is_empty() {
+                <#path as terminusdb_orm::ModelExt>::all()
+            } else {
+                terminusdb_orm::ModelQuery::<#path>::new(ids)
+            };
+            let documents = tdb_run(ctx, query, offset, limit).await?;
+            Ok(documents.into_iter().map(#node::from_document).collect())
+        }
+    }
+}
+
+/// Federation reference resolver loading an entity of one model by `_id`.
+fn generate_reference_resolver(name: &str) -> TokenStream {
+    let method = Ident::new(
+        &format!("resolve_{}_reference", name.to_lowercase()),
+        Span::call_site(),
+    );
+    let node = node_ident(name);
+
+    quote! {
+        #[graphql(entity)]
+        async fn #method(
+            &self,
+            ctx: &async_graphql::Context<'_>,
+            #[graphql(key, name = "_id")] id: async_graphql::ID,
+        ) -> async_graphql::Result<Option<#node>> {
+            let id = serde_json::Value::String(id.to_string());
+            let documents = tdb_documents(ctx, vec![&id]).await?;
+            Ok(documents.into_iter().next().map(#node::from_document))
+        }
+    }
+}
+
+/// Loader, schema builder and helpers shared by all generated resolvers.
+fn generate_support(federated: bool, used: &HashSet<String>) -> TokenStream {
+    let federation = if federated {
+        quote! { .enable_federation() }
+    } else {
+        quote! {}
+    };
+
+    let helpers = support_helpers()
+        .into_iter()
+        .filter(|(name, _)| used.contains(*name))
+        .map(|(_, helper)| helper);
+
+    quote! {
+        /// Loads TerminusDB documents by ID, batching all loads made while
+        /// resolving one response into a single ORM fetch.
+        pub struct TdbDocumentLoader {
+            client: terminusdb_orm::TerminusDBHttpClient,
+            spec: terminusdb_orm::BranchSpec,
+        }
+
+        impl TdbDocumentLoader {
+            pub fn new(
+                client: terminusdb_orm::TerminusDBHttpClient,
+                spec: terminusdb_orm::BranchSpec,
+            ) -> Self {
+                Self { client, spec }
+            }
+
+            pub fn client(&self) -> &terminusdb_orm::TerminusDBHttpClient {
+                &self.client
+            }
+
+            pub fn spec(&self) -> &terminusdb_orm::BranchSpec {
+                &self.spec
+            }
+        }
+
+        impl async_graphql::dataloader::Loader<String> for TdbDocumentLoader {
+            type Value = serde_json::Value;
+            type Error = std::sync::Arc<anyhow::Error>;
+
+            async fn load(
+                &self,
+                keys: &[String],
+            ) -> Result<std::collections::HashMap<String, serde_json::Value>, Self::Error> {
+                use terminusdb_orm::MultiTypeFetch;
+
+                let documents = self
+                    .client
+                    .fetch_by_ids(keys.to_vec(), &self.spec, terminusdb_orm::GetOpts::default())
+                    .await
+                    .map_err(std::sync::Arc::new)?
+                    .into_documents();
+                let by_id: std::collections::HashMap<&str, &serde_json::Value> = documents
+                    .iter()
+                    .filter_map(|doc| Some((doc.get("@id")?.as_str()?, doc)))
+                    .collect();
+
+                // Documents come back with short IDs, links may hold full IRIs.
+                Ok(keys
+                    .iter()
+                    .filter_map(|key| {
+                        let short = key.rsplit_once("///data/").map_or(key.as_str(), |(_, id)| id);
+                        let doc = by_id.get(key.as_str()).or_else(|| by_id.get(short))?;
+                        Some((key.clone(), (*doc).clone()))
+                    })
+                    .collect())
+            }
+        }
+
+        /// A schema builder serving the generated resolvers from `spec` in
+        /// `client`'s database.
+        pub fn tdb_schema_builder(
+            client: terminusdb_orm::TerminusDBHttpClient,
+            spec: terminusdb_orm::BranchSpec,
+        ) -> async_graphql::SchemaBuilder<TdbQuery, async_graphql::EmptyMutation, TdbSubscription>
+        {
+            async_graphql::Schema::build(TdbQuery, async_graphql::EmptyMutation, TdbSubscription)
+            .data(async_graphql::dataloader::DataLoader::new(
+                TdbDocumentLoader::new(client, spec),
+                tokio::spawn,
+            ))
+            #federation
+        }
+
+        /// How a document changed.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, async_graphql::Enum)]
+        #[graphql(name = "ChangeKind")]
+        pub enum TdbChangeKind {
+            Added,
+            Updated,
+            Deleted,
+        }
+
+        #(#helpers)*
+    }
+}
+
+/// Helper functions for the generated resolvers, by name.
+fn support_helpers() -> Vec<(&'static str, TokenStream)> {
+    vec![
+        (
+            "tdb_loader",
+            quote! {
+            fn tdb_loader<'a>(
+                ctx: &async_graphql::Context<'a>,
+            ) -> async_graphql::Result<&'a async_graphql::dataloader::DataLoader<TdbDocumentLoader>> {
+                ctx.data::<async_graphql::dataloader::DataLoader<TdbDocumentLoader>>()
+            }
+            },
+        ),
+        (
+            "tdb_run",
+            quote! {
+            async fn tdb_run<T>(
+                ctx: &async_graphql::Context<'_>,
+                mut query: terminusdb_orm::ModelQuery<T>,
+                offset: Option<i32>,
+                limit: Option<i32>,
+            ) -> async_graphql::Result<Vec<serde_json::Value>>
+            where
+                T: terminusdb_orm::OrmModel + terminusdb_orm::ToSchemaClass,
+            {
+                // `match` rather than `if let`: the emitted code is on one line.
+                query = match offset {
+                    Some(offset) => query.offset(offset),
+                    None => query,
+                };
+                query = match limit {
+                    Some(limit) => query.limit(limit),
+                    None => query,
+                };
+                let loader = tdb_loader(ctx)?.loader();
+                let result = query
+                    .with_client(loader.client())
+                    .execute(loader.spec())
+                    .await?;
+                Ok(result.into_documents())
+            }
+            },
+        ),
+        (
+            "tdb_changes",
+            quote! {
+            /// Changes to documents of type `T` on the served branch, fed by a
+            /// `ChangeListener` that lives as long as the stream.
+            fn tdb_changes<T>(
+                ctx: &async_graphql::Context<'_>,
+            ) -> async_graphql::Result<
+                impl async_graphql::futures_util::Stream<
+                    Item = (
+                        TdbChangeKind,
+                        String,
+                        std::collections::HashMap<String, serde_json::Value>,
+                    ),
+                >,
+            >
+            where
+                T: terminusdb_orm::TerminusDBModel + 'static,
+            {
+                let loader = tdb_loader(ctx)?.loader();
+                let listener = loader.client().change_listener(loader.spec().clone())?;
+                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+                let added = tx.clone();
+                listener.on_added_id::<T>(move |iri| {
+                    let _ = added.send((
+                        TdbChangeKind::Added,
+                        iri.typed_path().to_string(),
+                        std::collections::HashMap::new(),
+                    ));
+                });
+                let updated = tx.clone();
+                listener.on_changeset::<T>(move |iri, changed_fields| {
+                    let _ = updated.send((
+                        TdbChangeKind::Updated,
+                        iri.typed_path().to_string(),
+                        changed_fields,
+                    ));
+                });
+                listener.on_deleted::<T>(move |iri| {
+                    let _ = tx.send((
+                        TdbChangeKind::Deleted,
+                        iri.typed_path().to_string(),
+                        std::collections::HashMap::new(),
+                    ));
+                });
+
+                Ok(async_graphql::futures_util::stream::unfold(
+                    (rx, listener),
+                    |(mut rx, listener)| async move {
+                        let change = rx.recv().await?;
+                        Some((change, (rx, listener)))
+                    },
+                ))
+            }
+            },
+        ),
+        (
+            "tdb_documents",
+            quote! {
+            /// Linked documents for `values`: IDs are loaded, inline subdocuments
+            /// are used as they are.
+            async fn tdb_documents(
+                ctx: &async_graphql::Context<'_>,
+                values: Vec<&serde_json::Value>,
+            ) -> async_graphql::Result<Vec<serde_json::Value>> {
+                let ids: Vec<String> = values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect();
+                let loaded = if ids.is_empty() {
+                    std::collections::HashMap::new()
+                } else {
+                    tdb_loader(ctx)?.load_many(ids).await?
+                };
+                Ok(values
+                    .into_iter()
+                    .filter_map(|value| match value {
+                        serde_json::Value::String(id) => loaded.get(id).cloned(),
+                        serde_json::Value::Object(_) => Some(value.clone()),
+                        _ => None,
+                    })
+                    .collect())
+            }
+            },
+        ),
+        (
+            "tdb_field",
+            quote! {
+            fn tdb_field<'a>(document: &'a serde_json::Value, name: &str) -> Option<&'a serde_json::Value> {
+                document.get(name).filter(|value| !value.is_null())
+            }
+            },
+        ),
+        (
+            "tdb_values",
+            quote! {
+            fn tdb_values<'a>(document: &'a serde_json::Value, name: &str) -> Vec<&'a serde_json::Value> {
+                match tdb_field(document, name) {
+                    Some(serde_json::Value::Array(items)) => items.iter().collect(),
+                    Some(value) => vec![value],
+                    None => Vec::new(),
+                }
+            }
+            },
+        ),
+        (
+            "tdb_required",
+            quote! {
+            fn tdb_required<T>(value: Option<T>, name: &str) -> async_graphql::Result<T> {
+                value.ok_or_else(|| async_graphql::Error::new(format!("document has no {}", name)))
+            }
+            },
+        ),
+        (
+            "tdb_string",
+            quote! {
+            fn tdb_string(value: &serde_json::Value) -> Option<String> {
+                match value {
+                    serde_json::Value::String(s) => Some(s.clone()),
+                    other => Some(other.to_string()),
+                }
+            }
+            },
+        ),
+        (
+            "tdb_int",
+            quote! {
+            fn tdb_int(value: &serde_json::Value) -> Option<i32> {
+                value.as_i64().and_then(|n| i32::try_from(n).ok())
+            }
+            },
+        ),
+        (
+            "tdb_float",
+            quote! {
+            fn tdb_float(value: &serde_json::Value) -> Option<f64> {
+                value.as_f64()
+            }
+            },
+        ),
+        (
+            "tdb_bool",
+            quote! {
+            fn tdb_bool(value: &serde_json::Value) -> Option<bool> {
+                value.as_bool()
+            }
+            },
+        ),
+        (
+            "tdb_json",
+            quote! {
+            fn tdb_json(value: &serde_json::Value) -> Option<async_graphql::Json<serde_json::Value>> {
+                Some(async_graphql::Json(value.clone()))
+            }
+            },
+        ),
+    ]
+}
+
+/// Names of the helpers `tokens` calls, with the helpers those call in turn.
+fn used_helpers(tokens: &TokenStream) -> HashSet<String> {
+    let helpers = support_helpers();
+    let mut used = HashSet::new();
+    collect_idents(tokens.clone(), &mut used);
+    loop {
+        let before = used.len();
+        for (name, helper) in &helpers {
+            if used.contains(*name) {
+                collect_idents(helper.clone(), &mut used);
+            }
+        }
+        if used.len() == before {
+            return used;
+        }
+    }
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+    for tree in tokens {
+        match tree {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Nullability and list-ness of a field type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Required,
+    Optional,
+    List,
+}
+
+/// Named type and shape of a field type, e.g. `("Ticket", List)` for `[Ticket!]!`.
+fn field_shape(ty: &Type<String>) -> (String, Shape) {
+    fn named(ty: &Type<String>) -> String {
+        match ty {
+            Type::NamedType(name) => name.clone(),
+            Type::NonNullType(inner) | Type::ListType(inner) => named(inner),
+        }
+    }
+
+    match ty {
+        Type::NamedType(name) => (name.clone(), Shape::Optional),
+        Type::NonNullType(inner) => match inner.as_ref() {
+            Type::ListType(_) => (named(inner), Shape::List),
+            _ => (named(inner), Shape::Required),
+        },
+        Type::ListType(inner) => (named(inner), Shape::List),
+    }
+}
+
+/// Rust type and JSON conversion function for a scalar or enum.
+///
+/// `BigInt`, `BigFloat` and `DateTime` are served as strings, as are enums,
+/// whose values TerminusDB stores as strings.
+fn scalar(name: &str) -> (TokenStream, TokenStream) {
+    match name {
+        "Int" => (quote! { i32 }, quote! { tdb_int }),
+        "Float" => (quote! { f64 }, quote! { tdb_float }),
+        "Boolean" => (quote! { bool }, quote! { tdb_bool }),
+        "JSON" => (
+            quote! { async_graphql::Json<serde_json::Value> },
+            quote! { tdb_json },
+        ),
+        _ => (quote! { String }, quote! { tdb_string }),
+    }
+}
+
+fn node_ident(name: &str) -> Ident {
+    Ident::new(
+        &format!("{}Node", sanitize_type_name(name)),
+        Span::call_site(),
+    )
+}
+
+/// Parse a model path, falling back to a plain identifier like
+/// [`generate_filter_impls`](crate::generate_filter_impls) does.
+fn parse_path(path: &str) -> TokenStream {
+    path.parse().unwrap_or_else(|_| {
+        let ident = Ident::new(path, Span::call_site());
+        quote! { #ident }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = r#"
+        scalar JSON
+
+        enum Status {
+            Open
+            Closed
+        }
+
+        type Project {
+            _id: ID!
+            _type: ID!
+            _json: JSON
+            name: String!
+            budget: Int
+            tags: [String!]!
+        }
+
+        type Ticket {
+            _id: ID!
+            _type: ID!
+            _json: JSON
+            title: String!
+            status: Status
+            project: Project!
+            watchers: [Project!]!
+        }
+
+        type Query {
+            Project(id: ID, limit: Int): [Project!]!
+            Ticket(id: ID, limit: Int): [Ticket!]!
+        }
+    "#;
+
+    #[test]
+    fn test_field_shape() {
+        let doc = parse_schema::<String>("type T { a: Int b: Int! c: [Int!]! d: [Int] }").unwrap();
+        let Definition::TypeDefinition(TypeDefinition::Object(object)) = &doc.definitions[0] else {
+            panic!("expected an object type");
+        };
+        let shapes: Vec<_> = object
+            .fields
+            .iter()
+            .map(|f| field_shape(&f.field_type))
+            .collect();
+        assert_eq!(
+            shapes,
+            vec![
+                ("Int".to_string(), Shape::Optional),
+                ("Int".to_string(), Shape::Required),
+                ("Int".to_string(), Shape::List),
+                ("Int".to_string(), Shape::List),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_resolvers() {
+        let code = generate_resolvers(SDL, &[("Ticket", "my_crate::Ticket")]).unwrap();
+
+        // A node per object type, a root field per model only.
+        assert!(code.contains("pub struct ProjectNode"));
+        assert!(code.contains("pub struct TicketNode"));
+        assert!(code.contains("# [graphql (name = \"Ticket\")] async fn list_ticket"));
+        assert!(!code.contains("async fn list_project"));
+        assert!(code.contains("# [async_graphql :: Object (name = \"Query\")] impl TdbQuery"));
+        assert!(code.contains("pub fn model (& self) -> anyhow :: Result < my_crate :: Ticket >"));
+
+        // Scalars by shape, links through the loader.
+        assert!(
+            code.contains("async fn resolve_name (& self) -> async_graphql :: Result < String >")
+        );
+        assert!(code.contains(
+            "async fn resolve_budget (& self) -> async_graphql :: Result < Option < i32 > >"
+        ));
+        assert!(code.contains(
+            "async fn resolve_tags (& self) -> async_graphql :: Result < Vec < String > >"
+        ));
+        assert!(code.contains("async_graphql :: Result < ProjectNode >"));
+        assert!(code.contains("async_graphql :: Result < Vec < ProjectNode > >"));
+        assert!(code.contains(
+            "impl async_graphql :: dataloader :: Loader < String > for TdbDocumentLoader"
+        ));
+    }
+
+    #[test]
+    fn test_generate_subscriptions() {
+        let code = generate_resolvers(SDL, &[("Ticket", "my_crate::Ticket")]).unwrap();
+
+        assert!(code.contains(
+            "# [async_graphql :: Subscription (name = \"Subscription\")] impl TdbSubscription"
+        ));
+        assert!(
+            code.contains("# [graphql (name = \"onTicketChanged\")] async fn on_ticket_changed")
+        );
+        assert!(code.contains("tdb_changes :: < my_crate :: Ticket > (ctx)"));
+        assert!(code
+            .contains("# [graphql (complex , name = \"TicketChange\")] pub struct TicketChange"));
+        assert!(code.contains("async_graphql :: Result < Option < TicketNode >>"));
+        assert!(!code.contains("onProjectChanged"));
+        assert!(!code.contains("EmptySubscription"));
+    }
+
+    #[test]
+    fn test_generate_reference_resolvers() {
+        let plain = generate_resolvers(SDL, &[("Ticket", "my_crate::Ticket")]).unwrap();
+        assert!(!plain.contains("graphql (entity)"));
+        assert!(!plain.contains("enable_federation"));
+
+        let federated = format!(
+            "extend schema\n  @link(url: \"{}\", import: [\"@key\", \"@shareable\"])\n\n{}",
+            crate::FEDERATION_SPEC_URL,
+            SDL.replace("type Ticket {", "type Ticket @key(fields: \"_id\") {")
+        );
+        let code = generate_resolvers(&federated, &[("Ticket", "my_crate::Ticket")]).unwrap();
+        assert!(code.contains("# [graphql (entity)] async fn resolve_ticket_reference"));
+        assert!(code.contains("# [graphql (key , name = \"_id\")] id : async_graphql :: ID"));
+        assert!(code.contains(". enable_federation ()"));
+    }
+
+    #[test]
+    fn test_generate_used_helpers_only() {
+        let code = generate_resolvers(SDL, &[("Ticket", "my_crate::Ticket")]).unwrap();
+        assert!(!code.contains("allow (dead_code)"));
+        for helper in [
+            "tdb_run",
+            "tdb_changes",
+            "tdb_documents",
+            "tdb_loader",
+            "tdb_int",
+        ] {
+            assert!(
+                code.contains(&format!("fn {} ", helper)),
+                "{} missing",
+                helper
+            );
+        }
+        assert!(!code.contains("fn tdb_float"));
+        assert!(!code.contains("fn tdb_bool"));
+
+        // Without models there is no query or subscription to run.
+        let code = generate_resolvers(SDL, &[]).unwrap();
+        assert!(!code.contains("fn tdb_run"));
+        assert!(!code.contains("fn tdb_changes"));
+        assert!(code.contains("fn tdb_documents"));
+    }
+
+    #[test]
+    fn test_generate_resolvers_unknown_model() {
+        let err = generate_resolvers(SDL, &[("Missing", "my_crate::Missing")]).unwrap_err();
+        assert!(err.contains("Missing"));
+    }
+}
@@ -14,6 +14,7 @@ use terminusdb_community::graphql::naming::{
 };
 use terminusdb_schema::ToTDBSchemas;
 
+use crate::federation::EntityKeys;
 use crate::frames::schemas_to_allframes;
 
 /// Generate a GraphQL schema (SDL) from TerminusDB model definitions.
@@ -62,7 +63,7 @@ pub fn generate_gql_schema_unchecked<T: ToTDBSchemas>() -> String {
 /// `generate_gql_schema_unchecked` (which preserves the old behaviour),
 /// or — better — the live introspection path
 /// (`introspect_schema_for` under the `live` feature).
-fn validate_sdl_or_panic(sdl: &str) {
+pub(crate) fn validate_sdl_or_panic(sdl: &str) {
     use apollo_compiler::Schema;
     if let Err(e) = Schema::parse_and_validate(sdl, "generated.graphql") {
         // The SDL itself is what we want the developer to see, alongside
@@ -79,6 +80,17 @@ fn validate_sdl_or_panic(sdl: &str) {
 
 /// Generate SDL from AllFrames.
 pub fn allframes_to_sdl(frames: &AllFrames) -> String {
+    render_sdl(frames, None, false)
+}
+
+/// Generate SDL from AllFrames, with `@key` directives on the entity types
+/// when federation keys are given, and with Relay connection types and query
+/// fields when `connections` is set.
+pub(crate) fn render_sdl(
+    frames: &AllFrames,
+    entity_keys: Option<&EntityKeys>,
+    connections: bool,
+) -> String {
     let mut output = String::new();
     let mut used_base_filters: HashSet<String> = HashSet::new();
 
@@ -119,7 +131,10 @@ pub fn allframes_to_sdl(frames: &AllFrames) -> String {
     output.push_str("# Object Types\n");
     for (name, typedef) in &frames.frames {
         if let TypeDefinition::Class(c) = typedef {
-            output.push_str(&format!("type {} {{\n", name.as_str()));
+            let directives = entity_keys
+                .map(|keys| keys.directives(name.as_str()))
+                .unwrap_or_default();
+            output.push_str(&format!("type {}{} {{\n", name.as_str(), directives));
             output.push_str("  _id: ID!\n");
             output.push_str("  _type: ID!\n");
             output.push_str("  _json: JSON\n");
@@ -133,6 +148,11 @@ pub fn allframes_to_sdl(frames: &AllFrames) -> String {
         }
     }
 
+    if connections {
+        output.push_str("# Connection Types\n");
+        output.push_str(&generate_connection_types(frames));
+    }
+
     // Generate base filter types (only the ones we actually use)
     output.push_str("# Base Filter Input Types\n");
     output.push_str(&generate_base_filter_types(&used_base_filters));
@@ -197,6 +217,15 @@ pub fn allframes_to_sdl(frames: &AllFrames) -> String {
                 name.as_str(),
                 name.as_str()
             ));
+            if connections {
+                output.push_str(&format!(
+                    "  {}Connection(first: Int, after: String, last: Int, before: String, filter: {}, orderBy: {}_Ordering): {}Connection!\n",
+                    name.as_str(),
+                    filter_type.as_str(),
+                    name.as_str(),
+                    name.as_str()
+                ));
+            }
         }
     }
 
@@ -224,6 +253,34 @@ pub fn allframes_to_sdl(frames: &AllFrames) -> String {
     output
 }
 
+/// Generate the Relay `PageInfo` type, and a `{Type}Connection` and
+/// `{Type}Edge` type for every class.
+fn generate_connection_types(frames: &AllFrames) -> String {
+    let mut output = String::new();
+    output.push_str("type PageInfo {\n");
+    output.push_str("  hasNextPage: Boolean!\n");
+    output.push_str("  hasPreviousPage: Boolean!\n");
+    output.push_str("  startCursor: String\n");
+    output.push_str("  endCursor: String\n");
+    output.push_str("}\n\n");
+
+    for (name, typedef) in &frames.frames {
+        if let TypeDefinition::Class(_) = typedef {
+            let name = name.as_str();
+            output.push_str(&format!("type {}Edge {{\n", name));
+            output.push_str("  cursor: String!\n");
+            output.push_str(&format!("  node: {}!\n", name));
+            output.push_str("}\n\n");
+            output.push_str(&format!("type {}Connection {{\n", name));
+            output.push_str(&format!("  edges: [{}Edge!]!\n", name));
+            output.push_str("  pageInfo: PageInfo!\n");
+            output.push_str("}\n\n");
+        }
+    }
+
+    output
+}
+
 /// Convert a field definition to SDL type string.
 fn field_type_to_sdl(
     field_def: &FieldDefinition,
@@ -0,0 +1,1230 @@
+//! Translation of GraphQL queries into WOQL.
+//!
+//! [`WoqlTranslator`] reads the SDL emitted by
+//! [`generate_gql_schema`](crate::generate_gql_schema) and turns GraphQL
+//! queries against it into an equivalent WOQL [`Query`]. This gives
+//! GraphQL-style filtering on servers where the GraphQL endpoint is disabled,
+//! and a starting point for queries that need WOQL-only constructs mixed in.
+//!
+//! ```ignore
+//! use terminusdb_gql::{generate_gql_schema, WoqlTranslator};
+//!
+//! let translator = WoqlTranslator::from_sdl(&generate_gql_schema::<(Project, Ticket)>())?;
+//! let query = translator.translate(r#"
+//!     query {
+//!         Ticket(filter: {title: {startsWith: "Bug"}}, orderBy: {title: Asc}, limit: 10) {
+//!             _id
+//!             title
+//!             project { name }
+//!         }
+//!     }
+//! "#)?;
+//! ```
+//!
+//! # Result shape
+//!
+//! The WOQL query returns the response flattened into rows. Every selected
+//! field is bound to a variable named after its path in the response, joined
+//! with `_`: the query above selects `Ticket` (the document itself, for
+//! `_id`), `Ticket_title` and `Ticket_project_name`. Nullable fields and
+//! collections are optional, and a collection gives one row per element.
+//! Enum values and references come back as IRIs.
+//!
+//! `limit`, `offset` and `orderBy` page through documents rather than rows.
+//! Several root fields in one query are translated into a disjunction, so
+//! every row belongs to one of them.
+//!
+//! # Limitations
+//!
+//! Only query operations are translated. The `_count` and `_getDocument`
+//! root fields have no translation, and nested fields accept a `filter` but
+//! no paging or ordering arguments. `allOfTerms` and `anyOfTerms` match
+//! terms as substrings. The orderings of one `orderBy` object are applied in
+//! alphabetical order of their fields.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use decimal_rs::Decimal;
+use graphql_parser::query::{
+    self as gql, Definition, Directive, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, TypeCondition,
+};
+use graphql_parser::schema::{self as sdl, TypeDefinition};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use terminusdb_schema::XSDAnySimpleType;
+use terminusdb_woql2::prelude::{
+    And, DataValue, Equals, Greater, Gte, Less, Limit, Lte, NodeValue, Not, Or, Order, OrderBy,
+    OrderTemplate, Query, ReadDocument, Regexp, Select, Start, Subsumption, Triple, True, Value,
+    WoqlOptional,
+};
+
+/// Why a GraphQL query could not be translated into WOQL
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TranslateError {
+    #[error("invalid SDL: {0}")]
+    Schema(String),
+
+    #[error("invalid GraphQL query: {0}")]
+    Syntax(String),
+
+    #[error("expected exactly one operation, found {0}")]
+    OperationCount(usize),
+
+    #[error("{0} operations have no WOQL translation")]
+    NotAQuery(&'static str),
+
+    #[error("'{type_name}' has no field '{field}'")]
+    UnknownField { type_name: String, field: String },
+
+    #[error("unknown type '{0}'")]
+    UnknownType(String),
+
+    #[error("fragment '{0}' is not defined")]
+    UnknownFragment(String),
+
+    #[error("variable '${0}' is not defined")]
+    UndefinedVariable(String),
+
+    #[error("field '{0}' needs a selection of subfields")]
+    MissingSelection(String),
+
+    #[error("field '{0}' has no subfields to select")]
+    UnexpectedSelection(String),
+
+    #[error("invalid value for '{argument}': {value}")]
+    InvalidValue { argument: String, value: String },
+
+    #[error("not supported: {0}")]
+    Unsupported(String),
+}
+
+/// The declared type of a field in the SDL
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldType {
+    /// name of the scalar, enum or object type
+    name: String,
+    list: bool,
+    non_null: bool,
+}
+
+impl FieldType {
+    fn from_sdl(ty: &sdl::Type<'_, String>) -> Self {
+        match ty {
+            sdl::Type::NamedType(name) => Self {
+                name: name.clone(),
+                list: false,
+                non_null: false,
+            },
+            sdl::Type::NonNullType(inner) => Self {
+                non_null: true,
+                ..Self::from_sdl(inner)
+            },
+            sdl::Type::ListType(inner) => Self {
+                list: true,
+                non_null: false,
+                ..Self::from_sdl(inner)
+            },
+        }
+    }
+}
+
+/// Translates GraphQL queries against a generated schema into WOQL; see the
+/// [module documentation](self)
+#[derive(Clone, Debug, Default)]
+pub struct WoqlTranslator {
+    /// fields of the object types, by type name
+    objects: BTreeMap<String, BTreeMap<String, FieldType>>,
+    enums: BTreeSet<String>,
+    /// root query fields, with the object type they return
+    roots: BTreeMap<String, FieldType>,
+}
+
+impl WoqlTranslator {
+    /// Read the object, enum and root query types of an SDL document, such as
+    /// the output of [`generate_gql_schema`](crate::generate_gql_schema)
+    pub fn from_sdl(sdl: &str) -> Result<Self, TranslateError> {
+        let doc =
+            sdl::parse_schema::<String>(sdl).map_err(|e| TranslateError::Schema(e.to_string()))?;
+
+        let mut translator = Self::default();
+        for def in &doc.definitions {
+            match def {
+                sdl::Definition::TypeDefinition(TypeDefinition::Object(object)) => {
+                    let fields = object
+                        .fields
+                        .iter()
+                        .map(|field| (field.name.clone(), FieldType::from_sdl(&field.field_type)))
+                        .collect();
+                    if object.name == "Query" {
+                        translator.roots = fields;
+                    } else {
+                        translator.objects.insert(object.name.clone(), fields);
+                    }
+                }
+                sdl::Definition::TypeDefinition(TypeDefinition::Enum(e)) => {
+                    translator.enums.insert(e.name.clone());
+                }
+                _ => {}
+            }
+        }
+        Ok(translator)
+    }
+
+    /// Translate a query without variables
+    pub fn translate(&self, query: &str) -> Result<Query, TranslateError> {
+        self.translate_with_variables(query, &JsonValue::Null)
+    }
+
+    /// Translate a query, resolving its `$variables` from a JSON object as it
+    /// would be sent alongside the query to a GraphQL endpoint
+    pub fn translate_with_variables(
+        &self,
+        query: &str,
+        variables: &JsonValue,
+    ) -> Result<Query, TranslateError> {
+        let doc =
+            gql::parse_query::<String>(query).map_err(|e| TranslateError::Syntax(e.to_string()))?;
+
+        let mut fragments = HashMap::new();
+        let mut operations = Vec::new();
+        for def in &doc.definitions {
+            match def {
+                Definition::Fragment(fragment) => {
+                    fragments.insert(fragment.name.as_str(), fragment);
+                }
+                Definition::Operation(operation) => operations.push(operation),
+            }
+        }
+        if operations.len() != 1 {
+            return Err(TranslateError::OperationCount(operations.len()));
+        }
+
+        let mut variables = match variables {
+            JsonValue::Null => Map::new(),
+            JsonValue::Object(variables) => variables.clone(),
+            other => {
+                return Err(TranslateError::InvalidValue {
+                    argument: "variables".to_string(),
+                    value: other.to_string(),
+                })
+            }
+        };
+        let selection_set = match operations[0] {
+            OperationDefinition::SelectionSet(selection_set) => selection_set,
+            OperationDefinition::Query(query) => {
+                for definition in &query.variable_definitions {
+                    if let Some(default) = &definition.default_value {
+                        if !variables.contains_key(&definition.name) {
+                            let default = resolve(default, &Map::new())?;
+                            variables.insert(definition.name.clone(), default);
+                        }
+                    }
+                }
+                &query.selection_set
+            }
+            OperationDefinition::Mutation(_) => return Err(TranslateError::NotAQuery("mutation")),
+            OperationDefinition::Subscription(_) => {
+                return Err(TranslateError::NotAQuery("subscription"))
+            }
+        };
+
+        Translation {
+            translator: self,
+            fragments,
+            variables,
+            selected: Vec::new(),
+            fresh: 0,
+        }
+        .operation(selection_set)
+    }
+
+    fn field_type(&self, type_name: &str, field: &str) -> Result<&FieldType, TranslateError> {
+        self.objects
+            .get(type_name)
+            .ok_or_else(|| TranslateError::UnknownType(type_name.to_string()))?
+            .get(field)
+            .ok_or_else(|| TranslateError::UnknownField {
+                type_name: type_name.to_string(),
+                field: field.to_string(),
+            })
+    }
+}
+
+/// Translate a GraphQL query against `sdl` into WOQL; see [`WoqlTranslator`]
+pub fn graphql_to_woql(sdl: &str, query: &str) -> Result<Query, TranslateError> {
+    WoqlTranslator::from_sdl(sdl)?.translate(query)
+}
+
+/// State of the translation of one operation
+struct Translation<'a, 'q> {
+    translator: &'a WoqlTranslator,
+    fragments: HashMap<&'a str, &'a FragmentDefinition<'q, String>>,
+    variables: Map<String, JsonValue>,
+    /// variables of the selected fields, in response order
+    selected: Vec<String>,
+    /// counter for internal variables
+    fresh: usize,
+}
+
+impl<'a, 'q> Translation<'a, 'q> {
+    fn operation(
+        mut self,
+        selection_set: &SelectionSet<'q, String>,
+    ) -> Result<Query, TranslateError> {
+        let mut roots = Vec::new();
+        for item in &selection_set.items {
+            let Selection::Field(field) = item else {
+                return Err(TranslateError::Unsupported(
+                    "fragments on the query root".to_string(),
+                ));
+            };
+            if field.name != "__typename" && self.included(&field.directives)? {
+                roots.push(self.root_field(field)?);
+            }
+        }
+
+        let query = match roots.len() {
+            1 => roots.pop().unwrap(),
+            _ => Query::Or(Or { or: roots }),
+        };
+        Ok(Query::Select(Select {
+            variables: self.selected,
+            query: Box::new(query),
+        }))
+    }
+
+    /// Documents of the root field's type that pass its arguments, paged, and
+    /// the bindings of the selected fields
+    fn root_field(&mut self, field: &gql::Field<'q, String>) -> Result<Query, TranslateError> {
+        let type_name = match self.translator.roots.get(&field.name) {
+            Some(ty) if self.translator.objects.contains_key(&ty.name) => ty.name.clone(),
+            Some(_) => {
+                return Err(TranslateError::Unsupported(format!(
+                    "root field '{}'",
+                    field.name
+                )))
+            }
+            None => {
+                return Err(TranslateError::UnknownField {
+                    type_name: "Query".to_string(),
+                    field: field.name.clone(),
+                })
+            }
+        };
+        let subject = response_name(field).to_string();
+        let arguments = self.arguments(field)?;
+
+        let mut documents = Vec::new();
+        let include_children = match arguments.get("include_children") {
+            None | Some(JsonValue::Null) => true,
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| invalid("include_children", value))?,
+        };
+        if include_children {
+            let class = self.fresh_variable(&subject);
+            documents.push(triple(&subject, "rdf:type", Value::Variable(class.clone())));
+            documents.push(Query::Subsumption(Subsumption {
+                child: NodeValue::Variable(class),
+                parent: NodeValue::Node(schema_iri(&type_name)),
+            }));
+        } else {
+            documents.push(triple(
+                &subject,
+                "rdf:type",
+                Value::Node(schema_iri(&type_name)),
+            ));
+        }
+        if let Some(id) = arguments.get("id").filter(|id| !id.is_null()) {
+            documents.push(id_is(&subject, id, "id")?);
+        }
+        if let Some(ids) = arguments.get("ids").filter(|ids| !ids.is_null()) {
+            documents.push(id_in(&subject, ids, "ids")?);
+        }
+        if let Some(filter) = arguments.get("filter").filter(|filter| !filter.is_null()) {
+            documents.push(self.filter(&subject, &type_name, filter)?);
+        }
+
+        let mut ordering = Vec::new();
+        if let Some(order_by) = arguments.get("orderBy").filter(|order| !order.is_null()) {
+            let order_by = order_by
+                .as_object()
+                .ok_or_else(|| invalid("orderBy", order_by))?;
+            for (name, direction) in order_by {
+                let ty = self.translator.field_type(&type_name, name)?;
+                if ty.list || self.translator.objects.contains_key(&ty.name) {
+                    return Err(invalid("orderBy", direction));
+                }
+                let order = match direction.as_str().map(str::to_ascii_lowercase).as_deref() {
+                    Some("asc") => Order::Asc,
+                    Some("desc") => Order::Desc,
+                    _ => return Err(invalid("orderBy", direction)),
+                };
+                let value = self.fresh_variable(&subject);
+                documents.push(optional(triple(
+                    &subject,
+                    &schema_iri(name),
+                    Value::Variable(value.clone()),
+                )));
+                ordering.push(OrderTemplate {
+                    variable: value,
+                    order,
+                });
+            }
+        }
+
+        let mut documents = and(documents);
+        if !ordering.is_empty() {
+            documents = Query::OrderBy(OrderBy {
+                ordering,
+                query: Box::new(documents),
+            });
+        }
+        if let Some(offset) = arguments.get("offset").filter(|offset| !offset.is_null()) {
+            documents = Query::Start(Start {
+                start: offset.as_u64().ok_or_else(|| invalid("offset", offset))?,
+                query: Box::new(documents),
+            });
+        }
+        if let Some(limit) = arguments.get("limit").filter(|limit| !limit.is_null()) {
+            documents = Query::Limit(Limit {
+                limit: limit.as_u64().ok_or_else(|| invalid("limit", limit))?,
+                query: Box::new(documents),
+            });
+        }
+
+        if field.selection_set.items.is_empty() {
+            return Err(TranslateError::MissingSelection(field.name.clone()));
+        }
+        let mut parts = vec![documents];
+        self.selection(&subject, &type_name, &field.selection_set, &mut parts)?;
+        Ok(and(parts))
+    }
+
+    /// Add the bindings of the fields selected from `subject` to `parts`
+    fn selection(
+        &mut self,
+        subject: &str,
+        type_name: &str,
+        selection_set: &SelectionSet<'q, String>,
+        parts: &mut Vec<Query>,
+    ) -> Result<(), TranslateError> {
+        for item in &selection_set.items {
+            match item {
+                Selection::Field(field) => {
+                    if self.included(&field.directives)? {
+                        self.selected_field(subject, type_name, field, parts)?;
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if self.included(&spread.directives)? {
+                        let fragment = *self
+                            .fragments
+                            .get(spread.fragment_name.as_str())
+                            .ok_or_else(|| {
+                                TranslateError::UnknownFragment(spread.fragment_name.clone())
+                            })?;
+                        let TypeCondition::On(condition) = &fragment.type_condition;
+                        self.fragment(
+                            subject,
+                            type_name,
+                            condition,
+                            &fragment.selection_set,
+                            parts,
+                        )?;
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    if self.included(&fragment.directives)? {
+                        let condition = match &fragment.type_condition {
+                            Some(TypeCondition::On(condition)) => condition.as_str(),
+                            None => type_name,
+                        };
+                        self.fragment(
+                            subject,
+                            type_name,
+                            condition,
+                            &fragment.selection_set,
+                            parts,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A fragment on a subclass only binds its fields for documents of that
+    /// subclass
+    fn fragment(
+        &mut self,
+        subject: &str,
+        type_name: &str,
+        condition: &str,
+        selection_set: &SelectionSet<'q, String>,
+        parts: &mut Vec<Query>,
+    ) -> Result<(), TranslateError> {
+        if condition == type_name {
+            return self.selection(subject, type_name, selection_set, parts);
+        }
+        if !self.translator.objects.contains_key(condition) {
+            return Err(TranslateError::UnknownType(condition.to_string()));
+        }
+        let mut inner = vec![triple(
+            subject,
+            "rdf:type",
+            Value::Node(schema_iri(condition)),
+        )];
+        self.selection(subject, condition, selection_set, &mut inner)?;
+        parts.push(optional(and(inner)));
+        Ok(())
+    }
+
+    fn selected_field(
+        &mut self,
+        subject: &str,
+        type_name: &str,
+        field: &gql::Field<'q, String>,
+        parts: &mut Vec<Query>,
+    ) -> Result<(), TranslateError> {
+        let variable = format!("{}_{}", subject, response_name(field));
+        match field.name.as_str() {
+            "__typename" => return Ok(()),
+            "_id" => {
+                self.select(subject);
+                return Ok(());
+            }
+            "_type" => {
+                parts.push(triple(
+                    subject,
+                    "rdf:type",
+                    Value::Variable(variable.clone()),
+                ));
+                self.select(&variable);
+                return Ok(());
+            }
+            "_json" => {
+                parts.push(Query::ReadDocument(ReadDocument {
+                    identifier: NodeValue::Variable(subject.to_string()),
+                    document: Value::Variable(variable.clone()),
+                }));
+                self.select(&variable);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let ty = self.translator.field_type(type_name, &field.name)?.clone();
+        let arguments = self.arguments(field)?;
+        if let Some(argument) = arguments.keys().find(|name| *name != "filter") {
+            return Err(TranslateError::Unsupported(format!(
+                "argument '{}' of nested field '{}'",
+                argument, field.name
+            )));
+        }
+
+        let mut bindings = vec![triple(
+            subject,
+            &schema_iri(&field.name),
+            Value::Variable(variable.clone()),
+        )];
+        if self.translator.objects.contains_key(&ty.name) {
+            if field.selection_set.items.is_empty() {
+                return Err(TranslateError::MissingSelection(field.name.clone()));
+            }
+            if let Some(filter) = arguments.get("filter").filter(|filter| !filter.is_null()) {
+                bindings.push(self.filter(&variable, &ty.name, filter)?);
+            }
+            self.selection(&variable, &ty.name, &field.selection_set, &mut bindings)?;
+        } else {
+            if !field.selection_set.items.is_empty() {
+                return Err(TranslateError::UnexpectedSelection(field.name.clone()));
+            }
+            if arguments.contains_key("filter") {
+                return Err(TranslateError::Unsupported(format!(
+                    "filter on field '{}' of type {}",
+                    field.name, ty.name
+                )));
+            }
+            self.select(&variable);
+        }
+
+        if ty.non_null && !ty.list {
+            parts.extend(bindings);
+        } else {
+            parts.push(optional(and(bindings)));
+        }
+        Ok(())
+    }
+
+    /// Constraint on `subject`, a document of type `type_name`, from a
+    /// `<Type>_Filter` value
+    fn filter(
+        &mut self,
+        subject: &str,
+        type_name: &str,
+        filter: &JsonValue,
+    ) -> Result<Query, TranslateError> {
+        let object = filter
+            .as_object()
+            .ok_or_else(|| invalid(&format!("{}_Filter", type_name), filter))?;
+
+        let mut parts = Vec::new();
+        for (key, value) in object.iter().filter(|(_, value)| !value.is_null()) {
+            let part = match key.as_str() {
+                "_id" => id_is(subject, value, key)?,
+                "_ids" => id_in(subject, value, key)?,
+                "_and" | "_or" => {
+                    let operands = value
+                        .as_array()
+                        .ok_or_else(|| invalid(key, value))?
+                        .iter()
+                        .map(|operand| self.filter(subject, type_name, operand))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if key == "_and" {
+                        and(operands)
+                    } else {
+                        Query::Or(Or { or: operands })
+                    }
+                }
+                "_not" => not(self.filter(subject, type_name, value)?),
+                field => {
+                    let ty = self.translator.field_type(type_name, field)?.clone();
+                    self.field_filter(subject, field, &ty, value)?
+                }
+            };
+            parts.push(part);
+        }
+        Ok(and(parts))
+    }
+
+    /// Constraint on the values of `field` of `subject`. A collection field is
+    /// filtered with `someHave` and `allHave`, which apply the element filter
+    /// to some or all of its values.
+    fn field_filter(
+        &mut self,
+        subject: &str,
+        field: &str,
+        ty: &FieldType,
+        filter: &JsonValue,
+    ) -> Result<Query, TranslateError> {
+        let predicate = schema_iri(field);
+        if !ty.list {
+            let value = self.fresh_variable(subject);
+            return Ok(and(vec![
+                triple(subject, &predicate, Value::Variable(value.clone())),
+                self.value_filter(&value, field, ty, filter)?,
+            ]));
+        }
+
+        let object = filter.as_object().ok_or_else(|| invalid(field, filter))?;
+        let mut parts = Vec::new();
+        for (quantifier, filter) in object.iter().filter(|(_, value)| !value.is_null()) {
+            let element = self.fresh_variable(subject);
+            let has_element = triple(subject, &predicate, Value::Variable(element.clone()));
+            let element_filter = self.value_filter(&element, field, ty, filter)?;
+            parts.push(match quantifier.as_str() {
+                "someHave" => and(vec![has_element, element_filter]),
+                "allHave" => not(and(vec![has_element, not(element_filter)])),
+                _ => return Err(invalid(field, filter)),
+            });
+        }
+        Ok(and(parts))
+    }
+
+    /// Constraint on a single value of `field`, bound to `value`
+    fn value_filter(
+        &mut self,
+        value: &str,
+        field: &str,
+        ty: &FieldType,
+        filter: &JsonValue,
+    ) -> Result<Query, TranslateError> {
+        if self.translator.objects.contains_key(&ty.name) {
+            return self.filter(value, &ty.name, filter);
+        }
+        let object = filter.as_object().ok_or_else(|| invalid(field, filter))?;
+
+        let mut parts = Vec::new();
+        for (operator, operand) in object.iter().filter(|(_, operand)| !operand.is_null()) {
+            let part = if ty.name == "ID" {
+                match operator.as_str() {
+                    "_id" => id_is(value, operand, field)?,
+                    "_ids" => id_in(value, operand, field)?,
+                    _ => return Err(invalid(field, filter)),
+                }
+            } else if self.translator.enums.contains(&ty.name) {
+                let member = operand.as_str().ok_or_else(|| invalid(field, operand))?;
+                let is_member = Query::Equals(Equals {
+                    left: Value::Variable(value.to_string()),
+                    right: Value::Node(format!("@schema:{}/{}", ty.name, member)),
+                });
+                match operator.as_str() {
+                    "eq" => is_member,
+                    "ne" => not(is_member),
+                    _ => return Err(invalid(field, filter)),
+                }
+            } else {
+                scalar_comparison(value, field, &ty.name, operator, operand)?
+            };
+            parts.push(part);
+        }
+        Ok(and(parts))
+    }
+
+    /// Argument values of a field, with variables resolved
+    fn arguments(
+        &self,
+        field: &gql::Field<'q, String>,
+    ) -> Result<Map<String, JsonValue>, TranslateError> {
+        field
+            .arguments
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), self.resolve(value)?)))
+            .collect()
+    }
+
+    /// Whether `@skip` and `@include` directives leave a selection in
+    fn included(&self, directives: &[Directive<'q, String>]) -> Result<bool, TranslateError> {
+        for directive in directives {
+            let skip_if = match directive.name.as_str() {
+                "skip" => true,
+                "include" => false,
+                _ => continue,
+            };
+            let condition = directive
+                .arguments
+                .iter()
+                .find(|(name, _)| name == "if")
+                .map(|(_, value)| self.resolve(value))
+                .transpose()?;
+            match condition.as_ref().and_then(JsonValue::as_bool) {
+                Some(condition) if condition == skip_if => return Ok(false),
+                Some(_) => {}
+                None => {
+                    return Err(invalid(
+                        &format!("@{}", directive.name),
+                        condition.as_ref().unwrap_or(&JsonValue::Null),
+                    ))
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn resolve(&self, value: &gql::Value<'q, String>) -> Result<JsonValue, TranslateError> {
+        resolve(value, &self.variables)
+    }
+
+    fn select(&mut self, variable: &str) {
+        if !self.selected.iter().any(|selected| selected == variable) {
+            self.selected.push(variable.to_string());
+        }
+    }
+
+    /// A variable that doesn't clash with those of the selected fields, which
+    /// never have a digit after a `_`
+    fn fresh_variable(&mut self, subject: &str) -> String {
+        self.fresh += 1;
+        format!("{}__{}", subject, self.fresh)
+    }
+}
+
+/// A GraphQL input value as JSON, with variables taken from `variables`
+fn resolve(
+    value: &gql::Value<'_, String>,
+    variables: &Map<String, JsonValue>,
+) -> Result<JsonValue, TranslateError> {
+    Ok(match value {
+        gql::Value::Variable(name) => variables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| TranslateError::UndefinedVariable(name.clone()))?,
+        gql::Value::Int(number) => match number.as_i64() {
+            Some(number) => JsonValue::from(number),
+            None => {
+                return Err(TranslateError::InvalidValue {
+                    argument: "Int".to_string(),
+                    value: format!("{:?}", number),
+                })
+            }
+        },
+        gql::Value::Float(number) => JsonValue::from(*number),
+        gql::Value::String(string) => JsonValue::String(string.clone()),
+        gql::Value::Boolean(boolean) => JsonValue::Bool(*boolean),
+        gql::Value::Null => JsonValue::Null,
+        gql::Value::Enum(name) => JsonValue::String(name.clone()),
+        gql::Value::List(items) => JsonValue::Array(
+            items
+                .iter()
+                .map(|item| resolve(item, variables))
+                .collect::<Result<_, _>>()?,
+        ),
+        gql::Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), resolve(value, variables)?)))
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
+
+/// Comparison of the scalar bound to `value` with an operand of a
+/// `<Scalar>Filter`
+fn scalar_comparison(
+    value: &str,
+    field: &str,
+    scalar: &str,
+    operator: &str,
+    operand: &JsonValue,
+) -> Result<Query, TranslateError> {
+    let variable = || DataValue::Variable(value.to_string());
+    let matches = |pattern: String| {
+        Query::Regexp(Regexp {
+            pattern: DataValue::Data(XSDAnySimpleType::String(pattern)),
+            string: variable(),
+            result: None,
+        })
+    };
+    let terms = || -> Result<Vec<String>, TranslateError> {
+        operand
+            .as_array()
+            .filter(|_| scalar == "String")
+            .ok_or_else(|| invalid(field, operand))?
+            .iter()
+            .map(|term| {
+                term.as_str()
+                    .map(escape_regex)
+                    .ok_or_else(|| invalid(field, term))
+            })
+            .collect()
+    };
+
+    Ok(match operator {
+        "eq" | "ne" => {
+            let equals = Query::Equals(Equals {
+                left: Value::Variable(value.to_string()),
+                right: Value::Data(literal(field, scalar, operand)?),
+            });
+            if operator == "eq" {
+                equals
+            } else {
+                not(equals)
+            }
+        }
+        "lt" | "le" | "gt" | "ge" => {
+            let (left, right) = (
+                variable(),
+                DataValue::Data(literal(field, scalar, operand)?),
+            );
+            match operator {
+                "lt" => Query::Less(Less { left, right }),
+                "le" => Query::Lte(Lte { left, right }),
+                "gt" => Query::Greater(Greater { left, right }),
+                _ => Query::Gte(Gte { left, right }),
+            }
+        }
+        "regex" | "startsWith" if scalar == "String" => {
+            let text = operand.as_str().ok_or_else(|| invalid(field, operand))?;
+            if operator == "regex" {
+                matches(text.to_string())
+            } else {
+                matches(format!("^{}", escape_regex(text)))
+            }
+        }
+        "allOfTerms" => and(terms()?.into_iter().map(&matches).collect()),
+        "anyOfTerms" => Query::Or(Or {
+            or: terms()?.into_iter().map(&matches).collect(),
+        }),
+        _ => return Err(invalid(field, &JsonValue::String(operator.to_string()))),
+    })
+}
+
+/// A filter operand as a literal of the field's scalar type
+fn literal(
+    field: &str,
+    scalar: &str,
+    operand: &JsonValue,
+) -> Result<XSDAnySimpleType, TranslateError> {
+    let text = operand.as_str();
+    let literal = match scalar {
+        "String" | "ID" => text.map(|text| XSDAnySimpleType::String(text.to_string())),
+        "Boolean" => operand.as_bool().map(XSDAnySimpleType::Boolean),
+        "Int" => operand.as_i64().map(XSDAnySimpleType::Integer),
+        // BigInt and BigFloat values are sent as strings to keep their precision
+        "BigInt" => operand
+            .as_i64()
+            .or_else(|| text.and_then(|text| text.parse().ok()))
+            .map(XSDAnySimpleType::Integer),
+        "Float" => operand.as_f64().map(XSDAnySimpleType::Float),
+        "BigFloat" => text
+            .map(str::to_string)
+            .or_else(|| operand.is_number().then(|| operand.to_string()))
+            .and_then(|text| text.parse::<Decimal>().ok())
+            .map(XSDAnySimpleType::Decimal),
+        "DateTime" => text.and_then(|text| {
+            DateTime::parse_from_rfc3339(text)
+                .map(|time| XSDAnySimpleType::DateTime(time.with_timezone(&Utc)))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(text, "%Y-%m-%d").map(XSDAnySimpleType::Date)
+                })
+                .ok()
+        }),
+        _ => {
+            return Err(TranslateError::Unsupported(format!(
+                "filter on field '{}' of type {}",
+                field, scalar
+            )))
+        }
+    };
+    literal.ok_or_else(|| invalid(field, operand))
+}
+
+fn response_name<'a>(field: &'a gql::Field<'_, String>) -> &'a str {
+    field.alias.as_deref().unwrap_or(&field.name)
+}
+
+fn schema_iri(name: &str) -> String {
+    format!("@schema:{}", name)
+}
+
+fn triple(subject: &str, predicate: &str, object: Value) -> Query {
+    Query::Triple(Triple {
+        subject: NodeValue::Variable(subject.to_string()),
+        predicate: NodeValue::Node(predicate.to_string()),
+        object,
+        graph: None,
+    })
+}
+
+fn and(mut parts: Vec<Query>) -> Query {
+    match parts.len() {
+        0 => Query::True(True {}),
+        1 => parts.pop().unwrap(),
+        _ => Query::And(And { and: parts }),
+    }
+}
+
+fn not(query: Query) -> Query {
+    Query::Not(Not {
+        query: Box::new(query),
+    })
+}
+
+fn optional(query: Query) -> Query {
+    Query::WoqlOptional(WoqlOptional {
+        query: Box::new(query),
+    })
+}
+
+/// `subject` is the document with the given id
+fn id_is(subject: &str, id: &JsonValue, argument: &str) -> Result<Query, TranslateError> {
+    let id = id.as_str().ok_or_else(|| invalid(argument, id))?;
+    Ok(Query::Equals(Equals {
+        left: Value::Variable(subject.to_string()),
+        right: Value::Node(id.to_string()),
+    }))
+}
+
+/// `subject` is one of the documents with the given ids
+fn id_in(subject: &str, ids: &JsonValue, argument: &str) -> Result<Query, TranslateError> {
+    let ids = ids.as_array().ok_or_else(|| invalid(argument, ids))?;
+    Ok(Query::Or(Or {
+        or: ids
+            .iter()
+            .map(|id| id_is(subject, id, argument))
+            .collect::<Result<_, _>>()?,
+    }))
+}
+
+fn invalid(argument: &str, value: &JsonValue) -> TranslateError {
+    TranslateError::InvalidValue {
+        argument: argument.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SDL: &str = r#"
+scalar JSON
+
+enum Status {
+  Open
+  Closed
+}
+
+type Project {
+  _id: ID!
+  _type: ID!
+  _json: JSON
+  name: String!
+}
+
+type Ticket {
+  _id: ID!
+  _type: ID!
+  _json: JSON
+  title: String!
+  number: Int!
+  status: Status
+  project: Project!
+  tags: [String!]!
+  subtasks: [Ticket!]!
+}
+
+type Query {
+  _getDocument(id: String!): JSON
+  Project(
+    id: ID
+    ids: [ID!]
+    include_children: Boolean
+    offset: Int
+    limit: Int
+    filter: Project_Filter
+    orderBy: Project_Ordering
+  ): [Project!]!
+  Ticket(
+    id: ID
+    ids: [ID!]
+    include_children: Boolean
+    offset: Int
+    limit: Int
+    filter: Ticket_Filter
+    orderBy: Ticket_Ordering
+  ): [Ticket!]!
+}
+"#;
+
+    fn translate(query: &str) -> Result<Query, TranslateError> {
+        graphql_to_woql(SDL, query)
+    }
+
+    /// The query and all queries nested in it
+    fn subqueries(query: &Query) -> Vec<&Query> {
+        let children: Vec<&Query> = match query {
+            Query::And(q) => q.and.iter().collect(),
+            Query::Or(q) => q.or.iter().collect(),
+            Query::Not(q) => vec![q.query.as_ref()],
+            Query::WoqlOptional(q) => vec![q.query.as_ref()],
+            Query::Select(q) => vec![q.query.as_ref()],
+            Query::Limit(q) => vec![q.query.as_ref()],
+            Query::Start(q) => vec![q.query.as_ref()],
+            Query::OrderBy(q) => vec![q.query.as_ref()],
+            _ => vec![],
+        };
+        std::iter::once(query)
+            .chain(children.into_iter().flat_map(subqueries))
+            .collect()
+    }
+
+    /// Variables bound as triple objects inside an optional
+    fn optional_variables(query: &Query) -> Vec<&str> {
+        subqueries(query)
+            .into_iter()
+            .filter_map(|q| match q {
+                Query::WoqlOptional(optional) => Some(subqueries(&optional.query)),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|q| match q {
+                Query::Triple(Triple {
+                    object: Value::Variable(variable),
+                    ..
+                }) => Some(variable.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn selected(query: &Query) -> Vec<&str> {
+        match query {
+            Query::Select(select) => select.variables.iter().map(String::as_str).collect(),
+            other => panic!("expected a select, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_selection_variables() {
+        let query =
+            translate("{ Ticket { _id title status project { name } tags subtasks { title } } }")
+                .unwrap();
+
+        assert_eq!(
+            selected(&query),
+            vec![
+                "Ticket",
+                "Ticket_title",
+                "Ticket_status",
+                "Ticket_project_name",
+                "Ticket_tags",
+                "Ticket_subtasks_title"
+            ]
+        );
+
+        let optional = optional_variables(&query);
+        for variable in ["Ticket_status", "Ticket_tags", "Ticket_subtasks_title"] {
+            assert!(optional.contains(&variable), "{} is optional", variable);
+        }
+        for variable in ["Ticket_title", "Ticket_project", "Ticket_project_name"] {
+            assert!(!optional.contains(&variable), "{} is required", variable);
+        }
+    }
+
+    #[test]
+    fn test_paging_wraps_the_documents() {
+        let query = translate(
+            r#"{ Ticket(filter: {title: {startsWith: "Bug (ui)"}, number: {gt: 3}},
+                        orderBy: {number: Desc}, offset: 5, limit: 10) { title } }"#,
+        )
+        .unwrap();
+
+        let Query::Select(select) = &query else {
+            unreachable!()
+        };
+        let Query::And(parts) = select.query.as_ref() else {
+            panic!("expected documents and selection, got {:?}", select.query)
+        };
+        let Query::Limit(limit) = &parts.and[0] else {
+            panic!("expected a limit, got {:?}", parts.and[0])
+        };
+        assert_eq!(limit.limit, 10);
+        let Query::Start(start) = limit.query.as_ref() else {
+            panic!("expected a start, got {:?}", limit.query)
+        };
+        assert_eq!(start.start, 5);
+        let Query::OrderBy(order_by) = start.query.as_ref() else {
+            panic!("expected an order by, got {:?}", start.query)
+        };
+        assert_eq!(order_by.ordering[0].order, Order::Desc);
+
+        let all = subqueries(&query);
+        assert!(all.iter().any(|q| matches!(q,
+            Query::Regexp(Regexp { pattern: DataValue::Data(XSDAnySimpleType::String(p)), .. })
+                if p == r"^Bug \(ui\)")));
+        assert!(all.iter().any(|q| matches!(
+            q,
+            Query::Greater(Greater {
+                right: DataValue::Data(XSDAnySimpleType::Integer(3)),
+                ..
+            })
+        )));
+        assert!(all.iter().any(|q| matches!(q, Query::Subsumption(_))));
+    }
+
+    #[test]
+    fn test_variables_and_fragments() {
+        let query = WoqlTranslator::from_sdl(SDL)
+            .unwrap()
+            .translate_with_variables(
+                r#"query Open($min: Int = 2, $status: Status, $withTags: Boolean!) {
+                    Ticket(
+                        include_children: false,
+                        filter: {number: {ge: $min}, status: {eq: $status}}
+                    ) {
+                        ...Fields
+                        tags @include(if: $withTags)
+                    }
+                }
+                fragment Fields on Ticket { label: title }"#,
+                &json!({"status": "Open", "withTags": false}),
+            )
+            .unwrap();
+
+        assert_eq!(selected(&query), vec!["Ticket_label"]);
+        let all = subqueries(&query);
+        assert!(all.iter().any(|q| matches!(
+            q,
+            Query::Gte(Gte {
+                right: DataValue::Data(XSDAnySimpleType::Integer(2)),
+                ..
+            })
+        )));
+        assert!(all.iter().any(|q| matches!(q,
+            Query::Equals(Equals { right: Value::Node(node), .. })
+                if node == "@schema:Status/Open")));
+        assert!(all.iter().any(|q| matches!(q,
+            Query::Triple(Triple { object: Value::Node(node), .. }) if node == "@schema:Ticket")));
+        assert!(!all.iter().any(|q| matches!(q, Query::Subsumption(_))));
+    }
+
+    #[test]
+    fn test_collection_and_logical_filters() {
+        let query = translate(
+            r#"{ Ticket(filter: {
+                    tags: {allHave: {regex: "^[a-z]+$"}},
+                    subtasks: {someHave: {_not: {status: {eq: Closed}}}},
+                    _or: [{_id: "Ticket/1"}, {project: {name: {eq: "apollo"}}}]
+                }) { _id } }"#,
+        )
+        .unwrap();
+
+        let all = subqueries(&query);
+        // allHave is a double negation, _not a single one
+        assert_eq!(all.iter().filter(|q| matches!(q, Query::Not(_))).count(), 3);
+        assert!(all.iter().any(|q| matches!(q,
+            Query::Equals(Equals { right: Value::Node(node), .. }) if node == "Ticket/1")));
+        assert!(all.iter().any(|q| matches!(q,
+            Query::Triple(Triple { predicate: NodeValue::Node(p), .. }) if p == "@schema:name")));
+    }
+
+    #[test]
+    fn test_multiple_roots_are_alternatives() {
+        let query = translate("{ open: Ticket { title } Project { name } }").unwrap();
+
+        assert_eq!(selected(&query), vec!["open_title", "Project_name"]);
+        let Query::Select(select) = &query else {
+            unreachable!()
+        };
+        assert!(matches!(select.query.as_ref(), Query::Or(or) if or.or.len() == 2));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            translate("{ Ticket { owner } }"),
+            Err(TranslateError::UnknownField {
+                type_name: "Ticket".to_string(),
+                field: "owner".to_string()
+            })
+        );
+        assert_eq!(
+            translate("{ Ticket { title { x } } }"),
+            Err(TranslateError::UnexpectedSelection("title".to_string()))
+        );
+        assert_eq!(
+            translate("{ Ticket { project } }"),
+            Err(TranslateError::MissingSelection("project".to_string()))
+        );
+        assert_eq!(
+            translate("query ($n: Int) { Ticket(limit: $m) { title } }"),
+            Err(TranslateError::UndefinedVariable("m".to_string()))
+        );
+        assert_eq!(
+            translate("mutation { Ticket { title } }"),
+            Err(TranslateError::NotAQuery("mutation"))
+        );
+        assert!(matches!(
+            translate(r#"{ _getDocument(id: "Ticket/1") }"#),
+            Err(TranslateError::Unsupported(_))
+        ));
+        assert!(matches!(
+            translate(r#"{ Ticket(filter: {number: {eq: "three"}}) { title } }"#),
+            Err(TranslateError::InvalidValue { .. })
+        ));
+    }
+}
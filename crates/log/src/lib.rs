@@ -0,0 +1,215 @@
+//! # terminusdb-log — tracing targets and filter setup
+//!
+//! Every crate in this workspace emits [`tracing`] events under its own module
+//! path, so an event's target says which layer produced it
+//! (`terminusdb_client::http::document`, `terminusdb_orm::resolver`, ...).
+//! [`targets`] lists the ones worth filtering on, and [`LogConfig`] turns a
+//! handful of per-target levels into a subscriber:
+//!
+//! ```rust,no_run
+//! use terminusdb_log::{targets, LogConfig};
+//! use tracing::Level;
+//!
+//! // Everything at `warn`, our crates at `info`, HTTP traffic at `debug`.
+//! LogConfig::new()
+//!     .level(targets::CLIENT_HTTP, Level::DEBUG)
+//!     .init();
+//! ```
+//!
+//! Operators can adjust any of this without a rebuild through
+//! [`ENV_VAR`] (`TERMINUSDB_LOG`), or `RUST_LOG` when that is unset. Both take
+//! the usual `EnvFilter` syntax and are applied after the configured levels, so
+//! `TERMINUSDB_LOG=terminusdb_orm::resolver=trace` turns up just the resolver
+//! while `TERMINUSDB_LOG=error` silences everything below `error`.
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable read for extra filter directives.
+pub const ENV_VAR: &str = "TERMINUSDB_LOG";
+
+/// Tracing targets for each subsystem.
+///
+/// Targets match by prefix, so a directive for [`CLIENT`] also covers
+/// [`CLIENT_HTTP`], and [`ALL`] covers every `terminusdb_*` crate.
+pub mod targets {
+    /// Every crate in the workspace
+    pub const ALL: &str = "terminusdb";
+
+    /// The HTTP client crate as a whole
+    pub const CLIENT: &str = "terminusdb_client";
+    /// HTTP requests, responses and retries
+    pub const CLIENT_HTTP: &str = "terminusdb_client::http";
+    /// The server-sent change event stream
+    pub const CLIENT_CHANGES: &str = "terminusdb_client::http::sse_manager";
+    /// Change listeners dispatching changesets to their handlers
+    pub const CLIENT_CHANGE_LISTENER: &str = "terminusdb_client::http::change_listener";
+    /// Commit-log polling, the change transport used when SSE is unavailable
+    pub const CLIENT_CHANGE_POLLING: &str = "terminusdb_client::http::change_transport";
+    /// Client-side query logging
+    pub const CLIENT_QUERY_LOG: &str = "terminusdb_client::debug";
+
+    /// Schema derivation and instance (de)serialization
+    pub const SCHEMA: &str = "terminusdb_schema";
+
+    /// The ORM crate as a whole
+    pub const ORM: &str = "terminusdb_orm";
+    /// Relation query generation
+    pub const ORM_RESOLVER: &str = "terminusdb_orm::resolver";
+    /// Batched multi-type document fetches
+    pub const ORM_FETCH: &str = "terminusdb_orm::multi_fetch";
+
+    /// Embedded server binary management
+    pub const SERVER: &str = "terminusdb_bin";
+    /// In-process GraphQL engine
+    pub const GQL: &str = "terminusdb_gql";
+    /// XSD schema parsing
+    pub const XSD: &str = "terminusdb_xsd";
+}
+
+/// Per-target log levels, turned into an `EnvFilter`.
+///
+/// The default is `warn` for everything and `info` for [`targets::ALL`], with
+/// the environment overrides described in the [crate docs](crate) enabled.
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    default_level: LevelFilter,
+    levels: Vec<(String, LevelFilter)>,
+    from_env: bool,
+    with_target: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            default_level: LevelFilter::WARN,
+            levels: vec![(targets::ALL.to_string(), LevelFilter::INFO)],
+            from_env: true,
+            with_target: true,
+        }
+    }
+}
+
+impl LogConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Level for events that no target-specific level matches.
+    pub fn default_level(mut self, level: impl Into<LevelFilter>) -> Self {
+        self.default_level = level.into();
+        self
+    }
+
+    /// Level for `target` and everything below it, e.g. [`targets::CLIENT_HTTP`].
+    /// Setting the same target twice keeps the last level.
+    pub fn level(mut self, target: impl Into<String>, level: impl Into<LevelFilter>) -> Self {
+        let target = target.into();
+        self.levels.retain(|(existing, _)| *existing != target);
+        self.levels.push((target, level.into()));
+        self
+    }
+
+    /// Whether [`ENV_VAR`] / `RUST_LOG` are applied on top (default: true).
+    pub fn from_env(mut self, enabled: bool) -> Self {
+        self.from_env = enabled;
+        self
+    }
+
+    /// Whether log lines include the event target (default: true).
+    pub fn with_target(mut self, enabled: bool) -> Self {
+        self.with_target = enabled;
+        self
+    }
+
+    /// The filter directives this config resolves to, environment included.
+    ///
+    /// A bare level in the environment (`TERMINUSDB_LOG=debug`) replaces the
+    /// configured levels altogether; targeted directives are added on top.
+    pub fn directives(&self) -> String {
+        let mut default = level_name(self.default_level);
+        let mut targeted: Vec<String> = self
+            .levels
+            .iter()
+            .map(|(target, level)| format!("{}={}", target, level_name(*level)))
+            .collect();
+
+        if self.from_env {
+            let env = std::env::var(ENV_VAR).or_else(|_| std::env::var("RUST_LOG"));
+            for directive in env.iter().flat_map(|env| env.split(',')) {
+                let directive = directive.trim();
+                if directive.is_empty() {
+                    continue;
+                }
+                if directive.parse::<LevelFilter>().is_ok() {
+                    default = directive.to_lowercase();
+                    targeted.clear();
+                } else {
+                    targeted.push(directive.to_string());
+                }
+            }
+        }
+
+        std::iter::once(default)
+            .chain(targeted)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Build the filter; invalid directives from the environment are ignored.
+    pub fn filter(&self) -> EnvFilter {
+        EnvFilter::new(self.directives())
+    }
+
+    /// Install a stderr `fmt` subscriber with this filter as the global default.
+    pub fn try_init(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        tracing_subscriber::fmt()
+            .with_env_filter(self.filter())
+            .with_writer(std::io::stderr)
+            .with_target(self.with_target)
+            .try_init()
+    }
+
+    /// Like [`try_init`](Self::try_init), but panics if a global subscriber is
+    /// already set.
+    pub fn init(self) {
+        self.try_init()
+            .expect("failed to install the global tracing subscriber")
+    }
+}
+
+/// Install the default [`LogConfig`].
+pub fn init() {
+    LogConfig::default().init()
+}
+
+fn level_name(level: LevelFilter) -> String {
+    level.to_string().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+
+    #[test]
+    fn test_default_directives() {
+        let config = LogConfig::new().from_env(false);
+        assert_eq!(config.directives(), "warn,terminusdb=info");
+    }
+
+    #[test]
+    fn test_per_target_levels() {
+        let config = LogConfig::new()
+            .from_env(false)
+            .default_level(LevelFilter::ERROR)
+            .level(targets::CLIENT_HTTP, Level::DEBUG)
+            .level(targets::ORM, LevelFilter::OFF)
+            .level(targets::CLIENT_HTTP, Level::TRACE);
+
+        assert_eq!(
+            config.directives(),
+            "error,terminusdb=info,terminusdb_orm=off,terminusdb_client::http=trace"
+        );
+    }
+}
@@ -4,7 +4,7 @@ use terminusdb_schema_derive::{FromTDBInstance, TerminusDBModel};
 
 /// Configuration for a TerminusDB instance node
 #[derive(Debug, Clone, TerminusDBModel, FromTDBInstance)]
-#[tdb(key = "id")]
+#[tdb(key = "random")]
 pub struct NodeConfig {
     /// Unique identifier for the node (also used as TerminusDB key)
     pub id: String,
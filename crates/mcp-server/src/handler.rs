@@ -9,7 +9,7 @@ use std::sync::Arc;
 use terminusdb_bin::TerminusDBServer;
 use terminusdb_client::debug::QueryLogEntry;
 use terminusdb_client::{BranchSpec, GetOpts, TerminusDBHttpClient};
-use terminusdb_woql2::prelude::{FromTDBInstance, Query};
+use terminusdb_woql2::prelude::{FromTDBInstance, Query, ToDSL};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 use url::Url;
@@ -157,6 +157,7 @@ impl TerminusDBMcpHandler {
                             anyhow::anyhow!("Failed to parse JavaScript syntax: {}", e)
                         })?
                     };
+                    info!("Mutating query:\n{}", query.to_dsl_pretty());
 
                     client
                         .query_mut(branch_spec, query, author, message)
@@ -57,8 +57,8 @@ impl TerminusDBMcpHandler {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to start server: {}", e))?;
 
-        // Get connection info - TerminusDB always runs on localhost:6363
-        let url = "http://localhost:6363".to_string();
+        // Connection info reflects the port the server actually bound
+        let url = format!("http://localhost:{}", server.port());
         let username = "admin";
 
         // Store server handle with connection info
@@ -0,0 +1,185 @@
+//! Aggregates over [`FilterQuery`] results, computed by the server.
+//!
+//! # Example
+//! ```ignore
+//! use terminusdb_orm::prelude::*;
+//!
+//! let adults = Person::filter(PersonFields::Age.gte(18));
+//! let count = adults.count(&client, &spec).await?;
+//! let total_age = adults.sum(PersonFields::Age, &client, &spec).await?;
+//!
+//! for group in Person::all().group_by(PersonFields::City, &client, &spec).await? {
+//!     println!("{}: {}", group.key, group.count);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use terminusdb_client::{BranchSpec, InstanceQueryable};
+use terminusdb_relation::{RelationField, RelationTo};
+use terminusdb_schema::{InstanceFromJson, TerminusDBModel};
+use terminusdb_woql2::prelude::{And, DataValue, GroupBy, Length, Query, Select, Sum, Value};
+
+use crate::filter::field_triple;
+use crate::{ClientProvider, FilterQuery};
+
+const SUBJECT: &str = "Subject";
+const FIELD_VALUE: &str = "AggregateValue";
+const FIELD_VALUES: &str = "AggregateValues";
+const SUM: &str = "Sum";
+const GROUP_KEY: &str = "GroupKey";
+const GROUP_MEMBERS: &str = "GroupMembers";
+const GROUP_COUNT: &str = "GroupCount";
+
+/// Number of matching instances sharing one field value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupCount<V> {
+    pub key: V,
+    pub count: usize,
+}
+
+impl<T: TerminusDBModel + InstanceFromJson> FilterQuery<T> {
+    /// Query binding `Sum` to the sum of `field` over the matching instances.
+    pub fn sum_query<F, V>(&self, _field: F) -> Query
+    where
+        F: RelationField,
+        T: RelationTo<V, F>,
+    {
+        let values = Query::GroupBy(GroupBy {
+            template: Value::Variable(FIELD_VALUE.to_string()),
+            group_by: vec![],
+            grouped_value: Value::Variable(FIELD_VALUES.to_string()),
+            query: Box::new(self.with_field(&F::field_name(), FIELD_VALUE)),
+        });
+        let sum = Query::Sum(Sum {
+            list: DataValue::Variable(FIELD_VALUES.to_string()),
+            result: DataValue::Variable(SUM.to_string()),
+        });
+
+        Query::Select(Select {
+            variables: vec![SUM.to_string()],
+            query: Box::new(Query::And(And {
+                and: vec![values, sum],
+            })),
+        })
+    }
+
+    /// Query binding `GroupKey` and `GroupCount` once per distinct value of
+    /// `field` among the matching instances.
+    pub fn group_by_query<F, V>(&self, _field: F) -> Query
+    where
+        F: RelationField,
+        T: RelationTo<V, F>,
+    {
+        let groups = Query::GroupBy(GroupBy {
+            template: Value::Variable(SUBJECT.to_string()),
+            group_by: vec![GROUP_KEY.to_string()],
+            grouped_value: Value::Variable(GROUP_MEMBERS.to_string()),
+            query: Box::new(self.with_field(&F::field_name(), GROUP_KEY)),
+        });
+        let count = Query::Length(Length {
+            list: DataValue::Variable(GROUP_MEMBERS.to_string()),
+            length: DataValue::Variable(GROUP_COUNT.to_string()),
+        });
+
+        Query::Select(Select {
+            variables: vec![GROUP_KEY.to_string(), GROUP_COUNT.to_string()],
+            query: Box::new(Query::And(And {
+                and: vec![groups, count],
+            })),
+        })
+    }
+
+    /// Sum of `field` over the matching instances; `0.0` when none match.
+    pub async fn sum<F, V, C>(&self, field: F, client: &C, spec: &BranchSpec) -> anyhow::Result<f64>
+    where
+        F: RelationField,
+        T: RelationTo<V, F>,
+        C: ClientProvider,
+    {
+        let res = client
+            .read_client()
+            .query::<HashMap<String, serde_json::Value>>(spec.clone().into(), self.sum_query(field))
+            .await?;
+
+        let Some(value) = res
+            .bindings
+            .into_iter()
+            .next()
+            .and_then(|mut b| b.remove(SUM))
+        else {
+            return Ok(0.0);
+        };
+        match literal(value) {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow::anyhow!("Failed to extract sum from query result"))
+    }
+
+    /// Count of matching instances per distinct value of `field`.
+    ///
+    /// Instances without a value for `field` are left out. Keys are read from
+    /// the stored literal, so this suits scalar fields.
+    pub async fn group_by<F, V, C>(
+        &self,
+        field: F,
+        client: &C,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<Vec<GroupCount<V>>>
+    where
+        F: RelationField,
+        T: RelationTo<V, F>,
+        V: DeserializeOwned,
+        C: ClientProvider,
+    {
+        let res = client
+            .read_client()
+            .query::<HashMap<String, serde_json::Value>>(
+                spec.clone().into(),
+                self.group_by_query(field),
+            )
+            .await?;
+
+        res.bindings
+            .into_iter()
+            .map(|mut binding| {
+                let key = binding
+                    .remove(GROUP_KEY)
+                    .ok_or_else(|| anyhow::anyhow!("group without a {} binding", GROUP_KEY))?;
+                let count = binding
+                    .remove(GROUP_COUNT)
+                    .map(literal)
+                    .and_then(|count| count.as_u64())
+                    .ok_or_else(|| anyhow::anyhow!("group without a {} binding", GROUP_COUNT))?;
+                Ok(GroupCount {
+                    key: serde_json::from_value(literal(key))?,
+                    count: count as usize,
+                })
+            })
+            .collect()
+    }
+
+    /// The filter constraints plus a triple binding `field` to `var`.
+    fn with_field(&self, field: &str, var: &str) -> Query {
+        let subject = Value::Variable(SUBJECT.to_string());
+        Query::And(And {
+            and: vec![
+                self.base_query(&subject),
+                field_triple(&subject, field, Value::Variable(var.to_string())),
+            ],
+        })
+    }
+}
+
+/// The value of a typed literal binding (`{"@type": .., "@value": v}`).
+fn literal(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(mut obj) if obj.contains_key("@value") => {
+            obj.remove("@value").unwrap_or_default()
+        }
+        other => other,
+    }
+}
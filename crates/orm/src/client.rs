@@ -1,12 +1,15 @@
 //! Global client singleton for ORM operations.
 //!
-//! Provides a set-once global client that can be accessed from anywhere in the application.
+//! Provides a set-once global client that can be accessed from anywhere in the application,
+//! optionally with read replicas (see [`ReplicatedClient`]).
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::OnceLock;
 use terminusdb_client::TerminusDBHttpClient;
+use url::Url;
 
 /// Global ORM client singleton
-static GLOBAL_CLIENT: OnceLock<TerminusDBHttpClient> = OnceLock::new();
+static GLOBAL_CLIENT: OnceLock<ReplicatedClient> = OnceLock::new();
 
 /// Error returned when attempting to initialize the global client twice
 #[derive(Debug, Clone)]
@@ -49,6 +52,14 @@ impl OrmClient {
     /// This method is thread-safe. If multiple threads race to initialize,
     /// exactly one will succeed and others will receive an error.
     pub fn init(client: TerminusDBHttpClient) -> Result<(), ClientAlreadyInitializedError> {
+        Self::init_replicated(ReplicatedClient::new(client))
+    }
+
+    /// Initialize the global ORM client with read replicas. Writes go to the
+    /// primary; reads are routed as configured on `client`.
+    ///
+    /// Fails like [`init`](Self::init) if a global client is already set.
+    pub fn init_replicated(client: ReplicatedClient) -> Result<(), ClientAlreadyInitializedError> {
         GLOBAL_CLIENT
             .set(client)
             .map_err(|_| ClientAlreadyInitializedError {
@@ -70,6 +81,14 @@ impl OrmClient {
     /// # Panics
     /// Panics if `OrmClient::init()` has not been called.
     pub fn get() -> &'static TerminusDBHttpClient {
+        Self::get_replicated().primary()
+    }
+
+    /// Get the global client together with its read replicas.
+    ///
+    /// # Panics
+    /// Panics if `OrmClient::init()` has not been called.
+    pub fn get_replicated() -> &'static ReplicatedClient {
         GLOBAL_CLIENT
             .get()
             .expect("ORM client not initialized. Call OrmClient::init() before using ORM features.")
@@ -79,7 +98,7 @@ impl OrmClient {
     ///
     /// Returns `None` if the client has not been initialized.
     pub fn try_get() -> Option<&'static TerminusDBHttpClient> {
-        GLOBAL_CLIENT.get()
+        GLOBAL_CLIENT.get().map(ReplicatedClient::primary)
     }
 
     /// Check if the global client has been initialized.
@@ -93,7 +112,14 @@ impl OrmClient {
 /// This allows methods to accept either the global client or an explicit client,
 /// useful for testing or multi-tenant scenarios.
 pub trait ClientProvider {
+    /// The client for writes, and for reads that must see them.
     fn client(&self) -> &TerminusDBHttpClient;
+
+    /// The client for reads that may be served by a replica. Defaults to
+    /// [`client`](Self::client).
+    fn read_client(&self) -> &TerminusDBHttpClient {
+        self.client()
+    }
 }
 
 /// Uses the global client singleton.
@@ -103,11 +129,19 @@ impl ClientProvider for GlobalClient {
     fn client(&self) -> &TerminusDBHttpClient {
         OrmClient::get()
     }
+
+    fn read_client(&self) -> &TerminusDBHttpClient {
+        OrmClient::get_replicated().read_client()
+    }
 }
 
-impl ClientProvider for &TerminusDBHttpClient {
+impl<C: ClientProvider + ?Sized> ClientProvider for &C {
     fn client(&self) -> &TerminusDBHttpClient {
-        self
+        (**self).client()
+    }
+
+    fn read_client(&self) -> &TerminusDBHttpClient {
+        (**self).read_client()
     }
 }
 
@@ -117,6 +151,114 @@ impl ClientProvider for TerminusDBHttpClient {
     }
 }
 
+/// How [`ReplicatedClient`] picks the client for a read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadRouting {
+    /// Read from the primary too; replicas are not used.
+    Primary,
+    /// Spread reads over the replicas in turn, or read from the primary when
+    /// there are none.
+    #[default]
+    RoundRobin,
+}
+
+/// A primary TerminusDB server plus read replicas.
+///
+/// Writes always go to the primary. Reads are routed by [`ReadRouting`];
+/// [`OrmSession`](crate::OrmSession) reads from the primary once it has
+/// committed, so a session sees its own writes even if replicas lag.
+///
+/// # Example
+/// ```ignore
+/// let client = ReplicatedClient::connect(
+///     Url::parse("http://primary:6363")?,
+///     [Url::parse("http://replica-1:6363")?, Url::parse("http://replica-2:6363")?],
+///     "admin",
+///     "root",
+///     "admin",
+/// )
+/// .await?;
+/// OrmClient::init_replicated(client)?;
+///
+/// let writers = Writer::all().execute(&spec).await?; // served by a replica
+/// ```
+pub struct ReplicatedClient {
+    primary: TerminusDBHttpClient,
+    replicas: Vec<TerminusDBHttpClient>,
+    routing: ReadRouting,
+    next: AtomicUsize,
+}
+
+impl ReplicatedClient {
+    /// A client with only a primary; add replicas with
+    /// [`with_replica`](Self::with_replica).
+    pub fn new(primary: TerminusDBHttpClient) -> Self {
+        Self {
+            primary,
+            replicas: Vec::new(),
+            routing: ReadRouting::default(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Connect to a primary and its replicas with the same credentials.
+    pub async fn connect(
+        primary: Url,
+        replicas: impl IntoIterator<Item = Url>,
+        user: &str,
+        pass: &str,
+        org: &str,
+    ) -> anyhow::Result<Self> {
+        let mut client = Self::new(TerminusDBHttpClient::new(primary, user, pass, org).await?);
+        for replica in replicas {
+            let replica = TerminusDBHttpClient::new(replica, user, pass, org).await?;
+            client = client.with_replica(replica);
+        }
+        Ok(client)
+    }
+
+    /// Add a replica to serve reads.
+    pub fn with_replica(mut self, replica: TerminusDBHttpClient) -> Self {
+        self.replicas.push(replica);
+        self
+    }
+
+    /// Set how reads are routed.
+    pub fn with_routing(mut self, routing: ReadRouting) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// The client writes go to.
+    pub fn primary(&self) -> &TerminusDBHttpClient {
+        &self.primary
+    }
+
+    pub fn replicas(&self) -> &[TerminusDBHttpClient] {
+        &self.replicas
+    }
+
+    pub fn routing(&self) -> ReadRouting {
+        self.routing
+    }
+}
+
+impl ClientProvider for ReplicatedClient {
+    fn client(&self) -> &TerminusDBHttpClient {
+        &self.primary
+    }
+
+    fn read_client(&self) -> &TerminusDBHttpClient {
+        match self.routing {
+            ReadRouting::RoundRobin if !self.replicas.is_empty() => {
+                let next = self.next.fetch_add(1, Ordering::Relaxed);
+                &self.replicas[next % self.replicas.len()]
+            }
+            _ => &self.primary,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +269,35 @@ mod tests {
         // Just test that try_get doesn't panic
         let _ = OrmClient::try_get();
     }
+
+    async fn node(port: u16) -> TerminusDBHttpClient {
+        let url = Url::parse(&format!("http://localhost:{}", port)).unwrap();
+        TerminusDBHttpClient::new(url, "admin", "root", "admin")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replicated_client_routes_reads_round_robin() {
+        let client = ReplicatedClient::new(node(6363).await)
+            .with_replica(node(6364).await)
+            .with_replica(node(6365).await);
+        let replicas = client.replicas();
+
+        assert!(std::ptr::eq(client.read_client(), &replicas[0]));
+        assert!(std::ptr::eq(client.read_client(), &replicas[1]));
+        assert!(std::ptr::eq(client.read_client(), &replicas[0]));
+        assert!(std::ptr::eq(client.client(), client.primary()));
+    }
+
+    #[tokio::test]
+    async fn test_replicated_client_reads_primary_without_replicas() {
+        let client = ReplicatedClient::new(node(6363).await);
+        assert!(std::ptr::eq(client.read_client(), client.primary()));
+
+        let client = ReplicatedClient::new(node(6363).await)
+            .with_replica(node(6364).await)
+            .with_routing(ReadRouting::Primary);
+        assert!(std::ptr::eq(client.read_client(), client.primary()));
+    }
 }
@@ -324,7 +324,7 @@ impl<C: ClientProvider> ComposedQuery<C> {
 
         let response = self
             .client
-            .client()
+            .read_client()
             .execute_graphql::<serde_json::Value>(&spec.db, spec.branch.as_deref(), request, None)
             .await?;
 
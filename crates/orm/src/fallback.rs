@@ -0,0 +1,490 @@
+//! Per-relation fallback when the GraphQL relation query fails.
+//!
+//! [`ModelQuery::execute`](crate::ModelQuery::execute) normally collects every
+//! related ID with a single GraphQL query. When that query cannot run (for
+//! example because the schema is not introspectable by the GraphQL engine),
+//! each relation is resolved on its own instead:
+//!
+//! - **Forward** relations read the reference field off the parent documents
+//!   through the document API.
+//! - **Reverse** relations run a WOQL query for documents of the target type
+//!   that link to any of the parents.
+//!
+//! Relations that carry a filter, ordering, limit or offset can only be
+//! honoured by GraphQL, so they are reported as failed rather than loaded
+//! unfiltered. A failed relation also fails everything nested under it.
+//!
+//! The outcome of every relation is collected in a [`ResolutionReport`].
+
+use std::collections::HashMap;
+
+use terminusdb_client::{BranchSpec, GetOpts, TerminusDBHttpClient};
+use terminusdb_schema::GraphType;
+use terminusdb_woql2::prelude::{And, NodeValue, Or, Query, Select, Triple, Value};
+
+use crate::query::{RelationDirection, RelationSpec};
+
+/// How a single relation ended up being resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionStrategy {
+    /// Part of the combined GraphQL relation query.
+    GraphQL,
+    /// WOQL query (reverse relations after a GraphQL failure).
+    Woql,
+    /// Reference fields read from the parent documents (forward relations
+    /// after a GraphQL failure).
+    DocumentApi,
+    /// Not resolved; see [`RelationOutcome::error`].
+    Failed,
+}
+
+/// What happened to one requested relation.
+#[derive(Debug, Clone)]
+pub struct RelationOutcome {
+    /// Dotted path of type names from the primary type, e.g. `Writer.BlogPost.Comment`.
+    pub path: String,
+    /// Schema class of the related documents.
+    pub target_type: String,
+    /// Direction and field of the relation.
+    pub direction: RelationDirection,
+    /// Strategy used to resolve it.
+    pub strategy: ResolutionStrategy,
+    /// Number of related IDs found (0 when failed).
+    pub ids_found: usize,
+    /// Why resolution failed, for [`ResolutionStrategy::Failed`].
+    pub error: Option<String>,
+}
+
+/// Per-relation report produced by
+/// [`ModelQuery::execute_with_report`](crate::ModelQuery::execute_with_report).
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionReport {
+    /// Error from the combined GraphQL query, if it failed and the fallback ran.
+    pub graphql_error: Option<String>,
+    /// One entry per requested relation, nested relations included, parents first.
+    pub outcomes: Vec<RelationOutcome>,
+}
+
+impl ResolutionReport {
+    /// Whether every relation was resolved by some strategy.
+    pub fn is_complete(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|o| o.strategy != ResolutionStrategy::Failed)
+    }
+
+    /// Whether the fallback path was taken.
+    pub fn used_fallback(&self) -> bool {
+        self.graphql_error.is_some()
+    }
+
+    /// Relations that could not be resolved.
+    pub fn failed(&self) -> impl Iterator<Item = &RelationOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.strategy == ResolutionStrategy::Failed)
+    }
+
+    /// Outcomes resolved with the given strategy.
+    pub fn with_strategy(
+        &self,
+        strategy: ResolutionStrategy,
+    ) -> impl Iterator<Item = &RelationOutcome> {
+        self.outcomes.iter().filter(move |o| o.strategy == strategy)
+    }
+
+    /// Record every relation (recursively) as resolved by the GraphQL query.
+    pub(crate) fn all_graphql(primary_type: &str, relations: &[RelationSpec]) -> Self {
+        let mut report = Self::default();
+        let mut stack: Vec<(String, &RelationSpec)> = relations
+            .iter()
+            .rev()
+            .map(|rel| (primary_type.to_string(), rel))
+            .collect();
+
+        while let Some((parent_path, rel)) = stack.pop() {
+            let outcome = outcome(&parent_path, rel, ResolutionStrategy::GraphQL, 0, None);
+            stack.extend(rel.children.iter().rev().map(|c| (outcome.path.clone(), c)));
+            report.outcomes.push(outcome);
+        }
+
+        report
+    }
+}
+
+fn outcome(
+    parent_path: &str,
+    rel: &RelationSpec,
+    strategy: ResolutionStrategy,
+    ids_found: usize,
+    error: Option<String>,
+) -> RelationOutcome {
+    RelationOutcome {
+        path: format!("{}.{}", parent_path, rel.target_type_name),
+        target_type: rel.target_type_name.clone(),
+        direction: rel.direction.clone(),
+        strategy,
+        ids_found,
+        error,
+    }
+}
+
+/// Resolve `relations` one by one without GraphQL, appending every related ID
+/// to `ids` and an outcome per relation to `report`.
+pub(crate) async fn resolve_without_graphql(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    primary_type: &str,
+    primary_ids: &[String],
+    relations: &[RelationSpec],
+    ids: &mut Vec<String>,
+    report: &mut ResolutionReport,
+) {
+    // (path of the parent, parent IDs or the reason they are missing, relation)
+    type Pending<'a> = (String, Result<Vec<String>, String>, &'a RelationSpec);
+
+    let mut stack: Vec<Pending> = relations
+        .iter()
+        .rev()
+        .map(|rel| (primary_type.to_string(), Ok(primary_ids.to_vec()), rel))
+        .collect();
+
+    while let Some((parent_path, parent_ids, rel)) = stack.pop() {
+        let resolved = match parent_ids {
+            Err(reason) => Err(reason),
+            Ok(parent_ids) => resolve_one(client, spec, &parent_ids, rel).await,
+        };
+
+        let outcome = match &resolved {
+            Ok((strategy, found)) => {
+                for id in found {
+                    if !ids.contains(id) {
+                        ids.push(id.clone());
+                    }
+                }
+                outcome(&parent_path, rel, *strategy, found.len(), None)
+            }
+            Err(reason) => {
+                tracing::warn!(
+                    target_type = %rel.target_type_name,
+                    error = %reason,
+                    "relation could not be resolved without GraphQL"
+                );
+                outcome(
+                    &parent_path,
+                    rel,
+                    ResolutionStrategy::Failed,
+                    0,
+                    Some(reason.clone()),
+                )
+            }
+        };
+
+        let child_ids = match resolved {
+            Ok((_, found)) => Ok(found),
+            Err(_) => Err(format!("parent relation {} failed", outcome.path)),
+        };
+        for child in rel.children.iter().rev() {
+            stack.push((outcome.path.clone(), child_ids.clone(), child));
+        }
+        report.outcomes.push(outcome);
+    }
+}
+
+async fn resolve_one(
+    client: &TerminusDBHttpClient,
+    spec: &BranchSpec,
+    parent_ids: &[String],
+    rel: &RelationSpec,
+) -> Result<(ResolutionStrategy, Vec<String>), String> {
+    if let Some(unsupported) = graphql_only_option(rel) {
+        return Err(format!("{} requires the GraphQL query", unsupported));
+    }
+    if parent_ids.is_empty() {
+        let strategy = match rel.direction {
+            RelationDirection::Forward { .. } => ResolutionStrategy::DocumentApi,
+            RelationDirection::Reverse { .. } => ResolutionStrategy::Woql,
+        };
+        return Ok((strategy, Vec::new()));
+    }
+
+    match &rel.direction {
+        RelationDirection::Forward { field_name } => {
+            let documents = client
+                .get_documents(parent_ids.to_vec(), spec, GetOpts::default())
+                .await
+                .map_err(|e| e.to_string())?;
+            let mut found = Vec::new();
+            for document in &documents {
+                collect_references(document.get(field_name.as_str()), &mut found);
+            }
+            Ok((ResolutionStrategy::DocumentApi, found))
+        }
+        RelationDirection::Reverse { via_field } => {
+            let query =
+                reverse_relation_query(parent_ids, via_field.as_deref(), &rel.target_type_name);
+            let result = client
+                .query::<HashMap<String, serde_json::Value>>(Some(spec.clone()), query)
+                .await
+                .map_err(|e| e.to_string())?;
+            let mut found = Vec::new();
+            for binding in &result.bindings {
+                if let Some(serde_json::Value::String(id)) = binding.get(CHILD_VAR) {
+                    if !found.contains(id) {
+                        found.push(id.clone());
+                    }
+                }
+            }
+            Ok((ResolutionStrategy::Woql, found))
+        }
+    }
+}
+
+/// The first relation option that only the GraphQL query can apply.
+fn graphql_only_option(rel: &RelationSpec) -> Option<&'static str> {
+    if rel.filter_gql.is_some() {
+        Some("filter")
+    } else if rel.order_by_gql.is_some() {
+        Some("order_by")
+    } else if rel.limit.is_some() {
+        Some("limit")
+    } else if rel.offset.is_some() {
+        Some("offset")
+    } else {
+        None
+    }
+}
+
+/// Collect document IDs from a forward reference field: a single reference,
+/// a collection of references, or unfolded documents carrying an `@id`.
+fn collect_references(value: Option<&serde_json::Value>, ids: &mut Vec<String>) {
+    use serde_json::Value;
+
+    match value {
+        Some(Value::String(id)) => {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        Some(Value::Array(items)) => {
+            for item in items {
+                collect_references(Some(item), ids);
+            }
+        }
+        Some(Value::Object(map)) => collect_references(map.get("@id"), ids),
+        _ => {}
+    }
+}
+
+const CHILD_VAR: &str = "Child";
+
+/// Documents of `target_type` linking to any of `parent_ids`, through
+/// `via_field` or, when `None`, through any field.
+fn reverse_relation_query(
+    parent_ids: &[String],
+    via_field: Option<&str>,
+    target_type: &str,
+) -> Query {
+    let child = NodeValue::Variable(CHILD_VAR.to_string());
+    let predicate = match via_field {
+        Some(field) => NodeValue::Node(format!("@schema:{}", field)),
+        None => NodeValue::Variable("Field".to_string()),
+    };
+
+    let mut links: Vec<Query> = parent_ids
+        .iter()
+        .map(|parent| {
+            Query::Triple(Triple {
+                subject: child.clone(),
+                predicate: predicate.clone(),
+                object: Value::Node(parent.clone()),
+                graph: Some(GraphType::Instance),
+            })
+        })
+        .collect();
+    let links = if links.len() == 1 {
+        links.pop().unwrap()
+    } else {
+        Query::Or(Or { or: links })
+    };
+
+    let is_target = Query::Triple(Triple {
+        subject: child,
+        predicate: NodeValue::Node("rdf:type".to_string()),
+        object: Value::Node(format!("@schema:{}", target_type)),
+        graph: Some(GraphType::Instance),
+    });
+
+    Query::Select(Select {
+        variables: vec![CHILD_VAR.to_string()],
+        query: Box::new(Query::And(And {
+            and: vec![links, is_target],
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::any::TypeId;
+
+    fn spec(
+        target: &str,
+        direction: RelationDirection,
+        children: Vec<RelationSpec>,
+    ) -> RelationSpec {
+        RelationSpec {
+            target_type_id: TypeId::of::<()>(),
+            target_type_name: target.to_string(),
+            direction,
+            children,
+            filter_gql: None,
+            limit: None,
+            offset: None,
+            order_by_gql: None,
+        }
+    }
+
+    #[test]
+    fn test_all_graphql_report_lists_nested_relations_in_order() {
+        let relations = vec![
+            spec(
+                "BlogPost",
+                RelationDirection::Reverse { via_field: None },
+                vec![spec(
+                    "Comment",
+                    RelationDirection::Reverse {
+                        via_field: Some("post".to_string()),
+                    },
+                    vec![],
+                )],
+            ),
+            spec(
+                "Profile",
+                RelationDirection::Forward {
+                    field_name: "profile".to_string(),
+                },
+                vec![],
+            ),
+        ];
+
+        let report = ResolutionReport::all_graphql("Writer", &relations);
+        let paths: Vec<_> = report.outcomes.iter().map(|o| o.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "Writer.BlogPost",
+                "Writer.BlogPost.Comment",
+                "Writer.Profile"
+            ]
+        );
+        assert!(report.is_complete());
+        assert!(!report.used_fallback());
+        assert_eq!(report.with_strategy(ResolutionStrategy::GraphQL).count(), 3);
+    }
+
+    #[test]
+    fn test_report_failed_relations() {
+        let rel = spec("Reply", RelationDirection::default(), vec![]);
+        let report = ResolutionReport {
+            graphql_error: Some("schema not introspectable".to_string()),
+            outcomes: vec![
+                outcome("Comment", &rel, ResolutionStrategy::Woql, 2, None),
+                outcome(
+                    "Comment",
+                    &rel,
+                    ResolutionStrategy::Failed,
+                    0,
+                    Some("limit requires the GraphQL query".to_string()),
+                ),
+            ],
+        };
+
+        assert!(report.used_fallback());
+        assert!(!report.is_complete());
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(report.with_strategy(ResolutionStrategy::Woql).count(), 1);
+    }
+
+    #[test]
+    fn test_graphql_only_options() {
+        let mut rel = spec("Reply", RelationDirection::default(), vec![]);
+        assert_eq!(graphql_only_option(&rel), None);
+
+        rel.limit = Some(5);
+        assert_eq!(graphql_only_option(&rel), Some("limit"));
+
+        rel.filter_gql = Some("{text: {eq: \"hi\"}}".to_string());
+        assert_eq!(graphql_only_option(&rel), Some("filter"));
+    }
+
+    #[test]
+    fn test_collect_references() {
+        let doc = json!({
+            "@id": "Car/1",
+            "front": ["Wheel/1", "Wheel/2"],
+            "spare": {"@id": "Wheel/3", "@type": "Wheel"},
+            "back": "Wheel/1",
+        });
+
+        let mut ids = Vec::new();
+        for field in ["front", "spare", "back", "missing"] {
+            collect_references(doc.get(field), &mut ids);
+        }
+
+        assert_eq!(ids, vec!["Wheel/1", "Wheel/2", "Wheel/3"]);
+    }
+
+    #[test]
+    fn test_reverse_relation_query() {
+        let parents = vec!["Writer/1".to_string(), "Writer/2".to_string()];
+
+        let Query::Select(select) = reverse_relation_query(&parents, Some("writer"), "BlogPost")
+        else {
+            panic!("expected a select");
+        };
+        assert_eq!(select.variables, vec![CHILD_VAR]);
+
+        let Query::And(And { and }) = *select.query else {
+            panic!("expected a conjunction");
+        };
+        let Query::Or(Or { or }) = &and[0] else {
+            panic!("expected one link per parent");
+        };
+        assert_eq!(or.len(), 2);
+        let Query::Triple(link) = &or[1] else {
+            panic!("expected a triple");
+        };
+        assert_eq!(
+            link.predicate,
+            NodeValue::Node("@schema:writer".to_string())
+        );
+        assert_eq!(link.object, Value::Node("Writer/2".to_string()));
+
+        let Query::Triple(is_target) = &and[1] else {
+            panic!("expected a type check");
+        };
+        assert_eq!(
+            is_target.object,
+            Value::Node("@schema:BlogPost".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reverse_relation_query_through_any_field() {
+        let parents = vec!["Writer/1".to_string()];
+
+        let Query::Select(select) = reverse_relation_query(&parents, None, "BlogPost") else {
+            panic!("expected a select");
+        };
+        let Query::And(And { and }) = *select.query else {
+            panic!("expected a conjunction");
+        };
+        let Query::Triple(link) = &and[0] else {
+            panic!("a single parent needs no disjunction");
+        };
+        assert!(matches!(link.predicate, NodeValue::Variable(_)));
+    }
+}
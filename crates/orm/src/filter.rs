@@ -0,0 +1,478 @@
+//! Typed filter DSL compiled to WOQL.
+//!
+//! Conditions are built from the derive-generated field markers
+//! (`{Struct}Fields::FieldName`), so a misspelled field name is a compile
+//! error rather than an empty result set.
+//!
+//! # Example
+//! ```ignore
+//! use terminusdb_orm::prelude::*;
+//!
+//! let adults = Person::filter(PersonFields::Age.gte(18))
+//!     .filter(
+//!         PersonFields::Name
+//!             .starts_with("A")
+//!             .or(PersonFields::Email.is_null()),
+//!     )
+//!     .apply(&client, &spec, Some(10), None)
+//!     .await?;
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::Not as NotOp;
+
+use terminusdb_client::{data_value_to_object, InstanceQueryable};
+use terminusdb_relation::RelationField;
+use terminusdb_schema::{
+    GraphType, InstanceFromJson, TDBEnum, TerminusDBModel, ToSchemaClass, XSDAnySimpleType,
+};
+use terminusdb_woql2::macros::IntoNodeValue;
+use terminusdb_woql2::prelude::{
+    And, DataValue, Equals, Greater, Gte, IntoDataValue, Less, Lte, Member, NodeValue, Not, Once,
+    Or, Query, Regexp, Substring, Triple, True, Value,
+};
+
+/// Comparison operator of a [`Condition::Compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+/// A filter condition on the fields of a model.
+///
+/// Field conditions are created through [`FieldFilter`] on a field marker and
+/// combined with [`Condition::and`], [`Condition::or`] and `!`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Compare the field value against a constant.
+    Compare {
+        field: &'static str,
+        op: CompareOp,
+        value: DataValue,
+    },
+    /// The string field contains `substring` (compiled to `Substring`).
+    Contains {
+        field: &'static str,
+        substring: String,
+    },
+    /// The string field starts with `prefix` (compiled to `Substring`).
+    StartsWith { field: &'static str, prefix: String },
+    /// The string field matches a PCRE `pattern` (compiled to `Regexp`).
+    Matches {
+        field: &'static str,
+        pattern: String,
+    },
+    /// The field value is one of `values` (compiled to `Member`).
+    InList {
+        field: &'static str,
+        values: Vec<DataValue>,
+    },
+    /// The field has no value.
+    IsNull { field: &'static str },
+    /// The field has a value.
+    IsNotNull { field: &'static str },
+    /// All conditions hold.
+    And(Vec<Condition>),
+    /// At least one condition holds.
+    Or(Vec<Condition>),
+    /// The condition does not hold.
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Condition that holds when all of `conditions` hold.
+    pub fn all(conditions: impl IntoIterator<Item = Condition>) -> Self {
+        Condition::And(conditions.into_iter().collect())
+    }
+
+    /// Condition that holds when any of `conditions` holds.
+    pub fn any(conditions: impl IntoIterator<Item = Condition>) -> Self {
+        Condition::Or(conditions.into_iter().collect())
+    }
+
+    /// Combine with `other`; both must hold.
+    pub fn and(self, other: Condition) -> Self {
+        match self {
+            Condition::And(mut conditions) => {
+                conditions.push(other);
+                Condition::And(conditions)
+            }
+            this => Condition::And(vec![this, other]),
+        }
+    }
+
+    /// Combine with `other`; either may hold.
+    pub fn or(self, other: Condition) -> Self {
+        match self {
+            Condition::Or(mut conditions) => {
+                conditions.push(other);
+                Condition::Or(conditions)
+            }
+            this => Condition::Or(vec![this, other]),
+        }
+    }
+
+    /// Compile this condition to WOQL constraints on `subject`.
+    pub fn to_query(&self, subject: &Value) -> Query {
+        self.compile(subject, &mut 0)
+    }
+
+    fn compile(&self, subject: &Value, vars: &mut usize) -> Query {
+        match self {
+            Condition::Compare {
+                field,
+                op: CompareOp::Eq,
+                value,
+            } => field_triple(subject, field, data_value_to_object(value)),
+            Condition::Compare {
+                field,
+                op: CompareOp::Ne,
+                value,
+            } => {
+                let var = fresh_var(vars);
+                let check = Query::Not(Not {
+                    query: Box::new(Query::Equals(Equals {
+                        left: Value::Variable(var.clone()),
+                        right: data_value_to_object(value),
+                    })),
+                });
+                bound_field(subject, field, var, check)
+            }
+            Condition::Compare { field, op, value } => {
+                let var = fresh_var(vars);
+                let left = DataValue::Variable(var.clone());
+                let right = normalize(value);
+                let check = match op {
+                    CompareOp::Gt => Query::Greater(Greater { left, right }),
+                    CompareOp::Gte => Query::Gte(Gte { left, right }),
+                    CompareOp::Lt => Query::Less(Less { left, right }),
+                    CompareOp::Lte => Query::Lte(Lte { left, right }),
+                    CompareOp::Eq | CompareOp::Ne => unreachable!("handled above"),
+                };
+                bound_field(subject, field, var, check)
+            }
+            Condition::Contains { field, substring } => {
+                let var = fresh_var(vars);
+                let check = Query::Substring(Substring {
+                    string: DataValue::Variable(var.clone()),
+                    before: DataValue::Variable(fresh_var(vars)),
+                    length: DataValue::Variable(fresh_var(vars)),
+                    after: DataValue::Variable(fresh_var(vars)),
+                    substring: substring.as_str().into_data_value(),
+                });
+                bound_field(subject, field, var, check)
+            }
+            Condition::StartsWith { field, prefix } => {
+                let var = fresh_var(vars);
+                let check = Query::Substring(Substring {
+                    string: DataValue::Variable(var.clone()),
+                    before: DataValue::Data(XSDAnySimpleType::Integer(0)),
+                    length: DataValue::Data(XSDAnySimpleType::Integer(
+                        prefix.chars().count() as i64
+                    )),
+                    after: DataValue::Variable(fresh_var(vars)),
+                    substring: prefix.as_str().into_data_value(),
+                });
+                bound_field(subject, field, var, check)
+            }
+            Condition::Matches { field, pattern } => {
+                let var = fresh_var(vars);
+                let check = Query::Regexp(Regexp {
+                    pattern: pattern.as_str().into_data_value(),
+                    string: DataValue::Variable(var.clone()),
+                    result: None,
+                });
+                bound_field(subject, field, var, check)
+            }
+            Condition::InList { field, values } => {
+                let var = fresh_var(vars);
+                let check = Query::Member(Member {
+                    member: DataValue::Variable(var.clone()),
+                    list: DataValue::List(values.iter().map(normalize).collect()),
+                });
+                bound_field(subject, field, var, check)
+            }
+            Condition::IsNull { field } => {
+                let var = fresh_var(vars);
+                Query::Not(Not {
+                    query: Box::new(field_triple(subject, field, Value::Variable(var))),
+                })
+            }
+            Condition::IsNotNull { field } => {
+                let var = fresh_var(vars);
+                field_triple(subject, field, Value::Variable(var))
+            }
+            Condition::And(conditions) => {
+                let mut and = Vec::with_capacity(conditions.len());
+                for condition in conditions {
+                    // Flatten nested conjunctions into a single `And`
+                    match condition.compile(subject, vars) {
+                        Query::And(inner) => and.extend(inner.and),
+                        Query::True(_) => {}
+                        other => and.push(other),
+                    }
+                }
+                match and.len() {
+                    0 => Query::True(True {}),
+                    1 => and.into_iter().next().unwrap(),
+                    _ => Query::And(And { and }),
+                }
+            }
+            Condition::Or(conditions) => Query::Or(Or {
+                or: conditions
+                    .iter()
+                    .map(|condition| condition.compile(subject, vars))
+                    .collect(),
+            }),
+            Condition::Not(condition) => Query::Not(Not {
+                query: Box::new(condition.compile(subject, vars)),
+            }),
+        }
+    }
+}
+
+impl NotOp for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        match self {
+            Condition::Not(inner) => *inner,
+            this => Condition::Not(Box::new(this)),
+        }
+    }
+}
+
+fn fresh_var(vars: &mut usize) -> String {
+    *vars += 1;
+    format!("FilterValue{}", vars)
+}
+
+/// Map a constant through the same conversion used for triple objects, so
+/// comparisons see the same xsd types as equality matches.
+fn normalize(value: &DataValue) -> DataValue {
+    match value {
+        DataValue::List(items) => DataValue::List(items.iter().map(normalize).collect()),
+        _ => match data_value_to_object(value) {
+            Value::Data(data) => DataValue::Data(data),
+            Value::Variable(var) => DataValue::Variable(var),
+            _ => value.clone(),
+        },
+    }
+}
+
+pub(crate) fn field_triple(subject: &Value, field: &str, object: Value) -> Query {
+    Query::Triple(Triple {
+        subject: subject.clone().into_node_value(),
+        predicate: NodeValue::Node(format!("@schema:{}", field)),
+        object,
+        graph: Some(GraphType::Instance),
+    })
+}
+
+/// Bind the field value to `var` and apply `check` to it.
+fn bound_field(subject: &Value, field: &str, var: String, check: Query) -> Query {
+    Query::And(And {
+        and: vec![field_triple(subject, field, Value::Variable(var)), check],
+    })
+}
+
+/// Condition constructors on derive-generated field markers.
+///
+/// Implemented for every [`RelationField`], e.g. `PersonFields::Age.gt(30)`.
+pub trait FieldFilter: RelationField + Sized {
+    /// Field equals `value`.
+    fn eq(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Eq, value)
+    }
+
+    /// Field is set and differs from `value`.
+    fn ne(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Ne, value)
+    }
+
+    /// Field is greater than `value`.
+    fn gt(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Gt, value)
+    }
+
+    /// Field is greater than or equal to `value`.
+    fn gte(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Gte, value)
+    }
+
+    /// Field is less than `value`.
+    fn lt(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Lt, value)
+    }
+
+    /// Field is less than or equal to `value`.
+    fn lte(self, value: impl IntoDataValue) -> Condition {
+        compare::<Self>(CompareOp::Lte, value)
+    }
+
+    /// Enum field comes after `variant` in declared order.
+    ///
+    /// TerminusDB stores enum values as nodes, so [`FieldFilter::gt`] would
+    /// compare their IRIs lexically. The `enum_*` comparisons use
+    /// [`TDBEnum::ordinal`] instead and match any of the qualifying variants.
+    fn enum_gt<E: TDBEnum + ToSchemaClass>(self, variant: E) -> Condition {
+        let ordinal = variant.ordinal();
+        enum_where::<Self, E>(|o| o > ordinal)
+    }
+
+    /// Enum field is `variant` or comes after it in declared order.
+    fn enum_gte<E: TDBEnum + ToSchemaClass>(self, variant: E) -> Condition {
+        let ordinal = variant.ordinal();
+        enum_where::<Self, E>(|o| o >= ordinal)
+    }
+
+    /// Enum field comes before `variant` in declared order.
+    fn enum_lt<E: TDBEnum + ToSchemaClass>(self, variant: E) -> Condition {
+        let ordinal = variant.ordinal();
+        enum_where::<Self, E>(|o| o < ordinal)
+    }
+
+    /// Enum field is `variant` or comes before it in declared order.
+    fn enum_lte<E: TDBEnum + ToSchemaClass>(self, variant: E) -> Condition {
+        let ordinal = variant.ordinal();
+        enum_where::<Self, E>(|o| o <= ordinal)
+    }
+
+    /// String field contains `substring`.
+    fn contains(self, substring: impl Into<String>) -> Condition {
+        Condition::Contains {
+            field: Self::field_name(),
+            substring: substring.into(),
+        }
+    }
+
+    /// String field starts with `prefix`.
+    fn starts_with(self, prefix: impl Into<String>) -> Condition {
+        Condition::StartsWith {
+            field: Self::field_name(),
+            prefix: prefix.into(),
+        }
+    }
+
+    /// String field matches the PCRE `pattern`.
+    fn matches(self, pattern: impl Into<String>) -> Condition {
+        Condition::Matches {
+            field: Self::field_name(),
+            pattern: pattern.into(),
+        }
+    }
+
+    /// Field value is one of `values`.
+    fn in_list<V: IntoDataValue>(self, values: impl IntoIterator<Item = V>) -> Condition {
+        Condition::InList {
+            field: Self::field_name(),
+            values: values.into_iter().map(|v| v.into_data_value()).collect(),
+        }
+    }
+
+    /// Field has no value (`None` for optional fields).
+    fn is_null(self) -> Condition {
+        Condition::IsNull {
+            field: Self::field_name(),
+        }
+    }
+
+    /// Field has a value.
+    fn is_not_null(self) -> Condition {
+        Condition::IsNotNull {
+            field: Self::field_name(),
+        }
+    }
+}
+
+impl<F: RelationField> FieldFilter for F {}
+
+fn compare<F: RelationField>(op: CompareOp, value: impl IntoDataValue) -> Condition {
+    Condition::Compare {
+        field: F::field_name(),
+        op,
+        value: value.into_data_value(),
+    }
+}
+
+/// The field holds one of the variants of `E` whose ordinal satisfies `pred`:
+/// an `Or` of equalities against the `@schema:Enum/value` nodes.
+fn enum_where<F: RelationField, E: TDBEnum + ToSchemaClass>(
+    pred: impl Fn(i64) -> bool,
+) -> Condition {
+    Condition::any(E::tdb_values_where(pred).into_iter().map(|value| {
+        let node = format!("@schema:{}/{}", E::to_class(), value);
+        compare::<F>(CompareOp::Eq, DataValue::Data(XSDAnySimpleType::URI(node)))
+    }))
+}
+
+/// Instance query for `T` restricted by [`Condition`]s.
+///
+/// Runs through [`InstanceQueryable`], so `apply` and `count` are available.
+#[derive(Debug, Clone)]
+pub struct FilterQuery<T> {
+    conditions: Vec<Condition>,
+    _ty: PhantomData<T>,
+}
+
+impl<T> Default for FilterQuery<T> {
+    fn default() -> Self {
+        Self {
+            conditions: Vec::new(),
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<T> FilterQuery<T> {
+    /// Query matching every instance of `T`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a condition; all added conditions must hold.
+    pub fn filter(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// The conditions added so far.
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+}
+
+impl<T: TerminusDBModel + InstanceFromJson> InstanceQueryable for FilterQuery<T> {
+    type Model = T;
+
+    fn build(&self, subject: &Value) -> Query {
+        match Condition::all(self.conditions.iter().cloned()).to_query(subject) {
+            Query::True(t) => Query::True(t),
+            // `Or` branches and multi-valued fields can each yield a solution
+            // for the same subject; `Once` keeps one document per match.
+            constraints => Query::Once(Once {
+                query: Box::new(constraints),
+            }),
+        }
+    }
+}
+
+/// Entry points for [`FilterQuery`] on models.
+pub trait FilterExt: TerminusDBModel + InstanceFromJson + Sized {
+    /// Query all instances of this model.
+    fn all() -> FilterQuery<Self> {
+        FilterQuery::new()
+    }
+
+    /// Query the instances of this model matching `condition`.
+    fn filter(condition: Condition) -> FilterQuery<Self> {
+        FilterQuery::new().filter(condition)
+    }
+}
+
+impl<T: TerminusDBModel + InstanceFromJson> FilterExt for T {}
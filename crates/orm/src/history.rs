@@ -0,0 +1,108 @@
+//! Comparing the instances of a model between two commits.
+//!
+//! # Example
+//! ```ignore
+//! use terminusdb_orm::prelude::*;
+//!
+//! let diff = diff_between::<Person, _>(&client, &spec, "commit_a", "commit_b").await?;
+//! for change in &diff.changed {
+//!     println!("{} -> {}", change.before.name, change.after.name);
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use terminusdb_client::{BranchSpec, CommitId, GetOpts};
+use terminusdb_schema::{FromTDBInstance, InstanceFromJson, TerminusDBModel};
+
+use crate::ClientProvider;
+
+/// The instances of `T` added, changed and deleted between two commits,
+/// each list ordered by document ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDiff<T> {
+    pub added: Vec<T>,
+    pub changed: Vec<ModelChange<T>>,
+    pub deleted: Vec<T>,
+}
+
+impl<T> ModelDiff<T> {
+    /// True when no instance of `T` differs between the two commits.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// One instance as stored at both commits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelChange<T> {
+    pub before: T,
+    pub after: T,
+}
+
+/// Compare the instances of `T` in `spec`'s database as of `commit_a` and as
+/// of `commit_b`.
+///
+/// Both states are read through the document API pinned to each commit, and
+/// documents are matched by ID. A document counts as changed when its stored
+/// JSON differs.
+pub async fn diff_between<T, C>(
+    client: &C,
+    spec: &BranchSpec,
+    commit_a: impl Into<CommitId>,
+    commit_b: impl Into<CommitId>,
+) -> anyhow::Result<ModelDiff<T>>
+where
+    T: TerminusDBModel + InstanceFromJson,
+    C: ClientProvider,
+{
+    let mut before = documents_at::<T, C>(client, spec, commit_a.into()).await?;
+    let after = documents_at::<T, C>(client, spec, commit_b.into()).await?;
+
+    let mut diff = ModelDiff {
+        added: Vec::new(),
+        changed: Vec::new(),
+        deleted: Vec::new(),
+    };
+    for (id, doc) in after {
+        match before.remove(&id) {
+            None => diff.added.push(T::from_json(doc)?),
+            Some(old) if old != doc => diff.changed.push(ModelChange {
+                before: T::from_json(old)?,
+                after: T::from_json(doc)?,
+            }),
+            Some(_) => {}
+        }
+    }
+    for (_, doc) in before {
+        diff.deleted.push(T::from_json(doc)?);
+    }
+    Ok(diff)
+}
+
+/// Every `T` document as of `commit`, keyed by ID.
+async fn documents_at<T: TerminusDBModel, C: ClientProvider>(
+    client: &C,
+    spec: &BranchSpec,
+    commit: CommitId,
+) -> anyhow::Result<BTreeMap<String, serde_json::Value>> {
+    let docs = client
+        .read_client()
+        .get_documents(
+            vec![],
+            &spec.clone().ref_commit(commit),
+            GetOpts::filtered_by_type::<T>(),
+        )
+        .await?;
+
+    docs.into_iter()
+        .map(|doc| {
+            let id = doc
+                .get("@id")
+                .and_then(|id| id.as_str())
+                .ok_or_else(|| anyhow::anyhow!("document without an @id: {}", doc))?
+                .to_string();
+            Ok((id, doc))
+        })
+        .collect()
+}
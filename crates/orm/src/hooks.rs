@@ -0,0 +1,127 @@
+//! Lifecycle hooks run by the persistence layer.
+//!
+//! # Example
+//! ```ignore
+//! use terminusdb_orm::prelude::*;
+//!
+//! impl ModelHooks for Order {
+//!     fn before_save(&self) -> anyhow::Result<()> {
+//!         anyhow::ensure!(self.amount >= 0, "order amount must not be negative");
+//!         Ok(())
+//!     }
+//! }
+//!
+//! register_hooks::<Order>();
+//! order.save(&client, &spec).await?; // runs before_save, then validate
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use terminusdb_schema::{InstanceValidation, TerminusDBModel, ToTDBInstance, ToTDBSchema};
+
+/// Callbacks around writes of a model.
+///
+/// Hooks run for models registered with [`register_hooks`], whichever way
+/// they are written: [`Persist`](crate::Persist) methods,
+/// [`Tracked::save`](crate::Tracked::save), [`SaveGraph`](crate::SaveGraph)
+/// and [`OrmSession`](crate::OrmSession) commits. An error from a `before_*`
+/// hook or from [`validate`](Self::validate) aborts the write.
+pub trait ModelHooks: TerminusDBModel {
+    /// Runs before the model is inserted or replaced.
+    fn before_save(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Runs after the model was written as a new document.
+    fn after_create(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Runs before the model's document is deleted.
+    fn before_delete(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Runs after [`before_save`](Self::before_save). Checks the model
+    /// against its schema by default: required fields, enum values and the
+    /// cardinality set with `#[tdb(min_cardinality, max_cardinality)]`.
+    fn validate(&self) -> anyhow::Result<()> {
+        validate_schema(self)
+    }
+}
+
+/// Run the [`ModelHooks`] of `T` on every write of a `T` from now on.
+/// Registering a type again has no further effect.
+pub fn register_hooks<T: ModelHooks + 'static>() {
+    registry().write().expect("hook registry poisoned").insert(
+        TypeId::of::<T>(),
+        Hooks {
+            before_save: |model| {
+                let model = downcast::<T>(model);
+                model.before_save()?;
+                model.validate()
+            },
+            after_create: |model| downcast::<T>(model).after_create(),
+            before_delete: |model| downcast::<T>(model).before_delete(),
+        },
+    );
+}
+
+/// Check `model` against the schema of `T`, reporting every violation.
+pub fn validate_schema<T: ToTDBInstance + ToTDBSchema>(model: &T) -> anyhow::Result<()> {
+    model.to_instance(None).validate().map_err(|errors| {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        anyhow::anyhow!(
+            "{} failed validation: {}",
+            T::schema_name(),
+            errors.join("; ")
+        )
+    })
+}
+
+type Hook = fn(&dyn Any) -> anyhow::Result<()>;
+
+#[derive(Clone, Copy)]
+struct Hooks {
+    before_save: Hook,
+    after_create: Hook,
+    before_delete: Hook,
+}
+
+fn registry() -> &'static RwLock<HashMap<TypeId, Hooks>> {
+    static HOOKS: OnceLock<RwLock<HashMap<TypeId, Hooks>>> = OnceLock::new();
+    HOOKS.get_or_init(Default::default)
+}
+
+fn downcast<T: 'static>(model: &dyn Any) -> &T {
+    model
+        .downcast_ref::<T>()
+        .expect("hooks are registered under the model's TypeId")
+}
+
+fn run(model: &dyn Any, hook: fn(&Hooks) -> Hook) -> anyhow::Result<()> {
+    let hooks = registry()
+        .read()
+        .expect("hook registry poisoned")
+        .get(&model.type_id())
+        .copied();
+    match hooks {
+        Some(hooks) => hook(&hooks)(model),
+        None => Ok(()),
+    }
+}
+
+/// `before_save` and `validate`, if `model`'s type has registered hooks.
+pub(crate) fn before_save(model: &dyn Any) -> anyhow::Result<()> {
+    run(model, |hooks| hooks.before_save)
+}
+
+pub(crate) fn after_create(model: &dyn Any) -> anyhow::Result<()> {
+    run(model, |hooks| hooks.after_create)
+}
+
+pub(crate) fn before_delete(model: &dyn Any) -> anyhow::Result<()> {
+    run(model, |hooks| hooks.before_delete)
+}
@@ -16,14 +16,31 @@
 //! let replies: Vec<Reply> = result.get::<Reply>()?;
 //! ```
 
+#[cfg(not(target_arch = "wasm32"))]
+mod aggregate;
 mod client;
 mod compose;
+mod fallback;
+#[cfg(not(target_arch = "wasm32"))]
+mod filter;
 mod graphql_query;
+#[cfg(not(target_arch = "wasm32"))]
+mod history;
+#[cfg(not(target_arch = "wasm32"))]
+mod hooks;
 mod multi_fetch;
+#[cfg(not(target_arch = "wasm32"))]
+mod persist;
 mod query;
 mod relations;
 mod resolver;
 mod result;
+#[cfg(not(target_arch = "wasm32"))]
+mod save_graph;
+#[cfg(not(target_arch = "wasm32"))]
+mod session;
+#[cfg(not(target_arch = "wasm32"))]
+mod tracked;
 
 pub mod prelude;
 
@@ -33,21 +50,26 @@ pub mod prelude;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
-pub use client::{ClientAlreadyInitializedError, ClientProvider, GlobalClient, OrmClient};
+pub use client::{
+    ClientAlreadyInitializedError, ClientProvider, GlobalClient, OrmClient, ReadRouting,
+    ReplicatedClient,
+};
 pub use compose::{ComposedQuery, ComposedResult, Orm};
+pub use fallback::{RelationOutcome, ResolutionReport, ResolutionStrategy};
 pub use graphql_query::{parse_id_response, IdQueryBuilder, IdQueryResult, RelationPath};
 pub use multi_fetch::{fetch_by_ids, fetch_by_ids_default, FetchBuilder, MultiTypeFetch};
 pub use query::{
     IntoQueryPart, ModelExt, ModelQuery, OrmModel, QueryEntry, RelationBuilder, RelationDirection,
-    RelationOpts, RelationSpec,
+    RelationOpts, RelationSpec, DEFAULT_STREAM_PAGE_SIZE,
 };
 pub use relations::{
-    BelongsTo, BelongsToField, DefaultField, ForwardRelation, HasMany, HasManyField, HasOne,
-    HasOneField, ReverseRelation,
+    BelongsTo, BelongsToField, DefaultField, ForwardRelation, HasMany, HasManyField,
+    HasManyThrough, HasOne, HasOneField, ReverseRelation, TdbLazyExt, Through, ThroughExt,
 };
 pub use resolver::{
     generate_graphql_query, BatchResolver, GraphQLRelationQuery, LoadStrategy, QueryPlan,
-    RelationPlan, RelationResolution, RelationSelection, ResolvedRelations,
+    RelationPlan, RelationResolution, RelationSelection, ResolvedRelations, TracedQuery,
+    TracedQueryKind,
 };
 pub use result::OrmResult;
 
@@ -57,7 +79,33 @@ pub use graphql_query::execute_id_query;
 #[cfg(not(target_arch = "wasm32"))]
 pub use resolver::RelationResolver;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use filter::{CompareOp, Condition, FieldFilter, FilterExt, FilterQuery};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use aggregate::GroupCount;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use history::{diff_between, ModelChange, ModelDiff};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use hooks::{register_hooks, validate_schema, ModelHooks};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use persist::Persist;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use save_graph::SaveGraph;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use session::OrmSession;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use tracked::Tracked;
+
 // Re-export commonly used types from dependencies
+#[cfg(not(target_arch = "wasm32"))]
+pub use terminusdb_client::InstanceQueryable;
 pub use terminusdb_client::{BranchSpec, GetOpts, TerminusDBHttpClient};
 pub use terminusdb_relation::{RelationField, RelationFrom, RelationTo};
 pub use terminusdb_schema::{
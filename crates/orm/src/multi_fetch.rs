@@ -6,8 +6,11 @@
 use std::collections::{HashMap, HashSet};
 
 use terminusdb_client::{BranchSpec, GetOpts, TerminusDBHttpClient};
+use terminusdb_schema::{EntityIDFor, ToSchemaClass};
 
-use crate::{result::OrmResult, ClientProvider, GlobalClient};
+use crate::fallback::{resolve_without_graphql, ResolutionReport};
+use crate::query::{OrmModel, RelationBuilder, RelationSpec};
+use crate::{result::OrmResult, ClientProvider, GlobalClient, ReplicatedClient};
 
 /// Normalize an id / `@id` to its `Type/local` form for order matching:
 /// strips a leading `terminusdb:///<graph>/` IRI prefix if present, so a short
@@ -107,6 +110,7 @@ async fn fetch_by_ids_impl(
 
     // Deduplicate IDs while PRESERVING first-seen order, so a Phase-1 query with
     // `orderBy` keeps its ordering through this Phase-2 batch fetch.
+    let seen_total = ids.len();
     let mut seen = HashSet::new();
     let mut unique_ids: Vec<String> = Vec::with_capacity(ids.len());
     for id in ids {
@@ -115,6 +119,12 @@ async fn fetch_by_ids_impl(
         }
     }
 
+    tracing::debug!(
+        requested = unique_ids.len(),
+        duplicates = seen_total - unique_ids.len(),
+        "fetching documents by id"
+    );
+
     // Single API call for all IDs, regardless of type
     let documents = client.get_documents(unique_ids.clone(), spec, opts).await?;
 
@@ -146,7 +156,31 @@ impl MultiTypeFetch for GlobalClient {
         spec: &BranchSpec,
         opts: GetOpts,
     ) -> anyhow::Result<OrmResult> {
-        fetch_by_ids_impl(self.client(), ids, spec, opts).await
+        fetch_by_ids_impl(self.read_client(), ids, spec, opts).await
+    }
+}
+
+#[async_trait::async_trait]
+impl MultiTypeFetch for ReplicatedClient {
+    async fn fetch_by_ids(
+        &self,
+        ids: Vec<String>,
+        spec: &BranchSpec,
+        opts: GetOpts,
+    ) -> anyhow::Result<OrmResult> {
+        fetch_by_ids_impl(self.read_client(), ids, spec, opts).await
+    }
+}
+
+#[async_trait::async_trait]
+impl MultiTypeFetch for &ReplicatedClient {
+    async fn fetch_by_ids(
+        &self,
+        ids: Vec<String>,
+        spec: &BranchSpec,
+        opts: GetOpts,
+    ) -> anyhow::Result<OrmResult> {
+        fetch_by_ids_impl(self.read_client(), ids, spec, opts).await
     }
 }
 
@@ -191,17 +225,40 @@ pub async fn fetch_by_ids_default(
 ///     .execute(&spec)
 ///     .await?;
 /// ```
+///
+/// IDs added with [`add_tree`](Self::add_tree) also load a tree of relations.
+/// All trees are resolved by one GraphQL query before the batch fetch, however
+/// many levels deep they are:
+///
+/// ```ignore
+/// let result = FetchBuilder::new()
+///     .add_tree(comment_ids, |r| {
+///         r.with_nested::<Reply>(|r| r.with_field::<Author, ReplyFields::Author>())
+///     })
+///     .add_tree(post_ids, |r| r.with::<Like>())
+///     .execute(&spec)
+///     .await?;
+/// ```
 pub struct FetchBuilder<C: ClientProvider = GlobalClient> {
     ids: Vec<String>,
+    trees: Vec<RelationTree>,
     opts: GetOpts,
     client: C,
 }
 
+/// Root IDs of one type with the relations to load for them.
+struct RelationTree {
+    type_name: String,
+    ids: Vec<String>,
+    relations: Vec<RelationSpec>,
+}
+
 impl FetchBuilder<GlobalClient> {
     /// Create a new fetch builder using the global client.
     pub fn new() -> Self {
         Self {
             ids: Vec::new(),
+            trees: Vec::new(),
             opts: GetOpts::default(),
             client: GlobalClient,
         }
@@ -219,6 +276,7 @@ impl<C: ClientProvider> FetchBuilder<C> {
     pub fn with_client(client: C) -> Self {
         Self {
             ids: Vec::new(),
+            trees: Vec::new(),
             opts: GetOpts::default(),
             client,
         }
@@ -236,6 +294,48 @@ impl<C: ClientProvider> FetchBuilder<C> {
         self
     }
 
+    /// Add IDs of type T together with the relations to load for them.
+    ///
+    /// The closure receives a [`RelationBuilder`] for T, on which relations
+    /// are added with `.with::<R>()`, `.with_field::<R, F>()` etc. and nested
+    /// with `.with_nested::<R>(|r| ...)`.
+    pub fn add_tree<T, B>(mut self, ids: impl IntoIterator<Item = EntityIDFor<T>>, build: B) -> Self
+    where
+        T: OrmModel + ToSchemaClass,
+        B: FnOnce(RelationBuilder<T>) -> RelationBuilder<T>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(|id| id.iri().to_string()).collect();
+        self.ids.extend(ids.iter().cloned());
+        self.trees.push(RelationTree {
+            type_name: T::to_class(),
+            ids,
+            relations: build(RelationBuilder::new()).relations,
+        });
+        self
+    }
+
+    /// The GraphQL query collecting the related IDs of all relation trees,
+    /// or `None` when no relations were requested.
+    pub fn build_query(&self) -> Option<String> {
+        let trees: Vec<_> = self
+            .trees
+            .iter()
+            .filter(|tree| !tree.ids.is_empty() && !tree.relations.is_empty())
+            .map(|tree| {
+                (
+                    tree.type_name.as_str(),
+                    tree.ids.as_slice(),
+                    tree.relations.as_slice(),
+                )
+            })
+            .collect();
+        if trees.is_empty() {
+            None
+        } else {
+            Some(crate::resolver::build_graphql_from_relation_trees(&trees))
+        }
+    }
+
     /// Set the get options.
     pub fn opts(mut self, opts: GetOpts) -> Self {
         self.opts = opts;
@@ -264,10 +364,95 @@ impl<C: ClientProvider> FetchBuilder<C> {
     }
 
     /// Execute the fetch and return results.
+    ///
+    /// With relation trees this takes two requests, as
+    /// [`ModelQuery::execute`](crate::ModelQuery::execute) does: one GraphQL
+    /// query for the related IDs of every tree, then one batch fetch. If the
+    /// GraphQL query fails, each tree is resolved relation by relation instead.
     pub async fn execute(self, spec: &BranchSpec) -> anyhow::Result<OrmResult>
     where
         C: MultiTypeFetch + Sync,
     {
-        self.client.fetch_by_ids(self.ids, spec, self.opts).await
+        let Some(query) = self.build_query() else {
+            return self.client.fetch_by_ids(self.ids, spec, self.opts).await;
+        };
+
+        let mut ids = self.ids.clone();
+        match self.collect_relation_ids(&query, spec).await {
+            Ok(related) => ids.extend(related),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "GraphQL relation query failed, resolving relations individually"
+                );
+                let mut report = ResolutionReport {
+                    graphql_error: Some(e.to_string()),
+                    outcomes: Vec::new(),
+                };
+                for tree in &self.trees {
+                    resolve_without_graphql(
+                        self.client.read_client(),
+                        spec,
+                        &tree.type_name,
+                        &tree.ids,
+                        &tree.relations,
+                        &mut ids,
+                        &mut report,
+                    )
+                    .await;
+                }
+                for failed in report.failed() {
+                    tracing::warn!(
+                        relation = %failed.path,
+                        error = failed.error.as_deref().unwrap_or_default(),
+                        "relation was not loaded"
+                    );
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(id.clone()));
+
+        // Relations are loaded as full entities, as with ModelQuery
+        let mut fetch_opts = self.opts;
+        fetch_opts.unfold = true;
+
+        self.client.fetch_by_ids(ids, spec, fetch_opts).await
+    }
+
+    /// Run the relation query and collect every `_id` in the response.
+    async fn collect_relation_ids(
+        &self,
+        query: &str,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<Vec<String>> {
+        use terminusdb_client::graphql::GraphQLRequest;
+
+        let response = self
+            .client
+            .read_client()
+            .execute_graphql::<serde_json::Value>(
+                &spec.db,
+                spec.branch.as_deref(),
+                GraphQLRequest::new(query),
+                None,
+            )
+            .await?;
+
+        if let Some(errors) = &response.errors {
+            if !errors.is_empty() {
+                let error_msgs: Vec<_> = errors.iter().map(|e| e.message.clone()).collect();
+                return Err(anyhow::anyhow!("GraphQL errors: {:?}", error_msgs));
+            }
+        }
+
+        let data = response
+            .data
+            .ok_or_else(|| anyhow::anyhow!("No GraphQL data returned"))?;
+
+        let mut ids = Vec::new();
+        crate::resolver::extract_ids_recursive(&data, &mut ids);
+        Ok(ids)
     }
 }
@@ -0,0 +1,134 @@
+//! ActiveRecord-style write methods on models.
+//!
+//! # Example
+//! ```ignore
+//! use terminusdb_orm::prelude::*;
+//!
+//! let mut post = BlogPost { title: "Draft".into(), writer };
+//! let id = BlogPost::create(&post, &client, &spec).await?.root_ref::<BlogPost>()?;
+//!
+//! post.title = "Published".into();
+//! post.save(&client, &spec).await?;
+//! post.save_deep(&client, &spec).await?; // also saves the linked writer
+//!
+//! let stored: Option<BlogPost> = BlogPost::fetch(&id, &client, &spec).await?;
+//!
+//! post.delete(&client, &spec).await?;
+//! ```
+
+use std::collections::HashMap;
+
+use terminusdb_client::{
+    deserialize::DefaultTDBDeserializer, BranchSpec, DeleteOpts, DocumentInsertArgs,
+    InsertInstanceResult, ResponseWithHeaders, TDBInsertInstanceResult,
+};
+use terminusdb_schema::{EntityIDFor, ToTDBSchema};
+
+use crate::{hooks, ClientProvider, OrmModel, SaveGraph};
+
+/// Write methods wrapping the client document API, with a commit message
+/// naming the model and, when known before the write, its ID (see
+/// [`TerminusDBModel::known_id`](terminusdb_schema::TerminusDBModel::known_id)).
+/// Use the ID in the returned result for models the server assigns one.
+///
+/// Reads go through [`ModelExt`](crate::ModelExt) (`Model::find(id)` and
+/// friends), or [`fetch`](Self::fetch) for a single document without
+/// relations. Writes run the model's registered [`ModelHooks`](crate::ModelHooks).
+#[async_trait::async_trait]
+pub trait Persist: OrmModel + Send + Sync + 'static {
+    /// Insert this model, or replace the stored document if it exists.
+    async fn save<C>(&self, client: &C, spec: &BranchSpec) -> anyhow::Result<InsertInstanceResult>
+    where
+        C: ClientProvider + Sync,
+    {
+        hooks::before_save(self)?;
+        client
+            .client()
+            .save_instance(self, args(spec, commit_message("Save", self)))
+            .await
+    }
+
+    /// Insert or replace this model and every model linked from its
+    /// `TdbLazy` fields in one commit, linked models first. See
+    /// [`SaveGraph`](crate::SaveGraph).
+    async fn save_deep<C>(
+        &self,
+        client: &C,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<ResponseWithHeaders<HashMap<String, TDBInsertInstanceResult>>>
+    where
+        C: ClientProvider + Sync,
+    {
+        SaveGraph::new()
+            .add(self)
+            .save(client, spec, commit_message("Save", self))
+            .await
+    }
+
+    /// Insert `model`; a stored document with the same ID is left unchanged.
+    async fn create<C>(
+        model: &Self,
+        client: &C,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<InsertInstanceResult>
+    where
+        C: ClientProvider + Sync,
+    {
+        hooks::before_save(model)?;
+        let result = client
+            .client()
+            .create_instance(model, args(spec, commit_message("Create", model)))
+            .await?;
+        if matches!(result.root_result, TDBInsertInstanceResult::Inserted(_)) {
+            hooks::after_create(model)?;
+        }
+        Ok(result)
+    }
+
+    /// Delete the stored document of this model.
+    ///
+    /// The model must have an ID (set explicitly or derived from its key).
+    async fn delete<C>(&self, client: &C, spec: &BranchSpec) -> anyhow::Result<()>
+    where
+        C: ClientProvider + Sync,
+    {
+        hooks::before_delete(self)?;
+        client
+            .client()
+            .delete_instance(
+                self,
+                args(spec, commit_message("Delete", self)),
+                DeleteOpts::document_only(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Fetch the document with `id`, or `None` if it does not exist.
+    async fn fetch<C>(
+        id: &EntityIDFor<Self>,
+        client: &C,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<Option<Self>>
+    where
+        C: ClientProvider + Sync,
+    {
+        client
+            .read_client()
+            .get_instance_if_exists::<Self>(id.typed(), spec, &mut DefaultTDBDeserializer)
+            .await
+    }
+}
+
+impl<T: OrmModel + Send + Sync + 'static> Persist for T {}
+
+fn args(spec: &BranchSpec, message: String) -> DocumentInsertArgs {
+    DocumentInsertArgs::from(spec.clone()).with_message(message)
+}
+
+fn commit_message<T: OrmModel>(verb: &str, model: &T) -> String {
+    match model.known_id() {
+        Some(id) => format!("{} {}", verb, id),
+        None => format!("{} {}", verb, T::schema_name()),
+    }
+}
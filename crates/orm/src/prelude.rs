@@ -33,6 +33,7 @@ pub use crate::{
     GraphQLRelationQuery,
     HasMany,
     HasManyField,
+    HasManyThrough,
     HasOne,
     HasOneField,
     IdQueryBuilder,
@@ -48,23 +49,47 @@ pub use crate::{
     OrmResult,
     QueryEntry,
     QueryPlan,
+    ReadRouting,
     RelationBuilder,
     RelationDirection,
     RelationOpts,
+    RelationOutcome,
     RelationPath,
     RelationPlan,
     RelationResolution,
     RelationSelection,
     RelationSpec,
+    ReplicatedClient,
+    ResolutionReport,
+    ResolutionStrategy,
     ResolvedRelations,
     ReverseRelation,
     TdbGQLFilter,
     TdbGQLOrdering,
+    TdbLazyExt,
+    Through,
+    ThroughExt,
+    TracedQuery,
+    TracedQueryKind,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use crate::{execute_id_query, RelationResolver};
 
+// Filter DSL
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::{
+    CompareOp, Condition, FieldFilter, FilterExt, FilterQuery, GroupCount, InstanceQueryable,
+};
+
+// Persistence
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::{register_hooks, ModelHooks, OrmSession, Persist, SaveGraph, Tracked};
+
+// History
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::{diff_between, ModelChange, ModelDiff};
+
 // Convenience functions
 pub use crate::{fetch_by_ids, fetch_by_ids_default};
 
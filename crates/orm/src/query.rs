@@ -30,16 +30,22 @@
 use std::any::TypeId;
 use std::marker::PhantomData;
 
+use futures_util::Stream;
 use terminusdb_client::{BranchSpec, GetOpts};
-use terminusdb_relation::RelationField;
+use terminusdb_relation::{RelationField, RelationTo};
 use terminusdb_schema::{
     json::InstanceFromJson, EntityIDFor, FromTDBInstance, TerminusDBModel, ToSchemaClass,
     ToTDBSchema,
 };
 
-use crate::relations::{ForwardRelation, ReverseRelation};
+use crate::fallback::{resolve_without_graphql, ResolutionReport};
+use crate::relations::{ForwardRelation, HasManyThrough, ReverseRelation};
+use crate::resolver::QueryPlan;
 use crate::{result::OrmResult, ClientProvider, GlobalClient, MultiTypeFetch};
-use terminusdb_schema::{TdbGQLFilter, TdbGQLOrdering, ToGql};
+use terminusdb_schema::{TdbGQLFilter, TdbGQLOrdering, TerminusOrdering, ToGql};
+
+/// Number of instances [`ModelQuery::stream`] fetches per round trip.
+pub const DEFAULT_STREAM_PAGE_SIZE: i32 = 1000;
 
 /// Options for loading a relation with filtering/pagination.
 ///
@@ -169,6 +175,63 @@ pub struct RelationSpec {
     pub order_by_gql: Option<String>,
 }
 
+impl RelationSpec {
+    /// Spec for a `HasManyThrough<Target, Via>` relation of `Source`: the Via
+    /// entities referencing the source, with the Target entities referencing
+    /// them nested inside.
+    pub(crate) fn through<Source, Target, Via>() -> Self
+    where
+        Source: HasManyThrough<Target, Via>,
+        Target: OrmModel + ToSchemaClass + 'static,
+        Via: OrmModel + ToSchemaClass + 'static,
+    {
+        let target = RelationSpec {
+            target_type_id: TypeId::of::<Target>(),
+            target_type_name: Target::to_class(),
+            direction: RelationDirection::Reverse {
+                via_field: Source::target_field().map(|s| s.to_string()),
+            },
+            children: Vec::new(),
+            filter_gql: None,
+            limit: None,
+            offset: None,
+            order_by_gql: None,
+        };
+        RelationSpec {
+            target_type_id: TypeId::of::<Via>(),
+            target_type_name: Via::to_class(),
+            direction: RelationDirection::Reverse {
+                via_field: Source::via_field().map(|s| s.to_string()),
+            },
+            children: vec![target],
+            filter_gql: None,
+            limit: None,
+            offset: None,
+            order_by_gql: None,
+        }
+    }
+
+    /// Split a reverse relation to a TaggedUnion into one spec per variant
+    /// class, as the documents holding the links are instances of the
+    /// variants. `variants` comes from `ReverseRelation::variant_relations`;
+    /// other specs are returned unchanged.
+    pub(crate) fn per_variant(self, variants: Vec<(String, Option<&'static str>)>) -> Vec<Self> {
+        if variants.is_empty() {
+            return vec![self];
+        }
+        variants
+            .into_iter()
+            .map(|(class, field)| RelationSpec {
+                target_type_name: class,
+                direction: RelationDirection::Reverse {
+                    via_field: field.map(|s| s.to_string()),
+                },
+                ..self.clone()
+            })
+            .collect()
+    }
+}
+
 /// Builder for configuring nested relations inside a `with_nested()` closure.
 ///
 /// This builder accumulates relation specs that will be nested under a parent relation.
@@ -215,18 +278,21 @@ impl<Parent: OrmModel> RelationBuilder<Parent> {
         R: OrmModel + ToSchemaClass + 'static,
         R: ReverseRelation<Parent>,
     {
-        self.relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: Vec::new(),
-            filter_gql: None,
-            limit: None,
-            offset: None,
-            order_by_gql: None,
-        });
+        self.relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: Vec::new(),
+                filter_gql: None,
+                limit: None,
+                offset: None,
+                order_by_gql: None,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -247,18 +313,21 @@ impl<Parent: OrmModel> RelationBuilder<Parent> {
         O: TdbGQLOrdering<R>,
     {
         let (filter_gql, order_by_gql) = opts.to_gql_strings();
-        self.relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: Vec::new(),
-            filter_gql,
-            limit: opts.limit,
-            offset: opts.offset,
-            order_by_gql,
-        });
+        self.relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: Vec::new(),
+                filter_gql,
+                limit: opts.limit,
+                offset: opts.offset,
+                order_by_gql,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -370,18 +439,21 @@ impl<Parent: OrmModel> RelationBuilder<Parent> {
         B: FnOnce(RelationBuilder<R>) -> RelationBuilder<R>,
     {
         let nested_builder = builder_fn(RelationBuilder::new());
-        self.relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: nested_builder.relations,
-            filter_gql: None,
-            limit: None,
-            offset: None,
-            order_by_gql: None,
-        });
+        self.relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: nested_builder.relations,
+                filter_gql: None,
+                limit: None,
+                offset: None,
+                order_by_gql: None,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -407,18 +479,40 @@ impl<Parent: OrmModel> RelationBuilder<Parent> {
     {
         let nested_builder = builder_fn(RelationBuilder::new());
         let (filter_gql, order_by_gql) = opts.to_gql_strings();
-        self.relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: nested_builder.relations,
-            filter_gql,
-            limit: opts.limit,
-            offset: opts.offset,
-            order_by_gql,
-        });
+        self.relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: nested_builder.relations,
+                filter_gql,
+                limit: opts.limit,
+                offset: opts.offset,
+                order_by_gql,
+            }
+            .per_variant(R::variant_relations()),
+        );
+        self
+    }
+
+    /// Add a two-hop relation: the Target entities that reference the Via
+    /// entities referencing Parent. Loads both Via and Target.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Post has TdbLazy<Author>, Comment has TdbLazy<Post>
+    /// b.with_through::<Comment, Post>()
+    /// ```
+    pub fn with_through<Target, Via>(mut self) -> Self
+    where
+        Target: OrmModel + ToSchemaClass + 'static,
+        Via: OrmModel + ToSchemaClass + 'static,
+        Parent: HasManyThrough<Target, Via>,
+    {
+        self.relations
+            .push(RelationSpec::through::<Parent, Target, Via>());
         self
     }
 }
@@ -598,6 +692,8 @@ pub struct ModelQuery<T: OrmModel, C: ClientProvider = GlobalClient> {
     pub(crate) offset: Option<i32>,
     /// Ordering serialized to GraphQL syntax.
     pub(crate) order_by_gql: Option<String>,
+    /// Branch to read from instead of the one in the executed spec.
+    pub(crate) branch: Option<String>,
 }
 
 impl<T: OrmModel> ModelQuery<T, GlobalClient> {
@@ -613,6 +709,7 @@ impl<T: OrmModel> ModelQuery<T, GlobalClient> {
             limit: None,
             offset: None,
             order_by_gql: None,
+            branch: None,
         }
     }
 }
@@ -645,6 +742,7 @@ impl<T: OrmModel + ToSchemaClass> ModelQuery<T, GlobalClient> {
             limit: None,
             offset: None,
             order_by_gql: None,
+            branch: None,
         }
     }
 
@@ -665,6 +763,7 @@ impl<T: OrmModel + ToSchemaClass> ModelQuery<T, GlobalClient> {
             limit: None,
             offset: None,
             order_by_gql: None,
+            branch: None,
         }
     }
 }
@@ -682,21 +781,38 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
             limit: self.limit,
             offset: self.offset,
             order_by_gql: self.order_by_gql,
+            branch: self.branch,
         }
     }
 
-    /// Set the maximum number of results (for filter-based queries).
+    /// Set the maximum number of results.
     pub fn limit(mut self, limit: i32) -> Self {
         self.limit = Some(limit);
         self
     }
 
-    /// Set the offset for pagination (for filter-based queries).
+    /// Set the offset for pagination.
     pub fn offset(mut self, offset: i32) -> Self {
         self.offset = Some(offset);
         self
     }
 
+    /// Read from `branch` instead of the branch in the spec passed to
+    /// [`execute`](Self::execute).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let staged = Writer::all()
+    ///     .on_branch("staging")
+    ///     .with_client(&client)
+    ///     .execute(&spec)
+    ///     .await?;
+    /// ```
+    pub fn on_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
     /// Set a timeout for the query execution.
     ///
     /// # Example
@@ -798,18 +914,21 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         R: OrmModel + ToSchemaClass + 'static,
         R: ReverseRelation<T>,
     {
-        self.with_relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: Vec::new(),
-            filter_gql: None,
-            limit: None,
-            offset: None,
-            order_by_gql: None,
-        });
+        self.with_relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: Vec::new(),
+                filter_gql: None,
+                limit: None,
+                offset: None,
+                order_by_gql: None,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -833,18 +952,21 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         O: TdbGQLOrdering<R>,
     {
         let (filter_gql, order_by_gql) = opts.to_gql_strings();
-        self.with_relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: Vec::new(),
-            filter_gql,
-            limit: opts.limit,
-            offset: opts.offset,
-            order_by_gql,
-        });
+        self.with_relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: Vec::new(),
+                filter_gql,
+                limit: opts.limit,
+                offset: opts.offset,
+                order_by_gql,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -946,18 +1068,21 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         B: FnOnce(RelationBuilder<R>) -> RelationBuilder<R>,
     {
         let nested_builder = builder_fn(RelationBuilder::new());
-        self.with_relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: nested_builder.relations,
-            filter_gql: None,
-            limit: None,
-            offset: None,
-            order_by_gql: None,
-        });
+        self.with_relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: nested_builder.relations,
+                filter_gql: None,
+                limit: None,
+                offset: None,
+                order_by_gql: None,
+            }
+            .per_variant(R::variant_relations()),
+        );
         self
     }
 
@@ -985,18 +1110,79 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
     {
         let nested_builder = builder_fn(RelationBuilder::new());
         let (filter_gql, order_by_gql) = opts.to_gql_strings();
-        self.with_relations.push(RelationSpec {
-            target_type_id: TypeId::of::<R>(),
-            target_type_name: R::to_class(),
-            direction: RelationDirection::Reverse {
-                via_field: R::default_field_name().map(|s| s.to_string()),
-            },
-            children: nested_builder.relations,
-            filter_gql,
-            limit: opts.limit,
-            offset: opts.offset,
-            order_by_gql,
-        });
+        self.with_relations.extend(
+            RelationSpec {
+                target_type_id: TypeId::of::<R>(),
+                target_type_name: R::to_class(),
+                direction: RelationDirection::Reverse {
+                    via_field: R::default_field_name().map(|s| s.to_string()),
+                },
+                children: nested_builder.relations,
+                filter_gql,
+                limit: opts.limit,
+                offset: opts.offset,
+                order_by_gql,
+            }
+            .per_variant(R::variant_relations()),
+        );
+        self
+    }
+
+    /// Load a tree of relations built in a closure.
+    ///
+    /// The closure receives a [`RelationBuilder`] for `T`, so a multi-level
+    /// tree reads as one expression. Like the other `with*` methods it adds to
+    /// the single GraphQL query of [`execute`](Self::execute), however deep the
+    /// tree is.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let result = Post::find(id)
+    ///     .with_tree(|r| {
+    ///         r.with::<Like>()
+    ///             .with_nested::<Comment>(|r| {
+    ///                 r.with::<Reply>()
+    ///                     .with_field::<Author, CommentFields::Author>()
+    ///             })
+    ///     })
+    ///     .execute(&spec)
+    ///     .await?;
+    /// ```
+    pub fn with_tree<B>(mut self, builder_fn: B) -> Self
+    where
+        B: FnOnce(RelationBuilder<T>) -> RelationBuilder<T>,
+    {
+        self.with_relations
+            .extend(builder_fn(RelationBuilder::new()).relations);
+        self
+    }
+
+    /// Load related entities through an intermediate model, in the same query.
+    ///
+    /// This loads the Via entities referencing the primary entities and the
+    /// Target entities referencing those, as declared by
+    /// `#[tdb(has_many_through(...))]` on `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Post has: author: TdbLazy<Author>, Comment has: post: TdbLazy<Post>
+    /// let result = Author::find(id)
+    ///     .with_through::<Comment, Post>()
+    ///     .execute(&spec)
+    ///     .await?;
+    /// let comments: Vec<Comment> = result.get()?;
+    /// ```
+    ///
+    /// # Compile-time safety
+    /// This will not compile if `T` does not implement `HasManyThrough<Target, Via>`.
+    pub fn with_through<Target, Via>(mut self) -> Self
+    where
+        Target: OrmModel + ToSchemaClass + 'static,
+        Via: OrmModel + ToSchemaClass + 'static,
+        T: HasManyThrough<Target, Via>,
+    {
+        self.with_relations
+            .push(RelationSpec::through::<T, Target, Via>());
         self
     }
 
@@ -1014,7 +1200,7 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
 
     /// Check if this is a filter-based query (vs ID-based).
     pub fn is_filter_query(&self) -> bool {
-        self.filter_gql.is_some() || (self.primary_ids.is_empty() && self.limit.is_some())
+        self.filter_gql.is_some() || (self.primary_ids.is_empty() && self.is_paginated())
     }
 
     /// Get the primary IDs being queried.
@@ -1037,6 +1223,28 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         &self.with_relations
     }
 
+    /// The plan for loading this query's relations; print
+    /// [`explain`](QueryPlan::explain) to see it as a tree.
+    ///
+    /// Filter and "all" queries list no primary IDs, since those are only
+    /// known once the query runs.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let query = Writer::find(&id).with_nested::<BlogPost>(|b| b.with::<Comment>());
+    /// println!("{}", query.plan().explain());
+    /// ```
+    pub fn plan(&self) -> QueryPlan
+    where
+        T: ToSchemaClass,
+    {
+        let mut plan = QueryPlan::new(self.primary_ids.clone(), T::to_class());
+        for relation in &self.with_relations {
+            plan.add_relation(relation.clone());
+        }
+        plan
+    }
+
     /// Execute the query and return all results.
     ///
     /// This will:
@@ -1055,33 +1263,108 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
     /// when relations are requested:
     /// 1. GraphQL query to traverse relations and collect IDs
     /// 2. Batch document fetch by all collected IDs
-    pub async fn execute(mut self, spec: &BranchSpec) -> anyhow::Result<OrmResult>
+    ///
+    /// If the GraphQL relation query fails, relations are resolved one at a
+    /// time through WOQL and the document API instead, and relations that still
+    /// cannot be resolved are left out of the result with a warning. Use
+    /// [`execute_with_report`](Self::execute_with_report) to see which.
+    pub async fn execute(self, spec: &BranchSpec) -> anyhow::Result<OrmResult>
     where
         C: MultiTypeFetch + Sync + ClientProvider,
         T: ToSchemaClass,
     {
+        let (result, report) = self.execute_with_report(spec).await?;
+
+        for failed in report.failed() {
+            tracing::warn!(
+                relation = %failed.path,
+                error = failed.error.as_deref().unwrap_or_default(),
+                "relation was not loaded"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Like [`execute`](Self::execute), but also report how each requested
+    /// relation was resolved.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let (result, report) = Writer::find(&id)
+    ///     .with::<BlogPost>()
+    ///     .execute_with_report(&spec)
+    ///     .await?;
+    ///
+    /// for failed in report.failed() {
+    ///     eprintln!("{} not loaded: {:?}", failed.path, failed.error);
+    /// }
+    /// ```
+    pub async fn execute_with_report(
+        mut self,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<(OrmResult, ResolutionReport)>
+    where
+        C: MultiTypeFetch + Sync + ClientProvider,
+        T: ToSchemaClass,
+    {
+        let spec = &self.read_spec(spec);
+
         // If no primary IDs, we need to run a GraphQL query first to find them
-        // This handles both filter-based queries and "query all" scenarios
-        if self.primary_ids.is_empty() {
+        // This handles both filter-based queries and "query all" scenarios.
+        // Explicit IDs go through the same query when ordering or pagination
+        // is requested, scoped to those IDs.
+        if self.primary_ids.is_empty() || self.is_paginated() {
             self.primary_ids = self.discover_primary_ids(spec).await?;
 
             // If still no IDs after discovery, there are no matching documents
             if self.primary_ids.is_empty() {
-                return Ok(OrmResult::empty());
+                return Ok((OrmResult::empty(), ResolutionReport::default()));
             }
         }
 
         // If no relations, simple fetch
         if self.with_relations.is_empty() {
-            return self
+            let result = self
                 .client
                 .fetch_by_ids(self.primary_ids, spec, self.opts)
-                .await;
+                .await?;
+            return Ok((result, ResolutionReport::default()));
         }
 
         // Two-phase relation loading:
-        // Phase 1: GraphQL query to collect ALL related IDs
-        let all_ids = self.collect_relation_ids(spec).await?;
+        // Phase 1: GraphQL query to collect ALL related IDs, falling back to
+        // per-relation resolution if GraphQL is unavailable
+        let primary_type = self.primary_type_name()?.to_string();
+        let (all_ids, report) = match self.collect_relation_ids(spec).await {
+            Ok(ids) => (
+                ids,
+                ResolutionReport::all_graphql(&primary_type, &self.with_relations),
+            ),
+            Err(e) => {
+                tracing::warn!(
+                    primary_type = %primary_type,
+                    error = %e,
+                    "GraphQL relation query failed, resolving relations individually"
+                );
+                let mut ids = self.primary_ids.clone();
+                let mut report = ResolutionReport {
+                    graphql_error: Some(e.to_string()),
+                    outcomes: Vec::new(),
+                };
+                resolve_without_graphql(
+                    self.client.read_client(),
+                    spec,
+                    &primary_type,
+                    &self.primary_ids,
+                    &self.with_relations,
+                    &mut ids,
+                    &mut report,
+                )
+                .await;
+                (ids, report)
+            }
+        };
 
         // Phase 2: Single batch fetch of all documents
         // Always enable unfold for relation queries to get full subdocument data
@@ -1089,7 +1372,46 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         let mut fetch_opts = self.opts;
         fetch_opts.unfold = true;
 
-        self.client.fetch_by_ids(all_ids, spec, fetch_opts).await
+        let result = self.client.fetch_by_ids(all_ids, spec, fetch_opts).await?;
+        Ok((result, report))
+    }
+
+    /// Execute the query against the database as of the commit `spec`
+    /// references, e.g. `spec.clone().ref_commit(commit_id)`.
+    ///
+    /// Filters, ordering and relations are all resolved at that commit.
+    /// Returns an error if `spec` has no commit reference.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let then = Writer::find(&id)
+    ///     .with::<BlogPost>()
+    ///     .with_client(&client)
+    ///     .execute_at(&spec.clone().ref_commit(commit_id))
+    ///     .await?;
+    /// ```
+    pub async fn execute_at(self, spec: &BranchSpec) -> anyhow::Result<OrmResult>
+    where
+        C: MultiTypeFetch + Sync + ClientProvider,
+        T: ToSchemaClass,
+    {
+        if !spec.is_commit_ref() {
+            return Err(anyhow::anyhow!(
+                "execute_at needs a spec with a commit reference; use execute to read a branch"
+            ));
+        }
+        self.execute(spec).await
+    }
+
+    /// `spec` with the branch set by [`on_branch`](Self::on_branch), if any.
+    fn read_spec(&self, spec: &BranchSpec) -> BranchSpec {
+        match &self.branch {
+            Some(branch) => BranchSpec {
+                branch: Some(branch.clone()),
+                ..spec.clone()
+            },
+            None => spec.clone(),
+        }
     }
 
     /// Discover primary IDs by executing a GraphQL query.
@@ -1107,6 +1429,11 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
 
         // Build GraphQL query arguments
         let mut args = Vec::new();
+        if !self.primary_ids.is_empty() {
+            let ids_json =
+                serde_json::to_string(&self.primary_ids).unwrap_or_else(|_| "[]".to_string());
+            args.push(format!("ids: {}", ids_json));
+        }
         if let Some(filter) = &self.filter_gql {
             args.push(format!("filter: {}", filter));
         }
@@ -1136,8 +1463,8 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         let request = GraphQLRequest::new(&graphql_query);
         let response = self
             .client
-            .client()
-            .execute_graphql::<serde_json::Value>(&spec.db, spec.branch.as_deref(), request, None)
+            .read_client()
+            .execute_graphql_at::<serde_json::Value>(spec, request, None)
             .await?;
 
         // Check for errors
@@ -1165,23 +1492,12 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         Ok(ids)
     }
 
-    /// Collect all related entity IDs using a single GraphQL query.
-    ///
-    /// This generates a GraphQL query that traverses all requested relations
-    /// (including nested ones) and extracts only the `_id` fields.
-    async fn collect_relation_ids(&self, spec: &BranchSpec) -> anyhow::Result<Vec<String>>
-    where
-        C: ClientProvider,
-    {
-        use crate::resolver::{build_graphql_from_relation_specs, extract_ids_recursive};
-        use terminusdb_client::graphql::GraphQLRequest;
-
-        // Get the primary type name from the ID
+    /// Get the primary type name from the first primary ID.
+    fn primary_type_name(&self) -> anyhow::Result<&str> {
         // IDs can be:
         // - "Writer/123" -> "Writer"
         // - "terminusdb:///data/Writer/123" -> "Writer"
-        let primary_type = self
-            .primary_ids
+        self.primary_ids
             .first()
             .and_then(|id| {
                 // Handle both short and full URI formats
@@ -1193,7 +1509,21 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
                     id.split('/').next()
                 }
             })
-            .ok_or_else(|| anyhow::anyhow!("Could not extract type from ID"))?;
+            .ok_or_else(|| anyhow::anyhow!("Could not extract type from ID"))
+    }
+
+    /// Collect all related entity IDs using a single GraphQL query.
+    ///
+    /// This generates a GraphQL query that traverses all requested relations
+    /// (including nested ones) and extracts only the `_id` fields.
+    async fn collect_relation_ids(&self, spec: &BranchSpec) -> anyhow::Result<Vec<String>>
+    where
+        C: ClientProvider,
+    {
+        use crate::resolver::{build_graphql_from_relation_specs, extract_ids_recursive};
+        use terminusdb_client::graphql::GraphQLRequest;
+
+        let primary_type = self.primary_type_name()?;
 
         // Build GraphQL query from relation specs
         let graphql_query = build_graphql_from_relation_specs(
@@ -1206,8 +1536,8 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
         let request = GraphQLRequest::new(&graphql_query);
         let response = self
             .client
-            .client()
-            .execute_graphql::<serde_json::Value>(&spec.db, spec.branch.as_deref(), request, None)
+            .read_client()
+            .execute_graphql_at::<serde_json::Value>(spec, request, None)
             .await?;
 
         // Check for errors
@@ -1256,6 +1586,85 @@ impl<T: OrmModel, C: ClientProvider> ModelQuery<T, C> {
             anyhow::anyhow!("No {} found for the given ID", std::any::type_name::<T>())
         })
     }
+
+    /// Stream the matching instances of the primary type, fetched
+    /// [`DEFAULT_STREAM_PAGE_SIZE`] at a time.
+    ///
+    /// See [`stream_paged`](Self::stream_paged).
+    pub fn stream(self, spec: &BranchSpec) -> impl Stream<Item = anyhow::Result<T>>
+    where
+        C: MultiTypeFetch + Sync + ClientProvider,
+        T: ToSchemaClass,
+    {
+        self.stream_paged(spec, DEFAULT_STREAM_PAGE_SIZE)
+    }
+
+    /// Stream the matching instances of the primary type, fetching
+    /// `page_size` of them per round trip so that only one page is held in
+    /// memory at a time.
+    ///
+    /// Each page is a `limit`/`offset` window of the same query, so filters
+    /// and ordering apply across pages, and a `limit` or `offset` set on the
+    /// query bounds the whole stream. Relations requested with `.with*()` are
+    /// not loaded. Add an ordering for a stable order when the data can change
+    /// while streaming.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mut writers = std::pin::pin!(Writer::all()
+    ///     .with_client(&client)
+    ///     .stream_paged(&spec, 500));
+    /// while let Some(writer) = writers.try_next().await? {
+    ///     println!("{}", writer.name);
+    /// }
+    /// ```
+    pub fn stream_paged(
+        mut self,
+        spec: &BranchSpec,
+        page_size: i32,
+    ) -> impl Stream<Item = anyhow::Result<T>>
+    where
+        C: MultiTypeFetch + Sync + ClientProvider,
+        T: ToSchemaClass,
+    {
+        let spec = self.read_spec(spec);
+        let page_size = page_size.max(1);
+        let start = self.offset.unwrap_or(0);
+        let end = self.limit.map(|limit| start + limit);
+
+        async_stream::try_stream! {
+            let mut offset = start;
+            loop {
+                let size = end.map_or(page_size, |end| page_size.min(end - offset));
+                if size <= 0 {
+                    break;
+                }
+
+                self.offset = Some(offset);
+                self.limit = Some(size);
+                let ids = self.discover_primary_ids(&spec).await?;
+                let fetched = ids.len() as i32;
+                if fetched == 0 {
+                    break;
+                }
+
+                let page = self
+                    .client
+                    .fetch_by_ids(ids, &spec, self.opts.clone())
+                    .await?;
+                for model in page.get::<T>()? {
+                    yield model;
+                }
+
+                if fetched < size {
+                    break;
+                }
+                offset += fetched;
+            }
+        }
+    }
 }
 
 /// Additional methods for queries with ordering.
@@ -1281,6 +1690,41 @@ where
         self.order_by_gql = Some(order_by.to_gql());
         self
     }
+
+    /// Order results by a field, named by its derive-generated marker.
+    ///
+    /// Repeated calls add secondary sort keys, in call order; the field must
+    /// belong to `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let page = Person::query_all()
+    ///     .order_by_field(PersonFields::Name, TerminusOrdering::Desc)
+    ///     .order_by_field(PersonFields::Age, TerminusOrdering::Asc)
+    ///     .offset(20)
+    ///     .limit(10);
+    /// ```
+    pub fn order_by_field<F, V>(mut self, _field: F, direction: TerminusOrdering) -> Self
+    where
+        F: RelationField,
+        T: RelationTo<V, F>,
+    {
+        let key = format!("{}: {}", F::field_name(), direction.to_gql());
+        self.order_by_gql = Some(match self.order_by_gql.take() {
+            Some(existing) => match existing.trim().strip_suffix('}') {
+                Some(body) if body.trim_end() != "{" => format!("{}, {}}}", body.trim_end(), key),
+                _ => format!("{{{}}}", key),
+            },
+            None => format!("{{{}}}", key),
+        });
+        self
+    }
+
+    /// Whether ordering or pagination applies, which requires resolving the
+    /// primary IDs through GraphQL even when they were given explicitly.
+    fn is_paginated(&self) -> bool {
+        self.order_by_gql.is_some() || self.limit.is_some() || self.offset.is_some()
+    }
 }
 
 /// Implementation of IntoQueryPart for ModelQuery.
@@ -3,19 +3,26 @@
 //! Provides both:
 //! - **Wrapper types** (`HasOneField`, `HasManyField`, `BelongsToField`) for field storage
 //! - **Traits** (`HasOne`, `HasMany`, `BelongsTo`) for compile-time relation reasoning
+//! - **`ThroughExt`** for loading `HasManyThrough` relations from a model instance
+//! - **`TdbLazyExt`** for resolving a `TdbLazy` reference when it is accessed
 
 use std::ops::{Deref, DerefMut};
 
+use terminusdb_client::{BranchSpec, GetOpts};
 use terminusdb_relation::RelationField;
-use terminusdb_schema::{EntityIDFor, TdbLazy, TerminusDBModel};
+use terminusdb_schema::{EntityIDFor, TdbLazy, TerminusDBModel, ToSchemaClass};
 
+use crate::query::{ModelQuery, OrmModel};
 use crate::result::OrmResult;
+use crate::{GlobalClient, MultiTypeFetch};
 
 // ============================================================================
 // Re-export ORM relation traits from terminusdb_relation
 // ============================================================================
 
-pub use terminusdb_relation::{BelongsTo, DefaultField, ForwardRelation, ReverseRelation};
+pub use terminusdb_relation::{
+    BelongsTo, DefaultField, ForwardRelation, HasManyThrough, ReverseRelation, Through,
+};
 
 // ============================================================================
 // Additional Relation Traits (for compile-time reasoning)
@@ -47,6 +54,107 @@ pub trait HasMany<T: TerminusDBModel, Field: RelationField = DefaultField> {
     fn set_from_result(&mut self, result: &OrmResult) -> anyhow::Result<()>;
 }
 
+/// Loads the [`Through`] relations returned by derived accessor methods.
+///
+/// # Example
+/// ```ignore
+/// #[derive(TerminusDBModel)]
+/// #[tdb(has_many_through(name = "comments", target = "Comment", via = "Post"))]
+/// struct Author { name: String }
+///
+/// // One GraphQL query for the posts and their comments, one batch fetch
+/// let comments: Vec<Comment> = author.comments().execute(&spec).await?;
+/// ```
+#[async_trait::async_trait]
+pub trait ThroughExt<Source: OrmModel, Target> {
+    /// The query for the source entity with the relation attached, e.g. to
+    /// use another client or to keep the intermediate entities.
+    ///
+    /// Fails if the source instance has no ID.
+    fn query(self) -> anyhow::Result<ModelQuery<Source, GlobalClient>>;
+
+    /// Load the target entities.
+    async fn execute(self, spec: &BranchSpec) -> anyhow::Result<Vec<Target>>;
+}
+
+#[async_trait::async_trait]
+impl<Source, Target, Via> ThroughExt<Source, Target> for Through<Source, Target, Via>
+where
+    Source: HasManyThrough<Target, Via> + OrmModel + ToSchemaClass + Send + Sync + 'static,
+    Target: OrmModel + ToSchemaClass + Send + Sync + 'static,
+    Via: OrmModel + ToSchemaClass + Send + Sync + 'static,
+{
+    fn query(self) -> anyhow::Result<ModelQuery<Source, GlobalClient>> {
+        let id = self.source_id().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} instance has no ID to load relations from",
+                Source::to_class()
+            )
+        })?;
+        Ok(ModelQuery::new([id.iri().to_string()]).with_through::<Target, Via>())
+    }
+
+    async fn execute(self, spec: &BranchSpec) -> anyhow::Result<Vec<Target>> {
+        let query = self.query()?;
+        query.execute(spec).await?.get::<Target>()
+    }
+}
+
+/// Resolves a [`TdbLazy`] reference through a client on first access.
+///
+/// Models returned by the ORM hold their links as references unless the
+/// relation was loaded. These methods fetch the referenced document when it is
+/// needed and cache it in the link, so it is fetched at most once.
+///
+/// # Example
+/// ```ignore
+/// let post: BlogPost = BlogPost::find(id).execute_one(&spec).await?;
+///
+/// // Fetches the writer once; later calls and `get_expect()` use the cache
+/// let writer: &Writer = post.writer.get_or_fetch(&spec).await?;
+/// ```
+#[async_trait::async_trait]
+pub trait TdbLazyExt<T> {
+    /// The referenced document, fetched with `client` unless the link already
+    /// holds it.
+    async fn load<C>(&self, client: &C, spec: &BranchSpec) -> anyhow::Result<&T>
+    where
+        C: MultiTypeFetch + Sync;
+
+    /// Like [`load`](Self::load), with the global client.
+    async fn get_or_fetch(&self, spec: &BranchSpec) -> anyhow::Result<&T>;
+}
+
+#[async_trait::async_trait]
+impl<T> TdbLazyExt<T> for TdbLazy<T>
+where
+    T: OrmModel + Send + Sync,
+{
+    async fn load<C>(&self, client: &C, spec: &BranchSpec) -> anyhow::Result<&T>
+    where
+        C: MultiTypeFetch + Sync,
+    {
+        if let Some(data) = self.loaded() {
+            return Ok(data);
+        }
+
+        let id = self.id().iri().to_string();
+        let result = client
+            .fetch_by_ids(vec![id.clone()], spec, GetOpts::default())
+            .await?;
+        let data = result
+            .get::<T>()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Document {} not found", id))?;
+        Ok(self.cache_fetched(data))
+    }
+
+    async fn get_or_fetch(&self, spec: &BranchSpec) -> anyhow::Result<&T> {
+        self.load(&GlobalClient, spec).await
+    }
+}
+
 // ============================================================================
 // Wrapper Types (for field storage with lazy loading)
 // ============================================================================
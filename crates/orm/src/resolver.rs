@@ -27,9 +27,18 @@
 
 use std::any::TypeId;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use terminusdb_client::{BranchSpec, GetOpts};
 
 use crate::query::{RelationDirection, RelationSpec};
 use crate::result::OrmResult;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{ClientProvider, MultiTypeFetch};
 
 /// Strategy for loading relations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -131,6 +140,93 @@ impl QueryPlan {
         self.strategy = strategy;
         self
     }
+
+    /// Render the plan as a tree, one line per relation.
+    ///
+    /// ```text
+    /// Writer [2 ids, Batch]
+    /// ├── Writer <- BlogPost.writer
+    /// │   └── BlogPost <- Comment.post (limit: 5)
+    /// └── Writer.profile -> Profile
+    /// ```
+    pub fn explain(&self) -> String {
+        let ids = match self.primary_ids.len() {
+            0 => "ids found at execution".to_string(),
+            1 => "1 id".to_string(),
+            n => format!("{} ids", n),
+        };
+        let mut out = format!("{} [{}, {:?}]\n", self.primary_type, ids, self.strategy);
+
+        let count = self.relation_plans.len();
+        for (i, plan) in self.relation_plans.iter().enumerate() {
+            explain_relation(&mut out, &self.primary_type, &plan.spec, "", i + 1 == count);
+        }
+        out
+    }
+}
+
+/// Write one relation line of [`QueryPlan::explain`] and its children.
+fn explain_relation(
+    out: &mut String,
+    parent_type: &str,
+    rel: &RelationSpec,
+    prefix: &str,
+    last: bool,
+) {
+    let mut options = Vec::new();
+    if let Some(filter) = &rel.filter_gql {
+        options.push(format!("filter: {}", filter));
+    }
+    if let Some(order_by) = &rel.order_by_gql {
+        options.push(format!("orderBy: {}", order_by));
+    }
+    if let Some(limit) = rel.limit {
+        options.push(format!("limit: {}", limit));
+    }
+    if let Some(offset) = rel.offset {
+        options.push(format!("offset: {}", offset));
+    }
+    let options = if options.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", options.join(", "))
+    };
+
+    out.push_str(&format!(
+        "{}{}{}{}\n",
+        prefix,
+        if last { "└── " } else { "├── " },
+        relation_label(parent_type, rel),
+        options
+    ));
+
+    let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+    let count = rel.children.len();
+    for (i, child) in rel.children.iter().enumerate() {
+        explain_relation(
+            out,
+            &rel.target_type_name,
+            child,
+            &child_prefix,
+            i + 1 == count,
+        );
+    }
+}
+
+/// Short description of a relation from `parent_type`, e.g.
+/// `Writer.profile -> Profile` or `Writer <- BlogPost.writer`.
+fn relation_label(parent_type: &str, rel: &RelationSpec) -> String {
+    match &rel.direction {
+        RelationDirection::Forward { field_name } => {
+            format!("{}.{} -> {}", parent_type, field_name, rel.target_type_name)
+        }
+        RelationDirection::Reverse {
+            via_field: Some(field),
+        } => format!("{} <- {}.{}", parent_type, rel.target_type_name, field),
+        RelationDirection::Reverse { via_field: None } => {
+            format!("{} <- {}", parent_type, rel.target_type_name)
+        }
+    }
 }
 
 /// Result of relation resolution, with entities organized by type and relation.
@@ -195,23 +291,350 @@ pub trait RelationResolver: Send + Sync {
 }
 
 // ============================================================================
-// Batch Resolution Implementation (Stub)
+// Batch Resolution Implementation
 // ============================================================================
 
-/// Batch resolver that executes relations in two phases.
+/// Batch resolver that executes relations in two phases: one GraphQL query
+/// collects the related IDs, then one document fetch loads them.
+///
+/// # Debug Mode
+///
+/// A resolver built with [`with_debug`](Self::with_debug) records every query
+/// it sends, with its duration, and counts how often each relation is
+/// resolved. Resolving the same relation more than once (typically
+/// [`resolve_forward`](RelationResolver::resolve_forward) called in a loop over
+/// sibling entities) is the N+1 pattern and logs a warning; collect the parent
+/// IDs and resolve them in one call instead.
+///
+/// ```ignore
+/// let resolver = BatchResolver::new(&client).with_debug();
+/// for post in &posts {
+///     resolver.resolve_forward(vec![post.id()], "writer", "Writer", &spec).await?;
+/// }
+/// assert!(!resolver.repeated_relations().is_empty());
+/// for query in resolver.queries() {
+///     println!("{:?} {} took {:?}", query.kind, query.relation, query.elapsed);
+/// }
+/// ```
 #[derive(Debug, Clone)]
 pub struct BatchResolver<C> {
-    #[allow(dead_code)]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
     client: C,
+    /// Shared by clones, so a cloned resolver records into the same log.
+    trace: Option<Arc<QueryTrace>>,
+}
+
+/// Kind of request recorded by a [`BatchResolver`] in debug mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TracedQueryKind {
+    /// GraphQL query collecting related IDs.
+    GraphQL,
+    /// Batch document fetch by ID.
+    DocumentFetch,
+}
+
+/// One request sent by a [`BatchResolver`] in debug mode.
+#[derive(Debug, Clone)]
+pub struct TracedQuery {
+    pub kind: TracedQueryKind,
+    /// The relation being resolved, e.g. `Writer <- BlogPost.writer`, or the
+    /// primary type when a whole plan is resolved.
+    pub relation: String,
+    /// GraphQL text, or the fetched IDs for a document fetch.
+    pub query: String,
+    /// Time until the response arrived, including failed requests.
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Default)]
+struct QueryTrace {
+    queries: Mutex<Vec<TracedQuery>>,
+    resolutions: Mutex<HashMap<String, usize>>,
 }
 
 impl<C> BatchResolver<C> {
     /// Create a new batch resolver with the given client.
     pub fn new(client: C) -> Self {
-        Self { client }
+        Self {
+            client,
+            trace: None,
+        }
+    }
+
+    /// Record queries and warn about relations resolved repeatedly.
+    pub fn with_debug(mut self) -> Self {
+        self.trace.get_or_insert_with(Default::default);
+        self
+    }
+
+    /// Whether debug mode is on.
+    pub fn is_debug(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Queries sent so far, oldest first. Empty unless in debug mode.
+    pub fn queries(&self) -> Vec<TracedQuery> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.queries.lock().expect("query trace poisoned").clone())
+            .unwrap_or_default()
+    }
+
+    /// Relations resolved more than once, with how often, ordered by relation.
+    /// Empty unless in debug mode.
+    pub fn repeated_relations(&self) -> Vec<(String, usize)> {
+        let Some(trace) = &self.trace else {
+            return Vec::new();
+        };
+        let mut repeated: Vec<_> = trace
+            .resolutions
+            .lock()
+            .expect("query trace poisoned")
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(relation, count)| (relation.clone(), *count))
+            .collect();
+        repeated.sort();
+        repeated
+    }
+
+    /// Forget the recorded queries and resolution counts.
+    pub fn clear_trace(&self) {
+        if let Some(trace) = &self.trace {
+            trace.queries.lock().expect("query trace poisoned").clear();
+            trace
+                .resolutions
+                .lock()
+                .expect("query trace poisoned")
+                .clear();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn record(&self, kind: TracedQueryKind, relation: &str, query: String, elapsed: Duration) {
+        if let Some(trace) = &self.trace {
+            tracing::debug!(?kind, relation, ?elapsed, %query, "resolver query");
+            trace
+                .queries
+                .lock()
+                .expect("query trace poisoned")
+                .push(TracedQuery {
+                    kind,
+                    relation: relation.to_string(),
+                    query,
+                    elapsed,
+                });
+        }
+    }
+
+    /// Count a resolution of `relation`, warning on the first repeat.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn note_resolution(&self, relation: &str) {
+        let Some(trace) = &self.trace else {
+            return;
+        };
+        let mut resolutions = trace.resolutions.lock().expect("query trace poisoned");
+        let count = resolutions.entry(relation.to_string()).or_default();
+        *count += 1;
+        if *count == 2 {
+            tracing::warn!(
+                relation,
+                "relation resolved again for a sibling entity (N+1); \
+                 resolve all parent IDs in one call instead"
+            );
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<C: ClientProvider + MultiTypeFetch + Sync> BatchResolver<C> {
+    /// Collect the IDs of `primary_ids` and everything related to them with
+    /// one GraphQL query.
+    async fn collect_ids(
+        &self,
+        label: &str,
+        primary_type: &str,
+        primary_ids: &[String],
+        relations: &[RelationSpec],
+        spec: &BranchSpec,
+    ) -> anyhow::Result<Vec<String>> {
+        use terminusdb_client::graphql::GraphQLRequest;
+
+        let query = build_graphql_from_relation_specs(primary_type, primary_ids, relations);
+        let started = Instant::now();
+        let response = self
+            .client
+            .read_client()
+            .execute_graphql_at::<serde_json::Value>(spec, GraphQLRequest::new(&query), None)
+            .await;
+        self.record(TracedQueryKind::GraphQL, label, query, started.elapsed());
+        let response = response?;
+
+        if let Some(errors) = &response.errors {
+            if !errors.is_empty() {
+                let error_msgs: Vec<_> = errors.iter().map(|e| e.message.clone()).collect();
+                return Err(anyhow::anyhow!("GraphQL errors: {:?}", error_msgs));
+            }
+        }
+        let data = response
+            .data
+            .ok_or_else(|| anyhow::anyhow!("No GraphQL data returned"))?;
+
+        let mut ids = primary_ids.to_vec();
+        extract_ids_recursive(&data, &mut ids);
+        Ok(ids)
+    }
+
+    /// Fetch `ids` with subdocuments unfolded.
+    async fn fetch(
+        &self,
+        label: &str,
+        ids: Vec<String>,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<OrmResult> {
+        let mut opts = GetOpts::default();
+        opts.unfold = true;
+
+        let query = ids.join(", ");
+        let started = Instant::now();
+        let result = self.client.fetch_by_ids(ids, spec, opts).await;
+        self.record(
+            TracedQueryKind::DocumentFetch,
+            label,
+            query,
+            started.elapsed(),
+        );
+        result
+    }
+
+    /// Resolve a single relation of `parent_ids`, returning only the related
+    /// entities.
+    async fn resolve_one(
+        &self,
+        parent_ids: Vec<String>,
+        rel: RelationSpec,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<OrmResult> {
+        let parent_type = parent_ids
+            .first()
+            .and_then(|id| id_type(id))
+            .ok_or_else(|| anyhow::anyhow!("Could not extract type from parent IDs"))?
+            .to_string();
+        let label = relation_label(&parent_type, &rel);
+        self.note_resolution(&label);
+
+        let mut ids = self
+            .collect_ids(&label, &parent_type, &parent_ids, &[rel], spec)
+            .await?;
+        ids.retain(|id| {
+            !parent_ids
+                .iter()
+                .any(|parent| short_id(parent) == short_id(id))
+        });
+        self.fetch(&label, ids, spec).await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait::async_trait]
+impl<C: ClientProvider + MultiTypeFetch + Send + Sync> RelationResolver for BatchResolver<C> {
+    async fn resolve(
+        &self,
+        plan: QueryPlan,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<ResolvedRelations> {
+        let relations: Vec<RelationSpec> = match plan.strategy {
+            LoadStrategy::Lazy => Vec::new(),
+            LoadStrategy::Batch | LoadStrategy::SingleQuery => plan
+                .relation_plans
+                .into_iter()
+                .map(|relation| relation.spec)
+                .collect(),
+        };
+
+        for rel in &relations {
+            self.note_resolution(&relation_label(&plan.primary_type, rel));
+        }
+
+        let ids = if relations.is_empty() {
+            plan.primary_ids
+        } else {
+            self.collect_ids(
+                &plan.primary_type,
+                &plan.primary_type,
+                &plan.primary_ids,
+                &relations,
+                spec,
+            )
+            .await?
+        };
+
+        let result = self.fetch(&plan.primary_type, ids, spec).await?;
+        Ok(ResolvedRelations::new(result))
+    }
+
+    async fn resolve_forward(
+        &self,
+        parent_ids: Vec<String>,
+        field_name: &str,
+        target_type: &str,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<OrmResult> {
+        let rel = single_relation(
+            target_type,
+            RelationDirection::Forward {
+                field_name: field_name.to_string(),
+            },
+        );
+        self.resolve_one(parent_ids, rel, spec).await
+    }
+
+    async fn resolve_reverse(
+        &self,
+        parent_ids: Vec<String>,
+        field_name: Option<&str>,
+        target_type: &str,
+        spec: &BranchSpec,
+    ) -> anyhow::Result<OrmResult> {
+        let rel = single_relation(
+            target_type,
+            RelationDirection::Reverse {
+                via_field: field_name.map(str::to_string),
+            },
+        );
+        self.resolve_one(parent_ids, rel, spec).await
     }
 }
 
+/// An unfiltered relation to `target_type` with no nested relations.
+#[cfg(not(target_arch = "wasm32"))]
+fn single_relation(target_type: &str, direction: RelationDirection) -> RelationSpec {
+    RelationSpec {
+        // Only the type name is used to build the query.
+        target_type_id: TypeId::of::<()>(),
+        target_type_name: target_type.to_string(),
+        direction,
+        children: Vec::new(),
+        filter_gql: None,
+        limit: None,
+        offset: None,
+        order_by_gql: None,
+    }
+}
+
+/// Type name of a document ID, from `Writer/123` or
+/// `terminusdb:///data/Writer/123`.
+#[cfg(not(target_arch = "wasm32"))]
+fn id_type(id: &str) -> Option<&str> {
+    short_id(id).split('/').next()
+}
+
+/// `Writer/123` for both `Writer/123` and `terminusdb:///data/Writer/123`.
+#[cfg(not(target_arch = "wasm32"))]
+fn short_id(id: &str) -> &str {
+    id.split_once("///data/").map_or(id, |(_, rest)| rest)
+}
+
 // ============================================================================
 // GraphQL Query Generation
 // ============================================================================
@@ -577,20 +1000,72 @@ pub fn build_graphql_from_relation_specs(
 ) -> String {
     let mut query = String::new();
     query.push_str("query {\n");
+    write_relation_root(&mut query, None, primary_type, primary_ids, relations);
+    query.push_str("}\n");
+
+    tracing::trace!(
+        primary_type,
+        primary_ids = primary_ids.len(),
+        relations = relations.len(),
+        %query,
+        "generated relation query"
+    );
+    query
+}
+
+/// Build one GraphQL query for several relation trees, each given as
+/// `(root type, root IDs, relations)`.
+///
+/// Every root is aliased (`tree0`, `tree1`, ...) so the same type can appear
+/// more than once. Used by [`FetchBuilder`](crate::FetchBuilder) to collect the
+/// related IDs of all its trees in a single request.
+pub(crate) fn build_graphql_from_relation_trees(
+    trees: &[(&str, &[String], &[RelationSpec])],
+) -> String {
+    let mut query = String::new();
+    query.push_str("query {\n");
+    for (i, (primary_type, primary_ids, relations)) in trees.iter().enumerate() {
+        let alias = format!("tree{}", i);
+        write_relation_root(
+            &mut query,
+            Some(&alias),
+            primary_type,
+            primary_ids,
+            relations,
+        );
+    }
+    query.push_str("}\n");
+
+    tracing::trace!(trees = trees.len(), %query, "generated relation query");
+    query
+}
+
+/// Write the selection of a root type, scoped to its IDs, with its relations.
+fn write_relation_root(
+    query: &mut String,
+    alias: Option<&str>,
+    primary_type: &str,
+    primary_ids: &[String],
+    relations: &[RelationSpec],
+) {
+    let alias = alias.map(|a| format!("{}: ", a)).unwrap_or_default();
 
     // Primary type, scoped to the primary IDs. With a single ID use `id:`;
     // with many (e.g. a filter that matched multiple primaries) use `ids: [...]`
     // so we don't fall back to scanning the entire type and ignoring the filter.
     if primary_ids.len() == 1 {
         query.push_str(&format!(
-            "  {}(id: \"{}\") {{\n",
-            primary_type, primary_ids[0]
+            "  {}{}(id: \"{}\") {{\n",
+            alias, primary_type, primary_ids[0]
         ));
     } else if !primary_ids.is_empty() {
         let ids_json = serde_json::to_string(primary_ids).unwrap_or_else(|_| "[]".to_string());
-        query.push_str(&format!("  {}(ids: {}) {{\n", primary_type, ids_json));
+        query.push_str(&format!(
+            "  {}{}(ids: {}) {{\n",
+            alias, primary_type, ids_json
+        ));
     } else {
-        query.push_str(&format!("  {} {{\n", primary_type));
+        query.push_str(&format!("  {}{} {{\n", alias, primary_type));
     }
 
     // Always include _id on primary type
@@ -598,12 +1073,10 @@ pub fn build_graphql_from_relation_specs(
 
     // Write relation selections
     for rel in relations {
-        write_relation_spec(&mut query, rel, primary_type, 4);
+        write_relation_spec(query, rel, primary_type, 4);
     }
 
     query.push_str("  }\n");
-    query.push_str("}\n");
-    query
 }
 
 /// Recursively write a RelationSpec to a GraphQL query string.
@@ -1094,4 +1567,71 @@ mod tests {
         assert!(!query.contains("offset:"));
         assert!(!query.contains("orderBy:"));
     }
+
+    fn relation(target: &str, direction: RelationDirection) -> RelationSpec {
+        RelationSpec {
+            target_type_id: TypeId::of::<()>(),
+            target_type_name: target.to_string(),
+            direction,
+            children: Vec::new(),
+            filter_gql: None,
+            limit: None,
+            offset: None,
+            order_by_gql: None,
+        }
+    }
+
+    #[test]
+    fn test_query_plan_explain_renders_tree() {
+        let mut posts = relation(
+            "BlogPost",
+            RelationDirection::Reverse {
+                via_field: Some("writer".to_string()),
+            },
+        );
+        let mut comments = relation("Comment", RelationDirection::Reverse { via_field: None });
+        comments.limit = Some(5);
+        posts.children.push(comments);
+
+        let mut plan = QueryPlan::new(
+            vec!["Writer/1".to_string(), "Writer/2".to_string()],
+            "Writer".to_string(),
+        );
+        plan.add_relation(posts);
+        plan.add_relation(relation(
+            "Profile",
+            RelationDirection::Forward {
+                field_name: "profile".to_string(),
+            },
+        ));
+
+        assert_eq!(
+            plan.explain(),
+            "Writer [2 ids, Batch]\n\
+             ├── Writer <- BlogPost.writer\n\
+             │   └── BlogPost <- Comment (limit: 5)\n\
+             └── Writer.profile -> Profile\n"
+        );
+    }
+
+    #[test]
+    fn test_batch_resolver_counts_repeated_relations() {
+        let resolver = BatchResolver::new(());
+        resolver.note_resolution("Post.writer -> Writer");
+        assert!(resolver.repeated_relations().is_empty(), "off by default");
+
+        let resolver = resolver.with_debug();
+        let clone = resolver.clone();
+        resolver.note_resolution("Post.writer -> Writer");
+        clone.note_resolution("Post.writer -> Writer");
+        resolver.note_resolution("Post <- Comment.post");
+
+        assert_eq!(
+            resolver.repeated_relations(),
+            vec![("Post.writer -> Writer".to_string(), 2)]
+        );
+
+        resolver.clear_trace();
+        assert!(clone.repeated_relations().is_empty());
+    }
 }
@@ -5,7 +5,9 @@
 
 use std::collections::HashMap;
 
-use terminusdb_schema::{json::InstanceFromJson, EntityIDFor, FromTDBInstance, ToTDBSchema};
+use terminusdb_schema::{
+    json::InstanceFromJson, EntityIDFor, FromTDBInstance, Schema, TerminusDBModel, ToTDBSchema,
+};
 
 /// Result container for ORM fetch operations.
 ///
@@ -72,6 +74,23 @@ impl OrmResult {
         doc.get("@type").and_then(|v| v.as_str())
     }
 
+    /// The classes whose documents deserialize as T: its own class and, for a
+    /// TaggedUnion, the class of each variant. Documents linked through a
+    /// `TdbLazy<Union>` are stored as instances of the variant classes.
+    fn classes_of<T: ToTDBSchema>() -> Vec<String> {
+        let schema = T::to_schema();
+        let mut classes = vec![schema.class_name().to_string()];
+        if let Schema::TaggedUnion { properties, .. } = &schema {
+            classes.extend(properties.iter().map(|p| p.class.clone()));
+        }
+        classes
+    }
+
+    /// Check whether a JSON document's `@type` is one of `classes`.
+    fn has_class(doc: &serde_json::Value, classes: &[String]) -> bool {
+        Self::get_type(doc).is_some_and(|t| classes.iter().any(|c| c == t))
+    }
+
     /// Extract the `@id` field from a JSON document.
     fn get_id(doc: &serde_json::Value) -> Option<&str> {
         doc.get("@id").and_then(|v| v.as_str())
@@ -92,15 +111,29 @@ impl OrmResult {
     where
         T: FromTDBInstance + InstanceFromJson + ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
 
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .map(|doc| T::from_json(doc.clone()))
             .collect()
     }
 
+    /// Like [`get`](Self::get), with each model wrapped in [`Tracked`] so a
+    /// later `save` writes only the fields changed after loading.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_tracked<T>(&self) -> anyhow::Result<Vec<crate::Tracked<T>>>
+    where
+        T: TerminusDBModel,
+    {
+        Ok(self
+            .get::<T>()?
+            .into_iter()
+            .map(crate::Tracked::new)
+            .collect())
+    }
+
     /// Extract a single instance of type T, if present.
     ///
     /// Returns `None` if no matching documents are found.
@@ -131,11 +164,11 @@ impl OrmResult {
     where
         T: FromTDBInstance + InstanceFromJson + ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
 
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .map(|doc| {
                 // Typed id parsed once at the boundary (canonical TDB `@id`).
                 let id = EntityIDFor::<T>::new_untyped(Self::get_id(doc).ok_or_else(|| {
@@ -156,11 +189,11 @@ impl OrmResult {
     where
         T: FromTDBInstance + InstanceFromJson + ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
 
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .map(|doc| {
                 // Parse the canonical TDB `@id` to a typed id once, here at the
                 // boundary, so callers receive `EntityIDFor<T>` and never parse.
@@ -201,10 +234,10 @@ impl OrmResult {
     where
         T: ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
         self.documents
             .iter()
-            .any(|doc| Self::get_type(doc) == Some(&target_class))
+            .any(|doc| Self::has_class(doc, &classes))
     }
 
     /// Merge another result into this one.
@@ -246,11 +279,11 @@ impl OrmResult {
     where
         T: FromTDBInstance + InstanceFromJson + ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
         let mut grouped: HashMap<String, Vec<T>> = HashMap::new();
 
         for doc in &self.documents {
-            if Self::get_type(doc) != Some(&target_class) {
+            if !Self::has_class(doc, &classes) {
                 continue;
             }
 
@@ -284,10 +317,10 @@ impl OrmResult {
     where
         T: ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .filter_map(|doc| Self::get_id(doc).map(|s| s.to_string()))
             .collect()
     }
@@ -305,10 +338,10 @@ impl OrmResult {
     where
         T: ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .filter_map(|doc| {
                 doc.get(field_name).and_then(|v| {
                     if let Some(s) = v.as_str() {
@@ -336,10 +369,10 @@ impl OrmResult {
     where
         T: ToTDBSchema,
     {
-        let target_class = T::to_schema().class_name().to_string();
+        let classes = Self::classes_of::<T>();
         self.documents
             .iter()
-            .filter(|doc| Self::get_type(doc) == Some(&target_class))
+            .filter(|doc| Self::has_class(doc, &classes))
             .filter_map(|doc| doc.get(field_name))
             .filter_map(|v| v.as_array())
             .flat_map(|arr| {
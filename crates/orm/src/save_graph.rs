@@ -0,0 +1,225 @@
+//! Saving a graph of related models in one commit.
+//!
+//! # Example
+//! ```ignore
+//! let writer = Writer { name: "Ada".into() };
+//! let post = BlogPost {
+//!     title: "Hello".into(),
+//!     writer: TdbLazy::new_data(writer)?,
+//! };
+//!
+//! // Inserts the Writer, then the BlogPost linking to it.
+//! post.save_deep(&client, &spec).await?;
+//!
+//! // Models linked by `EntityIDFor` are added explicitly.
+//! SaveGraph::new()
+//!     .add(&comment)
+//!     .add(&post)
+//!     .save(&client, &spec, "Add comment")
+//!     .await?;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use terminusdb_client::{
+    BranchSpec, DocumentInsertArgs, ResponseWithHeaders, TDBInsertInstanceResult,
+};
+use terminusdb_schema::{
+    Instance, InstanceProperty, PrimitiveValue, RelationValue, TerminusDBModel, ToTDBInstances,
+};
+
+use crate::{hooks, ClientProvider};
+
+/// Documents to save together, ordered so that every document comes after
+/// the documents it references.
+///
+/// Documents are identified by their id field or, for lexical keys, the id
+/// the server will derive from the key fields. Documents with a random or
+/// hash key and no id are always saved as new documents.
+///
+/// Linked models held in `TdbLazy` fields are collected with the model that
+/// holds them. Targets of `EntityIDFor` fields are only known by ID, so add
+/// them to the graph as well; they are ordered before their referrers too.
+/// Documents in a reference cycle keep the order they were added in.
+///
+/// The [`ModelHooks`](crate::ModelHooks) `before_save` hook runs for each
+/// model passed to [`add`](Self::add), not for the models linked from it; a
+/// rejected model makes [`save`](Self::save) fail without writing anything.
+#[derive(Debug, Clone, Default)]
+pub struct SaveGraph {
+    instances: Vec<Instance>,
+    /// Errors from `before_save` hooks of added models.
+    rejected: Vec<String>,
+}
+
+impl SaveGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `model` and every document linked from it. A document added twice
+    /// is saved once, with the state added last.
+    pub fn add<T: TerminusDBModel + 'static>(mut self, model: &T) -> Self {
+        if let Err(e) = hooks::before_save(model) {
+            self.rejected.push(e.to_string());
+        }
+        for mut instance in model.to_instance(None).to_instance_tree_flatten(true) {
+            if instance.is_reference() {
+                continue;
+            }
+            if !instance.schema.is_subdocument() {
+                instance.set_random_key_prefix();
+            }
+            instance.capture = true;
+            link_known(&mut instance);
+
+            match instance.known_id().and_then(|id| self.position(&id)) {
+                Some(pos) => self.instances[pos] = instance,
+                None => self.instances.push(instance),
+            }
+        }
+        self
+    }
+
+    /// Number of documents in the graph.
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// The documents in save order: referenced documents before the
+    /// documents that reference them.
+    pub fn ordered(&self) -> Vec<&Instance> {
+        let ids: Vec<Option<String>> = self.instances.iter().map(Instance::known_id).collect();
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| id.as_deref().map(|id| (id, i)))
+            .collect();
+
+        // For each document, the other documents in the graph it references.
+        let deps: Vec<HashSet<usize>> = self
+            .instances
+            .iter()
+            .enumerate()
+            .map(|(i, instance)| {
+                let mut refs = Vec::new();
+                collect_refs(instance, &mut refs);
+                refs.iter()
+                    .filter_map(|id| index.get(id.as_str()).copied())
+                    .filter(|dep| *dep != i)
+                    .collect()
+            })
+            .collect();
+
+        let mut saved = vec![false; self.instances.len()];
+        let mut order = Vec::with_capacity(self.instances.len());
+        while order.len() < self.instances.len() {
+            let next = (0..self.instances.len())
+                .filter(|i| !saved[*i])
+                .find(|i| deps[*i].iter().all(|dep| saved[*dep]))
+                // A cycle: take the earliest remaining document.
+                .or_else(|| (0..self.instances.len()).find(|i| !saved[*i]))
+                .expect("an unsaved document remains");
+            saved[next] = true;
+            order.push(&self.instances[next]);
+        }
+        order
+    }
+
+    /// Insert or replace every document in the graph, in one commit.
+    pub async fn save<C: ClientProvider>(
+        &self,
+        client: &C,
+        spec: &BranchSpec,
+        message: impl Into<String>,
+    ) -> anyhow::Result<ResponseWithHeaders<HashMap<String, TDBInsertInstanceResult>>> {
+        if !self.rejected.is_empty() {
+            anyhow::bail!("{}", self.rejected.join("; "));
+        }
+        let args = DocumentInsertArgs::from(spec.clone()).with_message(message);
+        client.client().insert_documents(self.ordered(), args).await
+    }
+
+    fn position(&self, id: &str) -> Option<usize> {
+        self.instances
+            .iter()
+            .position(|instance| instance.known_id().as_deref() == Some(id))
+    }
+}
+
+/// Replace linked documents held inline with references to their known id.
+/// They are saved as documents of their own, so the link stays a reference
+/// and the document is written once.
+fn link_known(instance: &mut Instance) {
+    for prop in instance.properties.values_mut() {
+        match prop {
+            InstanceProperty::Relation(rel) => link_known_rel(rel),
+            InstanceProperty::Relations(rels) => rels.iter_mut().for_each(link_known_rel),
+            _ => {}
+        }
+    }
+}
+
+fn link_known_rel(rel: &mut RelationValue) {
+    if let RelationValue::One(nested) = rel {
+        if nested.should_remain_embedded() {
+            link_known(nested);
+        } else if let Some(id) = nested.known_id() {
+            *rel = RelationValue::ExternalReference(id);
+        }
+    }
+}
+
+/// IDs `instance` refers to, through links, embedded subdocuments and
+/// string properties (how `EntityIDFor` fields are stored).
+fn collect_refs(instance: &Instance, refs: &mut Vec<String>) {
+    for prop in instance.properties.values() {
+        collect_prop_refs(prop, refs);
+    }
+}
+
+fn collect_prop_refs(prop: &InstanceProperty, refs: &mut Vec<String>) {
+    match prop {
+        InstanceProperty::Primitive(PrimitiveValue::String(s)) => refs.push(s.clone()),
+        InstanceProperty::Primitives(values) => {
+            for value in values {
+                if let PrimitiveValue::String(s) = value {
+                    refs.push(s.clone());
+                }
+            }
+        }
+        InstanceProperty::Primitive(_) => {}
+        InstanceProperty::Relation(rel) => collect_rel_refs(rel, refs),
+        InstanceProperty::Relations(rels) => {
+            for rel in rels {
+                collect_rel_refs(rel, refs);
+            }
+        }
+        InstanceProperty::Any(props) => {
+            for prop in props {
+                collect_prop_refs(prop, refs);
+            }
+        }
+    }
+}
+
+fn collect_rel_refs(rel: &RelationValue, refs: &mut Vec<String>) {
+    match rel {
+        RelationValue::ExternalReference(id) | RelationValue::TransactionRef(id) => {
+            refs.push(id.clone())
+        }
+        RelationValue::ExternalReferences(ids) | RelationValue::TransactionRefs(ids) => {
+            refs.extend(ids.iter().cloned())
+        }
+        RelationValue::One(nested) => collect_refs(nested, refs),
+        RelationValue::More(nested) => {
+            for instance in nested {
+                collect_refs(instance, refs);
+            }
+        }
+    }
+}
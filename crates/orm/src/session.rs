@@ -0,0 +1,425 @@
+//! Unit of work with an identity map.
+//!
+//! # Example
+//! ```ignore
+//! let mut session = OrmSession::new(&client, &spec).with_message("Publish posts");
+//!
+//! // Loaded once; later lookups of the same ID come from the identity map.
+//! let mut writer = session.get(&writer_id).await?.unwrap();
+//! writer.name = "Ada L.".into();
+//! session.update(writer)?;
+//!
+//! session.insert(post);
+//! session.delete(&old_post)?;
+//!
+//! session.commit().await?; // one commit for all three changes
+//! ```
+
+use std::any::Any;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use terminusdb_client::{
+    deserialize::DefaultTDBDeserializer, BranchSpec, GetOpts, TerminusDBHttpClient,
+};
+use terminusdb_schema::{EntityIDFor, ToJson, ToTDBInstance, XSDAnySimpleType};
+use terminusdb_woql2::prelude::{
+    And, DeleteDocument, DictionaryTemplate, FieldValuePair, InsertDocument, NodeValue, Query,
+    UpdateDocument, Value,
+};
+
+use crate::{hooks, ClientProvider, OrmModel};
+
+/// A change waiting for [`OrmSession::commit`].
+#[derive(Debug, Clone)]
+enum Pending {
+    Insert(serde_json::Value),
+    Update(serde_json::Value),
+    Delete,
+}
+
+/// Collects inserts, updates and deletes and writes them in a single commit.
+///
+/// Models are keyed by their ID: queuing the same document twice keeps only
+/// the last change, and [`get`](Self::get) serves documents that were already
+/// loaded or queued instead of fetching them again. The ID is the model's id
+/// field or its lexical key; models whose ID the server assigns (random or
+/// hash keys without an id field) can only be inserted.
+///
+/// The commit runs as one WOQL query through `query_mut`, which writes to the
+/// database's default branch. [`ModelHooks`](crate::ModelHooks) run at commit
+/// for queued inserts and updates, and when a model is queued with
+/// [`delete`](Self::delete).
+///
+/// With a [`ReplicatedClient`](crate::ReplicatedClient), reads go to the
+/// replicas until the first commit and to the primary after it.
+pub struct OrmSession<'c, C: ClientProvider> {
+    client: &'c C,
+    spec: BranchSpec,
+    author: String,
+    message: String,
+    /// Latest known state of each document, by typed ID (`Type/key`).
+    identity: HashMap<String, Box<dyn Any + Send + Sync>>,
+    /// IDs known to exist in the database.
+    stored: HashSet<String>,
+    /// Queued changes by ID, in the order they were first queued.
+    pending: Vec<(String, Pending)>,
+    /// Queued inserts of models without an ID, with the models.
+    unkeyed: Vec<(serde_json::Value, Box<dyn Any + Send + Sync>)>,
+    /// Whether the session has committed; later reads go to the primary.
+    committed: bool,
+}
+
+impl<'c, C: ClientProvider> OrmSession<'c, C> {
+    /// Start an empty session against `spec`.
+    pub fn new(client: &'c C, spec: &BranchSpec) -> Self {
+        Self {
+            client,
+            spec: spec.clone(),
+            author: "system".to_string(),
+            message: "ORM session commit".to_string(),
+            identity: HashMap::new(),
+            stored: HashSet::new(),
+            pending: Vec::new(),
+            unkeyed: Vec::new(),
+            committed: false,
+        }
+    }
+
+    /// Commit author recorded by the server.
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Commit message for [`commit`](Self::commit).
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Get the document with `id`, fetching it only if the session has not
+    /// seen it yet. Returns `None` if it does not exist or is queued for
+    /// deletion.
+    pub async fn get<T>(&mut self, id: &EntityIDFor<T>) -> anyhow::Result<Option<T>>
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        let key = id.typed().to_string();
+        if let Some(known) = self.lookup::<T>(&key)? {
+            return Ok(known);
+        }
+
+        let fetched = self
+            .reader()
+            .get_instance_if_exists::<T>(&key, &self.spec, &mut DefaultTDBDeserializer)
+            .await?;
+        if let Some(model) = &fetched {
+            self.remember(key, model.clone());
+        }
+        Ok(fetched)
+    }
+
+    /// Get several documents, fetching the ones the session has not seen in
+    /// one request. Missing and deleted documents are left out; the rest keep
+    /// the order of `ids`.
+    pub async fn get_all<T>(&mut self, ids: &[EntityIDFor<T>]) -> anyhow::Result<Vec<T>>
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        let mut unseen = Vec::new();
+        for id in ids {
+            let key = id.typed().to_string();
+            if self.lookup::<T>(&key)?.is_none() && !unseen.contains(&key) {
+                unseen.push(key);
+            }
+        }
+
+        if !unseen.is_empty() {
+            let fetched: Vec<T> = self
+                .reader()
+                .get_instances(
+                    unseen,
+                    &self.spec,
+                    GetOpts::default(),
+                    &mut DefaultTDBDeserializer,
+                )
+                .await?;
+            for model in fetched {
+                if let Some(id) = model.known_id() {
+                    self.remember(id.typed().to_string(), model);
+                }
+            }
+        }
+
+        let mut models = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(Some(model)) = self.lookup::<T>(id.typed())? {
+                models.push(model);
+            }
+        }
+        Ok(models)
+    }
+
+    /// Queue `model` for insertion. A document the session loaded or queued
+    /// for deletion is replaced instead.
+    pub fn insert<T>(&mut self, model: T)
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        let document = model.to_instance(None).to_json();
+        let Some(id) = model.known_id() else {
+            self.unkeyed.push((document, Box::new(model)));
+            return;
+        };
+
+        let key = id.typed().to_string();
+        let change = match self.pending_for(&key) {
+            Some(Pending::Insert(_)) => Pending::Insert(document),
+            Some(_) => Pending::Update(document),
+            None if self.stored.contains(&key) => Pending::Update(document),
+            None => Pending::Insert(document),
+        };
+        self.queue(key.clone(), change);
+        self.identity.insert(key, Box::new(model));
+    }
+
+    /// Queue `model` to replace its stored document.
+    ///
+    /// Fails if the model has no ID.
+    pub fn update<T>(&mut self, model: T) -> anyhow::Result<()>
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        let key = required_id(&model)?;
+        let document = model.to_instance(None).to_json();
+        let change = match self.pending_for(&key) {
+            Some(Pending::Insert(_)) => Pending::Insert(document),
+            _ => Pending::Update(document),
+        };
+        self.queue(key.clone(), change);
+        self.identity.insert(key, Box::new(model));
+        Ok(())
+    }
+
+    /// Queue the document of `model` for deletion.
+    ///
+    /// Fails if the model has no ID.
+    pub fn delete<T>(&mut self, model: &T) -> anyhow::Result<()>
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        let key = required_id(model)?;
+        hooks::before_delete(model)?;
+        self.delete_key(key);
+        Ok(())
+    }
+
+    /// Queue the document with `id` for deletion.
+    pub fn delete_by_id<T>(&mut self, id: &EntityIDFor<T>)
+    where
+        T: OrmModel + Send + Sync + 'static,
+    {
+        self.delete_key(id.typed().to_string());
+    }
+
+    /// Number of queued changes.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len() + self.unkeyed.len()
+    }
+
+    /// Whether there is nothing to commit.
+    pub fn is_empty(&self) -> bool {
+        self.pending_count() == 0
+    }
+
+    /// Drop all queued changes and everything the session has loaded.
+    pub fn clear(&mut self) {
+        self.identity.clear();
+        self.stored.clear();
+        self.pending.clear();
+        self.unkeyed.clear();
+    }
+
+    /// Write all queued changes in a single commit. Does nothing when the
+    /// session is empty. Queued changes are kept if the commit fails or a
+    /// `before_save` hook rejects a model.
+    pub async fn commit(&mut self) -> anyhow::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        for model in self.queued_models(|_| true) {
+            hooks::before_save(model)?;
+        }
+
+        let query = self.to_query();
+        self.client
+            .client()
+            .query_mut::<serde_json::Value>(
+                self.spec.clone(),
+                query,
+                self.author.clone(),
+                self.message.clone(),
+            )
+            .await?;
+        self.committed = true;
+
+        let created = self
+            .queued_models(|change| matches!(change, Pending::Insert(_)))
+            .map(|model| hooks::after_create(model))
+            .collect::<Vec<_>>();
+
+        for (key, change) in self.pending.drain(..) {
+            match change {
+                Pending::Delete => {
+                    self.stored.remove(&key);
+                }
+                Pending::Insert(_) | Pending::Update(_) => {
+                    self.stored.insert(key);
+                }
+            }
+        }
+        self.unkeyed.clear();
+
+        for result in created {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// The WOQL query [`commit`](Self::commit) runs.
+    pub fn to_query(&self) -> Query {
+        let keyed = self.pending.iter().map(|(key, change)| match change {
+            Pending::Insert(document) => Query::InsertDocument(InsertDocument {
+                document: json_to_value(document),
+                identifier: None,
+            }),
+            Pending::Update(document) => Query::UpdateDocument(UpdateDocument {
+                document: json_to_value(document),
+                identifier: None,
+            }),
+            Pending::Delete => Query::DeleteDocument(DeleteDocument {
+                identifier: NodeValue::Node(key.clone()),
+            }),
+        });
+        let unkeyed = self.unkeyed.iter().map(|(document, _)| {
+            Query::InsertDocument(InsertDocument {
+                document: json_to_value(document),
+                identifier: None,
+            })
+        });
+
+        Query::And(And {
+            and: keyed.chain(unkeyed).collect(),
+        })
+    }
+
+    /// `Some(None)` if `key` is queued for deletion, `Some(Some(model))` if it
+    /// is in the identity map, `None` if the session has not seen it.
+    fn lookup<T: OrmModel + 'static>(&self, key: &str) -> anyhow::Result<Option<Option<T>>> {
+        if let Some(Pending::Delete) = self.pending_for(key) {
+            return Ok(Some(None));
+        }
+        match self.identity.get(key) {
+            None => Ok(None),
+            Some(known) => match known.downcast_ref::<T>() {
+                Some(model) => Ok(Some(Some(model.clone()))),
+                None => anyhow::bail!(
+                    "{} is held in the session as a different type than {}",
+                    key,
+                    T::schema_name()
+                ),
+            },
+        }
+    }
+
+    /// The client for reads: a replica until the session has committed, the
+    /// primary afterwards so that reads see the session's own writes.
+    fn reader(&self) -> &TerminusDBHttpClient {
+        if self.committed {
+            self.client.client()
+        } else {
+            self.client.read_client()
+        }
+    }
+
+    /// Models of the queued inserts and updates whose change matches
+    /// `filter`, plus every unkeyed insert.
+    fn queued_models(
+        &self,
+        filter: impl Fn(&Pending) -> bool,
+    ) -> impl Iterator<Item = &(dyn Any + Send + Sync)> {
+        let keyed = self
+            .pending
+            .iter()
+            .filter(move |(_, change)| !matches!(change, Pending::Delete) && filter(change))
+            .filter_map(|(key, _)| self.identity.get(key));
+        let unkeyed = self.unkeyed.iter().map(|(_, model)| model);
+        keyed.chain(unkeyed).map(|model| &**model)
+    }
+
+    fn remember<T: Send + Sync + 'static>(&mut self, key: String, model: T) {
+        self.stored.insert(key.clone());
+        self.identity.insert(key, Box::new(model));
+    }
+
+    fn delete_key(&mut self, key: String) {
+        self.identity.remove(&key);
+        match self.pending_for(&key) {
+            // Never written, so there is nothing to delete.
+            Some(Pending::Insert(_)) if !self.stored.contains(&key) => {
+                self.pending.retain(|(queued, _)| *queued != key);
+            }
+            _ => self.queue(key, Pending::Delete),
+        }
+    }
+
+    fn pending_for(&self, key: &str) -> Option<&Pending> {
+        self.pending
+            .iter()
+            .find(|(queued, _)| queued == key)
+            .map(|(_, change)| change)
+    }
+
+    fn queue(&mut self, key: String, change: Pending) {
+        match self.pending.iter_mut().find(|(queued, _)| *queued == key) {
+            Some((_, queued)) => *queued = change,
+            None => self.pending.push((key, change)),
+        }
+    }
+}
+
+fn required_id<T: OrmModel>(model: &T) -> anyhow::Result<String> {
+    match model.known_id() {
+        Some(id) => Ok(id.typed().to_string()),
+        None => anyhow::bail!(
+            "{} has no ID; only inserts are supported for models without one",
+            T::schema_name()
+        ),
+    }
+}
+
+/// Convert a document to the WOQL dictionary `InsertDocument` and
+/// `UpdateDocument` expect. Null fields (`Option::None`) are left out.
+fn json_to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Object(map) => Value::Dictionary(DictionaryTemplate {
+            data: map
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(field, value)| FieldValuePair {
+                    field: field.clone(),
+                    value: json_to_value(value),
+                })
+                .collect::<BTreeSet<_>>(),
+        }),
+        serde_json::Value::Array(items) => Value::List(items.iter().map(json_to_value).collect()),
+        serde_json::Value::String(s) => Value::Data(XSDAnySimpleType::String(s.clone())),
+        serde_json::Value::Bool(b) => Value::Data(XSDAnySimpleType::Boolean(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Data(XSDAnySimpleType::Integer(i)),
+            None => Value::Data(XSDAnySimpleType::Float(n.as_f64().unwrap_or_default())),
+        },
+        serde_json::Value::Null => Value::List(vec![]),
+    }
+}
@@ -0,0 +1,114 @@
+//! Change tracking for loaded models.
+//!
+//! # Example
+//! ```ignore
+//! let mut posts = result.get_tracked::<BlogPost>()?;
+//! posts[0].title = "Edited".into();
+//!
+//! assert_eq!(posts[0].changed_fields(), vec!["title"]);
+//! posts[0].save(&client, &spec).await?; // patches `title` only
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use terminusdb_client::{BranchSpec, DocumentInsertArgs};
+use terminusdb_schema::{Instance, TerminusDBModel, ToTDBInstance, ToTDBSchema};
+
+use crate::{hooks, ClientProvider};
+
+/// A model plus a snapshot of its state when it was loaded or last saved.
+///
+/// Derefs to the model for reading and mutation. Changes are found by
+/// comparing instance properties against the snapshot, so setting a field back
+/// to its original value leaves it clean.
+#[derive(Debug, Clone)]
+pub struct Tracked<T> {
+    value: T,
+    snapshot: Instance,
+}
+
+impl<T: TerminusDBModel> Tracked<T> {
+    /// Start tracking `value`, treating its current state as stored.
+    pub fn new(value: T) -> Self {
+        let snapshot = value.to_instance(None);
+        Self { value, snapshot }
+    }
+
+    /// TDB property names whose value differs from the snapshot, sorted.
+    pub fn changed_fields(&self) -> Vec<String> {
+        let current = self.value.to_instance(None);
+        let mut changed: Vec<String> = current
+            .properties
+            .iter()
+            .filter(|(name, prop)| self.snapshot.properties.get(*name) != Some(*prop))
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed.extend(
+            self.snapshot
+                .properties
+                .keys()
+                .filter(|name| !current.properties.contains_key(*name))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+
+    /// Whether any field changed since the snapshot.
+    pub fn is_dirty(&self) -> bool {
+        !self.changed_fields().is_empty()
+    }
+
+    /// Accept the current state as stored without writing it.
+    pub fn mark_clean(&mut self) {
+        self.snapshot = self.value.to_instance(None);
+    }
+
+    /// Stop tracking and return the model.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Write the changed fields as a patch, leaving other stored fields
+    /// untouched, and return their names. Nothing is written when clean.
+    pub async fn save<C>(&mut self, client: &C, spec: &BranchSpec) -> anyhow::Result<Vec<String>>
+    where
+        C: ClientProvider,
+        T: 'static,
+    {
+        let changed = self.changed_fields();
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+        hooks::before_save(&self.value)?;
+
+        let target = match self.value.known_id() {
+            Some(id) => id.to_string(),
+            None => T::schema_name(),
+        };
+        let message = format!("Update {} ({})", target, changed.join(", "));
+        let args = DocumentInsertArgs::from(spec.clone()).with_message(message);
+        let fields: Vec<&str> = changed.iter().map(String::as_str).collect();
+
+        client
+            .client()
+            .patch_instance_fields(&self.value, &fields, args)
+            .await?;
+        self.mark_clean();
+        Ok(changed)
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
@@ -0,0 +1,98 @@
+#![recursion_limit = "512"]
+//! Tests for `FilterQuery` aggregates (`count`, `sum`, `group_by`).
+
+use terminusdb_client::DocumentInsertArgs;
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+use terminusdb_woql2::prelude::{DataValue, Query};
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "sku")]
+pub struct AggOrder {
+    pub sku: String,
+    pub region: String,
+    pub amount: i32,
+}
+
+#[test]
+fn test_sum_query_groups_values_then_sums() {
+    let query = AggOrder::filter(AggOrderFields::Region.eq("EU")).sum_query(AggOrderFields::Amount);
+
+    let Query::Select(select) = query else {
+        panic!("expected Select, got {:?}", query);
+    };
+    assert_eq!(select.variables, vec!["Sum"]);
+    let Query::And(and) = *select.query else {
+        panic!("expected And");
+    };
+    let [Query::GroupBy(group), Query::Sum(sum)] = and.and.as_slice() else {
+        panic!("expected GroupBy then Sum, got {:?}", and.and);
+    };
+    assert!(group.group_by.is_empty());
+    assert_eq!(sum.result, DataValue::Variable("Sum".into()));
+}
+
+#[test]
+fn test_group_by_query_counts_members_per_key() {
+    let query = AggOrder::all().group_by_query(AggOrderFields::Region);
+
+    let Query::Select(select) = query else {
+        panic!("expected Select, got {:?}", query);
+    };
+    assert_eq!(select.variables, vec!["GroupKey", "GroupCount"]);
+    let Query::And(and) = *select.query else {
+        panic!("expected And");
+    };
+    let [Query::GroupBy(group), Query::Length(length)] = and.and.as_slice() else {
+        panic!("expected GroupBy then Length, got {:?}", and.and);
+    };
+    assert_eq!(group.group_by, vec!["GroupKey"]);
+    assert_eq!(length.length, DataValue::Variable("GroupCount".into()));
+}
+
+#[db_test(db = "orm_aggregate_test")]
+async fn test_aggregates_against_database(client: _, spec: _) -> anyhow::Result<()> {
+    let args = DocumentInsertArgs::from(spec.clone());
+    client
+        .insert_entity_schema::<AggOrder>(args.clone())
+        .await?;
+
+    for (sku, region, amount) in [("a", "EU", 10), ("b", "EU", 15), ("c", "US", 7)] {
+        let order = AggOrder {
+            sku: sku.to_string(),
+            region: region.to_string(),
+            amount,
+        };
+        client.insert_instance(&order, args.clone()).await?;
+    }
+
+    let eu = AggOrder::filter(AggOrderFields::Region.eq("EU"));
+    assert_eq!(eu.count(&client, &spec).await?, 2);
+    assert_eq!(eu.sum(AggOrderFields::Amount, &client, &spec).await?, 25.0);
+
+    let mut groups = AggOrder::all()
+        .group_by(AggOrderFields::Region, &client, &spec)
+        .await?;
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    assert_eq!(
+        groups,
+        vec![
+            GroupCount {
+                key: "EU".to_string(),
+                count: 2
+            },
+            GroupCount {
+                key: "US".to_string(),
+                count: 1
+            },
+        ]
+    );
+
+    let none = AggOrder::filter(AggOrderFields::Amount.gt(100));
+    assert_eq!(none.sum(AggOrderFields::Amount, &client, &spec).await?, 0.0);
+
+    Ok(())
+}
@@ -0,0 +1,330 @@
+#![recursion_limit = "512"]
+//! Tests for the typed filter DSL (`FieldFilter` / `FilterExt` / `FilterQuery`).
+//!
+//! The unit tests check the WOQL each operator compiles to; the integration
+//! test runs a combined filter against an embedded TerminusDB.
+
+use terminusdb_client::DocumentInsertArgs;
+use terminusdb_orm::prelude::*;
+use terminusdb_schema::XSDAnySimpleType;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+use terminusdb_woql2::prelude::{DataValue, NodeValue, Query, Value};
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "name")]
+pub struct FilterPerson {
+    pub name: String,
+    pub age: i32,
+    pub email: Option<String>,
+}
+
+/// Declared order differs from the lexical order of the TDB values.
+#[derive(Debug, Clone, Copy, PartialEq, TerminusDBModel)]
+pub enum FilterSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "title")]
+pub struct FilterTicket {
+    pub title: String,
+    pub severity: FilterSeverity,
+}
+
+fn subject() -> Value {
+    Value::Variable("Subject".to_string())
+}
+
+/// Unwrap the `And[Triple(Subject, @schema:field, Var), check]` shape shared
+/// by every operator that binds the field value first.
+fn bound(query: Query, field: &str) -> (String, Query) {
+    let Query::And(and) = query else {
+        panic!("expected And, got {:?}", query);
+    };
+    assert_eq!(and.and.len(), 2);
+    let mut parts = and.and.into_iter();
+    let Some(Query::Triple(triple)) = parts.next() else {
+        panic!("expected the field triple first");
+    };
+    assert_eq!(
+        triple.predicate,
+        NodeValue::Node(format!("@schema:{}", field))
+    );
+    let Value::Variable(var) = triple.object else {
+        panic!("expected the field value to be bound to a variable");
+    };
+    (var, parts.next().unwrap())
+}
+
+#[test]
+fn test_eq_matches_the_triple_object_directly() {
+    let query = FilterPersonFields::Name.eq("Alice").to_query(&subject());
+
+    let Query::Triple(triple) = query else {
+        panic!("expected a single triple, got {:?}", query);
+    };
+    assert_eq!(triple.predicate, NodeValue::Node("@schema:name".into()));
+    assert_eq!(
+        triple.object,
+        Value::Data(XSDAnySimpleType::String("Alice".into()))
+    );
+}
+
+#[test]
+fn test_ordering_operators_compare_the_bound_value() {
+    let (var, check) = bound(FilterPersonFields::Age.gt(30).to_query(&subject()), "age");
+    let Query::Greater(greater) = check else {
+        panic!("gt should compile to Greater, got {:?}", check);
+    };
+    assert_eq!(greater.left, DataValue::Variable(var));
+    assert_eq!(
+        greater.right,
+        DataValue::Data(XSDAnySimpleType::Integer(30))
+    );
+
+    let (_, check) = bound(FilterPersonFields::Age.gte(30).to_query(&subject()), "age");
+    assert!(matches!(check, Query::Gte(_)));
+    let (_, check) = bound(FilterPersonFields::Age.lt(30).to_query(&subject()), "age");
+    assert!(matches!(check, Query::Less(_)));
+    let (_, check) = bound(FilterPersonFields::Age.lte(30).to_query(&subject()), "age");
+    assert!(matches!(check, Query::Lte(_)));
+
+    let (_, check) = bound(FilterPersonFields::Age.ne(30).to_query(&subject()), "age");
+    let Query::Not(not) = check else {
+        panic!("ne should compile to Not(Equals), got {:?}", check);
+    };
+    assert!(matches!(*not.query, Query::Equals(_)));
+}
+
+#[test]
+fn test_string_operators_compile_to_substring_and_regexp() {
+    let (var, check) = bound(
+        FilterPersonFields::Name.contains("li").to_query(&subject()),
+        "name",
+    );
+    let Query::Substring(sub) = check else {
+        panic!("contains should compile to Substring, got {:?}", check);
+    };
+    assert_eq!(sub.string, DataValue::Variable(var));
+    assert!(matches!(sub.before, DataValue::Variable(_)));
+    assert_eq!(
+        sub.substring,
+        DataValue::Data(XSDAnySimpleType::String("li".into()))
+    );
+
+    let (_, check) = bound(
+        FilterPersonFields::Name
+            .starts_with("Al")
+            .to_query(&subject()),
+        "name",
+    );
+    let Query::Substring(sub) = check else {
+        panic!("starts_with should compile to Substring, got {:?}", check);
+    };
+    assert_eq!(sub.before, DataValue::Data(XSDAnySimpleType::Integer(0)));
+    assert_eq!(sub.length, DataValue::Data(XSDAnySimpleType::Integer(2)));
+
+    let (_, check) = bound(
+        FilterPersonFields::Name
+            .matches("^A.*e$")
+            .to_query(&subject()),
+        "name",
+    );
+    assert!(matches!(check, Query::Regexp(_)));
+}
+
+#[test]
+fn test_in_list_compiles_to_member() {
+    let (var, check) = bound(
+        FilterPersonFields::Age
+            .in_list([20, 30])
+            .to_query(&subject()),
+        "age",
+    );
+    let Query::Member(member) = check else {
+        panic!("in_list should compile to Member, got {:?}", check);
+    };
+    assert_eq!(member.member, DataValue::Variable(var));
+    assert_eq!(
+        member.list,
+        DataValue::List(vec![
+            DataValue::Data(XSDAnySimpleType::Integer(20)),
+            DataValue::Data(XSDAnySimpleType::Integer(30)),
+        ])
+    );
+}
+
+#[test]
+fn test_enum_comparisons_follow_declared_order() {
+    // Lexically "critical" < "high"; by declared order Critical comes last.
+    let query = FilterTicketFields::Severity
+        .enum_gte(FilterSeverity::High)
+        .to_query(&subject());
+    let Query::Or(or) = query else {
+        panic!("enum_gte should compile to Or, got {:?}", query);
+    };
+    let nodes: Vec<Value> = or
+        .or
+        .into_iter()
+        .map(|query| match query {
+            Query::Triple(triple) => {
+                assert_eq!(triple.predicate, NodeValue::Node("@schema:severity".into()));
+                triple.object
+            }
+            other => panic!("expected a triple per variant, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            Value::Node("@schema:FilterSeverity/high".into()),
+            Value::Node("@schema:FilterSeverity/critical".into()),
+        ]
+    );
+
+    let Query::Or(or) = FilterTicketFields::Severity
+        .enum_lt(FilterSeverity::Medium)
+        .to_query(&subject())
+    else {
+        panic!("enum_lt should compile to Or");
+    };
+    assert_eq!(or.or.len(), 1);
+    let Query::Or(or) = FilterTicketFields::Severity
+        .enum_gt(FilterSeverity::Critical)
+        .to_query(&subject())
+    else {
+        panic!("enum_gt should compile to Or");
+    };
+    assert!(or.or.is_empty(), "nothing comes after the last variant");
+}
+
+#[test]
+fn test_null_checks() {
+    let query = FilterPersonFields::Email.is_null().to_query(&subject());
+    let Query::Not(not) = query else {
+        panic!("is_null should compile to Not(Triple), got {:?}", query);
+    };
+    assert!(matches!(*not.query, Query::Triple(_)));
+
+    let query = FilterPersonFields::Email.is_not_null().to_query(&subject());
+    assert!(matches!(query, Query::Triple(_)));
+}
+
+#[test]
+fn test_combinators_and_fresh_variables() {
+    let condition = FilterPersonFields::Age
+        .gte(18)
+        .and(FilterPersonFields::Age.lt(65))
+        .and(
+            !FilterPersonFields::Name
+                .eq("Bob")
+                .or(FilterPersonFields::Email.is_null()),
+        );
+
+    let Query::And(and) = condition.to_query(&subject()) else {
+        panic!("expected a flat And");
+    };
+    // gte and lt each contribute a triple + check; the negated Or stays whole.
+    assert_eq!(and.and.len(), 5);
+    assert!(matches!(and.and[4], Query::Not(_)));
+
+    // The two age conditions must not share a value variable.
+    let vars: Vec<_> = and
+        .and
+        .iter()
+        .filter_map(|q| match q {
+            Query::Triple(t) => Some(t.object.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vars.len(), 2);
+    assert_ne!(vars[0], vars[1]);
+
+    assert_eq!(
+        !!FilterPersonFields::Email.is_null(),
+        FilterPersonFields::Email.is_null()
+    );
+}
+
+#[test]
+fn test_filter_query_includes_type_and_conditions() {
+    let query = FilterPerson::filter(FilterPersonFields::Age.gt(30))
+        .filter(FilterPersonFields::Email.is_not_null());
+    assert_eq!(query.conditions().len(), 2);
+
+    let Query::And(and) = query.base_query(&subject()) else {
+        panic!("expected And");
+    };
+    // rdf:type triple, then the conditions wrapped in `Once` so each subject
+    // matches at most once.
+    assert_eq!(and.and.len(), 2);
+    let Query::Once(once) = &and.and[1] else {
+        panic!("expected the conditions in Once, got {:?}", and.and[1]);
+    };
+    // (triple, Greater) + email triple
+    assert!(matches!(&*once.query, Query::And(inner) if inner.and.len() == 3));
+
+    let Query::True(_) = FilterPerson::all().build(&subject()) else {
+        panic!("an unfiltered query has no constraints");
+    };
+}
+
+#[db_test(db = "orm_filter_dsl_test")]
+async fn test_filter_dsl_against_database(client: _, spec: _) -> anyhow::Result<()> {
+    let args = DocumentInsertArgs::from(spec.clone());
+    client
+        .insert_entity_schema::<FilterPerson>(args.clone())
+        .await?;
+
+    for (name, age, email) in [
+        ("Alice", 34, Some("alice@example.com")),
+        ("Albert", 17, None),
+        ("Bob", 52, None),
+        ("Carol", 29, Some("carol@example.com")),
+    ] {
+        let person = FilterPerson {
+            name: name.to_string(),
+            age,
+            email: email.map(str::to_string),
+        };
+        client.insert_instance(&person, args.clone()).await?;
+    }
+
+    let names = |mut people: Vec<FilterPerson>| {
+        people.sort_by(|a, b| a.name.cmp(&b.name));
+        people.into_iter().map(|p| p.name).collect::<Vec<_>>()
+    };
+
+    let adults = FilterPerson::filter(FilterPersonFields::Age.gte(18))
+        .apply(&client, &spec, None, None)
+        .await?;
+    assert_eq!(names(adults), vec!["Alice", "Bob", "Carol"]);
+
+    let al_or_no_email = FilterPerson::filter(
+        FilterPersonFields::Name
+            .starts_with("Al")
+            .or(FilterPersonFields::Email.is_null()),
+    )
+    .apply(&client, &spec, None, None)
+    .await?;
+    assert_eq!(names(al_or_no_email), vec!["Albert", "Alice", "Bob"]);
+
+    let picked = FilterPerson::filter(FilterPersonFields::Age.in_list([29, 52]))
+        .filter(!FilterPersonFields::Name.contains("ob"))
+        .apply(&client, &spec, None, None)
+        .await?;
+    assert_eq!(names(picked), vec!["Carol"]);
+
+    let count = FilterPerson::filter(FilterPersonFields::Age.lt(30))
+        .count(&client, &spec)
+        .await?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
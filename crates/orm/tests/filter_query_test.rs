@@ -16,14 +16,14 @@ use terminusdb_schema;
 // Test models with relations
 
 #[derive(Debug, Clone, Default, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct TestProject {
     pub name: String,
     pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct TestTicket {
     pub title: String,
     pub project: EntityIDFor<TestProject>,
@@ -384,13 +384,13 @@ async fn test_filter_query_all(client: _, spec: _) -> anyhow::Result<()> {
 use terminusdb_schema::TdbLazy;
 
 #[derive(Debug, Clone, Default, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct Author {
     pub name: String,
 }
 
 #[derive(Debug, Clone, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct Article {
     pub title: String,
     /// TdbLazy creates actual document link - enables nested filters
@@ -591,7 +591,7 @@ async fn test_tdblazy_nested_filter_execution(client: _, spec: _) -> anyhow::Res
 // ============================================================================
 
 #[derive(Debug, Clone, Default, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct Task {
     pub title: String,
     /// Optional assignee - can be None (absent)
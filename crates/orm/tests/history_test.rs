@@ -0,0 +1,83 @@
+#![recursion_limit = "512"]
+//! Tests for time-travel queries and `diff_between`.
+
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "code")]
+pub struct HistCity {
+    pub code: String,
+    pub name: String,
+}
+
+fn city(code: &str, name: &str) -> HistCity {
+    HistCity {
+        code: code.to_string(),
+        name: name.to_string(),
+    }
+}
+
+#[db_test(db = "orm_history_test")]
+async fn test_queries_and_diffs_across_commits(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<HistCity>(spec.clone().into())
+        .await?;
+
+    let (_, first) = client
+        .insert_instance_with_commit_id(&city("ams", "Amsterdam"), spec.clone().into())
+        .await?;
+    let (_, second) = client
+        .insert_instance_with_commit_id(&city("ber", "Berlin"), spec.clone().into())
+        .await?;
+    let (_, third) = client
+        .insert_instance_with_commit_id(&city("ams", "Mokum"), spec.clone().into())
+        .await?;
+
+    // The query reads the database as it was at the first commit.
+    let then = HistCity::all()
+        .with_client(&client)
+        .execute_at(&spec.clone().ref_commit(first.clone()))
+        .await?;
+    assert_eq!(then.get::<HistCity>()?, vec![city("ams", "Amsterdam")]);
+
+    // A branch spec is rejected, so a missing commit does not read the head.
+    assert!(HistCity::all()
+        .with_client(&client)
+        .execute_at(&spec)
+        .await
+        .is_err());
+
+    let now = HistCity::all()
+        .on_branch("main")
+        .with_client(&client)
+        .execute(&spec)
+        .await?;
+    assert_eq!(now.get::<HistCity>()?.len(), 2);
+
+    let diff = diff_between::<HistCity, _>(&client, &spec, first.clone(), second.clone()).await?;
+    assert_eq!(diff.added, vec![city("ber", "Berlin")]);
+    assert!(diff.changed.is_empty() && diff.deleted.is_empty());
+
+    let diff = diff_between::<HistCity, _>(&client, &spec, second.clone(), first).await?;
+    assert_eq!(diff.deleted, vec![city("ber", "Berlin")]);
+
+    let diff = diff_between::<HistCity, _>(&client, &spec, second, third.clone()).await?;
+    assert_eq!(
+        diff.changed,
+        vec![ModelChange {
+            before: city("ams", "Amsterdam"),
+            after: city("ams", "Mokum"),
+        }]
+    );
+    assert!(
+        diff_between::<HistCity, _>(&client, &spec, third.clone(), third)
+            .await?
+            .is_empty()
+    );
+
+    Ok(())
+}
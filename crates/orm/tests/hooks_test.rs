@@ -0,0 +1,89 @@
+#![recursion_limit = "512"]
+//! Tests for `ModelHooks` and schema validation on writes.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use terminusdb_orm::prelude::*;
+use terminusdb_orm::validate_schema;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "name")]
+pub struct HookTeam {
+    pub name: String,
+    #[tdb(min_cardinality = 1)]
+    pub members: HashSet<String>,
+}
+
+impl ModelHooks for HookTeam {
+    fn before_save(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.name.trim().is_empty(), "team name is blank");
+        Ok(())
+    }
+
+    fn after_create(&self) -> anyhow::Result<()> {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn before_delete(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.name != "core", "the core team cannot be deleted");
+        Ok(())
+    }
+}
+
+fn team(name: &str, members: &[&str]) -> HookTeam {
+    HookTeam {
+        name: name.to_string(),
+        members: members.iter().map(|m| m.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_validate_schema_checks_cardinality() {
+    assert!(validate_schema(&team("core", &["ada"])).is_ok());
+    assert!(validate_schema(&team("core", &[])).is_err());
+}
+
+#[db_test(db = "orm_hooks_test")]
+async fn test_hooks_guard_every_write_path(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<HookTeam>(spec.clone().into())
+        .await?;
+    register_hooks::<HookTeam>();
+
+    // Rejected by before_save, then by schema validation.
+    assert!(team(" ", &["ada"]).save(&client, &spec).await.is_err());
+    assert!(team("ops", &[]).save(&client, &spec).await.is_err());
+    assert!(SaveGraph::new()
+        .add(&team(" ", &["ada"]))
+        .save(&client, &spec, "Blank")
+        .await
+        .is_err());
+
+    let mut session = OrmSession::new(&client, &spec);
+    session.insert(team("ops", &[]));
+    assert!(session.commit().await.is_err());
+    assert_eq!(session.pending_count(), 1, "rejected changes stay queued");
+    session.clear();
+
+    let core = team("core", &["ada", "grace"]);
+    let created = CREATED.load(Ordering::SeqCst);
+    let id = HookTeam::create(&core, &client, &spec)
+        .await?
+        .root_ref::<HookTeam>()?;
+    assert_eq!(CREATED.load(Ordering::SeqCst), created + 1);
+
+    assert!(core.delete(&client, &spec).await.is_err());
+    assert!(session.delete(&core).is_err());
+
+    assert_eq!(HookTeam::fetch(&id, &client, &spec).await?, Some(core));
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ use terminusdb_test::test as db_test;
 use terminusdb_schema; // required by the derive
 
 #[derive(Debug, Clone, Default, TerminusDBModel)]
-#[tdb(key = "Lexical")]
+#[tdb(key = "random")]
 pub struct OrdBook {
     pub title: String,
 }
@@ -166,3 +166,72 @@ async fn top_level_query_orders_by_sort_key(client: _, spec: _) -> anyhow::Resul
 
     Ok(())
 }
+
+/// `order_by_field` renders the marker's field name; repeated calls append
+/// secondary keys in call order.
+#[test]
+fn order_by_field_accumulates_sort_keys() {
+    let entry = OrdChapter::query_all()
+        .order_by_field(OrdChapterFields::SortKey, TerminusOrdering::Desc)
+        .order_by_field(OrdChapterFields::Heading, TerminusOrdering::Asc)
+        .into_query_entry();
+    assert_eq!(
+        entry.order_by_gql.as_deref(),
+        Some("{sort_key: DESC, heading: ASC}")
+    );
+
+    // Appends to an ordering set through the generated `{Model}Ordering` too.
+    let entry = OrdChapter::query_all()
+        .order_by(OrdChapterOrdering {
+            sort_key: Some(TerminusOrdering::Asc),
+        })
+        .order_by_field(OrdChapterFields::Heading, TerminusOrdering::Desc)
+        .into_query_entry();
+    assert_eq!(
+        entry.order_by_gql.as_deref(),
+        Some("{sort_key: ASC, heading: DESC}")
+    );
+}
+
+/// Field-marker ordering with offset/limit pages through the results, both for
+/// "query all" and for an explicit ID list.
+#[db_test(db = "ordering_field_marker_pagination")]
+async fn order_by_field_paginates(client: _, spec: _) -> anyhow::Result<()> {
+    seed(&client, &spec).await?;
+
+    let page = OrdChapter::query_all()
+        .order_by_field(OrdChapterFields::SortKey, TerminusOrdering::Desc)
+        .offset(1)
+        .limit(1)
+        .with_client(&client)
+        .execute(&spec)
+        .await?;
+    let headings: Vec<String> = page.get::<OrdChapter>()?.into_iter().map(|c| c.heading).collect();
+    assert_eq!(headings, vec!["second"]);
+
+    let all = OrdChapter::query_all()
+        .with_client(&client)
+        .execute(&spec)
+        .await?;
+    let ids: Vec<EntityIDFor<OrdChapter>> = all
+        .get_ordered::<OrdChapter>()?
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(ids.len(), 3);
+
+    let first_two = OrdChapter::find_all(ids)
+        .order_by_field(OrdChapterFields::SortKey, TerminusOrdering::Asc)
+        .limit(2)
+        .with_client(&client)
+        .execute(&spec)
+        .await?;
+    let headings: Vec<String> = first_two
+        .get_ordered::<OrdChapter>()?
+        .into_iter()
+        .map(|(_, c)| c.heading)
+        .collect();
+    assert_eq!(headings, vec!["first", "second"]);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+#![recursion_limit = "512"]
+//! Integration tests for the `Persist` write methods.
+
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "slug")]
+pub struct PersistArticle {
+    pub slug: String,
+    pub title: String,
+}
+
+#[db_test(db = "orm_persist_test")]
+async fn test_create_save_fetch_delete(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<PersistArticle>(spec.clone().into())
+        .await?;
+
+    let mut article = PersistArticle {
+        slug: "hello".to_string(),
+        title: "Hello".to_string(),
+    };
+    let id = PersistArticle::create(&article, &client, &spec)
+        .await?
+        .root_ref::<PersistArticle>()?;
+    assert_eq!(
+        PersistArticle::fetch(&id, &client, &spec).await?,
+        Some(article.clone())
+    );
+
+    // Creating again leaves the stored document alone; save replaces it.
+    article.title = "Hello, again".to_string();
+    PersistArticle::create(&article, &client, &spec).await?;
+    assert_eq!(
+        PersistArticle::fetch(&id, &client, &spec)
+            .await?
+            .map(|a| a.title),
+        Some("Hello".to_string())
+    );
+    article.save(&client, &spec).await?;
+    assert_eq!(
+        PersistArticle::fetch(&id, &client, &spec).await?,
+        Some(article.clone())
+    );
+
+    // Reads through ModelExt see the same document.
+    let found = PersistArticle::find(id.clone())
+        .with_client(&client)
+        .execute_one(&spec)
+        .await?;
+    assert_eq!(found, article);
+
+    article.delete(&client, &spec).await?;
+    assert_eq!(PersistArticle::fetch(&id, &client, &spec).await?, None);
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ use serde::{Deserialize, Serialize};
 
 /// A user in the system
 #[derive(Clone, Debug, Default, TerminusDBModel)]
+#[tdb(has_many_through(name = "post_comments", target = "Comment", via = "Post"))]
 pub struct User {
     pub name: String,
     pub email: String,
@@ -74,6 +75,28 @@ pub struct Wheel {
     pub brand: String,
 }
 
+/// An image attached to a post
+#[derive(Clone, Debug, TerminusDBModel)]
+pub struct Image {
+    pub url: String,
+    pub post: TdbLazy<Post>,
+}
+
+/// A video attached to a post
+#[derive(Clone, Debug, TerminusDBModel)]
+pub struct Video {
+    pub url: String,
+    pub seconds: u32,
+    pub post: TdbLazy<Post>,
+}
+
+/// A polymorphic attachment: a TaggedUnion whose variants both reference Post
+#[derive(Clone, Debug, TerminusDBModel)]
+pub enum Attachment {
+    Image(Image),
+    Video(Video),
+}
+
 // ============================================================================
 // Trait Implementations - NOW AUTOMATICALLY DERIVED!
 // ============================================================================
@@ -182,6 +205,174 @@ fn test_with_chained_reverse_relations() {
     assert_eq!(query.relations().len(), 1);
 }
 
+// ============================================================================
+// Test: Relation Trees - .with_tree(|r| ...)
+// ============================================================================
+
+#[test]
+fn test_with_tree_builds_nested_specs() {
+    // User <- Post <- Comment, with the comment authors loaded as well
+    let id = EntityIDFor::<User>::new("user1").unwrap();
+    let query = User::find(id).with_tree(|r| {
+        r.with::<Document>().with_nested::<Post>(|r| {
+            r.with_nested::<Comment>(|r| r.with_field::<User, CommentFields::Author>())
+        })
+    });
+
+    let relations = query.relations();
+    assert_eq!(relations.len(), 2);
+    assert_eq!(relations[0].target_type_name, "Document");
+    assert_eq!(relations[1].target_type_name, "Post");
+
+    let comment = &relations[1].children[0];
+    assert_eq!(comment.target_type_name, "Comment");
+    match &comment.children[0].direction {
+        RelationDirection::Forward { field_name } => assert_eq!(field_name, "author"),
+        _ => panic!("Expected Forward direction"),
+    }
+}
+
+#[test]
+fn test_fetch_builder_trees_share_one_query() {
+    let users = [EntityIDFor::<User>::new("user1").unwrap()];
+    let posts = [
+        EntityIDFor::<Post>::new("post1").unwrap(),
+        EntityIDFor::<Post>::new("post2").unwrap(),
+    ];
+    let builder = FetchBuilder::new()
+        .add_id("Wheel/w1")
+        .add_tree(users, |r| r.with_nested::<Post>(|r| r.with::<Comment>()))
+        .add_tree(posts, |r| r.with::<Comment>());
+
+    // Plain and tree IDs are all fetched
+    assert_eq!(builder.len(), 4);
+
+    let query = builder.build_query().expect("relation query");
+    assert!(query.contains("tree0: User(id: \"User/user1\")"));
+    assert!(query.contains("_user_of_Post {"));
+    assert!(query.contains("tree1: Post(ids: [\"Post/post1\",\"Post/post2\"])"));
+    assert_eq!(query.matches("_post_of_Comment").count(), 2);
+    assert_eq!(query.matches("query {").count(), 1);
+
+    assert!(FetchBuilder::new()
+        .add_id("Wheel/w1")
+        .build_query()
+        .is_none());
+}
+
+// ============================================================================
+// Test: Through Relations - .with_through::<Target, Via>()
+// ============================================================================
+
+#[test]
+fn test_with_through_nests_target_under_via() {
+    // User <- Post (user) <- Comment (post)
+    let id = EntityIDFor::<User>::new("user1").unwrap();
+    let query = User::find(id).with_through::<Comment, Post>();
+
+    assert_eq!(query.relations().len(), 1);
+    let via = &query.relations()[0];
+    assert_eq!(via.target_type_name, "Post");
+    match &via.direction {
+        RelationDirection::Reverse { via_field } => assert_eq!(via_field.as_deref(), Some("user")),
+        _ => panic!("Expected Reverse direction"),
+    }
+
+    assert_eq!(via.children.len(), 1);
+    let target = &via.children[0];
+    assert_eq!(target.target_type_name, "Comment");
+    match &target.direction {
+        RelationDirection::Reverse { via_field } => assert_eq!(via_field.as_deref(), Some("post")),
+        _ => panic!("Expected Reverse direction"),
+    }
+}
+
+#[test]
+fn test_derived_through_accessor() {
+    assert_eq!(
+        <User as HasManyThrough<Comment, Post>>::via_field(),
+        Some("user")
+    );
+    assert_eq!(
+        <User as HasManyThrough<Comment, Post>>::target_field(),
+        Some("post")
+    );
+
+    let id = EntityIDFor::<User>::new("user1").unwrap();
+    let query = Through::<User, Comment, Post>::from_id(id).query().unwrap();
+    assert_eq!(query.len(), 1);
+    assert_eq!(query.relations()[0].children.len(), 1);
+
+    // The accessor returns the same relation for an instance
+    let user = User::default();
+    let _: Through<User, Comment, Post> = user.post_comments();
+}
+
+// ============================================================================
+// Test: Polymorphic Relations - TaggedUnion targets
+// ============================================================================
+
+#[test]
+fn test_with_tagged_union_loads_each_variant() {
+    // Attachment documents are stored as Image or Video instances, each with
+    // its own `post` link, so the relation is loaded once per variant class
+    let id = EntityIDFor::<Post>::new("post1").unwrap();
+    let query = Post::find(id).with::<Attachment>();
+
+    let relations = query.relations();
+    assert_eq!(relations.len(), 2);
+    assert_eq!(relations[0].target_type_name, "Image");
+    assert_eq!(relations[1].target_type_name, "Video");
+    for relation in relations {
+        match &relation.direction {
+            RelationDirection::Reverse { via_field } => {
+                assert_eq!(via_field.as_deref(), Some("post"))
+            }
+            _ => panic!("Expected Reverse direction"),
+        }
+    }
+}
+
+#[test]
+fn test_tagged_union_documents_from_variant_classes() {
+    let result = OrmResult::new(vec![
+        serde_json::json!({
+            "@id": "Image/img1",
+            "@type": "Image",
+            "url": "cat.png",
+            "post": "Post/post1"
+        }),
+        serde_json::json!({
+            "@id": "Video/vid1",
+            "@type": "Video",
+            "url": "cat.mp4",
+            "seconds": 12,
+            "post": "Post/post1"
+        }),
+        serde_json::json!({
+            "@id": "Post/post1",
+            "@type": "Post",
+            "title": "Cats",
+            "content": "",
+            "user": "User/user1"
+        }),
+    ]);
+
+    assert!(result.contains::<Attachment>());
+    assert_eq!(
+        result.get_ids::<Attachment>(),
+        vec!["Image/img1", "Video/vid1"]
+    );
+
+    let attachments = result.get::<Attachment>().unwrap();
+    assert_eq!(attachments.len(), 2);
+    assert!(matches!(&attachments[0], Attachment::Image(image) if image.url == "cat.png"));
+    assert!(matches!(&attachments[1], Attachment::Video(video) if video.seconds == 12));
+
+    // The variants remain available by their own class
+    assert_eq!(result.get::<Image>().unwrap().len(), 1);
+}
+
 // ============================================================================
 // Test: Reverse Relations with Field - .with_via::<T, Field>()
 // ============================================================================
@@ -379,6 +570,51 @@ mod integration {
         Ok(())
     }
 
+    #[db_test(db = "orm_lazy_load_test")]
+    async fn test_lazy_load_fetches_once(client: _, spec: _) -> anyhow::Result<()> {
+        let schema_args = DocumentInsertArgs {
+            spec: spec.clone(),
+            ..Default::default()
+        };
+
+        client
+            .insert_schema(&User::to_schema(), schema_args.clone())
+            .await?;
+        client
+            .insert_schema(&Post::to_schema(), schema_args.clone())
+            .await?;
+
+        let user = User {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        };
+        let user_id = client
+            .save_instance(&user, schema_args.clone())
+            .await?
+            .root_id;
+        let post = Post {
+            title: "First Post".to_string(),
+            content: "Hello world".to_string(),
+            user: TdbLazy::new_id(&user_id)?,
+        };
+        let post_id = client.save_instance(&post, schema_args).await?.root_id;
+
+        // Loaded without its relations, the post holds a reference
+        let post: Post = Post::find_by_string(&post_id)
+            .with_client(&client)
+            .execute_one(&spec)
+            .await?;
+        assert!(!post.user.is_loaded());
+
+        let author = post.user.load(&client, &spec).await?;
+        assert_eq!(author.name, "Alice");
+
+        // Cached in the link from now on
+        assert!(post.user.is_loaded());
+        assert_eq!(post.user.get_expect().email, "alice@example.com");
+        Ok(())
+    }
+
     #[db_test(db = "orm_with_via_test")]
     async fn test_execute_with_via_specific_field(client: _, spec: _) -> anyhow::Result<()> {
         // Insert schemas
@@ -0,0 +1,87 @@
+#![recursion_limit = "512"]
+//! Tests for `BatchResolver` debug mode and `QueryPlan::explain`.
+
+use terminusdb_client::DocumentInsertArgs;
+use terminusdb_orm::prelude::*;
+use terminusdb_schema::TdbLazy;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, TerminusDBModel)]
+pub struct DbgAuthor {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, TerminusDBModel)]
+pub struct DbgPost {
+    pub title: String,
+    pub author: TdbLazy<DbgAuthor>,
+}
+
+#[test]
+fn test_query_plan_explain() {
+    let explained = DbgAuthor::all().with::<DbgPost>().plan().explain();
+    assert!(
+        explained
+            .starts_with("DbgAuthor [ids found at execution, Batch]\n└── DbgAuthor <- DbgPost"),
+        "unexpected plan:\n{explained}"
+    );
+}
+
+#[db_test(db = "orm_resolver_debug_test")]
+async fn test_debug_mode_flags_per_entity_resolution(client: _, spec: _) -> anyhow::Result<()> {
+    let args = DocumentInsertArgs::from(spec.clone());
+    client
+        .insert_entity_schema::<DbgAuthor>(args.clone())
+        .await?;
+    client.insert_entity_schema::<DbgPost>(args.clone()).await?;
+
+    let author_id = client
+        .insert_instance(&DbgAuthor { name: "Ada".into() }, args.clone())
+        .await?
+        .root_id
+        .clone();
+    let mut post_ids = Vec::new();
+    for title in ["first", "second"] {
+        let post = DbgPost {
+            title: title.into(),
+            author: TdbLazy::new_id(&author_id)?,
+        };
+        post_ids.push(client.insert_instance(&post, args.clone()).await?.root_id);
+    }
+
+    // One call per post: the N+1 pattern.
+    let resolver = BatchResolver::new(&client).with_debug();
+    for post_id in &post_ids {
+        let authors = resolver
+            .resolve_forward(vec![post_id.clone()], "author", "DbgAuthor", &spec)
+            .await?;
+        assert_eq!(authors.get::<DbgAuthor>()?.len(), 1);
+    }
+    assert_eq!(
+        resolver.repeated_relations(),
+        vec![("DbgPost.author -> DbgAuthor".to_string(), 2)]
+    );
+    let kinds: Vec<_> = resolver.queries().iter().map(|q| q.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TracedQueryKind::GraphQL,
+            TracedQueryKind::DocumentFetch,
+            TracedQueryKind::GraphQL,
+            TracedQueryKind::DocumentFetch,
+        ]
+    );
+
+    // The same relation resolved once for all parents is not flagged.
+    let resolver = BatchResolver::new(&client).with_debug();
+    let posts = resolver
+        .resolve_reverse(vec![author_id], Some("author"), "DbgPost", &spec)
+        .await?;
+    assert_eq!(posts.get::<DbgPost>()?.len(), 2);
+    assert!(resolver.repeated_relations().is_empty());
+
+    Ok(())
+}
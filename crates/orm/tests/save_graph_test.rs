@@ -0,0 +1,85 @@
+#![recursion_limit = "512"]
+//! Tests for `SaveGraph` dependency ordering and `Persist::save_deep`.
+
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "name")]
+pub struct DeepWriter {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "title")]
+pub struct DeepPost {
+    pub title: String,
+    pub writer: TdbLazy<DeepWriter>,
+}
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "text")]
+pub struct DeepComment {
+    pub text: String,
+    pub post_id: EntityIDFor<DeepPost>,
+}
+
+fn post() -> anyhow::Result<DeepPost> {
+    Ok(DeepPost {
+        title: "Hello".to_string(),
+        writer: TdbLazy::new_data(DeepWriter {
+            name: "Ada".to_string(),
+        })?,
+    })
+}
+
+#[test]
+fn test_referenced_documents_come_first() -> anyhow::Result<()> {
+    let post = post()?;
+    let comment = DeepComment {
+        text: "Nice".to_string(),
+        post_id: post.known_id().expect("lexical key yields an id"),
+    };
+
+    // Added referrer-first; saved referenced-first.
+    let graph = SaveGraph::new().add(&comment).add(&post);
+    let order: Vec<String> = graph
+        .ordered()
+        .iter()
+        .map(|instance| instance.schema.class_name().to_string())
+        .collect();
+    assert_eq!(order, vec!["DeepWriter", "DeepPost", "DeepComment"]);
+
+    // Adding a document again replaces it instead of saving it twice.
+    assert_eq!(graph.add(&post).len(), 3);
+    Ok(())
+}
+
+#[db_test(db = "orm_save_graph_test")]
+async fn test_save_deep_inserts_linked_models(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<DeepWriter>(spec.clone().into())
+        .await?;
+    client
+        .insert_entity_schema::<DeepPost>(spec.clone().into())
+        .await?;
+
+    // The writer is not stored yet; a plain save of the post would dangle.
+    let post = post()?;
+    post.save_deep(&client, &spec).await?;
+
+    let writer_id = EntityIDFor::<DeepWriter>::new("DeepWriter/Ada")?;
+    assert_eq!(
+        DeepWriter::fetch(&writer_id, &client, &spec).await?,
+        Some(DeepWriter {
+            name: "Ada".to_string()
+        })
+    );
+    let post_id = post.known_id().expect("lexical key yields an id");
+    assert!(DeepPost::fetch(&post_id, &client, &spec).await?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+#![recursion_limit = "512"]
+//! Integration tests for `OrmSession`.
+
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "code")]
+pub struct SessionCity {
+    pub code: String,
+    pub name: String,
+}
+
+fn city(code: &str, name: &str) -> SessionCity {
+    SessionCity {
+        code: code.to_string(),
+        name: name.to_string(),
+    }
+}
+
+#[db_test(db = "orm_session_test")]
+async fn test_session_commits_queued_changes(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<SessionCity>(spec.clone().into())
+        .await?;
+    client
+        .insert_instance(&city("ams", "Amsterdam"), spec.clone().into())
+        .await?;
+
+    let ams = EntityIDFor::<SessionCity>::new("SessionCity/ams")?;
+    let mut session = OrmSession::new(&client, &spec).with_message("Cities");
+
+    // The second lookup is served from the identity map, so it does not see
+    // a write made after the first one.
+    let mut loaded = session.get(&ams).await?.expect("stored city");
+    client
+        .save_instance(&city("ams", "Mokum"), spec.clone().into())
+        .await?;
+    assert_eq!(session.get(&ams).await?, Some(loaded.clone()));
+
+    loaded.name = "Amsterdam NL".to_string();
+    session.update(loaded.clone())?;
+    session.insert(city("ber", "Berlin"));
+    session.insert(city("ber", "Berlin DE"));
+    session.insert(city("par", "Paris"));
+    session.delete(&city("par", "Paris"))?;
+    assert_eq!(session.pending_count(), 2, "same-ID changes are merged");
+
+    session.commit().await?;
+    assert!(session.is_empty());
+
+    let mut fresh = OrmSession::new(&client, &spec);
+    let ids = ["ams", "ber", "par"]
+        .iter()
+        .map(|code| EntityIDFor::<SessionCity>::new(&format!("SessionCity/{}", code)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    assert_eq!(
+        fresh.get_all(&ids).await?,
+        vec![loaded, city("ber", "Berlin DE")]
+    );
+
+    fresh.delete_by_id(&ids[1]);
+    assert_eq!(fresh.get(&ids[1]).await?, None);
+    fresh.commit().await?;
+    assert_eq!(OrmSession::new(&client, &spec).get(&ids[1]).await?, None);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+#![recursion_limit = "512"]
+//! Tests for paged streaming with `ModelQuery::stream_paged`.
+
+use futures_util::TryStreamExt;
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "n")]
+pub struct StreamItem {
+    pub n: i32,
+}
+
+#[db_test(db = "orm_stream_test")]
+async fn test_stream_pages_through_all_results(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<StreamItem>(spec.clone().into())
+        .await?;
+    let items: Vec<StreamItem> = (0..7).map(|n| StreamItem { n }).collect();
+    client
+        .insert_instances(items.clone(), spec.clone().into())
+        .await?;
+
+    // Seven items in pages of three: two full pages and a partial one.
+    let mut streamed: Vec<StreamItem> = StreamItem::all()
+        .with_client(&client)
+        .stream_paged(&spec, 3)
+        .try_collect()
+        .await?;
+    streamed.sort_by_key(|item| item.n);
+    assert_eq!(streamed, items);
+
+    // Offset and limit bound the whole stream, not each page.
+    let window: Vec<StreamItem> = StreamItem::all()
+        .offset(1)
+        .limit(4)
+        .with_client(&client)
+        .stream_paged(&spec, 3)
+        .try_collect()
+        .await?;
+    assert_eq!(window.len(), 4);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+#![recursion_limit = "512"]
+//! Tests for `Tracked<T>` change tracking and partial saves.
+
+use terminusdb_client::deserialize::DefaultTDBDeserializer;
+use terminusdb_orm::prelude::*;
+use terminusdb_schema_derive::TerminusDBModel;
+use terminusdb_test::test as db_test;
+
+use terminusdb_schema; // required by the derive
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(key = "lexical", key_fields = "sku")]
+pub struct TrackedProduct {
+    pub sku: String,
+    pub name: String,
+    pub price: i32,
+    pub note: Option<String>,
+}
+
+fn product() -> TrackedProduct {
+    TrackedProduct {
+        sku: "p1".to_string(),
+        name: "Lamp".to_string(),
+        price: 40,
+        note: None,
+    }
+}
+
+#[test]
+fn test_changed_fields_compares_against_snapshot() {
+    let mut tracked = Tracked::new(product());
+    assert!(!tracked.is_dirty());
+
+    tracked.price = 45;
+    tracked.note = Some("on sale".to_string());
+    assert_eq!(tracked.changed_fields(), vec!["note", "price"]);
+
+    // Restoring the original value makes the field clean again.
+    tracked.price = 40;
+    assert_eq!(tracked.changed_fields(), vec!["note"]);
+
+    tracked.mark_clean();
+    assert!(!tracked.is_dirty());
+    assert_eq!(tracked.into_inner().note.as_deref(), Some("on sale"));
+}
+
+#[db_test(db = "orm_tracked_test")]
+async fn test_save_patches_only_changed_fields(client: _, spec: _) -> anyhow::Result<()> {
+    client
+        .insert_entity_schema::<TrackedProduct>(spec.clone().into())
+        .await?;
+    let product_id = client
+        .insert_instance(&product(), spec.clone().into())
+        .await?
+        .root_ref::<TrackedProduct>()?;
+    assert_eq!(Some(product_id.clone()), product().known_id());
+
+    let result = TrackedProduct::find_by_string(product_id.typed())
+        .with_client(&client)
+        .execute(&spec)
+        .await?;
+    let mut tracked = result.get_tracked::<TrackedProduct>()?.remove(0);
+
+    // A concurrent writer changes `name` after we loaded the product.
+    let mut concurrent = product();
+    concurrent.name = "Desk lamp".to_string();
+    client
+        .save_instance(&concurrent, spec.clone().into())
+        .await?;
+
+    tracked.price = 35;
+    assert_eq!(tracked.save(&client, &spec).await?, vec!["price"]);
+    assert!(!tracked.is_dirty());
+    assert!(tracked.save(&client, &spec).await?.is_empty());
+
+    let stored: TrackedProduct = client
+        .get_instance(product_id.typed(), &spec, &mut DefaultTDBDeserializer)
+        .await?;
+    assert_eq!(stored.price, 35);
+    assert_eq!(stored.name, "Desk lamp", "unchanged fields are not written");
+
+    Ok(())
+}
@@ -481,3 +481,104 @@ pub fn generate_relation_impls(
         #(#orm_relation_impls)*
     }
 }
+
+/// A `has_many_through` relation declared on a struct
+pub struct ThroughRelation {
+    /// Name of the generated accessor method
+    pub name: syn::Ident,
+    /// The model the relation loads
+    pub target: syn::Path,
+    /// The model in between, which references the struct
+    pub via: syn::Path,
+}
+
+/// Generate `HasManyThrough` implementations and accessor methods
+///
+/// For each relation this generates `HasManyThrough<Target, Via>` for the struct, whose
+/// field lookups require `Via: ReverseRelation<Struct>` and `Target: ReverseRelation<Via>`,
+/// and a method returning a `Through` for the instance.
+pub fn generate_through_impls(
+    struct_name: &syn::Ident,
+    relations: &[ThroughRelation],
+    impl_generics: &TokenStream,
+    ty_generics: &TokenStream,
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let relation_path = match get_relation_path() {
+        Some(path) => path,
+        None => return quote! {},
+    };
+
+    let impls = relations.iter().map(|relation| {
+        let ThroughRelation { name, target, via } = relation;
+        let type_name = |path: &syn::Path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default()
+        };
+        let doc = format!(
+            "The `{}` entities linked to this `{}` through `{}`.",
+            type_name(target),
+            struct_name,
+            type_name(via)
+        );
+
+        quote! {
+            impl #impl_generics #relation_path::HasManyThrough<#target, #via> for #struct_name #ty_generics
+            #where_clause
+            {
+                fn via_field() -> Option<&'static str> {
+                    <#via as #relation_path::ReverseRelation<#struct_name #ty_generics>>::default_field_name()
+                }
+
+                fn target_field() -> Option<&'static str> {
+                    <#target as #relation_path::ReverseRelation<#via>>::default_field_name()
+                }
+            }
+
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #[doc = #doc]
+                pub fn #name(&self) -> #relation_path::Through<Self, #target, #via> {
+                    #relation_path::Through::new(self)
+                }
+            }
+        }
+    });
+
+    quote! { #(#impls)* }
+}
+
+/// Generate the `ReverseRelation` implementation for a TaggedUnion
+///
+/// The union gets `ReverseRelation<P>` for every `P` referenced by all of its model
+/// variants, so `.with::<Union>()` loads the union's documents through each variant
+/// class. The variants' own `ReverseRelation<P>` impls supply the link fields; their
+/// `TaggedUnionVariant` impls already require `ToTDBSchema`, which supplies the classes.
+pub fn generate_union_relation_impls(
+    enum_name: &syn::Ident,
+    variant_types: &[&Type],
+) -> TokenStream {
+    let relation_path = match get_relation_path() {
+        Some(path) => path,
+        None => return quote! {},
+    };
+
+    if variant_types.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        impl<P: terminusdb_schema::TerminusDBModel> #relation_path::ReverseRelation<P, #relation_path::DefaultField> for #enum_name
+        where
+            #(#variant_types: #relation_path::ReverseRelation<P, #relation_path::DefaultField>,)*
+        {
+            fn variant_relations() -> Vec<(String, Option<&'static str>)> {
+                vec![#((
+                    <#variant_types as terminusdb_schema::ToTDBSchema>::to_schema().class_name().to_string(),
+                    <#variant_types as #relation_path::ReverseRelation<P, #relation_path::DefaultField>>::default_field_name(),
+                )),*]
+            }
+        }
+    }
+}
@@ -3,8 +3,11 @@
 
 //! High-level relation traits and macros for TerminusDB models
 
+mod registry;
 mod traits;
 
+pub use registry::{RelationEdge, RelationRegistry};
+
 pub use traits::{
     basic_relation_constraints,
     generate_relation_constraints,
@@ -12,10 +15,12 @@ pub use traits::{
     BelongsTo,
     DefaultField,
     ForwardRelation,
+    HasManyThrough,
     RelationField,
     RelationFrom,
     RelationTo,
     ReverseRelation,
+    Through,
 };
 
 // Re-export for convenience
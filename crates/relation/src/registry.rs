@@ -0,0 +1,321 @@
+//! Runtime relation registry for traversing relations by name
+//!
+//! The typed markers in [`crate::traits`] need the model types at compile time.
+//! Generic tools (admin UIs, schema summaries) only have class and field names,
+//! so [`RelationRegistry`] records the same links as plain strings, taken from
+//! the schemas the derive macro generates.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use terminusdb_schema::{Schema, ToTDBSchema, TypeFamily};
+
+/// A link from a field of one class to another class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationEdge {
+    /// Class declaring the field
+    pub source: String,
+    /// Field (property) name
+    pub field: String,
+    /// Class the field points to
+    pub target: String,
+    /// `List`/`Set`/`Array`/`Optional`, or `None` for exactly one
+    pub family: Option<TypeFamily>,
+}
+
+impl RelationEdge {
+    /// Whether the field can hold more than one target
+    pub fn is_collection(&self) -> bool {
+        matches!(
+            self.family,
+            Some(TypeFamily::List | TypeFamily::Set(_) | TypeFamily::Array(_))
+        )
+    }
+
+    /// Whether the field may be empty
+    pub fn is_optional(&self) -> bool {
+        self.family.is_some()
+    }
+}
+
+/// Relations between classes, keyed by `(class, field)`
+///
+/// # Example
+/// ```ignore
+/// let registry = RelationRegistry::new().with::<BlogPost>().with::<Comment>();
+///
+/// assert_eq!(registry.target("Comment", "post"), Some("BlogPost"));
+/// assert_eq!(registry.traverse("Comment", ["post", "author"]), Some("Writer"));
+///
+/// for edge in registry.relations_to("BlogPost") {
+///     println!("{}.{} -> BlogPost", edge.source, edge.field);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RelationRegistry {
+    /// class -> field -> edge, own fields only
+    edges: BTreeMap<String, BTreeMap<String, RelationEdge>>,
+    /// class -> classes it inherits from
+    parents: BTreeMap<String, Vec<String>>,
+    /// Enums look like links in the schema but point at values, not documents
+    enums: BTreeSet<String>,
+}
+
+impl RelationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a registry from schema definitions
+    pub fn from_schemas<'a>(schemas: impl IntoIterator<Item = &'a Schema>) -> Self {
+        let mut registry = Self::new();
+        registry.add_schemas(schemas);
+        registry
+    }
+
+    /// Register `T` and every class reachable from it
+    pub fn with<T: ToTDBSchema>(mut self) -> Self {
+        self.register::<T>();
+        self
+    }
+
+    /// Register `T` and every class reachable from it
+    pub fn register<T: ToTDBSchema>(&mut self) -> &mut Self {
+        self.add_schemas(&T::to_schema_tree())
+    }
+
+    /// Register schema definitions, e.g. ones fetched from a database
+    pub fn add_schemas<'a>(&mut self, schemas: impl IntoIterator<Item = &'a Schema>) -> &mut Self {
+        for schema in schemas {
+            self.add_schema(schema);
+        }
+        self
+    }
+
+    fn add_schema(&mut self, schema: &Schema) {
+        let (id, properties) = match schema {
+            Schema::Enum { id, .. } => {
+                self.enums.insert(id.clone());
+                for fields in self.edges.values_mut() {
+                    fields.retain(|_, edge| edge.target != *id);
+                }
+                return;
+            }
+            Schema::Class {
+                id,
+                properties,
+                inherits,
+                ..
+            } => {
+                if !inherits.is_empty() {
+                    self.parents.insert(id.clone(), inherits.clone());
+                }
+                (id, properties)
+            }
+            Schema::OneOfClass { id, properties, .. }
+            | Schema::TaggedUnion { id, properties, .. } => (id, properties),
+        };
+
+        let fields = self.edges.entry(id.clone()).or_default();
+        fields.clear();
+        for property in properties {
+            if !property.is_link() || self.enums.contains(&property.class) {
+                continue;
+            }
+            fields.insert(
+                property.name.clone(),
+                RelationEdge {
+                    source: id.clone(),
+                    field: property.name.clone(),
+                    target: property.class.clone(),
+                    family: property.r#type,
+                },
+            );
+        }
+    }
+
+    /// Every registered class, enums excluded
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.edges.keys().map(String::as_str)
+    }
+
+    /// The relation declared by `field` on `class`, inherited fields included
+    pub fn relation(&self, class: &str, field: &str) -> Option<&RelationEdge> {
+        if let Some(edge) = self.edges.get(class).and_then(|fields| fields.get(field)) {
+            return Some(edge);
+        }
+        self.parents
+            .get(class)?
+            .iter()
+            .find_map(|parent| self.relation(parent, field))
+    }
+
+    /// The class `field` on `class` points to
+    pub fn target(&self, class: &str, field: &str) -> Option<&str> {
+        self.relation(class, field).map(|edge| edge.target.as_str())
+    }
+
+    /// Outgoing relations of `class`, inherited fields included
+    pub fn relations_from(&self, class: &str) -> Vec<&RelationEdge> {
+        let mut edges: Vec<&RelationEdge> = Vec::new();
+        let mut pending = vec![class];
+        let mut visited = BTreeSet::new();
+
+        while let Some(current) = pending.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            for edge in self.edges.get(current).into_iter().flat_map(|f| f.values()) {
+                // A subclass field shadows the inherited one of the same name
+                if !edges.iter().any(|e| e.field == edge.field) {
+                    edges.push(edge);
+                }
+            }
+            if let Some(parents) = self.parents.get(current) {
+                pending.extend(parents.iter().rev().map(String::as_str));
+            }
+        }
+
+        edges
+    }
+
+    /// Relations from any class pointing at `class`
+    pub fn relations_to(&self, class: &str) -> Vec<&RelationEdge> {
+        self.edges
+            .values()
+            .flat_map(|fields| fields.values())
+            .filter(|edge| edge.target == class)
+            .collect()
+    }
+
+    /// Follow a path of field names from `class`, returning the class reached
+    pub fn traverse<'a>(
+        &self,
+        class: &str,
+        path: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&str> {
+        let mut current = self.edges.get_key_value(class)?.0.as_str();
+        for field in path {
+            current = self.target(current, field)?;
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use terminusdb_schema::{Key, Property};
+
+    fn class(id: &str, inherits: &[&str], properties: Vec<Property>) -> Schema {
+        Schema::Class {
+            id: id.to_string(),
+            base: None,
+            key: Key::Random,
+            documentation: None,
+            subdocument: false,
+            r#abstract: false,
+            inherits: inherits.iter().map(|s| s.to_string()).collect(),
+            properties,
+            unfoldable: false,
+        }
+    }
+
+    fn prop(name: &str, class: &str, family: Option<TypeFamily>) -> Property {
+        Property {
+            name: name.to_string(),
+            r#type: family,
+            class: class.to_string(),
+        }
+    }
+
+    fn blog_schemas() -> Vec<Schema> {
+        vec![
+            class("Writer", &[], vec![prop("name", "xsd:string", None)]),
+            class(
+                "Content",
+                &[],
+                vec![prop("author", "Writer", Some(TypeFamily::Optional))],
+            ),
+            class(
+                "BlogPost",
+                &["Content"],
+                vec![
+                    prop("title", "xsd:string", None),
+                    prop("status", "Status", None),
+                    prop("comments", "Comment", Some(TypeFamily::List)),
+                ],
+            ),
+            class("Comment", &[], vec![prop("post", "BlogPost", None)]),
+            Schema::Enum {
+                id: "Status".to_string(),
+                base: None,
+                values: vec!["draft".to_string(), "published".to_string()],
+                documentation: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_lookup_by_name() {
+        let registry = RelationRegistry::from_schemas(&blog_schemas());
+
+        assert_eq!(registry.target("Comment", "post"), Some("BlogPost"));
+        assert_eq!(registry.target("BlogPost", "title"), None);
+        assert_eq!(registry.target("Nope", "post"), None);
+
+        let comments = registry.relation("BlogPost", "comments").unwrap();
+        assert!(comments.is_collection());
+        assert!(!registry.relation("Comment", "post").unwrap().is_optional());
+    }
+
+    #[test]
+    fn test_enum_fields_are_not_relations() {
+        // The enum is registered after the class using it
+        let registry = RelationRegistry::from_schemas(&blog_schemas());
+
+        assert_eq!(registry.target("BlogPost", "status"), None);
+        assert!(!registry.classes().any(|c| c == "Status"));
+    }
+
+    #[test]
+    fn test_inherited_relations() {
+        let registry = RelationRegistry::from_schemas(&blog_schemas());
+
+        assert_eq!(registry.target("BlogPost", "author"), Some("Writer"));
+
+        let fields: Vec<_> = registry
+            .relations_from("BlogPost")
+            .iter()
+            .map(|e| e.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["comments", "author"]);
+    }
+
+    #[test]
+    fn test_reverse_lookup_and_traversal() {
+        let registry = RelationRegistry::from_schemas(&blog_schemas());
+
+        let incoming: Vec<_> = registry
+            .relations_to("BlogPost")
+            .iter()
+            .map(|e| format!("{}.{}", e.source, e.field))
+            .collect();
+        assert_eq!(incoming, vec!["Comment.post"]);
+
+        assert_eq!(
+            registry.traverse("Comment", ["post", "author"]),
+            Some("Writer")
+        );
+        assert_eq!(registry.traverse("Comment", ["post", "title"]), None);
+        assert_eq!(registry.traverse("Comment", []), Some("Comment"));
+    }
+
+    #[test]
+    fn test_reregistering_replaces_fields() {
+        let mut registry = RelationRegistry::from_schemas(&blog_schemas());
+        registry.add_schemas(&[class("Comment", &[], vec![])]);
+
+        assert_eq!(registry.target("Comment", "post"), None);
+        assert!(registry.relations_to("BlogPost").is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Compositional relation traits for generating typed WOQL constraints from model relationships
 
+use std::marker::PhantomData;
+
 use terminusdb_schema::{EntityIDFor, TerminusDBModel};
 use terminusdb_woql2::prelude::{Query, Value};
 // Import macros so type_! macro can find them
@@ -269,6 +271,77 @@ pub trait ReverseRelation<Target: TerminusDBModel, Field: RelationField = Defaul
     fn default_field_name() -> Option<&'static str> {
         None
     }
+
+    /// The variant classes of a TaggedUnion with their reference to Target.
+    ///
+    /// The derive generates `ReverseRelation<T>` for a TaggedUnion when each of
+    /// its model variants references T. The documents holding the links are
+    /// instances of the variant classes, so the ORM loads the relation once per
+    /// `(variant class, default field name)` returned here. Empty for structs.
+    fn variant_relations() -> Vec<(String, Option<&'static str>)> {
+        Vec::new()
+    }
+}
+
+/// Two-hop relation: Via references Self, and Target references Via.
+///
+/// For `Author <- Post <- Comment`, `Author: HasManyThrough<Comment, Post>` loads
+/// the comments on an author's posts in one query, instead of loading the posts
+/// first and then their comments.
+///
+/// # Implementation
+/// The derive macro implements this for each `has_many_through` attribute and adds
+/// an accessor method with the given name:
+/// ```ignore
+/// #[derive(TerminusDBModel)]
+/// #[tdb(has_many_through(name = "comments", target = "Comment", via = "Post"))]
+/// struct Author { name: String }
+///
+/// let comments: Vec<Comment> = author.comments().execute(&spec).await?;
+/// ```
+pub trait HasManyThrough<Target: TerminusDBModel, Via: TerminusDBModel>: TerminusDBModel {
+    /// The field on Via that references Self, when there is exactly one.
+    fn via_field() -> Option<&'static str>;
+
+    /// The field on Target that references Via, when there is exactly one.
+    fn target_field() -> Option<&'static str>;
+}
+
+/// The Target entities reached from one Source entity through Via.
+///
+/// Returned by the accessor methods the derive generates for
+/// [`HasManyThrough`] relations. The ORM executes it.
+pub struct Through<Source: TerminusDBModel, Target, Via> {
+    source: Option<EntityIDFor<Source>>,
+    _phantom: PhantomData<(Target, Via)>,
+}
+
+impl<Source, Target, Via> Through<Source, Target, Via>
+where
+    Source: HasManyThrough<Target, Via>,
+    Target: TerminusDBModel,
+    Via: TerminusDBModel,
+{
+    /// Start from a model instance.
+    pub fn new(source: &Source) -> Self {
+        Self {
+            source: source.instance_id(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Start from an entity ID.
+    pub fn from_id(id: EntityIDFor<Source>) -> Self {
+        Self {
+            source: Some(id),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The ID of the source entity, if the instance has one.
+    pub fn source_id(&self) -> Option<&EntityIDFor<Source>> {
+        self.source.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,61 @@
+//! The runtime registry sees the same relations the derive macro generates
+
+use terminusdb_relation::RelationRegistry;
+use terminusdb_schema::TdbLazy;
+use terminusdb_schema_derive::TerminusDBModel;
+
+// Required for TerminusDBModel derive to work
+use terminusdb_schema;
+
+#[derive(TerminusDBModel, Debug, Clone)]
+#[tdb(key = "random", class_name = "Author")]
+struct Author {
+    name: String,
+    articles: Vec<TdbLazy<Article>>,
+    mentor: Option<TdbLazy<Author>>,
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+#[tdb(key = "random", class_name = "Article")]
+struct Article {
+    title: String,
+    author: TdbLazy<Author>,
+}
+
+#[test]
+fn test_registry_from_derived_models() {
+    let registry = RelationRegistry::new().with::<Author>();
+
+    // Article is reachable from Author, so it is registered too
+    assert_eq!(registry.target("Article", "author"), Some("Author"));
+    assert_eq!(registry.target("Author", "articles"), Some("Article"));
+    assert_eq!(registry.target("Author", "name"), None);
+
+    assert!(registry
+        .relation("Author", "articles")
+        .unwrap()
+        .is_collection());
+    assert!(registry.relation("Author", "mentor").unwrap().is_optional());
+    assert!(!registry
+        .relation("Article", "author")
+        .unwrap()
+        .is_optional());
+}
+
+#[test]
+fn test_dynamic_traversal() {
+    let registry = RelationRegistry::new().with::<Article>();
+
+    assert_eq!(
+        registry.traverse("Article", ["author", "mentor", "articles"]),
+        Some("Article")
+    );
+
+    let mut incoming: Vec<_> = registry
+        .relations_to("Author")
+        .iter()
+        .map(|edge| format!("{}.{}", edge.source, edge.field))
+        .collect();
+    incoming.sort();
+    assert_eq!(incoming, vec!["Article.author", "Author.mentor"]);
+}
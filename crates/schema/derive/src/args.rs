@@ -18,7 +18,8 @@ pub struct TDBModelOpts {
     #[darling(default)]
     pub(crate) base: Option<String>,
 
-    /// Optional - define a custom key strategy
+    /// Optional - define a custom key strategy: "random" (default), "lexical",
+    /// "hash" or "value_hash". Any other value is a compile error.
     #[darling(default)]
     pub(crate) key: Option<String>,
 
@@ -64,6 +65,36 @@ pub struct TDBModelOpts {
     /// field types that aren't `Filterable` yet).
     #[darling(default)]
     pub(crate) no_filter: bool,
+
+    /// Integer representation for a simple enum (`#[tdb(repr = "u8")]`, next
+    /// to a matching `#[repr(u8)]`). Emits `From<Enum> for u8` /
+    /// `TryFrom<u8> for Enum` using the declared discriminants, so the ordinal
+    /// survives a round trip through storage.
+    #[darling(default)]
+    pub(crate) repr: Option<String>,
+
+    /// Enum of document links (`#[tdb(one_of)]`): instead of a TaggedUnion,
+    /// the variants become an `@oneOf` group on the class holding the field.
+    #[darling(default)]
+    pub(crate) one_of: bool,
+
+    /// Two-hop relations, e.g.
+    /// `#[tdb(has_many_through(name = "comments", target = "Comment", via = "Post"))]`.
+    /// Each one implements `HasManyThrough<Target, Via>` and adds an accessor
+    /// method with the given name. Requires the `relations` feature.
+    #[darling(multiple)]
+    pub(crate) has_many_through: Vec<HasManyThroughOpts>,
+}
+
+/// A `has_many_through` relation on a struct
+#[derive(Debug, FromMeta, Clone)]
+pub struct HasManyThroughOpts {
+    /// Name of the accessor method
+    pub(crate) name: syn::Ident,
+    /// The model the relation loads
+    pub(crate) target: syn::Path,
+    /// The model in between, which has a link to this struct
+    pub(crate) via: syn::Path,
 }
 
 /// Rename case conversion strategies
@@ -216,6 +247,14 @@ pub struct TDBFieldOpts {
     #[darling(default)]
     pub(crate) default: bool,
 
+    /// Server-computed field (`#[tdb(readonly)]`): the property is part of the
+    /// schema and is read back on deserialization, but `to_instance()` omits it
+    /// so inserts/replaces never overwrite what the server or a trigger wrote.
+    /// Must be an `Option<T>` so an insert without it still passes the schema
+    /// check.
+    #[darling(default)]
+    pub(crate) readonly: bool,
+
     /// Exact cardinality for a `Set` field (`@cardinality`). Emits
     /// `SetCardinality::Exact`; mutually exclusive with min/max_cardinality.
     #[darling(default)]
@@ -228,6 +267,11 @@ pub struct TDBFieldOpts {
     /// Maximum cardinality for a `Set` field (`@max_cardinality`).
     #[darling(default)]
     pub(crate) max_cardinality: Option<usize>,
+
+    /// Field holding a `#[tdb(one_of)]` enum (`#[tdb(one_of)]`): stored as an
+    /// `@oneOf` group of the enum's variants instead of a property of its own.
+    #[darling(default)]
+    pub(crate) one_of: bool,
 }
 
 /// Container for attributes specified on enum variants
@@ -0,0 +1,128 @@
+use crate::prelude::*;
+use tracing::trace;
+
+/// Process an enum of document links marked `#[tdb(one_of)]` to generate a
+/// `ToOneOf` implementation. The enum gets no schema class of its own: the
+/// class holding it stores its variants as an `@oneOf` group.
+pub fn implement_for_one_of_enum(
+    input: &DeriveInput,
+    opts: &TDBModelOpts,
+) -> proc_macro2::TokenStream {
+    let enum_name = &input.ident;
+    let Data::Enum(data_enum) = &input.data else {
+        return syn::Error::new(
+            enum_name.span(),
+            "#[tdb(one_of)] is only supported on enums; mark the field holding the enum with #[tdb(one_of)]",
+        )
+        .to_compile_error();
+    };
+
+    trace!("Processing OneOf enum: {}", enum_name);
+
+    // Same naming as TaggedUnion variants: lowercase unless rename_all says otherwise
+    let rename_strategy = match opts.get_rename_strategy() {
+        crate::args::RenameStrategy::None => crate::args::RenameStrategy::Lowercase,
+        other => other,
+    };
+
+    let mut variants = Vec::new();
+    for variant in &data_enum.variants {
+        match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let choice = rename_strategy.apply(&variant.ident.to_string());
+                variants.push((&variant.ident, choice, &fields.unnamed[0].ty));
+            }
+            _ => {
+                return syn::Error::new(
+                    variant.span(),
+                    "#[tdb(one_of)] enum variants must wrap a single document link, e.g. `Person(TdbLazy<Person>)`",
+                )
+                .to_compile_error();
+            }
+        }
+    }
+
+    let choices = variants.iter().map(|(_, choice, ty)| {
+        quote! {
+            (#choice, <#ty as terminusdb_schema::ToSchemaClass>::to_class())
+        }
+    });
+
+    let to_choice_arms = variants.iter().map(|(ident, choice, ty)| {
+        quote! {
+            Self::#ident(link) => (
+                #choice,
+                <#ty as terminusdb_schema::ToInstanceProperty<Self>>::to_property(link, #choice, parent),
+            )
+        }
+    });
+
+    let from_choice_checks = variants.iter().map(|(ident, _, ty)| {
+        quote! {
+            if class == <#ty as terminusdb_schema::ToSchemaClass>::to_class() {
+                return ::core::result::Result::Ok(Self::#ident(
+                    <#ty as terminusdb_schema::FromInstanceProperty>::from_property(prop)?,
+                ));
+            }
+        }
+    });
+
+    let choice_from_json_arms = variants.iter().map(|(_, choice, ty)| {
+        quote! {
+            #choice => <#ty as terminusdb_schema::json::InstancePropertyFromJson<Self>>::property_from_json(json)
+        }
+    });
+
+    let variant_types = variants.iter().map(|(_, _, ty)| ty);
+
+    quote! {
+        impl terminusdb_schema::ToOneOf for #enum_name {
+            fn choices() -> Vec<(&'static str, String)> {
+                vec![#(#choices),*]
+            }
+
+            fn to_choice(
+                self,
+                parent: &terminusdb_schema::Schema,
+            ) -> (&'static str, terminusdb_schema::InstanceProperty) {
+                match self {
+                    #(#to_choice_arms),*
+                }
+            }
+
+            fn from_choice(
+                class: &str,
+                prop: &terminusdb_schema::InstanceProperty,
+            ) -> ::core::result::Result<Self, anyhow::Error> {
+                #(#from_choice_checks)*
+                ::core::result::Result::Err(anyhow::anyhow!(
+                    "{} cannot link to a document of type '{}'",
+                    stringify!(#enum_name),
+                    class
+                ))
+            }
+
+            fn choice_from_json(
+                choice: &str,
+                json: serde_json::Value,
+            ) -> ::core::result::Result<terminusdb_schema::InstanceProperty, anyhow::Error> {
+                match choice {
+                    #(#choice_from_json_arms,)*
+                    _ => ::core::result::Result::Err(anyhow::anyhow!(
+                        "'{}' is not a choice of {}",
+                        choice,
+                        stringify!(#enum_name)
+                    )),
+                }
+            }
+
+            fn to_schema_tree_mut(
+                collection: &mut std::collections::HashSet<terminusdb_schema::Schema>,
+            ) {
+                #(
+                    <#variant_types as terminusdb_schema::ToMaybeTDBSchema>::to_schema_tree_mut(collection);
+                )*
+            }
+        }
+    }
+}
@@ -132,6 +132,9 @@ pub fn implement_for_simple_enum(
     // Generate TDBEnum trait implementation for enum deserialization
     let tdb_enum_impl = generate_tdbenum_impl(data_enum, enum_name, rename_strategy);
 
+    // Generate From/TryFrom for `#[tdb(repr = "...")]`
+    let repr_impl = generate_repr_conversions(data_enum, enum_name, opts);
+
     // Combine all implementations
     quote! {
         #schema_impl
@@ -143,6 +146,8 @@ pub fn implement_for_simple_enum(
         #class_marker_impl
 
         #tdb_enum_impl
+
+        #repr_impl
     }
 }
 
@@ -201,6 +206,19 @@ fn generate_tdbenum_impl(
         })
         .collect();
 
+    // Generate ordinal()/from_ordinal() - a unit-only enum casts to its
+    // discriminant, so explicit `Variant = n` values are honoured for free
+    let ordinal_arms: Vec<_> = variant_info
+        .iter()
+        .map(|(ident, _)| quote! { #enum_name::#ident => #enum_name::#ident as i64 })
+        .collect();
+    let from_ordinal_arms: Vec<_> = variant_info
+        .iter()
+        .map(
+            |(ident, _)| quote! { n if n == #enum_name::#ident as i64 => Some(#enum_name::#ident) },
+        )
+        .collect();
+
     quote! {
         impl terminusdb_schema::TDBEnum for #enum_name {
             fn variants() -> Vec<Self> {
@@ -219,6 +237,181 @@ fn generate_tdbenum_impl(
                     _ => None,
                 }
             }
+
+            fn ordinal(&self) -> i64 {
+                match self {
+                    #(#ordinal_arms),*
+                }
+            }
+
+            fn from_ordinal(ordinal: i64) -> Option<Self> {
+                match ordinal {
+                    #(#from_ordinal_arms,)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Integer types accepted by `#[tdb(repr = "...")]`, with their value range.
+const REPR_TYPES: &[(&str, i128, i128)] = &[
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+    ("isize", i64::MIN as i128, i64::MAX as i128),
+    ("u8", 0, u8::MAX as i128),
+    ("u16", 0, u16::MAX as i128),
+    ("u32", 0, u32::MAX as i128),
+    ("u64", 0, u64::MAX as i128),
+    ("usize", 0, u64::MAX as i128),
+];
+
+/// Reject `#[tdb(repr)]` on anything but a simple enum, with a non-integer
+/// type, without a matching `#[repr(..)]`, or with discriminants outside the
+/// type's range.
+pub fn validate_repr(input: &DeriveInput, opts: &TDBModelOpts) -> Result<(), syn::Error> {
+    let Some(repr) = &opts.repr else {
+        return Ok(());
+    };
+    let data_enum = match &input.data {
+        Data::Enum(e) if e.variants.iter().all(|v| matches!(v.fields, Fields::Unit)) => e,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "#[tdb(repr)] is only supported on simple enums (unit variants only)",
+            ))
+        }
+    };
+    let Some(&(_, min, max)) = REPR_TYPES.iter().find(|(ty, _, _)| ty == repr) else {
+        let types: Vec<&str> = REPR_TYPES.iter().map(|(ty, _, _)| *ty).collect();
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!(
+                "#[tdb(repr = \"{}\")] must be one of: {}",
+                repr,
+                types.join(", ")
+            ),
+        ));
+    };
+
+    // The conversions cast with `as`, so the enum must have the same layout.
+    let mut declared = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if REPR_TYPES.iter().any(|(ty, _, _)| ident == ty) {
+                    declared = Some(ident.to_string());
+                }
+            }
+            // Skip the arguments of e.g. `align(4)`.
+            if meta.input.peek(syn::token::Paren) {
+                let args;
+                syn::parenthesized!(args in meta.input);
+                args.parse::<proc_macro2::TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+    match declared {
+        Some(declared) if declared == *repr => {}
+        Some(declared) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                format!(
+                    "#[tdb(repr = \"{}\")] does not match #[repr({})]",
+                    repr, declared
+                ),
+            ))
+        }
+        None => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                format!(
+                    "#[tdb(repr = \"{}\")] needs #[repr({})] on the enum",
+                    repr, repr
+                ),
+            ))
+        }
+    }
+
+    // Discriminants given as integer literals, and the implicit ones after them.
+    let mut next = Some(0i128);
+    for variant in &data_enum.variants {
+        if let Some((_, expr)) = &variant.discriminant {
+            next = int_literal(expr);
+        }
+        if let Some(value) = next {
+            if value < min || value > max {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!(
+                        "discriminant {} of {} does not fit in {}",
+                        value, variant.ident, repr
+                    ),
+                ));
+            }
+        }
+        next = next.map(|value| value + 1);
+    }
+    Ok(())
+}
+
+/// Value of an integer literal discriminant such as `10` or `-1`.
+fn int_literal(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        syn::Expr::Group(group) => int_literal(&group.expr),
+        syn::Expr::Paren(paren) => int_literal(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Generate `From<Enum> for repr` and `TryFrom<repr> for Enum` for
+/// `#[tdb(repr = "...")]`, both keyed on the declared discriminants.
+fn generate_repr_conversions(
+    data_enum: &DataEnum,
+    enum_name: &syn::Ident,
+    opts: &TDBModelOpts,
+) -> proc_macro2::TokenStream {
+    let Some(repr) = &opts.repr else {
+        return quote! {};
+    };
+    let repr_ty = syn::Ident::new(repr, proc_macro2::Span::call_site());
+
+    let try_from_arms = data_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        quote! { v if v == #enum_name::#ident as #repr_ty => ::core::result::Result::Ok(#enum_name::#ident) }
+    });
+
+    quote! {
+        impl From<#enum_name> for #repr_ty {
+            fn from(value: #enum_name) -> Self {
+                value as #repr_ty
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr_ty> for #enum_name {
+            type Error = terminusdb_schema::InvalidDiscriminant;
+
+            fn try_from(value: #repr_ty) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    #(#try_from_arms,)*
+                    other => ::core::result::Result::Err(terminusdb_schema::InvalidDiscriminant {
+                        enum_name: stringify!(#enum_name),
+                        value: other as i128,
+                    }),
+                }
+            }
         }
     }
 }
@@ -111,14 +111,12 @@ pub fn implement_for_tagged_enum(
         }
     } else {
         // Collect variant struct schema names for inclusion in the schema tree
+        // Share `collection` with the variant structs: a fresh `to_schema_tree()`
+        // per variant would recurse forever for variants that refer back to the enum.
         let variant_struct_names = virtual_structs.iter().map(|(struct_name, _)| {
             let struct_name_ident = format_ident!("{}", struct_name);
             quote! {
-                for variant_schema in <#struct_name_ident as terminusdb_schema::ToTDBSchema>::to_schema_tree() {
-                    if !collection.iter().any(|s| s.class_name() == variant_schema.class_name()) {
-                        collection.insert(variant_schema);
-                    }
-                }
+                <#struct_name_ident as terminusdb_schema::ToTDBSchema>::to_schema_tree_mut(collection);
             }
         }).collect::<Vec<_>>();
 
@@ -269,6 +267,16 @@ pub fn implement_for_tagged_enum(
         }
     });
 
+    // Relations to the union resolve through its model variants
+    #[cfg(feature = "relations")]
+    let relation_impls = terminusdb_relation_derive::generate_union_relation_impls(
+        enum_name,
+        &single_field_variant_types,
+    );
+
+    #[cfg(not(feature = "relations"))]
+    let relation_impls = quote! {};
+
     // Combine all the implementations
     quote! {
         #schema_impl
@@ -287,6 +295,8 @@ pub fn implement_for_tagged_enum(
 
         // Include TaggedUnionVariant marker implementations for single-field model types
         #(#single_field_marker_impls)*
+
+        #relation_impls
     }
 }
 
@@ -456,6 +466,7 @@ fn generate_virtual_structs(
                         rename_all: None,
                         key_fields: None,
                         no_filter: false,
+                        ..Default::default()
                     },
                     quote! { None },
                     quote! { SchemaTypeClass },
@@ -521,6 +532,7 @@ fn generate_virtual_structs(
                         rename_all: None,
                         key_fields: None,
                         no_filter: false,
+                        ..Default::default()
                     },
                     (&quote! {}, &quote! {}, &None), // No generics for virtual structs
                     None,                            // No custom ID extraction for virtual structs
@@ -597,6 +609,7 @@ fn generate_virtual_structs(
                     rename_all: None,
                     key_fields: None,
                     no_filter: false,
+                    ..Default::default()
                 };
 
                 // Process the struct fields to generate instance conversions
@@ -78,6 +78,8 @@ fn generate_struct_filter(
         .iter()
         // PhantomData isn't a real property.
         .filter(|f| !is_phantom_data_type(&f.ty))
+        // `@oneOf` groups are stored under their variants' properties, not the field name.
+        .filter(|f| !is_one_of_field(f))
         // The explicit id field (if any) is represented by the `_id` envelope.
         .filter(|f| match (f.ident.as_ref(), id_field_name) {
             (Some(id), Some(idf)) => *id != idf,
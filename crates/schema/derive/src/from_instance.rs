@@ -210,6 +210,14 @@ fn process_named_fields_for_deserialization<'a>(
             let property_name = field_opts.name.unwrap_or_else(|| field_name_str.clone());
             let field_ty = &field.ty;
 
+            // `#[tdb(one_of)]`: the variant is picked from whichever `@oneOf` property is set
+            if field_opts.one_of {
+                return Some(quote! {
+                    let #field_ident = <#field_ty as terminusdb_schema::ToOneOf>::from_instance_properties(instance)
+                        .with_context(|| format!("Failed to deserialize field '{}' for struct '{}'", #field_name_str, <Self as terminusdb_schema::ToTDBSchema>::schema_name()))?;
+                });
+            }
+
             // ALWAYS use from_maybe_property, passing the Option<&InstanceProperty> directly.
             // The trait implementation for T or Option<T> will handle missing/null/present cases.
             Some(quote! {
@@ -112,6 +112,24 @@ pub fn generate_totdbinstance_impl(
     }
 }
 
+/// Reject `#[tdb(readonly)]` on fields that are not `Option<T>`: inserts omit
+/// them, so the schema has to allow the property to be missing.
+pub fn validate_readonly(input: &DeriveInput) -> Result<(), syn::Error> {
+    let Data::Struct(data_struct) = &input.data else {
+        return Ok(());
+    };
+    for field in &data_struct.fields {
+        let readonly = TDBFieldOpts::from_field(field).is_ok_and(|opts| opts.readonly);
+        if readonly && !is_option_type(&field.ty) {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "#[tdb(readonly)] fields must be `Option<T>`",
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Process struct fields to convert them to InstanceProperty values
 /// (Used to generate part of the instance_body_code for structs)
 pub fn process_fields_for_instance(
@@ -125,6 +143,8 @@ pub fn process_fields_for_instance(
             // Skip PhantomData fields - they're zero-sized and don't need instance conversion
             !crate::prelude::is_phantom_data_type(&field.ty)
         })
+        // Skip `#[tdb(readonly)]` fields - they are owned by the server
+        .filter(|field| !TDBFieldOpts::from_field(field).is_ok_and(|opts| opts.readonly))
         .map(|field| {
             let field_name = field.ident.as_ref().unwrap();
             let _field_ty = &field.ty;
@@ -186,6 +206,16 @@ pub fn process_fields_for_instance(
 
              */
 
+            // `#[tdb(one_of)]`: stored under the property of the variant taken
+            if field_opts.one_of {
+                return quote! {
+                    {
+                        let (choice, link) = terminusdb_schema::ToOneOf::to_choice(#field_access, &schema);
+                        properties.insert(choice.to_string(), link);
+                    }
+                };
+            }
+
             quote! {
                 properties.insert(
                     #property_name.to_string(),
@@ -573,7 +573,18 @@ fn generate_field_deserializers(
 
         // Use property_from_maybe_json for all fields, letting the trait implementation
         // handle the differences between Option and non-Option types
-        let deserializer = if is_id_field {
+        let deserializer = if field_opts.one_of {
+            // `#[tdb(one_of)]`: one JSON key per variant, at most one of them set
+            quote! {
+                for (choice, _) in <#field_ty as terminusdb_schema::ToOneOf>::choices() {
+                    if let Some(json_value) = json_map.remove(choice) {
+                        let _prop = <#field_ty as terminusdb_schema::ToOneOf>::choice_from_json(choice, json_value)
+                            .with_context(|| format!("generate_field_deserializers() - @oneOf '{}'", choice))?;
+                        _properties.insert(choice.to_string(), _prop);
+                    }
+                }
+            }
+        } else if is_id_field {
             // Special handling for id_field - use the extracted @id value.
             // NOTE: use `quote!` (not `quote_spanned!` on `field.span()`): these
             // statements reference the `json_map` / `_properties` locals defined
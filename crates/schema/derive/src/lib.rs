@@ -3,6 +3,7 @@
 mod args;
 #[cfg(feature = "generic-derive")]
 mod bounds;
+mod enum_one_of;
 mod enum_simple;
 mod filter;
 mod from_tuple;
@@ -66,6 +67,10 @@ fn implement_for_enum(
 /// - `#[tdb(doc = "Class documentation")]` - Provide documentation.
 /// - `#[tdb(rename_all = "lowercase")]` - Rename all enum variants (for enums only). Supported values:
 ///   "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE", "kebab-case".
+/// - `#[tdb(repr = "u8")]` - Integer repr for simple enums: generates `From`/`TryFrom` conversions
+///   keyed on the declared discriminants (see `TDBEnum::ordinal` for declared-order comparisons).
+/// - `#[tdb(one_of)]` - Enum of document links (`Person(TdbLazy<Person>)`, ...): stored as an
+///   `@oneOf` group on the class holding it instead of a TaggedUnion. See `terminusdb_schema::ToOneOf`.
 ///
 /// # Field Attributes
 ///
@@ -74,6 +79,10 @@ fn implement_for_enum(
 /// - `#[tdb(name = "customName")]` - Custom property name (defaults to field name).
 /// - `#[tdb(class = "xsd:string")]` - Specify a custom class for this field.
 /// - `#[tdb(doc = "Property documentation")]` - Provide documentation.
+/// - `#[tdb(readonly)]` - Server-computed field: kept in the schema and read on deserialization,
+///   but left out of `to_instance()` so inserts/replaces never overwrite it. Must be `Option<T>`.
+/// - `#[tdb(one_of)]` - Field holding a `#[tdb(one_of)]` enum: exactly one of the enum's variant
+///   properties is stored, and the variant is read back from the type of the linked document.
 ///
 /// # Examples
 ///
@@ -187,6 +196,21 @@ pub fn derive_terminusdb_model(input: TokenStream) -> TokenStream {
         return err.to_compile_error().into();
     }
 
+    // `#[tdb(repr)]` only makes sense on simple enums
+    if let Err(err) = enum_simple::validate_repr(&input, &opts) {
+        return err.to_compile_error().into();
+    }
+
+    // `#[tdb(readonly)]` fields are left out of inserts, so must be optional
+    if let Err(err) = instance::validate_readonly(&input) {
+        return err.to_compile_error().into();
+    }
+
+    // `#[tdb(one_of)]` enums are not classes of their own, only `@oneOf` groups
+    if opts.one_of {
+        return enum_one_of::implement_for_one_of_enum(&input, &opts).into();
+    }
+
     // Generate implementation based on whether this is a struct or enum
     let expanded = match &input.data {
         Data::Struct(data_struct) => {
@@ -286,6 +310,21 @@ pub fn derive_terminusdb_schema(input: TokenStream) -> TokenStream {
         return err.to_compile_error().into();
     }
 
+    // `#[tdb(repr)]` only makes sense on simple enums
+    if let Err(err) = enum_simple::validate_repr(&input, &opts) {
+        return err.to_compile_error().into();
+    }
+
+    // `#[tdb(readonly)]` fields are left out of inserts, so must be optional
+    if let Err(err) = instance::validate_readonly(&input) {
+        return err.to_compile_error().into();
+    }
+
+    // `#[tdb(one_of)]` enums are not classes of their own, only `@oneOf` groups
+    if opts.one_of {
+        return enum_one_of::implement_for_one_of_enum(&input, &opts).into();
+    }
+
     // Generate ONLY the schema + serialize impls (the `#expanded` block). Unlike
     // `TerminusDBModel`, no deserialize / serde / filter / ordering code is emitted.
     let expanded = match &input.data {
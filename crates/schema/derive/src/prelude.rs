@@ -1,5 +1,6 @@
 pub use darling::FromDeriveInput;
 pub use darling::FromField;
+pub use darling::FromMeta;
 pub use proc_macro::TokenStream;
 pub use quote::{quote, ToTokens};
 pub use syn::spanned::Spanned;
@@ -72,3 +73,8 @@ pub fn is_primary_key_type(ty: &syn::Type) -> bool {
     }
     false
 }
+
+/// Check if a field is marked `#[tdb(one_of)]`
+pub fn is_one_of_field(field: &syn::Field) -> bool {
+    TDBFieldOpts::from_field(field).is_ok_and(|opts| opts.one_of)
+}
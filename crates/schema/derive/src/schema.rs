@@ -43,7 +43,14 @@ pub fn generate_totdbschema_impl(
                     quote! { terminusdb_schema::Key::Lexical(vec!["id".to_string()]) }
                 }
             }
-            _ => quote! { terminusdb_schema::Key::Random },
+            other => syn::Error::new(
+                struct_name.span(),
+                format!(
+                    "unknown key `{}`; expected \"random\", \"lexical\", \"hash\" or \"value_hash\"",
+                    other
+                ),
+            )
+            .to_compile_error(),
         }
     } else {
         quote! { terminusdb_schema::Key::Random }
@@ -312,6 +312,28 @@ pub fn implement_for_struct(
         _ => Vec::new(),
     };
 
+    // `#[tdb(one_of)]` fields become `@oneOf` groups instead of properties
+    let one_of_types = match &data_struct.fields {
+        Fields::Named(fields_named) => fields_named
+            .named
+            .iter()
+            .filter(|field| is_one_of_field(field))
+            .map(|field| &field.ty)
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    let one_of_impl = if one_of_types.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn one_of() -> Option<Vec<std::collections::BTreeSet<terminusdb_schema::Property>>> {
+                Some(vec![
+                    #(<#one_of_types as terminusdb_schema::ToOneOf>::one_of_group()),*
+                ])
+            }
+        }
+    };
+
     // Generate the to_schema_tree implementation that collects schemas from all field types
     let to_schema_tree_impl = if field_types.is_empty() {
         quote! {
@@ -350,8 +372,13 @@ pub fn implement_for_struct(
                     #(
                         <#field_types as terminusdb_schema::ToMaybeTDBSchema>::to_schema_tree_mut(collection);
                     )*
+                    #(
+                        <#one_of_types as terminusdb_schema::ToOneOf>::to_schema_tree_mut(collection);
+                    )*
                 }
             }
+
+            #one_of_impl
         }
     };
 
@@ -452,9 +479,32 @@ pub fn implement_for_struct(
         _ => quote! {},
     };
 
+    #[cfg(feature = "relations")]
+    let through_impls = {
+        let relations = opts
+            .has_many_through
+            .iter()
+            .map(|through| terminusdb_relation_derive::ThroughRelation {
+                name: through.name.clone(),
+                target: through.target.clone(),
+                via: through.via.clone(),
+            })
+            .collect::<Vec<_>>();
+        terminusdb_relation_derive::generate_through_impls(
+            struct_name,
+            &relations,
+            &impl_generics,
+            &ty_generics,
+            &base_where_clause.cloned(),
+        )
+    };
+
     #[cfg(not(feature = "relations"))]
     let relation_impls = quote! {};
 
+    #[cfg(not(feature = "relations"))]
+    let through_impls = quote! {};
+
     // Generate Class marker trait implementation
     let class_marker_impl = quote! {
         impl #impl_generics terminusdb_schema::Class for #struct_name #ty_generics #base_where_clause {}
@@ -471,6 +521,8 @@ pub fn implement_for_struct(
         #class_marker_impl
 
         #relation_impls
+
+        #through_impls
     }
 }
 
@@ -487,6 +539,8 @@ pub fn process_named_fields(
             // Skip PhantomData fields - they're zero-sized and shouldn't appear in schema
             !crate::prelude::is_phantom_data_type(&field.ty)
         })
+        // `#[tdb(one_of)]` fields are emitted as `@oneOf` groups, see `implement_for_struct`
+        .filter(|field| !is_one_of_field(field))
         .map(|field| {
             let field_name = field.ident.as_ref().unwrap();
             let field_ty = &field.ty;
@@ -181,3 +181,64 @@ fn test_multiword_enum_json_deserialization() {
     assert_eq!(instance.enum_value(), Some("fullyimported".to_string()));
 }
 // }
+
+/// Severity declares an integer repr with explicit discriminants; its declared
+/// order differs from the lexical order of its TDB values.
+#[derive(TerminusDBModel, Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+#[tdb(repr = "u8")]
+pub enum Severity {
+    Low = 1,
+    Medium = 5,
+    High = 10,
+    Critical,
+}
+
+#[test]
+fn test_enum_ordinal_follows_declared_order() {
+    assert_eq!(Severity::Low.ordinal(), 1);
+    assert_eq!(Severity::High.ordinal(), 10);
+    assert_eq!(Severity::Critical.ordinal(), 11);
+    assert_eq!(Severity::from_ordinal(5), Some(Severity::Medium));
+    assert_eq!(Severity::from_ordinal(2), None);
+
+    // Lexically "critical" < "high" < "low" < "medium"; declared order wins.
+    let mut sorted = Severity::variants();
+    sorted.sort_by(|a, b| a.cmp_ordinal(b));
+    assert_eq!(
+        sorted,
+        vec![
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical
+        ]
+    );
+
+    assert_eq!(
+        Severity::tdb_values_where(|o| o >= Severity::High.ordinal()),
+        vec!["high".to_string(), "critical".to_string()]
+    );
+
+    // Enums without explicit discriminants rank by position.
+    assert_eq!(ImportStatus::LocallyCreated.ordinal(), 0);
+    assert_eq!(ImportStatus::ImportFailed.ordinal(), 5);
+}
+
+#[test]
+fn test_enum_repr_conversions_round_trip() {
+    use std::convert::TryFrom;
+
+    for variant in Severity::variants() {
+        let raw: u8 = variant.into();
+        assert_eq!(Severity::try_from(raw).unwrap(), variant);
+    }
+    assert_eq!(u8::from(Severity::Critical), 11);
+    assert_eq!(
+        Severity::try_from(3u8),
+        Err(terminusdb_schema::InvalidDiscriminant {
+            enum_name: "Severity",
+            value: 3
+        })
+    );
+}
@@ -10,6 +10,7 @@ mod instance_test;
 mod integration_test;
 mod json_deserialize;
 mod lexical_key_test;
+mod model_diff_test;
 mod rename_test;
 mod special_types_test;
 mod struct_test;
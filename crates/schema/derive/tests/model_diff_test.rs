@@ -0,0 +1,60 @@
+use terminusdb_schema::{diff_models, ChangeKind};
+use terminusdb_schema_derive::{FromTDBInstance, TerminusDBModel};
+
+#[derive(TerminusDBModel, FromTDBInstance, Debug, Clone)]
+#[tdb(subdocument = true, key = "value_hash")]
+struct DiffAddress {
+    street: String,
+    city: String,
+}
+
+#[derive(TerminusDBModel, FromTDBInstance, Debug, Clone)]
+struct DiffPerson {
+    name: String,
+    age: i32,
+    nickname: Option<String>,
+    tags: Vec<String>,
+    address: DiffAddress,
+}
+
+fn person() -> DiffPerson {
+    DiffPerson {
+        name: "Ann".to_string(),
+        age: 40,
+        nickname: None,
+        tags: vec!["admin".to_string()],
+        address: DiffAddress {
+            street: "Oudegracht 1".to_string(),
+            city: "Utrecht".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_diff_unchanged_model() {
+    assert!(diff_models(&person(), &person()).is_empty());
+}
+
+#[test]
+fn test_diff_reports_nested_fields() {
+    let old = person();
+    let mut new = person();
+    new.age = 41;
+    new.nickname = Some("A".to_string());
+    new.tags.push("editor".to_string());
+    new.address.street = "Oudegracht 2".to_string();
+
+    let diff = diff_models(&old, &new);
+    assert_eq!(
+        diff.paths().collect::<Vec<_>>(),
+        vec!["address.street", "age", "nickname", "tags"]
+    );
+    assert_eq!(diff.get("age").unwrap().kind, ChangeKind::Changed);
+    assert_eq!(diff.get("nickname").unwrap().kind, ChangeKind::Added);
+    assert_eq!(
+        diff.get("address.street").unwrap().old,
+        Some(serde_json::json!("Oudegracht 1"))
+    );
+    assert!(diff.touches("address"));
+    assert!(!diff.touches("name"));
+}
@@ -0,0 +1,158 @@
+use serde_json::json;
+use terminusdb_schema::{
+    FromTDBInstance, Instance, InstanceProperty, Property, RelationValue, Schema, TdbLazy, ToJson,
+    ToTDBInstance, ToTDBSchema,
+};
+use terminusdb_schema_derive::TerminusDBModel;
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+struct Person {
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+struct Organization {
+    name: String,
+}
+
+/// A contract party is either a Person or an Organization
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+#[tdb(one_of)]
+enum Party {
+    Person(TdbLazy<Person>),
+    Organization(TdbLazy<Organization>),
+}
+
+#[derive(Debug, Clone, PartialEq, TerminusDBModel)]
+struct Contract {
+    title: String,
+    #[tdb(one_of)]
+    party: Party,
+}
+
+fn contract_with(party: Party) -> Contract {
+    Contract {
+        title: "Lease".to_string(),
+        party,
+    }
+}
+
+#[test]
+fn test_one_of_schema() {
+    let Schema::OneOfClass {
+        id,
+        classes,
+        properties,
+        ..
+    } = Contract::to_schema()
+    else {
+        panic!("expected a OneOfClass, got {:#?}", Contract::to_schema());
+    };
+
+    assert_eq!(id, "Contract");
+    assert_eq!(
+        properties
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["title"]
+    );
+    assert_eq!(
+        classes,
+        vec![[
+            Property {
+                name: "person".to_string(),
+                r#type: None,
+                class: "Person".to_string(),
+            },
+            Property {
+                name: "organization".to_string(),
+                r#type: None,
+                class: "Organization".to_string(),
+            },
+        ]
+        .into_iter()
+        .collect()]
+    );
+
+    assert_eq!(
+        Contract::to_schema().to_json()["@oneOf"],
+        json!([{ "person": "Person", "organization": "Organization" }])
+    );
+}
+
+#[test]
+fn test_one_of_schema_tree_includes_targets() {
+    Contract::assert_schema_tree_includes::<Person>();
+    Contract::assert_schema_tree_includes::<Organization>();
+}
+
+#[test]
+fn test_one_of_instance_uses_variant_property() {
+    let contract = contract_with(Party::Organization(
+        TdbLazy::new_id("Organization/acme").unwrap(),
+    ));
+    let instance = contract.to_instance(None);
+
+    assert!(instance.get_property("party").is_none());
+    assert!(instance.get_property("person").is_none());
+    assert_eq!(
+        instance.get_property("organization"),
+        Some(&InstanceProperty::Relation(
+            RelationValue::ExternalReference("Organization/acme".to_string())
+        ))
+    );
+
+    assert_eq!(Contract::from_instance(&instance).unwrap(), contract);
+}
+
+#[test]
+fn test_one_of_variant_follows_linked_type() {
+    let json = json!({
+        "@type": "Contract",
+        "title": "Lease",
+        "person": "Person/alice",
+    });
+    let contract = Contract::from_json(json).unwrap();
+
+    let Party::Person(person) = &contract.party else {
+        panic!("expected a Person party, got {:?}", contract.party);
+    };
+    assert_eq!(person.id().to_string(), "Person/alice");
+
+    // An unfolded document is matched on its @type
+    let json = json!({
+        "@type": "Contract",
+        "title": "Lease",
+        "organization": { "@id": "Organization/acme", "@type": "Organization", "name": "Acme" },
+    });
+    let contract = Contract::from_json(json).unwrap();
+    assert!(matches!(contract.party, Party::Organization(_)));
+}
+
+#[test]
+fn test_one_of_rejects_missing_or_ambiguous_links() {
+    let mut instance =
+        contract_with(Party::Person(TdbLazy::new_id("Person/alice").unwrap())).to_instance(None);
+
+    // Linked document of a type that is not a variant
+    instance.properties.insert(
+        "person".to_string(),
+        InstanceProperty::Relation(RelationValue::ExternalReference("Invoice/1".to_string())),
+    );
+    assert!(Contract::from_instance(&instance).is_err());
+
+    instance.properties.insert(
+        "organization".to_string(),
+        InstanceProperty::Relation(RelationValue::ExternalReference(
+            "Organization/acme".to_string(),
+        )),
+    );
+    assert!(Contract::from_instance(&instance).is_err());
+
+    let empty = Instance {
+        properties: Default::default(),
+        ..instance
+    };
+    assert!(Contract::from_instance(&empty).is_err());
+}
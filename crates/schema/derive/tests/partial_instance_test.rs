@@ -0,0 +1,74 @@
+use serde_json::json;
+use terminusdb_schema::ToTDBInstance;
+use terminusdb_schema_derive::TerminusDBModel;
+
+#[derive(TerminusDBModel, Debug, Clone)]
+#[tdb(id_field = "id")]
+struct Profile {
+    id: String,
+    name: String,
+    age: i32,
+    #[tdb(name = "emailAddress")]
+    email: String,
+}
+
+fn profile() -> Profile {
+    Profile {
+        id: "alice".to_string(),
+        name: "Alice".to_string(),
+        age: 31,
+        email: "alice@example.com".to_string(),
+    }
+}
+
+#[test]
+fn test_partial_instance_keeps_selected_fields() {
+    let partial = profile().to_instance_partial(&["age", "emailAddress", "missing"]);
+
+    assert!(partial.id_contains("alice"));
+    assert!(partial.has_property("age"));
+    assert!(partial.has_property("emailAddress"));
+    assert!(!partial.has_property("name"));
+    assert_eq!(partial.properties.len(), 2);
+}
+
+#[test]
+fn test_partial_instance_force_patch() {
+    let partial = profile().to_instance_partial(&["age"]);
+    let patch = partial.to_force_patch().unwrap();
+
+    assert_eq!(
+        patch,
+        json!({
+            "@id": partial.id().unwrap(),
+            "age": {"@op": "ForceValue", "@after": 31}
+        })
+    );
+}
+
+#[test]
+fn test_force_patch_requires_id() {
+    let mut partial = profile().to_instance_partial(&["age"]);
+    partial.id = None;
+
+    assert!(partial.to_force_patch().is_err());
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+#[tdb(key = "lexical", key_fields = "sku")]
+struct Product {
+    sku: String,
+    price: i32,
+}
+
+#[test]
+fn test_partial_instance_keeps_lexical_id() {
+    let product = Product {
+        sku: "p1".to_string(),
+        price: 40,
+    };
+    let partial = product.to_instance_partial(&["price"]);
+
+    assert_eq!(partial.id().map(String::as_str), Some("Product/p1"));
+    assert!(!partial.has_property("sku"));
+}
@@ -0,0 +1,62 @@
+use terminusdb_schema::{
+    FromTDBInstance, InstanceProperty, PrimitiveValue, Schema, ToTDBInstance, ToTDBSchema,
+};
+use terminusdb_schema_derive::TerminusDBModel;
+
+/// `revision` and `computed_at` are filled in by a server-side trigger.
+#[derive(TerminusDBModel, Debug, Clone, PartialEq)]
+struct Article {
+    title: String,
+    #[tdb(readonly)]
+    revision: Option<i32>,
+    #[tdb(readonly, name = "computedAt")]
+    computed_at: Option<String>,
+}
+
+fn article() -> Article {
+    Article {
+        title: "Hello".to_string(),
+        revision: Some(7),
+        computed_at: Some("2024-01-01T00:00:00Z".to_string()),
+    }
+}
+
+#[test]
+fn test_readonly_fields_stay_in_schema() {
+    let Schema::Class { properties, .. } = Article::to_schema() else {
+        panic!("Expected Schema::Class");
+    };
+    assert!(properties.iter().any(|p| p.name == "revision"));
+    assert!(properties.iter().any(|p| p.name == "computedAt"));
+}
+
+#[test]
+fn test_readonly_fields_omitted_from_instance() {
+    let instance = article().to_instance(None);
+
+    assert!(instance.has_property("title"));
+    assert!(!instance.has_property("revision"));
+    assert!(!instance.has_property("computedAt"));
+}
+
+#[test]
+fn test_readonly_fields_are_deserialized() {
+    // Simulate what the server returns: the written fields plus the computed ones.
+    let mut instance = article().to_instance(None);
+    instance.properties.insert(
+        "revision".to_string(),
+        InstanceProperty::Primitive(PrimitiveValue::Number(7.into())),
+    );
+    instance.properties.insert(
+        "computedAt".to_string(),
+        InstanceProperty::Primitive(PrimitiveValue::String("2024-01-01T00:00:00Z".to_string())),
+    );
+
+    let parsed = Article::from_instance(&instance).unwrap();
+    assert_eq!(parsed, article());
+
+    // Without the computed values the fields simply come back empty.
+    let parsed = Article::from_instance(&article().to_instance(None)).unwrap();
+    assert_eq!(parsed.revision, None);
+    assert_eq!(parsed.computed_at, None);
+}
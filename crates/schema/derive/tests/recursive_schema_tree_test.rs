@@ -0,0 +1,129 @@
+use terminusdb_schema::{Schema, ToTDBSchema, ToTDBSchemas};
+use terminusdb_schema_derive::TerminusDBModel;
+
+fn class_names(schemas: &[Schema]) -> Vec<String> {
+    let mut names: Vec<String> = schemas.iter().map(|s| s.class_name().to_string()).collect();
+    names.sort();
+    names
+}
+
+// Self-referencing tree through both `Vec<Self>` and `Box<Self>`.
+#[derive(TerminusDBModel, Debug, Clone)]
+struct TreeNode {
+    label: String,
+    children: Vec<TreeNode>,
+    parent: Option<Box<TreeNode>>,
+}
+
+// Mutual recursion: Author -> Book -> Author.
+#[derive(TerminusDBModel, Debug, Clone)]
+struct Author {
+    name: String,
+    books: Vec<Book>,
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+struct Book {
+    title: String,
+    author: Option<Box<Author>>,
+}
+
+// Deep chain behind a `Box`: every level must still be collected.
+#[derive(TerminusDBModel, Debug, Clone)]
+struct LevelOne {
+    next: Box<LevelTwo>,
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+struct LevelTwo {
+    next: Box<LevelThree>,
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+struct LevelThree {
+    back: Option<Box<LevelOne>>,
+    leaf: Leaf,
+}
+
+#[derive(TerminusDBModel, Debug, Clone)]
+struct Leaf {
+    value: i32,
+}
+
+// Tagged union whose struct variant refers back to the enum.
+#[derive(TerminusDBModel, Debug, Clone)]
+enum Outline {
+    Heading {
+        text: String,
+        sections: Vec<Outline>,
+    },
+    Paragraph(String),
+}
+
+#[test]
+fn test_self_referencing_tree() {
+    let schemas = TreeNode::to_schema_tree();
+    assert_eq!(class_names(&schemas), vec!["TreeNode"]);
+}
+
+#[test]
+fn test_mutually_recursive_models() {
+    assert_eq!(
+        class_names(&Author::to_schema_tree()),
+        vec!["Author", "Book"]
+    );
+    assert_eq!(class_names(&Book::to_schema_tree()), vec!["Author", "Book"]);
+}
+
+#[test]
+fn test_deep_cycle_through_box_collects_every_level() {
+    assert_eq!(
+        class_names(&LevelOne::to_schema_tree()),
+        vec!["Leaf", "LevelOne", "LevelThree", "LevelTwo"]
+    );
+}
+
+#[test]
+fn test_recursive_tagged_union() {
+    let names = class_names(&Outline::to_schema_tree());
+    assert!(names.contains(&"Outline".to_string()));
+    assert!(names.contains(&"OutlineHeading".to_string()));
+
+    let mut deduped = names.clone();
+    deduped.dedup();
+    assert_eq!(names, deduped);
+}
+
+#[test]
+fn test_tuple_schemas_are_deduplicated() {
+    assert_eq!(
+        class_names(&<(Author, Book, LevelOne)>::to_schemas()),
+        vec![
+            "Author",
+            "Book",
+            "Leaf",
+            "LevelOne",
+            "LevelThree",
+            "LevelTwo"
+        ]
+    );
+}
+
+#[test]
+fn test_tuple_schemas_keep_member_order() {
+    // Each member's own class first, then the classes it newly reaches by
+    // name; identical on every call.
+    let names = |schemas: Vec<Schema>| -> Vec<String> {
+        schemas.iter().map(|s| s.class_name().to_string()).collect()
+    };
+    let expected = vec![
+        "LevelOne",
+        "Leaf",
+        "LevelThree",
+        "LevelTwo",
+        "Book",
+        "Author",
+    ];
+    assert_eq!(names(<(LevelOne, Book, Author)>::to_schemas()), expected);
+    assert_eq!(names(<(LevelOne, Book, Author)>::to_schemas()), expected);
+}
@@ -293,3 +293,35 @@ fn test_tdblazy_transparent_serialization() {
     assert!(!deserialized_id.is_loaded());
     assert_eq!(deserialized_id.id().to_string(), "Activity/activity123");
 }
+
+#[test]
+fn test_tdblazy_fetched_cache_stays_a_reference() {
+    let lazy = TdbLazy::<Activity>::new_id("activity1").unwrap();
+    assert!(lazy.loaded().is_none());
+
+    // Fetched data is available through a shared reference...
+    let activity = Activity {
+        name: "Coding".to_string(),
+        description: "Writing code".to_string(),
+    };
+    assert_eq!(lazy.cache_fetched(activity).name, "Coding");
+    assert!(lazy.is_loaded());
+    assert_eq!(lazy.get_expect().description, "Writing code");
+
+    // ...is kept from the first fetch...
+    let other = Activity {
+        name: "Other".to_string(),
+        description: String::new(),
+    };
+    assert_eq!(lazy.cache_fetched(other).name, "Coding");
+
+    // ...and is not embedded when the link is serialized
+    assert_eq!(
+        serde_json::to_string(&lazy).unwrap(),
+        r#""Activity/activity1""#
+    );
+
+    let mut lazy = lazy;
+    lazy.make_ref();
+    assert!(!lazy.is_loaded());
+}
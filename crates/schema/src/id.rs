@@ -14,7 +14,7 @@ use rocket::form::{self, FromFormField, ValueField};
 use rocket::request::FromParam;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::convert::TryInto;
 use std::fmt;
 use std::fmt::Formatter;
@@ -96,6 +96,10 @@ impl<T: ToTDBSchema> ToTDBSchema for EntityIDFor<T> {
     fn values() -> Option<Vec<URI>> {
         T::values()
     }
+
+    fn one_of() -> Option<Vec<BTreeSet<Property>>> {
+        T::one_of()
+    }
 }
 
 impl<T: ToTDBSchema + ToSchemaClass> ToSchemaClass for EntityIDFor<T> {
@@ -767,6 +771,10 @@ impl<T: ToTDBSchema> ToTDBSchema for ServerIDFor<T> {
     fn values() -> Option<Vec<URI>> {
         EntityIDFor::<T>::values()
     }
+
+    fn one_of() -> Option<Vec<BTreeSet<Property>>> {
+        EntityIDFor::<T>::one_of()
+    }
 }
 
 // ToSchemaProperty implementation - treats it as an optional string property
@@ -0,0 +1,93 @@
+use crate::json::InstancePropertyFromJson;
+use crate::{
+    FromInstanceProperty, InstanceProperty, Primitive, PrimitiveValue, Schema, ToInstanceProperty,
+    ToMaybeTDBSchema, ToSchemaClass, STRING,
+};
+use serde_json::Value;
+use terminusdb_types::CommitId;
+
+// CommitId is stored as a plain string
+impl ToSchemaClass for CommitId {
+    fn to_class() -> String {
+        STRING.to_string()
+    }
+}
+
+// Mark CommitId as a primitive type
+impl Primitive for CommitId {}
+
+// Implement ToMaybeTDBSchema (default impl for primitives)
+impl ToMaybeTDBSchema for CommitId {}
+
+// Implement conversion to PrimitiveValue
+impl From<CommitId> for PrimitiveValue {
+    fn from(commit_id: CommitId) -> Self {
+        PrimitiveValue::String(commit_id.into_string())
+    }
+}
+
+// Implement ToInstanceProperty with generic parent type
+impl<Parent> ToInstanceProperty<Parent> for CommitId {
+    fn to_property(self, _field_name: &str, _parent: &Schema) -> InstanceProperty {
+        InstanceProperty::Primitive(self.into())
+    }
+}
+
+// Stored ids are taken as-is, like ids read from server responses
+impl FromInstanceProperty for CommitId {
+    fn from_property(prop: &InstanceProperty) -> anyhow::Result<Self> {
+        match prop {
+            InstanceProperty::Primitive(PrimitiveValue::String(s)) => {
+                Ok(CommitId::from(s.as_str()))
+            }
+            _ => anyhow::bail!("Expected string for CommitId, got {:?}", prop),
+        }
+    }
+}
+
+// Implement InstancePropertyFromJson for JSON deserialization
+impl<Parent> InstancePropertyFromJson<Parent> for CommitId {
+    fn property_from_json(json: Value) -> anyhow::Result<InstanceProperty> {
+        match json {
+            Value::String(s) => Ok(InstanceProperty::Primitive(PrimitiveValue::String(s))),
+            _ => anyhow::bail!("Expected string for CommitId, got {:?}", json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_id_conversions() {
+        let commit_id = CommitId::new("abc123").unwrap();
+
+        // Test conversion to PrimitiveValue
+        let primitive_value: PrimitiveValue = commit_id.clone().into();
+        assert!(matches!(primitive_value, PrimitiveValue::String(s) if s == "abc123"));
+
+        // Test ToInstanceProperty
+        let instance_prop = <CommitId as ToInstanceProperty<()>>::to_property(
+            commit_id,
+            "commit",
+            &Schema::empty_class("Test"),
+        );
+        assert!(
+            matches!(instance_prop, InstanceProperty::Primitive(PrimitiveValue::String(s)) if s == "abc123")
+        );
+
+        // Test FromInstanceProperty
+        let prop = InstanceProperty::Primitive(PrimitiveValue::String("def456".to_string()));
+        let restored = CommitId::from_property(&prop).unwrap();
+        assert_eq!(restored.as_str(), "def456");
+
+        // Test InstancePropertyFromJson
+        let json_val = Value::String("xyz789".to_string());
+        let prop_from_json =
+            <CommitId as InstancePropertyFromJson<()>>::property_from_json(json_val).unwrap();
+        assert!(
+            matches!(prop_from_json, InstanceProperty::Primitive(PrimitiveValue::String(s)) if s == "xyz789")
+        );
+    }
+}
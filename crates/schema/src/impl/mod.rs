@@ -2,6 +2,7 @@ pub mod map;
 
 // Special types implementations
 pub mod btreemap;
+pub mod commit_id;
 pub mod datetime;
 pub mod hashmap;
 pub mod hashmap_uuid;
@@ -34,16 +35,10 @@ macro_rules! impl_ref_tdb_schema {
             }
 
             fn to_schema_tree_mut(collection: &mut HashSet<crate::Schema>) {
-                let schema = <$ref as ToTDBSchema>::to_schema();
-                let class_name = schema.class_name().clone();
-
-                // Check if we already have a schema with this class name
-                if !collection.iter().any(|s: &Schema| s.class_name() == &class_name) {
-                    collection.insert(schema);
-
-                    // Process the inner type
-                    T::to_schema_tree_mut(collection);
-                }
+                // The wrapper shares T's class, so let T do the insertion; inserting
+                // it here first would make T think it was already visited and skip
+                // its own fields.
+                T::to_schema_tree_mut(collection);
             }
         }
     };
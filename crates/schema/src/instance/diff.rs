@@ -0,0 +1,293 @@
+use crate::{Instance, InstanceProperty, PrimitiveValue, RelationValue, ToTDBInstance};
+use serde_json::Value;
+use std::fmt;
+
+/// How a field differs between the two sides of a [`ModelDiff`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ChangeKind {
+    /// the field only has a value on the new side
+    Added,
+    /// the field only has a value on the old side
+    Removed,
+    /// the field has a different value on each side
+    Changed,
+}
+
+/// A single field that differs between two instances
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    /// dot-separated property path, e.g. `address.street` for a field of a
+    /// nested subdocument
+    pub path: String,
+    pub kind: ChangeKind,
+    /// JSON value on the old side, `None` when the field was added
+    pub old: Option<Value>,
+    /// JSON value on the new side, `None` when the field was removed
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<Value>| v.as_ref().map(Value::to_string).unwrap_or_default();
+        match self.kind {
+            ChangeKind::Added => write!(f, "+ {}: {}", self.path, show(&self.new)),
+            ChangeKind::Removed => write!(f, "- {}: {}", self.path, show(&self.old)),
+            ChangeKind::Changed => write!(
+                f,
+                "~ {}: {} -> {}",
+                self.path,
+                show(&self.old),
+                show(&self.new)
+            ),
+        }
+    }
+}
+
+/// Field-level change set between two instances of the same model, as
+/// produced by [`diff_models`]. Changes are ordered by path.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelDiff {
+    changes: Vec<FieldChange>,
+}
+
+impl ModelDiff {
+    /// whether both sides have the same field values
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn changes(&self) -> &[FieldChange] {
+        &self.changes
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FieldChange> {
+        self.changes.iter()
+    }
+
+    /// the change for the given dot-separated path, if that field differs
+    pub fn get(&self, path: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.path == path)
+    }
+
+    /// whether the given field, or any field nested below it, differs
+    pub fn touches(&self, path: &str) -> bool {
+        self.changes.iter().any(|change| {
+            change.path == path
+                || change
+                    .path
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    /// paths of all changed fields
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.changes.iter().map(|change| change.path.as_str())
+    }
+}
+
+impl IntoIterator for ModelDiff {
+    type Item = FieldChange;
+    type IntoIter = std::vec::IntoIter<FieldChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ModelDiff {
+    type Item = &'a FieldChange;
+    type IntoIter = std::slice::Iter<'a, FieldChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.iter()
+    }
+}
+
+/// one change per line, `+` added, `-` removed, `~` changed
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two instances of the same model field by field.
+///
+/// Nested documents and subdocuments are compared recursively, so a change to
+/// `person.address.street` is reported at that path rather than as a change of
+/// the whole `address`. Lists and references are compared as a whole. Instance
+/// ids are not compared: only the field values are.
+pub fn diff_models<T: ToTDBInstance>(old: &T, new: &T) -> ModelDiff {
+    diff_instances(&old.to_instance(None), &new.to_instance(None))
+}
+
+/// Compare two instances field by field; see [`diff_models`]
+pub fn diff_instances(old: &Instance, new: &Instance) -> ModelDiff {
+    let mut changes = Vec::new();
+    diff_properties(old, new, "", &mut changes);
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    ModelDiff { changes }
+}
+
+fn diff_properties(old: &Instance, new: &Instance, prefix: &str, changes: &mut Vec<FieldChange>) {
+    let keys = old.properties.keys().chain(
+        new.properties
+            .keys()
+            .filter(|key| !old.properties.contains_key(*key)),
+    );
+
+    for key in keys {
+        let path = format!("{}{}", prefix, key);
+        match (present(old, key), present(new, key)) {
+            (None, None) => {}
+            (Some(old_prop), None) => changes.push(FieldChange {
+                path,
+                kind: ChangeKind::Removed,
+                old: Some(old_prop.clone().into()),
+                new: None,
+            }),
+            (None, Some(new_prop)) => changes.push(FieldChange {
+                path,
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(new_prop.clone().into()),
+            }),
+            (Some(old_prop), Some(new_prop)) if old_prop == new_prop => {}
+            (Some(old_prop), Some(new_prop)) => match (nested(old_prop), nested(new_prop)) {
+                (Some(old_inner), Some(new_inner))
+                    if old_inner.schema.class_name() == new_inner.schema.class_name() =>
+                {
+                    diff_properties(old_inner, new_inner, &format!("{}.", path), changes)
+                }
+                _ => changes.push(FieldChange {
+                    path,
+                    kind: ChangeKind::Changed,
+                    old: Some(old_prop.clone().into()),
+                    new: Some(new_prop.clone().into()),
+                }),
+            },
+        }
+    }
+}
+
+/// A property with a value; an explicit null (a `None` option) counts as absent
+fn present<'a>(instance: &'a Instance, key: &str) -> Option<&'a InstanceProperty> {
+    instance
+        .properties
+        .get(key)
+        .filter(|prop| !matches!(prop, InstanceProperty::Primitive(PrimitiveValue::Null)))
+}
+
+/// The embedded instance of a property, if it is one that has fields to compare
+fn nested(prop: &InstanceProperty) -> Option<&Instance> {
+    match prop {
+        InstanceProperty::Relation(RelationValue::One(instance))
+            if !instance.is_enum() && !instance.is_reference() =>
+        {
+            Some(instance)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Key, Property, Schema};
+    use std::collections::BTreeMap;
+
+    fn instance(class: &str, props: Vec<(&str, InstanceProperty)>) -> Instance {
+        Instance {
+            schema: Schema::Class {
+                id: class.to_string(),
+                base: None,
+                properties: Vec::<Property>::new(),
+                subdocument: false,
+                key: Key::Random,
+                unfoldable: false,
+                documentation: None,
+                r#abstract: false,
+                inherits: vec![],
+            },
+            id: None,
+            capture: false,
+            ref_props: false,
+            properties: props
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    fn string(s: &str) -> InstanceProperty {
+        InstanceProperty::Primitive(PrimitiveValue::String(s.to_string()))
+    }
+
+    fn address(street: &str) -> InstanceProperty {
+        InstanceProperty::Relation(RelationValue::One(instance(
+            "Address",
+            vec![("street", string(street)), ("city", string("Utrecht"))],
+        )))
+    }
+
+    #[test]
+    fn test_identical_instances() {
+        let a = instance(
+            "Person",
+            vec![("name", string("Ann")), ("address", address("A"))],
+        );
+        assert!(diff_instances(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_nested_and_optional_fields() {
+        let old = instance(
+            "Person",
+            vec![
+                ("name", string("Ann")),
+                (
+                    "nickname",
+                    InstanceProperty::Primitive(PrimitiveValue::Null),
+                ),
+                ("address", address("Oudegracht 1")),
+            ],
+        );
+        let new = instance(
+            "Person",
+            vec![
+                ("name", string("Anne")),
+                ("nickname", string("A")),
+                ("address", address("Oudegracht 2")),
+            ],
+        );
+
+        let diff = diff_instances(&old, &new);
+        assert_eq!(
+            diff.paths().collect::<Vec<_>>(),
+            vec!["address.street", "name", "nickname"]
+        );
+        assert_eq!(diff.get("nickname").unwrap().kind, ChangeKind::Added);
+        assert_eq!(
+            diff.get("address.street").unwrap().new,
+            Some(Value::from("Oudegracht 2"))
+        );
+        assert!(diff.touches("address"));
+        assert!(!diff.touches("addr"));
+        assert_eq!(
+            diff.to_string(),
+            "~ address.street: \"Oudegracht 1\" -> \"Oudegracht 2\"\n\
+             ~ name: \"Ann\" -> \"Anne\"\n\
+             + nickname: \"A\"\n"
+        );
+
+        let reversed = diff_instances(&new, &old);
+        assert_eq!(reversed.get("nickname").unwrap().kind, ChangeKind::Removed);
+    }
+}
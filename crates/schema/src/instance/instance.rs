@@ -1,5 +1,6 @@
 use crate::{
-    deserialize_property, json::InstanceFromJson, InstanceProperty, Key, RelationValue, ToTDBSchema,
+    deserialize_property, json::InstanceFromJson, InstanceProperty, Key, PrimitiveValue,
+    RelationValue, ToTDBSchema,
 };
 use crate::{json::ToJson, Schema};
 use serde::{Deserialize, Serialize};
@@ -159,6 +160,21 @@ pub trait ToTDBInstance: ToTDBSchema + ToTDBInstances {
             .expect("Failed to serialize instance to JSON string")
     }
 
+    /// Instance fragment holding only the listed properties (TDB property names,
+    /// i.e. after any `#[tdb(name = ...)]` rename) plus the
+    /// [known id](Instance::known_id), for partial updates. Unknown names are
+    /// ignored. Render it with [`Instance::to_force_patch`] to update just
+    /// those fields.
+    fn to_instance_partial(&self, fields: &[&str]) -> Instance {
+        let mut instance = self.to_instance(None);
+        // Set before the key fields a lexical id is built from are dropped.
+        instance.id = instance.known_id();
+        instance
+            .properties
+            .retain(|name, _| fields.contains(&name.as_str()));
+        instance
+    }
+
     // fn from_value(
     //     instance: serde_json::Value,
     // ) -> anyhow::Result<<<Self as ToRelational>::Relational as Relational<Model = Self>> + Cacheable,
@@ -355,6 +371,31 @@ impl Instance {
         }
     }
 
+    /// The id this instance is stored under when it is known before saving:
+    /// its explicit id, or its [lexical id](Self::lexical_id). Unlike
+    /// [`gen_id`](Self::gen_id), this never makes up a random id.
+    pub fn known_id(&self) -> Option<String> {
+        self.id.clone().or_else(|| self.lexical_id())
+    }
+
+    /// The id the server derives for a lexical key: the class name, then the
+    /// url-encoded key field values joined by `+` (`Person/Ada`). `None` for
+    /// other keys, or when a key field is missing or not a plain value.
+    pub fn lexical_id(&self) -> Option<String> {
+        let fields = match self.schema.key() {
+            Some(Key::Lexical(fields)) if !fields.is_empty() => fields,
+            _ => return None,
+        };
+        let values = fields
+            .iter()
+            .map(|field| match self.get_property(field)? {
+                InstanceProperty::Primitive(value) => lexical_value(value),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("{}/{}", self.schema.class_name(), values.join("+")))
+    }
+
     pub fn has_id(&self) -> bool {
         self.id.is_some()
     }
@@ -402,6 +443,30 @@ impl Instance {
         removed
     }
 
+    /// Render as a TerminusDB JSON patch that sets every property present on
+    /// this instance regardless of its current value
+    /// (`{"@id": .., "name": {"@op": "ForceValue", "@after": ..}}`), leaving all
+    /// other properties of the stored document untouched. Requires an id.
+    pub fn to_force_patch(&self) -> anyhow::Result<serde_json::Value> {
+        let id = self.id().ok_or_else(|| {
+            anyhow::anyhow!(
+                "cannot build a patch for a {} instance without an id",
+                self.schema.class_name()
+            )
+        })?;
+
+        let mut map = Map::new();
+        map.insert("@id".to_string(), id.clone().into());
+        for (propkey, propval) in &self.properties {
+            let after: Value = propval.clone().into();
+            map.insert(
+                propkey.clone(),
+                serde_json::json!({ "@op": "ForceValue", "@after": after }),
+            );
+        }
+        Ok(Value::Object(map))
+    }
+
     pub fn to_json_tree(&self) -> Vec<serde_json::Value> {
         // self.to_instance_tree_flatten().into_iter().map(|i| i.to_json()).collect()
         self.to_instance_tree()
@@ -507,6 +572,21 @@ impl Instance {
     }
 }
 
+/// A key field value as it appears in a lexical id
+fn lexical_value(value: &PrimitiveValue) -> Option<String> {
+    match value {
+        PrimitiveValue::String(s) => Some(urlencoding::encode(s).into_owned()),
+        PrimitiveValue::Number(n) => Some(n.to_string()),
+        PrimitiveValue::Bool(b) => Some(b.to_string()),
+        // Typed literals such as decimals and dates
+        PrimitiveValue::Object(Value::Object(literal)) => match literal.get("@value")? {
+            Value::String(s) => Some(urlencoding::encode(s).into_owned()),
+            other => Some(other.to_string()),
+        },
+        _ => None,
+    }
+}
+
 impl Serialize for Instance {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -1,10 +1,11 @@
+mod diff;
 mod instance;
 pub mod prop;
 pub mod validation;
 mod value_primitive;
 mod value_rel;
 
-pub use {instance::*, prop::*, validation::*, value_primitive::*, value_rel::*};
+pub use {diff::*, instance::*, prop::*, validation::*, value_primitive::*, value_rel::*};
 
 #[cfg(test)]
 mod tests;
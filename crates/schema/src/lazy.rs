@@ -9,8 +9,9 @@ use anyhow::{anyhow, bail};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::convert::TryInto;
+use std::sync::OnceLock;
 
 /// A **link** (object property / graph edge) to another TerminusDB document of
 /// type `T`, loaded lazily.
@@ -25,10 +26,17 @@ use std::convert::TryInto;
 /// `xsd:string` value and is not an edge. Construct a link from an id via
 /// `TdbLazy::from(EntityIDFor::<T>::new(id)?)`, or from a full instance via
 /// `TdbLazy::from(model)`.
+///
+/// A reference can be resolved later through a `&self`: data fetched from the
+/// database is kept in an internal cache, filled at most once (see
+/// [`cache_fetched`](Self::cache_fetched)). Unlike data the link was created
+/// with, cached data is not embedded when the link is serialized or saved, as
+/// the document already exists.
 #[derive(Debug, Clone)]
 pub struct TdbLazy<T: TerminusDBModel> {
     id: Option<EntityIDFor<T>>,
     data: Option<Box<T>>,
+    fetched: OnceLock<Box<T>>,
 }
 
 /// A traversable reference (graph edge / object property) to another TerminusDB
@@ -64,6 +72,7 @@ impl<T: TerminusDBModel> TdbLazy<T> {
         Self {
             id,
             data: data.map(Box::new),
+            fetched: OnceLock::new(),
         }
     }
 
@@ -71,6 +80,7 @@ impl<T: TerminusDBModel> TdbLazy<T> {
         Ok(Self {
             id: Some(EntityIDFor::new_untyped(&id)?),
             data: None,
+            fetched: OnceLock::new(),
         })
     }
 
@@ -78,6 +88,7 @@ impl<T: TerminusDBModel> TdbLazy<T> {
         Self {
             id: Some(EntityIDFor::new_untyped(&id).unwrap()),
             data: None,
+            fetched: OnceLock::new(),
         }
     }
 
@@ -111,7 +122,26 @@ impl<T: TerminusDBModel> TdbLazy<T> {
     }
 
     pub fn is_loaded(&self) -> bool {
-        self.data.is_some()
+        self.loaded().is_some()
+    }
+
+    /// The data, if the link was created with it or it has been fetched.
+    pub fn loaded(&self) -> Option<&T> {
+        self.data
+            .as_deref()
+            .or_else(|| self.fetched.get().map(|data| data.as_ref()))
+    }
+
+    /// Keep data fetched for this reference and return the cached value.
+    ///
+    /// The cache is filled once: if it already holds data, `data` is dropped
+    /// and the cached value returned. Used by loaders that resolve the
+    /// reference through a client, such as the ORM's `TdbLazyExt::load`.
+    pub fn cache_fetched(&self, data: T) -> &T {
+        if let Some(data) = &self.data {
+            return data;
+        }
+        self.fetched.get_or_init(|| Box::new(data))
     }
 
     /// Get a reference to the inner data, panicking if not loaded.
@@ -119,10 +149,7 @@ impl<T: TerminusDBModel> TdbLazy<T> {
     /// # Panics
     /// Panics if the data has not been loaded yet.
     pub fn get_expect(&self) -> &T {
-        self.data
-            .as_ref()
-            .expect("TdbLazy data not loaded")
-            .as_ref()
+        self.loaded().expect("TdbLazy data not loaded")
     }
 
     /// Take ownership of the inner data, panicking if not loaded.
@@ -130,7 +157,10 @@ impl<T: TerminusDBModel> TdbLazy<T> {
     /// # Panics
     /// Panics if the data has not been loaded yet.
     pub fn take_expect(self) -> T {
-        *self.data.expect("TdbLazy data not loaded")
+        match self.data {
+            Some(data) => *data,
+            None => *self.fetched.into_inner().expect("TdbLazy data not loaded"),
+        }
     }
 
     /// Convert this TdbLazy to a reference-only variant, discarding loaded data.
@@ -154,6 +184,7 @@ impl<T: TerminusDBModel> TdbLazy<T> {
             }
             self.data = None;
         }
+        self.fetched = OnceLock::new();
     }
 }
 
@@ -174,9 +205,9 @@ impl<T: TerminusDBModel + Serialize> Serialize for TdbLazy<T> {
     where
         S: Serializer,
     {
-        if self.is_loaded() {
+        if let Some(data) = &self.data {
             // When data is loaded, serialize it transparently
-            self.data.as_ref().unwrap().serialize(serializer)
+            data.serialize(serializer)
         } else {
             // When only ID is present, serialize the ID
             match &self.id {
@@ -242,6 +273,10 @@ impl<T: TerminusDBModel> ToTDBSchema for TdbLazy<T> {
     fn values() -> Option<Vec<URI>> {
         T::values()
     }
+
+    fn one_of() -> Option<Vec<BTreeSet<Property>>> {
+        T::one_of()
+    }
 }
 
 // impl<Parent, T: ToSchemaProperty<Parent>+TerminusDBModel> ToSchemaProperty<Parent> for TdbLazy<T> {
@@ -282,12 +317,10 @@ impl<T: TerminusDBModel> ToTDBSchema for TdbLazy<T> {
 // so that we dont needlessly nest Instances?
 impl<Parent, T: TerminusDBModel> ToInstanceProperty<Parent> for TdbLazy<T> {
     fn to_property(self, _field_name: &str, _parent: &Schema) -> InstanceProperty {
-        if self.is_loaded() {
+        if let Some(data) = &self.data {
             // When loaded, pass the ID if available (it might be None for lexical keys)
             let id = self.id.as_ref().map(|id| id.to_string());
-            InstanceProperty::Relation(RelationValue::One(
-                self.data.as_ref().unwrap().to_instance(id),
-            ))
+            InstanceProperty::Relation(RelationValue::One(data.to_instance(id)))
         } else {
             // When not loaded, we need an ID to reference
             match self.id.as_ref() {
@@ -381,8 +414,8 @@ impl<T: TerminusDBModel> ToTDBInstances for TdbLazy<T> {
 
 impl<T: TerminusDBModel> ToTDBInstance for TdbLazy<T> {
     fn to_instance(&self, id: Option<String>) -> Instance {
-        if self.is_loaded() {
-            self.data.as_ref().unwrap().to_instance(id)
+        if let Some(data) = &self.data {
+            data.to_instance(id)
         } else {
             // When not loaded, we need an ID to create a reference
             match self.id.as_ref() {
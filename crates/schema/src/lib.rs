@@ -1,7 +1,7 @@
+// Nightly features; docs/nightly-features.md lists where each one is needed
 #![feature(specialization)]
 #![feature(associated_type_defaults)]
 #![feature(negative_impls, with_negative_coherence)]
-#![feature(trait_alias)]
 // Minimal targeted lint allow-set: `dead_code` is high-volume noise across the
 // internal API surface and stays allowed; `incomplete_features` is unavoidable
 // while we depend on `#![feature(specialization)]`. Everything else — notably
@@ -30,6 +30,7 @@ pub use json::{InstanceFromJson, ToJson};
 pub use lazy::*;
 pub use marker::*;
 pub use model::*;
+pub use one_of::*;
 pub use pred::*;
 pub use r#impl::map::HashMapStringEntry;
 pub use ty::*;
@@ -55,6 +56,7 @@ mod iri;
 mod lazy;
 mod marker;
 mod model;
+mod one_of;
 mod pred;
 mod primitive;
 mod schema;
@@ -232,6 +234,12 @@ pub trait ToTDBSchemas {
 ///     Some(ImportStatus::FullyImported)
 /// );
 /// ```
+///
+/// Variants also carry their declared order ([`TDBEnum::ordinal`]). Add
+/// `#[tdb(repr = "u8")]` (any integer type, matching the enum's `#[repr]`) to
+/// get `From`/`TryFrom` conversions between the enum and its discriminant. TerminusDB's GraphQL
+/// `_Ordering` inputs have no enum fields, so results fetched over GraphQL
+/// are sorted by declared order client-side, with [`TDBEnum::cmp_ordinal`].
 pub trait TDBEnum: Sized {
     /// Returns all variants of this enum.
     fn variants() -> Vec<Self>;
@@ -243,8 +251,70 @@ pub trait TDBEnum: Sized {
     /// Try to construct enum from TDB string representation.
     /// (e.g., `"fullyimported"` -> `Some(FullyImported)`)
     fn from_tdb_value(s: &str) -> Option<Self>;
+
+    /// Declared-order rank of this variant. The derive returns the variant's
+    /// discriminant (`Variant = 10` is honoured); the default falls back to the
+    /// position in [`TDBEnum::variants`].
+    fn ordinal(&self) -> i64 {
+        let value = self.to_tdb_value();
+        Self::variants()
+            .iter()
+            .position(|v| v.to_tdb_value() == value)
+            .map(|pos| pos as i64)
+            .unwrap_or(i64::MAX)
+    }
+
+    /// Inverse of [`TDBEnum::ordinal`].
+    fn from_ordinal(ordinal: i64) -> Option<Self> {
+        Self::variants().into_iter().find(|v| v.ordinal() == ordinal)
+    }
+
+    /// Compare two variants by declared order rather than by their (lexically
+    /// sorted) TDB string values.
+    fn cmp_ordinal(&self, other: &Self) -> std::cmp::Ordering {
+        self.ordinal().cmp(&other.ordinal())
+    }
+
+    /// TDB values of every variant whose ordinal satisfies `pred`, in declared
+    /// order. TerminusDB compares enum values lexically, so a range comparison
+    /// such as `status >= Pending` has to be expressed as membership in this
+    /// list; the ORM's `FieldFilter::enum_gte` (and `enum_gt`/`enum_lt`/
+    /// `enum_lte`) compile it to a WOQL `Or` of equalities.
+    ///
+    /// ```rust,ignore
+    /// let open = Status::tdb_values_where(|o| o >= Status::Pending.ordinal());
+    /// ```
+    fn tdb_values_where(pred: impl Fn(i64) -> bool) -> Vec<String> {
+        let mut variants = Self::variants();
+        variants.sort_by_key(|v| v.ordinal());
+        variants
+            .iter()
+            .filter(|v| pred(v.ordinal()))
+            .map(|v| v.to_tdb_value())
+            .collect()
+    }
+}
+
+/// Error of the `TryFrom` conversion `#[tdb(repr = "...")]` derives: `value`
+/// is not the discriminant of any variant of `enum_name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDiscriminant {
+    pub enum_name: &'static str,
+    pub value: i128,
+}
+
+impl std::fmt::Display for InvalidDiscriminant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is not a valid discriminant for enum {}",
+            self.value, self.enum_name
+        )
+    }
 }
 
+impl std::error::Error for InvalidDiscriminant {}
+
 // Blanket implementation: any ToTDBSchema automatically implements ToTDBSchemas
 impl<T: ToTDBSchema> ToTDBSchemas for T {
     fn to_schemas() -> Vec<crate::Schema> {
@@ -252,29 +322,48 @@ impl<T: ToTDBSchema> ToTDBSchemas for T {
     }
 }
 
+/// Append the classes in `collection` that are not in `schemas` yet: `root`
+/// first, then the rest by class name, so the output order is deterministic.
+fn append_new_schemas(
+    schemas: &mut Vec<crate::Schema>,
+    seen: &mut std::collections::HashSet<String>,
+    collection: &std::collections::HashSet<crate::Schema>,
+    root: &str,
+) {
+    let mut new: Vec<&crate::Schema> = collection
+        .iter()
+        .filter(|schema| !seen.contains(schema.class_name()))
+        .collect();
+    new.sort_by_key(|schema| (schema.class_name() != root, schema.class_name().clone()));
+    for schema in new {
+        seen.insert(schema.class_name().clone());
+        schemas.push(schema.clone());
+    }
+}
+
 // Macro to generate ToTDBSchemas implementations for tuples
 macro_rules! impl_to_tdb_schemas_for_tuple {
-    // Base case for single element tuple
-    ($T:ident) => {
-        impl<$T> ToTDBSchemas for ($T,)
-        where
-            $T: ToTDBSchema,
-        {
-            fn to_schemas() -> Vec<crate::Schema> {
-                $T::to_schema_tree()
-            }
-        }
-    };
-
-    // Recursive case for multiple elements
     ($($T:ident),+) => {
         impl<$($T),+> ToTDBSchemas for ($($T,)+)
         where
             $($T: ToTDBSchema,)+
         {
             fn to_schemas() -> Vec<crate::Schema> {
+                // One shared collection, so classes reachable from several members
+                // are emitted once. It is unordered, so the output is built in
+                // member order alongside it.
+                let mut collection = std::collections::HashSet::new();
+                let mut seen = std::collections::HashSet::new();
                 let mut schemas = Vec::new();
-                $(schemas.extend($T::to_schema_tree());)+
+                $(
+                    $T::to_schema_tree_mut(&mut collection);
+                    append_new_schemas(
+                        &mut schemas,
+                        &mut seen,
+                        &collection,
+                        $T::to_schema().class_name(),
+                    );
+                )+
                 schemas
             }
         }
@@ -6,9 +6,13 @@ use crate::{
 /// marker trait
 pub trait Primitive: Into<PrimitiveValue> {}
 
-/// Trait alias that combines all the required bounds for generic field types in TerminusDB models
+/// Combines all the required bounds for generic field types in TerminusDB models
 /// This ensures that generic parameters can work with both primitive types and model types
-pub trait TerminusDBField<Parent> = std::fmt::Debug
+///
+/// Implemented for every type that meets the bounds. It is a plain trait with a
+/// blanket impl rather than a trait alias, so it doesn't need `trait_alias`.
+pub trait TerminusDBField<Parent>:
+    std::fmt::Debug
     + Clone
     + Send
     + serde::Serialize
@@ -17,7 +21,23 @@ pub trait TerminusDBField<Parent> = std::fmt::Debug
     + ToInstanceProperty<Parent>
     + FromInstanceProperty
     + ToMaybeTDBSchema
-    + crate::json::InstancePropertyFromJson<Parent>;
+    + crate::json::InstancePropertyFromJson<Parent>
+{
+}
+
+impl<Parent, T> TerminusDBField<Parent> for T where
+    T: std::fmt::Debug
+        + Clone
+        + Send
+        + serde::Serialize
+        + serde::de::DeserializeOwned
+        + ToSchemaProperty<Parent>
+        + ToInstanceProperty<Parent>
+        + FromInstanceProperty
+        + ToMaybeTDBSchema
+        + crate::json::InstancePropertyFromJson<Parent>
+{
+}
 
 // impl<T: PrimitiveMarker> PrimitiveMarker for Box<T> {}
 
@@ -11,6 +11,15 @@ pub trait TerminusDBModel:
             Some(id) => EntityIDFor::new_untyped(&id).unwrap().into(),
         }
     }
+
+    /// The id this model is stored under when it is known without asking the
+    /// server: an explicit id field or a lexical key. Unlike
+    /// [`instance_id`](Self::instance_id), `None` for random-key models
+    /// without an id instead of a new random id on every call.
+    fn known_id(&self) -> Option<EntityIDFor<Self>> {
+        let id = self.to_instance(None).known_id()?;
+        EntityIDFor::new_untyped(&id).ok()
+    }
 }
 
 impl<T> TerminusDBModel for T where
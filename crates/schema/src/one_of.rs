@@ -0,0 +1,133 @@
+//! Fields that link to one of several document classes.
+//!
+//! A field such as "links to either a Person or an Organization" is modelled as
+//! an enum of document links. Instead of becoming a `TaggedUnion` subdocument,
+//! its variants are stored directly on the holding class as an `@oneOf` group:
+//!
+//! ```rust,ignore
+//! #[derive(TerminusDBModel, Clone, Debug)]
+//! #[tdb(one_of)]
+//! enum Party {
+//!     Person(TdbLazy<Person>),
+//!     Organization(TdbLazy<Organization>),
+//! }
+//!
+//! #[derive(TerminusDBModel, Clone, Debug)]
+//! struct Contract {
+//!     title: String,
+//!     #[tdb(one_of)]
+//!     party: Party,
+//! }
+//! ```
+//!
+//! `Contract` then gets `"@oneOf": [{"person": "Person", "organization":
+//! "Organization"}]`, and a stored contract holds exactly one of `person` or
+//! `organization`. On the way back the variant is chosen by the type of the
+//! linked document.
+
+use crate::{Instance, InstanceProperty, PrimitiveValue, Property, RelationValue, Schema, TdbIRI};
+use anyhow::{anyhow, bail, Context};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// An enum of document links stored as an `@oneOf` group on the class holding
+/// it. Derived by `TerminusDBModel` on enums marked `#[tdb(one_of)]`.
+pub trait ToOneOf: Sized {
+    /// `(property, class)` for every variant, in declaration order
+    fn choices() -> Vec<(&'static str, String)>;
+
+    /// The property this value is stored under, and the link stored there
+    fn to_choice(self, parent: &Schema) -> (&'static str, InstanceProperty);
+
+    /// Rebuild the variant linking to a document of type `class`
+    fn from_choice(class: &str, prop: &InstanceProperty) -> anyhow::Result<Self>;
+
+    /// Parse the JSON stored under the property `choice`
+    fn choice_from_json(choice: &str, json: Value) -> anyhow::Result<InstanceProperty>;
+
+    /// Add the schemas of all linkable classes to `collection`
+    fn to_schema_tree_mut(collection: &mut HashSet<Schema>);
+
+    /// The `@oneOf` group to add to the holding class
+    fn one_of_group() -> BTreeSet<Property> {
+        Self::choices()
+            .into_iter()
+            .map(|(name, class)| Property {
+                name: name.to_string(),
+                r#type: None,
+                class,
+            })
+            .collect()
+    }
+
+    /// Read the value back from the holding instance
+    fn from_instance_properties(instance: &Instance) -> anyhow::Result<Self> {
+        let choices = Self::choices();
+        let mut set = choices
+            .iter()
+            .filter_map(|(name, _)| instance.get_property(name).map(|prop| (*name, prop)));
+
+        let (name, prop) = set.next().ok_or_else(|| {
+            anyhow!(
+                "none of the @oneOf properties [{}] is set",
+                choices
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+        if let Some((other, _)) = set.next() {
+            bail!("@oneOf properties '{}' and '{}' are both set", name, other);
+        }
+
+        let class = linked_class(prop)
+            .ok_or_else(|| anyhow!("cannot tell the document type linked by '{}'", name))?;
+        Self::from_choice(&class, prop)
+            .with_context(|| format!("failed to read @oneOf property '{}'", name))
+    }
+}
+
+/// The class of the document a link points to: the `@type` of an unfolded
+/// document, or the type segment of a document id
+pub fn linked_class(prop: &InstanceProperty) -> Option<String> {
+    match prop {
+        InstanceProperty::Relation(RelationValue::One(instance)) => {
+            Some(instance.schema.class_name().clone())
+        }
+        InstanceProperty::Relation(
+            RelationValue::ExternalReference(id) | RelationValue::TransactionRef(id),
+        )
+        | InstanceProperty::Primitive(PrimitiveValue::String(id)) => TdbIRI::parse(id)
+            .ok()
+            .map(|iri| iri.type_name().to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_class_from_reference() {
+        let reference =
+            |id: &str| InstanceProperty::Relation(RelationValue::ExternalReference(id.to_string()));
+
+        assert_eq!(
+            linked_class(&reference("Person/alice")).as_deref(),
+            Some("Person")
+        );
+        assert_eq!(
+            linked_class(&reference("terminusdb:///data/Organization/acme")).as_deref(),
+            Some("Organization")
+        );
+        assert_eq!(linked_class(&reference("alice")), None);
+        assert_eq!(
+            linked_class(&InstanceProperty::Primitive(PrimitiveValue::Number(
+                1.into()
+            ))),
+            None
+        );
+    }
+}
@@ -484,7 +484,7 @@ impl Schema {
     pub fn is_abstract(&self) -> bool {
         match self {
             Schema::Class { r#abstract, .. } => *r#abstract,
-            Schema::OneOfClass { .. } => false,
+            Schema::OneOfClass { r#abstract, .. } => *r#abstract,
             Schema::Enum { .. } => false,
             Schema::TaggedUnion { r#abstract, .. } => *r#abstract,
         }
@@ -504,6 +504,7 @@ impl Schema {
     pub fn is_subdocument(&self) -> bool {
         match self {
             Schema::Class { subdocument, .. } => *subdocument,
+            Schema::OneOfClass { subdocument, .. } => *subdocument,
             Schema::TaggedUnion { subdocument, .. } => *subdocument,
             _ => false,
         }
@@ -512,7 +513,8 @@ impl Schema {
     pub fn is_key_random(&self) -> bool {
         match self {
             Schema::Class { key, .. } => key == &Key::Random,
-            Schema::OneOfClass { .. } => false,
+            // No @key, so TerminusDB falls back to Random
+            Schema::OneOfClass { .. } => true,
             Schema::Enum { .. } => false,
             Schema::TaggedUnion { key, .. } => key == &Key::Random,
         }
@@ -1018,7 +1020,25 @@ pub trait ToTDBSchema {
     fn to_schema() -> Schema {
         let ty: SchemaType = Self::Type::default().into();
         match ty {
-            SchemaType::SchemaTypeClass => Schema::Class {
+            // A class holding `@oneOf` fields is a OneOfClass
+            SchemaType::SchemaTypeClass | SchemaType::SchemaTypeOneOfClass
+                if Self::one_of().is_some_and(|groups| !groups.is_empty()) =>
+            {
+                Schema::OneOfClass {
+                    id: Self::id().expect(&format!(
+                        "id for OneOfClass not defined in ToTDBSchema for entity {}",
+                        std::any::type_name::<Self>()
+                    )),
+                    base: Self::base(),
+                    documentation: Self::documentation(),
+                    subdocument: Self::subdocument().unwrap_or_default(),
+                    r#abstract: Self::abstractdocument().unwrap_or_default(),
+                    inherits: Self::inherits().unwrap_or_default(),
+                    classes: Self::one_of().unwrap_or_default(),
+                    properties: Self::properties().unwrap_or_default(),
+                }
+            }
+            SchemaType::SchemaTypeClass | SchemaType::SchemaTypeOneOfClass => Schema::Class {
                 id: Self::id().expect(&format!(
                     "id for Class not defined in ToTDBSchema for entity {}",
                     std::any::type_name::<Self>()
@@ -1032,9 +1052,6 @@ pub trait ToTDBSchema {
                 unfoldable: Self::unfoldable(),
                 properties: Self::properties().unwrap_or_default(),
             },
-            SchemaType::SchemaTypeOneOfClass => {
-                todo!()
-            }
             SchemaType::SchemaTypeEnum => Schema::Enum {
                 id: Self::id().expect("id for Enum not defined in ToTDBSchema"),
                 base: Self::base(),
@@ -1073,9 +1090,17 @@ pub trait ToTDBSchema {
         );
     }
 
+    /// This class plus every class reachable from its fields, each exactly once.
     fn to_schema_tree() -> Vec<Schema>;
 
-    // Change to_schema_tree_mut to be a static method
+    /// Add this class and everything reachable from it to `collection`.
+    ///
+    /// `collection` doubles as the visited set: implementations must insert their
+    /// own schema *before* descending into field types, and stop when a schema
+    /// with the same class name is already present. That is what keeps
+    /// self-referencing (`Box<Self>`, `Vec<Self>`) and mutually recursive models
+    /// from recursing forever. Wrappers sharing their inner type's class
+    /// (`Box<T>`, `Option<T>`, ...) should delegate to `T` instead of inserting.
     fn to_schema_tree_mut(collection: &mut HashSet<Schema>) {
         let schema = Self::to_schema();
         let class_name = schema.class_name().clone();
@@ -1158,6 +1183,11 @@ pub trait ToTDBSchema {
     fn values() -> Option<Vec<URI>> {
         None
     }
+
+    /// `@oneOf` groups: each is a set of properties of which exactly one is set
+    fn one_of() -> Option<Vec<BTreeSet<Property>>> {
+        None
+    }
 }
 
 impl<T: ToTDBSchema> From<T> for Schema {
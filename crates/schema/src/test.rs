@@ -942,6 +942,38 @@ mod optional_entity_id_tests {
 
         assert_eq!(lazy_from_instance.get_expect().email, "test@example.com");
     }
+
+    #[test]
+    fn test_known_id_is_stable() {
+        #[derive(Clone, Debug, TerminusDBModel, PartialEq)]
+        #[tdb(key = "lexical", key_fields = "country, city")]
+        pub struct KnownIdPlace {
+            pub country: String,
+            pub city: String,
+        }
+
+        #[derive(Clone, Debug, TerminusDBModel, PartialEq)]
+        #[tdb(key = "random")]
+        pub struct KnownIdNote {
+            pub text: String,
+        }
+
+        let place = KnownIdPlace {
+            country: "nl".to_string(),
+            city: "den haag".to_string(),
+        };
+        assert_eq!(
+            place.known_id().map(|id| id.typed().to_string()),
+            Some("KnownIdPlace/nl+den%20haag".to_string())
+        );
+
+        // Random keys are assigned by the server, so there is nothing to know.
+        let note = KnownIdNote {
+            text: "hi".to_string(),
+        };
+        assert!(note.known_id().is_none());
+        assert!(note.to_instance(None).known_id().is_none());
+    }
 }
 
 #[cfg(test)]
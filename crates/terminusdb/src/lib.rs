@@ -17,6 +17,7 @@
 //! - [`BranchSpec`] - Database/branch/commit specification
 //! - [`BranchClient`] - Branch-specific operations
 //! - [`CommitId`] - Strongly typed commit identifier
+//! - [`DocumentInsertArgs`] - Target and commit metadata for writes
 //! - [`TerminusDBResult`] - Result type alias for operations
 //! - [`TerminusAPIStatus`] - API response status enum
 //!
@@ -30,6 +31,8 @@
 //! - [`DatabasePath`] - Organization/database path
 //! - [`ResourcePath`] - Full resource path
 //! - [`DatabaseName`] - Validated database name
+//! - [`OrganizationName`] / [`BranchName`] - Validated organization and branch names
+//! - [`BranchRef`] - Branch or commit ref (`main`, `main~3`, `commit/<id>`)
 //!
 //! ### Server Types (from `terminusdb-bin`)
 //! - [`TerminusDBServer`] - Embedded server handle for testing
@@ -51,6 +54,9 @@ pub use terminusdb_client::BranchClient;
 /// Strongly typed commit identifier
 pub use terminusdb_client::CommitId;
 
+/// Target spec plus commit author/message for write operations
+pub use terminusdb_client::DocumentInsertArgs;
+
 /// Result type alias for TerminusDB operations
 pub use terminusdb_client::TerminusDBResult;
 
@@ -167,12 +173,24 @@ pub use terminusdb_types::ResourcePath;
 /// Validated database name
 pub use terminusdb_types::DatabaseName;
 
+/// Validated organization name
+pub use terminusdb_types::OrganizationName;
+
+/// Validated branch name
+pub use terminusdb_types::BranchName;
+
+/// Graph within a resource (Instance or Schema)
+pub use terminusdb_types::GraphType;
+
 /// Location type (Local or Remote)
 pub use terminusdb_types::Location;
 
 /// Resource type (Branch, Commit, Meta, etc.)
 pub use terminusdb_types::ResourceType;
 
+/// Branch or commit ref, including relative refs like `main~3`
+pub use terminusdb_types::BranchRef;
+
 /// Path parsing errors
 pub use terminusdb_types::PathError;
 
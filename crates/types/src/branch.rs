@@ -0,0 +1,417 @@
+//! Branch specifications: a database plus the branch or commit to work on.
+//!
+//! The grammar is
+//!
+//! ```text
+//! spec     := org "/" db [ "/" location "/" ref ]
+//! location := "local" | "remote"
+//! ref      := "branch/" name [ "~" [ n ] ] | "commit/" id
+//! ```
+//!
+//! `org/db` means the `main` branch. A branch followed by `~n` is a relative
+//! ref: the `n`th ancestor of the branch head, as in git (`main~` is
+//! `main~1`). The server has no notion of relative refs, so they must be
+//! resolved to a commit before the spec is used in a request.
+//!
+//! A [`BranchSpec`] serializes as its string form.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{
+    BranchName, CommitId, DatabasePath, GraphType, Location, PathError, ResourcePath, ResourceType,
+};
+
+/// Branch used when a spec names only the database.
+pub const DEFAULT_BRANCH: &str = "main";
+
+/// The branch or commit a [`BranchSpec`] points at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BranchRef {
+    /// The head of a branch, or `ancestor` commits before it.
+    Branch { name: BranchName, ancestor: u32 },
+    /// A specific commit.
+    Commit(CommitId),
+}
+
+impl BranchRef {
+    /// The head of the branch `name`.
+    pub fn branch(name: BranchName) -> Self {
+        BranchRef::Branch { name, ancestor: 0 }
+    }
+
+    /// The commit `id`.
+    pub fn commit(id: CommitId) -> Self {
+        BranchRef::Commit(id)
+    }
+
+    /// Parse `main`, `main~3`, `branch/main~3` or `commit/<id>`.
+    pub fn parse(s: &str) -> Result<Self, PathError> {
+        if let Some(id) = s.strip_prefix("commit/") {
+            return CommitId::new(id).map(BranchRef::Commit);
+        }
+
+        let branch = s.strip_prefix("branch/").unwrap_or(s);
+        let (name, ancestor) = match branch.rsplit_once('~') {
+            Some((name, "")) => (name, 1),
+            Some((name, n)) => {
+                let ancestor = n.parse().map_err(|_| {
+                    PathError::InvalidBranchSpec(format!(
+                        "Invalid relative ref '~{}' in '{}', expected '~<number>'",
+                        n, s
+                    ))
+                })?;
+                (name, ancestor)
+            }
+            None => (branch, 0),
+        };
+        if name.is_empty() {
+            return Err(PathError::InvalidBranchSpec(format!(
+                "Missing branch name in '{}'",
+                s
+            )));
+        }
+        Ok(BranchRef::Branch {
+            name: BranchName::new(name)?,
+            ancestor,
+        })
+    }
+
+    /// The branch name, if this is a branch ref.
+    pub fn branch_name(&self) -> Option<&str> {
+        match self {
+            BranchRef::Branch { name, .. } => Some(name.as_str()),
+            BranchRef::Commit(_) => None,
+        }
+    }
+
+    /// The commit id, if this is a commit ref.
+    pub fn commit_id(&self) -> Option<&CommitId> {
+        match self {
+            BranchRef::Branch { .. } => None,
+            BranchRef::Commit(id) => Some(id),
+        }
+    }
+
+    /// How many commits before the branch head this ref is (0 for commits).
+    pub fn ancestor(&self) -> u32 {
+        match self {
+            BranchRef::Branch { ancestor, .. } => *ancestor,
+            BranchRef::Commit(_) => 0,
+        }
+    }
+
+    /// Whether this ref must be resolved to a commit before use.
+    pub fn is_relative(&self) -> bool {
+        self.ancestor() > 0
+    }
+}
+
+impl FromStr for BranchRef {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for BranchRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BranchRef::Branch { name, ancestor: 0 } => write!(f, "branch/{}", name),
+            BranchRef::Branch { name, ancestor } => write!(f, "branch/{}~{}", name, ancestor),
+            BranchRef::Commit(id) => write!(f, "commit/{}", id),
+        }
+    }
+}
+
+/// A database and the branch or commit within it, e.g.
+/// `admin/mydb/local/branch/dev` or `admin/mydb/local/branch/main~3`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BranchSpec {
+    database_path: DatabasePath,
+    location: Location,
+    reference: BranchRef,
+}
+
+impl BranchSpec {
+    /// The head of `branch` in the local repository of `database_path`.
+    pub fn new(database_path: DatabasePath, branch: BranchName) -> Self {
+        Self::with_ref(database_path, BranchRef::branch(branch))
+    }
+
+    /// The commit `id` in the local repository of `database_path`.
+    pub fn commit(database_path: DatabasePath, id: CommitId) -> Self {
+        Self::with_ref(database_path, BranchRef::commit(id))
+    }
+
+    /// Any ref in the local repository of `database_path`.
+    pub fn with_ref(database_path: DatabasePath, reference: BranchRef) -> Self {
+        Self {
+            database_path,
+            location: Location::Local,
+            reference,
+        }
+    }
+
+    /// Set the repository location (default: local).
+    pub fn location(mut self, location: Location) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Point `n` commits before the branch head.
+    ///
+    /// Commit refs are already absolute and are returned unchanged.
+    pub fn ancestor(mut self, n: u32) -> Self {
+        if let BranchRef::Branch { ancestor, .. } = &mut self.reference {
+            *ancestor = n;
+        }
+        self
+    }
+
+    /// Parse a spec such as `admin/mydb`, `admin/mydb/local/branch/dev`,
+    /// `admin/mydb/local/commit/<id>` or `admin/mydb/local/branch/main~3`.
+    pub fn parse(spec: &str) -> Result<Self, PathError> {
+        let mut parts = spec.splitn(4, '/');
+        let (org, db) = match (parts.next(), parts.next()) {
+            (Some(org), Some(db)) => (org, db),
+            _ => {
+                return Err(PathError::InvalidBranchSpec(format!(
+                    "Expected 'organization/database[/location/ref]', got '{}'",
+                    spec
+                )))
+            }
+        };
+        let database_path = DatabasePath::parse(&format!("{}/{}", org, db))?;
+
+        let (location, reference) = match (parts.next(), parts.next()) {
+            (None, _) => (Location::Local, BranchRef::branch(BranchName::main())),
+            (Some(location), Some(reference))
+                if reference.starts_with("branch/") || reference.starts_with("commit/") =>
+            {
+                (Location::from_str(location)?, BranchRef::parse(reference)?)
+            }
+            _ => {
+                return Err(PathError::InvalidBranchSpec(format!(
+                    "Expected 'branch/<name>' or 'commit/<id>' after the location in '{}'",
+                    spec
+                )))
+            }
+        };
+
+        Ok(Self {
+            database_path,
+            location,
+            reference,
+        })
+    }
+
+    /// Get the database path
+    pub fn database_path(&self) -> &DatabasePath {
+        &self.database_path
+    }
+
+    /// Get the repository location
+    pub fn repository(&self) -> Location {
+        self.location
+    }
+
+    /// Get the branch or commit ref
+    pub fn reference(&self) -> &BranchRef {
+        &self.reference
+    }
+
+    /// The branch name, if this spec points at a branch.
+    pub fn branch(&self) -> Option<&str> {
+        self.reference.branch_name()
+    }
+
+    /// The commit id, if this spec points at a commit.
+    pub fn commit_id(&self) -> Option<&CommitId> {
+        self.reference.commit_id()
+    }
+
+    /// Whether this spec must be resolved to a commit before use.
+    pub fn is_relative(&self) -> bool {
+        self.reference.is_relative()
+    }
+
+    /// The resource path of the branch or commit, ignoring any `~n` offset.
+    pub fn to_resource_path(&self) -> ResourcePath {
+        let resource = match &self.reference {
+            BranchRef::Branch { name, .. } => ResourceType::Branch(name.to_string()),
+            BranchRef::Commit(id) => ResourceType::Commit(id.to_string()),
+        };
+        ResourcePath::new(self.database_path.clone(), self.location, resource)
+    }
+
+    /// The resource path of one graph of the branch or commit, ignoring any
+    /// `~n` offset.
+    pub fn graph(&self, graph: GraphType) -> ResourcePath {
+        self.to_resource_path().with_graph(graph)
+    }
+
+    /// Convert to a full path string
+    pub fn to_path_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The main branch of the database
+impl From<DatabasePath> for BranchSpec {
+    fn from(database_path: DatabasePath) -> Self {
+        Self::new(database_path, BranchName::main())
+    }
+}
+
+impl TryFrom<ResourcePath> for BranchSpec {
+    type Error = PathError;
+
+    fn try_from(path: ResourcePath) -> Result<Self, Self::Error> {
+        if let Some(graph) = path.graph() {
+            return Err(PathError::InvalidBranchSpec(format!(
+                "'{}' addresses the {} graph, not a whole branch or commit",
+                path, graph
+            )));
+        }
+        let reference = match path.resource() {
+            ResourceType::Branch(name) => BranchRef::branch(BranchName::new(name.as_str())?),
+            ResourceType::Commit(id) => BranchRef::commit(CommitId::new(id.as_str())?),
+            other => {
+                return Err(PathError::InvalidBranchSpec(format!(
+                    "'{}' is not a branch or commit",
+                    other
+                )))
+            }
+        };
+        Ok(Self::with_ref(path.database_path().clone(), reference).location(path.location()))
+    }
+}
+
+impl FromStr for BranchSpec {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for BranchSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.database_path, self.location, self.reference
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_ref_parse() {
+        assert_eq!(
+            BranchRef::parse("main").unwrap(),
+            BranchRef::branch(BranchName::main())
+        );
+        assert_eq!(
+            BranchRef::parse("branch/main~3").unwrap(),
+            BranchRef::Branch {
+                name: BranchName::main(),
+                ancestor: 3
+            }
+        );
+        assert_eq!(BranchRef::parse("dev~").unwrap().ancestor(), 1);
+        assert_eq!(
+            BranchRef::parse("commit/abc123").unwrap(),
+            BranchRef::commit(CommitId::new("abc123").unwrap())
+        );
+
+        assert!(BranchRef::parse("").is_err());
+        assert!(BranchRef::parse("~2").is_err());
+        assert!(BranchRef::parse("main~two").is_err());
+        assert!(BranchRef::parse("commit/").is_err());
+        assert!(BranchRef::parse("commit/abc~1").is_err());
+        assert!(matches!(
+            BranchRef::parse("my branch"),
+            Err(PathError::InvalidBranchName(_))
+        ));
+    }
+
+    #[test]
+    fn test_branch_spec_parse() {
+        let spec = BranchSpec::parse("admin/mydb").unwrap();
+        assert_eq!(spec.branch(), Some("main"));
+        assert_eq!(spec.repository(), Location::Local);
+        assert_eq!(spec.to_string(), "admin/mydb/local/branch/main");
+
+        let spec: BranchSpec = "admin/mydb/local/branch/release/1.0".parse().unwrap();
+        assert_eq!(spec.branch(), Some("release/1.0"));
+
+        let spec = BranchSpec::parse("admin/mydb/local/commit/abc123").unwrap();
+        assert_eq!(spec.commit_id().map(CommitId::as_str), Some("abc123"));
+        assert!(!spec.is_relative());
+
+        let spec = BranchSpec::parse("admin/mydb/local/branch/main~3").unwrap();
+        assert_eq!(spec.reference().ancestor(), 3);
+        assert!(spec.is_relative());
+        assert_eq!(
+            spec.to_resource_path().to_path_string(),
+            "admin/mydb/local/branch/main"
+        );
+
+        assert!(BranchSpec::parse("admin").is_err());
+        assert!(BranchSpec::parse("admin/mydb/local").is_err());
+        assert!(BranchSpec::parse("admin/mydb/local/_meta").is_err());
+        assert!(BranchSpec::parse("admin/mydb/elsewhere/branch/main").is_err());
+    }
+
+    #[test]
+    fn test_branch_spec_round_trip() {
+        for spec in [
+            "admin/mydb/local/branch/dev",
+            "admin/mydb/remote/branch/main~2",
+            "admin/mydb/local/commit/abc123",
+        ] {
+            assert_eq!(BranchSpec::parse(spec).unwrap().to_string(), spec);
+        }
+
+        let path = DatabasePath::parse("admin/mydb").unwrap();
+        assert_eq!(
+            BranchSpec::new(path.clone(), BranchName::main())
+                .ancestor(1)
+                .to_string(),
+            "admin/mydb/local/branch/main~1"
+        );
+        assert_eq!(
+            BranchSpec::from(path),
+            BranchSpec::parse("admin/mydb").unwrap()
+        );
+
+        let resource = ResourcePath::parse("admin/mydb/local/commit/abc123").unwrap();
+        assert_eq!(
+            BranchSpec::try_from(resource)
+                .unwrap()
+                .commit_id()
+                .map(CommitId::as_str),
+            Some("abc123")
+        );
+        let meta = ResourcePath::parse("admin/mydb/local/_meta").unwrap();
+        assert!(BranchSpec::try_from(meta).is_err());
+        let graph = ResourcePath::parse("admin/mydb/local/branch/dev/schema").unwrap();
+        assert!(BranchSpec::try_from(graph).is_err());
+
+        let spec = BranchSpec::parse("admin/mydb/local/branch/dev~1").unwrap();
+        assert_eq!(
+            spec.graph(GraphType::Instance).to_string(),
+            "admin/mydb/local/branch/dev/instance"
+        );
+    }
+}
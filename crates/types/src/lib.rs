@@ -1,19 +1,60 @@
-use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::str::FromStr;
+//! Validated paths to TerminusDB databases and resources, and the
+//! [`BranchSpec`] grammar shared by the client, CLI and MCP server.
+//!
+//! The crate is `no_std` and only needs `alloc`, so it can be used from WASM
+//! plugins and other environments without the standard library.
+//!
+//! All path and name types serialize as their canonical string form. The
+//! `schemars` feature adds matching `JsonSchema` implementations.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use core::str::FromStr;
 use thiserror::Error;
 
+mod branch;
+mod name;
+mod reference;
+mod string_form;
+
+pub use branch::{BranchRef, BranchSpec, DEFAULT_BRANCH};
+pub use name::{BranchName, OrganizationName, MAX_NAME_LENGTH};
+pub use reference::{CommitId, Ref, MAX_COMMIT_ID_LENGTH};
+
 #[derive(Error, Debug)]
 pub enum PathError {
     #[error("Invalid database name: {0}")]
     InvalidDatabaseName(String),
 
+    #[error("Invalid organization name: {0}")]
+    InvalidOrganizationName(String),
+
+    #[error("Invalid branch name: {0}")]
+    InvalidBranchName(String),
+
     #[error("Invalid database path: {0}")]
     InvalidDatabasePath(String),
 
     #[error("Invalid resource path: {0}")]
     InvalidResourcePath(String),
 
+    #[error("Invalid branch spec: {0}")]
+    InvalidBranchSpec(String),
+
+    #[error("Invalid commit id: {0}")]
+    InvalidCommitId(String),
+
+    #[error("Cannot resolve ref: {0}")]
+    UnresolvedRef(String),
+
     #[error("System database cannot be used in this context: {0}")]
     SystemDatabase(String),
 
@@ -83,12 +124,12 @@ impl AsRef<str> for DatabaseName {
 /// A database path with organization prefix (e.g., "admin/mydb")
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DatabasePath {
-    organization: String,
+    organization: OrganizationName,
     database: DatabaseName,
 }
 
 impl DatabasePath {
-    /// Create a new DatabasePath
+    /// Create a new DatabasePath, validating the organization as an [`OrganizationName`]
     pub fn new(organization: impl Into<String>, database: DatabaseName) -> Result<Self, PathError> {
         let organization = organization.into();
 
@@ -97,11 +138,19 @@ impl DatabasePath {
         }
 
         Ok(Self {
-            organization,
+            organization: OrganizationName::new(organization)?,
             database,
         })
     }
 
+    /// Create a new DatabasePath from already validated names
+    pub fn from_names(organization: OrganizationName, database: DatabaseName) -> Self {
+        Self {
+            organization,
+            database,
+        }
+    }
+
     /// Parse from a string like "admin/mydb"
     ///
     /// Only accepts paths with exactly 2 components separated by '/'.
@@ -128,13 +177,18 @@ impl DatabasePath {
         }
 
         Ok(Self {
-            organization: organization.to_string(),
+            organization: OrganizationName::new(organization)?,
             database: DatabaseName::new(database)?,
         })
     }
 
     /// Get the organization
     pub fn organization(&self) -> &str {
+        self.organization.as_str()
+    }
+
+    /// Get the validated organization name
+    pub fn organization_name(&self) -> &OrganizationName {
         &self.organization
     }
 
@@ -226,12 +280,54 @@ impl fmt::Display for ResourceType {
     }
 }
 
-/// A full resource path (e.g., "admin/mydb/local/branch/main" or "admin/mydb/local/_meta")
+/// Graph within a branch, commit or repository graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphType {
+    /// Instance data graph
+    Instance,
+    /// Schema graph
+    Schema,
+}
+
+impl GraphType {
+    /// Get the graph name as used in resource paths
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GraphType::Instance => "instance",
+            GraphType::Schema => "schema",
+        }
+    }
+}
+
+impl FromStr for GraphType {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instance" => Ok(GraphType::Instance),
+            "schema" => Ok(GraphType::Schema),
+            _ => Err(PathError::InvalidResourcePath(format!(
+                "Invalid graph type '{}', expected 'instance' or 'schema'",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for GraphType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A full resource path (e.g., "admin/mydb/local/branch/main" or "admin/mydb/local/_meta"),
+/// optionally narrowed to one graph (e.g., "admin/mydb/local/branch/main/schema")
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ResourcePath {
     database_path: DatabasePath,
     location: Location,
     resource: ResourceType,
+    graph: Option<GraphType>,
 }
 
 impl ResourcePath {
@@ -241,12 +337,36 @@ impl ResourcePath {
             database_path,
             location,
             resource,
+            graph: None,
         }
     }
 
+    /// Narrow the path to a single graph
+    pub fn with_graph(mut self, graph: GraphType) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
     /// Parse from a string like "admin/mydb/local/branch/main"
+    ///
+    /// A trailing `instance` or `schema` component after a branch, commit,
+    /// `_meta` or `_commits` resource is read as the graph, so a branch named
+    /// e.g. `release/schema` can only be addressed through [`ResourcePath::new`].
     pub fn parse(path: &str) -> Result<Self, PathError> {
-        let parts: Vec<&str> = path.split('/').collect();
+        let mut parts: Vec<&str> = path.split('/').collect();
+
+        let graph_target = match parts.get(3).copied() {
+            Some("_meta" | "_commits") => parts.len() == 5,
+            Some("branch" | "commit") => parts.len() >= 6,
+            _ => false,
+        };
+        let graph = match parts.last() {
+            Some(last) if graph_target => GraphType::from_str(last).ok(),
+            _ => None,
+        };
+        if graph.is_some() {
+            parts.pop();
+        }
 
         if parts.len() < 4 {
             return Err(PathError::InvalidResourcePath(format!(
@@ -298,6 +418,7 @@ impl ResourcePath {
             database_path,
             location,
             resource,
+            graph,
         })
     }
 
@@ -316,9 +437,14 @@ impl ResourcePath {
         &self.resource
     }
 
+    /// Get the graph, if the path is narrowed to one
+    pub fn graph(&self) -> Option<GraphType> {
+        self.graph
+    }
+
     /// Convert to a full path string
     pub fn to_path_string(&self) -> String {
-        format!("{}/{}/{}", self.database_path, self.location, self.resource)
+        self.to_string()
     }
 }
 
@@ -336,7 +462,11 @@ impl fmt::Display for ResourcePath {
             f,
             "{}/{}/{}",
             self.database_path, self.location, self.resource
-        )
+        )?;
+        if let Some(graph) = self.graph {
+            write!(f, "/{}", graph)?;
+        }
+        Ok(())
     }
 }
 
@@ -371,6 +501,8 @@ mod tests {
         assert!(DatabasePath::parse("admin/mydb/extra").is_err());
         assert!(DatabasePath::parse("admin/").is_err());
         assert!(DatabasePath::parse("/mydb").is_err());
+        assert!(DatabasePath::parse("my org/mydb").is_err());
+        assert!(DatabasePath::new("my org", DatabaseName::new("mydb").unwrap()).is_err());
     }
 
     #[test]
@@ -397,6 +529,41 @@ mod tests {
         assert!(ResourcePath::parse("admin/mydb/invalid/branch/main").is_err());
     }
 
+    #[test]
+    fn test_resource_path_graph() {
+        let path = ResourcePath::parse("admin/mydb/local/branch/main/instance").unwrap();
+        assert_eq!(path.resource(), &ResourceType::Branch("main".to_string()));
+        assert_eq!(path.graph(), Some(GraphType::Instance));
+        assert_eq!(
+            path.to_path_string(),
+            "admin/mydb/local/branch/main/instance"
+        );
+
+        let path = ResourcePath::parse("admin/mydb/local/branch/release/1.0/schema").unwrap();
+        assert_eq!(
+            path.resource(),
+            &ResourceType::Branch("release/1.0".to_string())
+        );
+        assert_eq!(path.graph(), Some(GraphType::Schema));
+
+        let path = ResourcePath::parse("admin/mydb/local/_commits/instance").unwrap();
+        assert_eq!(path.resource(), &ResourceType::Commits);
+        assert_eq!(path.graph(), Some(GraphType::Instance));
+
+        // A branch that happens to be called "schema" is still a branch
+        let path = ResourcePath::parse("admin/mydb/local/branch/schema").unwrap();
+        assert_eq!(path.resource(), &ResourceType::Branch("schema".to_string()));
+        assert_eq!(path.graph(), None);
+
+        let path = ResourcePath::parse("admin/mydb/local/commit/abc123")
+            .unwrap()
+            .with_graph(GraphType::Schema);
+        assert_eq!(path.to_string(), "admin/mydb/local/commit/abc123/schema");
+
+        assert!(ResourcePath::parse("admin/mydb/local/_meta/inference").is_err());
+        assert!(GraphType::from_str("inference").is_err());
+    }
+
     #[test]
     fn test_location_parse() {
         assert_eq!(Location::from_str("local").unwrap(), Location::Local);
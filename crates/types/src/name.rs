@@ -0,0 +1,215 @@
+//! Organization and branch names, validated before they reach the server.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+use core::str::FromStr;
+
+use crate::PathError;
+
+/// Longest organization or branch name, in bytes.
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// Characters git-style ref syntax gives a meaning, which a branch name
+/// cannot contain (`~` would also clash with relative refs like `main~3`).
+const BRANCH_RESERVED: &[char] = &['~', '^', ':', '?', '*', '[', '\\'];
+
+/// Checks shared by every name: non-empty, bounded length, printable and
+/// without whitespace. Returns the reason the name is invalid.
+fn check_common(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("name is empty".to_string());
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "name is {} bytes long, the maximum is {}",
+            name.len(),
+            MAX_NAME_LENGTH
+        ));
+    }
+    if let Some(c) = name.chars().find(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!(
+            "name contains whitespace or control character {:?}",
+            c
+        ));
+    }
+    Ok(())
+}
+
+/// An organization name (e.g., "admin")
+///
+/// Organization names are non-empty, at most [`MAX_NAME_LENGTH`] bytes, and
+/// contain no whitespace, control characters or `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrganizationName(String);
+
+impl OrganizationName {
+    /// Create a new OrganizationName with validation
+    pub fn new(name: impl Into<String>) -> Result<Self, PathError> {
+        let name = name.into();
+        let invalid =
+            |reason: String| PathError::InvalidOrganizationName(format!("'{}': {}", name, reason));
+
+        check_common(&name).map_err(invalid)?;
+        if name.contains('/') {
+            return Err(invalid("name contains '/'".to_string()));
+        }
+        Ok(Self(name))
+    }
+
+    /// Create a new OrganizationName without validation (use with caution)
+    pub fn new_unchecked(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Get the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume and return the inner string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for OrganizationName {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for OrganizationName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for OrganizationName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A branch name (e.g., "main" or "feature/login")
+///
+/// Branch names follow the common rules for names, may be split into
+/// segments by `/` (no empty segments, no leading or trailing `/`), and
+/// contain neither `..` nor any of `~ ^ : ? * [ \`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BranchName(String);
+
+impl BranchName {
+    /// Create a new BranchName with validation
+    pub fn new(name: impl Into<String>) -> Result<Self, PathError> {
+        let name = name.into();
+        let invalid =
+            |reason: String| PathError::InvalidBranchName(format!("'{}': {}", name, reason));
+
+        check_common(&name).map_err(invalid)?;
+        if let Some(c) = name.chars().find(|c| BRANCH_RESERVED.contains(c)) {
+            return Err(invalid(format!("name contains reserved character '{}'", c)));
+        }
+        if name.split('/').any(str::is_empty) {
+            return Err(invalid(
+                "name has an empty segment (leading, trailing or double '/')".to_string(),
+            ));
+        }
+        if name.contains("..") {
+            return Err(invalid("name contains '..'".to_string()));
+        }
+        Ok(Self(name))
+    }
+
+    /// Create a new BranchName without validation (use with caution)
+    pub fn new_unchecked(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// The default branch, `main`
+    pub fn main() -> Self {
+        Self(crate::DEFAULT_BRANCH.to_string())
+    }
+
+    /// Get the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume and return the inner string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for BranchName {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for BranchName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for BranchName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_organization_name() {
+        assert_eq!(OrganizationName::new("admin").unwrap().as_str(), "admin");
+        assert!(OrganizationName::new("my-team_2").is_ok());
+
+        assert!(OrganizationName::new("").is_err());
+        assert!(OrganizationName::new("my team").is_err());
+        assert!(OrganizationName::new("a/b").is_err());
+        assert!(OrganizationName::new("x".repeat(MAX_NAME_LENGTH + 1)).is_err());
+
+        let err = OrganizationName::new("a/b").unwrap_err().to_string();
+        assert!(err.contains("'a/b'") && err.contains("'/'"), "{}", err);
+    }
+
+    #[test]
+    fn test_branch_name() {
+        for name in ["main", "dev-2", "feature/login", "release/1.0"] {
+            assert_eq!(BranchName::new(name).unwrap().as_str(), name);
+        }
+        assert_eq!(BranchName::main().as_str(), "main");
+
+        for name in [
+            "",
+            "my branch",
+            "main~3",
+            "a:b",
+            "what?",
+            "/dev",
+            "dev/",
+            "a//b",
+            "a..b",
+        ] {
+            assert!(
+                BranchName::new(name).is_err(),
+                "{:?} should be rejected",
+                name
+            );
+        }
+
+        let err = BranchName::new("main~3").unwrap_err().to_string();
+        assert!(err.contains("reserved character '~'"), "{}", err);
+    }
+}
@@ -0,0 +1,373 @@
+//! Commit identifiers and references for time travel.
+//!
+//! A [`Ref`] names a point in a branch's history: any [`BranchRef`] (a branch
+//! head, `main~n` or a commit), or `HEAD`, `HEAD~n` and `FIRST` on the branch
+//! it is used with. Relative refs and `FIRST` are resolved against the
+//! branch's commit history, newest commit first, as returned by the log
+//! endpoint.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BranchName, BranchRef, BranchSpec, PathError};
+
+/// Longest commit id accepted, in characters.
+pub const MAX_COMMIT_ID_LENGTH: usize = 64;
+
+/// A commit identifier (e.g., "9v1ehf0urfwqvt5f2ulj8dnxvp8tmlm")
+///
+/// TerminusDB commit ids are lowercase base-36 strings, so validation accepts
+/// non-empty strings of ASCII digits and lowercase letters (which includes
+/// hex ids) up to [`MAX_COMMIT_ID_LENGTH`] characters.
+///
+/// [`CommitId::new`] and `FromStr` validate user input. Ids the server hands
+/// back are taken as-is: the `From<String>`/`From<&str>` conversions and serde
+/// (a plain string) do not validate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CommitId(String);
+
+impl CommitId {
+    /// Create a new CommitId with validation
+    pub fn new(id: impl Into<String>) -> Result<Self, PathError> {
+        let id = id.into();
+
+        if id.is_empty() {
+            return Err(PathError::InvalidCommitId("commit id is empty".to_string()));
+        }
+        if id.len() > MAX_COMMIT_ID_LENGTH {
+            return Err(PathError::InvalidCommitId(format!(
+                "'{}' is {} characters long, the maximum is {}",
+                id,
+                id.len(),
+                MAX_COMMIT_ID_LENGTH
+            )));
+        }
+        if let Some(c) = id
+            .chars()
+            .find(|c| !(c.is_ascii_digit() || c.is_ascii_lowercase()))
+        {
+            return Err(PathError::InvalidCommitId(format!(
+                "'{}' contains '{}', expected only digits and lowercase letters",
+                id, c
+            )));
+        }
+        Ok(Self(id))
+    }
+
+    /// Create a new CommitId without validation (use with caution)
+    pub fn new_unchecked(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume and return the inner string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for CommitId {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for CommitId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for CommitId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for CommitId {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<String> for CommitId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for CommitId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+/// A point in history, possibly relative to the branch it is used with.
+///
+/// The grammar is that of [`BranchRef`] (`main`, `main~3`, `branch/main`,
+/// `commit/<id>`) plus two names for the branch the ref is used with: `HEAD`
+/// (with the same `~n` suffix, so `HEAD~2`) and `FIRST`, its first commit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Ref {
+    /// A branch or commit named explicitly
+    Named(BranchRef),
+    /// The head of the branch (`HEAD`), or `n` commits before it (`HEAD~n`)
+    Head(u32),
+    /// The first (oldest) commit of the branch
+    FirstCommit,
+}
+
+impl Ref {
+    /// Parse a ref string
+    pub fn parse(s: &str) -> Result<Self, PathError> {
+        if s == "FIRST" {
+            return Ok(Ref::FirstCommit);
+        }
+        Ok(match BranchRef::parse(s)? {
+            BranchRef::Branch { name, ancestor } if name.as_str() == "HEAD" => Ref::Head(ancestor),
+            reference => Ref::Named(reference),
+        })
+    }
+
+    /// The branch whose commit history resolves this ref, when used with
+    /// `branch`: the named branch for `main~3`, otherwise `branch` itself.
+    pub fn history_branch<'a>(&'a self, branch: &'a BranchName) -> &'a BranchName {
+        match self {
+            Ref::Named(BranchRef::Branch { name, .. }) => name,
+            _ => branch,
+        }
+    }
+
+    /// Whether resolving this ref needs the branch's commit history.
+    pub fn needs_history(&self) -> bool {
+        self.history_depth() != Some(0)
+    }
+
+    /// How many of the newest commits [`Ref::resolve`] needs, `None` meaning
+    /// the whole history.
+    pub fn history_depth(&self) -> Option<usize> {
+        let ancestor = match self {
+            Ref::Named(reference) => reference.ancestor(),
+            Ref::Head(n) => *n,
+            Ref::FirstCommit => return None,
+        };
+        Some(if ancestor == 0 {
+            0
+        } else {
+            ancestor as usize + 1
+        })
+    }
+
+    /// Resolve to a branch head or commit, used with `branch`. `history` is
+    /// the commit history (newest first) of [`Ref::history_branch`].
+    ///
+    /// `history` only needs to be as deep as [`Ref::history_depth`].
+    pub fn resolve(
+        &self,
+        branch: &BranchName,
+        history: &[CommitId],
+    ) -> Result<BranchRef, PathError> {
+        match self {
+            Ref::Named(reference) => reference.resolve(history),
+            Ref::Head(n) => BranchRef::Branch {
+                name: branch.clone(),
+                ancestor: *n,
+            }
+            .resolve(history),
+            Ref::FirstCommit => history
+                .last()
+                .map(|id| BranchRef::Commit(id.clone()))
+                .ok_or_else(|| {
+                    PathError::UnresolvedRef(format!("branch '{}' has no commits", branch))
+                }),
+        }
+    }
+}
+
+impl From<BranchRef> for Ref {
+    fn from(reference: BranchRef) -> Self {
+        Ref::Named(reference)
+    }
+}
+
+impl FromStr for Ref {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ref::Named(reference) => write!(f, "{}", reference),
+            Ref::Head(0) => write!(f, "HEAD"),
+            Ref::Head(n) => write!(f, "HEAD~{}", n),
+            Ref::FirstCommit => write!(f, "FIRST"),
+        }
+    }
+}
+
+impl BranchRef {
+    /// Resolve a relative ref (`main~3`) to a commit, given the branch's
+    /// commit history newest first. Absolute refs are returned unchanged.
+    pub fn resolve(&self, history: &[CommitId]) -> Result<BranchRef, PathError> {
+        match self {
+            BranchRef::Branch { name, ancestor } if *ancestor > 0 => history
+                .get(*ancestor as usize)
+                .map(|id| BranchRef::Commit(id.clone()))
+                .ok_or_else(|| {
+                    PathError::UnresolvedRef(format!(
+                        "'{}~{}' is beyond the {} commits of the branch",
+                        name,
+                        ancestor,
+                        history.len()
+                    ))
+                }),
+            _ => Ok(self.clone()),
+        }
+    }
+}
+
+impl BranchSpec {
+    /// Resolve a relative spec (`.../branch/main~3`) to a commit spec, given
+    /// the branch's commit history newest first.
+    pub fn resolve(&self, history: &[CommitId]) -> Result<BranchSpec, PathError> {
+        let reference = self.reference().resolve(history)?;
+        Ok(
+            BranchSpec::with_ref(self.database_path().clone(), reference)
+                .location(self.repository()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn history() -> Vec<CommitId> {
+        ["c3", "c2", "c1"]
+            .into_iter()
+            .map(|id| CommitId::new(id).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_commit_id() {
+        assert!(CommitId::new("9v1ehf0urfwqvt5f2ulj8dnxvp8tmlm").is_ok());
+        assert!(CommitId::new("0123456789abcdef").is_ok());
+
+        assert!(CommitId::new("").is_err());
+        assert!(CommitId::new("ABC").is_err());
+        assert!(CommitId::new("abc/def").is_err());
+        assert!(CommitId::new("a".repeat(MAX_COMMIT_ID_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_ref_parse() {
+        assert_eq!(Ref::parse("HEAD").unwrap(), Ref::Head(0));
+        assert_eq!(Ref::parse("HEAD~").unwrap(), Ref::Head(1));
+        assert_eq!(Ref::parse("HEAD~4").unwrap(), Ref::Head(4));
+        assert_eq!(Ref::parse("FIRST").unwrap(), Ref::FirstCommit);
+        assert_eq!(
+            Ref::parse("commit/abc123").unwrap(),
+            Ref::Named(BranchRef::commit(CommitId::new("abc123").unwrap()))
+        );
+        // Same relative syntax as BranchRef: `HEAD~n` is `<branch>~n`.
+        assert_eq!(
+            Ref::parse("main~2").unwrap(),
+            Ref::Named(BranchRef::parse("main~2").unwrap())
+        );
+        assert_eq!(
+            Ref::parse("dev").unwrap(),
+            Ref::parse("branch/dev").unwrap()
+        );
+
+        assert!(Ref::parse("HEAD~x").is_err());
+        assert!(Ref::parse("HEAD^").is_err());
+        assert_eq!(
+            Ref::parse("HEADER").unwrap(),
+            Ref::Named(BranchRef::branch(BranchName::new("HEADER").unwrap()))
+        );
+        assert!(Ref::parse("commit/ABC").is_err());
+
+        for s in [
+            "HEAD",
+            "HEAD~2",
+            "FIRST",
+            "branch/dev",
+            "branch/dev~2",
+            "commit/abc123",
+        ] {
+            assert_eq!(Ref::parse(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_ref_resolve() {
+        let main = BranchName::main();
+        let history = history();
+        let commit = |id: &str| BranchRef::Commit(CommitId::new(id).unwrap());
+
+        assert_eq!(
+            Ref::Head(0).resolve(&main, &[]).unwrap(),
+            BranchRef::branch(main.clone())
+        );
+        assert_eq!(Ref::Head(1).resolve(&main, &history).unwrap(), commit("c2"));
+        assert_eq!(
+            Ref::FirstCommit.resolve(&main, &history).unwrap(),
+            commit("c1")
+        );
+        assert!(Ref::Head(3).resolve(&main, &history).is_err());
+        assert!(Ref::FirstCommit.resolve(&main, &[]).is_err());
+
+        assert!(!Ref::Head(0).needs_history());
+        assert_eq!(Ref::Head(2).history_depth(), Some(3));
+        assert_eq!(Ref::FirstCommit.history_depth(), None);
+
+        // A relative ref on another branch resolves against that branch.
+        let dev_back = Ref::parse("dev~1").unwrap();
+        assert_eq!(dev_back.history_branch(&main).as_str(), "dev");
+        assert_eq!(dev_back.history_depth(), Some(2));
+        assert_eq!(dev_back.resolve(&main, &history).unwrap(), commit("c2"));
+        assert_eq!(Ref::Head(1).history_branch(&main), &main);
+    }
+
+    #[test]
+    fn test_commit_id_serde_keeps_server_ids() {
+        let id: CommitId = serde_json::from_str("\"branch:abc123\"").unwrap();
+        assert_eq!(id.as_str(), "branch:abc123");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"branch:abc123\"");
+        assert!(CommitId::new("branch:abc123").is_err());
+    }
+
+    #[test]
+    fn test_branch_spec_resolve() {
+        let spec = BranchSpec::parse("admin/mydb/local/branch/main~2").unwrap();
+        assert_eq!(
+            spec.resolve(&history()).unwrap().to_string(),
+            "admin/mydb/local/commit/c1"
+        );
+
+        let head = BranchSpec::parse("admin/mydb").unwrap();
+        assert_eq!(head.resolve(&[]).unwrap(), head);
+    }
+}
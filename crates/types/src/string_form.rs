@@ -0,0 +1,131 @@
+//! Serde and (with the `schemars` feature) JSON Schema implementations that
+//! represent each path type by its canonical string form, e.g.
+//! `"admin/mydb/local/branch/main"`, so they can appear directly in config
+//! files, CLI profiles and MCP tool schemas.
+
+use alloc::string::String;
+use core::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "schemars")]
+use crate::CommitId;
+use crate::{
+    BranchName, BranchSpec, DatabaseName, DatabasePath, GraphType, Location, OrganizationName, Ref,
+    ResourcePath,
+};
+
+/// Implement `JsonSchema` (with the `schemars` feature) as a string with the
+/// given description and optional pattern.
+macro_rules! string_schema {
+    ($($ty:ident => $description:literal $(, pattern = $pattern:literal)?;)*) => {$(
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $ty {
+            fn schema_name() -> String {
+                String::from(stringify!($ty))
+            }
+
+            fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                #[allow(unused_mut)]
+                let mut string = schemars::schema::StringValidation::default();
+                $(string.pattern = Some(String::from($pattern));)?
+                schemars::schema::SchemaObject {
+                    instance_type: Some(schemars::schema::InstanceType::String.into()),
+                    metadata: Some(alloc::boxed::Box::new(schemars::schema::Metadata {
+                        description: Some(String::from($description)),
+                        ..Default::default()
+                    })),
+                    string: Some(alloc::boxed::Box::new(string)),
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    )*};
+}
+
+/// Implement `Serialize`/`Deserialize` through `Display`/`FromStr`, and
+/// `JsonSchema` as in [`string_schema`].
+macro_rules! string_form {
+    ($($ty:ident => $description:literal $(, pattern = $pattern:literal)?;)*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                $ty::from_str(&s).map_err(de::Error::custom)
+            }
+        }
+
+        string_schema! { $ty => $description $(, pattern = $pattern)?; }
+    )*};
+}
+
+string_form! {
+    DatabaseName => "Database name without organization, e.g. \"mydb\"";
+    OrganizationName => "Organization name, e.g. \"admin\"", pattern = "^[^/\\s]+$";
+    BranchName => "Branch name, e.g. \"main\" or \"feature/login\"";
+    DatabasePath => "Database path, e.g. \"admin/mydb\"", pattern = "^[^/]+/[^/]+$";
+    Location => "Repository location", pattern = "^(local|remote)$";
+    GraphType => "Graph type", pattern = "^(instance|schema)$";
+    ResourcePath =>
+        "Resource path, e.g. \"admin/mydb/local/branch/main\" or \"admin/mydb/local/_meta\"";
+    Ref => "History ref: \"HEAD\", \"HEAD~<n>\", \"FIRST\", a branch (\"main\", \"main~<n>\") or \"commit/<id>\"";
+    BranchSpec =>
+        "Branch or commit of a database, e.g. \"admin/mydb\", \"admin/mydb/local/branch/dev~2\" \
+         or \"admin/mydb/local/commit/<id>\"";
+}
+
+// Commit ids are kept as the server returns them, so `CommitId` derives its
+// (non-validating) serde impls and only gets the schema here.
+string_schema! {
+    CommitId => "Commit id, e.g. \"9v1ehf0urfwqvt5f2ulj8dnxvp8tmlm\"";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_paths_serialize_as_strings() {
+        let path = ResourcePath::parse("admin/mydb/local/branch/main/schema").unwrap();
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!(json, "\"admin/mydb/local/branch/main/schema\"");
+        assert_eq!(serde_json::from_str::<ResourcePath>(&json).unwrap(), path);
+
+        let locations: Vec<Location> = serde_json::from_str("[\"local\", \"remote\"]").unwrap();
+        assert_eq!(locations, vec![Location::Local, Location::Remote]);
+
+        let spec: BranchSpec = serde_json::from_str("\"admin/mydb/local/branch/dev~2\"").unwrap();
+        assert_eq!(spec.reference().ancestor(), 2);
+        assert_eq!(
+            serde_json::to_string(&spec).unwrap(),
+            "\"admin/mydb/local/branch/dev~2\""
+        );
+    }
+
+    #[test]
+    fn test_invalid_paths_fail_to_deserialize() {
+        let err = serde_json::from_str::<DatabasePath>("\"admin\"").unwrap_err();
+        assert!(err.to_string().contains("Invalid database path"), "{}", err);
+        assert!(serde_json::from_str::<DatabaseName>("\"\"").is_err());
+        assert!(serde_json::from_str::<BranchName>("\"a b\"").is_err());
+        assert!(serde_json::from_str::<Location>("\"elsewhere\"").is_err());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema_is_string() {
+        let schema = schemars::schema_for!(DatabasePath);
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["type"], "string");
+        assert_eq!(json["pattern"], "^[^/]+/[^/]+$");
+    }
+}
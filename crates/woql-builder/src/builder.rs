@@ -1,4 +1,4 @@
-use crate::value::{IntoWoql2, Var};
+use crate::value::{IntoWoql2, NumericInput, StringInput, Var};
 use terminusdb_schema::{GraphType, ToTDBSchema};
 // Import Query from the prelude
 use terminusdb_woql2::prelude::{
@@ -419,8 +419,8 @@ impl WoqlBuilder {
     /// Trims whitespace from `untrimmed`, binding the result to `trimmed`.
     pub fn trim<U, T>(self, untrimmed: U, trimmed: T) -> Self
     where
-        U: IntoWoql2,
-        T: IntoWoql2,
+        U: StringInput,
+        T: StringInput,
     {
         let trim_query = Woql2Query::Trim(Woql2Trim {
             untrimmed: untrimmed.into_woql2_data_value(),
@@ -432,8 +432,8 @@ impl WoqlBuilder {
     /// Converts `mixed_case` string to lowercase, binding the result to `lower_case`.
     pub fn lower<M, L>(self, mixed_case: M, lower_case: L) -> Self
     where
-        M: IntoWoql2,
-        L: IntoWoql2,
+        M: StringInput,
+        L: StringInput,
     {
         let lower_query = Woql2Query::Lower(Woql2Lower {
             mixed: mixed_case.into_woql2_data_value(),
@@ -445,8 +445,8 @@ impl WoqlBuilder {
     /// Converts `mixed_case` string to uppercase, binding the result to `upper_case`.
     pub fn upper<M, U>(self, mixed_case: M, upper_case: U) -> Self
     where
-        M: IntoWoql2,
-        U: IntoWoql2,
+        M: StringInput,
+        U: StringInput,
     {
         let upper_query = Woql2Query::Upper(Woql2Upper {
             mixed: mixed_case.into_woql2_data_value(),
@@ -458,10 +458,10 @@ impl WoqlBuilder {
     /// Pads `input_string` with `pad_char` `times` times, binding the result to `result_string`.
     pub fn pad<S, C, T, R>(self, input_string: S, pad_char: C, times: T, result_string: R) -> Self
     where
-        S: IntoWoql2,
-        C: IntoWoql2,
-        T: IntoWoql2, // Should resolve to Integer DataValue
-        R: IntoWoql2,
+        S: StringInput,
+        C: StringInput,
+        T: NumericInput, // Should resolve to Integer DataValue
+        R: StringInput,
     {
         let pad_query = Woql2Query::Pad(Woql2Pad {
             string: input_string.into_woql2_data_value(),
@@ -475,8 +475,8 @@ impl WoqlBuilder {
     /// Splits `input_string` by `pattern`, binding the resulting list to `result_list`.
     pub fn split<S, P, L>(self, input_string: S, pattern: P, result_list: L) -> Self
     where
-        S: IntoWoql2,
-        P: IntoWoql2,
+        S: StringInput,
+        P: StringInput,
         L: IntoWoql2, // Should resolve to List DataValue (usually a Var)
     {
         let split_query = Woql2Query::Split(Woql2Split {
@@ -491,8 +491,8 @@ impl WoqlBuilder {
     pub fn join<L, S, R>(self, input_list: L, separator: S, result_string: R) -> Self
     where
         L: IntoWoql2, // Should resolve to List DataValue
-        S: IntoWoql2,
-        R: IntoWoql2,
+        S: StringInput,
+        R: StringInput,
     {
         let list_data_value = input_list.into_woql2_data_value();
 
@@ -519,7 +519,7 @@ impl WoqlBuilder {
     pub fn concatenate<L, R>(self, input_list: L, result_string: R) -> Self
     where
         L: IntoWoql2, // Should resolve to List DataValue
-        R: IntoWoql2,
+        R: StringInput,
     {
         let list_data_value = input_list.into_woql2_data_value();
 
@@ -544,7 +544,7 @@ impl WoqlBuilder {
     pub fn concat<L, R>(self, input_list: L, result_string: R) -> Self
     where
         L: IntoWoql2,
-        R: IntoWoql2,
+        R: StringInput,
     {
         self.concatenate(input_list, result_string)
     }
@@ -560,11 +560,11 @@ impl WoqlBuilder {
         substring: Sub,
     ) -> Self
     where
-        S: IntoWoql2,
-        B: IntoWoql2, // Should resolve to Integer DataValue
-        L: IntoWoql2, // Should resolve to Integer DataValue
-        A: IntoWoql2, // Should resolve to Integer DataValue
-        Sub: IntoWoql2,
+        S: StringInput,
+        B: NumericInput, // Should resolve to Integer DataValue
+        L: NumericInput, // Should resolve to Integer DataValue
+        A: NumericInput, // Should resolve to Integer DataValue
+        Sub: StringInput,
     {
         let sub_query = Woql2Query::Substring(Woql2Substring {
             string: input_string.into_woql2_data_value(),
@@ -580,8 +580,8 @@ impl WoqlBuilder {
     /// Note: WOQL uses PCRE style regex.
     pub fn regexp<P, S, R>(self, pattern: P, input_string: S, result_list: Option<R>) -> Self
     where
-        P: IntoWoql2,
-        S: IntoWoql2,
+        P: StringInput,
+        S: StringInput,
         R: IntoWoql2, // Should resolve to List DataValue
     {
         let regexp_query = Woql2Query::Regexp(Woql2Regexp {
@@ -596,9 +596,9 @@ impl WoqlBuilder {
     /// The similarity score is a number between -1 and 1.
     pub fn like<L, R, Sim>(self, left: L, right: R, similarity: Sim) -> Self
     where
-        L: IntoWoql2,
-        R: IntoWoql2,
-        Sim: IntoWoql2, // Should resolve to Float DataValue
+        L: StringInput,
+        R: StringInput,
+        Sim: NumericInput, // Should resolve to Float DataValue
     {
         let like_query = Woql2Query::Like(Woql2Like {
             left: left.into_woql2_data_value(),
@@ -658,6 +658,25 @@ impl WoqlBuilder {
 
     /// Inserts `document_value` into the database.
     /// Optionally binds the IRI of the inserted document to `new_identifier_var`.
+    ///
+    /// `document_value` is usually a variable or a
+    /// [`JsonLiteral`](crate::value::JsonLiteral), whose `"v:Name"` strings are
+    /// substituted with variables:
+    ///
+    /// ```
+    /// # use terminusdb_woql_builder::prelude::*;
+    /// let (person, friend) = vars!("Person", "Friend");
+    /// let query = WoqlBuilder::new()
+    ///     .triple(friend, "name", string_literal("Bob"))
+    ///     .insert_document(
+    ///         JsonLiteral::try_from(serde_json::json!({
+    ///             "@type": "Person", "name": "Alice", "friend": "v:Friend"
+    ///         }))?,
+    ///         Some(person),
+    ///     )
+    ///     .finalize();
+    /// # Ok::<(), JsonLiteralError>(())
+    /// ```
     pub fn insert_document<DocVal, NewIdVar>(
         self,
         document_value: DocVal,
@@ -787,6 +806,20 @@ impl WoqlBuilder {
     /// Groups results of `subquery` by the `group_vars`.
     /// The `template` defines the structure of the output for each group.
     /// Binds the resulting list of grouped templates to `grouped_result_var`.
+    ///
+    /// Combine with [`length`](Self::length) or [`sum`](Self::sum) to aggregate
+    /// each group:
+    ///
+    /// ```
+    /// # use terminusdb_woql_builder::prelude::*;
+    /// let (person, city, people, size) = vars!("Person", "City", "People", "Size");
+    /// // One solution per city, with the number of people living there.
+    /// let query = WoqlBuilder::new()
+    ///     .triple(person.clone(), "city", city.clone())
+    ///     .group_by(person, [city], people.clone())
+    ///     .and([WoqlBuilder::length(people, size)])
+    ///     .finalize();
+    /// ```
     pub fn group_by<Template, GroupVar, ResultVar>(
         self,
         template: Template,
@@ -871,12 +904,35 @@ impl WoqlBuilder {
 
     /// Orders the results of the current query.
     /// Takes an iterator of tuples `(Var, Order)`, where `Order` is `woql2::order::Order` (`Asc` or `Desc`).
+    /// Earlier keys take precedence; later keys break ties.
+    ///
+    /// The ordering is applied beneath any `select`, `limit` or `start` already
+    /// on the builder, so it can still see unselected variables and pagination
+    /// always pages through the sorted results:
+    ///
+    /// ```
+    /// use terminusdb_woql_builder::prelude::*;
+    /// use terminusdb_woql2::prelude::Query;
+    ///
+    /// let (name, age) = vars!("Name", "Age");
+    /// let query = WoqlBuilder::new()
+    ///     .triple(name.clone(), "age", age.clone())
+    ///     .select([name.clone()])
+    ///     .start(20)
+    ///     .limit(10)
+    ///     .order_by([(age, Order::Desc), (name, Order::Asc)])
+    ///     .finalize();
+    ///
+    /// // Limit(Start(Select(OrderBy(Triple))))
+    /// let Query::Limit(limit) = query else { panic!() };
+    /// let Query::Start(start) = *limit.query else { panic!() };
+    /// let Query::Select(select) = *start.query else { panic!() };
+    /// assert!(matches!(*select.query, Query::OrderBy(_)));
+    /// ```
     pub fn order_by<OrdVar>(self, ordering: impl IntoIterator<Item = (OrdVar, Woql2Order)>) -> Self
     where
         OrdVar: Into<Var>,
     {
-        let final_subquery = self.finalize(); // The query to order
-
         let order_templates: Vec<Woql2OrderTemplate> = ordering
             .into_iter()
             .map(|(var, order)| Woql2OrderTemplate {
@@ -885,14 +941,8 @@ impl WoqlBuilder {
             })
             .collect();
 
-        let order_by_query = Woql2Query::OrderBy(Woql2OrderBy {
-            ordering: order_templates,
-            query: Box::new(final_subquery),
-        });
-
-        // OrderBy replaces the current query context
         WoqlBuilder {
-            query: Some(order_by_query),
+            query: Some(order_beneath_projection(self.finalize(), order_templates)),
         }
     }
 
@@ -978,3 +1028,26 @@ impl WoqlBuilder {
         }
     }
 }
+
+/// Wrap `query` in an `OrderBy`, below any `Select`, `Limit` or `Start` at its
+/// top, so those keep applying to the ordered solutions.
+fn order_beneath_projection(query: Woql2Query, ordering: Vec<Woql2OrderTemplate>) -> Woql2Query {
+    match query {
+        Woql2Query::Select(Woql2Select { variables, query }) => Woql2Query::Select(Woql2Select {
+            variables,
+            query: Box::new(order_beneath_projection(*query, ordering)),
+        }),
+        Woql2Query::Limit(Woql2Limit { limit, query }) => Woql2Query::Limit(Woql2Limit {
+            limit,
+            query: Box::new(order_beneath_projection(*query, ordering)),
+        }),
+        Woql2Query::Start(Woql2Start { start, query }) => Woql2Query::Start(Woql2Start {
+            start,
+            query: Box::new(order_beneath_projection(*query, ordering)),
+        }),
+        query => Woql2Query::OrderBy(Woql2OrderBy {
+            ordering,
+            query: Box::new(query),
+        }),
+    }
+}
@@ -1,5 +1,5 @@
 //! Represents WOQL arithmetic expressions for use with the builder.
-use crate::value::Var; // Only need Var specifically here?
+use crate::value::{NumericType, Var};
 use decimal_rs::Decimal;
 use terminusdb_woql2::expression::{
     ArithmeticExpression as Woql2ArithmeticExpression, ArithmeticValue as Woql2ArithmeticValue,
@@ -115,8 +115,9 @@ where
 
 // Implement `Into<ArithmeticExpression>` for basic types
 
-impl From<Var> for ArithmeticExpression {
-    fn from(v: Var) -> Self {
+// Only untyped and numeric variables can take part in arithmetic.
+impl<T: NumericType> From<Var<T>> for ArithmeticExpression {
+    fn from(v: Var<T>) -> Self {
         ArithmeticExpression::Var(v.name().to_string())
     }
 }
@@ -31,6 +31,11 @@ pub mod prelude {
 
     // Value items
     pub use crate::value::{list, node, string_literal, IntoWoql2, Var, WoqlInput};
+    pub use crate::value::{JsonLiteral, JsonLiteralError};
+    pub use crate::value::{NumericInput, NumericType, StringInput, StringType, Untyped};
+
+    // Sort direction for `WoqlBuilder::order_by`
+    pub use terminusdb_woql2::prelude::Order;
 
     // Make the vars! macro available via the prelude
     pub use crate::vars;
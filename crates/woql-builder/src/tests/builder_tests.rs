@@ -10,6 +10,7 @@ use terminusdb_schema::XSDAnySimpleType;
 // Only import Query types used in assertions
 use terminusdb_woql2::prelude::{
     DataValue,
+    DictionaryTemplate,
     NodeValue,
     Query as Woql2Query,
     // Import types for new tests
@@ -965,6 +966,122 @@ fn test_insert_document_no_id() {
     }
 }
 
+#[test]
+fn test_insert_json_document_literal() {
+    let document = JsonLiteral::try_from(serde_json::json!({
+        "@type": "Person",
+        "name": "Alice",
+        "age": 42,
+        "friend": "v:Friend",
+        "nickname": null,
+    }))
+    .unwrap();
+    let builder = WoqlBuilder::new().insert_document(document, Some(Var::new("NewID")));
+    let final_query = builder.finalize();
+    match final_query {
+        Woql2Query::InsertDocument(insert_q) => {
+            let Woql2Value::Dictionary(dict) = insert_q.document else {
+                panic!("Expected dictionary, found {:?}", insert_q.document);
+            };
+            let field = |name: &str| {
+                dict.data
+                    .iter()
+                    .find(|pair| pair.field == name)
+                    .map(|pair| pair.value.clone())
+            };
+            assert_eq!(dict.data.len(), 4);
+            assert_eq!(
+                field("@type"),
+                Some(Woql2Value::Data(XSDAnySimpleType::String(
+                    "Person".to_string()
+                )))
+            );
+            assert_eq!(
+                field("age"),
+                Some(Woql2Value::Data(XSDAnySimpleType::Integer(42)))
+            );
+            assert_eq!(
+                field("friend"),
+                Some(Woql2Value::Variable("Friend".to_string()))
+            );
+            assert!(field("nickname").is_none());
+        }
+        _ => panic!("Expected InsertDocument query, found {:?}", final_query),
+    }
+}
+
+#[test]
+fn test_json_literal_rejects_null_outside_fields() {
+    assert_eq!(
+        JsonLiteral::try_from(serde_json::Value::Null),
+        Err(JsonLiteralError::Null)
+    );
+    assert_eq!(
+        JsonLiteral::try_from(serde_json::json!({"@type": "Person", "tags": ["a", null]})),
+        Err(JsonLiteralError::Null)
+    );
+    // Nested null fields are dropped like top-level ones.
+    let literal = JsonLiteral::try_from(serde_json::json!({"address": {"street": null}})).unwrap();
+    let Woql2Value::Dictionary(dict) = literal.into_woql2_value() else {
+        panic!("Expected dictionary");
+    };
+    assert_eq!(
+        dict.data[0].value,
+        Woql2Value::Dictionary(DictionaryTemplate { data: vec![] })
+    );
+}
+
+#[test]
+fn test_json_literal_documents_as_data_and_node() {
+    let document = serde_json::json!({"@id": "Person/alice", "name": "Alice"});
+
+    let data = JsonLiteral::try_from(document.clone())
+        .unwrap()
+        .into_woql2_data_value();
+    assert_eq!(
+        data,
+        DataValue::Data(XSDAnySimpleType::String(document.to_string()))
+    );
+
+    let node = JsonLiteral::try_from(document)
+        .unwrap()
+        .into_woql2_node_value();
+    assert_eq!(node, NodeValue::Node("Person/alice".to_string()));
+
+    let node = JsonLiteral::try_from(serde_json::json!({"@id": "v:Person"}))
+        .unwrap()
+        .into_woql2_node_value();
+    assert_eq!(node, NodeValue::Variable("Person".to_string()));
+}
+
+#[test]
+fn test_match_then_mutate_documents() {
+    let (person, doc) = vars!("Person", "Doc");
+    let builder = WoqlBuilder::new()
+        .triple(person.clone(), "rdf:type", "@schema:Person")
+        .read_document(person.clone(), doc.clone())
+        .update_document(doc, None::<Var>)
+        .delete_document(person);
+
+    match builder.finalize() {
+        Woql2Query::And(and_q) => {
+            let kinds: Vec<&str> = and_q
+                .and
+                .iter()
+                .map(|q| match q {
+                    Woql2Query::Triple(_) => "triple",
+                    Woql2Query::ReadDocument(_) => "read",
+                    Woql2Query::UpdateDocument(_) => "update",
+                    Woql2Query::DeleteDocument(_) => "delete",
+                    _ => "other",
+                })
+                .collect();
+            assert_eq!(kinds, vec!["triple", "read", "update", "delete"]);
+        }
+        other => panic!("Expected And query, found {:?}", other),
+    }
+}
+
 #[test]
 fn test_update_document() {
     let (doc_val_var, updated_id_var) = vars!("UpdatedDoc", "UpdatedID");
@@ -1302,6 +1419,46 @@ fn test_order_by() {
     }
 }
 
+#[test]
+fn test_order_by_after_select_and_pagination() {
+    let (name_var, age_var) = vars!("Name", "Age");
+    let builder = WoqlBuilder::new()
+        .triple(name_var.clone(), "age", age_var.clone())
+        .select(vec![name_var.clone()])
+        .start(20)
+        .limit(10)
+        .order_by(vec![
+            (age_var.clone(), Order::Desc),
+            (name_var.clone(), Order::Asc),
+        ]);
+    let final_query = builder.finalize();
+
+    // Expect Limit(Start(Select(OrderBy(Triple)))): the page is taken from the
+    // sorted results, and ordering can use the unselected Age
+    let Woql2Query::Limit(limit_q) = final_query else {
+        panic!("Expected Limit query");
+    };
+    assert_eq!(limit_q.limit, 10);
+    let Woql2Query::Start(start_q) = *limit_q.query else {
+        panic!("Expected Start query");
+    };
+    assert_eq!(start_q.start, 20);
+    let Woql2Query::Select(select_q) = *start_q.query else {
+        panic!("Expected Select query");
+    };
+    assert_eq!(select_q.variables, vec!["Name".to_string()]);
+    let Woql2Query::OrderBy(ob_q) = *select_q.query else {
+        panic!("Expected OrderBy query");
+    };
+    let keys: Vec<_> = ob_q
+        .ordering
+        .iter()
+        .map(|o| (o.variable.as_str(), o.order.clone()))
+        .collect();
+    assert_eq!(keys, vec![("Age", Order::Desc), ("Name", Order::Asc)]);
+    assert!(matches!(*ob_q.query, Woql2Query::Triple(_)));
+}
+
 fn print_query_json(query: &Woql2Query) {
     println!("{}", serde_json::to_string_pretty(query).unwrap());
 }
@@ -1,3 +1,4 @@
 // This file declares the modules within the tests directory
 pub mod builder_tests;
+pub mod typed_var_tests;
 pub mod value_tests;
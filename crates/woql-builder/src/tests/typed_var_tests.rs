@@ -0,0 +1,81 @@
+use crate::prelude::*;
+use serde_json::json;
+use std::collections::HashMap;
+use terminusdb_woql2::prelude::{DataValue, Query as Woql2Query};
+
+fn binding(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+#[test]
+fn test_typed_var_behaves_like_untyped() {
+    let age: Var<i64> = Var::typed("Age");
+    assert_eq!(age.name(), "Age");
+    assert_eq!(age.full_name(), "v:Age");
+    assert_eq!(age.untyped(), Var::new("Age"));
+}
+
+#[test]
+fn test_numeric_var_in_arithmetic() {
+    let (price, qty): (Var<f64>, Var<u64>) = (Var::typed("Price"), Var::typed("Qty"));
+    let total: Var<f64> = Var::typed("Total");
+
+    let query = WoqlBuilder::new().eval(times(price, qty), total).finalize();
+
+    assert!(matches!(query, Woql2Query::Eval(_)));
+}
+
+#[test]
+fn test_string_var_in_string_ops() {
+    let name: Var<String> = Var::typed("Name");
+    let lower: Var<String> = Var::typed("Lower");
+
+    match WoqlBuilder::new().lower(name, lower).finalize() {
+        Woql2Query::Lower(q) => {
+            assert!(matches!(q.mixed, DataValue::Variable(v) if v == "Name"));
+            assert!(matches!(q.lower, DataValue::Variable(v) if v == "Lower"));
+        }
+        other => panic!("Expected Lower query, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_mixed_typed_arguments() {
+    let text: Var<String> = Var::typed("Text");
+    let start: Var<u64> = Var::typed("Start");
+
+    let query = WoqlBuilder::new()
+        .substring(text, start, 3u64, Var::new("After"), Var::new("Sub"))
+        .finalize();
+
+    assert!(matches!(query, Woql2Query::Substring(_)));
+}
+
+#[test]
+fn test_extract_unwraps_typed_literals() {
+    let row = binding(&[
+        ("Age", json!({"@type": "xsd:decimal", "@value": 42})),
+        ("Name", json!({"@type": "xsd:string", "@value": "Alice"})),
+        ("Person", json!("Person/alice")),
+    ]);
+
+    assert_eq!(Var::<i64>::typed("Age").extract(&row).unwrap(), 42);
+    assert_eq!(Var::<String>::typed("Name").extract(&row).unwrap(), "Alice");
+    assert_eq!(
+        Var::<String>::typed("Person").extract(&row).unwrap(),
+        "Person/alice"
+    );
+}
+
+#[test]
+fn test_extract_reports_unbound_and_mismatched() {
+    let row = binding(&[("Name", json!({"@type": "xsd:string", "@value": "Alice"}))]);
+
+    let missing = Var::<String>::typed("Missing").extract(&row).unwrap_err();
+    assert!(missing.to_string().contains("Missing"));
+
+    assert!(Var::<i64>::typed("Name").extract(&row).is_err());
+}
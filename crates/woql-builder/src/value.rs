@@ -1,3 +1,9 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
 use std::ops::Deref;
 use terminusdb_woql2::value::Value as Woql2Value;
 // Import XSDAnySimpleType for literals
@@ -6,7 +12,7 @@ use terminusdb_schema::XSDAnySimpleType;
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use decimal_rs::Decimal; // Import the Decimal type
 use std::str::FromStr;
-use terminusdb_woql2::prelude::{DataValue, NodeValue}; // Import FromStr for Decimal parsing // Import chrono types for datetime support
+use terminusdb_woql2::prelude::{DataValue, DictionaryTemplate, FieldValuePair, NodeValue}; // Import FromStr for Decimal parsing // Import chrono types for datetime support
 
 /// Represents an input value for WOQL builder functions.
 /// This allows functions to accept variables, IRIs (as strings), or literals easily.
@@ -25,15 +31,49 @@ pub enum WoqlInput {
 }
 
 /// Represents a WOQL variable.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Var {
+///
+/// The type parameter records what the variable is expected to hold. Plain
+/// `Var::new` creates an [`Untyped`] variable that is accepted everywhere, as
+/// before. [`Var::typed`] pins a Rust type instead, which restricts where the
+/// variable may be used and lets [`Var::extract`] deserialize its binding:
+///
+/// ```
+/// # use terminusdb_woql_builder::prelude::*;
+/// let age: Var<i64> = Var::typed("Age");
+/// let next: Var<i64> = Var::typed("Next");
+/// let query = WoqlBuilder::new().eval(plus(age, 1u64), next).finalize();
+/// ```
+///
+/// Passing a string variable to an arithmetic helper does not compile:
+///
+/// ```compile_fail
+/// # use terminusdb_woql_builder::prelude::*;
+/// let name: Var<String> = Var::typed("Name");
+/// let expr = plus(name, 1u64);
+/// ```
+pub struct Var<T = Untyped> {
     name: String,
+    _type: PhantomData<fn() -> T>,
 }
 
+/// Type parameter of variables created without a Rust type; see [`Var`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Untyped;
+
 impl Var {
     /// Creates a new variable reference.
     pub fn new(name: impl Into<String>) -> Self {
-        Var { name: name.into() }
+        Var::typed(name)
+    }
+}
+
+impl<T> Var<T> {
+    /// Creates a new variable reference expected to hold a `T`.
+    pub fn typed(name: impl Into<String>) -> Self {
+        Var {
+            name: name.into(),
+            _type: PhantomData,
+        }
     }
 
     /// Returns the name of the variable (without the "v:" prefix).
@@ -45,13 +85,82 @@ impl Var {
     pub fn full_name(&self) -> String {
         format!("v:{}", self.name)
     }
+
+    /// Drops the type information, e.g. to pass the variable to `select`.
+    pub fn untyped(&self) -> Var {
+        Var::new(self.name.clone())
+    }
+}
+
+impl<T: DeserializeOwned> Var<T> {
+    /// Deserializes this variable's value from one row of query bindings.
+    ///
+    /// Typed literals (`{"@type": "xsd:...", "@value": ...}`) are unwrapped
+    /// first; node bindings deserialize from their IRI string.
+    pub fn extract(&self, binding: &HashMap<String, JsonValue>) -> serde_json::Result<T> {
+        let value = binding.get(&self.name).ok_or_else(|| {
+            serde::de::Error::custom(format!("variable '{}' is not bound", self.name))
+        })?;
+        let value = match value {
+            JsonValue::Object(literal) if literal.contains_key("@value") => &literal["@value"],
+            other => other,
+        };
+        T::deserialize(value)
+    }
+}
+
+// Manual impls so that `T` itself needs none of these traits.
+
+impl<T> Clone for Var<T> {
+    fn clone(&self) -> Self {
+        Var::typed(self.name.clone())
+    }
+}
+
+impl<T> fmt::Debug for Var<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Var").field("name", &self.name).finish()
+    }
 }
 
+impl<T> PartialEq for Var<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<T> Eq for Var<T> {}
+
+impl<T> Hash for Var<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+// --- Variable Type Markers ---
+
+/// Rust types that WOQL arithmetic operates on.
+pub trait NumericType {}
+
+/// Rust types that WOQL string operations operate on.
+pub trait StringType {}
+
+impl NumericType for Untyped {}
+impl StringType for Untyped {}
+impl StringType for String {}
+
+macro_rules! impl_numeric_type {
+    ($($t:ty),*) => {
+        $(impl NumericType for $t {})*
+    };
+}
+impl_numeric_type!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64, Decimal);
+
 // --- Input Type Conversions ---
 
-impl From<Var> for WoqlInput {
-    fn from(v: Var) -> Self {
-        WoqlInput::Variable(v)
+impl<T> From<Var<T>> for WoqlInput {
+    fn from(v: Var<T>) -> Self {
+        WoqlInput::Variable(v.untyped())
     }
 }
 
@@ -79,7 +188,7 @@ impl From<bool> for WoqlInput {
     }
 }
 
-impl Deref for Var {
+impl<T> Deref for Var<T> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -307,7 +416,19 @@ macro_rules! impl_into_woql2_for {
     };
 }
 
-impl_into_woql2_for!(Var, &str, String, bool, i8, u8, i16, u16, i32, u32, i64, u64);
+impl_into_woql2_for!(&str, String, bool, i8, u8, i16, u16, i32, u32, i64, u64);
+
+impl<T> IntoWoql2 for Var<T> {
+    fn into_woql2_value(self) -> Woql2Value {
+        Woql2Value::Variable(self.name)
+    }
+    fn into_woql2_node_value(self) -> NodeValue {
+        NodeValue::Variable(self.name)
+    }
+    fn into_woql2_data_value(self) -> DataValue {
+        DataValue::Variable(self.name)
+    }
+}
 
 // Add IntoWoql2 implementation for Vec<T>
 impl<T> IntoWoql2 for Vec<T>
@@ -352,6 +473,156 @@ impl IntoWoql2 for f64 {
     }
 }
 
+/// A JSON document literal, e.g. for `insert_document` / `update_document`.
+///
+/// Built with `JsonLiteral::try_from(json!(...))`: objects become dictionary
+/// templates (`null` fields are dropped), arrays become lists and scalars
+/// become literals. Strings follow the builder's convention: `"v:Name"` is a
+/// variable, anything else a string literal. Where a data value is expected
+/// an object is passed as its JSON text, and where a node is expected as its
+/// `@id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonLiteral(Woql2Value);
+
+/// Why a JSON value cannot be used as a [`JsonLiteral`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonLiteralError {
+    /// A `null` outside an object field (top level or list element).
+    Null,
+    /// A number with no `xsd` representation.
+    Number(String),
+}
+
+impl fmt::Display for JsonLiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonLiteralError::Null => {
+                write!(f, "JSON null is only allowed as an object field value")
+            }
+            JsonLiteralError::Number(n) => {
+                write!(f, "JSON number {} has no xsd representation", n)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonLiteralError {}
+
+impl TryFrom<JsonValue> for JsonLiteral {
+    type Error = JsonLiteralError;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        json_to_woql2(value).map(JsonLiteral)
+    }
+}
+
+fn json_to_woql2(value: JsonValue) -> Result<Woql2Value, JsonLiteralError> {
+    Ok(match value {
+        JsonValue::Object(map) => Woql2Value::Dictionary(DictionaryTemplate {
+            data: map
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(field, value)| {
+                    Ok(FieldValuePair {
+                        field,
+                        value: json_to_woql2(value)?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        }),
+        JsonValue::Array(items) => Woql2Value::List(
+            items
+                .into_iter()
+                .map(json_to_woql2)
+                .collect::<Result<_, _>>()?,
+        ),
+        JsonValue::String(s) => match s.strip_prefix("v:") {
+            Some(name) if !name.is_empty() => Woql2Value::Variable(name.to_string()),
+            _ => Woql2Value::Data(XSDAnySimpleType::String(s)),
+        },
+        JsonValue::Bool(b) => Woql2Value::Data(XSDAnySimpleType::Boolean(b)),
+        JsonValue::Number(n) => Woql2Value::Data(json_number_to_xsd(&n)?),
+        JsonValue::Null => return Err(JsonLiteralError::Null),
+    })
+}
+
+fn json_number_to_xsd(n: &serde_json::Number) -> Result<XSDAnySimpleType, JsonLiteralError> {
+    if let Some(i) = n.as_i64() {
+        Ok(XSDAnySimpleType::Integer(i))
+    } else if let Some(u) = n.as_u64().and_then(|u| usize::try_from(u).ok()) {
+        Ok(XSDAnySimpleType::UnsignedInt(u))
+    } else {
+        n.as_f64()
+            .filter(|f| f.is_finite())
+            .map(XSDAnySimpleType::Float)
+            .ok_or_else(|| JsonLiteralError::Number(n.to_string()))
+    }
+}
+
+/// A dictionary has no `DataValue` form, so in data position it becomes its
+/// JSON text as a string literal.
+fn woql2_to_data_value(value: Woql2Value) -> DataValue {
+    match value {
+        Woql2Value::Data(data) => DataValue::Data(data),
+        Woql2Value::Variable(name) => DataValue::Variable(name),
+        Woql2Value::List(items) => {
+            DataValue::List(items.into_iter().map(woql2_to_data_value).collect())
+        }
+        dictionary @ Woql2Value::Dictionary(_) => DataValue::Data(XSDAnySimpleType::String(
+            woql2_to_json(dictionary).to_string(),
+        )),
+        Woql2Value::Node(iri) => DataValue::Data(XSDAnySimpleType::String(iri)),
+    }
+}
+
+/// The JSON a [`JsonLiteral`] was built from, minus its dropped `null` fields.
+fn woql2_to_json(value: Woql2Value) -> JsonValue {
+    match value {
+        Woql2Value::Dictionary(dict) => JsonValue::Object(
+            dict.data
+                .into_iter()
+                .map(|pair| (pair.field, woql2_to_json(pair.value)))
+                .collect(),
+        ),
+        Woql2Value::List(items) => JsonValue::Array(items.into_iter().map(woql2_to_json).collect()),
+        Woql2Value::Variable(name) => JsonValue::String(format!("v:{}", name)),
+        Woql2Value::Node(iri) => JsonValue::String(iri),
+        Woql2Value::Data(XSDAnySimpleType::Boolean(b)) => JsonValue::Bool(b),
+        Woql2Value::Data(XSDAnySimpleType::Integer(i)) => JsonValue::from(i),
+        Woql2Value::Data(XSDAnySimpleType::UnsignedInt(u)) => JsonValue::from(u),
+        Woql2Value::Data(XSDAnySimpleType::Float(f)) => JsonValue::from(f),
+        Woql2Value::Data(XSDAnySimpleType::String(s)) => JsonValue::String(s),
+        // `json_to_woql2` produces no other literals.
+        Woql2Value::Data(other) => unreachable!("{:?} in a JSON literal", other),
+    }
+}
+
+impl IntoWoql2 for JsonLiteral {
+    fn into_woql2_value(self) -> Woql2Value {
+        self.0
+    }
+
+    /// A string is an IRI and a document stands for its `@id`. Other
+    /// literals, and documents without an `@id`, do not name a node.
+    fn into_woql2_node_value(self) -> NodeValue {
+        match self.0 {
+            Woql2Value::Variable(name) => NodeValue::Variable(name),
+            Woql2Value::Data(XSDAnySimpleType::String(iri)) => NodeValue::Node(iri),
+            Woql2Value::Dictionary(dict) => {
+                match dict.data.into_iter().find(|pair| pair.field == "@id") {
+                    Some(pair) => JsonLiteral(pair.value).into_woql2_node_value(),
+                    None => panic!("Cannot convert a JSON document without @id into a NodeValue"),
+                }
+            }
+            other => panic!("Cannot convert JSON literal {:?} into a NodeValue", other),
+        }
+    }
+
+    fn into_woql2_data_value(self) -> DataValue {
+        woql2_to_data_value(self.0)
+    }
+}
+
 impl IntoWoql2 for Decimal {
     fn into_woql2_value(self) -> Woql2Value {
         Woql2Value::Data(XSDAnySimpleType::Decimal(self))
@@ -391,3 +662,28 @@ impl IntoWoql2 for isize {
         Woql2Value::Data(XSDAnySimpleType::Decimal(Decimal::from(self)))
     }
 }
+
+// --- Argument Kinds ---
+
+/// Arguments accepted where WOQL expects a string: string literals, untyped
+/// variables and variables typed with a [`StringType`].
+pub trait StringInput: IntoWoql2 {}
+
+/// Arguments accepted where WOQL expects a number: numeric literals, untyped
+/// variables and variables typed with a [`NumericType`].
+pub trait NumericInput: IntoWoql2 {}
+
+impl StringInput for WoqlInput {}
+impl StringInput for &str {}
+impl StringInput for String {}
+impl<T: StringType> StringInput for Var<T> {}
+
+impl NumericInput for WoqlInput {}
+impl<T: NumericType> NumericInput for Var<T> {}
+
+macro_rules! impl_numeric_input {
+    ($($t:ty),*) => {
+        $(impl NumericInput for $t {})*
+    };
+}
+impl_numeric_input!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64, Decimal);
@@ -0,0 +1,1301 @@
+//! Turns parsed [`Expr`] trees into woql2 queries.
+//!
+//! Each function knows the woql2 type of every argument position, which is
+//! how the same text means different things in different places: `"Person/1"`
+//! is a node where a `NodeValue` is expected and string data where a
+//! `DataValue` is, and `plus(...)` is arithmetic in `eval` but a path
+//! quantifier in `path`. The accepted forms mirror what
+//! [`ToDSL`](terminusdb_woql2::dsl::ToDSL) renders, so rendering a query and
+//! parsing it back gives the same query.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use decimal_rs::Decimal;
+use terminusdb_schema::{GraphType, XSDAnySimpleType};
+use terminusdb_woql2::{
+    collection::{
+        Dot, Length, ListToSet, Member, SetDifference, SetIntersection, SetMember, SetUnion, Slice,
+        Sum,
+    },
+    compare::{Equals, Greater, Gte, IsA, Less, Lte, Subsumption, TypeOf, Typecast},
+    control::{self, Distinct, If, Immediately, Once, Pin, Select, Using, WoqlOptional},
+    document::{DeleteDocument, InsertDocument, ReadDocument, UpdateDocument},
+    dsl::is_node_literal,
+    expression::{
+        ArithmeticExpression, ArithmeticValue, Div, Divide, Exp, Floor, Minus, Plus, Times,
+    },
+    get::{Column, FormatType, Get, Indicator, QueryResource, Source},
+    interval::{
+        DateDuration, DayAfter, DayBefore, Interval, IntervalDurationEnd, IntervalRelation,
+        IntervalRelationTyped, IntervalStartDuration, IsoWeek, MonthEndDate, MonthEndDates,
+        MonthStartDate, MonthStartDates, Weekday, WeekdaySundayStart,
+    },
+    misc::{
+        Collect, Comment, Count, HashKey, InRange, LexicalKey, Limit, RandomKey, RangeMax,
+        RangeMin, Sequence, Size, Start, TripleCount,
+    },
+    order::{GroupBy, Order, OrderBy, OrderTemplate},
+    path::{
+        InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar,
+        PathTimes,
+    },
+    query::{self, And, Eval, Not, Or, Path, Query, True},
+    string::{Concatenate, Join, Like, Lower, Pad, Regexp, Split, Substring, Trim, Upper},
+    triple::{
+        AddData, AddLink, AddTriple, AddedData, AddedLink, AddedTriple, Data, DeleteLink,
+        DeleteTriple, DeletedLink, DeletedTriple, Link, Triple, TripleNext, TriplePrevious,
+        TripleSlice, TripleSliceRev,
+    },
+    value::{DataValue, DictionaryTemplate, FieldValuePair, ListOrVariable, NodeValue, Value},
+};
+
+use crate::error::{ParseError, ParseResult};
+use crate::parser::{Call, Expr};
+
+/// Convert a top-level expression, which must be a query
+pub(crate) fn query(expr: &Expr) -> ParseResult<Query> {
+    match expr {
+        Expr::Call(call) => call_query(call),
+        other => Err(ParseError::ParseError {
+            position: 0,
+            message: format!("expected a query, found {}", describe(other)),
+        }),
+    }
+}
+
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Variable(name) => format!("variable ${}", name),
+        Expr::String(s) => format!("string {:?}", s),
+        Expr::Number(n) => format!("number {}", n),
+        Expr::Bool(b) => format!("boolean {}", b),
+        Expr::Null => "null".to_string(),
+        Expr::List(_) => "a list".to_string(),
+        Expr::Dict(_) => "a dictionary".to_string(),
+        Expr::Call(call) => format!("{}(...)", call.name),
+    }
+}
+
+/// The arguments of one call, converted on demand by position
+struct Args<'a> {
+    call: &'a Call,
+}
+
+impl<'a> Args<'a> {
+    /// Check that there are at least `min` positional arguments, at most
+    /// `max` arguments in total, and that named arguments are among `names`
+    fn new(call: &'a Call, min: usize, max: usize, names: &[&str]) -> ParseResult<Self> {
+        let positional = call.args.len();
+        let total = positional + call.named.len();
+        if positional < min || total > max {
+            let expected = if min == max {
+                min.to_string()
+            } else if max == usize::MAX {
+                format!("at least {}", min)
+            } else {
+                format!("{} to {}", min, max)
+            };
+            return Err(ParseError::InvalidArgumentCount {
+                function: call.name.clone(),
+                expected,
+                got: total,
+            });
+        }
+        if let Some((name, _)) = call
+            .named
+            .iter()
+            .find(|(name, _)| !names.contains(&name.as_str()))
+        {
+            return Err(ParseError::InvalidArgument {
+                function: call.name.clone(),
+                message: format!("unknown argument '{}'", name),
+            });
+        }
+        Ok(Args { call })
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::InvalidArgument {
+            function: self.call.name.clone(),
+            message: message.into(),
+        }
+    }
+
+    fn expected(&self, expected: &str, found: &Expr) -> ParseError {
+        self.error(format!("expected {}, found {}", expected, describe(found)))
+    }
+
+    /// A required positional argument; `index` is always below the minimum
+    /// checked in [`Args::new`]
+    fn arg(&self, index: usize) -> &'a Expr {
+        &self.call.args[index]
+    }
+
+    /// An optional argument, given by position or as `name=value`
+    fn optional(&self, index: usize, name: &str) -> ParseResult<Option<&'a Expr>> {
+        let named = self
+            .call
+            .named
+            .iter()
+            .find(|(arg, _)| arg == name)
+            .map(|(_, expr)| expr);
+        match (self.call.args.get(index), named) {
+            (Some(_), Some(_)) => Err(self.error(format!("'{}' is given twice", name))),
+            (positional, named) => Ok(positional.or(named)),
+        }
+    }
+
+    fn query(&self, index: usize) -> ParseResult<Box<Query>> {
+        self.to_query(self.arg(index)).map(Box::new)
+    }
+
+    /// All positional arguments from `from` on, as queries
+    fn queries(&self, from: usize) -> ParseResult<Vec<Query>> {
+        self.call.args[from..]
+            .iter()
+            .map(|expr| self.to_query(expr))
+            .collect()
+    }
+
+    fn to_query(&self, expr: &Expr) -> ParseResult<Query> {
+        match expr {
+            Expr::Call(call) => call_query(call),
+            other => Err(self.expected("a query", other)),
+        }
+    }
+
+    fn value(&self, index: usize) -> ParseResult<Value> {
+        self.to_value(self.arg(index))
+    }
+
+    /// A value: strings read as nodes when they look like one (see
+    /// [`is_node_literal`]); `iri(...)` and `string(...)` say so explicitly
+    fn to_value(&self, expr: &Expr) -> ParseResult<Value> {
+        match expr {
+            Expr::Variable(name) => Ok(Value::Variable(name.clone())),
+            Expr::String(s) if is_node_literal(s) => Ok(Value::Node(s.clone())),
+            Expr::List(items) => Ok(Value::List(
+                items
+                    .iter()
+                    .map(|item| self.to_value(item))
+                    .collect::<ParseResult<_>>()?,
+            )),
+            Expr::Dict(pairs) => {
+                let data = pairs
+                    .iter()
+                    .map(|(field, value)| {
+                        Ok(FieldValuePair {
+                            field: field.clone(),
+                            value: self.to_value(value)?,
+                        })
+                    })
+                    .collect::<ParseResult<BTreeSet<_>>>()?;
+                Ok(Value::Dictionary(DictionaryTemplate { data }))
+            }
+            Expr::Call(call) if call.name == "iri" => Ok(Value::Node(iri(call)?)),
+            other => Ok(Value::Data(self.to_xsd(other)?)),
+        }
+    }
+
+    fn node(&self, index: usize) -> ParseResult<NodeValue> {
+        self.to_node(self.arg(index))
+    }
+
+    fn to_node(&self, expr: &Expr) -> ParseResult<NodeValue> {
+        match expr {
+            Expr::Variable(name) => Ok(NodeValue::Variable(name.clone())),
+            Expr::String(s) => Ok(NodeValue::Node(s.clone())),
+            Expr::Call(call) if call.name == "iri" => Ok(NodeValue::Node(iri(call)?)),
+            other => Err(self.expected("a node or variable", other)),
+        }
+    }
+
+    fn data(&self, index: usize) -> ParseResult<DataValue> {
+        self.to_data(self.arg(index))
+    }
+
+    fn optional_data(&self, index: usize, name: &str) -> ParseResult<Option<DataValue>> {
+        self.optional(index, name)?
+            .map(|expr| self.to_data(expr))
+            .transpose()
+    }
+
+    /// Data: in these positions a quoted string is always string data
+    fn to_data(&self, expr: &Expr) -> ParseResult<DataValue> {
+        match expr {
+            Expr::Variable(name) => Ok(DataValue::Variable(name.clone())),
+            Expr::List(items) => Ok(DataValue::List(self.to_data_list(items)?)),
+            other => Ok(DataValue::Data(self.to_xsd(other)?)),
+        }
+    }
+
+    fn to_data_list(&self, items: &[Expr]) -> ParseResult<Vec<DataValue>> {
+        items.iter().map(|item| self.to_data(item)).collect()
+    }
+
+    fn data_list(&self, index: usize) -> ParseResult<Vec<DataValue>> {
+        match self.arg(index) {
+            Expr::List(items) => self.to_data_list(items),
+            other => Err(self.expected("a list", other)),
+        }
+    }
+
+    /// A literal list is a list; anything else is a variable holding one
+    fn list_or_variable(&self, index: usize) -> ParseResult<ListOrVariable> {
+        match self.arg(index) {
+            Expr::List(items) => Ok(ListOrVariable::List(self.to_data_list(items)?)),
+            other => Ok(ListOrVariable::Variable(self.to_data(other)?)),
+        }
+    }
+
+    fn to_xsd(&self, expr: &Expr) -> ParseResult<XSDAnySimpleType> {
+        match expr {
+            Expr::String(s) => Ok(XSDAnySimpleType::String(s.clone())),
+            Expr::Number(n) => parse_float(n).map(XSDAnySimpleType::Float),
+            Expr::Bool(b) => Ok(XSDAnySimpleType::Boolean(*b)),
+            Expr::Call(call) if call.name == "string" => {
+                let args = Args::new(call, 1, 1, &[])?;
+                Ok(XSDAnySimpleType::String(args.string(0)?))
+            }
+            Expr::Call(call) if call.name == "literal" => literal(call),
+            other => Err(self.expected("a literal", other)),
+        }
+    }
+
+    fn string(&self, index: usize) -> ParseResult<String> {
+        self.to_string_literal(self.arg(index))
+    }
+
+    fn to_string_literal(&self, expr: &Expr) -> ParseResult<String> {
+        match expr {
+            Expr::String(s) => Ok(s.clone()),
+            other => Err(self.expected("a string", other)),
+        }
+    }
+
+    fn u64(&self, index: usize) -> ParseResult<u64> {
+        match self.arg(index) {
+            Expr::Number(n) => n
+                .parse()
+                .map_err(|_| self.error(format!("expected a non-negative integer, found {}", n))),
+            other => Err(self.expected("a non-negative integer", other)),
+        }
+    }
+
+    fn variable_name(&self, expr: &Expr) -> ParseResult<String> {
+        match expr {
+            Expr::Variable(name) => Ok(name.clone()),
+            other => Err(self.expected("a variable", other)),
+        }
+    }
+
+    fn variables(&self, index: usize) -> ParseResult<Vec<String>> {
+        match self.arg(index) {
+            Expr::List(items) => items.iter().map(|item| self.variable_name(item)).collect(),
+            other => Err(self.expected("a list of variables", other)),
+        }
+    }
+
+    fn ordering(&self, index: usize) -> ParseResult<Vec<OrderTemplate>> {
+        let Expr::List(items) = self.arg(index) else {
+            return Err(self.expected("a list of asc(...) / desc(...)", self.arg(index)));
+        };
+        items
+            .iter()
+            .map(|item| {
+                let (order, call) = match item {
+                    Expr::Call(call) if call.name == "asc" => (Order::Asc, call),
+                    Expr::Call(call) if call.name == "desc" => (Order::Desc, call),
+                    other => return Err(self.expected("asc(...) or desc(...)", other)),
+                };
+                let args = Args::new(call, 1, 1, &[])?;
+                Ok(OrderTemplate {
+                    variable: args.variable_name(args.arg(0))?,
+                    order,
+                })
+            })
+            .collect()
+    }
+
+    /// The graph of a triple-shaped operation, `"instance"` when omitted
+    fn graph(&self, index: usize) -> ParseResult<GraphType> {
+        match self.optional(index, "graph")? {
+            None => Ok(GraphType::Instance),
+            Some(Expr::String(s)) if s == "instance" => Ok(GraphType::Instance),
+            Some(Expr::String(s)) if s == "schema" => Ok(GraphType::Schema),
+            Some(other) => Err(self.expected("\"instance\" or \"schema\"", other)),
+        }
+    }
+
+    fn arithmetic(&self, index: usize) -> ParseResult<ArithmeticExpression> {
+        self.to_arithmetic(self.arg(index))
+    }
+
+    fn to_arithmetic(&self, expr: &Expr) -> ParseResult<ArithmeticExpression> {
+        let Expr::Call(call) = expr else {
+            return Ok(ArithmeticExpression::Value(self.to_arithmetic_value(expr)?));
+        };
+        let binary = |call: &Call| -> ParseResult<_> {
+            let args = Args::new(call, 2, 2, &[])?;
+            Ok((Box::new(args.arithmetic(0)?), Box::new(args.arithmetic(1)?)))
+        };
+        Ok(match call.name.as_str() {
+            "plus" => binary(call)
+                .map(|(left, right)| ArithmeticExpression::Plus(Plus { left, right }))?,
+            "minus" => binary(call)
+                .map(|(left, right)| ArithmeticExpression::Minus(Minus { left, right }))?,
+            "times" => binary(call)
+                .map(|(left, right)| ArithmeticExpression::Times(Times { left, right }))?,
+            "divide" => binary(call)
+                .map(|(left, right)| ArithmeticExpression::Divide(Divide { left, right }))?,
+            "div" => {
+                binary(call).map(|(left, right)| ArithmeticExpression::Div(Div { left, right }))?
+            }
+            "exp" => {
+                binary(call).map(|(left, right)| ArithmeticExpression::Exp(Exp { left, right }))?
+            }
+            "floor" => {
+                let args = Args::new(call, 1, 1, &[])?;
+                ArithmeticExpression::Floor(Floor {
+                    argument: Box::new(args.arithmetic(0)?),
+                })
+            }
+            _ => ArithmeticExpression::Value(self.to_arithmetic_value(expr)?),
+        })
+    }
+
+    fn to_arithmetic_value(&self, expr: &Expr) -> ParseResult<ArithmeticValue> {
+        match expr {
+            Expr::Variable(name) => Ok(ArithmeticValue::Variable(name.clone())),
+            other => Ok(ArithmeticValue::Data(self.to_xsd(other)?)),
+        }
+    }
+
+    fn path_pattern(&self, index: usize) -> ParseResult<PathPattern> {
+        self.to_path_pattern(self.arg(index))
+    }
+
+    fn to_path_pattern(&self, expr: &Expr) -> ParseResult<PathPattern> {
+        let Expr::Call(call) = expr else {
+            return Err(self.expected("a path pattern", expr));
+        };
+        let patterns = |call: &Call| -> ParseResult<Vec<PathPattern>> {
+            let args = Args::new(call, 0, usize::MAX, &[])?;
+            call.args
+                .iter()
+                .map(|arg| args.to_path_pattern(arg))
+                .collect()
+        };
+        Ok(match call.name.as_str() {
+            "pred" => {
+                let args = Args::new(call, 0, 1, &[])?;
+                let predicate = args
+                    .call
+                    .args
+                    .first()
+                    .map(|arg| args.to_string_literal(arg));
+                PathPattern::Predicate(PathPredicate {
+                    predicate: predicate.transpose()?,
+                })
+            }
+            "inv" => {
+                let args = Args::new(call, 0, 1, &[])?;
+                let predicate = args
+                    .call
+                    .args
+                    .first()
+                    .map(|arg| args.to_string_literal(arg));
+                PathPattern::InversePredicate(InversePathPredicate {
+                    predicate: predicate.transpose()?,
+                })
+            }
+            "star" => {
+                let args = Args::new(call, 1, 1, &[])?;
+                PathPattern::Star(PathStar {
+                    star: Box::new(args.path_pattern(0)?),
+                })
+            }
+            "plus" => {
+                let args = Args::new(call, 1, 1, &[])?;
+                PathPattern::Plus(PathPlus {
+                    plus: Box::new(args.path_pattern(0)?),
+                })
+            }
+            "times" => {
+                let args = Args::new(call, 3, 3, &[])?;
+                PathPattern::Times(PathTimes {
+                    times: Box::new(args.path_pattern(0)?),
+                    from: args.u64(1)?,
+                    to: args.u64(2)?,
+                })
+            }
+            "seq" => PathPattern::Sequence(PathSequence {
+                sequence: patterns(call)?,
+            }),
+            "or" => PathPattern::Or(PathOr {
+                or: patterns(call)?,
+            }),
+            _ => return Err(self.expected("a path pattern", expr)),
+        })
+    }
+
+    fn columns(&self, index: usize) -> ParseResult<Vec<Column>> {
+        let Expr::List(items) = self.arg(index) else {
+            return Err(self.expected("a list of as(...) columns", self.arg(index)));
+        };
+        items
+            .iter()
+            .map(|item| {
+                let call = match item {
+                    Expr::Call(call) if call.name == "as" => call,
+                    other => return Err(self.expected("as(...)", other)),
+                };
+                let args = Args::new(call, 2, 3, &["type"])?;
+                let indicator = match args.arg(0) {
+                    Expr::Number(_) => Indicator::Index(args.u64(0)?),
+                    _ => Indicator::Name(args.string(0)?),
+                };
+                Ok(Column {
+                    indicator,
+                    variable: args.variable_name(args.arg(1))?,
+                    type_of: args
+                        .optional(2, "type")?
+                        .map(|expr| args.to_string_literal(expr))
+                        .transpose()?,
+                })
+            })
+            .collect()
+    }
+
+    fn resource(&self, index: usize) -> ParseResult<QueryResource> {
+        let call = match self.arg(index) {
+            Expr::Call(call) if call.name == "post" || call.name == "url" => call,
+            other => return Err(self.expected("post(...) or url(...)", other)),
+        };
+        let args = Args::new(call, 1, 2, &["options"])?;
+        let location = args.string(0)?;
+        Ok(QueryResource {
+            source: if call.name == "post" {
+                Source::Post(location)
+            } else {
+                Source::Url(location)
+            },
+            format: FormatType::Csv,
+            options: args
+                .optional(1, "options")?
+                .map(|expr| args.to_json(expr))
+                .transpose()?,
+        })
+    }
+
+    fn to_json(&self, expr: &Expr) -> ParseResult<serde_json::Value> {
+        Ok(match expr {
+            Expr::Null => serde_json::Value::Null,
+            Expr::Bool(b) => serde_json::Value::Bool(*b),
+            Expr::Number(n) => serde_json::Value::Number(
+                serde_json::from_str(n).map_err(|_| self.error(format!("invalid number {}", n)))?,
+            ),
+            Expr::String(s) => serde_json::Value::String(s.clone()),
+            Expr::List(items) => serde_json::Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.to_json(item))
+                    .collect::<ParseResult<_>>()?,
+            ),
+            Expr::Dict(pairs) => serde_json::Value::Object(
+                pairs
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), self.to_json(value)?)))
+                    .collect::<ParseResult<_>>()?,
+            ),
+            other => return Err(self.expected("JSON", other)),
+        })
+    }
+}
+
+/// `iri("...")`: a node that doesn't look like one
+fn iri(call: &Call) -> ParseResult<String> {
+    Args::new(call, 1, 1, &[])?.string(0)
+}
+
+fn parse_float(n: &str) -> ParseResult<f64> {
+    n.parse()
+        .map_err(|_| ParseError::InvalidLiteral(format!("invalid number {}", n)))
+}
+
+/// `literal(value, "xsd:type")`: data of a type other than string, float or
+/// boolean
+fn literal(call: &Call) -> ParseResult<XSDAnySimpleType> {
+    let args = Args::new(call, 2, 2, &[])?;
+    let text = match args.arg(0) {
+        Expr::String(s) | Expr::Number(s) => s.clone(),
+        Expr::Bool(b) => b.to_string(),
+        other => return Err(args.expected("a literal value", other)),
+    };
+    let xsd_type = args.string(1)?;
+    let invalid = || ParseError::InvalidLiteral(format!("{:?} is not a valid {}", text, xsd_type));
+
+    Ok(match xsd_type.as_str() {
+        "xsd:string" => XSDAnySimpleType::String(text),
+        "xsd:boolean" => XSDAnySimpleType::Boolean(text.parse().map_err(|_| invalid())?),
+        "xsd:double" | "xsd:float" => XSDAnySimpleType::Float(text.parse().map_err(|_| invalid())?),
+        "xsd:decimal" => XSDAnySimpleType::Decimal(text.parse::<Decimal>().map_err(|_| invalid())?),
+        "xsd:integer" => XSDAnySimpleType::Integer(text.parse().map_err(|_| invalid())?),
+        "xsd:unsignedInt" => XSDAnySimpleType::UnsignedInt(text.parse().map_err(|_| invalid())?),
+        "xsd:dateTime" => XSDAnySimpleType::DateTime(
+            DateTime::parse_from_rfc3339(&text)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+        ),
+        "xsd:date" => XSDAnySimpleType::Date(text.parse::<NaiveDate>().map_err(|_| invalid())?),
+        "xsd:time" => XSDAnySimpleType::Time(text.parse::<NaiveTime>().map_err(|_| invalid())?),
+        "xsd:hexBinary" => XSDAnySimpleType::HexBinary(text),
+        "xsd:anyURI" => XSDAnySimpleType::URI(text),
+        _ => {
+            return Err(ParseError::InvalidLiteral(format!(
+                "unsupported literal type {}",
+                xsd_type
+            )))
+        }
+    })
+}
+
+/// Shorthand for the many operations whose arguments are all data values
+macro_rules! data_query {
+    ($call:expr, $variant:ident { $($field:ident),+ }) => {{
+        let fields = [$(stringify!($field)),+];
+        let args = Args::new($call, fields.len(), fields.len(), &[])?;
+        let mut index = 0..;
+        Query::$variant($variant {
+            $($field: args.data(index.next().unwrap())?,)+
+        })
+    }};
+}
+
+fn call_query(call: &Call) -> ParseResult<Query> {
+    Ok(match call.name.as_str() {
+        // Logic and control
+        "true" => {
+            Args::new(call, 0, 0, &[])?;
+            Query::True(True {})
+        }
+        "and" => Query::And(And {
+            and: Args::new(call, 0, usize::MAX, &[])?.queries(0)?,
+        }),
+        "or" => Query::Or(Or {
+            or: Args::new(call, 0, usize::MAX, &[])?.queries(0)?,
+        }),
+        "not" => Query::Not(Not {
+            query: Args::new(call, 1, 1, &[])?.query(0)?,
+        }),
+        "opt" | "optional" => Query::WoqlOptional(WoqlOptional {
+            query: Args::new(call, 1, 1, &[])?.query(0)?,
+        }),
+        "once" => Query::Once(Once {
+            query: Args::new(call, 1, 1, &[])?.query(0)?,
+        }),
+        "immediately" => Query::Immediately(Immediately {
+            query: Args::new(call, 1, 1, &[])?.query(0)?,
+        }),
+        "pin" => Query::Pin(Pin {
+            query: Args::new(call, 1, 1, &[])?.query(0)?,
+        }),
+        "if" => {
+            let args = Args::new(call, 3, 3, &[])?;
+            Query::If(If {
+                test: args.query(0)?,
+                then_query: args.query(1)?,
+                else_query: args.query(2)?,
+            })
+        }
+        "select" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Select(Select {
+                variables: args.variables(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "distinct" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Distinct(Distinct {
+                variables: args.variables(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "using" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Using(Using {
+                collection: args.string(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "from" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::From(control::From {
+                graph: args.string(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "into" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Into(control::Into {
+                graph: args.string(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "limit" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Limit(Limit {
+                limit: args.u64(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "start" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Start(Start {
+                start: args.u64(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "order_by" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::OrderBy(OrderBy {
+                ordering: args.ordering(0)?,
+                query: args.query(1)?,
+            })
+        }
+        "group_by" => {
+            let args = Args::new(call, 4, 4, &[])?;
+            Query::GroupBy(GroupBy {
+                group_by: args.variables(0)?,
+                template: args.value(1)?,
+                grouped_value: args.value(2)?,
+                query: args.query(3)?,
+            })
+        }
+        "count" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Count(Count {
+                query: args.query(0)?,
+                count: args.data(1)?,
+            })
+        }
+        "collect" => {
+            let args = Args::new(call, 3, 3, &[])?;
+            Query::Collect(Collect {
+                template: args.value(0)?,
+                into: args.value(1)?,
+                query: args.query(2)?,
+            })
+        }
+        "comment" => {
+            let args = Args::new(call, 1, 2, &["query"])?;
+            Query::Comment(Comment {
+                comment: args.data(0)?,
+                query: args
+                    .optional(1, "query")?
+                    .map(|expr| args.to_query(expr).map(Box::new))
+                    .transpose()?,
+            })
+        }
+        "call" => {
+            let args = Args::new(call, 1, usize::MAX, &[])?;
+            Query::Call(query::Call {
+                name: args.string(0)?,
+                arguments: call.args[1..]
+                    .iter()
+                    .map(|arg| args.to_value(arg))
+                    .collect::<ParseResult<_>>()?,
+            })
+        }
+
+        // Triples
+        "triple" => triple_query(call, Query::Triple, |s, p, o, g| Triple {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: Some(g),
+        })?,
+        "add_triple" => triple_query(call, Query::AddTriple, |s, p, o, g| AddTriple {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: Some(g),
+        })?,
+        "added_triple" => triple_query(call, Query::AddedTriple, |s, p, o, g| AddedTriple {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: Some(g),
+        })?,
+        "delete_triple" => triple_query(call, Query::DeleteTriple, |s, p, o, g| DeleteTriple {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: Some(g),
+        })?,
+        "deleted_triple" => triple_query(call, Query::DeletedTriple, |s, p, o, g| DeletedTriple {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: Some(g),
+        })?,
+        "link" => link_query(call, Query::Link, |s, p, o, g| Link {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "add_link" => link_query(call, Query::AddLink, |s, p, o, g| AddLink {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "added_link" => link_query(call, Query::AddedLink, |s, p, o, g| AddedLink {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "delete_link" => link_query(call, Query::DeleteLink, |s, p, o, g| DeleteLink {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "deleted_link" => link_query(call, Query::DeletedLink, |s, p, o, g| DeletedLink {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "data" => data_triple_query(call, Query::Data, |s, p, o, g| Data {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "add_data" => data_triple_query(call, Query::AddData, |s, p, o, g| AddData {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "added_data" => data_triple_query(call, Query::AddedData, |s, p, o, g| AddedData {
+            subject: s,
+            predicate: p,
+            object: o,
+            graph: g,
+        })?,
+        "triple_slice" | "triple_slice_rev" => {
+            let args = Args::new(call, 5, 6, &["graph"])?;
+            let (subject, predicate, object) = (args.node(0)?, args.node(1)?, args.value(2)?);
+            let (low, high) = (args.value(3)?, args.value(4)?);
+            let graph = Some(args.graph(5)?);
+            if call.name == "triple_slice" {
+                Query::TripleSlice(TripleSlice {
+                    subject,
+                    predicate,
+                    object,
+                    low,
+                    high,
+                    graph,
+                })
+            } else {
+                Query::TripleSliceRev(TripleSliceRev {
+                    subject,
+                    predicate,
+                    object,
+                    low,
+                    high,
+                    graph,
+                })
+            }
+        }
+        "triple_next" => {
+            let args = Args::new(call, 4, 5, &["graph"])?;
+            Query::TripleNext(TripleNext {
+                subject: args.node(0)?,
+                predicate: args.node(1)?,
+                object: args.value(2)?,
+                next: args.value(3)?,
+                graph: Some(args.graph(4)?),
+            })
+        }
+        "triple_previous" => {
+            let args = Args::new(call, 4, 5, &["graph"])?;
+            Query::TriplePrevious(TriplePrevious {
+                subject: args.node(0)?,
+                predicate: args.node(1)?,
+                object: args.value(2)?,
+                previous: args.value(3)?,
+                graph: Some(args.graph(4)?),
+            })
+        }
+        "path" => {
+            let args = Args::new(call, 3, 4, &["path"])?;
+            Query::Path(Path {
+                subject: args.value(0)?,
+                pattern: args.path_pattern(1)?,
+                object: args.value(2)?,
+                path: args
+                    .optional(3, "path")?
+                    .map(|expr| args.to_value(expr))
+                    .transpose()?,
+            })
+        }
+
+        // Documents
+        "read_document" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::ReadDocument(ReadDocument {
+                identifier: args.node(0)?,
+                document: args.value(1)?,
+            })
+        }
+        "insert_document" => {
+            let args = Args::new(call, 1, 2, &["identifier"])?;
+            Query::InsertDocument(InsertDocument {
+                document: args.value(0)?,
+                identifier: args
+                    .optional(1, "identifier")?
+                    .map(|expr| args.to_node(expr))
+                    .transpose()?,
+            })
+        }
+        "update_document" => {
+            let args = Args::new(call, 1, 2, &["identifier"])?;
+            Query::UpdateDocument(UpdateDocument {
+                document: args.value(0)?,
+                identifier: args
+                    .optional(1, "identifier")?
+                    .map(|expr| args.to_node(expr))
+                    .transpose()?,
+            })
+        }
+        "delete_document" => Query::DeleteDocument(DeleteDocument {
+            identifier: Args::new(call, 1, 1, &[])?.node(0)?,
+        }),
+
+        // Comparison and types
+        "eq" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Equals(Equals {
+                left: args.value(0)?,
+                right: args.value(1)?,
+            })
+        }
+        "greater" => data_query!(call, Greater { left, right }),
+        "less" => data_query!(call, Less { left, right }),
+        "gte" => data_query!(call, Gte { left, right }),
+        "lte" => data_query!(call, Lte { left, right }),
+        "isa" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::IsA(IsA {
+                element: args.node(0)?,
+                type_of: args.node(1)?,
+            })
+        }
+        "type_of" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::TypeOf(TypeOf {
+                value: args.value(0)?,
+                type_uri: args.node(1)?,
+            })
+        }
+        "typecast" => {
+            let args = Args::new(call, 3, 3, &[])?;
+            Query::Typecast(Typecast {
+                value: args.value(0)?,
+                type_uri: args.node(1)?,
+                result_value: args.value(2)?,
+            })
+        }
+        "subsumption" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Subsumption(Subsumption {
+                parent: args.node(0)?,
+                child: args.node(1)?,
+            })
+        }
+        "eval" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Eval(Eval {
+                expression: args.arithmetic(0)?,
+                result_value: args.to_arithmetic_value(args.arg(1))?,
+            })
+        }
+
+        // Strings
+        "concat" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Concatenate(Concatenate {
+                list: args.list_or_variable(0)?,
+                result_string: args.data(1)?,
+            })
+        }
+        "join" => {
+            let args = Args::new(call, 3, 3, &[])?;
+            Query::Join(Join {
+                list: args.list_or_variable(0)?,
+                separator: args.data(1)?,
+                result_string: args.data(2)?,
+            })
+        }
+        "substring" => data_query!(
+            call,
+            Substring {
+                string,
+                before,
+                length,
+                after,
+                substring
+            }
+        ),
+        "trim" => data_query!(call, Trim { untrimmed, trimmed }),
+        "upper" => data_query!(call, Upper { mixed, upper }),
+        "lower" => data_query!(call, Lower { mixed, lower }),
+        "pad" => data_query!(
+            call,
+            Pad {
+                string,
+                char,
+                times,
+                result_string
+            }
+        ),
+        "split" => data_query!(
+            call,
+            Split {
+                string,
+                pattern,
+                list
+            }
+        ),
+        "like" => data_query!(
+            call,
+            Like {
+                left,
+                right,
+                similarity
+            }
+        ),
+        "regexp" => {
+            let args = Args::new(call, 2, 3, &["result"])?;
+            Query::Regexp(Regexp {
+                pattern: args.data(0)?,
+                string: args.data(1)?,
+                result: args.optional_data(2, "result")?,
+            })
+        }
+
+        // Collections
+        "member" => data_query!(call, Member { member, list }),
+        "sum" => data_query!(call, Sum { list, result }),
+        "length" => data_query!(call, Length { list, length }),
+        "dot" => data_query!(
+            call,
+            Dot {
+                document,
+                field,
+                value
+            }
+        ),
+        "list_to_set" => data_query!(call, ListToSet { list, set }),
+        "set_union" => data_query!(
+            call,
+            SetUnion {
+                list_a,
+                list_b,
+                result
+            }
+        ),
+        "set_intersection" => data_query!(
+            call,
+            SetIntersection {
+                list_a,
+                list_b,
+                result
+            }
+        ),
+        "set_difference" => data_query!(
+            call,
+            SetDifference {
+                list_a,
+                list_b,
+                result
+            }
+        ),
+        "set_member" => data_query!(call, SetMember { element, set }),
+        "slice" => {
+            // slice(list, start, result) or slice(list, start, end, result)
+            let args = Args::new(call, 3, 4, &["end"])?;
+            let (end, result) = match call.args.len() {
+                4 => (Some(args.data(2)?), args.data(3)?),
+                _ => (args.optional_data(3, "end")?, args.data(2)?),
+            };
+            Query::Slice(Slice {
+                list: args.data(0)?,
+                start: args.data(1)?,
+                end,
+                result,
+            })
+        }
+        "sequence" => {
+            let args = Args::new(call, 3, 5, &["step", "count"])?;
+            Query::Sequence(Sequence {
+                value: args.data(0)?,
+                start: args.data(1)?,
+                end: args.data(2)?,
+                step: args.optional_data(3, "step")?,
+                count: args.optional_data(4, "count")?,
+            })
+        }
+        "in_range" => data_query!(call, InRange { value, start, end }),
+        "range_min" => data_query!(call, RangeMin { list, result }),
+        "range_max" => data_query!(call, RangeMax { list, result }),
+
+        // Keys and resources
+        "lexical_key" | "hash_key" => {
+            let args = Args::new(call, 3, 3, &[])?;
+            let (base, key_list, uri) = (args.data(0)?, args.data_list(1)?, args.node(2)?);
+            if call.name == "lexical_key" {
+                Query::LexicalKey(LexicalKey {
+                    base,
+                    key_list,
+                    uri,
+                })
+            } else {
+                Query::HashKey(HashKey {
+                    base,
+                    key_list,
+                    uri,
+                })
+            }
+        }
+        "random_key" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::RandomKey(RandomKey {
+                base: args.data(0)?,
+                uri: args.node(1)?,
+            })
+        }
+        "size" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::Size(Size {
+                resource: args.string(0)?,
+                size: args.data(1)?,
+            })
+        }
+        "triple_count" => {
+            let args = Args::new(call, 2, 2, &[])?;
+            Query::TripleCount(TripleCount {
+                resource: args.string(0)?,
+                count: args.data(1)?,
+            })
+        }
+        "get" => {
+            let args = Args::new(call, 2, 3, &["has_header"])?;
+            let has_header = match args.optional(2, "has_header")? {
+                None => None,
+                Some(Expr::Bool(b)) => Some(*b),
+                Some(other) => return Err(args.expected("a boolean", other)),
+            };
+            Query::Get(Get {
+                columns: args.columns(0)?,
+                resource: args.resource(1)?,
+                has_header,
+            })
+        }
+
+        // Dates and intervals
+        "interval" => data_query!(
+            call,
+            Interval {
+                start,
+                end,
+                interval
+            }
+        ),
+        "interval_start_duration" => {
+            data_query!(
+                call,
+                IntervalStartDuration {
+                    start,
+                    duration,
+                    interval
+                }
+            )
+        }
+        "interval_duration_end" => {
+            data_query!(
+                call,
+                IntervalDurationEnd {
+                    duration,
+                    end,
+                    interval
+                }
+            )
+        }
+        "interval_relation" => {
+            data_query!(
+                call,
+                IntervalRelation {
+                    relation,
+                    x_start,
+                    x_end,
+                    y_start,
+                    y_end
+                }
+            )
+        }
+        "interval_relation_typed" => data_query!(call, IntervalRelationTyped { relation, x, y }),
+        "date_duration" => data_query!(
+            call,
+            DateDuration {
+                start,
+                duration,
+                end
+            }
+        ),
+        "day_after" => data_query!(call, DayAfter { date, next }),
+        "day_before" => data_query!(call, DayBefore { date, previous }),
+        "iso_week" => data_query!(call, IsoWeek { date, week, year }),
+        "weekday" => data_query!(call, Weekday { date, weekday }),
+        "weekday_sunday_start" => data_query!(call, WeekdaySundayStart { date, weekday }),
+        "month_start_date" => data_query!(call, MonthStartDate { year_month, date }),
+        "month_end_date" => data_query!(call, MonthEndDate { year_month, date }),
+        "month_start_dates" => data_query!(call, MonthStartDates { date, start, end }),
+        "month_end_dates" => data_query!(call, MonthEndDates { date, start, end }),
+
+        name => return Err(ParseError::InvalidFunction(name.to_string())),
+    })
+}
+
+/// `name(subject, predicate, object[, graph])` with any value as object
+fn triple_query<T>(
+    call: &Call,
+    variant: fn(T) -> Query,
+    build: impl FnOnce(NodeValue, NodeValue, Value, GraphType) -> T,
+) -> ParseResult<Query> {
+    let args = Args::new(call, 3, 4, &["graph"])?;
+    let node = build(args.node(0)?, args.node(1)?, args.value(2)?, args.graph(3)?);
+    Ok(variant(node))
+}
+
+/// Like [`triple_query`], with a node as object
+fn link_query<T>(
+    call: &Call,
+    variant: fn(T) -> Query,
+    build: impl FnOnce(NodeValue, NodeValue, NodeValue, GraphType) -> T,
+) -> ParseResult<Query> {
+    let args = Args::new(call, 3, 4, &["graph"])?;
+    let node = build(args.node(0)?, args.node(1)?, args.node(2)?, args.graph(3)?);
+    Ok(variant(node))
+}
+
+/// Like [`triple_query`], with data as object
+fn data_triple_query<T>(
+    call: &Call,
+    variant: fn(T) -> Query,
+    build: impl FnOnce(NodeValue, NodeValue, DataValue, GraphType) -> T,
+) -> ParseResult<Query> {
+    let args = Args::new(call, 3, 4, &["graph"])?;
+    let node = build(args.node(0)?, args.node(1)?, args.data(2)?, args.graph(3)?);
+    Ok(variant(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_woql_dsl;
+
+    #[test]
+    fn test_position_decides_meaning() {
+        let query = parse_woql_dsl(
+            r#"and(
+                eval(plus($X, 1), $Y),
+                path($A, plus(pred("@schema:knows")), $B),
+                read_document("Person/1", $Doc),
+                eq($Name, "Person/1")
+            )"#,
+        )
+        .unwrap();
+        let Query::And(and) = query else {
+            panic!("Expected And");
+        };
+
+        assert!(matches!(
+            &and.and[0],
+            Query::Eval(Eval {
+                expression: ArithmeticExpression::Plus(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            &and.and[1],
+            Query::Path(Path {
+                pattern: PathPattern::Plus(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            &and.and[2],
+            Query::ReadDocument(ReadDocument {
+                identifier: NodeValue::Node(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            &and.and[3],
+            Query::Equals(Equals {
+                right: Value::Data(XSDAnySimpleType::String(_)),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_typed_literals() {
+        let query =
+            parse_woql_dsl(r#"eq(literal(42, "xsd:integer"), literal("2024-02-29", "xsd:date"))"#)
+                .unwrap();
+        let Query::Equals(eq) = query else {
+            panic!("Expected Equals");
+        };
+        assert_eq!(eq.left, Value::Data(XSDAnySimpleType::Integer(42)));
+        assert_eq!(
+            eq.right,
+            Value::Data(XSDAnySimpleType::Date(
+                NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+            ))
+        );
+
+        assert!(matches!(
+            parse_woql_dsl(r#"eq($X, literal("soon", "xsd:date"))"#),
+            Err(ParseError::InvalidLiteral(_))
+        ));
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        assert!(matches!(
+            parse_woql_dsl("not(true(), true())"),
+            Err(ParseError::InvalidArgumentCount { .. })
+        ));
+        assert!(matches!(
+            parse_woql_dsl("limit($N, true())"),
+            Err(ParseError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            parse_woql_dsl("sequence($V, 1, 10, 2, step = 3)"),
+            Err(ParseError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            parse_woql_dsl("frobnicate()"),
+            Err(ParseError::InvalidFunction(_))
+        ));
+    }
+}
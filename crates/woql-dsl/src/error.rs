@@ -26,6 +26,12 @@ pub enum ParseError {
         got: usize,
     },
 
+    #[error("Invalid argument to {function}: {message}")]
+    InvalidArgument {
+        function: String,
+        message: String,
+    },
+
     #[error("Invalid literal: {0}")]
     InvalidLiteral(String),
 
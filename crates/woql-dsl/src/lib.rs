@@ -2,6 +2,7 @@
 
 pub mod parser;
 pub mod error;
+mod convert;
 
 pub use parser::parse_woql_dsl;
 pub use error::{ParseError, ParseResult};
\ No newline at end of file
@@ -1,41 +1,57 @@
+//! Grammar of the WOQL DSL.
+//!
+//! Parsing happens in two steps. The grammar below only knows the shape of
+//! the text: variables, literals, lists, dictionaries and function calls. It
+//! produces an [`Expr`] tree, which [`crate::convert`] then turns into woql2
+//! types, deciding what each argument means from its position (so `plus` can
+//! be an arithmetic operator in `eval` and a path quantifier in `path`).
+
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while, take_while1},
-    character::complete::{char, multispace0},
-    combinator::{map, recognize},
-    error::{ParseError as NomParseError, VerboseError},
-    multi::{separated_list0, separated_list1},
-    number::complete::double,
-    sequence::{delimited, preceded, tuple},
+    character::complete::{char, digit1, hex_digit1, multispace0, one_of},
+    combinator::{cut, map, map_opt, opt, recognize},
+    error::{ErrorKind, ParseError as NomParseError, VerboseError},
+    multi::separated_list0,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
-use terminusdb_woql2::{
-    collection::Sum,
-    compare::{Equals, Greater, IsA, Less, Subsumption, TypeOf},
-    control::{Distinct, Select, WoqlOptional},
-    document::{DeleteDocument, InsertDocument, ReadDocument, UpdateDocument},
-    expression::{ArithmeticExpression, ArithmeticValue, Div, Minus, Plus, Times},
-    misc::{Count, Limit, Start},
-    order::{GroupBy, Order, OrderBy, OrderTemplate},
-    path::{InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar},
-    query::{And, Eval, Not, Or, Path, Query},
-    string::{Concatenate, Lower, Regexp, Substring, Trim, Upper},
-    triple::Triple,
-    value::{DataValue, NodeValue, Value},
-};
-use terminusdb_schema::{XSDAnySimpleType, GraphType};
+use terminusdb_woql2::query::Query;
 
+use crate::convert;
 use crate::error::{ParseError, ParseResult};
 
 type ParseInput<'a> = &'a str;
 type NomResult<'a, T> = IResult<ParseInput<'a>, T, VerboseError<ParseInput<'a>>>;
 
+/// A parsed DSL expression, before it is given a meaning
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Variable(String),
+    String(String),
+    /// Numbers keep their source text; the position decides the number type
+    Number(String),
+    Bool(bool),
+    Null,
+    List(Vec<Expr>),
+    Dict(Vec<(String, Expr)>),
+    Call(Call),
+}
+
+/// A function call with positional and `name=value` arguments
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Call {
+    pub name: String,
+    pub args: Vec<Expr>,
+    pub named: Vec<(String, Expr)>,
+}
+
 pub fn parse_woql_dsl(input: &str) -> ParseResult<Query> {
     let input = input.trim();
-    match parse_query(input) {
-        Ok((remaining, query)) => {
+    match parse_program(input) {
+        Ok((remaining, expr)) => {
             if remaining.trim().is_empty() {
-                Ok(query)
+                convert::query(&expr)
             } else {
                 Err(ParseError::ParseError {
                     position: input.len() - remaining.len(),
@@ -43,10 +59,42 @@ pub fn parse_woql_dsl(input: &str) -> ParseResult<Query> {
                 })
             }
         }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let remaining = e.errors.first().map_or("", |(rest, _)| *rest);
+            Err(ParseError::ParseError {
+                position: input.len() - remaining.len(),
+                message: format!("Unexpected input: '{}'", snippet(remaining)),
+            })
+        }
         Err(e) => Err(ParseError::NomError(format!("{:?}", e))),
     }
 }
 
+/// The start of the remaining input, for error messages
+fn snippet(remaining: &str) -> &str {
+    match remaining.char_indices().nth(40) {
+        Some((end, _)) => &remaining[..end],
+        None => remaining,
+    }
+}
+
+/// A query, optionally preceded by a `vars(...)` declaration
+fn parse_program(input: ParseInput) -> NomResult<Expr> {
+    preceded(opt(ws(parse_vars_declaration)), ws(parse_expr))(input)
+}
+
+/// `vars($A, $B)` only documents the variables; they are detected anyway
+fn parse_vars_declaration(input: ParseInput) -> NomResult<Vec<String>> {
+    preceded(
+        tag("vars"),
+        delimited(
+            ws(char('(')),
+            separated_list0(ws(char(',')), parse_variable),
+            ws(char(')')),
+        ),
+    )(input)
+}
+
 fn ws<'a, F, O>(f: F) -> impl FnMut(ParseInput<'a>) -> NomResult<'a, O>
 where
     F: FnMut(ParseInput<'a>) -> NomResult<'a, O>,
@@ -61,1563 +109,181 @@ fn parse_identifier(input: ParseInput) -> NomResult<&str> {
     )))(input)
 }
 
+/// `$name`, or `$"any name"` for names that aren't identifiers
 fn parse_variable(input: ParseInput) -> NomResult<String> {
-    map(
-        preceded(char('$'), parse_identifier),
-        |s: &str| s.to_string(),
+    preceded(
+        char('$'),
+        alt((
+            map(parse_identifier, |s: &str| s.to_string()),
+            parse_string_literal,
+        )),
     )(input)
 }
 
+/// A double-quoted string with `\"`, `\\`, `\n`, `\r`, `\t` and `\u{..}` escapes
 fn parse_string_literal(input: ParseInput) -> NomResult<String> {
-    let (input, _) = char('"')(input)?;
-    let (input, content) = take_while(|c| c != '"')(input)?;
-    let (input, _) = char('"')(input)?;
-    Ok((input, content.to_string()))
-}
-
-fn parse_number(input: ParseInput) -> NomResult<f64> {
-    double(input)
-}
-
-fn parse_boolean(input: ParseInput) -> NomResult<bool> {
-    alt((
-        map(tag("true"), |_| true),
-        map(tag("false"), |_| false),
-    ))(input)
+    let (mut rest, _) = char('"')(input)?;
+    let mut value = String::new();
+    loop {
+        let (after, chunk) = take_while(|c| c != '"' && c != '\\')(rest)?;
+        value.push_str(chunk);
+        if let Some(after) = after.strip_prefix('"') {
+            return Ok((after, value));
+        }
+        let (after, escaped) = preceded(char('\\'), parse_escape)(after)?;
+        value.push(escaped);
+        rest = after;
+    }
 }
 
-fn parse_value(input: ParseInput) -> NomResult<Value> {
+fn parse_escape(input: ParseInput) -> NomResult<char> {
     alt((
-        map(parse_variable, |var| Value::Variable(var)),
-        map(parse_string_literal, |s| {
-            if s.starts_with('@') || s.contains(':') {
-                Value::Node(s)
-            } else {
-                Value::Data(XSDAnySimpleType::String(s))
-            }
-        }),
-        map(parse_number, |n| Value::Data(XSDAnySimpleType::Float(n))),
-        map(parse_boolean, |b| Value::Data(XSDAnySimpleType::Boolean(b))),
-        parse_list_value,
-    ))(input)
-}
-
-fn parse_list_value(input: ParseInput) -> NomResult<Value> {
-    map(
-        delimited(
-            ws(char('[')),
-            separated_list0(ws(char(',')), parse_value),
-            ws(char(']')),
+        map(char('"'), |_| '"'),
+        map(char('\\'), |_| '\\'),
+        map(char('n'), |_| '\n'),
+        map(char('r'), |_| '\r'),
+        map(char('t'), |_| '\t'),
+        map_opt(
+            preceded(char('u'), delimited(char('{'), hex_digit1, char('}'))),
+            |hex: &str| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
         ),
-        |values| Value::List(values),
-    )(input)
-}
-
-fn parse_node_value(input: ParseInput) -> NomResult<NodeValue> {
-    alt((
-        map(parse_variable, |var| NodeValue::Variable(var)),
-        map(parse_string_literal, |s| NodeValue::Node(s)),
-    ))(input)
-}
-
-fn parse_data_value(input: ParseInput) -> NomResult<DataValue> {
-    alt((
-        map(parse_variable, |var| DataValue::Variable(var)),
-        map(parse_string_literal, |s| DataValue::Data(XSDAnySimpleType::String(s))),
-        map(parse_number, |n| DataValue::Data(XSDAnySimpleType::Float(n))),
-        map(parse_boolean, |b| DataValue::Data(XSDAnySimpleType::Boolean(b))),
     ))(input)
 }
 
-fn parse_function_call(input: ParseInput) -> NomResult<(&str, Vec<ParsedArg>)> {
-    let (input, name) = parse_identifier(input)?;
-    let (input, args) = delimited(
-        ws(char('(')),
-        separated_list0(ws(char(',')), parse_argument),
-        ws(char(')')),
-    )(input)?;
-    Ok((input, (name, args)))
-}
-
-#[derive(Debug, Clone)]
-enum ParsedArg {
-    Query(Query),
-    Value(Value),
-    NodeValue(NodeValue),
-    DataValue(DataValue),
-    ValueList(Vec<Value>),
-    StringList(Vec<String>),
-    ArithmeticExpr(ArithmeticExpression),
-    PathPattern(PathPattern),
-    OrderTemplate(Vec<OrderTemplate>),
+fn parse_number(input: ParseInput) -> NomResult<&str> {
+    recognize(tuple((
+        opt(char('-')),
+        digit1,
+        opt(pair(char('.'), digit1)),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+    )))(input)
 }
 
-fn parse_argument(input: ParseInput) -> NomResult<ParsedArg> {
-    alt((
-        map(parse_order_template_list, ParsedArg::OrderTemplate),
-        map(parse_value_list, ParsedArg::ValueList),
-        map(parse_arithmetic_expr, ParsedArg::ArithmeticExpr),
-        map(parse_path_pattern, ParsedArg::PathPattern),
-        map(parse_query, ParsedArg::Query),
-        map(parse_value, ParsedArg::Value),
-    ))(input)
+/// `true`, `false` and `null`; `true()` is a call and handled before this
+fn parse_keyword(input: ParseInput) -> NomResult<Expr> {
+    map_opt(parse_identifier, |word| match word {
+        "true" => Some(Expr::Bool(true)),
+        "false" => Some(Expr::Bool(false)),
+        "null" => Some(Expr::Null),
+        _ => None,
+    })(input)
 }
 
-fn parse_value_list(input: ParseInput) -> NomResult<Vec<Value>> {
+fn parse_list(input: ParseInput) -> NomResult<Vec<Expr>> {
     delimited(
         ws(char('[')),
-        separated_list0(ws(char(',')), parse_value),
+        terminated(
+            separated_list0(ws(char(',')), parse_expr),
+            opt(ws(char(','))),
+        ),
         ws(char(']')),
     )(input)
 }
 
-fn parse_order_template_list(input: ParseInput) -> NomResult<Vec<OrderTemplate>> {
+fn parse_dict(input: ParseInput) -> NomResult<Vec<(String, Expr)>> {
     delimited(
-        ws(char('[')),
-        separated_list0(ws(char(',')), parse_order_template),
-        ws(char(']')),
+        ws(char('{')),
+        terminated(
+            separated_list0(
+                ws(char(',')),
+                separated_pair(ws(parse_string_literal), char(':'), ws(parse_expr)),
+            ),
+            opt(ws(char(','))),
+        ),
+        ws(char('}')),
     )(input)
 }
 
-fn parse_order_template(input: ParseInput) -> NomResult<OrderTemplate> {
-    alt((
-        map(
-            preceded(tag("asc"), delimited(ws(char('(')), parse_variable, ws(char(')')))),
-            |var| OrderTemplate {
-                order: Order::Asc,
-                variable: var,
-            },
-        ),
-        map(
-            preceded(tag("desc"), delimited(ws(char('(')), parse_variable, ws(char(')')))),
-            |var| OrderTemplate {
-                order: Order::Desc,
-                variable: var,
-            },
-        ),
-    ))(input)
+enum Argument {
+    Positional(Expr),
+    Named(String, Expr),
 }
 
-fn parse_arithmetic_expr(input: ParseInput) -> NomResult<ArithmeticExpression> {
+fn parse_argument(input: ParseInput) -> NomResult<Argument> {
     alt((
         map(
-            preceded(tag("plus"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_arithmetic_value),
-                ws(char(')')),
-            )),
-            |args| {
-                if args.len() != 2 {
-                    panic!("plus requires exactly 2 arguments");
-                }
-                ArithmeticExpression::Plus(Plus {
-                    left: Box::new(args[0].clone()),
-                    right: Box::new(args[1].clone()),
-                })
-            },
-        ),
-        map(
-            preceded(tag("minus"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_arithmetic_value),
-                ws(char(')')),
-            )),
-            |args| {
-                if args.len() != 2 {
-                    panic!("minus requires exactly 2 arguments");
-                }
-                ArithmeticExpression::Minus(Minus {
-                    left: Box::new(args[0].clone()),
-                    right: Box::new(args[1].clone()),
-                })
-            },
-        ),
-        map(
-            preceded(tag("times"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_arithmetic_value),
-                ws(char(')')),
-            )),
-            |args| {
-                if args.len() != 2 {
-                    panic!("times requires exactly 2 arguments");
-                }
-                ArithmeticExpression::Times(Times {
-                    left: Box::new(args[0].clone()),
-                    right: Box::new(args[1].clone()),
-                })
-            },
-        ),
-        map(
-            preceded(tag("div"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_arithmetic_value),
-                ws(char(')')),
-            )),
-            |args| {
-                if args.len() != 2 {
-                    panic!("div requires exactly 2 arguments");
-                }
-                ArithmeticExpression::Div(Div {
-                    left: Box::new(args[0].clone()),
-                    right: Box::new(args[1].clone()),
-                })
-            },
+            separated_pair(parse_identifier, ws(char('=')), parse_expr),
+            |(name, expr)| Argument::Named(name.to_string(), expr),
         ),
+        map(parse_expr, Argument::Positional),
     ))(input)
 }
 
-fn parse_arithmetic_value(input: ParseInput) -> NomResult<ArithmeticExpression> {
-    alt((
-        map(parse_variable, |var| ArithmeticExpression::Value(ArithmeticValue::Variable(var))),
-        map(parse_number, |n| ArithmeticExpression::Value(ArithmeticValue::Data(XSDAnySimpleType::Float(n)))),
-        parse_arithmetic_expr,
-    ))(input)
-}
-
-fn parse_path_pattern(input: ParseInput) -> NomResult<PathPattern> {
-    alt((
-        map(
-            preceded(tag("pred"), delimited(
-                ws(char('(')),
-                parse_string_literal,
-                ws(char(')')),
-            )),
-            |pred| PathPattern::Predicate(PathPredicate { predicate: Some(pred) }),
-        ),
-        map(
-            preceded(tag("inv"), delimited(
-                ws(char('(')),
-                parse_string_literal,
-                ws(char(')')),
-            )),
-            |pred| PathPattern::InversePredicate(InversePathPredicate { predicate: Some(pred) }),
-        ),
-        map(
-            preceded(tag("star"), delimited(
-                ws(char('(')),
-                parse_path_pattern,
-                ws(char(')')),
-            )),
-            |pattern| PathPattern::Star(PathStar {
-                star: Box::new(pattern),
-            }),
-        ),
-        map(
-            preceded(tag("plus"), delimited(
-                ws(char('(')),
-                parse_path_pattern,
-                ws(char(')')),
-            )),
-            |pattern| PathPattern::Plus(PathPlus {
-                plus: Box::new(pattern),
-            }),
-        ),
-        map(
-            preceded(tag("seq"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_path_pattern),
-                ws(char(')')),
-            )),
-            |patterns| PathPattern::Sequence(PathSequence {
-                sequence: patterns,
-            }),
+fn parse_call(input: ParseInput) -> NomResult<Call> {
+    let (input, name) = terminated(parse_identifier, ws(char('(')))(input)?;
+    // Past `name(` nothing else can match, so errors point inside the call
+    let (input, arguments) = cut(terminated(
+        terminated(
+            separated_list0(ws(char(',')), ws(parse_argument)),
+            opt(ws(char(','))),
         ),
-        map(
-            preceded(tag("or"), delimited(
-                ws(char('(')),
-                separated_list1(ws(char(',')), parse_path_pattern),
-                ws(char(')')),
-            )),
-            |patterns| PathPattern::Or(PathOr {
-                or: patterns,
-            }),
-        ),
-    ))(input)
-}
-
-fn parse_query(input: ParseInput) -> NomResult<Query> {
-    alt((
-        parse_vars_statement,
-        parse_query_function,
-    ))(input)
-}
-
-fn parse_vars_statement(input: ParseInput) -> NomResult<Query> {
-    let (input, _) = tag("vars")(input)?;
-    let (input, _vars) = delimited(
-        ws(char('(')),
-        separated_list1(ws(char(',')), parse_variable),
-        ws(char(')')),
-    )(input)?;
-
-    // Continue parsing the rest of the query after vars declaration
-    let (input, _) = multispace0(input)?;
-    parse_query(input)
-}
-
-fn parse_query_function(input: ParseInput) -> NomResult<Query> {
-    let (input, (name, args)) = parse_function_call(input)?;
-
-    let query = match name {
-        "triple" => parse_triple_args(args),
-        "and" => parse_and_args(args),
-        "or" => parse_or_args(args),
-        "not" => parse_not_args(args),
-        "select" => parse_select_args(args),
-        "distinct" => parse_distinct_args(args),
-        "limit" => parse_limit_args(args),
-        "start" => parse_start_args(args),
-        "order_by" => parse_order_by_args(args),
-        "group_by" => parse_group_by_args(args),
-        "greater" => parse_greater_args(args),
-        "less" => parse_less_args(args),
-        "eq" => parse_equals_args(args),
-        "eval" => parse_eval_args(args),
-        "path" => parse_path_args(args),
-        "read_document" => parse_read_document_args(args),
-        "insert_document" => parse_insert_document_args(args),
-        "update_document" => parse_update_document_args(args),
-        "delete_document" => parse_delete_document_args(args),
-        "count" => parse_count_args(args),
-        "sum" => parse_sum_args(args),
-        "concat" => parse_concat_args(args),
-        "substring" => parse_substring_args(args),
-        "trim" => parse_trim_args(args),
-        "upper" => parse_upper_args(args),
-        "lower" => parse_lower_args(args),
-        "regexp" => parse_regexp_args(args),
-        "isa" => parse_isa_args(args),
-        "type_of" => parse_type_of_args(args),
-        "subsumption" => parse_subsumption_args(args),
-        "opt" => parse_opt_args(args),
-        "optional" => parse_opt_args(args),
-        _ => Err(nom::Err::Error(VerboseError::from_error_kind(
-            input,
-            nom::error::ErrorKind::Tag,
-        ))),
-    }?;
-
-    Ok((input, query))
-}
-
-// Helper functions for parsing specific query types
-fn parse_triple_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() < 3 || args.len() > 4 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let subject = match &args[0] {
-        ParsedArg::Value(Value::Variable(v)) => NodeValue::Variable(v.clone()),
-        ParsedArg::Value(Value::Node(n)) => NodeValue::Node(n.clone()),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let predicate = match &args[1] {
-        ParsedArg::Value(Value::Variable(v)) => NodeValue::Variable(v.clone()),
-        ParsedArg::Value(Value::Node(n)) => NodeValue::Node(n.clone()),
-        ParsedArg::Value(Value::Data(XSDAnySimpleType::String(s))) => NodeValue::Node(s.clone()),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let object = match &args[2] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let graph = if args.len() == 4 {
-        match &args[3] {
-            ParsedArg::Value(Value::Data(XSDAnySimpleType::String(s))) => {
-                match s.as_str() {
-                    "instance" => Some(GraphType::Instance),
-                    "schema" => Some(GraphType::Schema),
-                    _ => Some(GraphType::Instance),
-                }
-            }
-            _ => Some(GraphType::Instance),
-        }
-    } else {
-        Some(GraphType::Instance)
-    };
-
-    Ok(Query::Triple(Triple {
-        subject,
-        predicate,
-        object,
-        graph,
-    }))
-}
-
-fn parse_and_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    let mut queries = Vec::new();
-    for arg in args {
-        match arg {
-            ParsedArg::Query(q) => queries.push(q),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        }
-    }
-    Ok(Query::And(And { and: queries }))
-}
-
-fn parse_or_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    let mut queries = Vec::new();
-    for arg in args {
-        match arg {
-            ParsedArg::Query(q) => queries.push(q),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        }
-    }
-    Ok(Query::Or(Or { or: queries }))
-}
-
-fn parse_not_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 1 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    match &args[0] {
-        ParsedArg::Query(q) => Ok(Query::Not(Not { query: Box::new(q.clone()) })),
-        _ => Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    }
-}
-
-fn parse_select_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let variables = match &args[0] {
-        ParsedArg::ValueList(values) => {
-            let mut vars = Vec::new();
-            for v in values {
-                match v {
-                    Value::Variable(var) => vars.push(var.clone()),
-                    _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                        "",
-                        nom::error::ErrorKind::Tag,
-                    ))),
-                }
-            }
-            vars
-        }
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[1] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Select(Select {
-        variables,
-        query: Box::new(query),
-    }))
-}
-
-fn parse_distinct_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let variables = match &args[0] {
-        ParsedArg::ValueList(values) => {
-            let mut vars = Vec::new();
-            for v in values {
-                match v {
-                    Value::Variable(var) => vars.push(var.clone()),
-                    _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                        "",
-                        nom::error::ErrorKind::Tag,
-                    ))),
-                }
+        char(')'),
+    ))(input)?;
+
+    let mut call = Call {
+        name: name.to_string(),
+        args: Vec::new(),
+        named: Vec::new(),
+    };
+    for argument in arguments {
+        match argument {
+            Argument::Positional(expr) if call.named.is_empty() => call.args.push(expr),
+            // Positional arguments after named ones would be ambiguous
+            Argument::Positional(_) => {
+                return Err(nom::Err::Failure(VerboseError::from_error_kind(
+                    input,
+                    ErrorKind::Verify,
+                )))
             }
-            vars
+            Argument::Named(name, expr) => call.named.push((name, expr)),
         }
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[1] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Distinct(Distinct {
-        variables,
-        query: Box::new(query),
-    }))
-}
-
-fn parse_limit_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let limit = match &args[0] {
-        ParsedArg::Value(Value::Data(XSDAnySimpleType::Float(n))) => *n as i64,
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[1] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Limit(Limit {
-        limit: limit as u64,
-        query: Box::new(query),
-    }))
-}
-
-fn parse_start_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
     }
-
-    let start = match &args[0] {
-        ParsedArg::Value(Value::Data(XSDAnySimpleType::Float(n))) => *n as i64,
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[1] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Start(Start {
-        start: start as u64,
-        query: Box::new(query),
-    }))
+    Ok((input, call))
 }
 
-fn parse_order_by_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let ordering = match &args[0] {
-        ParsedArg::OrderTemplate(templates) => templates.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[1] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::OrderBy(OrderBy {
-        ordering,
-        query: Box::new(query),
-    }))
-}
-
-fn parse_group_by_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 4 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let template = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let group_by = match &args[1] {
-        ParsedArg::ValueList(values) => {
-            let mut vars = Vec::new();
-            for v in values {
-                match v {
-                    Value::Variable(var) => vars.push(var.clone()),
-                    _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                        "",
-                        nom::error::ErrorKind::Tag,
-                    ))),
-                }
-            }
-            vars
-        }
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let grouped_value = match &args[2] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let query = match &args[3] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::GroupBy(GroupBy {
-        template,
-        group_by,
-        grouped_value,
-        query: Box::new(query),
-    }))
-}
-
-fn parse_greater_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let left = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let right = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Greater(Greater { left, right }))
-}
-
-fn parse_less_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let left = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let right = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Less(Less { left, right }))
-}
-
-fn parse_equals_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let left = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let right = match &args[1] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Equals(Equals { left, right }))
-}
-
-fn parse_eval_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let expression = match &args[0] {
-        ParsedArg::ArithmeticExpr(expr) => expr.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let result_value = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => ArithmeticValue::Variable(var.clone()),
-            Value::Data(d) => ArithmeticValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Eval(Eval {
-        expression,
-        result_value,
-    }))
-}
-
-fn parse_path_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() < 3 || args.len() > 4 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let subject = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let pattern = match &args[1] {
-        ParsedArg::PathPattern(p) => p.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let object = match &args[2] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let path = if args.len() == 4 {
-        match &args[3] {
-            ParsedArg::Value(v) => Some(v.clone()),
-            _ => None,
-        }
-    } else {
-        None
-    };
-
-    Ok(Query::Path(Path {
-        subject,
-        pattern,
-        object,
-        path,
-    }))
-}
-
-fn parse_read_document_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let id = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let document = match &args[1] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::ReadDocument(ReadDocument {
-        identifier: id,
-        document,
-    }))
-}
-
-fn parse_insert_document_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() < 1 || args.len() > 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let document = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let identifier = if args.len() == 2 {
-        match &args[1] {
-            ParsedArg::Value(v) => match v {
-                Value::Variable(var) => Some(NodeValue::Variable(var.clone())),
-                Value::Node(n) => Some(NodeValue::Node(n.clone())),
-                _ => None,
-            },
-            _ => None,
-        }
-    } else {
-        None
-    };
-
-    Ok(Query::InsertDocument(InsertDocument {
-        document,
-        identifier,
-    }))
-}
-
-fn parse_update_document_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() < 1 || args.len() > 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let document = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let identifier = if args.len() == 2 {
-        match &args[1] {
-            ParsedArg::Value(v) => match v {
-                Value::Variable(var) => Some(NodeValue::Variable(var.clone())),
-                Value::Node(n) => Some(NodeValue::Node(n.clone())),
-                _ => None,
-            },
-            _ => None,
-        }
-    } else {
-        None
-    };
-
-    Ok(Query::UpdateDocument(UpdateDocument { document, identifier }))
-}
-
-fn parse_delete_document_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 1 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let identifier = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::DeleteDocument(DeleteDocument { identifier }))
-}
-
-fn parse_count_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let query = match &args[0] {
-        ParsedArg::Query(q) => q.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let count = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Count(Count {
-        query: Box::new(query),
-        count,
-    }))
-}
-
-fn parse_sum_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let list = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::List(_) => {
-                // Lists as DataValue not directly supported in this DSL
-                return Err(nom::Err::Error(VerboseError::from_error_kind(
-                    "",
-                    nom::error::ErrorKind::Tag,
-                )));
-            }
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let result = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Sum(Sum {
-        list,
-        result,
-    }))
-}
-
-fn parse_concat_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let list = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::List(_) => {
-                // For now, we'll just use a variable - proper list handling would need more work
-                return Err(nom::Err::Error(VerboseError::from_error_kind(
-                    "",
-                    nom::error::ErrorKind::Tag,
-                )));
-            }
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        ParsedArg::ValueList(_) => {
-            // The DSL doesn't directly support passing lists as DataValue
-            // This would need a redesign of how we handle list arguments
-            return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            )));
-        }
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let result_string = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Concatenate(Concatenate { list: list.into(), result_string }))
-}
-
-fn parse_substring_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 5 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let string = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let before = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let length = match &args[2] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let after = match &args[3] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let substring = match &args[4] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Substring(Substring {
-        string,
-        before,
-        length,
-        after,
-        substring,
-    }))
-}
-
-fn parse_trim_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let untrimmed = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let trimmed = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Trim(Trim { untrimmed, trimmed }))
-}
-
-fn parse_upper_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let mixed = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let upper = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Upper(Upper { mixed, upper }))
-}
-
-fn parse_lower_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let mixed = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let lower = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Lower(Lower { mixed, lower }))
-}
-
-fn parse_regexp_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 3 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let string = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let pattern = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => DataValue::Variable(var.clone()),
-            Value::Data(d) => DataValue::Data(d.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let result = match &args[2] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => Some(DataValue::Variable(var.clone())),
-            _ => None,
-        },
-        _ => None,
-    };
-
-    Ok(Query::Regexp(Regexp {
-        pattern,
-        string,
-        result,
-    }))
-}
-
-fn parse_isa_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let element = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let r#type = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            Value::Data(XSDAnySimpleType::String(s)) => NodeValue::Node(s.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::IsA(IsA { element, type_of: r#type }))
-}
-
-fn parse_type_of_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let value = match &args[0] {
-        ParsedArg::Value(v) => v.clone(),
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let type_uri = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::TypeOf(TypeOf { value, type_uri }))
-}
-
-fn parse_subsumption_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 2 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    let parent = match &args[0] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    let child = match &args[1] {
-        ParsedArg::Value(v) => match v {
-            Value::Variable(var) => NodeValue::Variable(var.clone()),
-            Value::Node(n) => NodeValue::Node(n.clone()),
-            _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-                "",
-                nom::error::ErrorKind::Tag,
-            ))),
-        },
-        _ => return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    };
-
-    Ok(Query::Subsumption(Subsumption { parent, child }))
-}
-
-fn parse_opt_args(args: Vec<ParsedArg>) -> Result<Query, nom::Err<VerboseError<&'static str>>> {
-    if args.len() != 1 {
-        return Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Count,
-        )));
-    }
-
-    match &args[0] {
-        ParsedArg::Query(q) => Ok(Query::WoqlOptional(WoqlOptional {
-            query: Box::new(q.clone())
-        })),
-        _ => Err(nom::Err::Error(VerboseError::from_error_kind(
-            "",
-            nom::error::ErrorKind::Tag,
-        ))),
-    }
+pub(crate) fn parse_expr(input: ParseInput) -> NomResult<Expr> {
+    alt((
+        map(parse_variable, Expr::Variable),
+        map(parse_string_literal, Expr::String),
+        map(parse_number, |n: &str| Expr::Number(n.to_string())),
+        map(parse_list, Expr::List),
+        map(parse_dict, Expr::Dict),
+        map(parse_call, Expr::Call),
+        parse_keyword,
+    ))(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use terminusdb_woql2::value::{NodeValue, Value};
 
     #[test]
     fn test_parse_variable() {
         assert_eq!(parse_variable("$Person").unwrap().1, "Person");
         assert_eq!(parse_variable("$my_var").unwrap().1, "my_var");
+        assert_eq!(parse_variable(r#"$"my var""#).unwrap().1, "my var");
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let (rest, s) = parse_string_literal(r#""a\"b\\c\nd\u{1f600}" tail"#).unwrap();
+        assert_eq!(s, "a\"b\\c\nd\u{1f600}");
+        assert_eq!(rest, " tail");
+        assert!(parse_string_literal(r#""unterminated"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_call_arguments() {
+        let (_, expr) = parse_expr(r#"sequence($V, 1, 10, count = $C)"#).unwrap();
+        let Expr::Call(call) = expr else {
+            panic!("Expected a call");
+        };
+        assert_eq!(call.name, "sequence");
+        assert_eq!(call.args.len(), 3);
+        assert_eq!(call.named[0].0, "count");
+
+        assert!(parse_call("f(a = 1, 2)").is_err());
     }
 
     #[test]
@@ -1657,4 +323,13 @@ select(
             _ => panic!("Expected Select query"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error_position() {
+        let err = parse_woql_dsl("and(triple($A, $B, $C) oops)").unwrap_err();
+        assert!(
+            matches!(err, ParseError::ParseError { position: 23, .. }),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,819 @@
+//! Every woql2 query survives rendering to the DSL and parsing back.
+//!
+//! The generators only produce queries in canonical form: the DSL can't tell
+//! `graph: None` from `graph: Some(Instance)` on triples (the parser gives the
+//! latter), and `ListOrVariable::Variable` never wraps a literal list.
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use proptest::prelude::*;
+use proptest::strategy::Union;
+use terminusdb_schema::{GraphType, XSDAnySimpleType};
+use terminusdb_woql2::{
+    collection::*,
+    compare::*,
+    control::{self, Distinct, Immediately, Once, Pin, Select, Using, WoqlOptional},
+    document::*,
+    dsl::ToDSL,
+    expression::*,
+    get::*,
+    interval::*,
+    misc::*,
+    order::*,
+    path::*,
+    query::{self, *},
+    string::*,
+    triple::*,
+    value::*,
+};
+use terminusdb_woql_dsl::parse_woql_dsl;
+
+fn name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => "[A-Za-z_][A-Za-z0-9_]{0,8}",
+        1 => "(?s).{0,8}",
+    ]
+}
+
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z]{0,6}",
+        "@schema:[A-Za-z]{1,6}",
+        "[A-Z][a-z]{0,5}/[0-9]{1,3}",
+        "(?s).{0,10}",
+    ]
+}
+
+fn xsd() -> impl Strategy<Value = XSDAnySimpleType> {
+    prop_oneof![
+        text().prop_map(XSDAnySimpleType::String),
+        any::<f64>()
+            .prop_filter("finite", |f| f.is_finite())
+            .prop_map(XSDAnySimpleType::Float),
+        any::<bool>().prop_map(XSDAnySimpleType::Boolean),
+        any::<i64>().prop_map(XSDAnySimpleType::Integer),
+        any::<usize>().prop_map(XSDAnySimpleType::UnsignedInt),
+        "-?[0-9]{1,12}(\\.[0-9]{1,6})?".prop_map(|d| XSDAnySimpleType::Decimal(d.parse().unwrap())),
+        (-10_000_000_000i64..10_000_000_000, 0u32..1_000_000_000).prop_map(|(secs, nanos)| {
+            XSDAnySimpleType::DateTime(DateTime::from_timestamp(secs, nanos).unwrap())
+        }),
+        (1i32..3_650_000).prop_map(|days| {
+            XSDAnySimpleType::Date(NaiveDate::from_num_days_from_ce_opt(days).unwrap())
+        }),
+        (0u32..86_400, 0u32..1_000_000_000).prop_map(|(secs, nanos)| {
+            XSDAnySimpleType::Time(
+                NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).unwrap(),
+            )
+        }),
+        "[0-9a-f]{0,8}".prop_map(XSDAnySimpleType::HexBinary),
+        text().prop_map(XSDAnySimpleType::URI),
+    ]
+}
+
+fn node() -> impl Strategy<Value = NodeValue> {
+    prop_oneof![
+        text().prop_map(NodeValue::Node),
+        name().prop_map(NodeValue::Variable),
+    ]
+}
+
+fn data() -> impl Strategy<Value = DataValue> {
+    let leaf = prop_oneof![
+        xsd().prop_map(DataValue::Data),
+        name().prop_map(DataValue::Variable),
+    ];
+    leaf.prop_recursive(2, 8, 4, |inner| {
+        prop::collection::vec(inner, 0..4).prop_map(DataValue::List)
+    })
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        xsd().prop_map(Value::Data),
+        text().prop_map(Value::Node),
+        name().prop_map(Value::Variable),
+    ];
+    leaf.prop_recursive(2, 8, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::List),
+            prop::collection::btree_set(
+                (text(), inner).prop_map(|(field, value)| FieldValuePair { field, value }),
+                0..4,
+            )
+            .prop_map(|data| Value::Dictionary(DictionaryTemplate { data })),
+        ]
+    })
+}
+
+fn list_or_variable() -> impl Strategy<Value = ListOrVariable> {
+    prop_oneof![
+        prop::collection::vec(data(), 0..4).prop_map(ListOrVariable::List),
+        xsd().prop_map(|d| ListOrVariable::Variable(DataValue::Data(d))),
+        name().prop_map(|n| ListOrVariable::Variable(DataValue::Variable(n))),
+    ]
+}
+
+fn graph() -> impl Strategy<Value = GraphType> {
+    prop_oneof![Just(GraphType::Instance), Just(GraphType::Schema)]
+}
+
+fn arithmetic_value() -> impl Strategy<Value = ArithmeticValue> {
+    prop_oneof![
+        xsd().prop_map(ArithmeticValue::Data),
+        name().prop_map(ArithmeticValue::Variable),
+    ]
+}
+
+fn arithmetic() -> impl Strategy<Value = ArithmeticExpression> {
+    arithmetic_value()
+        .prop_map(ArithmeticExpression::Value)
+        .prop_recursive(3, 12, 2, |inner| {
+            let pair = (inner.clone(), inner.clone())
+                .prop_map(|(left, right)| (Box::new(left), Box::new(right)));
+            prop_oneof![
+                pair.clone()
+                    .prop_map(|(left, right)| ArithmeticExpression::Plus(Plus { left, right })),
+                pair.clone()
+                    .prop_map(|(left, right)| ArithmeticExpression::Minus(Minus { left, right })),
+                pair.clone()
+                    .prop_map(|(left, right)| ArithmeticExpression::Times(Times { left, right })),
+                pair.clone().prop_map(|(left, right)| {
+                    ArithmeticExpression::Divide(Divide { left, right })
+                }),
+                pair.clone()
+                    .prop_map(|(left, right)| ArithmeticExpression::Div(Div { left, right })),
+                pair.prop_map(|(left, right)| ArithmeticExpression::Exp(Exp { left, right })),
+                inner.prop_map(|argument| {
+                    ArithmeticExpression::Floor(Floor {
+                        argument: Box::new(argument),
+                    })
+                }),
+            ]
+        })
+}
+
+fn path_pattern() -> impl Strategy<Value = PathPattern> {
+    let leaf = prop_oneof![
+        prop::option::of(text())
+            .prop_map(|predicate| PathPattern::Predicate(PathPredicate { predicate })),
+        prop::option::of(text()).prop_map(|predicate| {
+            PathPattern::InversePredicate(InversePathPredicate { predicate })
+        }),
+    ];
+    leaf.prop_recursive(3, 12, 3, |inner| {
+        prop_oneof![
+            inner
+                .clone()
+                .prop_map(|p| PathPattern::Star(PathStar { star: Box::new(p) })),
+            inner
+                .clone()
+                .prop_map(|p| PathPattern::Plus(PathPlus { plus: Box::new(p) })),
+            (inner.clone(), any::<u64>(), any::<u64>()).prop_map(|(p, from, to)| {
+                PathPattern::Times(PathTimes {
+                    times: Box::new(p),
+                    from,
+                    to,
+                })
+            }),
+            prop::collection::vec(inner.clone(), 0..3)
+                .prop_map(|sequence| PathPattern::Sequence(PathSequence { sequence })),
+            prop::collection::vec(inner, 0..3).prop_map(|or| PathPattern::Or(PathOr { or })),
+        ]
+    })
+}
+
+fn json() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::Bool),
+        any::<i64>().prop_map(|n| serde_json::json!(n)),
+        any::<f64>()
+            .prop_filter("finite", |f| f.is_finite())
+            .prop_map(|f| serde_json::json!(f)),
+        text().prop_map(serde_json::Value::String),
+    ];
+    leaf.prop_recursive(2, 8, 3, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..3).prop_map(serde_json::Value::Array),
+            prop::collection::vec((text(), inner), 0..3)
+                .prop_map(|pairs| serde_json::Value::Object(pairs.into_iter().collect())),
+        ]
+    })
+}
+
+fn get() -> impl Strategy<Value = Query> {
+    let indicator = prop_oneof![
+        any::<u64>().prop_map(Indicator::Index),
+        text().prop_map(Indicator::Name),
+    ];
+    let column =
+        (indicator, name(), prop::option::of(text())).prop_map(|(indicator, variable, type_of)| {
+            Column {
+                indicator,
+                variable,
+                type_of,
+            }
+        });
+    let source = prop_oneof![text().prop_map(Source::Post), text().prop_map(Source::Url),];
+    let resource = (source, prop::option::of(json())).prop_map(|(source, options)| QueryResource {
+        source,
+        format: FormatType::Csv,
+        options,
+    });
+    (
+        prop::collection::vec(column, 0..3),
+        resource,
+        prop::option::of(any::<bool>()),
+    )
+        .prop_map(|(columns, resource, has_header)| {
+            Query::Get(Get {
+                columns,
+                resource,
+                has_header,
+            })
+        })
+}
+
+/// Queries over nodes and values
+fn triple_leaves() -> Vec<BoxedStrategy<Query>> {
+    let spo = || (node(), node(), value(), graph());
+    let links = || (node(), node(), node(), graph());
+    let datas = || (node(), node(), data(), graph());
+    vec![
+        spo()
+            .prop_map(|(subject, predicate, object, g)| {
+                Query::Triple(Triple {
+                    subject,
+                    predicate,
+                    object,
+                    graph: Some(g),
+                })
+            })
+            .boxed(),
+        spo()
+            .prop_map(|(subject, predicate, object, g)| {
+                Query::AddTriple(AddTriple {
+                    subject,
+                    predicate,
+                    object,
+                    graph: Some(g),
+                })
+            })
+            .boxed(),
+        spo()
+            .prop_map(|(subject, predicate, object, g)| {
+                Query::AddedTriple(AddedTriple {
+                    subject,
+                    predicate,
+                    object,
+                    graph: Some(g),
+                })
+            })
+            .boxed(),
+        spo()
+            .prop_map(|(subject, predicate, object, g)| {
+                Query::DeleteTriple(DeleteTriple {
+                    subject,
+                    predicate,
+                    object,
+                    graph: Some(g),
+                })
+            })
+            .boxed(),
+        spo()
+            .prop_map(|(subject, predicate, object, g)| {
+                Query::DeletedTriple(DeletedTriple {
+                    subject,
+                    predicate,
+                    object,
+                    graph: Some(g),
+                })
+            })
+            .boxed(),
+        links()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::Link(Link {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        links()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::AddLink(AddLink {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        links()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::AddedLink(AddedLink {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        links()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::DeleteLink(DeleteLink {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        links()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::DeletedLink(DeletedLink {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        datas()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::Data(Data {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        datas()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::AddData(AddData {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        datas()
+            .prop_map(|(subject, predicate, object, graph)| {
+                Query::AddedData(AddedData {
+                    subject,
+                    predicate,
+                    object,
+                    graph,
+                })
+            })
+            .boxed(),
+        (spo(), value(), value(), any::<bool>())
+            .prop_map(|((subject, predicate, object, g), low, high, rev)| {
+                let graph = Some(g);
+                if rev {
+                    Query::TripleSliceRev(TripleSliceRev {
+                        subject,
+                        predicate,
+                        object,
+                        low,
+                        high,
+                        graph,
+                    })
+                } else {
+                    Query::TripleSlice(TripleSlice {
+                        subject,
+                        predicate,
+                        object,
+                        low,
+                        high,
+                        graph,
+                    })
+                }
+            })
+            .boxed(),
+        (spo(), value(), any::<bool>())
+            .prop_map(|((subject, predicate, object, g), other, next)| {
+                let graph = Some(g);
+                if next {
+                    Query::TripleNext(TripleNext {
+                        subject,
+                        predicate,
+                        object,
+                        next: other,
+                        graph,
+                    })
+                } else {
+                    Query::TriplePrevious(TriplePrevious {
+                        subject,
+                        predicate,
+                        object,
+                        previous: other,
+                        graph,
+                    })
+                }
+            })
+            .boxed(),
+        (value(), path_pattern(), value(), prop::option::of(value()))
+            .prop_map(|(subject, pattern, object, path)| {
+                Query::Path(Path {
+                    subject,
+                    pattern,
+                    object,
+                    path,
+                })
+            })
+            .boxed(),
+        (node(), value())
+            .prop_map(|(identifier, document)| {
+                Query::ReadDocument(ReadDocument {
+                    identifier,
+                    document,
+                })
+            })
+            .boxed(),
+        (value(), prop::option::of(node()), any::<bool>())
+            .prop_map(|(document, identifier, insert)| {
+                if insert {
+                    Query::InsertDocument(InsertDocument {
+                        document,
+                        identifier,
+                    })
+                } else {
+                    Query::UpdateDocument(UpdateDocument {
+                        document,
+                        identifier,
+                    })
+                }
+            })
+            .boxed(),
+        node()
+            .prop_map(|identifier| Query::DeleteDocument(DeleteDocument { identifier }))
+            .boxed(),
+        (value(), value())
+            .prop_map(|(left, right)| Query::Equals(Equals { left, right }))
+            .boxed(),
+        (node(), node())
+            .prop_map(|(element, type_of)| Query::IsA(IsA { element, type_of }))
+            .boxed(),
+        (node(), node())
+            .prop_map(|(parent, child)| Query::Subsumption(Subsumption { parent, child }))
+            .boxed(),
+        (value(), node())
+            .prop_map(|(value, type_uri)| Query::TypeOf(TypeOf { value, type_uri }))
+            .boxed(),
+        (value(), node(), value())
+            .prop_map(|(value, type_uri, result_value)| {
+                Query::Typecast(Typecast {
+                    value,
+                    type_uri,
+                    result_value,
+                })
+            })
+            .boxed(),
+        (arithmetic(), arithmetic_value())
+            .prop_map(|(expression, result_value)| {
+                Query::Eval(Eval {
+                    expression,
+                    result_value,
+                })
+            })
+            .boxed(),
+        (text(), prop::collection::vec(value(), 0..3))
+            .prop_map(|(name, arguments)| Query::Call(query::Call { name, arguments }))
+            .boxed(),
+        get().boxed(),
+    ]
+}
+
+/// Queries whose arguments are all data values
+fn data_leaves() -> Vec<BoxedStrategy<Query>> {
+    macro_rules! data_query {
+        ($variant:ident { $($field:ident),+ }) => {
+            data_query!(@fields $variant, [$($field),+], ($(data_query!(@data $field)),+))
+        };
+        (@data $field:ident) => { data() };
+        (@fields $variant:ident, [$($field:ident),+], $strategy:expr) => {
+            $strategy
+                .prop_map(|($($field),+)| Query::$variant($variant { $($field),+ }))
+                .boxed()
+        };
+    }
+
+    vec![
+        data_query!(Greater { left, right }),
+        data_query!(Less { left, right }),
+        data_query!(Gte { left, right }),
+        data_query!(Lte { left, right }),
+        data_query!(Substring {
+            string,
+            before,
+            length,
+            after,
+            substring
+        }),
+        data_query!(Trim { untrimmed, trimmed }),
+        data_query!(Upper { mixed, upper }),
+        data_query!(Lower { mixed, lower }),
+        data_query!(Pad {
+            string,
+            char,
+            times,
+            result_string
+        }),
+        data_query!(Split {
+            string,
+            pattern,
+            list
+        }),
+        data_query!(Like {
+            left,
+            right,
+            similarity
+        }),
+        data_query!(Member { member, list }),
+        data_query!(Sum { list, result }),
+        data_query!(Length { list, length }),
+        data_query!(Dot {
+            document,
+            field,
+            value
+        }),
+        data_query!(ListToSet { list, set }),
+        data_query!(SetUnion {
+            list_a,
+            list_b,
+            result
+        }),
+        data_query!(SetIntersection {
+            list_a,
+            list_b,
+            result
+        }),
+        data_query!(SetDifference {
+            list_a,
+            list_b,
+            result
+        }),
+        data_query!(SetMember { element, set }),
+        data_query!(InRange { value, start, end }),
+        data_query!(RangeMin { list, result }),
+        data_query!(RangeMax { list, result }),
+        data_query!(Interval {
+            start,
+            end,
+            interval
+        }),
+        data_query!(IntervalStartDuration {
+            start,
+            duration,
+            interval
+        }),
+        data_query!(IntervalDurationEnd {
+            duration,
+            end,
+            interval
+        }),
+        data_query!(IntervalRelation {
+            relation,
+            x_start,
+            x_end,
+            y_start,
+            y_end
+        }),
+        data_query!(IntervalRelationTyped { relation, x, y }),
+        data_query!(DateDuration {
+            start,
+            duration,
+            end
+        }),
+        data_query!(DayAfter { date, next }),
+        data_query!(DayBefore { date, previous }),
+        data_query!(IsoWeek { date, week, year }),
+        data_query!(Weekday { date, weekday }),
+        data_query!(WeekdaySundayStart { date, weekday }),
+        data_query!(MonthStartDate { year_month, date }),
+        data_query!(MonthEndDate { year_month, date }),
+        data_query!(MonthStartDates { date, start, end }),
+        data_query!(MonthEndDates { date, start, end }),
+        (data(), data(), prop::option::of(data()))
+            .prop_map(|(pattern, string, result)| {
+                Query::Regexp(Regexp {
+                    pattern,
+                    string,
+                    result,
+                })
+            })
+            .boxed(),
+        (data(), data(), prop::option::of(data()), data())
+            .prop_map(|(list, start, end, result)| {
+                Query::Slice(Slice {
+                    list,
+                    start,
+                    end,
+                    result,
+                })
+            })
+            .boxed(),
+        (
+            data(),
+            data(),
+            data(),
+            prop::option::of(data()),
+            prop::option::of(data()),
+        )
+            .prop_map(|(value, start, end, step, count)| {
+                Query::Sequence(Sequence {
+                    value,
+                    start,
+                    end,
+                    step,
+                    count,
+                })
+            })
+            .boxed(),
+        (list_or_variable(), data())
+            .prop_map(|(list, result_string)| {
+                Query::Concatenate(Concatenate {
+                    list,
+                    result_string,
+                })
+            })
+            .boxed(),
+        (list_or_variable(), data(), data())
+            .prop_map(|(list, separator, result_string)| {
+                Query::Join(Join {
+                    list,
+                    separator,
+                    result_string,
+                })
+            })
+            .boxed(),
+        (
+            data(),
+            prop::collection::vec(data(), 0..3),
+            node(),
+            any::<bool>(),
+        )
+            .prop_map(|(base, key_list, uri, lexical)| {
+                if lexical {
+                    Query::LexicalKey(LexicalKey {
+                        base,
+                        key_list,
+                        uri,
+                    })
+                } else {
+                    Query::HashKey(HashKey {
+                        base,
+                        key_list,
+                        uri,
+                    })
+                }
+            })
+            .boxed(),
+        (data(), node())
+            .prop_map(|(base, uri)| Query::RandomKey(RandomKey { base, uri }))
+            .boxed(),
+        (text(), data())
+            .prop_map(|(resource, size)| Query::Size(Size { resource, size }))
+            .boxed(),
+        (text(), data())
+            .prop_map(|(resource, count)| Query::TripleCount(TripleCount { resource, count }))
+            .boxed(),
+    ]
+}
+
+fn query() -> impl Strategy<Value = Query> {
+    let mut leaves = triple_leaves();
+    leaves.extend(data_leaves());
+    leaves.push(Just(Query::True(True {})).boxed());
+
+    Union::new(leaves).prop_recursive(3, 16, 3, |inner| {
+        let boxed = inner.clone().prop_map(Box::new);
+        let order = (name(), any::<bool>()).prop_map(|(variable, asc)| OrderTemplate {
+            variable,
+            order: if asc { Order::Asc } else { Order::Desc },
+        });
+        Union::new(vec![
+            prop::collection::vec(inner.clone(), 0..3)
+                .prop_map(|and| Query::And(And { and }))
+                .boxed(),
+            prop::collection::vec(inner.clone(), 0..3)
+                .prop_map(|or| Query::Or(Or { or }))
+                .boxed(),
+            boxed
+                .clone()
+                .prop_map(|query| Query::Not(Not { query }))
+                .boxed(),
+            boxed
+                .clone()
+                .prop_map(|query| Query::Pin(Pin { query }))
+                .boxed(),
+            boxed
+                .clone()
+                .prop_map(|query| Query::Once(Once { query }))
+                .boxed(),
+            boxed
+                .clone()
+                .prop_map(|query| Query::Immediately(Immediately { query }))
+                .boxed(),
+            boxed
+                .clone()
+                .prop_map(|query| Query::WoqlOptional(WoqlOptional { query }))
+                .boxed(),
+            (boxed.clone(), boxed.clone(), boxed.clone())
+                .prop_map(|(test, then_query, else_query)| {
+                    Query::If(control::If {
+                        test,
+                        then_query,
+                        else_query,
+                    })
+                })
+                .boxed(),
+            (prop::collection::vec(name(), 0..3), boxed.clone())
+                .prop_map(|(variables, query)| Query::Select(Select { variables, query }))
+                .boxed(),
+            (prop::collection::vec(name(), 0..3), boxed.clone())
+                .prop_map(|(variables, query)| Query::Distinct(Distinct { variables, query }))
+                .boxed(),
+            (text(), boxed.clone())
+                .prop_map(|(collection, query)| Query::Using(Using { collection, query }))
+                .boxed(),
+            (text(), boxed.clone())
+                .prop_map(|(graph, query)| Query::From(control::From { graph, query }))
+                .boxed(),
+            (text(), boxed.clone())
+                .prop_map(|(graph, query)| Query::Into(control::Into { graph, query }))
+                .boxed(),
+            (any::<u64>(), boxed.clone())
+                .prop_map(|(limit, query)| Query::Limit(Limit { limit, query }))
+                .boxed(),
+            (any::<u64>(), boxed.clone())
+                .prop_map(|(start, query)| Query::Start(Start { start, query }))
+                .boxed(),
+            (prop::collection::vec(order, 0..3), boxed.clone())
+                .prop_map(|(ordering, query)| Query::OrderBy(OrderBy { ordering, query }))
+                .boxed(),
+            (
+                prop::collection::vec(name(), 0..3),
+                value(),
+                value(),
+                boxed.clone(),
+            )
+                .prop_map(|(group_by, template, grouped_value, query)| {
+                    Query::GroupBy(GroupBy {
+                        group_by,
+                        template,
+                        grouped_value,
+                        query,
+                    })
+                })
+                .boxed(),
+            (boxed.clone(), data())
+                .prop_map(|(query, count)| Query::Count(Count { query, count }))
+                .boxed(),
+            (value(), value(), boxed.clone())
+                .prop_map(|(template, into, query)| {
+                    Query::Collect(Collect {
+                        template,
+                        into,
+                        query,
+                    })
+                })
+                .boxed(),
+            (data(), prop::option::of(boxed))
+                .prop_map(|(comment, query)| Query::Comment(Comment { comment, query }))
+                .boxed(),
+        ])
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn render_then_parse_is_identity(query in query()) {
+        let dsl = query.to_dsl();
+        let parsed = parse_woql_dsl(&dsl)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {}", dsl, e));
+        prop_assert_eq!(parsed, query, "rendered as {}", dsl);
+    }
+}
+
+#[test]
+fn test_roundtrip_examples() {
+    // A few hand-picked queries that exercise the lossy spots of the old DSL
+    let queries = [
+        r#"triple($S, "@schema:name", string("@schema:Person"), "schema")"#,
+        r#"eq($"my var", iri("plain"))"#,
+        r#"eq($X, {"age": literal(42, "xsd:integer"), "name": $Name})"#,
+        r#"eval(floor(divide($A, 2)), $B)"#,
+        r#"path($A, times(pred("@schema:knows"), 1, 3), $B)"#,
+        r#"group_by([$Dept], $Name, $Names, triple($P, "@schema:name", $Name))"#,
+    ];
+    for dsl in queries {
+        let query = parse_woql_dsl(dsl).unwrap();
+        assert_eq!(query.to_dsl(), dsl);
+    }
+}
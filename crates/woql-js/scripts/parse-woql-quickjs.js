@@ -8,38 +8,95 @@
  */
 
 // Import our WOQL-only module that doesn't have Node.js dependencies
-const WOQL = require('./woql-only');
+const library = require('./woql-only');
 
 /**
- * Parse a JavaScript-syntax WOQL query string into JSON-LD format.
- * @param {string} queryString - The JS-syntax WOQL query
- * @returns {string} JSON string of the JSON-LD representation
- * @throws {Error} If the query cannot be parsed
+ * Deep-freeze everything reachable from the given roots through properties
+ * and prototypes.
+ *
+ * The Rust side reuses one context for many queries, so the built-ins and the
+ * WOQL library are frozen once they are loaded: a query that overwrites
+ * `JSON.stringify` or `WOQL.triple` must not change how later queries parse.
+ * @param {...*} roots - Objects to freeze
  */
-globalThis.parseWoql = function(queryString) {
-  if (!queryString || typeof queryString !== 'string') {
-    throw new Error('Query must be a non-empty string');
+function lockdown(...roots) {
+  const pending = roots;
+  const seen = new Set();
+  while (pending.length > 0) {
+    const value = pending.pop();
+    const isObject = (typeof value === 'object' && value !== null) || typeof value === 'function';
+    if (!isObject || seen.has(value)) {
+      continue;
+    }
+    seen.add(value);
+    Object.freeze(value);
+    pending.push(Object.getPrototypeOf(value));
+    for (const key of Reflect.ownKeys(value)) {
+      const descriptor = Object.getOwnPropertyDescriptor(value, key);
+      pending.push(descriptor.value, descriptor.get, descriptor.set);
+    }
   }
+}
 
-  const trimmed = queryString.trim();
-  if (!trimmed) {
-    throw new Error('Query must be a non-empty string');
-  }
+/**
+ * Create the parse function.
+ *
+ * Queries are evaluated in the scope of the returned function, so they can
+ * see `WOQL`; it is a `const` here (bundling turns module-level declarations
+ * into `var`s) so a query can't rebind it for later queries either.
+ * @param {object} woql - The WOQL library
+ * @returns {function(string): string} The parse function
+ */
+function createParser(woql) {
+  const WOQL = woql;
 
   // Generate the prelude that defines all WOQL functions in the eval scope
   const prelude = WOQL.emerge();
 
-  // Evaluate the query string with the prelude
-  // This is the same approach used in the dashboard
-  const woqlQuery = eval(prelude + "\n" + trimmed);
+  /**
+   * Parse a JavaScript-syntax WOQL query string into JSON-LD format.
+   * @param {string} queryString - The JS-syntax WOQL query
+   * @returns {string} JSON string of the JSON-LD representation
+   * @throws {Error} If the query cannot be parsed
+   */
+  return function parseWoql(queryString) {
+    if (!queryString || typeof queryString !== 'string') {
+      throw new Error('Query must be a non-empty string');
+    }
 
-  if (!woqlQuery) {
-    throw new Error('Query evaluation returned null/undefined');
-  }
+    const trimmed = queryString.trim();
+    if (!trimmed) {
+      throw new Error('Query must be a non-empty string');
+    }
+
+    // Unique variable names are numbered per query, as in a fresh context
+    WOQL.vars_unique_reset_start(0);
+
+    // Evaluate the query string with the prelude
+    // This is the same approach used in the dashboard
+    const woqlQuery = eval(prelude + "\n" + trimmed);
+
+    if (!woqlQuery) {
+      throw new Error('Query evaluation returned null/undefined');
+    }
+
+    // Convert to JSON-LD
+    const jsonLD = woqlQuery.json();
+
+    // Return as JSON string
+    return JSON.stringify(jsonLD);
+  };
+}
 
-  // Convert to JSON-LD
-  const jsonLD = woqlQuery.json();
+globalThis.parseWoql = createParser(library);
 
-  // Return as JSON string
-  return JSON.stringify(jsonLD);
-};
+// The library's classes are only reachable through their instances
+const instances = [
+  library.query(),
+  library.lib(),
+  library.vars('v')[0],
+  library.vars_unique('v')[0],
+  library.Vars('v'),
+  library.doc({}),
+];
+lockdown(globalThis, library, ...instances.map(Object.getPrototypeOf));
@@ -3467,23 +3467,55 @@ ${nspaces(level * this.indent_spaces)}${i === 0 ? "WOQL" : ""}`;
   // parse-woql-quickjs.js
   var require_parse_woql_quickjs = __commonJS({
     "parse-woql-quickjs.js"(exports, module) {
-      var WOQL = require_woql_only();
-      globalThis.parseWoql = function(queryString) {
-        if (!queryString || typeof queryString !== "string") {
-          throw new Error("Query must be a non-empty string");
-        }
-        const trimmed = queryString.trim();
-        if (!trimmed) {
-          throw new Error("Query must be a non-empty string");
+      var library = require_woql_only();
+      function lockdown(...roots) {
+        const pending = roots;
+        const seen = /* @__PURE__ */ new Set();
+        while (pending.length > 0) {
+          const value = pending.pop();
+          const isObject = typeof value === "object" && value !== null || typeof value === "function";
+          if (!isObject || seen.has(value)) {
+            continue;
+          }
+          seen.add(value);
+          Object.freeze(value);
+          pending.push(Object.getPrototypeOf(value));
+          for (const key of Reflect.ownKeys(value)) {
+            const descriptor = Object.getOwnPropertyDescriptor(value, key);
+            pending.push(descriptor.value, descriptor.get, descriptor.set);
+          }
         }
+      }
+      function createParser(woql) {
+        const WOQL = woql;
         const prelude = WOQL.emerge();
-        const woqlQuery = eval(prelude + "\n" + trimmed);
-        if (!woqlQuery) {
-          throw new Error("Query evaluation returned null/undefined");
-        }
-        const jsonLD = woqlQuery.json();
-        return JSON.stringify(jsonLD);
-      };
+        return function parseWoql(queryString) {
+          if (!queryString || typeof queryString !== "string") {
+            throw new Error("Query must be a non-empty string");
+          }
+          const trimmed = queryString.trim();
+          if (!trimmed) {
+            throw new Error("Query must be a non-empty string");
+          }
+          WOQL.vars_unique_reset_start(0);
+          const woqlQuery = eval(prelude + "\n" + trimmed);
+          if (!woqlQuery) {
+            throw new Error("Query evaluation returned null/undefined");
+          }
+          const jsonLD = woqlQuery.json();
+          return JSON.stringify(jsonLD);
+        };
+      }
+      globalThis.parseWoql = createParser(library);
+      var instances = [
+        library.query(),
+        library.lib(),
+        library.vars("v")[0],
+        library.vars_unique("v")[0],
+        library.Vars("v"),
+        library.doc({})
+      ];
+      lockdown(globalThis, library, ...instances.map(Object.getPrototypeOf));
     }
   });
   require_parse_woql_quickjs();
@@ -8,7 +8,9 @@
 //! This crate supports two JavaScript runtimes:
 //!
 //! - **QuickJS** (default): Embedded JavaScript engine with no external dependencies.
-//!   Fast startup (~1ms), smaller bundle (~128KB), works everywhere Rust compiles.
+//!   Smaller bundle (~128KB), works everywhere Rust compiles. Each thread loads the
+//!   WOQL library once and reuses it, so after the first parse on a thread a parse
+//!   takes around a millisecond (see [`preload`]).
 //!
 //! - **Node.js** (fallback): Uses Node.js as a subprocess. Requires Node.js >= 14.0.0
 //!   to be installed. Slower startup (~50-100ms), larger bundle (~965KB).
@@ -47,8 +49,15 @@
 //! - Node references: `"@schema:Person"`, `"rdf:type"`
 //!
 //! This is different from the Rust DSL syntax which uses `$` for variables.
+//!
+//! # Query Scripts
+//!
+//! [`parse_js_woql_script`] evaluates a whole JS file that binds several
+//! queries, such as `let q1 = ...; let q2 = ...;`, and returns each of them by
+//! name.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
 use terminusdb_woql2::prelude::FromTDBInstance;
 
 // Runtime modules with conditional compilation
@@ -58,6 +67,8 @@ mod quickjs_runtime;
 #[cfg(feature = "nodejs")]
 mod nodejs_runtime;
 
+mod script;
+
 // Test module (only compiled during testing)
 #[cfg(test)]
 mod quickjs_test;
@@ -99,6 +110,16 @@ pub fn parse_js_woql(query: &str) -> Result<serde_json::Value> {
     quickjs_runtime::parse_js_woql(query)
 }
 
+/// Load the WOQL library on the current thread ahead of the first parse.
+///
+/// The first [`parse_js_woql`] call on each thread loads the library, which
+/// takes several times longer than a parse. Servers can call this from their
+/// worker threads at startup to keep that cost out of the first request.
+#[cfg(feature = "quickjs")]
+pub fn preload() -> Result<()> {
+    quickjs_runtime::preload()
+}
+
 #[cfg(all(feature = "nodejs", not(feature = "quickjs")))]
 pub fn parse_js_woql(query: &str) -> Result<serde_json::Value> {
     nodejs_runtime::parse_js_woql(query)
@@ -145,6 +166,70 @@ pub fn parse_js_woql_to_query(query: &str) -> Result<terminusdb_woql2::query::Qu
     Ok(woql_query)
 }
 
+/// Parse a JavaScript file of WOQL queries into the JSON-LD of each named query.
+///
+/// Where [`parse_js_woql`] takes a single query expression, this takes a whole
+/// script, so existing JS query files can be used as they are: variables can
+/// be declared with `Vars(...)` and queries bound with `const`, `let` or `var`,
+/// including queries built from earlier ones. Every top-level binding that
+/// holds a WOQL query is returned under its name; other bindings, such as the
+/// `Vars` object, are left out.
+///
+/// # Errors
+///
+/// Returns an error if the script fails to evaluate, or if it binds no query.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use terminusdb_woql_js::parse_js_woql_script;
+///
+/// let script = r#"
+///     const v = Vars("person", "name");
+///     let people = triple(v.person, "rdf:type", "@schema:Person");
+///     let names = and(people, triple(v.person, "@schema:name", v.name));
+/// "#;
+/// let queries = parse_js_woql_script(script).unwrap();
+/// assert_eq!(queries.len(), 2);
+/// assert_eq!(queries["names"]["@type"], "And");
+/// ```
+pub fn parse_js_woql_script(script: &str) -> Result<BTreeMap<String, serde_json::Value>> {
+    let names = script::declared_names(script);
+    if names.is_empty() {
+        bail!("Script declares no queries; bind them with `const`, `let` or `var`");
+    }
+
+    let json_ld = parse_js_woql(&script::collecting_script(script, &names))?;
+    let queries: BTreeMap<String, serde_json::Value> =
+        serde_json::from_value(json_ld).context("Script evaluation returned no queries")?;
+    if queries.is_empty() {
+        bail!(
+            "None of the script's bindings ({}) holds a WOQL query",
+            names.join(", ")
+        );
+    }
+    Ok(queries)
+}
+
+/// Parse a JavaScript file of WOQL queries into a `terminusdb_woql2::Query` per
+/// named query; see [`parse_js_woql_script`].
+pub fn parse_js_woql_script_to_queries(
+    script: &str,
+) -> Result<BTreeMap<String, terminusdb_woql2::query::Query>> {
+    parse_js_woql_script(script)?
+        .into_iter()
+        .map(|(name, json_ld)| {
+            let query = terminusdb_woql2::query::Query::from_json(json_ld).with_context(|| {
+                format!(
+                    "Failed to deserialize query '{}' into terminusdb_woql2::Query",
+                    name
+                )
+            })?;
+            Ok((name, query))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
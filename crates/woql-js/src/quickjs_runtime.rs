@@ -10,25 +10,54 @@ use std::cell::RefCell;
 /// The bundled QuickJS-compatible JavaScript for WOQL parsing (~128KB)
 const QUICKJS_BUNDLE: &str = include_str!("../scripts/parse-woql.quickjs.js");
 
-// Thread-local storage for the QuickJS runtime
-// Each thread gets its own runtime instance since QuickJS is not thread-safe
+// Thread-local QuickJS context with the bundle already loaded
+// Each thread gets its own context since QuickJS is not thread-safe. Loading the
+// bundle takes far longer than parsing a query, so the context is reused for
+// every parse on the thread; the bundle freezes the WOQL library and the
+// JavaScript built-ins after loading, so one query can't affect the next.
 thread_local! {
-    static RUNTIME: RefCell<Option<Runtime>> = const { RefCell::new(None) };
+    static PARSER: RefCell<Option<JsContext>> = const { RefCell::new(None) };
 }
 
-fn with_runtime<F, R>(f: F) -> R
+fn with_parser<F, R>(f: F) -> Result<R>
 where
-    F: FnOnce(&Runtime) -> R,
+    F: FnOnce(&JsContext) -> Result<R>,
 {
-    RUNTIME.with(|rt| {
-        let mut rt_ref = rt.borrow_mut();
-        if rt_ref.is_none() {
-            *rt_ref = Some(Runtime::new().expect("Failed to create QuickJS runtime"));
+    PARSER.with(|parser| {
+        let mut parser = parser.borrow_mut();
+        if parser.is_none() {
+            *parser = Some(load_parser()?);
         }
-        f(rt_ref.as_ref().unwrap())
+        f(parser.as_ref().unwrap())
     })
 }
 
+/// Create a QuickJS context and load the bundle, which defines the global
+/// parseWoql function
+fn load_parser() -> Result<JsContext> {
+    let runtime = Runtime::new().context("Failed to create QuickJS runtime")?;
+    let ctx = JsContext::full(&runtime).context("Failed to create QuickJS context")?;
+
+    ctx.with(|ctx| {
+        ctx.eval::<(), _>(QUICKJS_BUNDLE).map_err(|e| {
+            // Try to get more detailed error info
+            if let Some(exc) = ctx.catch().as_exception() {
+                if let Some(msg) = exc.message() {
+                    return anyhow::anyhow!("Failed to load WOQL bundle: {}", msg);
+                }
+            }
+            anyhow::anyhow!("Failed to load WOQL bundle: {:?}", e)
+        })
+    })?;
+
+    Ok(ctx)
+}
+
+/// Load the WOQL library on the current thread, if it isn't loaded yet
+pub fn preload() -> Result<()> {
+    with_parser(|_| Ok(()))
+}
+
 /// Parse a JavaScript-syntax WOQL query string into JSON-LD format using QuickJS.
 ///
 /// This function uses the embedded QuickJS JavaScript engine to parse the query.
@@ -58,32 +87,15 @@ where
 /// let json_ld = parse_js_woql(query).unwrap();
 /// ```
 pub fn parse_js_woql(query: &str) -> Result<serde_json::Value> {
-    with_runtime(|runtime| {
-        let ctx = JsContext::full(runtime).context("Failed to create QuickJS context")?;
-
-    ctx.with(|ctx| {
-        // Load the bundle (this defines the global parseWoql function)
-        let _: () = ctx
-            .eval(QUICKJS_BUNDLE)
-            .map_err(|e| {
-                // Try to get more detailed error info
-                if let Some(exc) = ctx.catch().as_exception() {
-                    if let Some(msg) = exc.message() {
-                        return anyhow::anyhow!("Failed to load WOQL bundle: {}", msg);
-                    }
-                }
-                anyhow::anyhow!("Failed to load WOQL bundle: {:?}", e)
-            })?;
-
-        // Escape the query string for JavaScript
-        let escaped_query = serde_json::to_string(query)
-            .context("Failed to escape query string")?;
-
-        // Call parseWoql with the query
-        let js_code = format!("parseWoql({})", escaped_query);
-        let result: String = ctx
-            .eval(js_code)
-            .map_err(|e| {
+    with_parser(|ctx| {
+        ctx.with(|ctx| {
+            // Escape the query string for JavaScript
+            let escaped_query =
+                serde_json::to_string(query).context("Failed to escape query string")?;
+
+            // Call parseWoql with the query
+            let js_code = format!("parseWoql({})", escaped_query);
+            let result: String = ctx.eval(js_code).map_err(|e| {
                 // Try to get more detailed error info
                 if let Some(exc) = ctx.catch().as_exception() {
                     if let Some(msg) = exc.message() {
@@ -93,9 +105,9 @@ pub fn parse_js_woql(query: &str) -> Result<serde_json::Value> {
                 anyhow::anyhow!("WOQL parse error: {:?}", e)
             })?;
 
-        // Parse the JSON result
-        serde_json::from_str(&result).context("Failed to parse JSON-LD output from QuickJS")
-    })
+            // Parse the JSON result
+            serde_json::from_str(&result).context("Failed to parse JSON-LD output from QuickJS")
+        })
     })
 }
 
@@ -157,4 +169,50 @@ mod tests {
         let result = parse_js_woql(query);
         assert!(result.is_err(), "Expected error for invalid syntax");
     }
+
+    #[test]
+    fn test_queries_cannot_affect_later_queries() {
+        let query = r#"triple("v:Subject", "v:Predicate", "v:Object")"#;
+        let expected = parse_js_woql(query).unwrap();
+
+        // Each attempt either fails or changes nothing outside its own parse
+        for tamper in [
+            r#"WOQL.triple = () => WOQL.true(); triple("v:A", "v:B", "v:C")"#,
+            r#"WOQL = { triple: () => null }; triple("v:A", "v:B", "v:C")"#,
+            r#"JSON.stringify = () => "{}"; triple("v:A", "v:B", "v:C")"#,
+            r#"Object.getPrototypeOf(triple("v:A", "v:B", "v:C")).json = () => ({}); triple("v:A", "v:B", "v:C")"#,
+            r#"globalThis.parseWoql = null; triple("v:A", "v:B", "v:C")"#,
+        ] {
+            let _ = parse_js_woql(tamper);
+            assert_eq!(parse_js_woql(query).unwrap(), expected, "after: {}", tamper);
+        }
+    }
+
+    #[test]
+    fn test_unique_variables_are_numbered_per_query() {
+        let query = r#"
+            let [a] = vars_unique("a");
+            triple(a, "rdf:type", "@schema:Person")
+        "#;
+        let first = parse_js_woql(query).unwrap();
+        assert_eq!(parse_js_woql(query).unwrap(), first);
+    }
+
+    #[test]
+    fn test_parses_on_several_threads() {
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    preload().unwrap();
+                    let query = format!(r#"triple("v:S{}", "v:P", "v:O")"#, i);
+                    parse_js_woql(&query).unwrap()
+                })
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            let json_ld = handle.join().unwrap();
+            assert_eq!(json_ld["subject"]["variable"], format!("S{}", i));
+        }
+    }
 }
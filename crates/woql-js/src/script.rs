@@ -0,0 +1,288 @@
+//! Support for parsing whole JS query scripts.
+//!
+//! A script such as
+//!
+//! ```js
+//! const v = Vars("person", "name");
+//! let people = triple(v.person, "rdf:type", "@schema:Person");
+//! let names = and(people, triple(v.person, "@schema:name", v.name));
+//! ```
+//!
+//! is evaluated like a single query, with an expression appended that collects
+//! the top-level bindings. The runtimes call `.json()` on the result of the
+//! evaluation, so that expression is an object whose `json()` returns the
+//! JSON-LD of every binding that holds a WOQL query, by name.
+
+/// Names bound by top-level `const`, `let` and `var` declarations, in order of
+/// first declaration. Destructuring patterns are skipped: they bind variables
+/// from `vars(...)` rather than queries.
+pub(crate) fn declared_names(source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut in_declaration = false;
+    let mut expecting_name = false;
+    let mut previous: Option<Token> = None;
+
+    for (token, depth) in tokenize(source) {
+        if depth == 0 {
+            match token {
+                Token::Word(word)
+                    if matches!(word, "const" | "let" | "var")
+                        && previous != Some(Token::Punct('.')) =>
+                {
+                    in_declaration = true;
+                    expecting_name = true;
+                }
+                Token::Word(word) if expecting_name && is_identifier(word) => {
+                    if !names.iter().any(|name| name == word) {
+                        names.push(word.to_string());
+                    }
+                    expecting_name = false;
+                }
+                Token::Punct(',') if in_declaration => expecting_name = true,
+                Token::Punct(';') => {
+                    in_declaration = false;
+                    expecting_name = false;
+                }
+                _ => expecting_name = false,
+            }
+        }
+        previous = Some(token);
+    }
+    names
+}
+
+/// The script followed by an expression that collects the queries bound to
+/// `names`, to be evaluated in place of a single query expression
+pub(crate) fn collecting_script(source: &str, names: &[String]) -> String {
+    let entries = names
+        .iter()
+        .map(|name| {
+            format!(
+                "[\"{name}\", typeof {name} === \"undefined\" ? undefined : {name}]",
+                name = name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // All queries share the base class of `WOQL.query()`; `Vars` objects and
+    // other values don't, and are left out.
+    format!(
+        r#"{source}
+;(function (entries) {{
+  let base = Object.getPrototypeOf(WOQL.query());
+  while (Object.getPrototypeOf(base) !== Object.prototype) {{
+    base = Object.getPrototypeOf(base);
+  }}
+  const queries = {{}};
+  for (const [name, value] of entries) {{
+    if (typeof value === "object" && value !== null && base.isPrototypeOf(value)) {{
+      queries[name] = value.json();
+    }}
+  }}
+  return {{ json: () => queries }};
+}})([{entries}])"#,
+        source = source,
+        entries = entries
+    )
+}
+
+fn is_identifier(word: &str) -> bool {
+    word.starts_with(|c: char| c == '_' || c == '$' || c.is_alphabetic())
+        && !matches!(
+            word,
+            "const" | "let" | "var" | "function" | "class" | "if" | "for" | "while" | "return"
+        )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// an identifier, keyword, number or string literal
+    Word(&'a str),
+    Punct(char),
+}
+
+/// Keywords after which a `/` starts a regular expression rather than a division
+const REGEX_PREFIX_KEYWORDS: &[&str] = &[
+    "return", "typeof", "case", "do", "else", "in", "of", "new", "delete", "void", "throw",
+];
+
+/// Split JS source into words and punctuation, each with its bracket nesting
+/// depth. Comments, string, template and regular expression literals are
+/// skipped, apart from the code inside template `${...}` substitutions.
+fn tokenize(source: &str) -> Vec<(Token<'_>, usize)> {
+    // open brackets; `$` is the `${` of a template substitution
+    let mut stack: Vec<char> = Vec::new();
+    let mut tokens: Vec<(Token<'_>, usize)> = Vec::new();
+    let bytes = source.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = source[i..].chars().next().unwrap();
+        let next = bytes.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += c.len_utf8(),
+            '/' if next == Some(b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            '/' if next == Some(b'*') => {
+                i = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            '/' if starts_regex(tokens.last().map(|(token, _)| *token)) => {
+                i = skip_regex(bytes, i + 1);
+            }
+            '"' | '\'' => {
+                let end = skip_string(bytes, i + 1, c as u8);
+                tokens.push((Token::Word(&source[i..end]), stack.len()));
+                i = end;
+            }
+            '`' => {
+                tokens.push((Token::Word("``"), stack.len()));
+                let (end, substitution) = skip_template(bytes, i + 1);
+                if substitution {
+                    stack.push('$');
+                }
+                i = end;
+            }
+            _ if c == '_' || c == '$' || c.is_alphanumeric() => {
+                let start = i;
+                while let Some(c) = source[i..].chars().next() {
+                    if c == '_' || c == '$' || c.is_alphanumeric() {
+                        i += c.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push((Token::Word(&source[start..i]), stack.len()));
+            }
+            '(' | '[' | '{' => {
+                tokens.push((Token::Punct(c), stack.len()));
+                stack.push(c);
+                i += 1;
+            }
+            ')' | ']' | '}' => {
+                let opened = stack.pop();
+                if opened == Some('$') {
+                    // back inside the template literal
+                    let (end, substitution) = skip_template(bytes, i + 1);
+                    if substitution {
+                        stack.push('$');
+                    }
+                    i = end;
+                } else {
+                    tokens.push((Token::Punct(c), stack.len()));
+                    i += 1;
+                }
+            }
+            _ => {
+                tokens.push((Token::Punct(c), stack.len()));
+                i += c.len_utf8();
+            }
+        }
+    }
+    tokens
+}
+
+fn starts_regex(previous: Option<Token<'_>>) -> bool {
+    match previous {
+        None => true,
+        Some(Token::Word(word)) => REGEX_PREFIX_KEYWORDS.contains(&word),
+        Some(Token::Punct(c)) => !matches!(c, ')' | ']' | '}'),
+    }
+}
+
+/// Index just past the closing quote of a string literal starting at `i`
+fn skip_string(bytes: &[u8], mut i: usize, quote: u8) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote || b == b'\n' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past the closing backtick of the template literal text starting
+/// at `i`, or just past the `${` of the next substitution, and whether it was
+/// a substitution
+fn skip_template(bytes: &[u8], mut i: usize) -> (usize, bool) {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => return (i + 1, false),
+            b'$' if bytes.get(i + 1) == Some(&b'{') => return (i + 2, true),
+            _ => i += 1,
+        }
+    }
+    (bytes.len(), false)
+}
+
+/// Index just past the flags of a regular expression literal whose body
+/// starts at `i`
+fn skip_regex(bytes: &[u8], mut i: usize) -> usize {
+    let mut in_class = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'[' => {
+                in_class = true;
+                i += 1;
+            }
+            b']' => {
+                in_class = false;
+                i += 1;
+            }
+            b'/' if !in_class => {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+                    i += 1;
+                }
+                return i;
+            }
+            b'\n' => return i,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_names() {
+        let source = r#"
+            const v = Vars("a", "b");
+            const [x, y] = vars("x", "y");
+            let q1 = triple(v.a, "knows", v.b), q2 = eq(x, y)
+            var q3 = and(q1, q2);
+            var q3 = or(q1, q2);
+            if (true) { let inner = q1; }
+            const helper = (a, b) => { const local = a; return local; };
+        "#;
+        assert_eq!(
+            declared_names(source),
+            vec!["v", "q1", "q2", "q3", "helper"]
+        );
+    }
+
+    #[test]
+    fn test_declarations_in_literals_are_ignored() {
+        let source = r#"
+            // let commented = 1;
+            /* const blocked = 2; */
+            let s = "let quoted = 3;", t = 'const single = 4';
+            let tpl = `let templated = ${ triple("a", "b", `c${1}`) }; let after = 5`;
+            let re = regex(/let x = "/, "v:S"), last = 6;
+            let ratio = 4 / 2, ok = "a" / 1, done = 7;
+        "#;
+        assert_eq!(
+            declared_names(source),
+            vec!["s", "t", "tpl", "re", "last", "ratio", "ok", "done"]
+        );
+    }
+}
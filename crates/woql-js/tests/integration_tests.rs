@@ -5,7 +5,9 @@
 //! tests no longer require Node.js to be installed.
 
 use terminusdb_woql2::query::Query;
-use terminusdb_woql_js::{parse_js_woql, parse_js_woql_to_query};
+use terminusdb_woql_js::{
+    parse_js_woql, parse_js_woql_script, parse_js_woql_script_to_queries, parse_js_woql_to_query,
+};
 
 /// Test parsing a simple triple query
 #[test]
@@ -264,3 +266,74 @@ fn test_parse_with_modifiers() {
         result.err()
     );
 }
+
+/// Test that queries rendered with `ToJsSyntax` parse back to the same shape
+#[test]
+fn test_parse_rendered_query() {
+    use terminusdb_woql2::prelude::*;
+    use terminusdb_woql2::value::{NodeValue, Value};
+
+    let query = Query::Select(Select {
+        variables: vec!["Name".to_string()],
+        query: Box::new(Query::And(And {
+            and: vec![
+                Query::Triple(Triple {
+                    subject: NodeValue::Variable("Person".to_string()),
+                    predicate: NodeValue::Node("@schema:name".to_string()),
+                    object: Value::Variable("Name".to_string()),
+                    graph: None,
+                }),
+                // No client-js function: embedded as `json({...})`
+                Query::Gte(Gte {
+                    left: DataValue::Variable("Name".to_string()),
+                    right: DataValue::Variable("Min".to_string()),
+                }),
+            ],
+        })),
+    });
+
+    let json_ld = parse_js_woql(&query.to_js()).expect("rendered query should parse");
+    assert_eq!(json_ld["@type"], "Select");
+    assert_eq!(json_ld["variables"][0], "Name");
+    assert_eq!(json_ld["query"]["and"][0]["@type"], "Triple");
+    assert_eq!(json_ld["query"]["and"][0]["object"]["variable"], "Name");
+    assert_eq!(json_ld["query"]["and"][1]["@type"], "Gte");
+}
+
+/// Test parsing a script that binds several queries
+#[test]
+fn test_parse_script() {
+    let script = r#"
+        // people and their names
+        const v = Vars("person", "name");
+        let people = triple(v.person, "rdf:type", "@schema:Person");
+        let names = and(
+            people,
+            triple(v.person, "@schema:name", v.name)
+        )
+        const pageSize = 10, firstNames = limit(pageSize, names);
+    "#;
+
+    let queries = parse_js_woql_script(script).expect("script should parse");
+    assert_eq!(
+        queries.keys().collect::<Vec<_>>(),
+        vec!["firstNames", "names", "people"]
+    );
+    assert_eq!(queries["people"]["@type"], "Triple");
+    assert_eq!(queries["names"]["and"][0], queries["people"]);
+    assert_eq!(queries["names"]["and"][1]["object"]["variable"], "name");
+    assert_eq!(queries["firstNames"]["@type"], "Limit");
+
+    let parsed = parse_js_woql_script_to_queries(script).unwrap();
+    assert!(matches!(parsed["people"], Query::Triple(_)));
+}
+
+/// Test that scripts without queries are rejected
+#[test]
+fn test_parse_script_without_queries() {
+    let err = parse_js_woql_script(r#"const v = Vars("a");"#).unwrap_err();
+    assert!(err.to_string().contains("holds a WOQL query"), "{err}");
+
+    assert!(parse_js_woql_script(r#"triple("v:S", "v:P", "v:O")"#).is_err());
+    assert!(parse_js_woql_script("let q = triple(").is_err());
+}
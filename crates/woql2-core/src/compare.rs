@@ -0,0 +1,100 @@
+//! Comparisons and type checks
+
+use crate::value::{DataValue, NodeValue, Value};
+use serde::Serialize;
+
+/// True whenever 'left' is the same as 'right'. Performs unification.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Equals {
+    /// A URI, data value or variable.
+    pub left: Value,
+    /// A URI, data value or variable.
+    pub right: Value,
+}
+
+/// Predicate determining if one thing is less than another according to natural ordering.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Less {
+    /// The lesser element.
+    pub left: DataValue,
+    /// The greater element.
+    pub right: DataValue,
+}
+
+/// Predicate determining if one thing is greater than another according to natural ordering.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Greater {
+    /// The greater element.
+    pub left: DataValue,
+    /// The lesser element.
+    pub right: DataValue,
+}
+
+/// Predicate determining if 'left' is greater than or equal to 'right' according to natural ordering. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Gte {
+    /// The left-hand element.
+    pub left: DataValue,
+    /// The right-hand element.
+    pub right: DataValue,
+}
+
+/// Predicate determining if 'left' is less than or equal to 'right' according to natural ordering. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Lte {
+    /// The left-hand element.
+    pub left: DataValue,
+    /// The right-hand element.
+    pub right: DataValue,
+}
+
+/// Provides class subsumption (the inheritance model) according to the schema design. True whenver 'child' is a child of 'parent'. Can be used as a generator or a check.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Subsumption {
+    /// The child class as a URI or variable.
+    pub child: NodeValue,
+    /// The parent class as a URI or variable
+    pub parent: NodeValue,
+}
+
+/// Test (or generate) the type of an element.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct IsA {
+    /// The element to test.
+    pub element: NodeValue,
+    /// The type of the element.
+    #[serde(rename = "type")]
+    pub type_of: NodeValue,
+}
+
+/// TypeOf gives the type of an element.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TypeOf {
+    /// The value of which to obtain the type.
+    pub value: Value,
+    /// The URI which that specifies the type.
+    #[serde(rename = "type")]
+    pub type_uri: NodeValue,
+}
+
+/// Casts one type as another if possible.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Typecast {
+    /// The value to cast.
+    pub value: Value,
+    /// The type to which to cast.
+    #[serde(rename = "type")]
+    pub type_uri: NodeValue,
+    /// The resulting value after cast.
+    #[serde(rename = "result")]
+    pub result_value: Value,
+}
@@ -0,0 +1,44 @@
+//! Document reads and writes
+
+use crate::value::{NodeValue, Value};
+use serde::Serialize;
+
+/// Read a full document from an identifier.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct ReadDocument {
+    /// The URI of the document to load.
+    pub identifier: NodeValue,
+    /// Variable which will be bound to the document.
+    pub document: Value,
+}
+
+/// Insert a document in the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct InsertDocument {
+    /// The document to insert. Must either have an '@id' or have a class specified key.
+    pub document: Value,
+    /// An optional returned identifier specifying the documentation location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<NodeValue>,
+}
+
+/// Update a document in the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct UpdateDocument {
+    /// The document to update. Must either have an '@id' or have a class specified key.
+    pub document: Value,
+    /// An optional returned identifier specifying the documentation location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<NodeValue>,
+}
+
+/// Delete a document from the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DeleteDocument {
+    /// An identifier specifying the documentation location to delete.
+    pub identifier: NodeValue,
+}
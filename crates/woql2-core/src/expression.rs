@@ -0,0 +1,123 @@
+//! Arithmetic expressions
+
+use crate::xsd::XSDAnySimpleType;
+use alloc::{boxed::Box, string::String};
+use serde::Serialize;
+
+/// A variable or data value used within an arithmetic expression.
+///
+/// # Numeric coercion
+///
+/// The server evaluates operands as plain numbers, whatever their xsd type:
+///
+/// - `Plus`, `Minus`, `Times`, `Exp` and `Floor` on two integers
+///   (`XSDAnySimpleType::Integer` / `UnsignedInt`) stay integral;
+/// - as soon as one operand is a `Float` or `Decimal` the result is a float;
+/// - `Divide` (`/`) always produces a float, even for `6 / 3`; use `Div` for
+///   truncating integer division.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArithmeticValue {
+    /// An xsd data type value.
+    Data(XSDAnySimpleType),
+    /// A variable.
+    Variable(String),
+}
+
+tagged_union!(ArithmeticValue, "ArithmeticValue", { Data => "data", Variable => "variable" });
+
+/// An abstract class specifying the AST super-class of all arithemtic expressions.
+///
+/// Each variant serializes as its concrete `@type` (e.g. `{"@type":"Divide",...}`).
+///
+/// See [`ArithmeticValue`] for how integer and float operands combine.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ArithmeticExpression {
+    Value(ArithmeticValue),
+    Plus(Plus),
+    Minus(Minus),
+    Times(Times),
+    Divide(Divide),
+    Div(Div),
+    Exp(Exp),
+    Floor(Floor),
+}
+
+from_variants!(ArithmeticExpression {
+    Value(ArithmeticValue),
+    Plus(Plus),
+    Minus(Minus),
+    Times(Times),
+    Divide(Divide),
+    Div(Div),
+    Exp(Exp),
+    Floor(Floor),
+});
+
+/// Add two numbers.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Plus {
+    /// First operand of add.
+    pub left: Box<ArithmeticExpression>,
+    /// Second operand of add.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Subtract two numbers.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Minus {
+    /// First operand of minus.
+    pub left: Box<ArithmeticExpression>,
+    /// Second operand of minus.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Multiply two numbers.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Times {
+    /// First operand of times.
+    pub left: Box<ArithmeticExpression>,
+    /// Second operand of times.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Divide two numbers.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Divide {
+    /// First operand of divide.
+    pub left: Box<ArithmeticExpression>,
+    /// Second operand of divide.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Integer divide two numbers.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Div {
+    /// First operand of div.
+    pub left: Box<ArithmeticExpression>,
+    /// Second operand of div.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Exponentiate a number.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Exp {
+    /// The base.
+    pub left: Box<ArithmeticExpression>,
+    /// The exponent.
+    pub right: Box<ArithmeticExpression>,
+}
+
+/// Find the integral part of a number.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Floor {
+    /// The number to floor.
+    pub argument: Box<ArithmeticExpression>,
+}
@@ -0,0 +1,73 @@
+//! Reading CSV resources
+
+use alloc::{string::String, vec::Vec};
+use serde::Serialize;
+
+/// Tagged union for specifying a column by index or name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indicator {
+    /// Column index (non-negative integer).
+    Index(u64),
+    /// Column name (string).
+    Name(String),
+}
+
+tagged_union!(Indicator, "Indicator", { Index => "index", Name => "name" });
+
+/// Specifies a column for data retrieval, mapping an indicator to a variable.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Column {
+    /// Indicator (index or name) for the column.
+    pub indicator: Indicator,
+    /// Variable name to bind the column data to.
+    pub variable: String,
+    /// Optional data type hint for the column.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_of: Option<String>,
+}
+
+/// Tagged union specifying the source of data (POST body or URL).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Data source is a POST request body.
+    Post(String),
+    /// Data source is a URL.
+    Url(String),
+}
+
+tagged_union!(Source, "Source", { Post => "post", Url => "url" });
+
+/// Enum specifying the format of the input data.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatType {
+    /// Comma-Separated Values format.
+    Csv,
+}
+
+/// Specifies the resource for the Get query, including source, format, and options.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct QueryResource {
+    /// The source of the data (URL or POST).
+    pub source: Source,
+    /// The format of the data (e.g., CSV).
+    pub format: FormatType,
+    /// Optional format-specific options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+/// Retrieves data from an external resource (CSV via URL or POST) and binds it to variables.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Get {
+    /// List of columns to extract and bind to variables.
+    pub columns: Vec<Column>,
+    /// The resource (source, format, options) to get data from.
+    pub resource: QueryResource,
+    /// Optional flag indicating if the resource has a header row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_header: Option<bool>,
+}
@@ -0,0 +1,121 @@
+//! Alloc-only WOQL AST for environments without the standard library.
+//!
+//! `terminusdb-woql2` derives its AST from the schema machinery, which needs std
+//! and a nightly compiler. This crate mirrors the same types, with the same
+//! names and fields, using only `alloc` and `serde`, so queries can be built
+//! inside WASM plugins and other constrained environments. Serializing a
+//! [`Query`](query::Query) produces the JSON-LD the server accepts, with literals
+//! typed the way terminusdb-client-js emits them.
+//!
+//! ```
+//! use terminusdb_woql2_core::prelude::*;
+//!
+//! let query = Query::Limit(Limit {
+//!     limit: 10,
+//!     query: Box::new(Query::Triple(Triple {
+//!         subject: NodeValue::Variable("Person".into()),
+//!         predicate: NodeValue::Node("rdf:type".into()),
+//!         object: Value::Node("@schema:Person".into()),
+//!         graph: None,
+//!     })),
+//! });
+//!
+//! let json = query.to_json();
+//! assert_eq!(json["@type"], "Limit");
+//! assert_eq!(json["query"]["graph"], "instance");
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+/// Serialize a tagged union as `{"@type": <class>, <variant key>: <payload>}`
+macro_rules! tagged_union {
+    ($name:ident, $class:literal, { $($variant:ident => $key:literal),* $(,)? }) => {
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ::serde::ser::SerializeMap;
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("@type", $class)?;
+                match self {
+                    $($name::$variant(value) => map.serialize_entry($key, value)?,)*
+                }
+                map.end()
+            }
+        }
+    };
+}
+
+/// Convert the payload of each variant of an abstract class into the class
+macro_rules! from_variants {
+    ($name:ident { $($variant:ident($ty:ty)),* $(,)? }) => {
+        $(
+            impl ::core::convert::From<$ty> for $name {
+                fn from(value: $ty) -> Self {
+                    $name::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+pub mod collection;
+pub mod compare;
+pub mod control;
+pub mod document;
+pub mod expression;
+pub mod get;
+pub mod interval;
+pub mod misc;
+pub mod order;
+pub mod path;
+pub mod query;
+pub mod string;
+pub mod triple;
+pub mod value;
+pub mod xsd;
+
+pub mod prelude {
+    pub use super::collection::{
+        Dot, Length, ListToSet, Member, SetDifference, SetIntersection, SetMember, SetUnion, Slice,
+        Sum,
+    };
+    pub use super::compare::{Equals, Greater, Gte, IsA, Less, Lte, Subsumption, TypeOf, Typecast};
+    pub use super::control::{
+        Distinct, From, If, Immediately, Into, Once, Pin, Select, Using, WoqlOptional,
+    };
+    pub use super::document::{DeleteDocument, InsertDocument, ReadDocument, UpdateDocument};
+    pub use super::expression::{
+        ArithmeticExpression, ArithmeticValue, Div, Divide, Exp, Floor, Minus, Plus, Times,
+    };
+    pub use super::get::{Column, FormatType, Get, Indicator, QueryResource, Source};
+    pub use super::interval::{
+        DateDuration, DayAfter, DayBefore, Interval, IntervalDurationEnd, IntervalRelation,
+        IntervalRelationTyped, IntervalStartDuration, IsoWeek, MonthEndDate, MonthEndDates,
+        MonthStartDate, MonthStartDates, Weekday, WeekdaySundayStart,
+    };
+    pub use super::misc::{
+        Collect, Comment, Count, HashKey, InRange, LexicalKey, Limit, RandomKey, RangeMax,
+        RangeMin, Sequence, Size, Start, TripleCount,
+    };
+    pub use super::order::{GroupBy, Order, OrderBy, OrderTemplate};
+    pub use super::path::{
+        InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar,
+        PathTimes,
+    };
+    pub use super::query::{
+        And, Call, Eval, NamedParametricQuery, NamedQuery, Not, Or, Path, Query, True,
+    };
+    pub use super::string::{
+        Concatenate, Join, Like, Lower, Pad, Regexp, Split, Substring, Trim, Upper,
+    };
+    pub use super::triple::{
+        AddData, AddLink, AddTriple, AddedData, AddedLink, AddedTriple, Data, DeleteLink,
+        DeleteTriple, DeletedLink, DeletedTriple, GraphType, Link, Triple, TripleNext,
+        TriplePrevious, TripleSlice, TripleSliceRev,
+    };
+    pub use super::value::{
+        DataValue, DictionaryTemplate, FieldValuePair, ListOrVariable, NodeValue, Value,
+    };
+    pub use super::xsd::XSDAnySimpleType;
+}
@@ -0,0 +1,163 @@
+//! Paging, counting, keys and ranges
+
+use crate::query::Query;
+use crate::value::{DataValue, NodeValue, Value};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::Serialize;
+
+/// Start a query at the nth solution specified by 'start'. Allows resumption and paging of queries.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Start {
+    /// The numbered solution to start at.
+    pub start: u64,
+    /// The query to perform.
+    pub query: Box<Query>,
+}
+
+/// Limit a query to a particular maximum number of solutions specified by 'limit'. Can be used with start to perform paging.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Limit {
+    /// Maximum number of solutions.
+    pub limit: u64,
+    /// The query to perform.
+    pub query: Box<Query>,
+}
+
+/// Counts the number of solutions of a query.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Count {
+    /// The query from which to obtain the count.
+    pub query: Box<Query>,
+    /// The count of the number of solutions.
+    pub count: DataValue,
+}
+
+/// Generates a key identical to those generated automatically by 'LexicalKey' specifications.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct LexicalKey {
+    /// The URI base to the left of the key.
+    pub base: DataValue,
+    /// List of data elements required to generate the key.
+    pub key_list: Vec<DataValue>,
+    /// The resulting URI.
+    pub uri: NodeValue,
+}
+
+/// Generates a key identical to those generated automatically by 'HashKey' specifications.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct HashKey {
+    /// The URI base to the left of the key.
+    pub base: DataValue,
+    /// List of data elements required to generate the key.
+    pub key_list: Vec<DataValue>,
+    /// The resulting URI.
+    pub uri: NodeValue,
+}
+
+/// Generates a key identical to those generated automatically by 'RandomKey' specifications.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct RandomKey {
+    /// The URI base to the left of the key.
+    pub base: DataValue,
+    /// The resulting URI.
+    pub uri: NodeValue,
+}
+
+/// Size of a database in magic units (bytes?).
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Size {
+    /// The resource to obtain the size of.
+    pub resource: String,
+    /// The size.
+    pub size: DataValue,
+}
+
+/// The number of edges in a database.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TripleCount {
+    /// The resource to obtain the edges from.
+    pub resource: String,
+    /// The count of edges.
+    pub count: DataValue,
+}
+
+/// Attach a comment to a query; the query itself (if any) is disabled. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Comment {
+    /// The comment text.
+    pub comment: DataValue,
+    /// The (disabled) query the comment applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Box<Query>>,
+}
+
+/// Collect all solutions of a sub-query into a list using a template. An empty solution set yields an empty list. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Collect {
+    /// The template describing what to collect per solution.
+    pub template: Value,
+    /// The variable receiving the collected list.
+    pub into: Value,
+    /// The sub-query whose solutions are collected.
+    pub query: Box<Query>,
+}
+
+/// Generate a sequence of numbers from 'start' to 'end' with optional 'step' and 'count'. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Sequence {
+    /// The generated value.
+    pub value: DataValue,
+    /// The start of the sequence.
+    pub start: DataValue,
+    /// The end of the sequence.
+    pub end: DataValue,
+    /// The step between elements (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<DataValue>,
+    /// The number of elements (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<DataValue>,
+}
+
+/// Test or generate whether 'value' lies in the half-open range ['start', 'end'). (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct InRange {
+    /// The value to test or generate.
+    pub value: DataValue,
+    /// The inclusive start of the range.
+    pub start: DataValue,
+    /// The exclusive end of the range.
+    pub end: DataValue,
+}
+
+/// The minimum element of a list. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct RangeMin {
+    /// The list of which to find the minimum.
+    pub list: DataValue,
+    /// The minimum element.
+    pub result: DataValue,
+}
+
+/// The maximum element of a list. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct RangeMax {
+    /// The list of which to find the maximum.
+    pub list: DataValue,
+    /// The maximum element.
+    pub result: DataValue,
+}
@@ -0,0 +1,51 @@
+//! Ordering and grouping
+
+use crate::query::Query;
+use crate::value::Value;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::Serialize;
+
+/// Specifies the ordering direction (ascending or descending).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    /// Ascending order.
+    Asc,
+    /// Descending order.
+    Desc,
+}
+
+/// The order template, consisting of the variable and ordering direction.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct OrderTemplate {
+    /// The variable to order.
+    pub variable: String,
+    /// An enum either 'asc' or 'desc'.
+    pub order: Order,
+}
+
+/// Orders query results according to an ordering specification.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct OrderBy {
+    /// A specification of the ordering of solutions.
+    pub ordering: Vec<OrderTemplate>,
+    /// The base query giving the solutions to order.
+    pub query: Box<Query>,
+}
+
+/// Group a query into a list with each element of the list specified by 'template' using a given variable set for the group.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct GroupBy {
+    /// The template of elements in the result list.
+    pub template: Value,
+    /// The variables which should be grouped into like solutions.
+    pub group_by: Vec<String>,
+    /// The final list of templated solutions.
+    #[serde(rename = "grouped")]
+    pub grouped_value: Value,
+    /// The subquery providing the solutions for the grouping.
+    pub query: Box<Query>,
+}
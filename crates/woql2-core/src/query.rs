@@ -0,0 +1,334 @@
+//! The query union and its logical connectives
+
+use crate::collection::{
+    Dot, Length, ListToSet, Member, SetDifference, SetIntersection, SetMember, SetUnion, Slice, Sum,
+};
+use crate::compare::{Equals, Greater, Gte, IsA, Less, Lte, Subsumption, TypeOf, Typecast};
+use crate::control::{
+    Distinct, From, If, Immediately, Into, Once, Pin, Select, Using, WoqlOptional,
+};
+use crate::document::{DeleteDocument, InsertDocument, ReadDocument, UpdateDocument};
+use crate::expression::{ArithmeticExpression, ArithmeticValue};
+use crate::get::Get;
+use crate::interval::{
+    DateDuration, DayAfter, DayBefore, Interval, IntervalDurationEnd, IntervalRelation,
+    IntervalRelationTyped, IntervalStartDuration, IsoWeek, MonthEndDate, MonthEndDates,
+    MonthStartDate, MonthStartDates, Weekday, WeekdaySundayStart,
+};
+use crate::misc::{
+    Collect, Comment, Count, HashKey, InRange, LexicalKey, Limit, RandomKey, RangeMax, RangeMin,
+    Sequence, Size, Start, TripleCount,
+};
+use crate::order::{GroupBy, OrderBy};
+use crate::path::PathPattern;
+use crate::string::{Concatenate, Join, Like, Lower, Pad, Regexp, Split, Substring, Trim, Upper};
+use crate::triple::{
+    AddData, AddLink, AddTriple, AddedData, AddedLink, AddedTriple, Data, DeleteLink, DeleteTriple,
+    DeletedLink, DeletedTriple, Link, Triple, TripleNext, TriplePrevious, TripleSlice,
+    TripleSliceRev,
+};
+use crate::value::Value;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::Serialize;
+
+/// A named query names a specific query for later retrieval and re-use.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct NamedQuery {
+    /// The name of the NamedQuery to be retrieved
+    pub name: String,
+    /// The query AST as WOQL JSON
+    pub query: Query,
+}
+
+/// A named parametric query which names a specific query for later retrieval and re-use and allows the specification of bindings for a specific set of variables in the query.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct NamedParametricQuery {
+    /// The name of the NamedParametricQuery to be retrieved.
+    pub name: String,
+    /// Variable name list for auxilliary bindings.
+    pub parameters: Vec<String>,
+    /// The query AST as WOQL JSON.
+    pub query: Query,
+}
+
+/// A call of a named parametric query. Variables will be passed to the named query and bound according to the results. Named queries can be (mutually) recursive.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Call {
+    /// The name of the NamedParametricQuery to be retrieved.
+    pub name: String,
+    /// The arguments to use when binding formal parameters of the parametric query.
+    pub arguments: Vec<Value>,
+}
+
+/// An abstract class which represents an arbitrary query AST.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Query {
+    And(And),
+    Or(Or),
+    Not(Not),
+    True(True),
+    Triple(Triple),
+    AddTriple(AddTriple),
+    AddedTriple(AddedTriple),
+    DeleteTriple(DeleteTriple),
+    DeletedTriple(DeletedTriple),
+    Link(Link),
+    Data(Data),
+    AddLink(AddLink),
+    AddedLink(AddedLink),
+    AddData(AddData),
+    AddedData(AddedData),
+    DeleteLink(DeleteLink),
+    DeletedLink(DeletedLink),
+    Eval(Eval),
+    Path(Path),
+    ReadDocument(ReadDocument),
+    InsertDocument(InsertDocument),
+    UpdateDocument(UpdateDocument),
+    DeleteDocument(DeleteDocument),
+    Equals(Equals),
+    Less(Less),
+    Greater(Greater),
+    Subsumption(Subsumption),
+    IsA(IsA),
+    TypeOf(TypeOf),
+    Typecast(Typecast),
+    Trim(Trim),
+    Lower(Lower),
+    Upper(Upper),
+    Pad(Pad),
+    Split(Split),
+    Join(Join),
+    Concatenate(Concatenate),
+    Substring(Substring),
+    Regexp(Regexp),
+    Like(Like),
+    Member(Member),
+    Sum(Sum),
+    Length(Length),
+    Dot(Dot),
+    Get(Get),
+    Using(Using),
+    From(From),
+    Into(Into),
+    Select(Select),
+    Distinct(Distinct),
+    Pin(Pin),
+    If(If),
+    WoqlOptional(WoqlOptional),
+    Once(Once),
+    Immediately(Immediately),
+    OrderBy(OrderBy),
+    GroupBy(GroupBy),
+    Start(Start),
+    Limit(Limit),
+    Count(Count),
+    LexicalKey(LexicalKey),
+    HashKey(HashKey),
+    RandomKey(RandomKey),
+    Size(Size),
+    TripleCount(TripleCount),
+    Call(Call),
+    Slice(Slice),
+    ListToSet(ListToSet),
+    SetUnion(SetUnion),
+    SetIntersection(SetIntersection),
+    SetDifference(SetDifference),
+    SetMember(SetMember),
+    Gte(Gte),
+    Lte(Lte),
+    Comment(Comment),
+    Collect(Collect),
+    Sequence(Sequence),
+    InRange(InRange),
+    RangeMin(RangeMin),
+    RangeMax(RangeMax),
+    TripleSlice(TripleSlice),
+    TripleSliceRev(TripleSliceRev),
+    TripleNext(TripleNext),
+    TriplePrevious(TriplePrevious),
+    Interval(Interval),
+    IntervalStartDuration(IntervalStartDuration),
+    IntervalDurationEnd(IntervalDurationEnd),
+    IntervalRelation(IntervalRelation),
+    IntervalRelationTyped(IntervalRelationTyped),
+    DateDuration(DateDuration),
+    DayAfter(DayAfter),
+    DayBefore(DayBefore),
+    IsoWeek(IsoWeek),
+    Weekday(Weekday),
+    WeekdaySundayStart(WeekdaySundayStart),
+    MonthStartDate(MonthStartDate),
+    MonthStartDates(MonthStartDates),
+    MonthEndDate(MonthEndDate),
+    MonthEndDates(MonthEndDates),
+}
+
+from_variants!(Query {
+    And(And),
+    Or(Or),
+    Not(Not),
+    True(True),
+    Triple(Triple),
+    AddTriple(AddTriple),
+    AddedTriple(AddedTriple),
+    DeleteTriple(DeleteTriple),
+    DeletedTriple(DeletedTriple),
+    Link(Link),
+    Data(Data),
+    AddLink(AddLink),
+    AddedLink(AddedLink),
+    AddData(AddData),
+    AddedData(AddedData),
+    DeleteLink(DeleteLink),
+    DeletedLink(DeletedLink),
+    Eval(Eval),
+    Path(Path),
+    ReadDocument(ReadDocument),
+    InsertDocument(InsertDocument),
+    UpdateDocument(UpdateDocument),
+    DeleteDocument(DeleteDocument),
+    Equals(Equals),
+    Less(Less),
+    Greater(Greater),
+    Subsumption(Subsumption),
+    IsA(IsA),
+    TypeOf(TypeOf),
+    Typecast(Typecast),
+    Trim(Trim),
+    Lower(Lower),
+    Upper(Upper),
+    Pad(Pad),
+    Split(Split),
+    Join(Join),
+    Concatenate(Concatenate),
+    Substring(Substring),
+    Regexp(Regexp),
+    Like(Like),
+    Member(Member),
+    Sum(Sum),
+    Length(Length),
+    Dot(Dot),
+    Get(Get),
+    Using(Using),
+    From(From),
+    Into(Into),
+    Select(Select),
+    Distinct(Distinct),
+    Pin(Pin),
+    If(If),
+    WoqlOptional(WoqlOptional),
+    Once(Once),
+    Immediately(Immediately),
+    OrderBy(OrderBy),
+    GroupBy(GroupBy),
+    Start(Start),
+    Limit(Limit),
+    Count(Count),
+    LexicalKey(LexicalKey),
+    HashKey(HashKey),
+    RandomKey(RandomKey),
+    Size(Size),
+    TripleCount(TripleCount),
+    Call(Call),
+    Slice(Slice),
+    ListToSet(ListToSet),
+    SetUnion(SetUnion),
+    SetIntersection(SetIntersection),
+    SetDifference(SetDifference),
+    SetMember(SetMember),
+    Gte(Gte),
+    Lte(Lte),
+    Comment(Comment),
+    Collect(Collect),
+    Sequence(Sequence),
+    InRange(InRange),
+    RangeMin(RangeMin),
+    RangeMax(RangeMax),
+    TripleSlice(TripleSlice),
+    TripleSliceRev(TripleSliceRev),
+    TripleNext(TripleNext),
+    TriplePrevious(TriplePrevious),
+    Interval(Interval),
+    IntervalStartDuration(IntervalStartDuration),
+    IntervalDurationEnd(IntervalDurationEnd),
+    IntervalRelation(IntervalRelation),
+    IntervalRelationTyped(IntervalRelationTyped),
+    DateDuration(DateDuration),
+    DayAfter(DayAfter),
+    DayBefore(DayBefore),
+    IsoWeek(IsoWeek),
+    Weekday(Weekday),
+    WeekdaySundayStart(WeekdaySundayStart),
+    MonthStartDate(MonthStartDate),
+    MonthStartDates(MonthStartDates),
+    MonthEndDate(MonthEndDate),
+    MonthEndDates(MonthEndDates),
+});
+
+/// A conjunction of queries which must all have a solution.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct And {
+    /// List of queries which must hold.
+    pub and: Vec<Query>,
+}
+
+/// A disjunction of queries any of which can provide a solution.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Or {
+    /// List of queries which may hold.
+    pub or: Vec<Query>,
+}
+
+/// The negation of a query. Provides no solution bindings, but will succeed if its sub-query fails.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Not {
+    /// The query which must not hold.
+    pub query: Box<Query>,
+}
+
+/// The query which is always true.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct True {}
+
+/// Evaluate an arithmetic expression to obtain a result.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Eval {
+    /// The expression to be evaluated.
+    pub expression: ArithmeticExpression,
+    /// The numeric result.
+    #[serde(rename = "result")]
+    pub result_value: ArithmeticValue,
+}
+
+/// Find a path through the graph according to 'pattern'. This 'pattern' is a regular graph expression which avoids cycles.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Path {
+    /// The starting node.
+    pub subject: Value,
+    /// The pattern which describes how to traverse edges.
+    pub pattern: PathPattern,
+    /// The ending node.
+    pub object: Value,
+    /// An optional list of edges traversed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Value>,
+}
+
+impl Query {
+    /// The query as WOQL JSON-LD, ready to send to the server
+    pub fn to_json(&self) -> serde_json::Value {
+        // every field serializes infallibly into a `Value`
+        serde_json::to_value(self).expect("WOQL AST serializes to JSON")
+    }
+}
@@ -0,0 +1,126 @@
+//! String manipulation
+
+use crate::value::{DataValue, ListOrVariable};
+use serde::Serialize;
+
+/// Trims whitespace from 'untrimmed'.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Trim {
+    /// The untrimmed string.
+    pub untrimmed: DataValue,
+    /// The string to be trimmed.
+    pub trimmed: DataValue,
+}
+
+/// Lowercase a string.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Lower {
+    /// The mixed case string.
+    pub mixed: DataValue,
+    /// The lower case string.
+    pub lower: DataValue,
+}
+
+/// Uppercase a string.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Upper {
+    /// The mixed case string.
+    pub mixed: DataValue,
+    /// The upper case string.
+    pub upper: DataValue,
+}
+
+/// Pad a string.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Pad {
+    /// The starting string.
+    pub string: DataValue,
+    /// The padding character.
+    pub char: DataValue,
+    /// The number of times to repeat the padding character.
+    pub times: DataValue,
+    /// The result of the padding as a string.
+    #[serde(rename = "result")]
+    pub result_string: DataValue,
+}
+
+/// Split a string.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Split {
+    /// The starting string.
+    pub string: DataValue,
+    /// The splitting pattern.
+    pub pattern: DataValue,
+    /// The result list of strings.
+    pub list: DataValue,
+}
+
+/// Join a list of strings using 'separator'.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Join {
+    /// The list to concatenate.
+    pub list: ListOrVariable,
+    /// The separator between each joined string
+    pub separator: DataValue,
+    /// The result string.
+    #[serde(rename = "result")]
+    pub result_string: DataValue,
+}
+
+/// Concatenate a list of strings.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Concatenate {
+    /// The list to concatenate.
+    pub list: ListOrVariable,
+    /// The result string.
+    #[serde(rename = "result")]
+    pub result_string: DataValue,
+}
+
+/// Finds the boundaries of a substring in a string.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Substring {
+    /// The super-string as data or variable.
+    pub string: DataValue,
+    /// The count of characters before substring as an integer or variable.
+    pub before: DataValue,
+    /// The length of the string as an integer or variable.
+    pub length: DataValue,
+    /// The count of characters after substring as an integer or variable.
+    pub after: DataValue,
+    /// The super-string as data or variable.
+    pub substring: DataValue,
+}
+
+/// Test a string against a PCRE style regex pattern.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Regexp {
+    /// The PCRE style pattern.
+    pub pattern: DataValue,
+    /// The string to test.
+    pub string: DataValue,
+    /// An optional result list of matches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<DataValue>,
+}
+
+/// Distance between strings, similar to a Levenstein distance.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Like {
+    /// The first string.
+    pub left: DataValue,
+    /// The second string.
+    pub right: DataValue,
+    /// Number between -1 and 1 which gives a scale for similarity.
+    pub similarity: DataValue,
+}
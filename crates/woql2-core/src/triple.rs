@@ -0,0 +1,280 @@
+//! Triple and quad patterns
+
+use crate::value::{DataValue, NodeValue, Value};
+use serde::{Serialize, Serializer};
+
+/// Specify an edge pattern in the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Triple {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// An optional graph (either 'instance' or 'schema'), defaults to 'instance' if not specified
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Specify an edge to add to the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddTriple {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// An optional graph (either 'instance' or 'schema'), defaults to 'instance' if not specified
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Specify an edge pattern which was *added* at *this commit*.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddedTriple {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// An optional graph (either 'instance' or 'schema'), defaults to 'instance' if not specified
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Specify an edge pattern to remove from the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DeleteTriple {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// An optional graph (either 'instance' or 'schema'), defaults to 'instance' if not specified
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Specify an edge pattern which was *deleted* at *this commit*.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DeletedTriple {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// An optional graph (either 'instance' or 'schema'), defaults to 'instance' if not specified
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Specify an edge pattern which is not terminal, but a link between objects.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Link {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI or variable which is the target or object of the graph edge.
+    pub object: NodeValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Specify an edge pattern which is terminal, and provides a data value association.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct Data {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A data type or variable which is the target or object of the graph edge.
+    pub object: DataValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Add an edge which links between nodes in the graph.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddLink {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI or variable which is the target or object of the graph edge.
+    pub object: NodeValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Specify an edge pattern which links between nodes at *this* commit.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddedLink {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI or variable which is the target or object of the graph edge.
+    pub object: NodeValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Add an edge with a data value.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddData {
+    /// A URI or variable which is the source or subject of the graph edge. The variable must be bound.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge. The variable must be bound.
+    pub predicate: NodeValue,
+    /// A data value or variable which is the target or object of the graph edge. The variable must be bound.
+    pub object: DataValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Specify an edge pattern with data value which was added in *this* commit*.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct AddedData {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A datatype or variable which is the target or object of the graph edge.
+    pub object: DataValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Delete an edge linking nodes.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DeleteLink {
+    /// A URI or variable which is the source or subject of the graph edge. The variable must be bound.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge. The variable must be bound.
+    pub predicate: NodeValue,
+    /// A URI or variable which is the target or object of the graph edge. The variable must be bound.
+    pub object: NodeValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// An edge pattern specifying a link beween nodes deleted *at this commit*.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DeletedLink {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI or variable which is the target or object of the graph edge.
+    pub object: NodeValue,
+    /// An optional graph (either 'instance' or 'schema')
+    pub graph: GraphType,
+}
+
+/// Ordered range query over triples: bind objects in the half-open range ['low', 'high'). (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TripleSlice {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// The inclusive lower bound of the object range.
+    pub low: Value,
+    /// The exclusive upper bound of the object range.
+    pub high: Value,
+    /// An optional graph (either 'instance' or 'schema')
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Ordered range query over triples in reverse order. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TripleSliceRev {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// The inclusive lower bound of the object range.
+    pub low: Value,
+    /// The exclusive upper bound of the object range.
+    pub high: Value,
+    /// An optional graph (either 'instance' or 'schema')
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Bind the next object after 'object' in the triple ordering. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TripleNext {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// The next object in the ordering.
+    pub next: Value,
+    /// An optional graph (either 'instance' or 'schema')
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// Bind the previous object before 'object' in the triple ordering. (TerminusDB 12)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct TriplePrevious {
+    /// A URI or variable which is the source or subject of the graph edge.
+    pub subject: NodeValue,
+    /// A URI or variable which is the edge-label or predicate of the graph edge.
+    pub predicate: NodeValue,
+    /// A URI, datatype or variable which is the target or object of the graph edge.
+    pub object: Value,
+    /// The previous object in the ordering.
+    pub previous: Value,
+    /// An optional graph (either 'instance' or 'schema')
+    #[serde(serialize_with = "serialize_graph")]
+    pub graph: Option<GraphType>,
+}
+
+/// The graph of a database a triple pattern refers to
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphType {
+    Schema,
+    #[default]
+    Instance,
+}
+
+/// Unset graphs are sent as `"instance"`, as the server requires the field
+fn serialize_graph<S: Serializer>(
+    graph: &Option<GraphType>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    graph.unwrap_or_default().serialize(serializer)
+}
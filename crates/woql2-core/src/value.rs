@@ -0,0 +1,103 @@
+//! Values, nodes and variables
+
+use crate::xsd::XSDAnySimpleType;
+use alloc::{string::String, vec::Vec};
+use serde::{Serialize, Serializer};
+
+/// A representation of a JSON style dictionary, but with free variables. It is similar to an interpolated string in that it is a template with quoted data and substituted values.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct FieldValuePair {
+    /// The field or key of a dictionary value pair
+    pub field: String,
+    /// The value of a dictionary value pair.
+    pub value: Value,
+}
+
+/// A representation of a JSON style dictionary, but with free variables. It is similar to an interpolated string in that it is a template with quoted data and substituted values.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub struct DictionaryTemplate {
+    /// Pairs of Key-Values to be constructed into a dictionary
+    pub data: Vec<FieldValuePair>,
+}
+
+/// A variable, node or data point.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// An xsd data type value.
+    Data(XSDAnySimpleType),
+    /// A dictionary.
+    Dictionary(DictionaryTemplate),
+    /// A list of datavalues
+    List(Vec<Value>),
+    /// A URI representing a resource.
+    Node(String),
+    /// A variable.
+    Variable(String),
+}
+
+tagged_union!(Value, "Value", {
+    Data => "data",
+    Dictionary => "dictionary",
+    List => "list",
+    Node => "node",
+    Variable => "variable",
+});
+
+/// A variable or node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeValue {
+    /// A URI representing a resource.
+    Node(String),
+    /// A variable.
+    Variable(String),
+}
+
+tagged_union!(NodeValue, "NodeValue", { Node => "node", Variable => "variable" });
+
+/// A variable or node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataValue {
+    /// An xsd data type value.
+    Data(XSDAnySimpleType),
+    /// A list of datavalues
+    List(Vec<DataValue>),
+    /// A variable.
+    Variable(String),
+}
+
+tagged_union!(DataValue, "DataValue", {
+    Data => "data",
+    List => "list",
+    Variable => "variable",
+});
+
+/// Represents either a list of values or a variable that will resolve to a list at runtime.
+/// Used in operations like Concatenate and Join that expect list inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListOrVariable {
+    /// A concrete list of data values
+    List(Vec<DataValue>),
+    /// A variable that will resolve to a list at runtime
+    Variable(DataValue),
+}
+
+impl From<DataValue> for ListOrVariable {
+    fn from(value: DataValue) -> Self {
+        match value {
+            DataValue::List(items) => ListOrVariable::List(items),
+            _ => ListOrVariable::Variable(value),
+        }
+    }
+}
+
+/// A list is a plain array of data values, anything else is the data value
+impl Serialize for ListOrVariable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ListOrVariable::List(items) => items.serialize(serializer),
+            ListOrVariable::Variable(value) => value.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Typed literals
+
+use alloc::string::String;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// An xsd literal. Unlike `terminusdb_schema::XSDAnySimpleType`, decimals and
+/// temporal values hold their lexical form, as no parsing libraries are
+/// available here; they are sent to the server as given.
+///
+/// Literals serialize the way `terminusdb-woql2` sends them: strings, floats,
+/// booleans, hex binaries, URIs and dates as plain JSON values, the others as
+/// `{"@type": "xsd:...", "@value": ...}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum XSDAnySimpleType {
+    String(String),
+    /// lexical form, e.g. `"12.50"`
+    Decimal(String),
+    Float(f64),
+    Boolean(bool),
+    HexBinary(String),
+    URI(String),
+    /// RFC 3339 timestamp
+    DateTime(String),
+    /// `YYYY-MM-DD`
+    Date(String),
+    /// `HH:MM:SS`, optionally with fractional seconds
+    Time(String),
+    UnsignedInt(u64),
+    Integer(i64),
+}
+
+impl XSDAnySimpleType {
+    /// The `xsd:` type of the literal
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::String(_) => "xsd:string",
+            Self::Decimal(_) => "xsd:decimal",
+            Self::Float(_) => "xsd:double",
+            Self::Boolean(_) => "xsd:boolean",
+            Self::HexBinary(_) => "xsd:hexBinary",
+            Self::URI(_) => "xsd:anyURI",
+            Self::DateTime(_) => "xsd:dateTime",
+            Self::Date(_) => "xsd:date",
+            Self::Time(_) => "xsd:time",
+            Self::UnsignedInt(_) => "xsd:unsignedInt",
+            Self::Integer(_) => "xsd:integer",
+        }
+    }
+}
+
+impl Serialize for XSDAnySimpleType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::String(s) | Self::HexBinary(s) | Self::URI(s) | Self::Date(s) => {
+                serializer.serialize_str(s)
+            }
+            Self::Float(f) => serializer.serialize_f64(*f),
+            Self::Boolean(b) => serializer.serialize_bool(*b),
+            Self::Decimal(s) | Self::DateTime(s) | Self::Time(s) => typed(serializer, self, s),
+            Self::UnsignedInt(n) => typed(serializer, self, n),
+            Self::Integer(n) => typed(serializer, self, n),
+        }
+    }
+}
+
+/// `{"@type": "xsd:...", "@value": value}`
+fn typed<S: Serializer, V: Serialize + ?Sized>(
+    serializer: S,
+    literal: &XSDAnySimpleType,
+    value: &V,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("@type", literal.type_name())?;
+    map.serialize_entry("@value", value)?;
+    map.end()
+}
+
+impl From<&str> for XSDAnySimpleType {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for XSDAnySimpleType {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<bool> for XSDAnySimpleType {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i64> for XSDAnySimpleType {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<u64> for XSDAnySimpleType {
+    fn from(value: u64) -> Self {
+        Self::UnsignedInt(value)
+    }
+}
+
+impl From<f64> for XSDAnySimpleType {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
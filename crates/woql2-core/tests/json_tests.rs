@@ -0,0 +1,186 @@
+use serde_json::json;
+use terminusdb_woql2_core::prelude::*;
+
+fn var(name: &str) -> NodeValue {
+    NodeValue::Variable(name.to_string())
+}
+
+fn node(iri: &str) -> NodeValue {
+    NodeValue::Node(iri.to_string())
+}
+
+#[test]
+fn test_nested_query_json() {
+    let query = Query::from(Limit {
+        limit: 10,
+        query: Box::new(Query::from(Select {
+            variables: vec!["Name".to_string()],
+            query: Box::new(Query::from(And {
+                and: vec![
+                    Query::from(Triple {
+                        subject: var("Person"),
+                        predicate: node("rdf:type"),
+                        object: Value::Node("@schema:Person".to_string()),
+                        graph: None,
+                    }),
+                    Query::from(Triple {
+                        subject: var("Person"),
+                        predicate: node("@schema:name"),
+                        object: Value::Variable("Name".to_string()),
+                        graph: Some(GraphType::Instance),
+                    }),
+                ],
+            })),
+        })),
+    });
+
+    assert_eq!(
+        query.to_json(),
+        json!({
+            "@type": "Limit",
+            "limit": 10,
+            "query": {
+                "@type": "Select",
+                "variables": ["Name"],
+                "query": {
+                    "@type": "And",
+                    "and": [
+                        {
+                            "@type": "Triple",
+                            "subject": {"@type": "NodeValue", "variable": "Person"},
+                            "predicate": {"@type": "NodeValue", "node": "rdf:type"},
+                            "object": {"@type": "Value", "node": "@schema:Person"},
+                            "graph": "instance"
+                        },
+                        {
+                            "@type": "Triple",
+                            "subject": {"@type": "NodeValue", "variable": "Person"},
+                            "predicate": {"@type": "NodeValue", "node": "@schema:name"},
+                            "object": {"@type": "Value", "variable": "Name"},
+                            "graph": "instance"
+                        }
+                    ]
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_renamed_classes_and_fields() {
+    let query = Query::from(If {
+        test: Box::new(Query::from(WoqlOptional {
+            query: Box::new(Query::from(True {})),
+        })),
+        then_query: Box::new(Query::from(IsA {
+            element: var("X"),
+            type_of: node("@schema:Person"),
+        })),
+        else_query: Box::new(Query::from(Not {
+            query: Box::new(Query::from(True {})),
+        })),
+    });
+
+    assert_eq!(
+        query.to_json(),
+        json!({
+            "@type": "If",
+            "test": {"@type": "Optional", "query": {"@type": "True"}},
+            "then": {
+                "@type": "IsA",
+                "element": {"@type": "NodeValue", "variable": "X"},
+                "type": {"@type": "NodeValue", "node": "@schema:Person"}
+            },
+            "else": {"@type": "Not", "query": {"@type": "True"}}
+        })
+    );
+}
+
+#[test]
+fn test_typed_literals_and_expressions() {
+    let query = Query::from(Eval {
+        expression: ArithmeticExpression::from(Plus {
+            left: Box::new(ArithmeticExpression::Value(ArithmeticValue::Data(
+                XSDAnySimpleType::Integer(2),
+            ))),
+            right: Box::new(ArithmeticExpression::Value(ArithmeticValue::Data(
+                XSDAnySimpleType::Decimal("0.5".to_string()),
+            ))),
+        }),
+        result_value: ArithmeticValue::Variable("Sum".to_string()),
+    });
+
+    assert_eq!(
+        query.to_json(),
+        json!({
+            "@type": "Eval",
+            "expression": {
+                "@type": "Plus",
+                "left": {
+                    "@type": "ArithmeticValue",
+                    "data": {"@type": "xsd:integer", "@value": 2}
+                },
+                "right": {
+                    "@type": "ArithmeticValue",
+                    "data": {"@type": "xsd:decimal", "@value": "0.5"}
+                }
+            },
+            "result": {"@type": "ArithmeticValue", "variable": "Sum"}
+        })
+    );
+}
+
+#[test]
+fn test_lists_orders_and_paths() {
+    let concat = Query::from(Concatenate {
+        list: ListOrVariable::List(vec![
+            DataValue::Data("Person/".into()),
+            DataValue::Variable("Id".to_string()),
+        ]),
+        result_string: DataValue::Variable("Iri".to_string()),
+    });
+    assert_eq!(
+        concat.to_json(),
+        json!({
+            "@type": "Concatenate",
+            "list": [
+                {"@type": "DataValue", "data": "Person/"},
+                {"@type": "DataValue", "variable": "Id"}
+            ],
+            "result": {"@type": "DataValue", "variable": "Iri"}
+        })
+    );
+
+    let order = Query::from(OrderBy {
+        ordering: vec![OrderTemplate {
+            variable: "Name".to_string(),
+            order: Order::Desc,
+        }],
+        query: Box::new(Query::from(Path {
+            subject: Value::Variable("A".to_string()),
+            pattern: PathPattern::from(PathPlus {
+                plus: Box::new(PathPattern::from(PathPredicate {
+                    predicate: Some("knows".to_string()),
+                })),
+            }),
+            object: Value::Variable("B".to_string()),
+            path: None,
+        })),
+    });
+    assert_eq!(
+        order.to_json(),
+        json!({
+            "@type": "OrderBy",
+            "ordering": [{"@type": "OrderTemplate", "variable": "Name", "order": "desc"}],
+            "query": {
+                "@type": "Path",
+                "subject": {"@type": "Value", "variable": "A"},
+                "pattern": {
+                    "@type": "PathPlus",
+                    "plus": {"@type": "PathPredicate", "predicate": "knows"}
+                },
+                "object": {"@type": "Value", "variable": "B"}
+            }
+        })
+    );
+}
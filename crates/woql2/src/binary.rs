@@ -0,0 +1,407 @@
+//! Compact binary encoding of WOQL queries.
+//!
+//! [`Query::to_binary`] stores a query in a fraction of the size of its JSON-LD,
+//! for caching on disk or passing between services; [`PreparedQuery`] has the
+//! same methods, which keep its parameter types.
+//!
+//! ```
+//! # use terminusdb_woql2::*;
+//! let query = and!(
+//!     triple!(var!(person), "rdf:type", "@schema:Person"),
+//!     triple!(var!(person), "@schema:name", var!(name))
+//! );
+//!
+//! let bytes = query.to_binary();
+//! assert!(bytes.len() < query.to_woql_json().to_string().len() / 2);
+//! assert_eq!(binary::decode(&bytes).unwrap(), query.to_woql_json());
+//! ```
+//!
+//! The encoding holds the serialized WOQL, encoded with `postcard`, with every
+//! string stored once in a table. Common class and field names are not stored
+//! at all but refer to a fixed vocabulary. The data starts with [`MAGIC`] and a
+//! [`FORMAT_VERSION`] byte; decoding rejects data written by another version,
+//! so a cache can discard such entries and encode the query again.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::prepared::PreparedQuery;
+use crate::query::Query;
+
+/// First bytes of every encoded query.
+pub const MAGIC: [u8; 4] = *b"WOQB";
+
+/// Version of the encoding written by [`encode`].
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Strings every encoding can refer to without storing them. Changing this
+/// list changes the format, so it requires a new [`FORMAT_VERSION`].
+const VOCABULARY: [&str; 96] = [
+    "@type",
+    "@value",
+    "Value",
+    "NodeValue",
+    "DataValue",
+    "ArithmeticValue",
+    "node",
+    "variable",
+    "data",
+    "list",
+    "dictionary",
+    "DictionaryTemplate",
+    "FieldValuePair",
+    "field",
+    "value",
+    "subject",
+    "predicate",
+    "object",
+    "graph",
+    "instance",
+    "schema",
+    "query",
+    "Triple",
+    "AddTriple",
+    "DeleteTriple",
+    "Link",
+    "Data",
+    "And",
+    "and",
+    "Or",
+    "or",
+    "Not",
+    "Optional",
+    "True",
+    "If",
+    "test",
+    "then",
+    "else",
+    "Select",
+    "Distinct",
+    "variables",
+    "Limit",
+    "limit",
+    "Start",
+    "start",
+    "Count",
+    "count",
+    "OrderBy",
+    "ordering",
+    "OrderTemplate",
+    "order",
+    "asc",
+    "desc",
+    "GroupBy",
+    "template",
+    "group_by",
+    "grouped",
+    "Equals",
+    "Greater",
+    "Less",
+    "left",
+    "right",
+    "IsA",
+    "element",
+    "type",
+    "Typecast",
+    "result",
+    "Eval",
+    "expression",
+    "Plus",
+    "Minus",
+    "Times",
+    "Divide",
+    "ReadDocument",
+    "InsertDocument",
+    "UpdateDocument",
+    "DeleteDocument",
+    "identifier",
+    "document",
+    "Path",
+    "pattern",
+    "path",
+    "PathPredicate",
+    "InversePathPredicate",
+    "PathSequence",
+    "PathOr",
+    "PathPlus",
+    "PathStar",
+    "PathTimes",
+    "sequence",
+    "plus",
+    "star",
+    "Using",
+    "collection",
+    "rdf:type",
+    "xsd:string",
+];
+
+/// Error returned when bytes can't be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The bytes don't start with [`MAGIC`].
+    NotWoqlBinary,
+    /// The bytes were written by a version this one can't read.
+    UnsupportedVersion(u8),
+    /// The bytes are truncated or corrupt.
+    Malformed(String),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::NotWoqlBinary => write!(f, "not an encoded WOQL query"),
+            BinaryError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported WOQL binary version {} (expected {})",
+                version, FORMAT_VERSION
+            ),
+            BinaryError::Malformed(message) => write!(f, "malformed WOQL binary: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+/// A JSON value with strings replaced by their index in the string table
+#[derive(Serialize, Deserialize)]
+enum Node {
+    Null,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    /// Any other number, in its JSON form so no precision is lost
+    Number(String),
+    String(u32),
+    Array(Vec<Node>),
+    Object(Vec<(u32, Node)>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Encoded {
+    strings: Vec<String>,
+    root: Node,
+}
+
+/// Encode serialized WOQL, such as the output of
+/// [`to_woql_json`](Query::to_woql_json).
+pub fn encode(json: &JsonValue) -> Vec<u8> {
+    let mut strings = Strings::new();
+    let encoded = Encoded {
+        root: strings.node(json),
+        strings: strings.table,
+    };
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(FORMAT_VERSION);
+    // Serializing into a Vec only fails when allocation does
+    postcard::to_extend(&encoded, bytes).expect("WOQL binary encoding")
+}
+
+/// Decode bytes written by [`encode`] back into serialized WOQL.
+pub fn decode(bytes: &[u8]) -> Result<JsonValue, BinaryError> {
+    let payload = bytes
+        .strip_prefix(&MAGIC[..])
+        .ok_or(BinaryError::NotWoqlBinary)?;
+    let (&version, payload) = payload
+        .split_first()
+        .ok_or_else(|| BinaryError::Malformed("missing version".to_string()))?;
+    if version != FORMAT_VERSION {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+
+    let encoded: Encoded =
+        postcard::from_bytes(payload).map_err(|e| BinaryError::Malformed(e.to_string()))?;
+    json(encoded.root, &encoded.strings)
+}
+
+/// Interns strings while building the encoded tree. Indices below the
+/// length of [`VOCABULARY`] refer to it, later ones to the table.
+struct Strings {
+    table: Vec<String>,
+    index: HashMap<String, u32>,
+}
+
+impl Strings {
+    fn new() -> Self {
+        let index = VOCABULARY
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.to_string(), i as u32))
+            .collect();
+        Self {
+            table: Vec::new(),
+            index,
+        }
+    }
+
+    fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&i) = self.index.get(s) {
+            return i;
+        }
+        let i = (VOCABULARY.len() + self.table.len()) as u32;
+        self.table.push(s.to_string());
+        self.index.insert(s.to_string(), i);
+        i
+    }
+
+    fn node(&mut self, json: &JsonValue) -> Node {
+        match json {
+            JsonValue::Null => Node::Null,
+            JsonValue::Bool(b) => Node::Bool(*b),
+            JsonValue::Number(n) => {
+                if let Some(u) = n.as_u64() {
+                    Node::UInt(u)
+                } else if let Some(i) = n.as_i64() {
+                    Node::Int(i)
+                } else {
+                    Node::Number(n.to_string())
+                }
+            }
+            JsonValue::String(s) => Node::String(self.intern(s)),
+            JsonValue::Array(items) => Node::Array(items.iter().map(|v| self.node(v)).collect()),
+            JsonValue::Object(map) => Node::Object(
+                map.iter()
+                    .map(|(k, v)| (self.intern(k), self.node(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn json(node: Node, strings: &[String]) -> Result<JsonValue, BinaryError> {
+    let string = |i: u32| {
+        let i = i as usize;
+        let s = match i.checked_sub(VOCABULARY.len()) {
+            None => Some(VOCABULARY[i]),
+            Some(j) => strings.get(j).map(String::as_str),
+        };
+        s.map(str::to_string)
+            .ok_or_else(|| BinaryError::Malformed(format!("string {} out of range", i)))
+    };
+    Ok(match node {
+        Node::Null => JsonValue::Null,
+        Node::Bool(b) => JsonValue::Bool(b),
+        Node::UInt(u) => JsonValue::from(u),
+        Node::Int(i) => JsonValue::from(i),
+        Node::Number(n) => JsonValue::Number(
+            n.parse::<Number>()
+                .map_err(|e| BinaryError::Malformed(e.to_string()))?,
+        ),
+        Node::String(i) => JsonValue::String(string(i)?),
+        Node::Array(items) => JsonValue::Array(
+            items
+                .into_iter()
+                .map(|v| json(v, strings))
+                .collect::<Result<_, _>>()?,
+        ),
+        Node::Object(entries) => {
+            let mut map = Map::new();
+            for (k, v) in entries {
+                map.insert(string(k)?, json(v, strings)?);
+            }
+            JsonValue::Object(map)
+        }
+    })
+}
+
+impl Query {
+    /// Encode the query; see the [module docs](self).
+    pub fn to_binary(&self) -> Vec<u8> {
+        encode(&self.to_woql_json())
+    }
+
+    /// Decode a query written by [`to_binary`](Self::to_binary).
+    pub fn from_binary(bytes: &[u8]) -> anyhow::Result<Query> {
+        use terminusdb_schema::FromTDBInstance;
+        Query::from_json(decode(bytes)?)
+    }
+}
+
+impl PreparedQuery {
+    /// Encode the query template together with its parameter types.
+    pub fn to_binary(&self) -> Vec<u8> {
+        encode(&self.to_json())
+    }
+
+    /// Decode a prepared query written by [`to_binary`](Self::to_binary).
+    pub fn from_binary(bytes: &[u8]) -> anyhow::Result<PreparedQuery> {
+        PreparedQuery::from_json(decode(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prepared::ParamType;
+    use crate::{and, greater, limit, param, select, triple, var};
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let query = limit!(
+            10,
+            select!(
+                [name],
+                and!(
+                    triple!(var!(person), "rdf:type", "@schema:Person"),
+                    triple!(var!(person), "@schema:name", var!(name))
+                )
+            )
+        );
+
+        let json = query.to_woql_json();
+        let bytes = query.to_binary();
+        assert_eq!(&bytes[..5], b"WOQB\x01");
+        assert_eq!(decode(&bytes).unwrap(), json);
+        assert!(bytes.len() * 2 < json.to_string().len());
+    }
+
+    #[test]
+    fn test_numbers_keep_their_value() {
+        let json = json!({
+            "small": -3,
+            "large": u64::MAX,
+            "float": 0.1,
+            "decimal": {"@type": "xsd:decimal", "@value": "1.50"},
+            "list": [null, true, "", "@type"]
+        });
+
+        assert_eq!(decode(&encode(&json)).unwrap(), json);
+    }
+
+    #[test]
+    fn test_rejects_other_data() {
+        let mut bytes = encode(&json!({"@type": "True"}));
+        assert_eq!(decode(b"{}"), Err(BinaryError::NotWoqlBinary));
+        assert!(matches!(
+            decode(&bytes[..bytes.len() - 1]),
+            Err(BinaryError::Malformed(_))
+        ));
+
+        bytes[4] = FORMAT_VERSION + 1;
+        assert_eq!(
+            decode(&bytes),
+            Err(BinaryError::UnsupportedVersion(FORMAT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn test_prepared_query_keeps_types() {
+        let prepared = PreparedQuery::new(and!(
+            triple!(var!(person), "age", var!(age)),
+            greater!(var!(age), param!(min_age))
+        ))
+        .with_type("min_age", ParamType::Integer);
+
+        let decoded = PreparedQuery::from_binary(&prepared.to_binary()).unwrap();
+        assert_eq!(decoded.parameters().collect::<Vec<_>>(), vec!["min_age"]);
+        assert_eq!(
+            decoded.bind([("min_age", 18)]).unwrap(),
+            prepared.bind([("min_age", 18)]).unwrap()
+        );
+        assert!(decoded.bind([("min_age", "eighteen")]).is_err());
+    }
+}
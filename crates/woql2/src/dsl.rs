@@ -1,16 +1,65 @@
 /// Module for rendering WOQL queries to DSL syntax
 use std::fmt;
 
+pub use crate::dsl_format::DslFormat;
+
 /// Trait for rendering WOQL queries to DSL syntax
 pub trait ToDSL {
     /// Render this value/query to DSL syntax
     fn to_dsl(&self) -> String;
+
+    /// Render this value/query to DSL syntax laid out over several lines
+    /// according to `format`
+    fn to_dsl_with(&self, format: &DslFormat) -> String {
+        format.format(&self.to_dsl())
+    }
+
+    /// Render this value/query to DSL syntax laid out with the default
+    /// [`DslFormat`], for logs and other output read by people
+    fn to_dsl_pretty(&self) -> String {
+        self.to_dsl_with(&DslFormat::default())
+    }
 }
 
-/// Helper function to escape string literals for DSL
+/// Helper function to quote and escape string literals for DSL
 fn escape_string(s: &str) -> String {
-    // For now, just wrap in quotes and escape internal quotes
-    format!("\"{}\"", s.replace('"', "\\\""))
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Whether a quoted string in a value position reads back as a node.
+///
+/// Strings starting with `@` or containing `:` (`"@schema:Person"`,
+/// `"rdf:type"`) are nodes, anything else is string data. Values that don't
+/// follow this rule are rendered explicitly as `iri("...")` or `string("...")`.
+pub fn is_node_literal(s: &str) -> bool {
+    s.starts_with('@') || s.contains(':')
+}
+
+/// Render a typed literal as `literal(value, "xsd:type")`
+fn render_literal(value: String, xsd_type: &str) -> String {
+    render_function("literal", &[value, escape_string(xsd_type)])
+}
+
+/// Render a graph argument as `"instance"` or `"schema"`
+fn render_graph(graph: &GraphType) -> String {
+    match graph {
+        GraphType::Instance => escape_string("instance"),
+        GraphType::Schema => escape_string("schema"),
+    }
 }
 
 /// Helper function to render a list of items with a separator
@@ -22,6 +71,24 @@ fn render_list<T: ToDSL>(items: &[T], separator: &str) -> String {
         .join(separator)
 }
 
+/// Render a variable as `$name`, quoting names that aren't identifiers
+fn render_variable(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if is_identifier {
+        format!("${}", name)
+    } else {
+        format!("${}", escape_string(name))
+    }
+}
+
+/// Render a list of variable names as `[$a, $b]`
+fn render_variable_list(names: &[String]) -> String {
+    let vars: Vec<String> = names.iter().map(|name| render_variable(name)).collect();
+    format!("[{}]", vars.join(", "))
+}
+
 /// Helper function to render a function call
 fn render_function(name: &str, args: &[String]) -> String {
     format!("{}({})", name, args.join(", "))
@@ -33,29 +100,37 @@ fn render_list_value<T: ToDSL>(items: &[T]) -> String {
 }
 
 // Implementations for value types
-use crate::value::{DataValue, ListOrVariable, NodeValue, Value};
+use crate::value::{DataValue, DictionaryTemplate, ListOrVariable, NodeValue, Value};
 use terminusdb_schema::XSDAnySimpleType;
 
 impl ToDSL for Value {
     fn to_dsl(&self) -> String {
         match self {
-            Value::Variable(var) => format!("${}", var),
-            Value::Node(uri) => escape_string(uri),
+            Value::Variable(var) => render_variable(var),
+            Value::Node(uri) if is_node_literal(uri) => escape_string(uri),
+            Value::Node(uri) => render_function("iri", &[escape_string(uri)]),
             Value::Data(data) => data.to_dsl(),
             Value::List(items) => render_list_value(items),
-            Value::Dictionary(_dict) => {
-                // Dictionary templates are complex, for now just render as empty dict
-                // This would need more work to properly render field-value pairs
-                "{}".to_string()
-            }
+            Value::Dictionary(dict) => dict.to_dsl(),
         }
     }
 }
 
+impl ToDSL for DictionaryTemplate {
+    fn to_dsl(&self) -> String {
+        let pairs: Vec<String> = self
+            .data
+            .iter()
+            .map(|pair| format!("{}: {}", escape_string(&pair.field), pair.value.to_dsl()))
+            .collect();
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
+
 impl ToDSL for NodeValue {
     fn to_dsl(&self) -> String {
         match self {
-            NodeValue::Variable(var) => format!("${}", var),
+            NodeValue::Variable(var) => render_variable(var),
             NodeValue::Node(uri) => escape_string(uri),
         }
     }
@@ -64,7 +139,7 @@ impl ToDSL for NodeValue {
 impl ToDSL for DataValue {
     fn to_dsl(&self) -> String {
         match self {
-            DataValue::Variable(var) => format!("${}", var),
+            DataValue::Variable(var) => render_variable(var),
             DataValue::Data(data) => data.to_dsl(),
             DataValue::List(items) => render_list_value(items),
         }
@@ -89,15 +164,48 @@ impl ToDSL for ListOrVariable {
 impl ToDSL for XSDAnySimpleType {
     fn to_dsl(&self) -> String {
         match self {
+            XSDAnySimpleType::String(s) if is_node_literal(s) => {
+                render_function("string", &[escape_string(s)])
+            }
             XSDAnySimpleType::String(s) => escape_string(s),
             XSDAnySimpleType::Boolean(b) => b.to_string(),
-            XSDAnySimpleType::Integer(i) => i.to_string(),
-            XSDAnySimpleType::Float(f) => f.to_string(),
-            XSDAnySimpleType::Decimal(d) => d.to_string(),
-            XSDAnySimpleType::DateTime(dt) => escape_string(&dt.to_string()),
-            XSDAnySimpleType::Date(d) => escape_string(&d.to_string()),
-            XSDAnySimpleType::Time(t) => escape_string(&t.to_string()),
-            _ => escape_string(&format!("{:?}", self)),
+            // Bare numbers read back as floats; everything else is typed
+            XSDAnySimpleType::Float(f) if f.is_finite() => f.to_string(),
+            XSDAnySimpleType::Float(f) => {
+                render_literal(escape_string(&f.to_string()), "xsd:double")
+            }
+            XSDAnySimpleType::Integer(i) => render_literal(i.to_string(), "xsd:integer"),
+            XSDAnySimpleType::UnsignedInt(i) => render_literal(i.to_string(), "xsd:unsignedInt"),
+            XSDAnySimpleType::Decimal(d) => {
+                render_literal(escape_string(&d.to_string()), "xsd:decimal")
+            }
+            XSDAnySimpleType::DateTime(dt) => {
+                render_literal(escape_string(&dt.to_rfc3339()), "xsd:dateTime")
+            }
+            XSDAnySimpleType::Date(d) => render_literal(escape_string(&d.to_string()), "xsd:date"),
+            XSDAnySimpleType::Time(t) => render_literal(escape_string(&t.to_string()), "xsd:time"),
+            XSDAnySimpleType::HexBinary(h) => render_literal(escape_string(h), "xsd:hexBinary"),
+            XSDAnySimpleType::URI(u) => render_literal(escape_string(u), "xsd:anyURI"),
+        }
+    }
+}
+
+/// Arbitrary JSON, as used for `get` resource options
+impl ToDSL for serde_json::Value {
+    fn to_dsl(&self) -> String {
+        match self {
+            serde_json::Value::Null => "null".to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::String(s) => escape_string(s),
+            serde_json::Value::Array(items) => render_list_value(items),
+            serde_json::Value::Object(map) => {
+                let pairs: Vec<String> = map
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", escape_string(key), value.to_dsl()))
+                    .collect();
+                format!("{{{}}}", pairs.join(", "))
+            }
         }
     }
 }
@@ -177,26 +285,71 @@ impl ToDSL for Query {
             Query::MonthStartDates(op) => op.to_dsl(),
             Query::MonthEndDate(op) => op.to_dsl(),
             Query::MonthEndDates(op) => op.to_dsl(),
-            // Add more as needed
-            _ => format!("// TODO: {}", std::any::type_name_of_val(self)),
+            Query::AddTriple(op) => op.to_dsl(),
+            Query::AddedTriple(op) => op.to_dsl(),
+            Query::DeleteTriple(op) => op.to_dsl(),
+            Query::DeletedTriple(op) => op.to_dsl(),
+            Query::Link(op) => op.to_dsl(),
+            Query::Data(op) => op.to_dsl(),
+            Query::AddLink(op) => op.to_dsl(),
+            Query::AddedLink(op) => op.to_dsl(),
+            Query::AddData(op) => op.to_dsl(),
+            Query::AddedData(op) => op.to_dsl(),
+            Query::DeleteLink(op) => op.to_dsl(),
+            Query::DeletedLink(op) => op.to_dsl(),
+            Query::Typecast(op) => op.to_dsl(),
+            Query::Pad(op) => op.to_dsl(),
+            Query::Split(op) => op.to_dsl(),
+            Query::Like(op) => op.to_dsl(),
+            Query::Member(op) => op.to_dsl(),
+            Query::Length(op) => op.to_dsl(),
+            Query::Dot(op) => op.to_dsl(),
+            Query::Get(op) => op.to_dsl(),
+            Query::Using(op) => op.to_dsl(),
+            Query::From(op) => op.to_dsl(),
+            Query::Into(op) => op.to_dsl(),
+            Query::Pin(op) => op.to_dsl(),
+            Query::If(op) => op.to_dsl(),
+            Query::Once(op) => op.to_dsl(),
+            Query::Immediately(op) => op.to_dsl(),
+            Query::LexicalKey(op) => op.to_dsl(),
+            Query::HashKey(op) => op.to_dsl(),
+            Query::RandomKey(op) => op.to_dsl(),
+            Query::Size(op) => op.to_dsl(),
+            Query::TripleCount(op) => op.to_dsl(),
+            Query::Call(op) => op.to_dsl(),
         }
     }
 }
 
+/// Arguments of a triple-shaped operation. The graph is only rendered when it
+/// isn't the default instance graph.
+fn triple_args(
+    subject: &NodeValue,
+    predicate: &NodeValue,
+    object: &dyn ToDSL,
+    extra: &[String],
+    graph: Option<&GraphType>,
+) -> Vec<String> {
+    let mut args = vec![subject.to_dsl(), predicate.to_dsl(), object.to_dsl()];
+    args.extend_from_slice(extra);
+    if let Some(graph @ GraphType::Schema) = graph {
+        args.push(render_graph(graph));
+    }
+    args
+}
+
 impl ToDSL for Triple {
     fn to_dsl(&self) -> String {
-        let mut args = vec![
-            self.subject.to_dsl(),
-            self.predicate.to_dsl(),
-            self.object.to_dsl(),
-        ];
-
-        // Only add graph parameter if it's not the default. Both `None` and
-        // `Some(Instance)` mean the default instance graph, so omit the arg.
-        if !matches!(self.graph, None | Some(GraphType::Instance)) {
-            args.push(escape_string(&format!("{:?}", self.graph).to_lowercase()));
-        }
-
+        // Both `None` and `Some(Instance)` mean the default instance graph, so
+        // the graph argument is omitted for either.
+        let args = triple_args(
+            &self.subject,
+            &self.predicate,
+            &self.object,
+            &[],
+            self.graph.as_ref(),
+        );
         render_function("triple", &args)
     }
 }
@@ -238,28 +391,14 @@ use crate::string::{Concatenate, Join, Lower, Regexp, Substring, Trim, Upper};
 
 impl ToDSL for Select {
     fn to_dsl(&self) -> String {
-        let vars = format!(
-            "[{}]",
-            self.variables
-                .iter()
-                .map(|v| format!("${}", v))
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
+        let vars = render_variable_list(&self.variables);
         render_function("select", &[vars, self.query.to_dsl()])
     }
 }
 
 impl ToDSL for Distinct {
     fn to_dsl(&self) -> String {
-        let vars = format!(
-            "[{}]",
-            self.variables
-                .iter()
-                .map(|v| format!("${}", v))
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
+        let vars = render_variable_list(&self.variables);
         render_function("distinct", &[vars, self.query.to_dsl()])
     }
 }
@@ -289,20 +428,13 @@ impl ToDSL for OrderTemplate {
             Order::Asc => "asc",
             Order::Desc => "desc",
         };
-        format!("{}(${})", order_fn, self.variable)
+        format!("{}({})", order_fn, render_variable(&self.variable))
     }
 }
 
 impl ToDSL for GroupBy {
     fn to_dsl(&self) -> String {
-        let group_vars = format!(
-            "[{}]",
-            self.group_by
-                .iter()
-                .map(|v| format!("${}", v))
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
+        let group_vars = render_variable_list(&self.group_by);
         render_function(
             "group_by",
             &[
@@ -494,7 +626,15 @@ impl ToDSL for ArithmeticExpression {
             ArithmeticExpression::Div(div) => {
                 render_function("div", &[div.left.to_dsl(), div.right.to_dsl()])
             }
-            _ => format!("// TODO: arithmetic {:?}", self),
+            ArithmeticExpression::Divide(divide) => {
+                render_function("divide", &[divide.left.to_dsl(), divide.right.to_dsl()])
+            }
+            ArithmeticExpression::Exp(exp) => {
+                render_function("exp", &[exp.left.to_dsl(), exp.right.to_dsl()])
+            }
+            ArithmeticExpression::Floor(floor) => {
+                render_function("floor", &[floor.argument.to_dsl()])
+            }
         }
     }
 }
@@ -502,7 +642,7 @@ impl ToDSL for ArithmeticExpression {
 impl ToDSL for ArithmeticValue {
     fn to_dsl(&self) -> String {
         match self {
-            ArithmeticValue::Variable(var) => format!("${}", var),
+            ArithmeticValue::Variable(var) => render_variable(var),
             ArithmeticValue::Data(data) => data.to_dsl(),
         }
     }
@@ -511,6 +651,7 @@ impl ToDSL for ArithmeticValue {
 // Path patterns
 use crate::path::{
     InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar,
+    PathTimes,
 };
 
 impl ToDSL for Path {
@@ -536,7 +677,7 @@ impl ToDSL for PathPattern {
             PathPattern::Plus(plus) => plus.to_dsl(),
             PathPattern::Sequence(seq) => seq.to_dsl(),
             PathPattern::Or(or) => or.to_dsl(),
-            _ => format!("// TODO: path pattern {:?}", self),
+            PathPattern::Times(times) => times.to_dsl(),
         }
     }
 }
@@ -572,6 +713,15 @@ impl ToDSL for PathPlus {
     }
 }
 
+impl ToDSL for PathTimes {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "times",
+            &[self.times.to_dsl(), self.from.to_string(), self.to.to_string()],
+        )
+    }
+}
+
 impl ToDSL for PathSequence {
     fn to_dsl(&self) -> String {
         let patterns: Vec<String> = self.sequence.iter().map(|p| p.to_dsl()).collect();
@@ -976,8 +1126,11 @@ impl ToDSL for Sequence {
         if let Some(step) = &self.step {
             args.push(step.to_dsl());
         }
-        if let Some(count) = &self.count {
-            args.push(count.to_dsl());
+        // Without a step, a positional fourth argument would read as the step
+        match (&self.step, &self.count) {
+            (Some(_), Some(count)) => args.push(count.to_dsl()),
+            (None, Some(count)) => args.push(format!("count={}", count.to_dsl())),
+            (_, None) => {}
         }
         render_function("sequence", &args)
     }
@@ -1004,29 +1157,14 @@ impl ToDSL for RangeMax {
     }
 }
 
-fn triple_slice_args(
-    subject: &crate::value::NodeValue,
-    predicate: &crate::value::NodeValue,
-    object: &crate::value::Value,
-    extra: &[String],
-    graph: &Option<GraphType>,
-) -> Vec<String> {
-    let mut args = vec![subject.to_dsl(), predicate.to_dsl(), object.to_dsl()];
-    args.extend_from_slice(extra);
-    if *graph != None && *graph != Some(GraphType::Instance) {
-        args.push(escape_string(&format!("{:?}", graph).to_lowercase()));
-    }
-    args
-}
-
 impl ToDSL for TripleSlice {
     fn to_dsl(&self) -> String {
-        let args = triple_slice_args(
+        let args = triple_args(
             &self.subject,
             &self.predicate,
             &self.object,
             &[self.low.to_dsl(), self.high.to_dsl()],
-            &self.graph,
+            self.graph.as_ref(),
         );
         render_function("triple_slice", &args)
     }
@@ -1034,12 +1172,12 @@ impl ToDSL for TripleSlice {
 
 impl ToDSL for TripleSliceRev {
     fn to_dsl(&self) -> String {
-        let args = triple_slice_args(
+        let args = triple_args(
             &self.subject,
             &self.predicate,
             &self.object,
             &[self.low.to_dsl(), self.high.to_dsl()],
-            &self.graph,
+            self.graph.as_ref(),
         );
         render_function("triple_slice_rev", &args)
     }
@@ -1047,12 +1185,12 @@ impl ToDSL for TripleSliceRev {
 
 impl ToDSL for TripleNext {
     fn to_dsl(&self) -> String {
-        let args = triple_slice_args(
+        let args = triple_args(
             &self.subject,
             &self.predicate,
             &self.object,
             &[self.next.to_dsl()],
-            &self.graph,
+            self.graph.as_ref(),
         );
         render_function("triple_next", &args)
     }
@@ -1060,12 +1198,12 @@ impl ToDSL for TripleNext {
 
 impl ToDSL for TriplePrevious {
     fn to_dsl(&self) -> String {
-        let args = triple_slice_args(
+        let args = triple_args(
             &self.subject,
             &self.predicate,
             &self.object,
             &[self.previous.to_dsl()],
-            &self.graph,
+            self.graph.as_ref(),
         );
         render_function("triple_previous", &args)
     }
@@ -1202,3 +1340,263 @@ impl ToDSL for MonthEndDates {
         )
     }
 }
+
+// ---------------------------------------------------------------------------
+// Triple variants, control flow, keys and resources
+// ---------------------------------------------------------------------------
+
+use crate::collection::{Dot, Length, Member};
+use crate::compare::Typecast;
+use crate::control::{From, If, Immediately, Into, Once, Pin, Using};
+use crate::get::{Column, Get, Indicator, QueryResource, Source};
+use crate::misc::{HashKey, LexicalKey, RandomKey, Size, TripleCount};
+use crate::query::Call;
+use crate::string::{Like, Pad, Split};
+use crate::triple::{
+    AddData, AddLink, AddTriple, AddedData, AddedLink, AddedTriple, Data, DeleteLink, DeleteTriple,
+    DeletedLink, DeletedTriple, Link,
+};
+
+macro_rules! impl_triple_dsl {
+    ($name:literal, $ty:ty, optional_graph) => {
+        impl ToDSL for $ty {
+            fn to_dsl(&self) -> String {
+                let args = triple_args(
+                    &self.subject,
+                    &self.predicate,
+                    &self.object,
+                    &[],
+                    self.graph.as_ref(),
+                );
+                render_function($name, &args)
+            }
+        }
+    };
+    ($name:literal, $ty:ty) => {
+        impl ToDSL for $ty {
+            fn to_dsl(&self) -> String {
+                let args = triple_args(
+                    &self.subject,
+                    &self.predicate,
+                    &self.object,
+                    &[],
+                    Some(&self.graph),
+                );
+                render_function($name, &args)
+            }
+        }
+    };
+}
+
+impl_triple_dsl!("add_triple", AddTriple, optional_graph);
+impl_triple_dsl!("added_triple", AddedTriple, optional_graph);
+impl_triple_dsl!("delete_triple", DeleteTriple, optional_graph);
+impl_triple_dsl!("deleted_triple", DeletedTriple, optional_graph);
+impl_triple_dsl!("link", Link);
+impl_triple_dsl!("data", Data);
+impl_triple_dsl!("add_link", AddLink);
+impl_triple_dsl!("added_link", AddedLink);
+impl_triple_dsl!("add_data", AddData);
+impl_triple_dsl!("added_data", AddedData);
+impl_triple_dsl!("delete_link", DeleteLink);
+impl_triple_dsl!("deleted_link", DeletedLink);
+
+impl ToDSL for Typecast {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "typecast",
+            &[self.value.to_dsl(), self.type_uri.to_dsl(), self.result_value.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for Pad {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "pad",
+            &[
+                self.string.to_dsl(),
+                self.char.to_dsl(),
+                self.times.to_dsl(),
+                self.result_string.to_dsl(),
+            ],
+        )
+    }
+}
+
+impl ToDSL for Split {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "split",
+            &[self.string.to_dsl(), self.pattern.to_dsl(), self.list.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for Like {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "like",
+            &[self.left.to_dsl(), self.right.to_dsl(), self.similarity.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for Member {
+    fn to_dsl(&self) -> String {
+        render_function("member", &[self.member.to_dsl(), self.list.to_dsl()])
+    }
+}
+
+impl ToDSL for Length {
+    fn to_dsl(&self) -> String {
+        render_function("length", &[self.list.to_dsl(), self.length.to_dsl()])
+    }
+}
+
+impl ToDSL for Dot {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "dot",
+            &[self.document.to_dsl(), self.field.to_dsl(), self.value.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for Using {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "using",
+            &[escape_string(&self.collection), self.query.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for From {
+    fn to_dsl(&self) -> String {
+        render_function("from", &[escape_string(&self.graph), self.query.to_dsl()])
+    }
+}
+
+impl ToDSL for Into {
+    fn to_dsl(&self) -> String {
+        render_function("into", &[escape_string(&self.graph), self.query.to_dsl()])
+    }
+}
+
+impl ToDSL for Pin {
+    fn to_dsl(&self) -> String {
+        render_function("pin", &[self.query.to_dsl()])
+    }
+}
+
+impl ToDSL for Once {
+    fn to_dsl(&self) -> String {
+        render_function("once", &[self.query.to_dsl()])
+    }
+}
+
+impl ToDSL for Immediately {
+    fn to_dsl(&self) -> String {
+        render_function("immediately", &[self.query.to_dsl()])
+    }
+}
+
+impl ToDSL for If {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "if",
+            &[
+                self.test.to_dsl(),
+                self.then_query.to_dsl(),
+                self.else_query.to_dsl(),
+            ],
+        )
+    }
+}
+
+impl ToDSL for LexicalKey {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "lexical_key",
+            &[self.base.to_dsl(), self.key_list.to_dsl(), self.uri.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for HashKey {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "hash_key",
+            &[self.base.to_dsl(), self.key_list.to_dsl(), self.uri.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for RandomKey {
+    fn to_dsl(&self) -> String {
+        render_function("random_key", &[self.base.to_dsl(), self.uri.to_dsl()])
+    }
+}
+
+impl ToDSL for Size {
+    fn to_dsl(&self) -> String {
+        render_function("size", &[escape_string(&self.resource), self.size.to_dsl()])
+    }
+}
+
+impl ToDSL for TripleCount {
+    fn to_dsl(&self) -> String {
+        render_function(
+            "triple_count",
+            &[escape_string(&self.resource), self.count.to_dsl()],
+        )
+    }
+}
+
+impl ToDSL for Call {
+    fn to_dsl(&self) -> String {
+        let mut args = vec![escape_string(&self.name)];
+        args.extend(self.arguments.iter().map(|arg| arg.to_dsl()));
+        render_function("call", &args)
+    }
+}
+
+impl ToDSL for Get {
+    fn to_dsl(&self) -> String {
+        let mut args = vec![render_list_value(&self.columns), self.resource.to_dsl()];
+        if let Some(has_header) = self.has_header {
+            args.push(has_header.to_string());
+        }
+        render_function("get", &args)
+    }
+}
+
+impl ToDSL for Column {
+    fn to_dsl(&self) -> String {
+        let indicator = match &self.indicator {
+            Indicator::Index(index) => index.to_string(),
+            Indicator::Name(name) => escape_string(name),
+        };
+        let mut args = vec![indicator, render_variable(&self.variable)];
+        if let Some(type_of) = &self.type_of {
+            args.push(escape_string(type_of));
+        }
+        render_function("as", &args)
+    }
+}
+
+impl ToDSL for QueryResource {
+    fn to_dsl(&self) -> String {
+        // CSV is the only format, so it isn't spelled out
+        let (kind, location) = match &self.source {
+            Source::Post(path) => ("post", path),
+            Source::Url(url) => ("url", url),
+        };
+        let mut args = vec![escape_string(location)];
+        if let Some(options) = &self.options {
+            args.push(options.to_dsl());
+        }
+        render_function(kind, &args)
+    }
+}
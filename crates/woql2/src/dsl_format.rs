@@ -0,0 +1,342 @@
+//! Pretty-printing of DSL syntax
+//!
+//! [`ToDSL::to_dsl`](crate::dsl::ToDSL::to_dsl) renders a query on a single
+//! line. [`DslFormat`] lays that output out over several lines: a call, list or
+//! dictionary stays on one line when it fits and is simple enough, otherwise
+//! its arguments go on lines of their own, one indentation level deeper.
+//!
+//! ```text
+//! select(
+//!     [$Name],
+//!     and(
+//!         triple($Person, "rdf:type", "@schema:Person"),
+//!         triple($Person, "@schema:name", $Name)
+//!     )
+//! )
+//! ```
+
+/// Layout options for pretty-printed DSL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DslFormat {
+    /// spaces per indentation level
+    pub indent: usize,
+    /// line length that inline calls must fit in, including indentation
+    pub max_width: usize,
+    /// calls and lists with more arguments than this are always split over
+    /// several lines
+    pub max_inline_args: usize,
+    /// calls and lists nested deeper than this are always split over several
+    /// lines; a call of plain values has depth 1, a call containing such a
+    /// call depth 2, and so on
+    pub max_inline_depth: usize,
+}
+
+impl Default for DslFormat {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            max_width: 80,
+            max_inline_args: 5,
+            max_inline_depth: 2,
+        }
+    }
+}
+
+impl DslFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn with_max_inline_args(mut self, max_inline_args: usize) -> Self {
+        self.max_inline_args = max_inline_args;
+        self
+    }
+
+    pub fn with_max_inline_depth(mut self, max_inline_depth: usize) -> Self {
+        self.max_inline_depth = max_inline_depth;
+        self
+    }
+
+    /// Lay out DSL source, such as the output of `to_dsl()`. Whitespace
+    /// outside string literals is not preserved. Input that isn't balanced
+    /// DSL is returned unchanged.
+    pub fn format(&self, dsl: &str) -> String {
+        match Parser::new(dsl).document() {
+            Some(doc) => {
+                let mut out = String::with_capacity(dsl.len() * 2);
+                self.write(&doc, 0, 0, &mut out);
+                out
+            }
+            None => dsl.to_string(),
+        }
+    }
+
+    /// Write `doc` starting at column `column` of a line indented `level`
+    /// levels deep
+    fn write(&self, doc: &Doc, level: usize, column: usize, out: &mut String) {
+        let Doc::Group {
+            head,
+            open,
+            close,
+            items,
+        } = doc
+        else {
+            out.push_str(&doc.flat());
+            return;
+        };
+
+        let inline = items.len() <= self.max_inline_args
+            && doc.depth() <= self.max_inline_depth
+            && column + doc.width() <= self.max_width;
+        if inline || items.is_empty() {
+            out.push_str(&doc.flat());
+            return;
+        }
+
+        let item_indent = " ".repeat(self.indent * (level + 1));
+        out.push_str(head);
+        out.push(*open);
+        for (i, item) in items.iter().enumerate() {
+            out.push('\n');
+            out.push_str(&item_indent);
+            self.write(item, level + 1, item_indent.len(), out);
+            if i + 1 < items.len() {
+                out.push(',');
+            }
+        }
+        out.push('\n');
+        out.push_str(&" ".repeat(self.indent * level));
+        out.push(*close);
+    }
+}
+
+/// A DSL expression: a plain value, or a call, list or dictionary with the
+/// items between its brackets
+#[derive(Debug)]
+enum Doc {
+    /// a value, prefixed by its key when it is a dictionary entry
+    Atom(String),
+    Group {
+        /// function name, and key of a dictionary entry
+        head: String,
+        open: char,
+        close: char,
+        items: Vec<Doc>,
+    },
+}
+
+impl Doc {
+    /// Single-line rendering
+    fn flat(&self) -> String {
+        match self {
+            Doc::Atom(text) => text.clone(),
+            Doc::Group {
+                head,
+                open,
+                close,
+                items,
+            } => {
+                let items: Vec<String> = items.iter().map(Doc::flat).collect();
+                format!("{}{}{}{}", head, open, items.join(", "), close)
+            }
+        }
+    }
+
+    /// Length of the single-line rendering
+    fn width(&self) -> usize {
+        match self {
+            Doc::Atom(text) => text.chars().count(),
+            Doc::Group { head, items, .. } => {
+                let separators = 2 * items.len().saturating_sub(1);
+                head.chars().count() + 2 + separators + items.iter().map(Doc::width).sum::<usize>()
+            }
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Doc::Atom(_) => 0,
+            Doc::Group { items, .. } => 1 + items.iter().map(Doc::depth).max().unwrap_or(0),
+        }
+    }
+}
+
+/// Parser from flat DSL into [`Doc`]s. It only knows about brackets, commas,
+/// dictionary keys and string literals; everything else is value text.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn document(&mut self) -> Option<Doc> {
+        let doc = self.item()?;
+        self.skip_whitespace();
+        self.peek().is_none().then_some(doc)
+    }
+
+    fn item(&mut self) -> Option<Doc> {
+        let mut text = self.text()?;
+        self.skip_whitespace();
+        if self.peek() == Some(':') {
+            // dictionary entry: the value follows the key
+            self.pos += 1;
+            text.push_str(": ");
+            text.push_str(&self.text()?);
+            self.skip_whitespace();
+        }
+
+        let close = match self.peek() {
+            Some('(') => ')',
+            Some('[') => ']',
+            Some('{') => '}',
+            _ if text.is_empty() => return None,
+            _ => return Some(Doc::Atom(text)),
+        };
+        let open = self.peek()?;
+        self.pos += 1;
+
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+        } else {
+            loop {
+                items.push(self.item()?);
+                self.skip_whitespace();
+                match self.peek()? {
+                    ',' => self.pos += 1,
+                    c if c == close => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        Some(Doc::Group {
+            head: text,
+            open,
+            close,
+            items,
+        })
+    }
+
+    /// Value text up to the next bracket, comma or key separator, with string
+    /// literals copied verbatim
+    fn text(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => self.skip_string()?,
+                '(' | ')' | '[' | ']' | '{' | '}' | ',' | ':' => break,
+                c if c.is_whitespace() => break,
+                c => self.pos += c.len_utf8(),
+            }
+        }
+        Some(self.input[start..self.pos].to_string())
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.pos += 1;
+        loop {
+            let c = self.peek()?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Some(()),
+                '\\' => self.pos += self.peek()?.len_utf8(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_call_stays_inline() {
+        let dsl = r#"triple($Person, "@schema:name", $Name)"#;
+        assert_eq!(DslFormat::default().format(dsl), dsl);
+    }
+
+    #[test]
+    fn test_nested_calls_are_indented() {
+        let dsl = r#"select([$Name], and(triple($Person, "rdf:type", "@schema:Person"), triple($Person, "@schema:name", $Name)))"#;
+        assert_eq!(
+            DslFormat::default().format(dsl),
+            r#"select(
+    [$Name],
+    and(
+        triple($Person, "rdf:type", "@schema:Person"),
+        triple($Person, "@schema:name", $Name)
+    )
+)"#
+        );
+    }
+
+    #[test]
+    fn test_strings_and_dictionaries() {
+        let dsl = r#"eq($Doc, {"name": "a, (b)", "tags": ["x", "y"]})"#;
+        assert_eq!(DslFormat::new().with_max_inline_depth(3).format(dsl), dsl);
+        assert_eq!(
+            DslFormat::new()
+                .with_indent(2)
+                .with_max_width(24)
+                .format(dsl),
+            r#"eq(
+  $Doc,
+  {
+    "name": "a, (b)",
+    "tags": ["x", "y"]
+  }
+)"#
+        );
+    }
+
+    #[test]
+    fn test_inline_thresholds() {
+        let dsl = "f(1, 2, 3)";
+        assert_eq!(
+            DslFormat::new().with_max_inline_args(2).format(dsl),
+            "f(\n    1,\n    2,\n    3\n)"
+        );
+        assert_eq!(
+            DslFormat::new().with_max_inline_depth(1).format("f(g(1))"),
+            "f(\n    g(1)\n)"
+        );
+        assert_eq!(DslFormat::default().format("f()"), "f()");
+    }
+
+    #[test]
+    fn test_malformed_input_is_unchanged() {
+        assert_eq!(DslFormat::default().format("f(1, 2"), "f(1, 2");
+        assert_eq!(DslFormat::default().format("f(1) g(2)"), "f(1) g(2)");
+    }
+}
@@ -1,10 +1,26 @@
 // Removed incorrect imports for TdbDataType, TdbDebug, TdbDisplay
+use crate::macros::IntoArithmeticValue;
+use crate::value::Value;
+use std::ops;
 use terminusdb_schema::ToTDBInstance;
 use terminusdb_schema::XSDAnySimpleType;
 use terminusdb_schema_derive::{FromTDBInstance, TerminusDBModel};
 
 // Represents TaggedUnion "ArithmeticValue"
 /// A variable or data value used within an arithmetic expression.
+///
+/// # Numeric coercion
+///
+/// The server evaluates operands as plain numbers, whatever their xsd type:
+///
+/// - `Plus`, `Minus`, `Times`, `Exp` and `Floor` on two integers
+///   (`XSDAnySimpleType::Integer` / `UnsignedInt`) stay integral;
+/// - as soon as one operand is a `Float` or `Decimal` the result is a float;
+/// - `Divide` (`/`) always produces a float, even for `6 / 3`; use `Div` for
+///   truncating integer division.
+///
+/// Literals are converted with `From`: `i32`/`i64` become `Integer`,
+/// `u32`/`u64`/`usize` become `UnsignedInt` and `f32`/`f64` become `Float`.
 #[derive(TerminusDBModel, FromTDBInstance, Debug, Clone, PartialEq)]
 
 #[tdb(rename_all = "lowercase")]
@@ -21,6 +37,18 @@ pub enum ArithmeticValue {
 /// `abstract_class` makes each variant serialize as its concrete `@type`
 /// (e.g. `{"@type":"Divide",...}`) rather than an illegal
 /// `{"@type":"ArithmeticExpression","divide":{...}}` wrapper the server rejects.
+///
+/// Expressions can be written with the usual operators instead of nesting the
+/// structs by hand; `+`, `-`, `*` and `/` map to [`Plus`], [`Minus`], [`Times`]
+/// and [`Divide`], and both variables and numeric literals are accepted on
+/// either side:
+///
+/// ```ignore
+/// let q = eval!(v!(x) + 3 * v!(y), v!(result));
+/// let avg = eval!((v!(total) / v!(count)).floor(), v!(avg));
+/// ```
+///
+/// See [`ArithmeticValue`] for how integer and float operands combine.
 #[derive(TerminusDBModel, FromTDBInstance, Debug, Clone, PartialEq)]
 #[tdb(abstract_class = true)]
 pub enum ArithmeticExpression {
@@ -101,3 +129,117 @@ pub struct Floor {
     /// The number to floor.
     pub argument: Box<ArithmeticExpression>,
 }
+
+// --- Operator sugar --- //
+
+impl ArithmeticExpression {
+    /// Truncating integer division (`div`), as opposed to `/` which maps to [`Divide`].
+    pub fn int_div(self, rhs: impl Into<ArithmeticExpression>) -> Self {
+        ArithmeticExpression::Div(Div {
+            left: Box::new(self),
+            right: Box::new(rhs.into()),
+        })
+    }
+
+    /// Raise this expression to the power `exponent`.
+    pub fn pow(self, exponent: impl Into<ArithmeticExpression>) -> Self {
+        ArithmeticExpression::Exp(Exp {
+            left: Box::new(self),
+            right: Box::new(exponent.into()),
+        })
+    }
+
+    /// Integral part of this expression.
+    pub fn floor(self) -> Self {
+        ArithmeticExpression::Floor(Floor {
+            argument: Box::new(self),
+        })
+    }
+}
+
+impl From<ArithmeticValue> for ArithmeticExpression {
+    fn from(value: ArithmeticValue) -> Self {
+        ArithmeticExpression::Value(value)
+    }
+}
+
+/// Panics on node, list or dictionary values, like [`IntoArithmeticValue`].
+impl From<Value> for ArithmeticExpression {
+    fn from(value: Value) -> Self {
+        ArithmeticExpression::Value(value.into_arithmetic_value())
+    }
+}
+
+macro_rules! impl_from_literal {
+    ($ty:ty => $variant:ident) => {
+        impl_from_literal!($ty => $variant, |value| value);
+    };
+    ($ty:ty => $variant:ident as $as_ty:ty) => {
+        impl_from_literal!($ty => $variant, |value| value as $as_ty);
+    };
+    ($ty:ty => $variant:ident, |$value:ident| $convert:expr) => {
+        impl From<$ty> for ArithmeticExpression {
+            fn from($value: $ty) -> Self {
+                ArithmeticExpression::Value(ArithmeticValue::Data(XSDAnySimpleType::$variant(
+                    $convert,
+                )))
+            }
+        }
+    };
+}
+
+impl_from_literal!(i32 => Integer as i64);
+impl_from_literal!(i64 => Integer);
+impl_from_literal!(u32 => UnsignedInt as usize);
+impl_from_literal!(u64 => UnsignedInt as usize);
+impl_from_literal!(usize => UnsignedInt);
+impl_from_literal!(f32 => Float as f64);
+impl_from_literal!(f64 => Float);
+
+/// `lhs <op> rhs` for every operand type that converts into an expression:
+/// the expression types themselves accept any such right-hand side, while
+/// literals on the left need one impl per expression-like right-hand side.
+macro_rules! impl_arithmetic_op {
+    ($trait:ident, $method:ident, $node:ident) => {
+        impl_arithmetic_op!(@expr $trait, $method, $node, ArithmeticExpression);
+        impl_arithmetic_op!(@expr $trait, $method, $node, ArithmeticValue);
+        impl_arithmetic_op!(@expr $trait, $method, $node, Value);
+        impl_arithmetic_op!(@literal $trait, $method, $node, i32, i64, u32, u64, usize, f32, f64);
+    };
+    (@expr $trait:ident, $method:ident, $node:ident, $lhs:ty) => {
+        impl<R: Into<ArithmeticExpression>> ops::$trait<R> for $lhs {
+            type Output = ArithmeticExpression;
+
+            fn $method(self, rhs: R) -> ArithmeticExpression {
+                ArithmeticExpression::$node($node {
+                    left: Box::new(self.into()),
+                    right: Box::new(rhs.into()),
+                })
+            }
+        }
+    };
+    (@literal $trait:ident, $method:ident, $node:ident, $($lit:ty),*) => {
+        $(
+            impl_arithmetic_op!(@literal_rhs $trait, $method, $node, $lit, ArithmeticExpression);
+            impl_arithmetic_op!(@literal_rhs $trait, $method, $node, $lit, ArithmeticValue);
+            impl_arithmetic_op!(@literal_rhs $trait, $method, $node, $lit, Value);
+        )*
+    };
+    (@literal_rhs $trait:ident, $method:ident, $node:ident, $lit:ty, $rhs:ty) => {
+        impl ops::$trait<$rhs> for $lit {
+            type Output = ArithmeticExpression;
+
+            fn $method(self, rhs: $rhs) -> ArithmeticExpression {
+                ArithmeticExpression::$node($node {
+                    left: Box::new(self.into()),
+                    right: Box::new(rhs.into()),
+                })
+            }
+        }
+    };
+}
+
+impl_arithmetic_op!(Add, add, Plus);
+impl_arithmetic_op!(Sub, sub, Minus);
+impl_arithmetic_op!(Mul, mul, Times);
+impl_arithmetic_op!(Div, div, Divide);
@@ -0,0 +1,533 @@
+//! Rendering WOQL queries as terminusdb-client-js source.
+//!
+//! This is the inverse of the `terminusdb-woql-js` parser: the output is the
+//! code a dashboard user would write, evaluated against the `WOQL.emerge()`
+//! prelude, e.g. `select("v:Name", triple("v:Person", "@schema:name", "v:Name"))`.
+//!
+//! The JS client decides what an argument means from its JavaScript type and
+//! from the function it is passed to, so each position is rendered in the form
+//! that function reads back correctly:
+//!
+//! - variables are `"v:Name"` strings;
+//! - nodes are plain strings, string data is `string("...")` where a plain
+//!   string would be read as a node;
+//! - other typed data is `literal(value, "xsd:type")`.
+//!
+//! Whatever the JS client has no function for, or would read back differently
+//! (the TerminusDB 12 operators, `link`/`data` triples, falsy typed literals in
+//! data positions, ...), is embedded as JSON-LD: values as object literals,
+//! which every client function passes through unchanged, and whole queries as
+//! `json({...})`. The result always evaluates to the same query.
+
+use serde_json::Value as JsonValue;
+use terminusdb_schema::{GraphType, ToJson, ToTDBInstance, XSDAnySimpleType};
+
+use crate::expression::{ArithmeticExpression, ArithmeticValue};
+use crate::json::normalize_woql_json;
+use crate::order::Order;
+use crate::path::PathPattern;
+use crate::query::Query;
+use crate::value::{DataValue, ListOrVariable, NodeValue, Value};
+
+/// Trait for rendering WOQL queries to terminusdb-client-js syntax
+pub trait ToJsSyntax {
+    /// Render this value/query as JS source for the `WOQL.emerge()` prelude
+    fn to_js(&self) -> String;
+}
+
+/// Quote a string as a JS string literal
+fn js_string(s: &str) -> String {
+    JsonValue::String(s.to_string()).to_string()
+}
+
+/// Render a variable as `"v:name"`
+fn js_var(name: &str) -> String {
+    js_string(&format!("v:{}", name))
+}
+
+/// Helper function to render a function call
+fn js_call(name: &str, args: &[String]) -> String {
+    format!("{}({})", name, args.join(", "))
+}
+
+/// Helper function to render an array of items
+fn js_array(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(", "))
+}
+
+/// Render a value as its JSON-LD object, which the client passes through as-is
+fn js_object<T: ToTDBInstance>(value: &T) -> String {
+    let mut json = value.to_instance(None).to_json();
+    normalize_woql_json(&mut json);
+    json.to_string()
+}
+
+/// A plain string the client reads as a node or data literal rather than a
+/// variable (any string containing `v:` is taken as a variable).
+fn is_plain(s: &str) -> bool {
+    !s.is_empty() && !s.contains("v:")
+}
+
+/// Split typed data into its JSON-LD `@value` and `@type`
+fn typed_value(data: &XSDAnySimpleType) -> (JsonValue, &'static str) {
+    match data {
+        XSDAnySimpleType::String(s) => (JsonValue::from(s.as_str()), "xsd:string"),
+        XSDAnySimpleType::Boolean(b) => (JsonValue::from(*b), "xsd:boolean"),
+        XSDAnySimpleType::Float(f) => {
+            let value = serde_json::Number::from_f64(*f)
+                .map(JsonValue::Number)
+                .unwrap_or_else(|| JsonValue::from(f.to_string()));
+            (value, "xsd:double")
+        }
+        XSDAnySimpleType::Integer(i) => (JsonValue::from(*i), "xsd:integer"),
+        XSDAnySimpleType::UnsignedInt(i) => (JsonValue::from(*i), "xsd:unsignedInt"),
+        XSDAnySimpleType::Decimal(d) => (JsonValue::from(d.to_string()), "xsd:decimal"),
+        XSDAnySimpleType::DateTime(dt) => (JsonValue::from(dt.to_rfc3339()), "xsd:dateTime"),
+        XSDAnySimpleType::Date(d) => (JsonValue::from(d.to_string()), "xsd:date"),
+        XSDAnySimpleType::Time(t) => (JsonValue::from(t.to_string()), "xsd:time"),
+        XSDAnySimpleType::HexBinary(h) => (JsonValue::from(h.as_str()), "xsd:hexBinary"),
+        XSDAnySimpleType::URI(u) => (JsonValue::from(u.to_string()), "xsd:anyURI"),
+    }
+}
+
+/// Render typed data as `literal(value, "xsd:type")`, or `None` when its value
+/// is falsy in JS: data and arithmetic positions would then not recognise it.
+fn truthy_literal(data: &XSDAnySimpleType) -> Option<String> {
+    let (value, xsd_type) = typed_value(data);
+    let truthy = match &value {
+        JsonValue::String(s) => !s.is_empty(),
+        JsonValue::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+        JsonValue::Bool(b) => *b,
+        _ => false,
+    };
+    truthy.then(|| js_call("literal", &[value.to_string(), js_string(xsd_type)]))
+}
+
+/// Render a data value in a position the client reads as a `Value` (like a
+/// triple object), where plain strings are nodes.
+fn data_as_value(value: &DataValue) -> String {
+    match value {
+        DataValue::Variable(var) => js_var(var),
+        DataValue::Data(data) => data_in_value_position(data),
+        DataValue::List(_) => js_object(value),
+    }
+}
+
+fn data_in_value_position(data: &XSDAnySimpleType) -> String {
+    match data {
+        XSDAnySimpleType::String(s) => js_call("string", &[js_string(s)]),
+        XSDAnySimpleType::Boolean(b) => b.to_string(),
+        _ => {
+            let (value, xsd_type) = typed_value(data);
+            js_call("literal", &[value.to_string(), js_string(xsd_type)])
+        }
+    }
+}
+
+/// A data value the client only accepts as a variable name
+fn variable_only(value: &DataValue) -> Option<String> {
+    match value {
+        DataValue::Variable(var) => Some(js_var(var)),
+        _ => None,
+    }
+}
+
+impl ToJsSyntax for Value {
+    fn to_js(&self) -> String {
+        match self {
+            Value::Variable(var) => js_var(var),
+            Value::Node(uri) if is_plain(uri) => js_string(uri),
+            Value::Data(data) => data_in_value_position(data),
+            Value::Node(_) | Value::List(_) | Value::Dictionary(_) => js_object(self),
+        }
+    }
+}
+
+impl ToJsSyntax for NodeValue {
+    fn to_js(&self) -> String {
+        match self {
+            NodeValue::Variable(var) => js_var(var),
+            NodeValue::Node(uri) if is_plain(uri) => js_string(uri),
+            NodeValue::Node(_) => js_object(self),
+        }
+    }
+}
+
+impl ToJsSyntax for DataValue {
+    fn to_js(&self) -> String {
+        match self {
+            DataValue::Variable(var) => js_var(var),
+            DataValue::Data(XSDAnySimpleType::String(s)) if is_plain(s) => js_string(s),
+            DataValue::Data(XSDAnySimpleType::Boolean(b)) => b.to_string(),
+            DataValue::Data(data) => truthy_literal(data).unwrap_or_else(|| js_object(self)),
+            DataValue::List(items) => js_array(items.iter().map(|item| item.to_js())),
+        }
+    }
+}
+
+impl ToJsSyntax for ArithmeticValue {
+    fn to_js(&self) -> String {
+        match self {
+            ArithmeticValue::Variable(var) => js_var(var),
+            ArithmeticValue::Data(data) => truthy_literal(data).unwrap_or_else(|| js_object(self)),
+        }
+    }
+}
+
+impl ToJsSyntax for ArithmeticExpression {
+    fn to_js(&self) -> String {
+        match self {
+            ArithmeticExpression::Value(value) => value.to_js(),
+            ArithmeticExpression::Plus(op) => js_call("plus", &[op.left.to_js(), op.right.to_js()]),
+            ArithmeticExpression::Minus(op) => {
+                js_call("minus", &[op.left.to_js(), op.right.to_js()])
+            }
+            ArithmeticExpression::Times(op) => {
+                js_call("times", &[op.left.to_js(), op.right.to_js()])
+            }
+            ArithmeticExpression::Divide(op) => {
+                js_call("divide", &[op.left.to_js(), op.right.to_js()])
+            }
+            ArithmeticExpression::Div(op) => js_call("div", &[op.left.to_js(), op.right.to_js()]),
+            ArithmeticExpression::Exp(op) => js_call("exp", &[op.left.to_js(), op.right.to_js()]),
+            ArithmeticExpression::Floor(op) => js_call("floor", &[op.argument.to_js()]),
+        }
+    }
+}
+
+/// Render a path pattern as a client path string (`"<parent,friend+"`), or as
+/// its JSON-LD object when a predicate can't be written in that syntax.
+impl ToJsSyntax for PathPattern {
+    fn to_js(&self) -> String {
+        match path_string(self) {
+            Some(pattern) => js_string(&pattern),
+            None => js_object(self),
+        }
+    }
+}
+
+/// A predicate as it appears in a path string; `None` (any predicate) is `.`
+fn path_predicate(predicate: &Option<String>) -> Option<String> {
+    match predicate {
+        None => Some(".".to_string()),
+        Some(p) if p != "." && !p.is_empty() => {
+            let special = |c: char| c.is_whitespace() || ",|+*{}()<>".contains(c);
+            (!p.contains(special)).then(|| p.clone())
+        }
+        Some(_) => None,
+    }
+}
+
+/// The client path syntax: `|` binds loosest, then `,`, then the `+`, `*` and
+/// `{from,to}` suffixes, so anything but a predicate is parenthesized before a
+/// suffix and alternatives are parenthesized inside a sequence.
+fn path_string(pattern: &PathPattern) -> Option<String> {
+    let suffixed = |inner: &PathPattern, suffix: String| {
+        let inner = match inner {
+            PathPattern::Predicate(_) | PathPattern::InversePredicate(_) => path_string(inner)?,
+            _ => format!("({})", path_string(inner)?),
+        };
+        Some(format!("{}{}", inner, suffix))
+    };
+    match pattern {
+        PathPattern::Predicate(pred) => path_predicate(&pred.predicate),
+        PathPattern::InversePredicate(pred) => {
+            path_predicate(&pred.predicate).map(|p| format!("<{}", p))
+        }
+        PathPattern::Sequence(seq) if seq.sequence.len() >= 2 => {
+            let parts = seq
+                .sequence
+                .iter()
+                .map(|item| match item {
+                    PathPattern::Or(_) => Some(format!("({})", path_string(item)?)),
+                    _ => path_string(item),
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(parts.join(","))
+        }
+        PathPattern::Or(or) if or.or.len() >= 2 => {
+            let parts = or.or.iter().map(path_string).collect::<Option<Vec<_>>>()?;
+            Some(parts.join("|"))
+        }
+        PathPattern::Plus(plus) => suffixed(&plus.plus, "+".to_string()),
+        PathPattern::Star(star) => suffixed(&star.star, "*".to_string()),
+        PathPattern::Times(times) => {
+            suffixed(&times.times, format!("{{{},{}}}", times.from, times.to))
+        }
+        PathPattern::Sequence(_) | PathPattern::Or(_) => None,
+    }
+}
+
+impl ToJsSyntax for Query {
+    fn to_js(&self) -> String {
+        render_query(self).unwrap_or_else(|| js_call("json", &[js_object(self)]))
+    }
+}
+
+/// Arguments of a triple-shaped call, with the graph appended for the `quad`
+/// form. Only the schema graph needs one: the client defaults to instance.
+fn triple_call(
+    name: &str,
+    subject: &NodeValue,
+    predicate: &NodeValue,
+    object: &Value,
+    graph: &Option<GraphType>,
+) -> String {
+    let mut args = vec![subject.to_js(), predicate.to_js(), object.to_js()];
+    match graph {
+        Some(GraphType::Schema) => {
+            args.push(js_string("schema"));
+            js_call(&name.replace("triple", "quad"), &args)
+        }
+        _ => js_call(name, &args),
+    }
+}
+
+/// Variable names followed by a subquery, as `select` and `distinct` take them
+fn variables_then_query(name: &str, variables: &[String], query: &Query) -> Option<String> {
+    if variables.is_empty() {
+        return None;
+    }
+    let mut args: Vec<String> = variables.iter().map(|var| js_var(var)).collect();
+    args.push(query.to_js());
+    Some(js_call(name, &args))
+}
+
+/// Render a query with the client's own functions, or `None` if it has to be
+/// embedded as JSON-LD.
+fn render_query(query: &Query) -> Option<String> {
+    let rendered = match query {
+        Query::And(and) if !and.and.is_empty() => js_call(
+            "and",
+            &and.and.iter().map(|q| q.to_js()).collect::<Vec<_>>(),
+        ),
+        Query::Or(or) if !or.or.is_empty() => {
+            js_call("or", &or.or.iter().map(|q| q.to_js()).collect::<Vec<_>>())
+        }
+        Query::Not(op) => js_call("not", &[op.query.to_js()]),
+        Query::WoqlOptional(op) => js_call("opt", &[op.query.to_js()]),
+        Query::Once(op) => js_call("once", &[op.query.to_js()]),
+        Query::Immediately(op) => js_call("immediately", &[op.query.to_js()]),
+        // `true` and `eval` are reserved words, so the prelude doesn't define them
+        Query::True(_) => "WOQL.true()".to_string(),
+        Query::Select(op) => variables_then_query("select", &op.variables, &op.query)?,
+        Query::Distinct(op) => variables_then_query("distinct", &op.variables, &op.query)?,
+        Query::Limit(op) => js_call("limit", &[op.limit.to_string(), op.query.to_js()]),
+        Query::Start(op) => js_call("start", &[op.start.to_string(), op.query.to_js()]),
+        Query::OrderBy(op) if !op.ordering.is_empty() => {
+            let mut args: Vec<String> = op
+                .ordering
+                .iter()
+                .map(|template| match template.order {
+                    Order::Asc => js_var(&template.variable),
+                    Order::Desc => js_array([js_var(&template.variable), js_string("desc")]),
+                })
+                .collect();
+            args.push(op.query.to_js());
+            js_call("order_by", &args)
+        }
+        Query::GroupBy(op) => {
+            let template = match &op.template {
+                Value::Variable(var) => js_var(var),
+                Value::List(items) => js_array(
+                    items
+                        .iter()
+                        .map(|item| match item {
+                            Value::Variable(var) => Some(js_var(var)),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()?,
+                ),
+                _ => return None,
+            };
+            let Value::Variable(grouped) = &op.grouped_value else {
+                return None;
+            };
+            let group_by = js_array(op.group_by.iter().map(|var| js_var(var)));
+            js_call(
+                "group_by",
+                &[group_by, template, js_var(grouped), op.query.to_js()],
+            )
+        }
+        Query::Triple(t) => triple_call("triple", &t.subject, &t.predicate, &t.object, &t.graph),
+        Query::AddTriple(t) => {
+            triple_call("add_triple", &t.subject, &t.predicate, &t.object, &t.graph)
+        }
+        Query::DeleteTriple(t) => triple_call(
+            "delete_triple",
+            &t.subject,
+            &t.predicate,
+            &t.object,
+            &t.graph,
+        ),
+        // The client's `added_quad` and `removed_quad` emit unknown types
+        Query::AddedTriple(t) if t.graph != Some(GraphType::Schema) => {
+            triple_call("added_triple", &t.subject, &t.predicate, &t.object, &None)
+        }
+        Query::DeletedTriple(t) if t.graph != Some(GraphType::Schema) => {
+            triple_call("removed_triple", &t.subject, &t.predicate, &t.object, &None)
+        }
+        Query::Equals(op) => js_call("eq", &[op.left.to_js(), op.right.to_js()]),
+        Query::Greater(op) => js_call("greater", &[op.left.to_js(), op.right.to_js()]),
+        Query::Less(op) => js_call("less", &[op.left.to_js(), op.right.to_js()]),
+        Query::IsA(op) => js_call("isa", &[op.element.to_js(), op.type_of.to_js()]),
+        Query::Subsumption(op) => js_call("sub", &[op.parent.to_js(), op.child.to_js()]),
+        Query::TypeOf(op) => js_call("type_of", &[op.value.to_js(), op.type_uri.to_js()]),
+        Query::Typecast(op) => js_call(
+            "typecast",
+            &[
+                op.value.to_js(),
+                op.type_uri.to_js(),
+                op.result_value.to_js(),
+            ],
+        ),
+        Query::Eval(op) if !matches!(op.expression, ArithmeticExpression::Value(_)) => js_call(
+            "evaluate",
+            &[op.expression.to_js(), op.result_value.to_js()],
+        ),
+        Query::Path(op) => {
+            let subject = match &op.subject {
+                Value::Variable(var) => js_var(var),
+                Value::Node(uri) if is_plain(uri) => js_string(uri),
+                subject => js_object(subject),
+            };
+            let mut args = vec![subject, op.pattern.to_js(), op.object.to_js()];
+            match &op.path {
+                None => {}
+                Some(Value::Variable(var)) => args.push(js_var(var)),
+                Some(_) => return None,
+            }
+            js_call("path", &args)
+        }
+        Query::Count(op) => js_call("count", &[data_as_value(&op.count), op.query.to_js()]),
+        Query::Sum(op) => js_call("sum", &[op.list.to_js(), data_as_value(&op.result)]),
+        Query::Length(op) => js_call("length", &[op.list.to_js(), variable_only(&op.length)?]),
+        Query::Member(op) => {
+            let list = match &op.list {
+                DataValue::Variable(var) => js_var(var),
+                DataValue::List(items) => js_array(items.iter().map(data_as_value)),
+                DataValue::Data(_) => return None,
+            };
+            js_call("member", &[data_as_value(&op.member), list])
+        }
+        Query::Concatenate(op) => {
+            // A string here is a `"v:A v:B"` template, so only lists render
+            let ListOrVariable::List(items) = &op.list else {
+                return None;
+            };
+            let list = js_array(items.iter().map(|item| item.to_js()));
+            js_call("concat", &[list, op.result_string.to_js()])
+        }
+        Query::Join(op) => {
+            let list = match &op.list {
+                ListOrVariable::List(items) => js_array(items.iter().map(|item| item.to_js())),
+                ListOrVariable::Variable(var) => var.to_js(),
+            };
+            js_call(
+                "join",
+                &[list, op.separator.to_js(), op.result_string.to_js()],
+            )
+        }
+        Query::Substring(op) => {
+            // The client insists on a plain string for the substring
+            let substring = op.substring.to_js();
+            if !substring.starts_with('"') {
+                return None;
+            }
+            js_call(
+                "substr",
+                &[
+                    op.string.to_js(),
+                    op.before.to_js(),
+                    op.length.to_js(),
+                    op.after.to_js(),
+                    substring,
+                ],
+            )
+        }
+        Query::Trim(op) => js_call("trim", &[op.untrimmed.to_js(), op.trimmed.to_js()]),
+        Query::Upper(op) => js_call("upper", &[op.mixed.to_js(), op.upper.to_js()]),
+        Query::Lower(op) => js_call("lower", &[op.mixed.to_js(), op.lower.to_js()]),
+        Query::Pad(op) => js_call(
+            "pad",
+            &[
+                op.string.to_js(),
+                op.char.to_js(),
+                op.times.to_js(),
+                op.result_string.to_js(),
+            ],
+        ),
+        Query::Split(op) => js_call(
+            "split",
+            &[op.string.to_js(), op.pattern.to_js(), op.list.to_js()],
+        ),
+        Query::Regexp(op) => {
+            let result = op.result.as_ref()?;
+            js_call(
+                "re",
+                &[op.pattern.to_js(), op.string.to_js(), result.to_js()],
+            )
+        }
+        Query::Like(op) => js_call(
+            "like",
+            &[op.left.to_js(), op.right.to_js(), op.similarity.to_js()],
+        ),
+        Query::ReadDocument(op) => {
+            let Value::Variable(document) = &op.document else {
+                return None;
+            };
+            js_call("read_document", &[op.identifier.to_js(), js_var(document)])
+        }
+        Query::InsertDocument(op) => {
+            let mut args = vec![op.document.to_js()];
+            args.extend(op.identifier.as_ref().map(|id| id.to_js()));
+            js_call("insert_document", &args)
+        }
+        Query::UpdateDocument(op) => {
+            let mut args = vec![op.document.to_js()];
+            args.extend(op.identifier.as_ref().map(|id| id.to_js()));
+            js_call("update_document", &args)
+        }
+        Query::DeleteDocument(op) => js_call("delete_document", &[op.identifier.to_js()]),
+        Query::Using(op) if !op.collection.is_empty() => {
+            js_call("using", &[js_string(&op.collection), op.query.to_js()])
+        }
+        Query::From(op) if !op.graph.is_empty() => {
+            js_call("from", &[js_string(&op.graph), op.query.to_js()])
+        }
+        Query::Into(op) if !op.graph.is_empty() => {
+            js_call("into", &[js_string(&op.graph), op.query.to_js()])
+        }
+        Query::Comment(op) => {
+            let (DataValue::Data(XSDAnySimpleType::String(comment)), Some(query)) =
+                (&op.comment, &op.query)
+            else {
+                return None;
+            };
+            js_call("comment", &[js_string(comment), query.to_js()])
+        }
+        Query::LexicalKey(op) => js_call(
+            "idgen",
+            &[
+                op.base.to_js(),
+                js_array(op.key_list.iter().map(|key| key.to_js())),
+                op.uri.to_js(),
+            ],
+        ),
+        Query::HashKey(op) => js_call(
+            "unique",
+            &[
+                op.base.to_js(),
+                js_array(op.key_list.iter().map(|key| key.to_js())),
+                op.uri.to_js(),
+            ],
+        ),
+        Query::Size(op) => js_call("size", &[js_string(&op.resource), variable_only(&op.size)?]),
+        Query::TripleCount(op) => js_call(
+            "triple_count",
+            &[js_string(&op.resource), variable_only(&op.count)?],
+        ),
+        _ => return None,
+    };
+    Some(rendered)
+}
@@ -10,20 +10,28 @@
 // -
 
 // Add pub mod declarations here as modules are created.
+pub mod binary;
 pub mod collection;
 pub mod compare;
 pub mod control;
 pub mod document;
 pub mod dsl;
+pub mod dsl_format;
 pub mod expression;
 pub mod get;
 pub mod interval;
+pub mod js;
 pub mod json;
+pub mod lint;
 pub mod macros;
 pub mod misc;
+pub mod optimize;
 pub mod order;
 pub mod path;
+pub mod path_parser;
+pub mod prepared;
 pub mod query;
+pub mod stats;
 pub mod string;
 pub mod triple;
 pub mod value;
@@ -32,6 +40,8 @@ pub mod value;
 pub mod path_builder;
 pub mod query_dsl;
 
+pub use lint::lint;
+
 pub mod prelude {
     // Re-export core types
     pub use super::collection::{
@@ -57,10 +67,12 @@ pub mod prelude {
         RangeMin, Sequence, Size, Start, TripleCount,
     };
     pub use super::order::{GroupBy, Order, OrderBy, OrderTemplate};
+    pub use super::prepared::{ParamType, PreparedQuery};
     pub use super::path::{
         InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar,
         PathTimes,
     };
+    pub use super::path_parser::{parse_path_pattern, PathParseError};
     pub use super::query::*;
     pub use super::string::{
         Concatenate, Join, Like, Lower, Pad, Regexp, Split, Substring, Trim, Upper,
@@ -73,6 +85,8 @@ pub mod prelude {
     pub use super::value::{DataValue, DictionaryTemplate, FieldValuePair, NodeValue, Value};
     // Export the IntoDataValue trait for ergonomic API usage
     pub use super::macros::{IntoDataValue, IntoOrderTemplate};
+    // Conjunctions/disjunctions of a runtime number of queries
+    pub use super::macros::{and_all, or_all};
 
     // Re-export macros
     pub use crate::{
@@ -147,11 +161,20 @@ pub mod prelude {
     pub use terminusdb_schema::FromTDBInstance;
     pub use terminusdb_schema::ToTDBInstance;
 
-    // DSL rendering trait
-    pub use super::dsl::ToDSL;
+    // DSL rendering trait and pretty-printing options
+    pub use super::dsl::{DslFormat, ToDSL};
+
+    // terminusdb-client-js rendering trait
+    pub use super::js::ToJsSyntax;
 
     // WOQL JSON-LD serialization (strip null props, graph defaults)
     pub use super::json::normalize_woql_json;
+
+    // Query rewriting (flattening, dedup, pattern reordering)
+    pub use super::optimize::optimize;
+
+    // Static checks (unbound/single-use variables, unknown schema names)
+    pub use super::lint::{lint, Diagnostic, LintCode, Severity};
 }
 
 #[test]
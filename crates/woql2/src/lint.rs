@@ -0,0 +1,454 @@
+//! Static checks for WOQL queries.
+//!
+//! [`lint`] looks for mistakes that TerminusDB would not reject but that make a
+//! query silently return nothing or the wrong thing:
+//!
+//! - a `Select` of a variable that its subquery never binds;
+//! - a variable that occurs only once in the whole query, which is usually a
+//!   misspelling of another one (prefix a deliberately unused variable with
+//!   `_` to silence this);
+//! - a triple predicate, path predicate or `rdf:type`/`IsA` class that is not
+//!   declared in the given schemas.
+//!
+//! Like [`PreparedQuery`](crate::prepared::PreparedQuery), the checks run over
+//! the serialized WOQL, so they apply to every query shape without a separate
+//! visitor. Schema checks are skipped when no schemas are given.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use terminusdb_schema::{Property, Schema};
+
+use crate::prepared::PARAM_PREFIX;
+use crate::query::Query;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const SCHEMA_PREFIXES: [&str; 2] = ["@schema:", "terminusdb:///schema#"];
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Likely a mistake, but the query may still be intended.
+    Warning,
+    /// The query cannot do what it says.
+    Error,
+}
+
+/// The check a [`Diagnostic`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    UnboundSelectVariable,
+    SingleUseVariable,
+    UnknownProperty,
+    UnknownClass,
+}
+
+impl LintCode {
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintCode::SingleUseVariable => Severity::Warning,
+            LintCode::UnboundSelectVariable
+            | LintCode::UnknownProperty
+            | LintCode::UnknownClass => Severity::Error,
+        }
+    }
+}
+
+/// A problem found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub code: LintCode,
+    pub severity: Severity,
+    /// The variable, property or class the diagnostic is about.
+    pub subject: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(code: LintCode, subject: &str, message: String) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            subject: subject.to_string(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
+
+/// Check `query` against `schemas`; see the [module docs](self) for the checks.
+///
+/// Diagnostics come in query order, with single-use variables last.
+pub fn lint(query: &Query, schemas: &[Schema]) -> Vec<Diagnostic> {
+    let json = query.to_woql_json();
+    let known = (!schemas.is_empty()).then(|| KnownNames::new(schemas));
+
+    let mut diagnostics = Vec::new();
+    let mut reported = BTreeSet::new();
+    check(&json, known.as_ref(), &mut diagnostics, &mut reported);
+
+    // An unbound selected variable is reported once, as such
+    let unbound: BTreeSet<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == LintCode::UnboundSelectVariable)
+        .map(|d| d.subject.clone())
+        .collect();
+
+    let mut uses = BTreeMap::new();
+    count_variables(&json, &mut uses);
+    diagnostics.extend(
+        uses.into_iter()
+            .filter(|(name, count)| {
+                *count == 1 && !name.starts_with('_') && !unbound.contains(name)
+            })
+            .map(|(name, _)| {
+                Diagnostic::new(
+                    LintCode::SingleUseVariable,
+                    &name,
+                    format!("variable '{}' is only used once", name),
+                )
+            }),
+    );
+
+    diagnostics
+}
+
+impl Query {
+    /// Shorthand for [`lint`].
+    pub fn lint(&self, schemas: &[Schema]) -> Vec<Diagnostic> {
+        lint(self, schemas)
+    }
+}
+
+/// Class and property names declared by the schemas.
+struct KnownNames {
+    classes: BTreeSet<String>,
+    properties: BTreeSet<String>,
+}
+
+impl KnownNames {
+    fn new(schemas: &[Schema]) -> Self {
+        let mut class_names = BTreeSet::new();
+        let mut property_names = BTreeSet::new();
+        for schema in schemas {
+            class_names.insert(schema.class_name().clone());
+            class_names.insert(schema.full_class_uri());
+
+            let own: Vec<&Property> = match schema {
+                Schema::Class { properties, .. } | Schema::TaggedUnion { properties, .. } => {
+                    properties.iter().collect()
+                }
+                Schema::OneOfClass {
+                    properties,
+                    classes,
+                    ..
+                } => properties.iter().chain(classes.iter().flatten()).collect(),
+                Schema::Enum { .. } => vec![],
+            };
+            property_names.extend(own.into_iter().map(|p| p.name.clone()));
+        }
+        Self {
+            classes: class_names,
+            properties: property_names,
+        }
+    }
+}
+
+/// The schema name an IRI refers to, or `None` for IRIs outside the schema
+/// namespace such as `rdf:type`.
+fn schema_name(iri: &str) -> Option<&str> {
+    for prefix in SCHEMA_PREFIXES {
+        if let Some(name) = iri.strip_prefix(prefix) {
+            return Some(name);
+        }
+    }
+    (!iri.contains(':')).then_some(iri)
+}
+
+fn node(json: &JsonValue) -> Option<&str> {
+    json.get("node")?.as_str()
+}
+
+fn variable(json: &JsonValue) -> Option<&str> {
+    json.get("variable")?
+        .as_str()
+        .filter(|name| !name.starts_with(PARAM_PREFIX))
+}
+
+fn check(
+    json: &JsonValue,
+    known: Option<&KnownNames>,
+    diagnostics: &mut Vec<Diagnostic>,
+    reported: &mut BTreeSet<(&'static str, String)>,
+) {
+    match json {
+        JsonValue::Object(map) => {
+            let ty = map.get("@type").and_then(JsonValue::as_str).unwrap_or("");
+
+            if ty == "Select" {
+                check_select(json, diagnostics);
+            }
+            if let Some(known) = known {
+                check_schema_refs(json, ty, known, diagnostics, reported);
+            }
+
+            map.values()
+                .for_each(|v| check(v, known, diagnostics, reported));
+        }
+        JsonValue::Array(items) => items
+            .iter()
+            .for_each(|v| check(v, known, diagnostics, reported)),
+        _ => {}
+    }
+}
+
+fn check_select(json: &JsonValue, diagnostics: &mut Vec<Diagnostic>) {
+    let mut bound = BTreeMap::new();
+    if let Some(query) = json.get("query") {
+        count_variables(query, &mut bound);
+    }
+
+    let selected = json
+        .get("variables")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(JsonValue::as_str);
+    for name in selected {
+        if !bound.contains_key(name) {
+            diagnostics.push(Diagnostic::new(
+                LintCode::UnboundSelectVariable,
+                name,
+                format!("selected variable '{}' does not occur in the query", name),
+            ));
+        }
+    }
+}
+
+fn check_schema_refs(
+    json: &JsonValue,
+    ty: &str,
+    known: &KnownNames,
+    diagnostics: &mut Vec<Diagnostic>,
+    reported: &mut BTreeSet<(&'static str, String)>,
+) {
+    // Triples on the schema graph talk about the schema itself
+    if json.get("graph").and_then(JsonValue::as_str) == Some("schema") {
+        return;
+    }
+
+    match json.get("predicate") {
+        // Path predicates are plain strings
+        Some(JsonValue::String(name)) => check_property(name, known, diagnostics, reported),
+        Some(predicate) => match node(predicate) {
+            Some("rdf:type" | RDF_TYPE) => {
+                if let Some(class) = json.get("object").and_then(node) {
+                    check_class(class, known, diagnostics, reported);
+                }
+            }
+            Some(name) => check_property(name, known, diagnostics, reported),
+            None => {}
+        },
+        None => {}
+    }
+
+    if ty == "IsA" {
+        if let Some(class) = json.get("type").and_then(node) {
+            check_class(class, known, diagnostics, reported);
+        }
+    }
+}
+
+fn check_property(
+    property: &str,
+    known: &KnownNames,
+    diagnostics: &mut Vec<Diagnostic>,
+    reported: &mut BTreeSet<(&'static str, String)>,
+) {
+    if let Some(name) = schema_name(property) {
+        if !known.properties.contains(name) && reported.insert(("property", name.to_string())) {
+            diagnostics.push(Diagnostic::new(
+                LintCode::UnknownProperty,
+                name,
+                format!("property '{}' is not declared in the schema", name),
+            ));
+        }
+    }
+}
+
+fn check_class(
+    class: &str,
+    known: &KnownNames,
+    diagnostics: &mut Vec<Diagnostic>,
+    reported: &mut BTreeSet<(&'static str, String)>,
+) {
+    if known.classes.contains(class) {
+        return;
+    }
+    if let Some(name) = schema_name(class) {
+        if !known.classes.contains(name) && reported.insert(("class", name.to_string())) {
+            diagnostics.push(Diagnostic::new(
+                LintCode::UnknownClass,
+                name,
+                format!("class '{}' is not declared in the schema", name),
+            ));
+        }
+    }
+}
+
+/// Count the occurrences of each variable, including in `Select` lists
+fn count_variables(json: &JsonValue, uses: &mut BTreeMap<String, usize>) {
+    if let Some(name) = variable(json) {
+        *uses.entry(name.to_string()).or_default() += 1;
+    }
+    if json.get("@type").and_then(JsonValue::as_str) == Some("Select") {
+        let selected = json.get("variables").and_then(JsonValue::as_array);
+        for name in selected.into_iter().flatten().filter_map(JsonValue::as_str) {
+            *uses.entry(name.to_string()).or_default() += 1;
+        }
+    }
+    match json {
+        JsonValue::Object(map) => map.values().for_each(|v| count_variables(v, uses)),
+        JsonValue::Array(items) => items.iter().for_each(|v| count_variables(v, uses)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{and, isa, select, triple, var};
+    use terminusdb_schema::{Key, Property};
+
+    fn person_schema() -> Vec<Schema> {
+        let property = |name: &str, class: &str| Property {
+            name: name.to_string(),
+            r#type: None,
+            class: class.to_string(),
+        };
+        vec![Schema::Class {
+            id: "Person".to_string(),
+            base: None,
+            key: Key::Random,
+            documentation: None,
+            subdocument: false,
+            r#abstract: false,
+            inherits: vec![],
+            unfoldable: false,
+            properties: vec![
+                property("name", "xsd:string"),
+                property("friends", "Person"),
+            ],
+        }]
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<(LintCode, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.code, d.subject.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_query() {
+        let query = select!(
+            [name],
+            and!(
+                triple!(var!(person), "rdf:type", "@schema:Person"),
+                triple!(var!(person), "name", var!(name)),
+                triple!(var!(person), "@schema:friends", var!(friend)),
+                isa!(var!(friend), "Person")
+            )
+        );
+
+        assert_eq!(lint(&query, &person_schema()), vec![]);
+    }
+
+    #[test]
+    fn test_unbound_select_variable() {
+        let query = select!(
+            [person, nmae],
+            and!(
+                triple!(var!(person), "name", var!(name)),
+                triple!(var!(person), "friends", var!(name))
+            )
+        );
+
+        assert_eq!(
+            codes(&query.lint(&[])),
+            vec![(LintCode::UnboundSelectVariable, "nmae")]
+        );
+    }
+
+    #[test]
+    fn test_single_use_variable() {
+        let query = and!(
+            triple!(var!(person), "name", var!(name)),
+            triple!(var!(persn), "friends", var!(_friend)),
+            triple!(var!(person), "friends", var!(_friend))
+        );
+
+        let diagnostics = lint(&query, &[]);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                (LintCode::SingleUseVariable, "name"),
+                (LintCode::SingleUseVariable, "persn"),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_unknown_schema_references() {
+        let query = and!(
+            triple!(var!(person), "rdf:type", "@schema:Persn"),
+            triple!(var!(person), "email", var!(email)),
+            triple!(var!(person), "email", var!(email)),
+            triple!(var!(person), "rdfs:label", var!(person)),
+            isa!(var!(person), "Company")
+        );
+
+        assert_eq!(
+            codes(&lint(&query, &person_schema())),
+            vec![
+                (LintCode::UnknownClass, "Persn"),
+                (LintCode::UnknownProperty, "email"),
+                (LintCode::UnknownClass, "Company"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_serialize() {
+        let query = select!([x], triple!(var!(y), "name", var!(y)));
+        let diagnostic = &lint(&query, &[])[0];
+
+        assert_eq!(
+            serde_json::to_value(diagnostic).unwrap(),
+            serde_json::json!({
+                "code": "unbound_select_variable",
+                "severity": "error",
+                "subject": "x",
+                "message": "selected variable 'x' does not occur in the query",
+            })
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: selected variable 'x' does not occur in the query"
+        );
+    }
+}
@@ -75,6 +75,28 @@ macro_rules! var {
     };
 }
 
+/// Create a placeholder for a [`PreparedQuery`](crate::prepared::PreparedQuery)
+/// parameter, bound to a literal value later
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::*;
+/// let min_age = param!(min_age); // Creates Value::Variable("param:min_age".to_string())
+/// ```
+#[macro_export]
+macro_rules! param {
+    ($name:ident) => {
+        $crate::value::Value::Variable(format!(
+            "{}{}",
+            $crate::prepared::PARAM_PREFIX,
+            stringify!($name)
+        ))
+    };
+    ($name:expr) => {
+        $crate::value::Value::Variable(format!("{}{}", $crate::prepared::PARAM_PREFIX, $name))
+    };
+}
+
 /// Create a NodeValue Variable
 ///
 /// # Examples
@@ -157,20 +179,19 @@ macro_rules! data {
 
 /// Create a DateTime value
 ///
+/// Accepts an RFC 3339 string or a `chrono` date-time in any time zone, which
+/// is converted to UTC. A `NaiveDateTime` is taken to be in UTC.
+///
 /// # Examples
 /// ```
 /// # use terminusdb_woql2::*;
 /// let dt = datetime!("2024-01-01T00:00:00Z"); // Creates Value::Data(XsdAnySimpleType::DateTime)
-/// let dt2 = datetime!("2024-12-31T23:59:59Z");
+/// let dt2 = datetime!(chrono::Utc::now());
 /// ```
 #[macro_export]
 macro_rules! datetime {
     ($value:expr) => {
-        $crate::value::Value::Data(terminusdb_schema::XSDAnySimpleType::DateTime(
-            chrono::DateTime::parse_from_rfc3339($value)
-                .expect("Invalid datetime format, expected RFC3339 (e.g., '2024-01-01T00:00:00Z')")
-                .with_timezone(&chrono::Utc),
-        ))
+        $crate::value::Value::Data($crate::macros::into_datetime($value))
     };
 }
 
@@ -237,6 +258,9 @@ macro_rules! t {
 
 /// Create an And query with multiple sub-queries
 ///
+/// An argument written as `..clauses` is spread: every query yielded by the
+/// iterator `clauses` is added in its place. See also [`and_all`].
+///
 /// # Examples
 /// ```
 /// # use terminusdb_woql2::*;
@@ -244,18 +268,35 @@ macro_rules! t {
 ///     triple!(var!(x), "rdf:type", "Person"),
 ///     triple!(var!(x), "name", var!(name))
 /// );
+///
+/// let filters = vec![
+///     triple!(var!(x), "status", data!("active")),
+///     triple!(var!(x), "verified", data!(true)),
+/// ];
+/// let q = and!(triple!(var!(x), "rdf:type", "Person"), ..filters);
 /// ```
 #[macro_export]
 macro_rules! and {
-    ($($query:expr),+ $(,)?) => {
+    (@chain [$($acc:tt)*]) => { $($acc)* };
+    (@chain [$($acc:tt)*] .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::and!(@chain [$($acc)*.chain($spread)] $($($rest)*)?)
+    };
+    (@chain [$($acc:tt)*] $query:expr $(, $($rest:tt)*)?) => {
+        $crate::and!(@chain [$($acc)*.chain(::std::iter::once($query))] $($($rest)*)?)
+    };
+    ($($args:tt)+) => {
         $crate::query::Query::And($crate::query::And {
-            and: vec![$($query),+]
+            and: $crate::and!(@chain [::std::iter::empty::<$crate::query::Query>()] $($args)+)
+                .collect(),
         })
     };
 }
 
 /// Create an Or query with multiple sub-queries
 ///
+/// Like [`and!`], an argument written as `..alternatives` is spread. See also
+/// [`or_all`].
+///
 /// # Examples
 /// ```
 /// # use terminusdb_woql2::*;
@@ -263,16 +304,62 @@ macro_rules! and {
 ///     triple!(var!(x), "rdf:type", "Person"),
 ///     triple!(var!(x), "rdf:type", "Organization")
 /// );
+///
+/// let types = ["Person", "Organization", "Team"];
+/// let q = or!(..types.iter().map(|t| triple!(var!(x), "rdf:type", *t)));
 /// ```
 #[macro_export]
 macro_rules! or {
-    ($($query:expr),+ $(,)?) => {
+    (@chain [$($acc:tt)*]) => { $($acc)* };
+    (@chain [$($acc:tt)*] .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::or!(@chain [$($acc)*.chain($spread)] $($($rest)*)?)
+    };
+    (@chain [$($acc:tt)*] $query:expr $(, $($rest:tt)*)?) => {
+        $crate::or!(@chain [$($acc)*.chain(::std::iter::once($query))] $($($rest)*)?)
+    };
+    ($($args:tt)+) => {
         $crate::query::Query::Or($crate::query::Or {
-            or: vec![$($query),+]
+            or: $crate::or!(@chain [::std::iter::empty::<$crate::query::Query>()] $($args)+)
+                .collect(),
         })
     };
 }
 
+/// Conjunction of all queries yielded by `clauses`, for when the number of
+/// clauses is only known at runtime. No clauses give an empty `And`, which
+/// always succeeds.
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::prelude::*;
+/// let filters = [("status", "active"), ("role", "admin")];
+/// let q = and_all(
+///     filters
+///         .iter()
+///         .map(|(field, value)| triple!(var!(x), *field, data!(*value))),
+/// );
+/// ```
+pub fn and_all(clauses: impl IntoIterator<Item = crate::query::Query>) -> crate::query::Query {
+    crate::query::Query::And(crate::query::And {
+        and: clauses.into_iter().collect(),
+    })
+}
+
+/// Disjunction of all queries yielded by `alternatives`. No alternatives give
+/// an empty `Or`, which always fails.
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::prelude::*;
+/// let ids = ["Person/1", "Person/2"];
+/// let q = or_all(ids.iter().map(|id| eq!(var!(x), node!(*id))));
+/// ```
+pub fn or_all(alternatives: impl IntoIterator<Item = crate::query::Query>) -> crate::query::Query {
+    crate::query::Query::Or(crate::query::Or {
+        or: alternatives.into_iter().collect(),
+    })
+}
+
 /// Create a Not query
 ///
 /// # Examples
@@ -383,21 +470,31 @@ macro_rules! less {
     };
 }
 
-/// Create a Path query
+/// Create a Path query, or parse a path expression into a `PathPattern`
+///
+/// The pattern may be a `PathPattern` or a path expression string in the
+/// syntax described in [`path_parser`](crate::path_parser). A malformed
+/// expression string panics; use `parse_path_pattern` to handle the error.
 ///
 /// # Examples
 /// ```
 /// # use terminusdb_woql2::*;
-/// use woql2::path::PathPattern;
-/// let pattern = PathPattern::Predicate(...);
-/// let q = path!(var!(start), pattern, var!(end));
+/// // A pattern on its own
+/// let pattern = path!("friend+>(knows|manages){1,3}");
+///
+/// // A path query from start to end
+/// let q = path!(var!(start), "friend+,name", var!(end));
+/// let q = path!(var!(start), pattern, var!(end), var!(edges));
 /// ```
 #[macro_export]
 macro_rules! path {
+    ($pattern:literal) => {
+        $crate::macros::into_path_pattern($pattern)
+    };
     ($subject:expr, $pattern:expr, $object:expr) => {
         $crate::query::Query::Path($crate::query::Path {
             subject: $crate::macros::into_value($subject),
-            pattern: $pattern,
+            pattern: $crate::macros::into_path_pattern($pattern),
             object: $crate::macros::into_value($object),
             path: None,
         })
@@ -405,7 +502,7 @@ macro_rules! path {
     ($subject:expr, $pattern:expr, $object:expr, $path:expr) => {
         $crate::query::Query::Path($crate::query::Path {
             subject: $crate::macros::into_value($subject),
-            pattern: $pattern,
+            pattern: $crate::macros::into_path_pattern($pattern),
             object: $crate::macros::into_value($object),
             path: Some($crate::macros::into_value($path)),
         })
@@ -985,9 +1082,7 @@ macro_rules! contains {
 #[macro_export]
 macro_rules! today {
     () => {
-        $crate::value::Value::Data(terminusdb_schema::XSDAnySimpleType::DateTime(
-            chrono::Utc::now(),
-        ))
+        $crate::value::Value::Data($crate::macros::now())
     };
 }
 
@@ -1051,6 +1146,77 @@ macro_rules! today_in_between {
     };
 }
 
+/// The date-time a duration before now
+///
+/// The duration is a `chrono::Duration`, a `std::time::Duration` or a whole
+/// number of days.
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::*;
+/// let q = after!(var!(last_login), ago!(30));
+/// let q2 = after!(var!(updated), ago!(chrono::Duration::hours(12)));
+/// ```
+#[macro_export]
+macro_rules! ago {
+    ($duration:expr) => {
+        $crate::value::Value::Data($crate::macros::from_now(-$crate::macros::into_duration(
+            $duration,
+        )))
+    };
+}
+
+/// The date-time a duration after now
+///
+/// Takes the same durations as [`ago!`].
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::*;
+/// let q = before!(var!(expires), from_now!(7));
+/// ```
+#[macro_export]
+macro_rules! from_now {
+    ($duration:expr) => {
+        $crate::value::Value::Data($crate::macros::from_now($crate::macros::into_duration(
+            $duration,
+        )))
+    };
+}
+
+/// Check if a date-time lies within a duration before now (inclusive)
+///
+/// Takes the same durations as [`ago!`].
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::*;
+/// let recent = within_last!(var!(created), 7); // the last 7 days
+/// let q = within_last!(var!(seen), chrono::Duration::minutes(5));
+/// ```
+#[macro_export]
+macro_rules! within_last {
+    ($date:expr, $duration:expr) => {
+        in_between!($date, ago!($duration), today!())
+    };
+}
+
+/// Check if a date-time lies within a duration after now (inclusive)
+///
+/// Takes the same durations as [`ago!`].
+///
+/// # Examples
+/// ```
+/// # use terminusdb_woql2::*;
+/// let upcoming = within_next!(var!(due), 14);
+/// ```
+#[macro_export]
+macro_rules! within_next {
+    ($date:expr, $duration:expr) => {
+        in_between!($date, today!(), from_now!($duration))
+    };
+}
+
 /// Compare macro using Rust comparison operators
 ///
 /// # Examples
@@ -2212,7 +2378,9 @@ pub use self::conversion::*;
 
 mod conversion {
     use crate::expression::ArithmeticValue;
+    use crate::path::PathPattern;
     use crate::value::{DataValue, ListOrVariable, NodeValue, Value};
+    use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
     use terminusdb_schema::XSDAnySimpleType;
 
     /// Convert various types into XSDAnySimpleType
@@ -2220,6 +2388,26 @@ mod conversion {
         value.into_xsd_type()
     }
 
+    /// Convert a string or `chrono` value into an xsd:dateTime
+    pub fn into_datetime<T: IntoDateTime>(value: T) -> XSDAnySimpleType {
+        XSDAnySimpleType::DateTime(value.into_datetime())
+    }
+
+    /// Convert a number of days or a duration into a `chrono::Duration`
+    pub fn into_duration<T: IntoDuration>(value: T) -> Duration {
+        value.into_duration()
+    }
+
+    /// The current date-time
+    pub fn now() -> XSDAnySimpleType {
+        XSDAnySimpleType::DateTime(Utc::now())
+    }
+
+    /// The date-time `offset` from now
+    pub fn from_now(offset: Duration) -> XSDAnySimpleType {
+        XSDAnySimpleType::DateTime(Utc::now() + offset)
+    }
+
     /// Convert various types into Value
     pub fn into_value<T: IntoValue>(value: T) -> Value {
         value.into_value()
@@ -2245,6 +2433,11 @@ mod conversion {
         value.into_list_or_variable()
     }
 
+    /// Convert various types into PathPattern
+    pub fn into_path_pattern<T: IntoPathPattern>(value: T) -> PathPattern {
+        value.into_path_pattern()
+    }
+
     // Trait for converting to XSDAnySimpleType
     pub trait IntoXsdType {
         fn into_xsd_type(self) -> XSDAnySimpleType;
@@ -2292,6 +2485,84 @@ mod conversion {
         }
     }
 
+    impl<Tz: TimeZone> IntoXsdType for DateTime<Tz> {
+        fn into_xsd_type(self) -> XSDAnySimpleType {
+            into_datetime(self)
+        }
+    }
+
+    impl IntoXsdType for NaiveDateTime {
+        fn into_xsd_type(self) -> XSDAnySimpleType {
+            into_datetime(self)
+        }
+    }
+
+    impl IntoXsdType for NaiveDate {
+        fn into_xsd_type(self) -> XSDAnySimpleType {
+            XSDAnySimpleType::Date(self)
+        }
+    }
+
+    // Trait for converting to a UTC date-time
+    pub trait IntoDateTime {
+        fn into_datetime(self) -> DateTime<Utc>;
+    }
+
+    impl IntoDateTime for &str {
+        fn into_datetime(self) -> DateTime<Utc> {
+            DateTime::parse_from_rfc3339(self)
+                .expect("Invalid datetime format, expected RFC3339 (e.g., '2024-01-01T00:00:00Z')")
+                .with_timezone(&Utc)
+        }
+    }
+
+    impl IntoDateTime for String {
+        fn into_datetime(self) -> DateTime<Utc> {
+            self.as_str().into_datetime()
+        }
+    }
+
+    impl<Tz: TimeZone> IntoDateTime for DateTime<Tz> {
+        fn into_datetime(self) -> DateTime<Utc> {
+            self.with_timezone(&Utc)
+        }
+    }
+
+    impl IntoDateTime for NaiveDateTime {
+        fn into_datetime(self) -> DateTime<Utc> {
+            self.and_utc()
+        }
+    }
+
+    // Trait for converting to a duration; plain numbers are days
+    pub trait IntoDuration {
+        fn into_duration(self) -> Duration;
+    }
+
+    impl IntoDuration for Duration {
+        fn into_duration(self) -> Duration {
+            self
+        }
+    }
+
+    impl IntoDuration for std::time::Duration {
+        fn into_duration(self) -> Duration {
+            Duration::from_std(self).expect("duration out of range")
+        }
+    }
+
+    impl IntoDuration for i32 {
+        fn into_duration(self) -> Duration {
+            Duration::days(self as i64)
+        }
+    }
+
+    impl IntoDuration for i64 {
+        fn into_duration(self) -> Duration {
+            Duration::days(self)
+        }
+    }
+
     // Trait for converting to Value
     pub trait IntoValue {
         fn into_value(self) -> Value;
@@ -2345,6 +2616,24 @@ mod conversion {
         }
     }
 
+    impl<Tz: TimeZone> IntoValue for DateTime<Tz> {
+        fn into_value(self) -> Value {
+            Value::Data(self.into_xsd_type())
+        }
+    }
+
+    impl IntoValue for NaiveDateTime {
+        fn into_value(self) -> Value {
+            Value::Data(self.into_xsd_type())
+        }
+    }
+
+    impl IntoValue for NaiveDate {
+        fn into_value(self) -> Value {
+            Value::Data(self.into_xsd_type())
+        }
+    }
+
     // Trait for converting to NodeValue
     pub trait IntoNodeValue {
         fn into_node_value(self) -> NodeValue;
@@ -2480,6 +2769,24 @@ mod conversion {
         }
     }
 
+    impl<Tz: TimeZone> IntoDataValue for DateTime<Tz> {
+        fn into_data_value(self) -> DataValue {
+            DataValue::Data(self.into_xsd_type())
+        }
+    }
+
+    impl IntoDataValue for NaiveDateTime {
+        fn into_data_value(self) -> DataValue {
+            DataValue::Data(self.into_xsd_type())
+        }
+    }
+
+    impl IntoDataValue for NaiveDate {
+        fn into_data_value(self) -> DataValue {
+            DataValue::Data(self.into_xsd_type())
+        }
+    }
+
     // Trait for converting to ListOrVariable
     pub trait IntoListOrVariable {
         fn into_list_or_variable(self) -> ListOrVariable;
@@ -2533,6 +2840,30 @@ mod conversion {
         }
     }
 
+    // Trait for converting to PathPattern; strings are parsed as path
+    // expressions and panic when malformed, like the other macro conversions
+    pub trait IntoPathPattern {
+        fn into_path_pattern(self) -> PathPattern;
+    }
+
+    impl IntoPathPattern for PathPattern {
+        fn into_path_pattern(self) -> PathPattern {
+            self
+        }
+    }
+
+    impl IntoPathPattern for &str {
+        fn into_path_pattern(self) -> PathPattern {
+            crate::path_parser::parse_path_pattern(self).unwrap_or_else(|err| panic!("{}", err))
+        }
+    }
+
+    impl IntoPathPattern for String {
+        fn into_path_pattern(self) -> PathPattern {
+            self.as_str().into_path_pattern()
+        }
+    }
+
     // Trait for converting to select! arguments
     pub trait IntoSelectArg {
         fn into_select_arg(self) -> String;
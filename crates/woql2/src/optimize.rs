@@ -0,0 +1,403 @@
+//! Semantics-preserving rewrites for machine-generated WOQL.
+//!
+//! [`optimize`] runs a single bottom-up pass that:
+//!
+//! - flattens nested `And`/`Or` into their parent, drops `True` conjuncts and
+//!   unwraps single-element `And`/`Or`;
+//! - removes repeated `Triple`/`Link`/`Data` patterns from a conjunction (a
+//!   pattern that already matched matches again);
+//! - collapses `Select(outer, Select(inner, q))` into `Select(outer, q)` when
+//!   every outer variable is also selected by the inner one;
+//! - reorders each contiguous run of triple patterns in a conjunction so the
+//!   most selective ones (bound subject, then bound object, then bound
+//!   predicate) run first and `rdf:type` patterns run last.
+//!
+//! Reordering only ever happens inside a run of plain patterns, so operators
+//! that depend on earlier bindings (`Not`, `Eval`, comparisons, ...) still see
+//! exactly the same bound variables. It does change the order in which
+//! solutions are produced, so it is skipped under `Start`, `Limit` and `Once`,
+//! whose result depends on that order, and under `Collect` and `GroupBy`, whose
+//! lists are built in that order. Subqueries wrapped in `Pin` are left
+//! untouched entirely.
+
+use crate::control::{self, Distinct, If, Immediately, Once, Select, Using, WoqlOptional};
+use crate::misc::{Collect, Count, Limit, Start};
+use crate::order::{GroupBy, OrderBy};
+use crate::query::{And, Not, Or, Query};
+use crate::value::{DataValue, NodeValue, Value};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// Optimize a query; see the [module docs](self) for the rewrites applied.
+pub fn optimize(query: Query) -> Query {
+    optimize_with(query, true)
+}
+
+impl Query {
+    /// Shorthand for [`optimize`].
+    pub fn optimize(self) -> Query {
+        optimize(self)
+    }
+}
+
+fn optimize_with(query: Query, reorder: bool) -> Query {
+    let sub = |q: Box<Query>| Box::new(optimize_with(*q, reorder));
+    let ordered = |q: Box<Query>| Box::new(optimize_with(*q, false));
+
+    match query {
+        Query::And(And { and }) => optimize_and(and, reorder),
+        Query::Or(Or { or }) => optimize_or(or, reorder),
+        Query::Select(Select { variables, query }) => match optimize_with(*query, reorder) {
+            Query::Select(inner) if variables.iter().all(|v| inner.variables.contains(v)) => {
+                Query::Select(Select {
+                    variables,
+                    query: inner.query,
+                })
+            }
+            query => Query::Select(Select {
+                variables,
+                query: Box::new(query),
+            }),
+        },
+        Query::Not(Not { query }) => Query::Not(Not { query: sub(query) }),
+        Query::Distinct(Distinct { variables, query }) => Query::Distinct(Distinct {
+            variables,
+            query: sub(query),
+        }),
+        Query::If(If {
+            test,
+            then_query,
+            else_query,
+        }) => Query::If(If {
+            test: sub(test),
+            then_query: sub(then_query),
+            else_query: sub(else_query),
+        }),
+        Query::WoqlOptional(WoqlOptional { query }) => {
+            Query::WoqlOptional(WoqlOptional { query: sub(query) })
+        }
+        Query::Immediately(Immediately { query }) => {
+            Query::Immediately(Immediately { query: sub(query) })
+        }
+        Query::Using(Using { collection, query }) => Query::Using(Using {
+            collection,
+            query: sub(query),
+        }),
+        Query::From(control::From { graph, query }) => Query::From(control::From {
+            graph,
+            query: sub(query),
+        }),
+        Query::Into(control::Into { graph, query }) => Query::Into(control::Into {
+            graph,
+            query: sub(query),
+        }),
+        Query::Count(Count { query, count }) => Query::Count(Count {
+            query: sub(query),
+            count,
+        }),
+        Query::Collect(Collect {
+            template,
+            into,
+            query,
+        }) => Query::Collect(Collect {
+            template,
+            into,
+            query: ordered(query),
+        }),
+        Query::GroupBy(GroupBy {
+            template,
+            group_by,
+            grouped_value,
+            query,
+        }) => Query::GroupBy(GroupBy {
+            template,
+            group_by,
+            grouped_value,
+            query: ordered(query),
+        }),
+        // Sorting makes the inner solution order irrelevant again.
+        Query::OrderBy(OrderBy { ordering, query }) => Query::OrderBy(OrderBy {
+            ordering,
+            query: Box::new(optimize_with(*query, true)),
+        }),
+        Query::Start(Start { start, query }) => Query::Start(Start {
+            start,
+            query: ordered(query),
+        }),
+        Query::Limit(Limit { limit, query }) => Query::Limit(Limit {
+            limit,
+            query: ordered(query),
+        }),
+        Query::Once(Once { query }) => Query::Once(Once {
+            query: ordered(query),
+        }),
+        // `Pin` opts its subquery out of optimization.
+        other => other,
+    }
+}
+
+fn optimize_and(conjuncts: Vec<Query>, reorder: bool) -> Query {
+    let mut flat = Vec::with_capacity(conjuncts.len());
+    for conjunct in conjuncts {
+        match optimize_with(conjunct, reorder) {
+            Query::And(And { and }) => flat.extend(and),
+            Query::True(_) => {}
+            query => flat.push(query),
+        }
+    }
+
+    let mut deduped: Vec<Query> = Vec::with_capacity(flat.len());
+    for query in flat {
+        if !(is_pattern(&query) && deduped.contains(&query)) {
+            deduped.push(query);
+        }
+    }
+
+    if reorder {
+        // Sort each maximal run of patterns in place, leaving other operators
+        // where they are.
+        let mut start = 0;
+        while start < deduped.len() {
+            let len = deduped[start..]
+                .iter()
+                .take_while(|q| is_pattern(q))
+                .count();
+            deduped[start..start + len].sort_by_key(pattern_cost);
+            start += len.max(1);
+        }
+    }
+
+    match deduped.len() {
+        0 => Query::And(And { and: vec![] }),
+        1 => deduped.pop().unwrap(),
+        _ => Query::And(And { and: deduped }),
+    }
+}
+
+fn optimize_or(disjuncts: Vec<Query>, reorder: bool) -> Query {
+    // Duplicate branches are kept: each contributes its own solutions.
+    let mut flat = Vec::with_capacity(disjuncts.len());
+    for disjunct in disjuncts {
+        match optimize_with(disjunct, reorder) {
+            Query::Or(Or { or }) => flat.extend(or),
+            query => flat.push(query),
+        }
+    }
+
+    if flat.len() == 1 {
+        flat.pop().unwrap()
+    } else {
+        Query::Or(Or { or: flat })
+    }
+}
+
+/// Plain graph lookups: free of side effects and commutative within a conjunction.
+fn is_pattern(query: &Query) -> bool {
+    matches!(query, Query::Triple(_) | Query::Link(_) | Query::Data(_))
+}
+
+/// Lower runs earlier. Unbound subjects cost most, then unbound objects, then
+/// unbound predicates; type lookups go after everything else.
+fn pattern_cost(query: &Query) -> u8 {
+    let (subject, predicate, object_bound) = match query {
+        Query::Triple(t) => (
+            &t.subject,
+            &t.predicate,
+            !matches!(t.object, Value::Variable(_)),
+        ),
+        Query::Link(l) => (
+            &l.subject,
+            &l.predicate,
+            matches!(l.object, NodeValue::Node(_)),
+        ),
+        Query::Data(d) => (
+            &d.subject,
+            &d.predicate,
+            !matches!(d.object, DataValue::Variable(_)),
+        ),
+        _ => return u8::MAX,
+    };
+
+    let unbound = |node: &NodeValue| matches!(node, NodeValue::Variable(_));
+    let is_type = matches!(predicate, NodeValue::Node(p) if p == "rdf:type" || p == RDF_TYPE);
+
+    (is_type as u8) * 8
+        + (unbound(subject) as u8) * 4
+        + (!object_bound as u8) * 2
+        + (unbound(predicate) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::Pin;
+    use crate::query::True;
+    use crate::triple::Triple;
+
+    fn triple(s: &str, p: &str, o: &str) -> Query {
+        let node = |x: &str| match x.strip_prefix('?') {
+            Some(v) => NodeValue::Variable(v.to_string()),
+            None => NodeValue::Node(x.to_string()),
+        };
+        Query::Triple(Triple {
+            subject: node(s),
+            predicate: node(p),
+            object: match o.strip_prefix('?') {
+                Some(v) => Value::Variable(v.to_string()),
+                None => Value::Node(o.to_string()),
+            },
+            graph: None,
+        })
+    }
+
+    fn and(queries: Vec<Query>) -> Query {
+        Query::And(And { and: queries })
+    }
+
+    #[test]
+    fn test_flattens_and_drops_true() {
+        let q = and(vec![
+            and(vec![triple("?x", "name", "?n"), Query::True(True {})]),
+            and(vec![and(vec![triple("?x", "age", "?a")])]),
+        ]);
+
+        assert_eq!(
+            optimize(q),
+            and(vec![triple("?x", "name", "?n"), triple("?x", "age", "?a")])
+        );
+    }
+
+    #[test]
+    fn test_flattens_or_but_keeps_duplicate_branches() {
+        let q = Query::Or(Or {
+            or: vec![
+                triple("?x", "a", "?y"),
+                Query::Or(Or {
+                    or: vec![triple("?x", "a", "?y")],
+                }),
+            ],
+        });
+
+        assert_eq!(
+            optimize(q),
+            Query::Or(Or {
+                or: vec![triple("?x", "a", "?y"), triple("?x", "a", "?y")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_removes_duplicate_patterns() {
+        let q = and(vec![
+            triple("?x", "name", "?n"),
+            and(vec![triple("?x", "name", "?n")]),
+        ]);
+
+        assert_eq!(optimize(q), triple("?x", "name", "?n"));
+    }
+
+    #[test]
+    fn test_reorders_patterns_by_selectivity() {
+        let q = and(vec![
+            triple("?x", "rdf:type", "Person"),
+            triple("?x", "?p", "?o"),
+            triple("?x", "name", "Alice"),
+            triple("Person/bob", "friend", "?x"),
+        ]);
+
+        assert_eq!(
+            optimize(q),
+            and(vec![
+                triple("Person/bob", "friend", "?x"),
+                triple("?x", "name", "Alice"),
+                triple("?x", "?p", "?o"),
+                triple("?x", "rdf:type", "Person"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_reordering_stays_within_pattern_runs() {
+        let not = Query::Not(Not {
+            query: Box::new(triple("?x", "deleted", "true")),
+        });
+        let q = and(vec![
+            triple("?x", "rdf:type", "Person"),
+            not.clone(),
+            triple("?x", "?p", "?o"),
+            triple("?x", "name", "Alice"),
+        ]);
+
+        assert_eq!(
+            optimize(q),
+            and(vec![
+                triple("?x", "rdf:type", "Person"),
+                not,
+                triple("?x", "name", "Alice"),
+                triple("?x", "?p", "?o"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_collapses_nested_select() {
+        let body = triple("?x", "name", "?n");
+        let q = Query::Select(Select {
+            variables: vec!["x".to_string()],
+            query: Box::new(Query::Select(Select {
+                variables: vec!["x".to_string(), "n".to_string()],
+                query: Box::new(body.clone()),
+            })),
+        });
+
+        assert_eq!(
+            optimize(q),
+            Query::Select(Select {
+                variables: vec!["x".to_string()],
+                query: Box::new(body),
+            })
+        );
+    }
+
+    #[test]
+    fn test_keeps_order_under_limit_and_pin() {
+        let patterns = and(vec![
+            triple("?x", "rdf:type", "Person"),
+            triple("?x", "name", "Alice"),
+        ]);
+
+        let limited = Query::Limit(Limit {
+            limit: 1,
+            query: Box::new(patterns.clone()),
+        });
+        assert_eq!(optimize(limited.clone()), limited);
+
+        let pinned = Query::Pin(Pin {
+            query: Box::new(and(vec![patterns.clone(), patterns])),
+        });
+        assert_eq!(optimize(pinned.clone()), pinned);
+    }
+
+    #[test]
+    fn test_keeps_order_under_collect_and_group_by() {
+        let patterns = and(vec![
+            triple("?x", "rdf:type", "Person"),
+            triple("?x", "name", "Alice"),
+        ]);
+
+        let collected = Query::Collect(Collect {
+            template: Value::Variable("x".to_string()),
+            into: Value::Variable("xs".to_string()),
+            query: Box::new(patterns.clone()),
+        });
+        assert_eq!(optimize(collected.clone()), collected);
+
+        let grouped = Query::GroupBy(GroupBy {
+            template: Value::Variable("x".to_string()),
+            group_by: vec!["x".to_string()],
+            grouped_value: Value::Variable("groups".to_string()),
+            query: Box::new(patterns),
+        });
+        assert_eq!(optimize(grouped.clone()), grouped);
+    }
+}
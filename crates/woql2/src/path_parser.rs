@@ -0,0 +1,298 @@
+//! Parser for TerminusDB path expression strings
+//!
+//! Turns the compact path syntax used by the server and the JavaScript client
+//! into a [`PathPattern`], so that
+//!
+//! ```rust
+//! use terminusdb_woql2::path_parser::parse_path_pattern;
+//!
+//! let pattern = parse_path_pattern("friend+>(knows|manages){1,3}").unwrap();
+//! ```
+//!
+//! replaces a hand-built `PathSequence` of a `PathPlus` and a `PathTimes`
+//! wrapping a `PathOr`. The grammar, from loosest to tightest binding:
+//!
+//! | Syntax       | Meaning                                       |
+//! |--------------|-----------------------------------------------|
+//! | `A\|B`       | choice ([`PathOr`])                           |
+//! | `A,B`, `A>B` | sequence ([`PathSequence`])                   |
+//! | `A+`         | one or more times ([`PathPlus`])              |
+//! | `A*`         | zero or more times ([`PathStar`])             |
+//! | `A{n,m}`     | between `n` and `m` times ([`PathTimes`])     |
+//! | `A{n}`       | exactly `n` times                             |
+//! | `p`, `p>`    | follow predicate `p` forward                  |
+//! | `<p`         | follow predicate `p` backward                 |
+//! | `.`          | any predicate                                 |
+//! | `(A)`        | grouping                                      |
+//!
+//! Whitespace between tokens is ignored.
+
+use crate::path::{
+    InversePathPredicate, PathOr, PathPattern, PathPlus, PathPredicate, PathSequence, PathStar,
+    PathTimes,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a path expression string is malformed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathParseError {
+    /// byte offset in the input at which parsing failed
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid path expression at position {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+impl std::error::Error for PathParseError {}
+
+/// Parse a path expression such as `"friend+>(knows|manages){1,3}"`
+pub fn parse_path_pattern(input: &str) -> Result<PathPattern, PathParseError> {
+    let mut parser = Parser { input, pos: 0 };
+    let pattern = parser.choice()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(pattern),
+        Some(c) => Err(parser.error(format!("unexpected '{}'", c))),
+    }
+}
+
+impl FromStr for PathPattern {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_path_pattern(s)
+    }
+}
+
+/// Characters with a meaning of their own, which therefore end a predicate name
+const SPECIAL: &str = ",|+*{}()<>";
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: impl Into<String>) -> PathParseError {
+        PathParseError {
+            position: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Consume `expected` if it is the next non-whitespace character
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PathParseError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", expected)))
+        }
+    }
+
+    fn choice(&mut self) -> Result<PathPattern, PathParseError> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat('|') {
+            alternatives.push(self.sequence()?);
+        }
+        Ok(match alternatives.len() {
+            1 => alternatives.pop().unwrap(),
+            _ => PathPattern::Or(PathOr { or: alternatives }),
+        })
+    }
+
+    fn sequence(&mut self) -> Result<PathPattern, PathParseError> {
+        let mut steps = vec![self.postfix()?];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.bump(),
+                Some('>') => {
+                    self.bump();
+                    // a trailing `>` only marks the step before it as forward
+                    self.skip_whitespace();
+                    if matches!(self.peek(), None | Some('|') | Some(')') | Some(',')) {
+                        continue;
+                    }
+                }
+                _ => break,
+            }
+            steps.push(self.postfix()?);
+        }
+        Ok(match steps.len() {
+            1 => steps.pop().unwrap(),
+            _ => PathPattern::Sequence(PathSequence { sequence: steps }),
+        })
+    }
+
+    fn postfix(&mut self) -> Result<PathPattern, PathParseError> {
+        let mut pattern = self.atom()?;
+        loop {
+            if self.eat('+') {
+                pattern = PathPattern::Plus(PathPlus {
+                    plus: Box::new(pattern),
+                });
+            } else if self.eat('*') {
+                pattern = PathPattern::Star(PathStar {
+                    star: Box::new(pattern),
+                });
+            } else if self.eat('{') {
+                let from = self.number()?;
+                let to = if self.eat(',') { self.number()? } else { from };
+                if to < from {
+                    return Err(self.error(format!("repetition {{{},{}}} is empty", from, to)));
+                }
+                self.expect('}')?;
+                pattern = PathPattern::Times(PathTimes {
+                    times: Box::new(pattern),
+                    from,
+                    to,
+                });
+            } else {
+                return Ok(pattern);
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Result<PathPattern, PathParseError> {
+        if self.eat('(') {
+            let pattern = self.choice()?;
+            self.expect(')')?;
+            return Ok(pattern);
+        }
+        if self.eat('<') {
+            let predicate = self.predicate()?;
+            return Ok(PathPattern::InversePredicate(InversePathPredicate {
+                predicate,
+            }));
+        }
+        let predicate = self.predicate()?;
+        Ok(PathPattern::Predicate(PathPredicate { predicate }))
+    }
+
+    /// A predicate name, or `None` for the `.` wildcard
+    fn predicate(&mut self) -> Result<Option<String>, PathParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !SPECIAL.contains(c))
+        {
+            self.bump();
+        }
+        match &self.input[start..self.pos] {
+            "" => match self.peek() {
+                Some(c) => Err(self.error(format!("expected a predicate, found '{}'", c))),
+                None => Err(self.error("expected a predicate, found end of input")),
+            },
+            "." => Ok(None),
+            name => Ok(Some(name.to_string())),
+        }
+    }
+
+    fn number(&mut self) -> Result<u64, PathParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        self.input[start..self.pos]
+            .parse()
+            .map_err(|_| self.error("expected a repetition count"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pred(name: &str) -> PathPattern {
+        PathPattern::Predicate(PathPredicate {
+            predicate: Some(name.to_string()),
+        })
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(
+            parse_path_pattern("a,b|c").unwrap(),
+            PathPattern::Or(PathOr {
+                or: vec![
+                    PathPattern::Sequence(PathSequence {
+                        sequence: vec![pred("a"), pred("b")],
+                    }),
+                    pred("c"),
+                ],
+            })
+        );
+        assert_eq!(
+            parse_path_pattern("a,b+").unwrap(),
+            PathPattern::Sequence(PathSequence {
+                sequence: vec![
+                    pred("a"),
+                    PathPattern::Plus(PathPlus {
+                        plus: Box::new(pred("b")),
+                    }),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_forward_marker() {
+        assert_eq!(parse_path_pattern("friend>").unwrap(), pred("friend"));
+        assert_eq!(
+            parse_path_pattern("friend>,name").unwrap(),
+            parse_path_pattern("friend,name").unwrap()
+        );
+        assert_eq!(
+            parse_path_pattern("friend>name").unwrap(),
+            parse_path_pattern("friend,name").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse_path_pattern("friend{3,1}").unwrap_err();
+        assert!(err.message.contains("empty"), "{err}");
+        assert_eq!(parse_path_pattern("(a|b").unwrap_err().position, 4);
+        assert_eq!(parse_path_pattern("a,,b").unwrap_err().position, 2);
+        assert!(parse_path_pattern("").is_err());
+        assert!(parse_path_pattern("a)").is_err());
+        assert!(parse_path_pattern("a{x}").is_err());
+    }
+}
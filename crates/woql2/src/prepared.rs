@@ -0,0 +1,349 @@
+//! Query templates with named parameters.
+//!
+//! A [`PreparedQuery`] is built once from a query containing placeholders
+//! ([`param!`](crate::param)) and then bound to literal values as often as
+//! needed. Values are substituted into the serialized WOQL rather than spliced
+//! into a query string, so a bound value can only ever be data: it cannot turn
+//! into a variable or change the structure of the query.
+//!
+//! ```
+//! use terminusdb_woql2::prelude::*;
+//! use terminusdb_woql2::{and, greater, param, triple, var};
+//!
+//! let adults = PreparedQuery::new(and!(
+//!     triple!(var!(person), "age", var!(age)),
+//!     greater!(var!(age), param!(min_age))
+//! ))
+//! .with_type("min_age", ParamType::Integer);
+//!
+//! let json = adults.bind([("min_age", 18)]).unwrap();
+//! assert!(adults.bind([("min_age", "eighteen")]).is_err());
+//! # let _ = json;
+//! ```
+//!
+//! The bound JSON is what the client sends; pass it to `query_raw`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use terminusdb_schema::{ToJson, ToTDBInstance, XSDAnySimpleType};
+
+use crate::expression::ArithmeticValue;
+use crate::macros::IntoDataValue;
+use crate::prelude::Query;
+use crate::value::{DataValue, NodeValue, Value};
+
+/// Variable-name prefix marking a placeholder; see [`param!`](crate::param).
+pub const PARAM_PREFIX: &str = "param:";
+
+/// Expected type of a parameter value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamType {
+    String,
+    Decimal,
+    Float,
+    Boolean,
+    HexBinary,
+    Uri,
+    DateTime,
+    Date,
+    Time,
+    UnsignedInt,
+    Integer,
+}
+
+impl ParamType {
+    /// The type of a literal value.
+    pub fn of(value: &XSDAnySimpleType) -> Self {
+        match value {
+            XSDAnySimpleType::String(_) => ParamType::String,
+            XSDAnySimpleType::Decimal(_) => ParamType::Decimal,
+            XSDAnySimpleType::Float(_) => ParamType::Float,
+            XSDAnySimpleType::Boolean(_) => ParamType::Boolean,
+            XSDAnySimpleType::HexBinary(_) => ParamType::HexBinary,
+            XSDAnySimpleType::URI(_) => ParamType::Uri,
+            XSDAnySimpleType::DateTime(_) => ParamType::DateTime,
+            XSDAnySimpleType::Date(_) => ParamType::Date,
+            XSDAnySimpleType::Time(_) => ParamType::Time,
+            XSDAnySimpleType::UnsignedInt(_) => ParamType::UnsignedInt,
+            XSDAnySimpleType::Integer(_) => ParamType::Integer,
+        }
+    }
+}
+
+/// A query template whose [`param!`](crate::param) placeholders are filled in
+/// with literal values by [`bind`](Self::bind).
+#[derive(Debug, Clone)]
+pub struct PreparedQuery {
+    template: JsonValue,
+    params: BTreeSet<String>,
+    types: BTreeMap<String, ParamType>,
+}
+
+impl PreparedQuery {
+    /// Serialize `query` once and record the placeholders it contains.
+    pub fn new(query: Query) -> Self {
+        let template = query.to_woql_json();
+        let mut params = BTreeSet::new();
+        collect_params(&template, &mut params);
+        Self {
+            template,
+            params,
+            types: BTreeMap::new(),
+        }
+    }
+
+    /// Require values bound to `name` to be of type `ty`. Without a declared
+    /// type, any literal is accepted.
+    pub fn with_type(mut self, name: impl Into<String>, ty: ParamType) -> Self {
+        self.types.insert(name.into(), ty);
+        self
+    }
+
+    /// Names of the placeholders in the query, without [`PARAM_PREFIX`].
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.params.iter().map(String::as_str)
+    }
+
+    /// Substitute every placeholder and return the WOQL JSON to send.
+    ///
+    /// Fails if a placeholder is left unbound, a name is not a placeholder of
+    /// this query, a value is a variable or does not match its declared type,
+    /// or a value that is not a string or URI is used in a node position.
+    pub fn bind<'a, V: IntoDataValue>(
+        &self,
+        values: impl IntoIterator<Item = (&'a str, V)>,
+    ) -> anyhow::Result<JsonValue> {
+        let mut bound = BTreeMap::new();
+        for (name, value) in values {
+            if !self.params.contains(name) {
+                bail!("'{}' is not a parameter of this query", name);
+            }
+            let value = value.into_data_value();
+            self.check_type(name, &value)?;
+            bound.insert(name.to_string(), value);
+        }
+
+        if let Some(missing) = self.params.iter().find(|p| !bound.contains_key(*p)) {
+            bail!("parameter '{}' is not bound", missing);
+        }
+
+        let mut json = self.template.clone();
+        substitute(&mut json, &bound)?;
+        Ok(json)
+    }
+
+    /// The template with the declared types, as stored by
+    /// [`to_binary`](Self::to_binary)
+    pub(crate) fn to_json(&self) -> JsonValue {
+        serde_json::json!({ "template": self.template, "types": self.types })
+    }
+
+    pub(crate) fn from_json(mut json: JsonValue) -> anyhow::Result<Self> {
+        let template = json
+            .get_mut("template")
+            .map(JsonValue::take)
+            .ok_or_else(|| anyhow!("prepared query has no template"))?;
+        let types = match json.get_mut("types") {
+            Some(types) => serde_json::from_value(types.take())?,
+            None => BTreeMap::new(),
+        };
+        let mut params = BTreeSet::new();
+        collect_params(&template, &mut params);
+        Ok(Self {
+            template,
+            params,
+            types,
+        })
+    }
+
+    fn check_type(&self, name: &str, value: &DataValue) -> anyhow::Result<()> {
+        match value {
+            DataValue::Variable(var) => {
+                bail!(
+                    "parameter '{}' must be a literal, not variable '{}'",
+                    name,
+                    var
+                )
+            }
+            DataValue::List(items) => items
+                .iter()
+                .try_for_each(|item| self.check_type(name, item)),
+            DataValue::Data(data) => match self.types.get(name) {
+                Some(expected) if *expected != ParamType::of(data) => bail!(
+                    "parameter '{}' expects {:?}, got {:?}",
+                    name,
+                    expected,
+                    ParamType::of(data)
+                ),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// The placeholder name of a serialized variable, if it is one.
+fn placeholder(json: &JsonValue) -> Option<(&str, &str)> {
+    let object = json.as_object()?;
+    let name = object
+        .get("variable")?
+        .as_str()?
+        .strip_prefix(PARAM_PREFIX)?;
+    let ty = object
+        .get("@type")
+        .and_then(JsonValue::as_str)
+        .unwrap_or("Value");
+    Some((name, ty))
+}
+
+fn collect_params(json: &JsonValue, params: &mut BTreeSet<String>) {
+    if let Some((name, _)) = placeholder(json) {
+        params.insert(name.to_string());
+        return;
+    }
+    match json {
+        JsonValue::Object(map) => map.values().for_each(|v| collect_params(v, params)),
+        JsonValue::Array(items) => items.iter().for_each(|v| collect_params(v, params)),
+        _ => {}
+    }
+}
+
+fn substitute(json: &mut JsonValue, bound: &BTreeMap<String, DataValue>) -> anyhow::Result<()> {
+    if let Some((name, ty)) = placeholder(json) {
+        let value = bound
+            .get(name)
+            .ok_or_else(|| anyhow!("parameter '{}' is not bound", name))?;
+        let literal = literal_json(name, ty, value.clone())?;
+        *json = literal;
+        return Ok(());
+    }
+    match json {
+        JsonValue::Object(map) => map.values_mut().try_for_each(|v| substitute(v, bound)),
+        JsonValue::Array(items) => items.iter_mut().try_for_each(|v| substitute(v, bound)),
+        _ => Ok(()),
+    }
+}
+
+/// Serialize `value` as the value type the placeholder was serialized as.
+fn literal_json(name: &str, ty: &str, value: DataValue) -> anyhow::Result<JsonValue> {
+    Ok(match ty {
+        "DataValue" => value.to_instance(None).to_json(),
+        "NodeValue" => match value {
+            DataValue::Data(XSDAnySimpleType::String(node) | XSDAnySimpleType::URI(node)) => {
+                NodeValue::Node(node).to_instance(None).to_json()
+            }
+            _ => bail!(
+                "parameter '{}' is used as a node and must be a string or URI",
+                name
+            ),
+        },
+        "ArithmeticValue" => match value {
+            DataValue::Data(data) => ArithmeticValue::Data(data).to_instance(None).to_json(),
+            _ => bail!(
+                "parameter '{}' is used in arithmetic and must be a single value",
+                name
+            ),
+        },
+        _ => data_to_value(value).to_instance(None).to_json(),
+    })
+}
+
+fn data_to_value(value: DataValue) -> Value {
+    match value {
+        DataValue::Data(data) => Value::Data(data),
+        DataValue::List(items) => Value::List(items.into_iter().map(data_to_value).collect()),
+        DataValue::Variable(var) => Value::Variable(var),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macros::IntoNodeValue;
+    use crate::prelude::Triple;
+
+    fn adults() -> PreparedQuery {
+        PreparedQuery::new(crate::and!(
+            crate::triple!(crate::var!(person), "age", crate::var!(age)),
+            crate::greater!(crate::var!(age), crate::param!(min_age))
+        ))
+    }
+
+    #[test]
+    fn test_collects_parameters() {
+        let query = PreparedQuery::new(Query::Triple(Triple {
+            subject: crate::param!("person").into_node_value(),
+            predicate: NodeValue::Node("name".to_string()),
+            object: crate::param!(name),
+            graph: None,
+        }));
+
+        assert_eq!(
+            query.parameters().collect::<Vec<_>>(),
+            vec!["name", "person"]
+        );
+    }
+
+    #[test]
+    fn test_bind_substitutes_literals() {
+        let json = adults().bind([("min_age", 18)]).unwrap();
+        let expected = DataValue::Data(XSDAnySimpleType::Integer(18))
+            .to_instance(None)
+            .to_json();
+
+        assert_eq!(json["and"][1]["right"], expected);
+        // Ordinary variables are left alone
+        assert_eq!(json["and"][1]["left"]["variable"], "age");
+    }
+
+    #[test]
+    fn test_template_is_reusable() {
+        let query = adults();
+        let first = query.bind([("min_age", 18)]).unwrap();
+        let second = query.bind([("min_age", 65)]).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(query.bind([("min_age", 18)]).unwrap(), first);
+    }
+
+    #[test]
+    fn test_type_checking() {
+        let query = adults().with_type("min_age", ParamType::Integer);
+
+        assert!(query.bind([("min_age", 18)]).is_ok());
+        let err = query.bind([("min_age", "18")]).unwrap_err();
+        assert!(err.to_string().contains("expects Integer"));
+    }
+
+    #[test]
+    fn test_rejects_missing_unknown_and_variable_values() {
+        let query = adults();
+
+        assert!(query.bind(Vec::<(&str, i64)>::new()).is_err());
+        assert!(query.bind([("min_age", 18), ("max_age", 65)]).is_err());
+        assert!(query
+            .bind([("min_age", DataValue::Variable("x".to_string()))])
+            .is_err());
+    }
+
+    #[test]
+    fn test_node_position_requires_string() {
+        let query = PreparedQuery::new(Query::Triple(Triple {
+            subject: crate::param!(person).into_node_value(),
+            predicate: NodeValue::Node("name".to_string()),
+            object: crate::var!(name),
+            graph: None,
+        }));
+
+        let json = query.bind([("person", "Person/alice")]).unwrap();
+        assert_eq!(
+            json["subject"],
+            NodeValue::Node("Person/alice".to_string())
+                .to_instance(None)
+                .to_json()
+        );
+        assert!(query.bind([("person", 42)]).is_err());
+    }
+}
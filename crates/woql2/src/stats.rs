@@ -0,0 +1,192 @@
+//! Size and cost estimates for WOQL queries.
+//!
+//! [`Query::stats`] summarizes how big a query is, so applications can reject
+//! pathologically large machine-generated queries before sending them:
+//!
+//! ```
+//! # use terminusdb_woql2::*;
+//! let query = and!(
+//!     triple!(var!(person), "rdf:type", "@schema:Person"),
+//!     path!(var!(person), "friend+", var!(friend))
+//! );
+//!
+//! let stats = query.stats();
+//! assert_eq!(stats.triples, 1);
+//! assert_eq!(stats.depth, 4);
+//! assert!(stats.complexity < 100);
+//! ```
+//!
+//! Like [`lint`](crate::lint), the counts are taken over the serialized WOQL,
+//! so every query shape is covered. Values, literals and other leaf data are
+//! not nodes; queries, arithmetic expressions and path patterns are.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::query::Query;
+
+/// Classes that only carry data, rather than being part of the query tree
+const DATA_CLASSES: [&str; 11] = [
+    "Value",
+    "NodeValue",
+    "DataValue",
+    "ArithmeticValue",
+    "DictionaryTemplate",
+    "FieldValuePair",
+    "OrderTemplate",
+    "Column",
+    "QueryResource",
+    "Indicator",
+    "Source",
+];
+
+/// Classes that match edges in the graph
+const TRIPLE_CLASSES: [&str; 17] = [
+    "Triple",
+    "AddTriple",
+    "AddedTriple",
+    "DeleteTriple",
+    "DeletedTriple",
+    "Link",
+    "Data",
+    "AddLink",
+    "AddedLink",
+    "AddData",
+    "AddedData",
+    "DeleteLink",
+    "DeletedLink",
+    "TripleSlice",
+    "TripleSliceRev",
+    "TripleNext",
+    "TriplePrevious",
+];
+
+/// Size of a query, from [`Query::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QueryStats {
+    /// Number of nodes of each class, e.g. `"Triple"` or `"PathStar"`
+    pub nodes: BTreeMap<String, usize>,
+    /// Number of triple and link patterns, including inserts and deletes
+    pub triples: usize,
+    /// Deepest nesting of nodes; a lone triple has depth 1
+    pub depth: usize,
+    /// Relative cost estimate; see [`Query::stats`]
+    pub complexity: u64,
+}
+
+impl QueryStats {
+    /// Total number of nodes of all classes
+    pub fn node_count(&self) -> usize {
+        self.nodes.values().sum()
+    }
+}
+
+impl Query {
+    /// Count the nodes of this query and estimate its cost.
+    ///
+    /// The complexity score adds up a weight per node: 1 for most nodes, 4
+    /// for a triple pattern or document read, 8 for a `Path` or `Get`, and 16
+    /// for a repeated path pattern (`+`, `*` or `{n,m}`), as the server may
+    /// traverse a large part of the graph for it. It is only meant to compare
+    /// queries against each other or a threshold, not to predict run time.
+    pub fn stats(&self) -> QueryStats {
+        let mut stats = QueryStats::default();
+        let depth = visit(&self.to_woql_json(), &mut stats);
+        QueryStats { depth, ..stats }
+    }
+}
+
+/// Record the nodes in `json` and return its depth
+fn visit(json: &JsonValue, stats: &mut QueryStats) -> usize {
+    let children = match json {
+        JsonValue::Object(map) => map.values().collect(),
+        JsonValue::Array(items) => items.iter().collect(),
+        _ => Vec::new(),
+    };
+    let depth = children
+        .into_iter()
+        .map(|child| visit(child, stats))
+        .max()
+        .unwrap_or(0);
+
+    match node_class(json) {
+        Some(class) => {
+            *stats.nodes.entry(class.to_string()).or_default() += 1;
+            if TRIPLE_CLASSES.contains(&class) {
+                stats.triples += 1;
+            }
+            stats.complexity += weight(class);
+            depth + 1
+        }
+        None => depth,
+    }
+}
+
+fn node_class(json: &JsonValue) -> Option<&str> {
+    let class = json.get("@type")?.as_str()?;
+    (!class.starts_with("xsd:") && !DATA_CLASSES.contains(&class)).then_some(class)
+}
+
+fn weight(class: &str) -> u64 {
+    match class {
+        "PathPlus" | "PathStar" | "PathTimes" => 16,
+        "Path" | "Get" => 8,
+        "ReadDocument" => 4,
+        class if TRIPLE_CLASSES.contains(&class) => 4,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{and, limit, not, or, path, select, triple, var};
+
+    #[test]
+    fn test_counts_nodes_and_triples() {
+        let query = select!(
+            [name],
+            and!(
+                triple!(var!(person), "rdf:type", "@schema:Person"),
+                triple!(var!(person), "name", var!(name)),
+                not!(triple!(var!(person), "deleted", var!(_flag)))
+            )
+        );
+
+        let stats = query.stats();
+        assert_eq!(stats.triples, 3);
+        assert_eq!(stats.nodes["Triple"], 3);
+        assert_eq!(stats.nodes["And"], 1);
+        assert_eq!(stats.nodes["Select"], 1);
+        assert_eq!(stats.nodes["Not"], 1);
+        assert!(!stats.nodes.contains_key("Value"));
+        assert_eq!(stats.node_count(), 6);
+        // Select > And > Not > Triple
+        assert_eq!(stats.depth, 4);
+        assert_eq!(stats.complexity, 3 * 4 + 3);
+    }
+
+    #[test]
+    fn test_repeated_paths_weigh_more() {
+        let single = path!(var!(a), "knows", var!(b));
+        let repeated = path!(var!(a), "knows+", var!(b));
+
+        assert_eq!(single.stats().complexity, 8 + 1);
+        assert_eq!(repeated.stats().nodes["PathPlus"], 1);
+        assert_eq!(repeated.stats().complexity, 8 + 16 + 1);
+    }
+
+    #[test]
+    fn test_depth_grows_with_nesting() {
+        let leaf = || triple!(var!(s), "p", var!(o));
+        let mut query = leaf();
+        for _ in 0..10 {
+            query = or!(query, leaf());
+        }
+
+        let stats = limit!(5, query).stats();
+        assert_eq!(stats.depth, 12);
+        assert_eq!(stats.triples, 11);
+    }
+}
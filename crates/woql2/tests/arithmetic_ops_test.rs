@@ -0,0 +1,71 @@
+//! Tests for operator overloading on arithmetic expressions
+
+use terminusdb_schema::XSDAnySimpleType;
+use terminusdb_woql2::prelude::*;
+
+fn var_expr(name: &str) -> Box<ArithmeticExpression> {
+    Box::new(ArithmeticExpression::Value(ArithmeticValue::Variable(
+        name.to_string(),
+    )))
+}
+
+fn int_expr(value: i64) -> Box<ArithmeticExpression> {
+    Box::new(ArithmeticExpression::Value(ArithmeticValue::Data(
+        XSDAnySimpleType::Integer(value),
+    )))
+}
+
+#[test]
+fn test_operators_build_nested_expressions() {
+    let expr = v!(x) + 3 * v!(y);
+
+    assert_eq!(
+        expr,
+        ArithmeticExpression::Plus(Plus {
+            left: var_expr("x"),
+            right: Box::new(ArithmeticExpression::Times(Times {
+                left: int_expr(3),
+                right: var_expr("y"),
+            })),
+        })
+    );
+}
+
+#[test]
+fn test_operators_map_to_expression_types() {
+    assert!(matches!(v!(a) - 1, ArithmeticExpression::Minus(_)));
+    assert!(matches!(v!(a) * v!(b), ArithmeticExpression::Times(_)));
+    assert!(matches!(v!(a) / 2, ArithmeticExpression::Divide(_)));
+    assert!(matches!(10 / v!(a), ArithmeticExpression::Divide(_)));
+    assert!(matches!(
+        (v!(a) + 1).int_div(2),
+        ArithmeticExpression::Div(_)
+    ));
+    assert!(matches!(v!(a) + 1, ArithmeticExpression::Plus(_)));
+}
+
+#[test]
+fn test_float_literals_stay_floats() {
+    let ArithmeticExpression::Times(Times { left, .. }) = 1.5 * v!(price) else {
+        panic!("Expected Times");
+    };
+    assert_eq!(
+        *left,
+        ArithmeticExpression::Value(ArithmeticValue::Data(XSDAnySimpleType::Float(1.5)))
+    );
+}
+
+#[test]
+fn test_operator_expression_in_eval() {
+    let query = eval!((v!(total) / v!(count)).floor(), v!(avg));
+
+    let Query::Eval(Eval {
+        expression,
+        result_value,
+    }) = query
+    else {
+        panic!("Expected Eval query");
+    };
+    assert!(matches!(expression, ArithmeticExpression::Floor(_)));
+    assert_eq!(result_value, ArithmeticValue::Variable("avg".to_string()));
+}
@@ -0,0 +1,792 @@
+//! terminusdb-woql2-core mirrors this crate's AST by hand, so build the same
+//! queries with both and check they serialize to the same JSON-LD.
+//! [`test_every_query_variant_parity`] builds one query per `Query` variant; a
+//! variant added to the core crate fails to compile here until it is covered.
+
+/// Build `$query` once against each crate's prelude and compare the JSON
+macro_rules! parity_test {
+    ($name:ident, $query:expr) => {
+        #[test]
+        fn $name() {
+            let full = {
+                #[allow(unused_imports)]
+                use terminusdb_schema::{GraphType, XSDAnySimpleType};
+                use terminusdb_woql2::prelude::*;
+                #[allow(unused_imports)]
+                use terminusdb_woql2::value::ListOrVariable;
+                $query
+            };
+            let core = {
+                use terminusdb_woql2_core::prelude::*;
+                $query
+            };
+            assert_eq!(full.to_woql_json(), core.to_json());
+        }
+    };
+}
+
+parity_test!(
+    test_triple_parity,
+    Query::Triple(Triple {
+        subject: NodeValue::Variable("Person".to_string()),
+        predicate: NodeValue::Node("rdf:type".to_string()),
+        object: Value::Node("@schema:Person".to_string()),
+        graph: None,
+    })
+);
+
+parity_test!(
+    test_nested_control_parity,
+    Query::Limit(Limit {
+        limit: 10,
+        query: Box::new(Query::Distinct(Distinct {
+            variables: vec!["Name".to_string()],
+            query: Box::new(Query::Select(Select {
+                variables: vec!["Name".to_string()],
+                query: Box::new(Query::And(And {
+                    and: vec![
+                        Query::Triple(Triple {
+                            subject: NodeValue::Variable("Person".to_string()),
+                            predicate: NodeValue::Node("rdf:type".to_string()),
+                            object: Value::Node("@schema:Person".to_string()),
+                            graph: None,
+                        }),
+                        Query::Or(Or {
+                            or: vec![
+                                Query::Triple(Triple {
+                                    subject: NodeValue::Variable("Person".to_string()),
+                                    predicate: NodeValue::Node("@schema:name".to_string()),
+                                    object: Value::Variable("Name".to_string()),
+                                    graph: None,
+                                }),
+                                Query::WoqlOptional(WoqlOptional {
+                                    query: Box::new(Query::True(True {})),
+                                }),
+                            ],
+                        }),
+                    ],
+                })),
+            })),
+        })),
+    })
+);
+
+parity_test!(
+    test_renamed_fields_parity,
+    Query::If(If {
+        test: Box::new(Query::IsA(IsA {
+            element: NodeValue::Variable("X".to_string()),
+            type_of: NodeValue::Node("@schema:Person".to_string()),
+        })),
+        then_query: Box::new(Query::True(True {})),
+        else_query: Box::new(Query::Not(Not {
+            query: Box::new(Query::True(True {})),
+        })),
+    })
+);
+
+parity_test!(
+    test_path_optionals_parity,
+    Query::Path(Path {
+        subject: Value::Node("X".to_string()),
+        pattern: PathPattern::Star(PathStar {
+            star: Box::new(PathPattern::Predicate(PathPredicate { predicate: None })),
+        }),
+        object: Value::Variable("O".to_string()),
+        path: None,
+    })
+);
+
+parity_test!(
+    test_integer_literal_parity,
+    Query::Greater(Greater {
+        left: DataValue::Variable("Age".to_string()),
+        right: DataValue::Data(XSDAnySimpleType::Integer(18)),
+    })
+);
+
+parity_test!(
+    test_literal_parity,
+    Query::Equals(Equals {
+        left: Value::Data(XSDAnySimpleType::String("Ada".to_string())),
+        right: Value::Data(XSDAnySimpleType::Boolean(true)),
+    })
+);
+
+/// Number of `Query` variants; [`variant_index`] maps each to an index below it.
+const QUERY_VARIANTS: usize = 99;
+
+/// Position of the query's variant. The match has no wildcard arm, so a new
+/// variant must be listed (and built in `every_query!`) before this compiles.
+fn variant_index(query: &terminusdb_woql2_core::prelude::Query) -> usize {
+    use terminusdb_woql2_core::prelude::Query as Q;
+    match query {
+        Q::And(_) => 0,
+        Q::Or(_) => 1,
+        Q::Not(_) => 2,
+        Q::True(_) => 3,
+        Q::Triple(_) => 4,
+        Q::AddTriple(_) => 5,
+        Q::AddedTriple(_) => 6,
+        Q::DeleteTriple(_) => 7,
+        Q::DeletedTriple(_) => 8,
+        Q::Link(_) => 9,
+        Q::Data(_) => 10,
+        Q::AddLink(_) => 11,
+        Q::AddedLink(_) => 12,
+        Q::AddData(_) => 13,
+        Q::AddedData(_) => 14,
+        Q::DeleteLink(_) => 15,
+        Q::DeletedLink(_) => 16,
+        Q::Eval(_) => 17,
+        Q::Path(_) => 18,
+        Q::ReadDocument(_) => 19,
+        Q::InsertDocument(_) => 20,
+        Q::UpdateDocument(_) => 21,
+        Q::DeleteDocument(_) => 22,
+        Q::Equals(_) => 23,
+        Q::Less(_) => 24,
+        Q::Greater(_) => 25,
+        Q::Subsumption(_) => 26,
+        Q::IsA(_) => 27,
+        Q::TypeOf(_) => 28,
+        Q::Typecast(_) => 29,
+        Q::Trim(_) => 30,
+        Q::Lower(_) => 31,
+        Q::Upper(_) => 32,
+        Q::Pad(_) => 33,
+        Q::Split(_) => 34,
+        Q::Join(_) => 35,
+        Q::Concatenate(_) => 36,
+        Q::Substring(_) => 37,
+        Q::Regexp(_) => 38,
+        Q::Like(_) => 39,
+        Q::Member(_) => 40,
+        Q::Sum(_) => 41,
+        Q::Length(_) => 42,
+        Q::Dot(_) => 43,
+        Q::Get(_) => 44,
+        Q::Using(_) => 45,
+        Q::From(_) => 46,
+        Q::Into(_) => 47,
+        Q::Select(_) => 48,
+        Q::Distinct(_) => 49,
+        Q::Pin(_) => 50,
+        Q::If(_) => 51,
+        Q::WoqlOptional(_) => 52,
+        Q::Once(_) => 53,
+        Q::Immediately(_) => 54,
+        Q::OrderBy(_) => 55,
+        Q::GroupBy(_) => 56,
+        Q::Start(_) => 57,
+        Q::Limit(_) => 58,
+        Q::Count(_) => 59,
+        Q::LexicalKey(_) => 60,
+        Q::HashKey(_) => 61,
+        Q::RandomKey(_) => 62,
+        Q::Size(_) => 63,
+        Q::TripleCount(_) => 64,
+        Q::Call(_) => 65,
+        Q::Slice(_) => 66,
+        Q::ListToSet(_) => 67,
+        Q::SetUnion(_) => 68,
+        Q::SetIntersection(_) => 69,
+        Q::SetDifference(_) => 70,
+        Q::SetMember(_) => 71,
+        Q::Gte(_) => 72,
+        Q::Lte(_) => 73,
+        Q::Comment(_) => 74,
+        Q::Collect(_) => 75,
+        Q::Sequence(_) => 76,
+        Q::InRange(_) => 77,
+        Q::RangeMin(_) => 78,
+        Q::RangeMax(_) => 79,
+        Q::TripleSlice(_) => 80,
+        Q::TripleSliceRev(_) => 81,
+        Q::TripleNext(_) => 82,
+        Q::TriplePrevious(_) => 83,
+        Q::Interval(_) => 84,
+        Q::IntervalStartDuration(_) => 85,
+        Q::IntervalDurationEnd(_) => 86,
+        Q::IntervalRelation(_) => 87,
+        Q::IntervalRelationTyped(_) => 88,
+        Q::DateDuration(_) => 89,
+        Q::DayAfter(_) => 90,
+        Q::DayBefore(_) => 91,
+        Q::IsoWeek(_) => 92,
+        Q::Weekday(_) => 93,
+        Q::WeekdaySundayStart(_) => 94,
+        Q::MonthStartDate(_) => 95,
+        Q::MonthStartDates(_) => 96,
+        Q::MonthEndDate(_) => 97,
+        Q::MonthEndDates(_) => 98,
+    }
+}
+
+/// One query per `Query` variant, in [`variant_index`] order, built from
+/// whichever prelude is in scope
+macro_rules! every_query {
+    () => {{
+        let node = |iri: &str| NodeValue::Node(iri.to_string());
+        let nvar = |name: &str| NodeValue::Variable(name.to_string());
+        let var = |name: &str| Value::Variable(name.to_string());
+        let dvar = |name: &str| DataValue::Variable(name.to_string());
+        let text = |s: &str| DataValue::Data(XSDAnySimpleType::String(s.to_string()));
+        let int = |n: i64| DataValue::Data(XSDAnySimpleType::Integer(n));
+        let yes = || Box::new(Query::True(True {}));
+        let triple = || {
+            Query::Triple(Triple {
+                subject: nvar("S"),
+                predicate: node("@schema:name"),
+                object: var("O"),
+                graph: None,
+            })
+        };
+        let operand = |name: &str| {
+            Box::new(ArithmeticExpression::Value(ArithmeticValue::Variable(
+                name.to_string(),
+            )))
+        };
+
+        vec![
+            Query::And(And {
+                and: vec![triple(), Query::True(True {})],
+            }),
+            Query::Or(Or {
+                or: vec![triple(), triple()],
+            }),
+            Query::Not(Not { query: yes() }),
+            Query::True(True {}),
+            triple(),
+            Query::AddTriple(AddTriple {
+                subject: node("Person/ada"),
+                predicate: node("@schema:name"),
+                object: Value::Data(XSDAnySimpleType::String("Ada".to_string())),
+                graph: Some(GraphType::Instance),
+            }),
+            Query::AddedTriple(AddedTriple {
+                subject: nvar("S"),
+                predicate: nvar("P"),
+                object: var("O"),
+                graph: None,
+            }),
+            Query::DeleteTriple(DeleteTriple {
+                subject: nvar("S"),
+                predicate: node("@schema:name"),
+                object: var("O"),
+                graph: None,
+            }),
+            Query::DeletedTriple(DeletedTriple {
+                subject: nvar("S"),
+                predicate: nvar("P"),
+                object: var("O"),
+                graph: Some(GraphType::Schema),
+            }),
+            Query::Link(Link {
+                subject: nvar("S"),
+                predicate: node("@schema:friend"),
+                object: nvar("F"),
+                graph: GraphType::Instance,
+            }),
+            Query::Data(Data {
+                subject: nvar("S"),
+                predicate: node("@schema:age"),
+                object: int(36),
+                graph: GraphType::Instance,
+            }),
+            Query::AddLink(AddLink {
+                subject: node("Person/ada"),
+                predicate: node("@schema:friend"),
+                object: node("Person/grace"),
+                graph: GraphType::Instance,
+            }),
+            Query::AddedLink(AddedLink {
+                subject: nvar("S"),
+                predicate: nvar("P"),
+                object: nvar("O"),
+                graph: GraphType::Instance,
+            }),
+            Query::AddData(AddData {
+                subject: node("Person/ada"),
+                predicate: node("@schema:active"),
+                object: DataValue::Data(XSDAnySimpleType::Boolean(true)),
+                graph: GraphType::Instance,
+            }),
+            Query::AddedData(AddedData {
+                subject: nvar("S"),
+                predicate: nvar("P"),
+                object: dvar("O"),
+                graph: GraphType::Instance,
+            }),
+            Query::DeleteLink(DeleteLink {
+                subject: node("Person/ada"),
+                predicate: node("@schema:friend"),
+                object: nvar("F"),
+                graph: GraphType::Instance,
+            }),
+            Query::DeletedLink(DeletedLink {
+                subject: nvar("S"),
+                predicate: nvar("P"),
+                object: nvar("O"),
+                graph: GraphType::Schema,
+            }),
+            Query::Eval(Eval {
+                expression: ArithmeticExpression::Floor(Floor {
+                    argument: Box::new(ArithmeticExpression::Divide(Divide {
+                        left: Box::new(ArithmeticExpression::Plus(Plus {
+                            left: operand("A"),
+                            right: Box::new(ArithmeticExpression::Value(ArithmeticValue::Data(
+                                XSDAnySimpleType::Float(1.5),
+                            ))),
+                        })),
+                        right: Box::new(ArithmeticExpression::Times(Times {
+                            left: Box::new(ArithmeticExpression::Minus(Minus {
+                                left: operand("B"),
+                                right: operand("C"),
+                            })),
+                            right: Box::new(ArithmeticExpression::Exp(Exp {
+                                left: operand("D"),
+                                right: Box::new(ArithmeticExpression::Div(Div {
+                                    left: operand("E"),
+                                    right: operand("F"),
+                                })),
+                            })),
+                        })),
+                    })),
+                }),
+                result_value: ArithmeticValue::Variable("Result".to_string()),
+            }),
+            Query::Path(Path {
+                subject: var("X"),
+                pattern: PathPattern::Sequence(PathSequence {
+                    sequence: vec![
+                        PathPattern::Or(PathOr {
+                            or: vec![
+                                PathPattern::Predicate(PathPredicate {
+                                    predicate: Some("knows".to_string()),
+                                }),
+                                PathPattern::InversePredicate(InversePathPredicate {
+                                    predicate: Some("parent".to_string()),
+                                }),
+                            ],
+                        }),
+                        PathPattern::Plus(PathPlus {
+                            plus: Box::new(PathPattern::Predicate(PathPredicate {
+                                predicate: Some("child".to_string()),
+                            })),
+                        }),
+                        PathPattern::Times(PathTimes {
+                            times: Box::new(PathPattern::Star(PathStar {
+                                star: Box::new(PathPattern::Predicate(PathPredicate {
+                                    predicate: None,
+                                })),
+                            })),
+                            from: 1,
+                            to: 3,
+                        }),
+                    ],
+                }),
+                object: var("Y"),
+                path: Some(var("Edges")),
+            }),
+            Query::ReadDocument(ReadDocument {
+                identifier: nvar("Id"),
+                document: var("Doc"),
+            }),
+            Query::InsertDocument(InsertDocument {
+                document: var("Doc"),
+                identifier: Some(nvar("Id")),
+            }),
+            Query::UpdateDocument(UpdateDocument {
+                document: var("Doc"),
+                identifier: None,
+            }),
+            Query::DeleteDocument(DeleteDocument {
+                identifier: node("Person/ada"),
+            }),
+            Query::Equals(Equals {
+                left: var("X"),
+                right: Value::Node("Person/ada".to_string()),
+            }),
+            Query::Less(Less {
+                left: dvar("Age"),
+                right: int(65),
+            }),
+            Query::Greater(Greater {
+                left: dvar("Age"),
+                right: int(18),
+            }),
+            Query::Subsumption(Subsumption {
+                child: nvar("Child"),
+                parent: node("@schema:Person"),
+            }),
+            Query::IsA(IsA {
+                element: nvar("X"),
+                type_of: node("@schema:Person"),
+            }),
+            Query::TypeOf(TypeOf {
+                value: var("X"),
+                type_uri: nvar("T"),
+            }),
+            Query::Typecast(Typecast {
+                value: var("X"),
+                type_uri: node("xsd:integer"),
+                result_value: var("N"),
+            }),
+            Query::Trim(Trim {
+                untrimmed: text("  Ada "),
+                trimmed: dvar("Trimmed"),
+            }),
+            Query::Lower(Lower {
+                mixed: dvar("Name"),
+                lower: dvar("Lower"),
+            }),
+            Query::Upper(Upper {
+                mixed: dvar("Name"),
+                upper: dvar("Upper"),
+            }),
+            Query::Pad(Pad {
+                string: dvar("N"),
+                char: text("0"),
+                times: int(4),
+                result_string: dvar("Padded"),
+            }),
+            Query::Split(Split {
+                string: text("a,b"),
+                pattern: text(","),
+                list: dvar("Parts"),
+            }),
+            Query::Join(Join {
+                list: ListOrVariable::Variable(dvar("Parts")),
+                separator: text(", "),
+                result_string: dvar("Joined"),
+            }),
+            Query::Concatenate(Concatenate {
+                list: ListOrVariable::List(vec![text("Person/"), dvar("Id")]),
+                result_string: dvar("Iri"),
+            }),
+            Query::Substring(Substring {
+                string: dvar("Name"),
+                before: int(0),
+                length: int(3),
+                after: dvar("After"),
+                substring: dvar("Prefix"),
+            }),
+            Query::Regexp(Regexp {
+                pattern: text("^A"),
+                string: dvar("Name"),
+                result: None,
+            }),
+            Query::Like(Like {
+                left: dvar("Name"),
+                right: text("Ada"),
+                similarity: dvar("Similarity"),
+            }),
+            Query::Member(Member {
+                member: dvar("X"),
+                list: dvar("Xs"),
+            }),
+            Query::Sum(Sum {
+                list: dvar("Xs"),
+                result: dvar("Total"),
+            }),
+            Query::Length(Length {
+                list: dvar("Xs"),
+                length: dvar("N"),
+            }),
+            Query::Dot(Dot {
+                document: dvar("Doc"),
+                field: text("name"),
+                value: dvar("Name"),
+            }),
+            Query::Get(Get {
+                columns: vec![Column {
+                    indicator: Indicator::Name("name".to_string()),
+                    variable: "Name".to_string(),
+                    type_of: None,
+                }],
+                resource: QueryResource {
+                    source: Source::Url("https://example.com/people.csv".to_string()),
+                    format: FormatType::Csv,
+                    options: None,
+                },
+                has_header: Some(true),
+            }),
+            Query::Using(Using {
+                collection: "admin/people".to_string(),
+                query: yes(),
+            }),
+            Query::From(From {
+                graph: "schema".to_string(),
+                query: yes(),
+            }),
+            Query::Into(Into {
+                graph: "instance".to_string(),
+                query: yes(),
+            }),
+            Query::Select(Select {
+                variables: vec!["S".to_string()],
+                query: Box::new(triple()),
+            }),
+            Query::Distinct(Distinct {
+                variables: vec!["O".to_string()],
+                query: Box::new(triple()),
+            }),
+            Query::Pin(Pin { query: yes() }),
+            Query::If(If {
+                test: Box::new(triple()),
+                then_query: yes(),
+                else_query: Box::new(Query::Not(Not { query: yes() })),
+            }),
+            Query::WoqlOptional(WoqlOptional {
+                query: Box::new(triple()),
+            }),
+            Query::Once(Once { query: yes() }),
+            Query::Immediately(Immediately { query: yes() }),
+            Query::OrderBy(OrderBy {
+                ordering: vec![
+                    OrderTemplate {
+                        variable: "O".to_string(),
+                        order: Order::Desc,
+                    },
+                    OrderTemplate {
+                        variable: "S".to_string(),
+                        order: Order::Asc,
+                    },
+                ],
+                query: Box::new(triple()),
+            }),
+            Query::GroupBy(GroupBy {
+                template: var("S"),
+                group_by: vec!["O".to_string()],
+                grouped_value: var("Grouped"),
+                query: Box::new(triple()),
+            }),
+            Query::Start(Start {
+                start: 20,
+                query: Box::new(triple()),
+            }),
+            Query::Limit(Limit {
+                limit: 10,
+                query: Box::new(triple()),
+            }),
+            Query::Count(Count {
+                query: Box::new(triple()),
+                count: dvar("N"),
+            }),
+            Query::LexicalKey(LexicalKey {
+                base: text("Person/"),
+                key_list: vec![dvar("Name"), int(1)],
+                uri: nvar("Id"),
+            }),
+            Query::HashKey(HashKey {
+                base: text("Person/"),
+                key_list: vec![dvar("Name")],
+                uri: nvar("Id"),
+            }),
+            Query::RandomKey(RandomKey {
+                base: text("Person/"),
+                uri: nvar("Id"),
+            }),
+            Query::Size(Size {
+                resource: "admin/people".to_string(),
+                size: dvar("Size"),
+            }),
+            Query::TripleCount(TripleCount {
+                resource: "admin/people".to_string(),
+                count: dvar("Count"),
+            }),
+            Query::Call(Call {
+                name: "friends_of".to_string(),
+                arguments: vec![var("Person"), Value::Node("Person/ada".to_string())],
+            }),
+            Query::Slice(Slice {
+                list: dvar("Xs"),
+                start: int(1),
+                end: Some(int(3)),
+                result: dvar("Slice"),
+            }),
+            Query::ListToSet(ListToSet {
+                list: dvar("Xs"),
+                set: dvar("Set"),
+            }),
+            Query::SetUnion(SetUnion {
+                list_a: dvar("A"),
+                list_b: dvar("B"),
+                result: dvar("Union"),
+            }),
+            Query::SetIntersection(SetIntersection {
+                list_a: dvar("A"),
+                list_b: dvar("B"),
+                result: dvar("Both"),
+            }),
+            Query::SetDifference(SetDifference {
+                list_a: dvar("A"),
+                list_b: dvar("B"),
+                result: dvar("Only"),
+            }),
+            Query::SetMember(SetMember {
+                element: dvar("X"),
+                set: dvar("Set"),
+            }),
+            Query::Gte(Gte {
+                left: dvar("Age"),
+                right: int(18),
+            }),
+            Query::Lte(Lte {
+                left: dvar("Age"),
+                right: DataValue::Data(XSDAnySimpleType::UnsignedInt(65)),
+            }),
+            Query::Comment(Comment {
+                comment: text("every person"),
+                query: Some(Box::new(triple())),
+            }),
+            Query::Collect(Collect {
+                template: var("O"),
+                into: var("Os"),
+                query: Box::new(triple()),
+            }),
+            Query::Sequence(Sequence {
+                value: dvar("I"),
+                start: int(0),
+                end: int(10),
+                step: Some(int(2)),
+                count: None,
+            }),
+            Query::InRange(InRange {
+                value: dvar("I"),
+                start: int(0),
+                end: int(10),
+            }),
+            Query::RangeMin(RangeMin {
+                list: dvar("Xs"),
+                result: dvar("Min"),
+            }),
+            Query::RangeMax(RangeMax {
+                list: dvar("Xs"),
+                result: dvar("Max"),
+            }),
+            Query::TripleSlice(TripleSlice {
+                subject: nvar("S"),
+                predicate: node("@schema:at"),
+                object: var("T"),
+                low: var("Low"),
+                high: var("High"),
+                graph: None,
+            }),
+            Query::TripleSliceRev(TripleSliceRev {
+                subject: nvar("S"),
+                predicate: node("@schema:at"),
+                object: var("T"),
+                low: var("Low"),
+                high: var("High"),
+                graph: Some(GraphType::Instance),
+            }),
+            Query::TripleNext(TripleNext {
+                subject: nvar("S"),
+                predicate: node("@schema:at"),
+                object: var("T"),
+                next: var("Next"),
+                graph: None,
+            }),
+            Query::TriplePrevious(TriplePrevious {
+                subject: nvar("S"),
+                predicate: node("@schema:at"),
+                object: var("T"),
+                previous: var("Previous"),
+                graph: None,
+            }),
+            Query::Interval(Interval {
+                start: dvar("Start"),
+                end: dvar("End"),
+                interval: dvar("Interval"),
+            }),
+            Query::IntervalStartDuration(IntervalStartDuration {
+                start: dvar("Start"),
+                duration: dvar("Duration"),
+                interval: dvar("Interval"),
+            }),
+            Query::IntervalDurationEnd(IntervalDurationEnd {
+                duration: dvar("Duration"),
+                end: dvar("End"),
+                interval: dvar("Interval"),
+            }),
+            Query::IntervalRelation(IntervalRelation {
+                relation: text("before"),
+                x_start: dvar("XStart"),
+                x_end: dvar("XEnd"),
+                y_start: dvar("YStart"),
+                y_end: dvar("YEnd"),
+            }),
+            Query::IntervalRelationTyped(IntervalRelationTyped {
+                relation: dvar("Relation"),
+                x: dvar("X"),
+                y: dvar("Y"),
+            }),
+            Query::DateDuration(DateDuration {
+                start: dvar("Start"),
+                duration: dvar("Duration"),
+                end: dvar("End"),
+            }),
+            Query::DayAfter(DayAfter {
+                date: dvar("Date"),
+                next: dvar("Next"),
+            }),
+            Query::DayBefore(DayBefore {
+                date: dvar("Date"),
+                previous: dvar("Previous"),
+            }),
+            Query::IsoWeek(IsoWeek {
+                date: dvar("Date"),
+                week: dvar("Week"),
+                year: dvar("Year"),
+            }),
+            Query::Weekday(Weekday {
+                date: dvar("Date"),
+                weekday: dvar("Weekday"),
+            }),
+            Query::WeekdaySundayStart(WeekdaySundayStart {
+                date: dvar("Date"),
+                weekday: dvar("Weekday"),
+            }),
+            Query::MonthStartDate(MonthStartDate {
+                year_month: dvar("YearMonth"),
+                date: dvar("Date"),
+            }),
+            Query::MonthStartDates(MonthStartDates {
+                date: dvar("Date"),
+                start: dvar("Start"),
+                end: dvar("End"),
+            }),
+            Query::MonthEndDate(MonthEndDate {
+                year_month: dvar("YearMonth"),
+                date: dvar("Date"),
+            }),
+            Query::MonthEndDates(MonthEndDates {
+                date: dvar("Date"),
+                start: dvar("Start"),
+                end: dvar("End"),
+            }),
+        ]
+    }};
+}
+
+#[test]
+fn test_every_query_variant_parity() {
+    let full = {
+        use terminusdb_schema::{GraphType, XSDAnySimpleType};
+        use terminusdb_woql2::prelude::*;
+        use terminusdb_woql2::value::ListOrVariable;
+        every_query!()
+    };
+    let core = {
+        use terminusdb_woql2_core::prelude::*;
+        every_query!()
+    };
+
+    assert_eq!(core.len(), QUERY_VARIANTS);
+    for (i, (full, core)) in full.iter().zip(&core).enumerate() {
+        assert_eq!(variant_index(core), i, "queries are in variant order");
+        assert_eq!(full.to_woql_json(), core.to_json(), "variant {}", i);
+    }
+}
@@ -71,6 +71,58 @@ fn test_query_macros() {
     assert!(matches!(select_query, Query::Select(ref s) if s.variables.len() == 2));
 }
 
+#[test]
+fn test_spread_in_and_or() {
+    let filters = vec![
+        triple!(var!(x), "status", data!("active")),
+        triple!(var!(x), "verified", data!(true)),
+    ];
+
+    let and_query = and!(
+        triple!(var!(x), "rdf:type", "Person"),
+        ..filters.clone(),
+        triple!(var!(x), "name", var!(name)),
+    );
+    match &and_query {
+        Query::And(a) => {
+            assert_eq!(a.and.len(), 4);
+            assert_eq!(a.and[1], filters[0]);
+            assert_eq!(a.and[2], filters[1]);
+            assert!(matches!(&a.and[3], Query::Triple(_)));
+        }
+        _ => panic!("Expected And query"),
+    }
+
+    // Any iterator can be spread, and spreads can be the only arguments
+    let types = ["Person", "Organization"];
+    let or_query = or!(..types.iter().map(|t| triple!(var!(x), "rdf:type", *t)));
+    assert_eq!(
+        or_query,
+        or!(
+            triple!(var!(x), "rdf:type", "Person"),
+            triple!(var!(x), "rdf:type", "Organization")
+        )
+    );
+
+    let empty: Vec<Query> = Vec::new();
+    assert!(matches!(and!(..empty), Query::And(ref a) if a.and.is_empty()));
+}
+
+#[test]
+fn test_and_all_or_all() {
+    let filters = [("status", "active"), ("role", "admin")];
+    let clauses: Vec<Query> = filters
+        .iter()
+        .map(|(field, value)| triple!(var!(x), *field, data!(*value)))
+        .collect();
+
+    assert_eq!(and_all(clauses.clone()), and!(..clauses.clone()));
+    assert_eq!(or_all(clauses.clone()), or!(..clauses));
+
+    assert_eq!(and_all(Vec::new()), Query::And(And { and: vec![] }));
+    assert_eq!(or_all(std::iter::empty()), Query::Or(Or { or: vec![] }));
+}
+
 #[test]
 fn test_comparison_macros() {
     // Test eq! macro
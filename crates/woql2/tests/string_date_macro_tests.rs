@@ -1,6 +1,7 @@
 //! Tests for string operation and date/time macros
 
-use chrono::Datelike;
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc};
+use terminusdb_schema::XSDAnySimpleType;
 use terminusdb_woql2::prelude::*;
 
 #[test]
@@ -49,20 +50,71 @@ fn test_string_operation_macros() {
 
 #[test]
 fn test_today_macro() {
-    // Test today! macro
-    let today_value = today!();
-    match today_value {
-        Value::Data(terminusdb_schema::XSDAnySimpleType::String(date_str)) => {
-            // Check that it's a valid ISO 8601 date
-            assert!(date_str.len() > 20); // Should be like "2024-01-15T12:34:56.789Z"
-            assert!(date_str.contains("T"));
-            assert!(date_str.ends_with("Z"));
-
-            // Verify it can be parsed as a date
-            let parsed = chrono::DateTime::parse_from_rfc3339(&date_str);
-            assert!(parsed.is_ok(), "today!() should produce valid RFC3339 date");
+    let before = Utc::now();
+    match today!() {
+        Value::Data(XSDAnySimpleType::DateTime(now)) => {
+            assert!(now >= before);
+            assert!(now <= Utc::now());
+        }
+        _ => panic!("Expected Data dateTime value from today!()"),
+    }
+}
+
+#[test]
+fn test_datetime_from_chrono() {
+    let utc = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let offset = chrono::DateTime::parse_from_rfc3339("2024-01-01T02:00:00+02:00").unwrap();
+
+    let expected = Value::Data(XSDAnySimpleType::DateTime(utc));
+    assert_eq!(datetime!("2024-01-01T00:00:00Z"), expected);
+    assert_eq!(datetime!(utc), expected);
+    assert_eq!(datetime!(offset), expected);
+    assert_eq!(datetime!(utc.naive_utc()), expected);
+    assert_eq!(data!(offset), expected);
+
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    assert_eq!(data!(date), Value::Data(XSDAnySimpleType::Date(date)));
+
+    // chrono values can be compared against directly
+    assert!(matches!(after!(var!(created), utc), Query::Greater(_)));
+}
+
+#[test]
+fn test_duration_macros() {
+    let now = Utc::now();
+    let week_ago = match ago!(7) {
+        Value::Data(XSDAnySimpleType::DateTime(dt)) => dt,
+        _ => panic!("Expected Data dateTime value from ago!()"),
+    };
+    assert!(week_ago <= now - Duration::days(7));
+    assert!(week_ago > now - Duration::days(7) - Duration::minutes(1));
+
+    match from_now!(Duration::hours(2)) {
+        Value::Data(XSDAnySimpleType::DateTime(dt)) => assert!(dt >= now + Duration::hours(2)),
+        _ => panic!("Expected Data dateTime value from from_now!()"),
+    }
+
+    match ago!(std::time::Duration::from_secs(90)) {
+        Value::Data(XSDAnySimpleType::DateTime(dt)) => assert!(dt < Utc::now()),
+        _ => panic!("Expected Data dateTime value from ago!()"),
+    }
+}
+
+#[test]
+fn test_within_last_and_next() {
+    for query in [
+        within_last!(var!(created), 30),
+        within_last!(var!(seen), Duration::minutes(5)),
+        within_next!(var!(due), 14),
+    ] {
+        match query {
+            Query::And(and) => {
+                assert_eq!(and.and.len(), 2);
+                assert!(matches!(&and.and[0], Query::Or(_)));
+                assert!(matches!(&and.and[1], Query::Or(_)));
+            }
+            _ => panic!("Expected And query"),
         }
-        _ => panic!("Expected Data string value from today!()"),
     }
 }
 
@@ -192,9 +244,11 @@ fn test_string_patterns_with_special_chars() {
 #[test]
 fn test_practical_filter_example() {
     // Example: Find all documents created this year with specific naming pattern
-    let current_year = chrono::Utc::now().year();
-    let year_start = format!("{}-01-01T00:00:00Z", current_year);
-    let year_end = format!("{}-12-31T23:59:59Z", current_year);
+    let current_year = Utc::now().year();
+    let year_start = Utc.with_ymd_and_hms(current_year, 1, 1, 0, 0, 0).unwrap();
+    let year_end = Utc
+        .with_ymd_and_hms(current_year, 12, 31, 23, 59, 59)
+        .unwrap();
 
     let query = select!(
         [doc, title, created],
@@ -204,7 +258,7 @@ fn test_practical_filter_example() {
             triple!(var!(doc), "created_date", var!(created)),
             starts_with!(var!(title), "DOC-"),
             contains!(var!(title), current_year.to_string().as_str()),
-            in_between!(var!(created), data!(year_start), data!(year_end))
+            in_between!(var!(created), year_start, year_end)
         )
     );
 
@@ -188,3 +188,41 @@ fn test_document_operations_rendering() {
     let dsl = read.to_dsl();
     assert_eq!(dsl, r#"read_document("Person/john-doe", $PersonData)"#);
 }
+
+#[test]
+fn test_pretty_rendering() {
+    let triple = |predicate: &str, object: Value| {
+        Query::Triple(Triple {
+            subject: NodeValue::Variable("Person".to_string()),
+            predicate: NodeValue::Node(predicate.to_string()),
+            object,
+            graph: Some(GraphType::Instance),
+        })
+    };
+    let query = Query::Select(Select {
+        variables: vec!["Name".to_string()],
+        query: Box::new(Query::And(And {
+            and: vec![
+                triple("rdf:type", Value::Node("@schema:Person".to_string())),
+                triple("@schema:name", Value::Variable("Name".to_string())),
+            ],
+        })),
+    });
+
+    assert_eq!(
+        query.to_dsl_pretty(),
+        r#"select(
+    [$Name],
+    and(
+        triple($Person, "rdf:type", "@schema:Person"),
+        triple($Person, "@schema:name", $Name)
+    )
+)"#
+    );
+
+    // Wide enough and deep enough to stay on one line
+    let format = DslFormat::new()
+        .with_max_width(200)
+        .with_max_inline_depth(3);
+    assert_eq!(query.to_dsl_with(&format), query.to_dsl());
+}
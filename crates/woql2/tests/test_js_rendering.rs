@@ -0,0 +1,182 @@
+use terminusdb_schema::{GraphType, XSDAnySimpleType};
+use terminusdb_woql2::prelude::*;
+use terminusdb_woql2::query::{And, Query};
+use terminusdb_woql2::triple::Triple;
+use terminusdb_woql2::value::{NodeValue, Value};
+
+fn triple(subject: &str, predicate: &str, object: Value) -> Query {
+    Query::Triple(Triple {
+        subject: NodeValue::Variable(subject.to_string()),
+        predicate: NodeValue::Node(predicate.to_string()),
+        object,
+        graph: None,
+    })
+}
+
+#[test]
+fn test_select_and_triples() {
+    let query = Query::Select(Select {
+        variables: vec!["Name".to_string()],
+        query: Box::new(Query::And(And {
+            and: vec![
+                triple(
+                    "Person",
+                    "rdf:type",
+                    Value::Node("@schema:Person".to_string()),
+                ),
+                triple(
+                    "Person",
+                    "@schema:name",
+                    Value::Variable("Name".to_string()),
+                ),
+            ],
+        })),
+    });
+
+    assert_eq!(
+        query.to_js(),
+        r#"select("v:Name", and(triple("v:Person", "rdf:type", "@schema:Person"), triple("v:Person", "@schema:name", "v:Name")))"#
+    );
+}
+
+#[test]
+fn test_data_versus_nodes() {
+    // A plain string in a triple object is a node, so string data is explicit
+    let name = triple(
+        "Person",
+        "@schema:name",
+        Value::Data(XSDAnySimpleType::String("Alice".to_string())),
+    );
+    assert_eq!(
+        name.to_js(),
+        r#"triple("v:Person", "@schema:name", string("Alice"))"#
+    );
+
+    let age = triple(
+        "Person",
+        "@schema:age",
+        Value::Data(XSDAnySimpleType::Integer(42)),
+    );
+    assert_eq!(
+        age.to_js(),
+        r#"triple("v:Person", "@schema:age", literal(42, "xsd:integer"))"#
+    );
+
+    // Data positions read plain strings as data
+    let greater = Query::Greater(Greater {
+        left: DataValue::Variable("Name".to_string()),
+        right: DataValue::Data(XSDAnySimpleType::String("M".to_string())),
+    });
+    assert_eq!(greater.to_js(), r#"greater("v:Name", "M")"#);
+}
+
+#[test]
+fn test_falsy_literals_are_embedded() {
+    // The client drops a falsy literal in a data position, so it is spelled out
+    let less = Query::Less(Less {
+        left: DataValue::Variable("Count".to_string()),
+        right: DataValue::Data(XSDAnySimpleType::Integer(0)),
+    });
+    let js = less.to_js();
+    assert!(js.starts_with(r#"less("v:Count", {"#), "{js}");
+    assert!(js.contains(r#""@type":"DataValue""#), "{js}");
+}
+
+#[test]
+fn test_schema_graph_uses_quad() {
+    let query = Query::AddTriple(AddTriple {
+        subject: NodeValue::Node("@schema:Person".to_string()),
+        predicate: NodeValue::Node("rdf:type".to_string()),
+        object: Value::Node("sys:Class".to_string()),
+        graph: Some(GraphType::Schema),
+    });
+    assert_eq!(
+        query.to_js(),
+        r#"add_quad("@schema:Person", "rdf:type", "sys:Class", "schema")"#
+    );
+}
+
+#[test]
+fn test_order_by_and_reserved_names() {
+    let query = Query::OrderBy(OrderBy {
+        ordering: vec![
+            OrderTemplate {
+                variable: "Age".to_string(),
+                order: Order::Desc,
+            },
+            OrderTemplate {
+                variable: "Name".to_string(),
+                order: Order::Asc,
+            },
+        ],
+        query: Box::new(Query::True(True {})),
+    });
+    assert_eq!(
+        query.to_js(),
+        r#"order_by(["v:Age", "desc"], "v:Name", WOQL.true())"#
+    );
+
+    let eval = Query::Eval(Eval {
+        expression: ArithmeticExpression::Plus(Plus {
+            left: Box::new(ArithmeticExpression::Value(ArithmeticValue::Variable(
+                "X".to_string(),
+            ))),
+            right: Box::new(ArithmeticExpression::Value(ArithmeticValue::Data(
+                XSDAnySimpleType::Integer(1),
+            ))),
+        }),
+        result_value: ArithmeticValue::Variable("Y".to_string()),
+    });
+    assert_eq!(
+        eval.to_js(),
+        r#"evaluate(plus("v:X", literal(1, "xsd:integer")), "v:Y")"#
+    );
+}
+
+#[test]
+fn test_path_string() {
+    let pattern = PathPattern::Sequence(PathSequence {
+        sequence: vec![
+            PathPattern::InversePredicate(InversePathPredicate {
+                predicate: Some("@schema:parent".to_string()),
+            }),
+            PathPattern::Plus(PathPlus {
+                plus: Box::new(PathPattern::Or(PathOr {
+                    or: vec![
+                        PathPattern::Predicate(PathPredicate {
+                            predicate: Some("@schema:friend".to_string()),
+                        }),
+                        PathPattern::Predicate(PathPredicate { predicate: None }),
+                    ],
+                })),
+            }),
+        ],
+    });
+    let query = Query::Path(Path {
+        subject: Value::Variable("A".to_string()),
+        pattern,
+        object: Value::Variable("B".to_string()),
+        path: Some(Value::Variable("Edges".to_string())),
+    });
+    assert_eq!(
+        query.to_js(),
+        r#"path("v:A", "<@schema:parent,(@schema:friend|.)+", "v:B", "v:Edges")"#
+    );
+}
+
+#[test]
+fn test_unsupported_queries_fall_back_to_json() {
+    let gte = Query::Gte(Gte {
+        left: DataValue::Variable("Age".to_string()),
+        right: DataValue::Variable("Min".to_string()),
+    });
+    let js = gte.to_js();
+    assert!(js.starts_with("json({"), "{js}");
+    assert!(js.contains(r#""@type":"Gte""#), "{js}");
+
+    // Nested inside a supported query, only the unsupported part is JSON
+    let not = Query::Not(Not {
+        query: Box::new(gte),
+    });
+    assert!(not.to_js().starts_with("not(json({"));
+}
@@ -0,0 +1,98 @@
+use terminusdb_woql2::prelude::*;
+use terminusdb_woql2::query::Query;
+
+fn pred(name: &str) -> PathPattern {
+    PathPattern::Predicate(PathPredicate {
+        predicate: Some(name.to_string()),
+    })
+}
+
+#[test]
+fn test_parse_request_example() {
+    let expected = PathPattern::Sequence(PathSequence {
+        sequence: vec![
+            PathPattern::Plus(PathPlus {
+                plus: Box::new(pred("friend")),
+            }),
+            PathPattern::Times(PathTimes {
+                times: Box::new(PathPattern::Or(PathOr {
+                    or: vec![pred("knows"), pred("manages")],
+                })),
+                from: 1,
+                to: 3,
+            }),
+        ],
+    });
+
+    assert_eq!(
+        parse_path_pattern("friend+>(knows|manages){1,3}").unwrap(),
+        expected
+    );
+    assert_eq!(path!("friend+ , (knows | manages){1,3}"), expected);
+    assert_eq!(
+        "friend+,(knows|manages){1,3}"
+            .parse::<PathPattern>()
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_inverse_any_and_exact_repetition() {
+    assert_eq!(
+        path!("<@schema:parent,.*"),
+        PathPattern::Sequence(PathSequence {
+            sequence: vec![
+                PathPattern::InversePredicate(InversePathPredicate {
+                    predicate: Some("@schema:parent".to_string()),
+                }),
+                PathPattern::Star(PathStar {
+                    star: Box::new(PathPattern::Predicate(PathPredicate { predicate: None })),
+                }),
+            ],
+        })
+    );
+    assert_eq!(
+        path!("knows{2}"),
+        PathPattern::Times(PathTimes {
+            times: Box::new(pred("knows")),
+            from: 2,
+            to: 2,
+        })
+    );
+}
+
+#[test]
+fn test_path_query_accepts_string_pattern() {
+    let query = path!(var!(a), "friend+", var!(b), var!(edges));
+    let Query::Path(path) = query else {
+        panic!("expected a path query");
+    };
+    assert_eq!(
+        path.pattern,
+        PathPattern::Plus(PathPlus {
+            plus: Box::new(pred("friend")),
+        })
+    );
+    assert_eq!(path.path, Some(Value::Variable("edges".to_string())));
+}
+
+#[test]
+fn test_round_trips_through_client_path_string() {
+    for expression in [
+        "friend",
+        "<parent,name",
+        "(friend|foe)+,name",
+        "a,(b|c){1,3},<d*",
+        "a|b,c|.",
+    ] {
+        let pattern = parse_path_pattern(expression).unwrap();
+        assert_eq!(pattern.to_js(), format!("\"{}\"", expression));
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid path expression")]
+fn test_malformed_literal_panics() {
+    let _ = path!("friend{1,");
+}
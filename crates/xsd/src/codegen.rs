@@ -0,0 +1,733 @@
+//! Rust code generation from XSD
+//!
+//! Turns the TerminusDB schemas generated from an XSD into Rust source with
+//! `#[derive(TerminusDBModel)]` types, so schemas that are known at build time
+//! get typed models instead of only runtime [`Schema`] values:
+//!
+//! - Classes become structs, with one field per property
+//! - TaggedUnions (`{Type}Child`, choices, substitution groups) become enums
+//!   with one newtype variant per union variant
+//! - Enums become simple enums
+//!
+//! The generated code uses absolute paths, so the crate including it needs the
+//! same dependencies as hand-written models (`terminusdb-schema`,
+//! `terminusdb-schema-derive`, `serde`, `serde_json`, `anyhow`), plus `chrono`
+//! when the XSD has date or time types.
+//!
+//! # Usage in build.rs
+//!
+//! ```ignore
+//! use terminusdb_xsd::codegen::generate_rust_models_from_xsd;
+//!
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=schemas/catalog.xsd");
+//!
+//!     let models = generate_rust_models_from_xsd("schemas/catalog.xsd", None::<&str>).unwrap();
+//!     for warning in &models.warnings {
+//!         println!("cargo:warning={}", warning);
+//!     }
+//!
+//!     let out_dir = std::env::var("OUT_DIR").unwrap();
+//!     models.write_to(format!("{}/catalog.rs", out_dir)).unwrap();
+//! }
+//! ```
+//!
+//! and in the crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/catalog.rs"));
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use heck::{ToPascalCase, ToSnakeCase};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use terminusdb_schema::{ClassDocumentation, Key, Property, Schema, TypeFamily};
+
+use crate::xsd_model::XsdModel;
+use crate::Result;
+
+/// Rust source generated from TerminusDB schemas.
+#[derive(Debug, Clone, Default)]
+pub struct RustModels {
+    /// The generated items, ready to be written to a file and `include!`d
+    pub code: String,
+    /// Schema features that the generated models can't represent exactly,
+    /// such as variant names the derive macro can't reproduce
+    pub warnings: Vec<String>,
+}
+
+impl RustModels {
+    /// Write the generated code to a file.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, &self.code)?;
+        Ok(())
+    }
+}
+
+/// Generate Rust models from TerminusDB schemas.
+///
+/// Schemas that are neither a Class, a TaggedUnion nor an Enum are skipped
+/// with a warning.
+pub fn generate_rust_models(schemas: &[Schema]) -> RustModels {
+    let mut codegen = Codegen::new(schemas);
+    let items: Vec<TokenStream> = schemas.iter().filter_map(|s| codegen.item(s)).collect();
+
+    RustModels {
+        code: quote! { #(#items)* }.to_string(),
+        warnings: codegen.warnings,
+    }
+}
+
+/// Generate Rust models from an XSD file, for use in build scripts.
+pub fn generate_rust_models_from_xsd(
+    xsd_path: impl AsRef<Path>,
+    catalog_path: Option<impl AsRef<Path>>,
+) -> Result<RustModels> {
+    let model = XsdModel::from_file(xsd_path, catalog_path)?;
+    Ok(model.generate_rust_models())
+}
+
+/// Conversion from a Rust variant name to the name stored in the schema
+type Rename = fn(&str) -> String;
+
+/// Rename strategies of the derive macro that can reproduce names which are
+/// not simply lowercase, with the same conversion the macro applies to a
+/// PascalCase variant name.
+const RENAME_STRATEGIES: &[(&str, Rename)] = &[
+    ("snake_case", derive_snake_case),
+    ("kebab-case", |s| derive_snake_case(s).replace('_', "-")),
+    ("UPPERCASE", |s| s.to_uppercase()),
+    ("SCREAMING_SNAKE_CASE", |s| {
+        derive_snake_case(s).to_uppercase()
+    }),
+];
+
+/// Rust keywords, which can't be used as plain identifiers
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+struct Codegen<'a> {
+    /// Rust type name of each generated class
+    type_names: HashMap<&'a str, String>,
+    /// Index of each class's strongly connected component in the graph of
+    /// singular (non-collection) properties. A property pointing into its
+    /// own component would make the type infinitely sized, so it is boxed.
+    components: HashMap<&'a str, usize>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Codegen<'a> {
+    fn new(schemas: &'a [Schema]) -> Self {
+        let mut used = HashSet::new();
+        let type_names = schemas
+            .iter()
+            .filter(|s| !matches!(s, Schema::OneOfClass { .. }))
+            .map(|s| {
+                let id = s.class_name().as_str();
+                (id, unique_name(type_name(id), &mut used))
+            })
+            .collect();
+
+        Self {
+            type_names,
+            components: singular_components(schemas),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn item(&mut self, schema: &'a Schema) -> Option<TokenStream> {
+        match schema {
+            Schema::Class {
+                id,
+                base,
+                key,
+                documentation,
+                subdocument,
+                r#abstract,
+                inherits,
+                unfoldable,
+                properties,
+            } => {
+                let mut attrs = vec![];
+                attrs.extend(self.class_name_attr(id));
+                attrs.extend(base.as_ref().map(|base| quote! { base = #base }));
+                attrs.extend(key_attrs(key));
+                if *subdocument {
+                    attrs.push(quote! { subdocument = true });
+                }
+                if *r#abstract {
+                    attrs.push(quote! { abstract_class = true });
+                }
+                if *unfoldable {
+                    attrs.push(quote! { unfoldable = true });
+                }
+                if !inherits.is_empty() {
+                    let inherits = inherits.join(",");
+                    attrs.push(quote! { inherits = #inherits });
+                }
+
+                let ident = self.type_ident(id);
+                let docs = class_docs(documentation);
+                let fields = self.fields(id, documentation, properties);
+
+                Some(quote! {
+                    #docs
+                    #[derive(Debug, Clone, PartialEq, terminusdb_schema_derive::TerminusDBModel)]
+                    #[tdb(#(#attrs),*)]
+                    pub struct #ident {
+                        #(#fields),*
+                    }
+                })
+            }
+            Schema::TaggedUnion {
+                id,
+                base,
+                key,
+                documentation,
+                subdocument,
+                properties,
+                ..
+            } => {
+                let mut attrs = vec![];
+                attrs.extend(self.class_name_attr(id));
+                attrs.extend(base.as_ref().map(|base| quote! { base = #base }));
+                attrs.extend(key_attrs(key));
+                if *subdocument {
+                    attrs.push(quote! { subdocument = true });
+                }
+
+                let ident = self.type_ident(id);
+                let docs = class_docs(documentation);
+                let variants = self.union_variants(id, properties);
+
+                Some(quote! {
+                    #docs
+                    #[derive(Debug, Clone, PartialEq, terminusdb_schema_derive::TerminusDBModel)]
+                    #[tdb(#(#attrs),*)]
+                    pub enum #ident {
+                        #(#variants),*
+                    }
+                })
+            }
+            Schema::Enum {
+                id,
+                base,
+                values,
+                documentation,
+            } => {
+                let mut attrs = vec![];
+                attrs.extend(self.class_name_attr(id));
+                attrs.extend(base.as_ref().map(|base| quote! { base = #base }));
+
+                let mut used = HashSet::new();
+                let names: Vec<String> = values
+                    .iter()
+                    .map(|value| unique_name(variant_name(value), &mut used))
+                    .collect();
+
+                // The derive lowercases variant names unless told otherwise
+                let fits = |rename: Rename| {
+                    names
+                        .iter()
+                        .zip(values)
+                        .all(|(name, value)| rename(name) == *value)
+                };
+                if !fits(|s| s.to_lowercase()) {
+                    match RENAME_STRATEGIES.iter().find(|(_, rename)| fits(*rename)) {
+                        Some((strategy, _)) => attrs.push(quote! { rename_all = #strategy }),
+                        None => self.warnings.push(format!(
+                            "Enum '{}': values {:?} can't be reproduced from Rust variant names",
+                            id, values
+                        )),
+                    }
+                }
+
+                let ident = self.type_ident(id);
+                let docs = class_docs(documentation);
+                let variants = names.iter().map(|name| Ident::new(name, Span::call_site()));
+                let attrs = if attrs.is_empty() {
+                    quote! {}
+                } else {
+                    quote! { #[tdb(#(#attrs),*)] }
+                };
+
+                Some(quote! {
+                    #docs
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, terminusdb_schema_derive::TerminusDBModel)]
+                    #attrs
+                    pub enum #ident {
+                        #(#variants),*
+                    }
+                })
+            }
+            Schema::OneOfClass { id, .. } => {
+                self.warnings.push(format!(
+                    "Class '{}' has @oneOf properties, which are not generated",
+                    id
+                ));
+                None
+            }
+        }
+    }
+
+    fn fields(
+        &mut self,
+        owner: &str,
+        documentation: &Option<ClassDocumentation>,
+        properties: &[Property],
+    ) -> Vec<TokenStream> {
+        let mut used = HashSet::new();
+        properties
+            .iter()
+            .map(|property| {
+                let name = unique_name(field_name(&property.name), &mut used);
+                let ident = Ident::new(&name, Span::call_site());
+                let ty = self.property_type(owner, property);
+
+                let rename = (name != property.name).then(|| {
+                    let property_name = &property.name;
+                    quote! { #[tdb(name = #property_name)] }
+                });
+                let doc = documentation
+                    .as_ref()
+                    .and_then(|docs| docs.properties_or_values.get(&property.name))
+                    .map(|doc| quote! { #[doc = #doc] });
+
+                quote! {
+                    #doc
+                    #rename
+                    pub #ident: #ty
+                }
+            })
+            .collect()
+    }
+
+    fn union_variants(&mut self, owner: &str, properties: &[Property]) -> Vec<TokenStream> {
+        let mut used = HashSet::new();
+        properties
+            .iter()
+            .map(|property| {
+                let name = unique_name(variant_name(&property.name), &mut used);
+                // Unlike simple enums, TaggedUnion variants are always read
+                // back under their lowercased name
+                if name.to_lowercase() != property.name {
+                    self.warnings.push(format!(
+                        "TaggedUnion '{}': variant '{}' is named '{}' in the generated model",
+                        owner,
+                        property.name,
+                        name.to_lowercase()
+                    ));
+                }
+                let ident = Ident::new(&name, Span::call_site());
+
+                if property.class == "sys:Unit" {
+                    quote! { #ident }
+                } else {
+                    let ty = self.property_type(owner, property);
+                    quote! { #ident(#ty) }
+                }
+            })
+            .collect()
+    }
+
+    /// The Rust type of a property, including its type family.
+    fn property_type(&mut self, owner: &str, property: &Property) -> TokenStream {
+        let class = self.class_type(owner, &property.class);
+        let singular = if self.same_component(owner, &property.class) {
+            quote! { Box<#class> }
+        } else {
+            class.clone()
+        };
+
+        match &property.r#type {
+            None => singular,
+            Some(TypeFamily::Optional) => quote! { Option<#singular> },
+            Some(TypeFamily::List) => quote! { Vec<#class> },
+            Some(TypeFamily::Set(_)) => quote! { std::collections::HashSet<#class> },
+            Some(TypeFamily::Array(_)) => {
+                self.warnings.push(format!(
+                    "'{}.{}': Array is generated as a List",
+                    owner, property.name
+                ));
+                quote! { Vec<#class> }
+            }
+        }
+    }
+
+    /// The Rust type of a class, without type family.
+    fn class_type(&mut self, owner: &str, class: &str) -> TokenStream {
+        match class {
+            "xsd:string" => quote! { String },
+            "xsd:boolean" => quote! { bool },
+            "xsd:integer" => quote! { isize },
+            "xsd:long" => quote! { i64 },
+            "xsd:unsignedInt" => quote! { u32 },
+            "xsd:unsignedLong" => quote! { u64 },
+            "xsd:byte" => quote! { i8 },
+            "xsd:unsignedByte" => quote! { u8 },
+            "xsd:nonNegativeInteger" => quote! { u128 },
+            "xsd:decimal" => quote! { terminusdb_schema::BigDecimal },
+            "xsd:double" => quote! { f64 },
+            "xsd:float" => quote! { f32 },
+            "xsd:dateTime" => quote! { chrono::DateTime<chrono::Utc> },
+            "xsd:date" => quote! { chrono::NaiveDate },
+            "xsd:time" => quote! { chrono::NaiveTime },
+            "sys:JSON" => quote! { serde_json::Value },
+            _ if class.starts_with("xsd:") => {
+                self.warnings.push(format!(
+                    "'{}': {} is generated as String (xsd:string)",
+                    owner, class
+                ));
+                quote! { String }
+            }
+            _ => {
+                let ident = self.type_ident(class);
+                quote! { #ident }
+            }
+        }
+    }
+
+    fn type_ident(&mut self, class: &str) -> Ident {
+        let name = match self.type_names.get(class) {
+            Some(name) => name.clone(),
+            None => {
+                self.warnings
+                    .push(format!("Class '{}' is referenced but not generated", class));
+                type_name(class)
+            }
+        };
+        Ident::new(&name, Span::call_site())
+    }
+
+    /// `class_name` attribute for types whose Rust name differs from the class.
+    fn class_name_attr(&self, id: &str) -> Option<TokenStream> {
+        (self.type_names.get(id).map(String::as_str) != Some(id))
+            .then(|| quote! { class_name = #id })
+    }
+
+    fn same_component(&self, owner: &str, class: &str) -> bool {
+        match (self.components.get(owner), self.components.get(class)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn key_attrs(key: &Key) -> Vec<TokenStream> {
+    match key {
+        Key::Random => vec![quote! { key = "random" }],
+        Key::ValueHash => vec![quote! { key = "value_hash" }],
+        Key::Lexical(fields) => {
+            let fields = fields.join(",");
+            vec![quote! { key = "lexical" }, quote! { key_fields = #fields }]
+        }
+        Key::Hash(fields) => {
+            let fields = fields.join(",");
+            vec![quote! { key = "hash" }, quote! { key_fields = #fields }]
+        }
+    }
+}
+
+fn class_docs(documentation: &Option<ClassDocumentation>) -> TokenStream {
+    match documentation {
+        Some(docs) if !docs.comment.is_empty() => {
+            let comment = &docs.comment;
+            quote! { #[doc = #comment] }
+        }
+        _ => quote! {},
+    }
+}
+
+/// Strongly connected components of the graph whose edges are the singular
+/// (required or optional) properties between generated classes.
+fn singular_components(schemas: &[Schema]) -> HashMap<&str, usize> {
+    let mut edges: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for schema in schemas {
+        let properties = match schema {
+            Schema::Class { properties, .. } | Schema::TaggedUnion { properties, .. } => properties,
+            _ => continue,
+        };
+        let targets = properties
+            .iter()
+            .filter(|p| matches!(p.r#type, None | Some(TypeFamily::Optional)))
+            .map(|p| p.class.as_str())
+            .collect();
+        edges.insert(schema.class_name().as_str(), targets);
+    }
+
+    // Tarjan's algorithm
+    struct Tarjan<'a, 'e> {
+        edges: &'e BTreeMap<&'a str, Vec<&'a str>>,
+        index: HashMap<&'a str, usize>,
+        lowlink: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: HashSet<&'a str>,
+        components: HashMap<&'a str, usize>,
+        next_component: usize,
+    }
+
+    impl<'a> Tarjan<'a, '_> {
+        fn visit(&mut self, node: &'a str) {
+            let index = self.index.len();
+            self.index.insert(node, index);
+            self.lowlink.insert(node, index);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            let edges = self.edges;
+            for &target in &edges[node] {
+                if !self.edges.contains_key(target) {
+                    continue;
+                }
+                if !self.index.contains_key(target) {
+                    self.visit(target);
+                    let low = self.lowlink[node].min(self.lowlink[target]);
+                    self.lowlink.insert(node, low);
+                } else if self.on_stack.contains(target) {
+                    let low = self.lowlink[node].min(self.index[target]);
+                    self.lowlink.insert(node, low);
+                }
+            }
+
+            if self.lowlink[node] == self.index[node] {
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    self.components.insert(member, self.next_component);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.next_component += 1;
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges: &edges,
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: HashMap::new(),
+        next_component: 0,
+    };
+    for &node in edges.keys() {
+        if !tarjan.index.contains_key(node) {
+            tarjan.visit(node);
+        }
+    }
+
+    // A class alone in its component only needs boxing if it refers to itself
+    let mut components = tarjan.components;
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for component in components.values() {
+        *sizes.entry(*component).or_default() += 1;
+    }
+    for (node, targets) in &edges {
+        if sizes[&components[node]] == 1 && !targets.contains(node) {
+            components.remove(node);
+        }
+    }
+    components
+}
+
+/// Rust type name for a class id.
+fn type_name(id: &str) -> String {
+    let name = if is_identifier(id) {
+        id.to_string()
+    } else {
+        id.to_pascal_case()
+    };
+    escape(name, "T")
+}
+
+/// Rust variant name for a union variant or enum value.
+fn variant_name(name: &str) -> String {
+    escape(name.to_pascal_case(), "V")
+}
+
+/// Rust field name for a property.
+fn field_name(name: &str) -> String {
+    escape(name.to_snake_case(), "field")
+}
+
+/// Make a name a valid identifier: prefix names that are empty or start with
+/// a digit, and suffix keywords with an underscore.
+fn escape(name: String, prefix: &str) -> String {
+    if name.is_empty() {
+        prefix.to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{}{}", prefix, name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// Add a numeric suffix to names already used in the same scope.
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut unique = name.clone();
+    let mut n = 2;
+    while !used.insert(unique.clone()) {
+        unique = format!("{}{}", name, n);
+        n += 1;
+    }
+    unique
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The derive macro's snake_case conversion: an underscore before every
+/// uppercase letter but the first.
+fn derive_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        if c.is_uppercase() && !result.is_empty() {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(id: &str, properties: Vec<Property>) -> Schema {
+        Schema::Class {
+            id: id.to_string(),
+            base: None,
+            key: Key::Random,
+            documentation: None,
+            subdocument: true,
+            r#abstract: false,
+            inherits: vec![],
+            unfoldable: false,
+            properties,
+        }
+    }
+
+    fn property(name: &str, r#type: Option<TypeFamily>, class: &str) -> Property {
+        Property {
+            name: name.to_string(),
+            r#type,
+            class: class.to_string(),
+        }
+    }
+
+    /// Generated code with whitespace removed, for substring assertions
+    fn compact(models: &RustModels) -> String {
+        models.code.split_whitespace().collect()
+    }
+
+    #[test]
+    fn test_class_fields() {
+        let models = generate_rust_models(&[class(
+            "BookType",
+            vec![
+                property("title", None, "xsd:string"),
+                property("page-count", Some(TypeFamily::Optional), "xsd:integer"),
+                property("type", Some(TypeFamily::List), "xsd:dateTime"),
+            ],
+        )]);
+        let code = compact(&models);
+
+        assert!(code.contains("#[tdb(key=\"random\",subdocument=true)]pubstructBookType"));
+        assert!(code.contains("pubtitle:String"));
+        assert!(code.contains("#[tdb(name=\"page-count\")]pubpage_count:Option<isize>"));
+        assert!(code.contains("#[tdb(name=\"type\")]pubtype_:Vec<chrono::DateTime<chrono::Utc>>"));
+        assert!(models.warnings.is_empty(), "{:?}", models.warnings);
+    }
+
+    #[test]
+    fn test_tagged_union_variants() {
+        let models = generate_rust_models(&[
+            class(
+                "SectionType",
+                vec![property(
+                    "children",
+                    Some(TypeFamily::List),
+                    "SectionTypeChild",
+                )],
+            ),
+            Schema::TaggedUnion {
+                id: "SectionTypeChild".to_string(),
+                base: None,
+                key: Key::Random,
+                r#abstract: false,
+                documentation: None,
+                subdocument: true,
+                properties: vec![
+                    property("text", None, "xsd:string"),
+                    property("section", None, "SectionType"),
+                    property("ph-list", None, "SectionType"),
+                ],
+                unfoldable: false,
+            },
+        ]);
+        let code = compact(&models);
+
+        assert!(code.contains("pubchildren:Vec<SectionTypeChild>"));
+        assert!(code.contains(
+            "pubenumSectionTypeChild{Text(String),Section(SectionType),PhList(SectionType)}"
+        ));
+        assert_eq!(models.warnings.len(), 1, "{:?}", models.warnings);
+        assert!(models.warnings[0].contains("ph-list"));
+    }
+
+    #[test]
+    fn test_enum_rename_strategy() {
+        let models = generate_rust_models(&[Schema::Enum {
+            id: "status-type".to_string(),
+            base: None,
+            values: vec!["in-progress".to_string(), "done".to_string()],
+            documentation: None,
+        }]);
+        let code = compact(&models);
+
+        assert!(code.contains(
+            "#[tdb(class_name=\"status-type\",rename_all=\"kebab-case\")]pubenumStatusType{InProgress,Done}"
+        ));
+        assert!(models.warnings.is_empty(), "{:?}", models.warnings);
+    }
+
+    #[test]
+    fn test_singular_cycles_are_boxed() {
+        let models = generate_rust_models(&[
+            class(
+                "Node",
+                vec![
+                    property("next", Some(TypeFamily::Optional), "Node"),
+                    property("children", Some(TypeFamily::List), "Node"),
+                    property("leaf", None, "Leaf"),
+                ],
+            ),
+            class("Leaf", vec![property("value", None, "xsd:string")]),
+            class("A", vec![property("b", None, "B")]),
+            class("B", vec![property("a", Some(TypeFamily::Optional), "A")]),
+        ]);
+        let code = compact(&models);
+
+        assert!(code.contains("pubnext:Option<Box<Node>>"));
+        assert!(code.contains("pubchildren:Vec<Node>"));
+        assert!(code.contains("publeaf:Leaf"));
+        assert!(code.contains("pubb:Box<B>"));
+        assert!(code.contains("puba:Option<Box<A>>"));
+    }
+}
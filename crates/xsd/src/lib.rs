@@ -15,8 +15,14 @@
 //! ## Modules
 //!
 //! - `schema_model`: XSD schema extraction and parsing
+//! - `resolver`: Fetching multi-file schemas from local directories and HTTP URLs
 //! - `schema_generator`: Runtime TerminusDB Schema generation from XSD
+//! - `schema_diff`: Compatibility reports and migration plans between XSD versions
 //! - `xml_parser`: XML to TerminusDB instance parsing
+//! - `xml_serializer`: TerminusDB instances back to XML
+//! - `streaming`: Batched ingestion of large XML documents with a pull parser
+//! - `validation`: Checking XML documents with line/column diagnostics before conversion
+//! - `codegen`: Rust `#[derive(TerminusDBModel)]` code generation, for build scripts
 //!
 //! ## Usage
 //!
@@ -38,15 +44,28 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+pub mod codegen;
+pub mod resolver;
+pub mod schema_diff;
 pub mod schema_generator;
 pub mod schema_model;
+pub mod streaming;
+pub mod validation;
 pub mod xml_parser;
+pub mod xml_serializer;
 pub mod xsd_model;
 
 use thiserror::Error;
 
+#[cfg(feature = "http")]
+pub use resolver::HttpResolver;
+pub use resolver::{CachingResolver, FileResolver, SchemaBundle, SchemaLocation, SchemaResolver};
+pub use schema_diff::{Compatibility, XsdChange, XsdSchemaDiff};
 pub use schema_model::*;
+pub use streaming::{InstanceStream, StreamOptions};
+pub use validation::{ValidationReport, Violation, ViolationKind, XmlValidator};
 pub use xml_parser::{ParseResult, XmlParseError, XmlToInstanceParser};
+pub use xml_serializer::{InstanceToXmlSerializer, SerializeResult, XmlSerializeError};
 pub use xsd_model::XsdModel;
 
 #[derive(Debug, Error)]
@@ -65,6 +84,9 @@ pub enum XsdError {
 
     #[error("Value conversion error: {0}")]
     Conversion(String),
+
+    #[error("Schema resolution error: {0}")]
+    Resolution(String),
 }
 
 pub type Result<T> = std::result::Result<T, XsdError>;
@@ -0,0 +1,597 @@
+//! Resolution of multi-file XSD schemas
+//!
+//! Real-world schemas are split across many documents linked by `xs:include`,
+//! `xs:import`, `xs:redefine` and `xs:override`, with `schemaLocation`s that
+//! point at sibling files, other directories or HTTP URLs.
+//!
+//! A [`SchemaResolver`] fetches a single schema document. [`SchemaBundle`]
+//! follows the `schemaLocation`s from an entry point, fetching every document
+//! the schema needs through a resolver, and can write them to one local
+//! directory with the locations rewritten to match. The written entry point
+//! then loads like any local schema file.
+//!
+//! Available resolvers:
+//!
+//! - [`FileResolver`]: local files only
+//! - `HttpResolver` (`http` feature): local files and HTTP(S) URLs
+//! - [`CachingResolver`]: wraps another resolver, caching documents in memory
+//!   and, optionally, remote documents on disk
+//!
+//! URN locations are not resolved here; they are left to the XML catalog.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use url::Url;
+
+use crate::{Result, XsdError};
+
+/// Location of a schema document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SchemaLocation {
+    /// A local file
+    File(PathBuf),
+    /// An HTTP(S) URL
+    Url(Url),
+}
+
+impl SchemaLocation {
+    /// Parse a location given as a URL (`http`, `https` or `file`) or a path.
+    pub fn parse(location: &str) -> Result<Self> {
+        match parse_url(location) {
+            Some(url) => Self::from_url(url).ok_or_else(|| {
+                XsdError::Resolution(format!("Unsupported schema location: {}", location))
+            }),
+            None => Ok(Self::File(normalize(Path::new(location)))),
+        }
+    }
+
+    /// Resolve a `schemaLocation` reference relative to this location.
+    ///
+    /// Returns `None` for references that are neither paths nor HTTP(S) or
+    /// file URLs, such as URNs.
+    pub fn join(&self, reference: &str) -> Option<Self> {
+        if let Some(url) = parse_url(reference) {
+            return Self::from_url(url);
+        }
+        match self {
+            Self::Url(base) => base.join(reference).ok().map(Self::Url),
+            Self::File(path) => {
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                Some(Self::File(normalize(&dir.join(reference))))
+            }
+        }
+    }
+
+    /// Whether the document has to be fetched over the network.
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Self::Url(_))
+    }
+
+    /// The last segment of the location, if it has one.
+    pub fn file_name(&self) -> Option<&str> {
+        match self {
+            Self::File(path) => path.file_name().and_then(|name| name.to_str()),
+            Self::Url(url) => url.path_segments()?.next_back().filter(|s| !s.is_empty()),
+        }
+    }
+
+    fn from_url(url: Url) -> Option<Self> {
+        match url.scheme() {
+            "http" | "https" => Some(Self::Url(url)),
+            "file" => url.to_file_path().ok().map(Self::File),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SchemaLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Url(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Fetches schema documents.
+///
+/// Implement this to load schemas from other sources, such as a database or
+/// an archive.
+pub trait SchemaResolver {
+    /// Fetch the content of the schema document at `location`.
+    fn fetch(&self, location: &SchemaLocation) -> Result<String>;
+}
+
+/// Resolves local files only.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileResolver;
+
+impl SchemaResolver for FileResolver {
+    fn fetch(&self, location: &SchemaLocation) -> Result<String> {
+        match location {
+            SchemaLocation::File(path) => read_file(path),
+            SchemaLocation::Url(url) => Err(XsdError::Resolution(format!(
+                "Cannot fetch remote schema {} (enable the `http` feature and use HttpResolver)",
+                url
+            ))),
+        }
+    }
+}
+
+/// Resolves local files and HTTP(S) URLs.
+///
+/// Requests are blocking; from async code, load schemas inside
+/// `tokio::task::spawn_blocking`.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone)]
+pub struct HttpResolver {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "http")]
+impl HttpResolver {
+    /// Create a resolver with a 30 second request timeout.
+    pub fn new() -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| XsdError::Resolution(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self { client })
+    }
+
+    /// Create a resolver that uses the given HTTP client.
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "http")]
+impl SchemaResolver for HttpResolver {
+    fn fetch(&self, location: &SchemaLocation) -> Result<String> {
+        match location {
+            SchemaLocation::File(path) => read_file(path),
+            SchemaLocation::Url(url) => {
+                tracing::debug!("Fetching schema {}", url);
+                self.client
+                    .get(url.as_str())
+                    .send()
+                    .and_then(|response| response.error_for_status())
+                    .and_then(|response| response.text())
+                    .map_err(|e| {
+                        XsdError::Resolution(format!("Failed to fetch schema {}: {}", url, e))
+                    })
+            }
+        }
+    }
+}
+
+/// Caches the documents fetched by another resolver.
+///
+/// Documents are cached in memory for the lifetime of the resolver. With a
+/// cache directory, remote documents are also stored on disk and reused
+/// across runs; delete the directory to refresh them.
+#[derive(Debug)]
+pub struct CachingResolver<R> {
+    inner: R,
+    cache_dir: Option<PathBuf>,
+    documents: Mutex<HashMap<SchemaLocation, String>>,
+}
+
+impl<R: SchemaResolver> CachingResolver<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cache_dir: None,
+            documents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Store remote documents in `dir`.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Path of the on-disk cache entry for a remote location.
+    fn cache_path(&self, location: &SchemaLocation) -> Option<PathBuf> {
+        match location {
+            SchemaLocation::Url(url) => Some(self.cache_dir.as_ref()?.join(cache_file_name(url))),
+            SchemaLocation::File(_) => None,
+        }
+    }
+}
+
+impl<R: SchemaResolver> SchemaResolver for CachingResolver<R> {
+    fn fetch(&self, location: &SchemaLocation) -> Result<String> {
+        if let Some(content) = self.documents.lock().unwrap().get(location) {
+            return Ok(content.clone());
+        }
+
+        let cache_path = self.cache_path(location);
+        let content = match cache_path.as_deref().filter(|path| path.is_file()) {
+            Some(path) => read_file(path)?,
+            None => {
+                let content = self.inner.fetch(location)?;
+                if let Some(path) = &cache_path {
+                    if let Some(dir) = path.parent() {
+                        std::fs::create_dir_all(dir)?;
+                    }
+                    std::fs::write(path, &content)?;
+                }
+                content
+            }
+        };
+
+        self.documents
+            .lock()
+            .unwrap()
+            .insert(location.clone(), content.clone());
+        Ok(content)
+    }
+}
+
+/// A schema document fetched into a [`SchemaBundle`].
+#[derive(Debug, Clone)]
+pub struct BundledSchema {
+    /// Where the document was fetched from
+    pub location: SchemaLocation,
+    /// File name of the document inside the bundle
+    pub file_name: String,
+    /// Content with `schemaLocation`s pointing at other documents of the bundle
+    pub content: String,
+}
+
+/// An entry-point schema and every document it includes or imports.
+#[derive(Debug, Clone)]
+pub struct SchemaBundle {
+    /// Documents in discovery order; the entry point comes first
+    documents: Vec<BundledSchema>,
+}
+
+impl SchemaBundle {
+    /// Fetch the schema at `entry` and, transitively, every document it
+    /// references.
+    ///
+    /// References without a `schemaLocation` (imports of well-known
+    /// namespaces) and URN locations are left as they are.
+    pub fn resolve(entry: &SchemaLocation, resolver: &dyn SchemaResolver) -> Result<Self> {
+        let mut fetched: Vec<(SchemaLocation, String)> = Vec::new();
+        let mut file_names: HashMap<SchemaLocation, String> = HashMap::new();
+        let mut used_names = HashSet::new();
+        let mut queue = VecDeque::from([entry.clone()]);
+        file_names.insert(entry.clone(), unique_file_name(entry, &mut used_names));
+
+        while let Some(location) = queue.pop_front() {
+            tracing::debug!("Resolving schema {}", location);
+            let content = resolver.fetch(&location)?;
+
+            let mut references = Vec::new();
+            map_schema_locations(&content, |reference| {
+                references.push(reference.to_string());
+                None
+            })
+            .map_err(|e| XsdError::Resolution(format!("{}: {}", location, e)))?;
+
+            for reference in references {
+                let Some(referenced) = location.join(&reference) else {
+                    continue;
+                };
+                if !file_names.contains_key(&referenced) {
+                    let name = unique_file_name(&referenced, &mut used_names);
+                    file_names.insert(referenced.clone(), name);
+                    queue.push_back(referenced);
+                }
+            }
+            fetched.push((location, content));
+        }
+
+        let documents = fetched
+            .into_iter()
+            .map(|(location, content)| {
+                let content = map_schema_locations(&content, |reference| {
+                    location
+                        .join(reference)
+                        .and_then(|referenced| file_names.get(&referenced).cloned())
+                })
+                .map_err(|e| XsdError::Resolution(format!("{}: {}", location, e)))?;
+                Ok(BundledSchema {
+                    file_name: file_names[&location].clone(),
+                    location,
+                    content,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { documents })
+    }
+
+    /// The entry-point document.
+    pub fn entry(&self) -> &BundledSchema {
+        &self.documents[0]
+    }
+
+    /// All documents, entry point first.
+    pub fn documents(&self) -> &[BundledSchema] {
+        &self.documents
+    }
+
+    /// Write every document into `dir`, returning the path of the entry point.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> Result<PathBuf> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        for document in &self.documents {
+            std::fs::write(dir.join(&document.file_name), &document.content)?;
+        }
+        Ok(dir.join(&self.entry().file_name))
+    }
+}
+
+/// Rewrite the `schemaLocation` of every `xs:include`, `xs:import`,
+/// `xs:redefine` and `xs:override` in a schema document.
+///
+/// `rewrite` receives each location and returns its replacement, or `None`
+/// to keep it. Everything else is copied unchanged.
+fn map_schema_locations(
+    content: &str,
+    mut rewrite: impl FnMut(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut reader = Reader::from_str(content);
+    let mut writer = Writer::new(Vec::new());
+    let mut depth = 0usize;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("XML error at byte {}: {}", reader.error_position(), e))?;
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) => {
+                depth += 1;
+                if depth == 2 {
+                    Event::Start(rewrite_reference(&reader, start, &mut rewrite)?)
+                } else {
+                    Event::Start(start)
+                }
+            }
+            Event::Empty(start) if depth == 1 => {
+                Event::Empty(rewrite_reference(&reader, start, &mut rewrite)?)
+            }
+            Event::End(end) => {
+                depth = depth.saturating_sub(1);
+                Event::End(end)
+            }
+            other => other,
+        };
+        writer
+            .write_event(event)
+            .map_err(|e| format!("Failed to write schema: {}", e))?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| e.to_string())
+}
+
+/// Rewrite the `schemaLocation` of a top-level schema declaration, if it is a
+/// reference to another document.
+fn rewrite_reference<'a>(
+    reader: &Reader<&[u8]>,
+    start: BytesStart<'a>,
+    rewrite: &mut impl FnMut(&str) -> Option<String>,
+) -> std::result::Result<BytesStart<'a>, String> {
+    let local_name = start.local_name();
+    if !matches!(
+        local_name.as_ref(),
+        b"include" | b"import" | b"redefine" | b"override"
+    ) {
+        return Ok(start);
+    }
+
+    let mut location = None;
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        if attribute.key.as_ref() == b"schemaLocation" {
+            let value = attribute
+                .decode_and_unescape_value(reader.decoder())
+                .map_err(|e| e.to_string())?;
+            location = rewrite(&value);
+        }
+    }
+    let Some(location) = location else {
+        return Ok(start);
+    };
+
+    let mut rewritten =
+        BytesStart::new(String::from_utf8_lossy(start.name().as_ref()).into_owned());
+    for attribute in start.attributes().flatten() {
+        if attribute.key.as_ref() == b"schemaLocation" {
+            rewritten.push_attribute(("schemaLocation", location.as_str()));
+        } else {
+            rewritten.push_attribute(Attribute {
+                key: attribute.key,
+                value: attribute.value,
+            });
+        }
+    }
+    Ok(rewritten)
+}
+
+/// A file name for `location` that no other document of the bundle uses.
+fn unique_file_name(location: &SchemaLocation, used: &mut HashSet<String>) -> String {
+    let name = location.file_name().unwrap_or("schema.xsd");
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+
+    let mut candidate = name.to_string();
+    let mut n = 1;
+    while !used.insert(candidate.clone()) {
+        n += 1;
+        candidate = match extension {
+            Some(extension) => format!("{}_{}.{}", stem, n, extension),
+            None => format!("{}_{}", stem, n),
+        };
+    }
+    candidate
+}
+
+/// File name of a URL's on-disk cache entry: the URL with every character
+/// other than ASCII letters, digits, `-` and `.` percent-encoded.
+fn cache_file_name(url: &Url) -> String {
+    url.as_str()
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b == b'-' || b == b'.' {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+/// Parse `location` as a URL. Windows paths such as `C:\schemas\a.xsd` parse
+/// as URLs with a one-letter scheme, so those are treated as paths.
+fn parse_url(location: &str) -> Option<Url> {
+    Url::parse(location)
+        .ok()
+        .filter(|url| url.scheme().len() > 1)
+}
+
+/// Remove `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        XsdError::Resolution(format!("Failed to read schema {}: {}", path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves documents from memory, counting fetches.
+    #[derive(Default)]
+    struct MemoryResolver {
+        documents: HashMap<String, String>,
+        fetches: Mutex<usize>,
+    }
+
+    impl MemoryResolver {
+        fn with(mut self, location: &str, content: &str) -> Self {
+            self.documents
+                .insert(location.to_string(), content.to_string());
+            self
+        }
+    }
+
+    impl SchemaResolver for MemoryResolver {
+        fn fetch(&self, location: &SchemaLocation) -> Result<String> {
+            *self.fetches.lock().unwrap() += 1;
+            self.documents
+                .get(&location.to_string())
+                .cloned()
+                .ok_or_else(|| XsdError::Resolution(format!("Not found: {}", location)))
+        }
+    }
+
+    fn schema(references: &str) -> String {
+        format!(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}<xs:element name="e"/></xs:schema>"#,
+            references
+        )
+    }
+
+    #[test]
+    fn test_join() {
+        let file = SchemaLocation::parse("schemas/main/order.xsd").unwrap();
+        assert_eq!(
+            file.join("../common/types.xsd"),
+            Some(SchemaLocation::File(PathBuf::from(
+                "schemas/common/types.xsd"
+            )))
+        );
+
+        let remote = SchemaLocation::parse("https://example.com/xsd/v1/order.xsd").unwrap();
+        assert_eq!(
+            remote.join("../common.xsd").unwrap().to_string(),
+            "https://example.com/xsd/common.xsd"
+        );
+        assert!(file.join("http://example.com/a.xsd").unwrap().is_remote());
+        assert_eq!(file.join("urn:example:types"), None);
+    }
+
+    #[test]
+    fn test_bundle_rewrites_locations() {
+        let resolver = MemoryResolver::default()
+            .with(
+                "dir/main.xsd",
+                &schema(
+                    r#"<xs:include schemaLocation="types.xsd"/><xs:import namespace="urn:x" schemaLocation="https://example.com/types.xsd"/><xs:import namespace="http://www.w3.org/XML/1998/namespace"/>"#,
+                ),
+            )
+            .with(
+                "dir/types.xsd",
+                &schema(r#"<xs:include schemaLocation="./main.xsd"/>"#),
+            )
+            .with("https://example.com/types.xsd", &schema(""));
+
+        let bundle =
+            SchemaBundle::resolve(&SchemaLocation::parse("dir/main.xsd").unwrap(), &resolver)
+                .unwrap();
+
+        let names: Vec<&str> = bundle
+            .documents()
+            .iter()
+            .map(|d| d.file_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["main.xsd", "types.xsd", "types_2.xsd"]);
+        assert!(bundle
+            .entry()
+            .content
+            .contains(r#"<xs:import namespace="urn:x" schemaLocation="types_2.xsd"/>"#));
+        assert!(bundle
+            .entry()
+            .content
+            .contains(r#"<xs:import namespace="http://www.w3.org/XML/1998/namespace"/>"#));
+        // The include cycle is fetched once
+        assert_eq!(*resolver.fetches.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_caching_resolver() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://example.com/a.xsd";
+        let location = SchemaLocation::parse(url).unwrap();
+
+        let resolver = CachingResolver::new(MemoryResolver::default().with(url, &schema("")))
+            .with_cache_dir(dir.path());
+        resolver.fetch(&location).unwrap();
+        resolver.fetch(&location).unwrap();
+        assert_eq!(*resolver.inner.fetches.lock().unwrap(), 1);
+
+        // A new resolver reads the document from the cache directory
+        let resolver = CachingResolver::new(MemoryResolver::default()).with_cache_dir(dir.path());
+        assert_eq!(resolver.fetch(&location).unwrap(), schema(""));
+    }
+}
@@ -0,0 +1,1131 @@
+//! XSD schema evolution
+//!
+//! [`XsdSchema::diff`] compares two versions of a schema and lists what
+//! changed: global elements and types that were added, removed or renamed,
+//! and the attributes, child elements and enumeration values of the types
+//! present in both. Each change is classified as compatible or breaking,
+//! where *breaking* means some documents valid against the old version may be
+//! invalid against the new one.
+//!
+//! Renames are detected by structure: a removed and an added item are paired
+//! when they have the same shape and no other removed or added item does.
+//!
+//! With the `client` feature, [`XsdSchemaDiff::migration_plan`] maps the
+//! changes onto schema-migration operations for the TerminusDB schemas
+//! generated from both versions.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::schema_model::{Cardinality, Restriction, XsdComplexType, XsdSchema, XsdSimpleType};
+
+/// Whether a change is an attribute or a child element of a complex type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberKind {
+    Attribute,
+    Element,
+}
+
+impl fmt::Display for MemberKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Attribute => write!(f, "attribute"),
+            Self::Element => write!(f, "element"),
+        }
+    }
+}
+
+/// How many times a member may occur: `min..max`, `max` being `None` when
+/// unbounded. Attributes occur `0..1` or, when required, `1..1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Occurs {
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+impl Occurs {
+    /// Whether every number of occurrences allowed by `self` is allowed by
+    /// `other`.
+    pub fn is_within(&self, other: &Occurs) -> bool {
+        let max_within = match (self.max, other.max) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(max), Some(other_max)) => max <= other_max,
+        };
+        other.min <= self.min && max_within
+    }
+}
+
+impl fmt::Display for Occurs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) => write!(f, "{}..{}", self.min, max),
+            None => write!(f, "{}..*", self.min),
+        }
+    }
+}
+
+/// A change between two versions of an XSD schema.
+///
+/// Names are local names. Anonymous types are named after their element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum XsdChange {
+    /// A global element was added
+    ElementAdded { element: String },
+    /// A global element was removed
+    ElementRemoved { element: String },
+    /// A global element was renamed, keeping its type
+    ElementRenamed { from: String, to: String },
+    /// A global element's declared type changed
+    ElementTypeChanged {
+        element: String,
+        from: String,
+        to: String,
+    },
+    /// A complex or simple type was added
+    TypeAdded { type_name: String },
+    /// A complex or simple type was removed
+    TypeRemoved { type_name: String },
+    /// A type was renamed, keeping its structure
+    TypeRenamed { from: String, to: String },
+    /// An attribute or child element was added to a complex type
+    MemberAdded {
+        type_name: String,
+        kind: MemberKind,
+        name: String,
+        required: bool,
+    },
+    /// An attribute or child element was removed from a complex type
+    MemberRemoved {
+        type_name: String,
+        kind: MemberKind,
+        name: String,
+    },
+    /// An attribute or child element was renamed, keeping its type and
+    /// occurrence
+    MemberRenamed {
+        type_name: String,
+        kind: MemberKind,
+        from: String,
+        to: String,
+    },
+    /// The type of an attribute or child element changed
+    MemberTypeChanged {
+        type_name: String,
+        kind: MemberKind,
+        name: String,
+        from: String,
+        to: String,
+    },
+    /// The number of times an attribute or child element may occur changed
+    MemberOccursChanged {
+        type_name: String,
+        kind: MemberKind,
+        name: String,
+        from: Occurs,
+        to: Occurs,
+    },
+    /// Values were added to an enumerated simple type
+    EnumValuesAdded {
+        type_name: String,
+        values: Vec<String>,
+    },
+    /// Values were removed from an enumerated simple type
+    EnumValuesRemoved {
+        type_name: String,
+        values: Vec<String>,
+    },
+}
+
+/// Whether documents valid against the old schema stay valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    /// Every document valid against the old schema is valid against the new one
+    Compatible,
+    /// Some documents valid against the old schema may be invalid against the
+    /// new one
+    Breaking,
+}
+
+impl XsdChange {
+    pub fn compatibility(&self) -> Compatibility {
+        let compatible = match self {
+            Self::ElementAdded { .. } | Self::TypeAdded { .. } | Self::EnumValuesAdded { .. } => {
+                true
+            }
+            // Type names do not appear in documents
+            Self::TypeRenamed { .. } => true,
+            Self::MemberAdded { required, .. } => !required,
+            Self::MemberOccursChanged { from, to, .. } => from.is_within(to),
+            Self::ElementRemoved { .. }
+            | Self::ElementRenamed { .. }
+            | Self::ElementTypeChanged { .. }
+            | Self::TypeRemoved { .. }
+            | Self::MemberRemoved { .. }
+            | Self::MemberRenamed { .. }
+            | Self::MemberTypeChanged { .. }
+            | Self::EnumValuesRemoved { .. } => false,
+        };
+        if compatible {
+            Compatibility::Compatible
+        } else {
+            Compatibility::Breaking
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+}
+
+impl fmt::Display for XsdChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ElementAdded { element } => write!(f, "element <{}> added", element),
+            Self::ElementRemoved { element } => write!(f, "element <{}> removed", element),
+            Self::ElementRenamed { from, to } => {
+                write!(f, "element <{}> renamed to <{}>", from, to)
+            }
+            Self::ElementTypeChanged { element, from, to } => {
+                write!(
+                    f,
+                    "element <{}> changed type from {} to {}",
+                    element, from, to
+                )
+            }
+            Self::TypeAdded { type_name } => write!(f, "type {} added", type_name),
+            Self::TypeRemoved { type_name } => write!(f, "type {} removed", type_name),
+            Self::TypeRenamed { from, to } => write!(f, "type {} renamed to {}", from, to),
+            Self::MemberAdded {
+                type_name,
+                kind,
+                name,
+                required,
+            } => {
+                let required = if *required { "required" } else { "optional" };
+                write!(f, "{}: {} {} {} added", type_name, required, kind, name)
+            }
+            Self::MemberRemoved {
+                type_name,
+                kind,
+                name,
+            } => write!(f, "{}: {} {} removed", type_name, kind, name),
+            Self::MemberRenamed {
+                type_name,
+                kind,
+                from,
+                to,
+            } => write!(f, "{}: {} {} renamed to {}", type_name, kind, from, to),
+            Self::MemberTypeChanged {
+                type_name,
+                kind,
+                name,
+                from,
+                to,
+            } => write!(
+                f,
+                "{}: {} {} changed type from {} to {}",
+                type_name, kind, name, from, to
+            ),
+            Self::MemberOccursChanged {
+                type_name,
+                kind,
+                name,
+                from,
+                to,
+            } => write!(
+                f,
+                "{}: {} {} occurrence changed from {} to {}",
+                type_name, kind, name, from, to
+            ),
+            Self::EnumValuesAdded { type_name, values } => write!(
+                f,
+                "{}: enumeration values added: {}",
+                type_name,
+                values.join(", ")
+            ),
+            Self::EnumValuesRemoved { type_name, values } => write!(
+                f,
+                "{}: enumeration values removed: {}",
+                type_name,
+                values.join(", ")
+            ),
+        }
+    }
+}
+
+/// The changes between two versions of an XSD schema.
+///
+/// Its `Display` implementation is a human-readable compatibility report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct XsdSchemaDiff {
+    pub changes: Vec<XsdChange>,
+}
+
+impl XsdSchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether every document valid against the old schema is valid against
+    /// the new one.
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(XsdChange::is_breaking)
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &XsdChange> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+}
+
+impl fmt::Display for XsdSchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        writeln!(
+            f,
+            "{} changes, {} breaking",
+            self.changes.len(),
+            self.breaking_changes().count()
+        )?;
+        for change in &self.changes {
+            let label = match change.compatibility() {
+                Compatibility::Compatible => "compatible",
+                Compatibility::Breaking => "breaking",
+            };
+            writeln!(f, "  {:<10}  {}", label, change)?;
+        }
+        Ok(())
+    }
+}
+
+impl XsdSchema {
+    /// Compare two versions of a schema.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use terminusdb_xsd::schema_model::XsdSchema;
+    ///
+    /// let old = XsdSchema::from_xsd_file("catalog-v1.xsd", None::<&str>)?;
+    /// let new = XsdSchema::from_xsd_file("catalog-v2.xsd", None::<&str>)?;
+    ///
+    /// let diff = XsdSchema::diff(&old, &new);
+    /// print!("{}", diff);
+    /// if !diff.is_compatible() {
+    ///     println!("Existing documents need migrating");
+    /// }
+    /// # Ok::<(), terminusdb_xsd::XsdError>(())
+    /// ```
+    pub fn diff(old: &XsdSchema, new: &XsdSchema) -> XsdSchemaDiff {
+        let old_types = types_by_name(old);
+        let new_types = types_by_name(new);
+        let mut changes = Vec::new();
+
+        // Types first: their renames are needed to compare elements and members
+        let types = diff_keys(
+            &old_types,
+            &new_types,
+            |ty| ty.signature(&BTreeMap::new()),
+            |ty| ty.signature(&BTreeMap::new()),
+        );
+        let type_renames: BTreeMap<String, String> = types.renamed.iter().cloned().collect();
+
+        let old_elements = elements_by_name(old, &old_types);
+        let new_elements = elements_by_name(new, &new_types);
+        let elements = diff_keys(
+            &old_elements,
+            &new_elements,
+            |element| renamed_element_type(element, &type_renames),
+            |element| element.clone(),
+        );
+
+        for element in elements.added {
+            changes.push(XsdChange::ElementAdded { element });
+        }
+        for element in elements.removed {
+            changes.push(XsdChange::ElementRemoved { element });
+        }
+        for (from, to) in elements.renamed {
+            changes.push(XsdChange::ElementRenamed { from, to });
+        }
+        for element in elements.common {
+            let (from, to) = (&old_elements[&element], &new_elements[&element]);
+            // Changes within anonymous types are reported as member changes
+            let both_anonymous = is_anonymous(from) && is_anonymous(to);
+            if !both_anonymous && renamed_element_type(from, &type_renames) != *to {
+                changes.push(XsdChange::ElementTypeChanged {
+                    element,
+                    from: element_type_name(from).to_string(),
+                    to: element_type_name(to).to_string(),
+                });
+            }
+        }
+
+        for type_name in types.added {
+            changes.push(XsdChange::TypeAdded { type_name });
+        }
+        for type_name in types.removed {
+            changes.push(XsdChange::TypeRemoved { type_name });
+        }
+        for (from, to) in types.renamed {
+            changes.push(XsdChange::TypeRenamed { from, to });
+        }
+        for type_name in types.common {
+            match (&old_types[&type_name], &new_types[&type_name]) {
+                (TypeDef::Complex(old), TypeDef::Complex(new)) => {
+                    diff_members(&type_name, old, new, &type_renames, &mut changes);
+                }
+                (TypeDef::Simple(old), TypeDef::Simple(new)) => {
+                    diff_enum_values(&type_name, old, new, &mut changes);
+                }
+                // A complex type replaced by a simple one or the reverse
+                _ => {
+                    changes.push(XsdChange::TypeRemoved {
+                        type_name: type_name.clone(),
+                    });
+                    changes.push(XsdChange::TypeAdded { type_name });
+                }
+            }
+        }
+
+        XsdSchemaDiff { changes }
+    }
+}
+
+/// A global type of a schema.
+enum TypeDef<'a> {
+    Complex(&'a XsdComplexType),
+    Simple(&'a XsdSimpleType),
+}
+
+impl TypeDef<'_> {
+    /// The type's structure, for rename detection. Type references are
+    /// mapped through `renames`.
+    fn signature(&self, renames: &BTreeMap<String, String>) -> String {
+        match self {
+            Self::Complex(ct) => {
+                let members: Vec<String> = [MemberKind::Attribute, MemberKind::Element]
+                    .into_iter()
+                    .flat_map(|kind| members(ct, kind))
+                    .map(|(name, member)| format!("{}:{}", name, member.signature(renames)))
+                    .collect();
+                format!(
+                    "complex anonymous={} base={:?} mixed={} simple={} {}",
+                    ct.is_anonymous,
+                    ct.base_type.as_deref().map(|b| renamed_type(b, renames)),
+                    ct.mixed,
+                    ct.has_simple_content,
+                    members.join(" ")
+                )
+            }
+            Self::Simple(st) => format!(
+                "simple base={:?} variety={:?} restrictions={:?}",
+                st.base_type, st.variety, st.restrictions
+            ),
+        }
+    }
+}
+
+/// An attribute or child element of a complex type.
+struct Member {
+    kind: MemberKind,
+    type_name: String,
+    occurs: Occurs,
+}
+
+impl Member {
+    fn signature(&self, renames: &BTreeMap<String, String>) -> String {
+        format!(
+            "{} {} {}",
+            self.kind,
+            renamed_type(&self.type_name, renames),
+            self.occurs
+        )
+    }
+}
+
+/// Result of comparing two keyed collections.
+#[derive(Default)]
+struct KeyDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    renamed: Vec<(String, String)>,
+    common: Vec<String>,
+}
+
+/// Compare two collections by key, pairing removed and added items as
+/// renames when their signatures match one to one.
+fn diff_keys<T>(
+    old: &BTreeMap<String, T>,
+    new: &BTreeMap<String, T>,
+    old_signature: impl Fn(&T) -> String,
+    new_signature: impl Fn(&T) -> String,
+) -> KeyDiff {
+    let mut diff = KeyDiff::default();
+    let mut removed: Vec<(&String, String)> = Vec::new();
+    for (key, item) in old {
+        if new.contains_key(key) {
+            diff.common.push(key.clone());
+        } else {
+            removed.push((key, old_signature(item)));
+        }
+    }
+    let added: Vec<(&String, String)> = new
+        .iter()
+        .filter(|(key, _)| !old.contains_key(*key))
+        .map(|(key, item)| (key, new_signature(item)))
+        .collect();
+
+    let count = |items: &[(&String, String)], signature: &str| {
+        items.iter().filter(|(_, s)| s == signature).count()
+    };
+    let mut paired = BTreeSet::new();
+    for (key, signature) in &removed {
+        let unique = count(&removed, signature) == 1 && count(&added, signature) == 1;
+        match added.iter().find(|(_, s)| s == signature) {
+            Some((to, _)) if unique => {
+                diff.renamed.push(((*key).clone(), (*to).clone()));
+                paired.insert(*to);
+            }
+            _ => diff.removed.push((*key).clone()),
+        }
+    }
+    diff.added = added
+        .into_iter()
+        .filter(|(key, _)| !paired.contains(key))
+        .map(|(key, _)| key.clone())
+        .collect();
+    diff
+}
+
+/// Compare the attributes and child elements of a complex type.
+fn diff_members(
+    type_name: &str,
+    old: &XsdComplexType,
+    new: &XsdComplexType,
+    type_renames: &BTreeMap<String, String>,
+    changes: &mut Vec<XsdChange>,
+) {
+    for kind in [MemberKind::Attribute, MemberKind::Element] {
+        let (old_members, new_members) = (members(old, kind), members(new, kind));
+        let diff = diff_keys(
+            &old_members,
+            &new_members,
+            |member| member.signature(type_renames),
+            |member| member.signature(&BTreeMap::new()),
+        );
+
+        for name in diff.added {
+            changes.push(XsdChange::MemberAdded {
+                type_name: type_name.to_string(),
+                kind,
+                required: new_members[&name].occurs.min > 0,
+                name,
+            });
+        }
+        for name in diff.removed {
+            changes.push(XsdChange::MemberRemoved {
+                type_name: type_name.to_string(),
+                kind,
+                name,
+            });
+        }
+        for (from, to) in diff.renamed {
+            changes.push(XsdChange::MemberRenamed {
+                type_name: type_name.to_string(),
+                kind,
+                from,
+                to,
+            });
+        }
+        for name in diff.common {
+            let (from, to) = (&old_members[&name], &new_members[&name]);
+            if renamed_type(&from.type_name, type_renames) != to.type_name {
+                changes.push(XsdChange::MemberTypeChanged {
+                    type_name: type_name.to_string(),
+                    kind,
+                    name: name.clone(),
+                    from: local_name(&from.type_name).to_string(),
+                    to: local_name(&to.type_name).to_string(),
+                });
+            }
+            if from.occurs != to.occurs {
+                changes.push(XsdChange::MemberOccursChanged {
+                    type_name: type_name.to_string(),
+                    kind,
+                    name,
+                    from: from.occurs,
+                    to: to.occurs,
+                });
+            }
+        }
+    }
+}
+
+/// Compare the enumeration values of a simple type.
+fn diff_enum_values(
+    type_name: &str,
+    old: &XsdSimpleType,
+    new: &XsdSimpleType,
+    changes: &mut Vec<XsdChange>,
+) {
+    let (old_values, new_values) = (enum_values(old), enum_values(new));
+    let added: Vec<String> = new_values
+        .iter()
+        .filter(|v| !old_values.contains(v))
+        .cloned()
+        .collect();
+    let removed: Vec<String> = old_values
+        .iter()
+        .filter(|v| !new_values.contains(v))
+        .cloned()
+        .collect();
+
+    if !added.is_empty() {
+        changes.push(XsdChange::EnumValuesAdded {
+            type_name: type_name.to_string(),
+            values: added,
+        });
+    }
+    if !removed.is_empty() {
+        changes.push(XsdChange::EnumValuesRemoved {
+            type_name: type_name.to_string(),
+            values: removed,
+        });
+    }
+}
+
+fn enum_values(st: &XsdSimpleType) -> Vec<String> {
+    st.restrictions
+        .iter()
+        .flatten()
+        .filter_map(|r| match r {
+            Restriction::Enumeration { values } => Some(values.iter().cloned()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Attributes or child elements of a complex type, by local name.
+fn members(ct: &XsdComplexType, kind: MemberKind) -> BTreeMap<String, Member> {
+    let mut members = BTreeMap::new();
+    match kind {
+        MemberKind::Attribute => {
+            for attr in ct.attributes.iter().flatten() {
+                let required = attr.use_type == "required";
+                members
+                    .entry(local_name(&attr.name).to_string())
+                    .or_insert(Member {
+                        kind,
+                        type_name: attr.attr_type.clone(),
+                        occurs: Occurs {
+                            min: required as u32,
+                            max: Some(1),
+                        },
+                    });
+            }
+        }
+        MemberKind::Element => {
+            for child in ct.child_elements.iter().flatten() {
+                let max = match child.max_occurs {
+                    None => Some(1),
+                    Some(Cardinality::Number(n)) => Some(n),
+                    Some(Cardinality::Unbounded) => None,
+                };
+                members
+                    .entry(local_name(&child.name).to_string())
+                    .or_insert(Member {
+                        kind,
+                        type_name: child.element_type.clone(),
+                        occurs: Occurs {
+                            min: child.min_occurs.unwrap_or(1),
+                            max,
+                        },
+                    });
+            }
+        }
+    }
+    members
+}
+
+/// Global types by local name; anonymous types by their element's name.
+fn types_by_name(schema: &XsdSchema) -> BTreeMap<String, TypeDef<'_>> {
+    let mut types = BTreeMap::new();
+    for ct in &schema.complex_types {
+        let name = match (&ct.element_name, ct.is_anonymous) {
+            (Some(element), true) => element,
+            _ => &ct.name,
+        };
+        types
+            .entry(local_name(name).to_string())
+            .or_insert(TypeDef::Complex(ct));
+    }
+    for st in &schema.simple_types {
+        types
+            .entry(local_name(&st.name).to_string())
+            .or_insert(TypeDef::Simple(st));
+    }
+    types
+}
+
+/// Global elements by local name, mapped to their type: the declared type's
+/// name, or the structure of an anonymous type.
+fn elements_by_name(
+    schema: &XsdSchema,
+    types: &BTreeMap<String, TypeDef<'_>>,
+) -> BTreeMap<String, String> {
+    let mut elements = BTreeMap::new();
+    for element in &schema.root_elements {
+        let name = local_name(&element.name).to_string();
+        let declared = element
+            .type_info
+            .as_ref()
+            .and_then(|ti| ti.qualified_name.as_ref().or(ti.name.as_ref()));
+        let type_name = match declared {
+            Some(type_name) => type_name.clone(),
+            None => match types.get(&name) {
+                Some(ty) => format!("{}{}", ANONYMOUS, ty.signature(&BTreeMap::new())),
+                None => ANONYMOUS.to_string(),
+            },
+        };
+        elements.entry(name).or_insert(type_name);
+    }
+    elements
+}
+
+/// Prefix of the type of elements with anonymous types in
+/// [`elements_by_name`].
+const ANONYMOUS: &str = "anonymous:";
+
+fn is_anonymous(type_name: &str) -> bool {
+    type_name.starts_with(ANONYMOUS)
+}
+
+/// An element's type from [`elements_by_name`], with declared types mapped
+/// through `renames`.
+fn renamed_element_type(type_name: &str, renames: &BTreeMap<String, String>) -> String {
+    if is_anonymous(type_name) {
+        type_name.to_string()
+    } else {
+        renamed_type(type_name, renames)
+    }
+}
+
+/// Name of an element's type from [`elements_by_name`], for display.
+fn element_type_name(type_name: &str) -> &str {
+    if is_anonymous(type_name) {
+        "an anonymous type"
+    } else {
+        local_name(type_name)
+    }
+}
+
+/// Local part of a name in Clark notation (`{namespace}local`).
+fn local_name(name: &str) -> &str {
+    name.rsplit('}').next().unwrap_or(name)
+}
+
+/// A type reference with its local name mapped through `renames`.
+fn renamed_type(type_name: &str, renames: &BTreeMap<String, String>) -> String {
+    let local = local_name(type_name);
+    match renames.get(local) {
+        Some(renamed) => format!("{}{}", &type_name[..type_name.len() - local.len()], renamed),
+        None => type_name.to_string(),
+    }
+}
+
+#[cfg(feature = "client")]
+mod migration {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use heck::ToPascalCase;
+    use serde_json::json;
+    use terminusdb_client::MigrationOperation;
+    use terminusdb_schema::json::ToJson;
+    use terminusdb_schema::{Property, Schema, ToSchemaPropertyJsonValue, TypeFamily};
+
+    use super::{MemberKind, XsdChange, XsdSchemaDiff};
+
+    impl XsdSchemaDiff {
+        /// Map the changes onto schema-migration operations.
+        ///
+        /// `old` and `new` are the TerminusDB schemas generated from the two
+        /// versions, such as [`crate::XsdModel::schemas`]. Renames detected
+        /// in the XSD become `MoveClass` and `MoveClassProperty` operations;
+        /// all other differences between the generated schemas are added,
+        /// removed or cast.
+        ///
+        /// Operations are ordered: renames, then new classes, property
+        /// changes, and deletions last. Required properties are created
+        /// without a default and values that no longer fit their type make
+        /// the cast fail; run the plan with `MigrationOptions::dry_run()`
+        /// first. Removed enumeration values have no migration operation.
+        pub fn migration_plan(&self, old: &[Schema], new: &[Schema]) -> Vec<MigrationOperation> {
+            let new_by_id: BTreeMap<&str, &Schema> =
+                new.iter().map(|s| (s.class_name().as_str(), s)).collect();
+
+            // Class renames, applied to the old schemas before comparing
+            let mut class_renames: BTreeMap<String, String> = BTreeMap::new();
+            for change in &self.changes {
+                let (from, to) = match change {
+                    XsdChange::TypeRenamed { from, to } => {
+                        (from.to_pascal_case(), to.to_pascal_case())
+                    }
+                    XsdChange::ElementRenamed { from, to } => (
+                        format!("{}SubstitutionGroup", from.to_pascal_case()),
+                        format!("{}SubstitutionGroup", to.to_pascal_case()),
+                    ),
+                    _ => continue,
+                };
+                // A type's class and the TaggedUnions generated for its children
+                for schema in old {
+                    let id = schema.class_name();
+                    let Some(suffix) = id.strip_prefix(from.as_str()) else {
+                        continue;
+                    };
+                    if !(suffix.is_empty()
+                        || suffix.starts_with("Child")
+                        || suffix.starts_with("Choice"))
+                    {
+                        continue;
+                    }
+                    let renamed = format!("{}{}", to, suffix);
+                    if new_by_id.contains_key(renamed.as_str())
+                        && !new_by_id.contains_key(id.as_str())
+                    {
+                        class_renames.insert(id.clone(), renamed);
+                    }
+                }
+            }
+            let class_id = |id: &str| {
+                class_renames
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| id.to_string())
+            };
+            let old_by_id: BTreeMap<String, &Schema> =
+                old.iter().map(|s| (class_id(s.class_name()), s)).collect();
+
+            // Property renames, by (class after renames, old property name)
+            let mut property_renames: BTreeMap<(String, String), String> = BTreeMap::new();
+            for change in &self.changes {
+                let (classes, from, to): (Vec<String>, &str, &str) = match change {
+                    XsdChange::MemberRenamed {
+                        type_name,
+                        kind: MemberKind::Attribute,
+                        from,
+                        to,
+                    } => (vec![type_name.to_pascal_case()], from, to),
+                    // Child elements are variants of the type's TaggedUnions
+                    XsdChange::MemberRenamed {
+                        type_name,
+                        kind: MemberKind::Element,
+                        from,
+                        to,
+                    } => {
+                        let class = type_name.to_pascal_case();
+                        let unions = old_by_id
+                            .keys()
+                            .filter(|id| {
+                                id.starts_with(&format!("{}Child", class))
+                                    || id.starts_with(&format!("{}Choice", class))
+                            })
+                            .cloned()
+                            .collect();
+                        (unions, from, to)
+                    }
+                    XsdChange::ElementRenamed { from, to } => {
+                        let unions = old_by_id
+                            .keys()
+                            .filter(|id| id.ends_with("SubstitutionGroup"))
+                            .cloned()
+                            .collect();
+                        (unions, from, to)
+                    }
+                    _ => continue,
+                };
+                for class in classes {
+                    let (Some(old_schema), Some(new_schema)) =
+                        (old_by_id.get(&class), new_by_id.get(class.as_str()))
+                    else {
+                        continue;
+                    };
+                    if let (Some(from), Some(to)) = (
+                        find_property(old_schema, from),
+                        find_property(new_schema, to),
+                    ) {
+                        property_renames.insert((class, from.name.clone()), to.name.clone());
+                    }
+                }
+            }
+
+            let mut moves = Vec::new();
+            let mut created = Vec::new();
+            let mut property_moves = Vec::new();
+            let mut property_changes = Vec::new();
+            let mut property_deletes = Vec::new();
+            let mut deletes = Vec::new();
+
+            for (from, to) in &class_renames {
+                moves.push(MigrationOperation::MoveClass {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+
+            for (id, new_schema) in &new_by_id {
+                let Some(old_schema) = old_by_id.get(*id) else {
+                    created.push(*new_schema);
+                    continue;
+                };
+
+                if let (
+                    Schema::Enum {
+                        values: old_values, ..
+                    },
+                    Schema::Enum {
+                        values: new_values, ..
+                    },
+                ) = (old_schema, new_schema)
+                {
+                    let added: Vec<String> = new_values
+                        .iter()
+                        .filter(|v| !old_values.contains(v))
+                        .cloned()
+                        .collect();
+                    if !added.is_empty() {
+                        property_changes.push(MigrationOperation::ExpandEnum {
+                            enum_name: id.to_string(),
+                            values: added,
+                        });
+                    }
+                    continue;
+                }
+
+                let mut old_properties: BTreeMap<String, &Property> = BTreeMap::new();
+                for property in properties(old_schema) {
+                    let name = match property_renames.get(&(id.to_string(), property.name.clone()))
+                    {
+                        Some(to) => {
+                            property_moves.push(MigrationOperation::MoveClassProperty {
+                                class: id.to_string(),
+                                from: property.name.clone(),
+                                to: to.clone(),
+                            });
+                            to.clone()
+                        }
+                        None => property.name.clone(),
+                    };
+                    old_properties.insert(name, property);
+                }
+
+                let mut seen = BTreeSet::new();
+                for property in properties(new_schema) {
+                    seen.insert(property.name.as_str());
+                    match old_properties.get(&property.name) {
+                        None => property_changes.push(MigrationOperation::CreateClassProperty {
+                            class: id.to_string(),
+                            property: property.name.clone(),
+                            property_type: property.to_property_value(),
+                            default: None,
+                        }),
+                        Some(old_property) => {
+                            let old_class = class_id(&old_property.class);
+                            if old_class == property.class && old_property.r#type == property.r#type
+                            {
+                                continue;
+                            }
+                            let operation = if old_class == property.class
+                                && is_upcast(old_property.r#type, property.r#type)
+                            {
+                                MigrationOperation::UpcastClassProperty {
+                                    class: id.to_string(),
+                                    property: property.name.clone(),
+                                    property_type: property.to_property_value(),
+                                }
+                            } else {
+                                MigrationOperation::CastClassProperty {
+                                    class: id.to_string(),
+                                    property: property.name.clone(),
+                                    property_type: property.to_property_value(),
+                                    default: json!({ "@type": "Error" }),
+                                }
+                            };
+                            property_changes.push(operation);
+                        }
+                    }
+                }
+                for name in old_properties.keys() {
+                    if !seen.contains(name.as_str()) {
+                        property_deletes.push(MigrationOperation::DeleteClassProperty {
+                            class: id.to_string(),
+                            property: name.clone(),
+                        });
+                    }
+                }
+            }
+
+            for (id, old_schema) in &old_by_id {
+                if new_by_id.contains_key(id.as_str()) {
+                    continue;
+                }
+                for property in properties(old_schema) {
+                    property_deletes.push(MigrationOperation::DeleteClassProperty {
+                        class: id.clone(),
+                        property: property.name.clone(),
+                    });
+                }
+                deletes.push(MigrationOperation::DeleteClass { class: id.clone() });
+            }
+
+            let creates = creation_order(&created).into_iter().map(|schema| {
+                MigrationOperation::CreateClass {
+                    class_document: schema.to_json(),
+                }
+            });
+
+            moves
+                .into_iter()
+                .chain(creates)
+                .chain(property_moves)
+                .chain(property_changes)
+                .chain(property_deletes)
+                .chain(deletes)
+                .collect()
+        }
+    }
+
+    /// Properties of a class, or variants of a TaggedUnion.
+    fn properties(schema: &Schema) -> &[Property] {
+        match schema {
+            Schema::Class { properties, .. }
+            | Schema::OneOfClass { properties, .. }
+            | Schema::TaggedUnion { properties, .. } => properties,
+            Schema::Enum { .. } => &[],
+        }
+    }
+
+    /// The property generated for an attribute or element with this local
+    /// name. Namespaced attributes are generated as `prefix:name`.
+    fn find_property<'s>(schema: &'s Schema, local_name: &str) -> Option<&'s Property> {
+        properties(schema).iter().find(|p| {
+            p.name == local_name
+                || p.name
+                    .rsplit_once(':')
+                    .is_some_and(|(_, local)| local == local_name)
+        })
+    }
+
+    /// Whether a property's type family can be widened without touching
+    /// instance data: required to Optional, required or Optional to Set.
+    fn is_upcast(from: Option<TypeFamily>, to: Option<TypeFamily>) -> bool {
+        matches!(
+            (from, to),
+            (None, Some(TypeFamily::Optional))
+                | (None | Some(TypeFamily::Optional), Some(TypeFamily::Set(_)))
+        )
+    }
+
+    /// New classes ordered so that classes come after the new classes their
+    /// properties and parents refer to.
+    fn creation_order<'s>(created: &[&'s Schema]) -> Vec<&'s Schema> {
+        fn visit<'s>(
+            schema: &'s Schema,
+            created: &[&'s Schema],
+            visited: &mut BTreeSet<String>,
+            order: &mut Vec<&'s Schema>,
+        ) {
+            if !visited.insert(schema.class_name().clone()) {
+                return;
+            }
+            let parents = match schema {
+                Schema::Class { inherits, .. } => inherits.as_slice(),
+                _ => &[],
+            };
+            let dependencies = properties(schema).iter().map(|p| &p.class).chain(parents);
+            for dependency in dependencies {
+                if let Some(dependency) = created.iter().find(|s| s.class_name() == dependency) {
+                    visit(dependency, created, visited, order);
+                }
+            }
+            order.push(schema);
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut order = Vec::with_capacity(created.len());
+        for schema in created {
+            visit(schema, created, &mut visited, &mut order);
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_occurs_is_within() {
+        let optional = Occurs {
+            min: 0,
+            max: Some(1),
+        };
+        let required = Occurs {
+            min: 1,
+            max: Some(1),
+        };
+        let many = Occurs { min: 0, max: None };
+
+        assert!(required.is_within(&optional));
+        assert!(optional.is_within(&many));
+        assert!(!optional.is_within(&required));
+        assert!(!many.is_within(&optional));
+        assert_eq!(many.to_string(), "0..*");
+    }
+
+    #[test]
+    fn test_diff_keys_pairs_unique_renames() {
+        let map = |items: &[(&str, &str)]| -> BTreeMap<String, String> {
+            items
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let old = map(&[
+            ("kept", "a"),
+            ("phone", "string"),
+            ("x", "int"),
+            ("y", "int"),
+        ]);
+        let new = map(&[("kept", "a"), ("telephone", "string"), ("z", "int")]);
+
+        let diff = diff_keys(&old, &new, String::clone, String::clone);
+        assert_eq!(diff.common, vec!["kept"]);
+        assert_eq!(
+            diff.renamed,
+            vec![("phone".to_string(), "telephone".to_string())]
+        );
+        // Two removed ints and one added: ambiguous, so not a rename
+        assert_eq!(diff.removed, vec!["x", "y"]);
+        assert_eq!(diff.added, vec!["z"]);
+    }
+
+    #[test]
+    fn test_renamed_type() {
+        let renames = BTreeMap::from([("productType".to_string(), "itemType".to_string())]);
+        assert_eq!(
+            renamed_type("{http://example.com/ns}productType", &renames),
+            "{http://example.com/ns}itemType"
+        );
+        assert_eq!(renamed_type("xs:string", &renames), "xs:string");
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::schema_model::{
     Cardinality, ChildElement, Restriction, SimpleTypeVariety, XsdAttribute, XsdComplexType,
-    XsdSchema, XsdSimpleType,
+    XsdElement, XsdSchema, XsdSimpleType, XsdSubstitutionGroup,
 };
 use crate::Result;
 use heck::ToPascalCase;
@@ -44,6 +44,10 @@ pub struct XsdToSchemaGenerator {
     /// Built during generate() so map_xsd_type_to_tdb_class can resolve
     /// type aliases like HeadingLevelType → xs:positiveInteger → xsd:integer.
     simple_type_bases: std::collections::HashMap<String, String>,
+    /// Local names of substitution group head elements.
+    /// Built during generate() so references to a head element resolve to
+    /// its `{Head}SubstitutionGroup` TaggedUnion.
+    substitution_heads: std::collections::HashSet<String>,
 }
 
 impl XsdToSchemaGenerator {
@@ -53,6 +57,7 @@ impl XsdToSchemaGenerator {
             additional_document_roots: Vec::new(),
             entry_points: None,
             simple_type_bases: std::collections::HashMap::new(),
+            substitution_heads: std::collections::HashSet::new(),
         }
     }
 
@@ -62,6 +67,7 @@ impl XsdToSchemaGenerator {
             additional_document_roots: Vec::new(),
             entry_points: None,
             simple_type_bases: std::collections::HashMap::new(),
+            substitution_heads: std::collections::HashSet::new(),
         }
     }
 
@@ -123,6 +129,12 @@ impl XsdToSchemaGenerator {
             }
         }
 
+        self.substitution_heads = xsd_schema
+            .substitution_groups
+            .iter()
+            .map(|group| group.head.clone())
+            .collect();
+
         let mut schemas = Vec::new();
 
         // Determine which types are document roots (non-subdocuments).
@@ -314,6 +326,11 @@ impl XsdToSchemaGenerator {
             schemas.extend(type_schemas);
         }
 
+        // Generate a TaggedUnion per substitution group, used wherever the head is referenced
+        for group in &xsd_schema.substitution_groups {
+            schemas.push(self.generate_substitution_union(group, xsd_schema)?);
+        }
+
         // Generate schemas for simple types (enums, type aliases)
         for simple_type in &xsd_schema.simple_types {
             if let Some(schema) = self.generate_from_simple_type(simple_type)? {
@@ -1111,14 +1128,14 @@ impl XsdToSchemaGenerator {
             let child_elements = complex_type.child_elements.as_ref().unwrap();
             let include_text = complex_type.mixed;
 
-            let (child_union, child_union_name) = self.generate_children_union(
+            let (child_unions, child_union_name) = self.generate_children_union(
                 &class_id,
                 namespace.clone(),
                 child_elements,
                 include_text,
             )?;
 
-            schemas.push(child_union);
+            schemas.extend(child_unions);
             properties.push(Property {
                 name: "children".to_string(),
                 r#type: Some(TypeFamily::List),
@@ -1584,28 +1601,71 @@ impl XsdToSchemaGenerator {
     /// If `include_text_variant` is true (mixed content), a `text: xsd:string` variant is added
     /// to represent text nodes interspersed with child elements.
     ///
-    /// Returns: (union_schema, union_type_name)
+    /// An `xs:choice` inside the content model becomes its own `{TypeName}Choice`
+    /// TaggedUnion (`{TypeName}Choice{n}` when the type has several), and a single
+    /// `choice` (`choice{n}`) variant of the children union holds it. When the whole
+    /// content model is one choice, the children union already is that choice and
+    /// its elements stay direct variants.
+    ///
+    /// Returns: (union_schemas, children_union_type_name)
     fn generate_children_union(
         &self,
         type_name: &str,
         namespace: Option<String>,
         child_elements: &[ChildElement],
         include_text_variant: bool,
-    ) -> Result<(Schema, String)> {
+    ) -> Result<(Vec<Schema>, String)> {
         let union_name = format!("{}Child", type_name);
 
-        let mut union_properties: Vec<Property> = child_elements
-            .iter()
-            .filter_map(|element| {
-                let (_, local_name) = self.parse_clark_notation(&element.name);
-                let class = self.map_xsd_type_to_tdb_class(&element.element_type).ok()?;
-                Some(Property {
-                    name: local_name,
-                    r#type: None,
-                    class,
-                })
-            })
-            .collect();
+        let mut choices: Vec<usize> = child_elements.iter().filter_map(|e| e.choice).collect();
+        choices.sort_unstable();
+        choices.dedup();
+        let whole_choice = choices.len() == 1 && child_elements.iter().all(|e| e.choice.is_some());
+
+        let mut schemas = Vec::new();
+        let mut union_properties: Vec<Property> = Vec::new();
+
+        for element in child_elements {
+            let property = match element.choice {
+                Some(index) if !whole_choice => {
+                    let position = choices.iter().position(|c| *c == index).unwrap_or(0);
+                    let (variant, choice_name) = if choices.len() == 1 {
+                        ("choice".to_string(), format!("{}Choice", type_name))
+                    } else {
+                        (
+                            format!("choice{}", position + 1),
+                            format!("{}Choice{}", type_name, position + 1),
+                        )
+                    };
+                    if union_properties.iter().any(|p| p.name == variant) {
+                        continue;
+                    }
+
+                    let choice_elements = child_elements
+                        .iter()
+                        .filter(|e| e.choice == Some(index))
+                        .filter_map(|e| self.element_variant(e));
+                    schemas.push(self.tagged_union(
+                        &choice_name,
+                        namespace.clone(),
+                        choice_elements.collect(),
+                    ));
+
+                    Property {
+                        name: variant,
+                        r#type: None,
+                        class: choice_name,
+                    }
+                }
+                _ => match self.element_variant(element) {
+                    Some(property) => property,
+                    None => continue,
+                },
+            };
+            if !union_properties.iter().any(|p| p.name == property.name) {
+                union_properties.push(property);
+            }
+        }
 
         if include_text_variant {
             union_properties.push(Property {
@@ -1615,18 +1675,136 @@ impl XsdToSchemaGenerator {
             });
         }
 
-        let union_schema = Schema::TaggedUnion {
-            id: union_name.clone(),
+        schemas.insert(
+            0,
+            self.tagged_union(&union_name, namespace, union_properties),
+        );
+
+        Ok((schemas, union_name))
+    }
+
+    /// The TaggedUnion variant for a child element: its type's class, or the
+    /// `{Head}SubstitutionGroup` union when the element heads a substitution group.
+    ///
+    /// Returns None for elements whose type cannot be resolved.
+    fn element_variant(&self, element: &ChildElement) -> Option<Property> {
+        let (_, local_name) = self.parse_clark_notation(&element.name);
+        let class = if self.substitution_heads.contains(&local_name) {
+            Self::substitution_union_name(&local_name)
+        } else {
+            self.map_xsd_type_to_tdb_class(&element.element_type).ok()?
+        };
+        Some(Property {
+            name: local_name,
+            r#type: None,
+            class,
+        })
+    }
+
+    /// Build a subdocument TaggedUnion, dropping duplicate variant names.
+    fn tagged_union(&self, id: &str, namespace: Option<String>, variants: Vec<Property>) -> Schema {
+        let mut properties: Vec<Property> = Vec::with_capacity(variants.len());
+        for variant in variants {
+            if !properties.iter().any(|p| p.name == variant.name) {
+                properties.push(variant);
+            }
+        }
+
+        Schema::TaggedUnion {
+            id: id.to_string(),
             base: namespace,
             key: Key::Random,
             r#abstract: false,
             documentation: None,
             subdocument: true,
-            properties: union_properties,
+            properties,
             unfoldable: false,
+        }
+    }
+
+    // ========================================================================
+    // Substitution Group Support
+    // ========================================================================
+
+    /// Name of the TaggedUnion generated for a substitution group head.
+    fn substitution_union_name(head: &str) -> String {
+        format!("{}SubstitutionGroup", head.to_pascal_case())
+    }
+
+    /// Generate the `{Head}SubstitutionGroup` TaggedUnion for a substitution group.
+    ///
+    /// Variants are the head element itself (unless abstract) and every element
+    /// that can substitute for it, including members of members, each typed by
+    /// the element's declared type.
+    fn generate_substitution_union(
+        &self,
+        group: &XsdSubstitutionGroup,
+        xsd_schema: &XsdSchema,
+    ) -> Result<Schema> {
+        let find_element = |name: &str| {
+            xsd_schema
+                .root_elements
+                .iter()
+                .find(|e| self.parse_clark_notation(&e.name).1 == name)
         };
 
-        Ok((union_schema, union_name))
+        let mut names = Vec::new();
+        if !group.is_abstract {
+            names.push(group.head.clone());
+        }
+        let mut pending: Vec<&str> = vec![group.head.as_str()];
+        while let Some(head) = pending.pop() {
+            for member in xsd_schema
+                .substitution_groups
+                .iter()
+                .filter(|g| g.head == head)
+                .flat_map(|g| &g.members)
+            {
+                if !names.contains(member) && *member != group.head {
+                    names.push(member.clone());
+                    pending.push(member);
+                }
+            }
+        }
+
+        let mut variants = Vec::new();
+        for name in names {
+            let Some(element) = find_element(&name) else {
+                tracing::warn!(
+                    "Substitution group member <{}> of <{}> has no global declaration, skipping",
+                    name,
+                    group.head
+                );
+                continue;
+            };
+            variants.push(Property {
+                class: self.element_class(element)?,
+                name,
+                r#type: None,
+            });
+        }
+
+        let namespace =
+            find_element(&group.head).and_then(|head| self.parse_clark_notation(&head.name).0);
+
+        Ok(self.tagged_union(
+            &Self::substitution_union_name(&group.head),
+            namespace,
+            variants,
+        ))
+    }
+
+    /// TerminusDB class of a global element: its declared type, or the
+    /// element's own class for anonymous types.
+    fn element_class(&self, element: &XsdElement) -> Result<String> {
+        match element
+            .type_info
+            .as_ref()
+            .and_then(|ti| ti.qualified_name.as_ref().or(ti.name.as_ref()))
+        {
+            Some(type_name) => self.map_xsd_type_to_tdb_class(type_name),
+            None => Ok(self.parse_clark_notation(&element.name).1.to_pascal_case()),
+        }
     }
 
     /// Check if a complex type should use mixed content handling.
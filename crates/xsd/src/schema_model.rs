@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use xmlschema::documents::{Document, Element};
 use xmlschema::validators::{
     ComplexContent, FormDefault, GlobalType, GroupParticle, Occurs,
     XsdComplexType as RustComplexType, XsdGroup, XsdSchema as RustXsdSchema,
@@ -43,6 +45,10 @@ pub struct XsdSchema {
     /// Used to construct prefixed TDB property names for namespaced attributes.
     #[serde(default)]
     pub namespace_prefixes: std::collections::HashMap<String, String>,
+    /// Substitution groups declared in the schema and the documents it includes
+    /// or imports. Members may appear wherever their head element is referenced.
+    #[serde(default)]
+    pub substitution_groups: Vec<XsdSubstitutionGroup>,
 }
 
 /// XSD element declaration
@@ -179,6 +185,22 @@ pub struct ChildElement {
     pub min_occurs: Option<u32>,
     #[serde(default, deserialize_with = "cardinality_option_de::deserialize")]
     pub max_occurs: Option<Cardinality>,
+    /// Index of the outermost `xs:choice` this element belongs to, numbered
+    /// in document order within the complex type. `None` outside any choice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choice: Option<usize>,
+}
+
+/// XSD substitution group: global elements declaring `substitutionGroup="head"`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XsdSubstitutionGroup {
+    /// Local name of the head element
+    pub head: String,
+    /// True if the head is abstract and never appears in instance documents itself
+    #[serde(default)]
+    pub is_abstract: bool,
+    /// Local names of the direct members (members of members are listed under their own head)
+    pub members: Vec<String>,
 }
 
 /// XSD restriction (for simple types)
@@ -339,6 +361,8 @@ impl XsdSchema {
         // Collect namespace prefix→URI mappings from the XSD's xmlns: declarations
         let namespace_prefixes = schema.source.namespaces.clone();
 
+        let substitution_groups = Self::collect_substitution_groups(path);
+
         Ok(Self {
             target_namespace,
             schema_location,
@@ -348,9 +372,87 @@ impl XsdSchema {
             simple_types,
             entry_point_elements,
             namespace_prefixes,
+            substitution_groups,
         })
     }
 
+    /// Collect substitution groups from the schema document at `path` and the
+    /// local documents it includes or imports.
+    ///
+    /// Substitution groups are read from the `substitutionGroup` and `abstract`
+    /// attributes of global element declarations. Remote schema locations
+    /// (URLs, URNs) are skipped.
+    fn collect_substitution_groups(path: &Path) -> Vec<XsdSubstitutionGroup> {
+        let mut members: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut abstract_elements = HashSet::new();
+        let mut visited = HashSet::new();
+        Self::scan_substitution_groups(path, &mut visited, &mut members, &mut abstract_elements);
+
+        members
+            .into_iter()
+            .map(|(head, members)| XsdSubstitutionGroup {
+                is_abstract: abstract_elements.contains(&head),
+                head,
+                members,
+            })
+            .collect()
+    }
+
+    fn scan_substitution_groups(
+        path: &Path,
+        visited: &mut HashSet<PathBuf>,
+        members: &mut BTreeMap<String, Vec<String>>,
+        abstract_elements: &mut HashSet<String>,
+    ) {
+        let Ok(canonical) = path.canonicalize() else {
+            return;
+        };
+        if !visited.insert(canonical) {
+            return;
+        }
+        let Some(root) = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| Document::from_string(&content).ok())
+            .and_then(|doc| doc.root)
+        else {
+            return;
+        };
+
+        for decl in &root.children {
+            let kind = decl.local_name();
+            if kind == "element" {
+                let Some(name) = xsd_attribute(decl, "name") else {
+                    continue;
+                };
+                if xsd_attribute(decl, "abstract") == Some("true") {
+                    abstract_elements.insert(name.to_string());
+                }
+                if let Some(head) = xsd_attribute(decl, "substitutionGroup") {
+                    // Strip the namespace prefix of the head's QName
+                    let head = head.rsplit(':').next().unwrap_or(head);
+                    members
+                        .entry(head.to_string())
+                        .or_default()
+                        .push(name.to_string());
+                }
+            } else if kind == "include" || kind == "import" || kind == "redefine" {
+                let Some(location) = xsd_attribute(decl, "schemaLocation") else {
+                    continue;
+                };
+                if location.contains("://") || location.starts_with("urn:") {
+                    continue;
+                }
+                let dir = path.parent().unwrap_or_else(|| Path::new("."));
+                Self::scan_substitution_groups(
+                    &dir.join(location),
+                    visited,
+                    members,
+                    abstract_elements,
+                );
+            }
+        }
+    }
+
     /// Infer entry point element names from the XSD file path.
     ///
     /// DITA and similar XSD bundles often use naming conventions:
@@ -574,14 +676,28 @@ impl XsdSchema {
         parent_optional: bool,
         schema: &RustXsdSchema,
     ) -> Vec<ChildElement> {
-        Self::extract_group_children_with_full_context(group, parent_optional, None, schema)
+        let mut next_choice = 0;
+        Self::extract_group_children_with_full_context(
+            group,
+            parent_optional,
+            None,
+            None,
+            &mut next_choice,
+            schema,
+        )
     }
 
-    /// Internal helper that also propagates parent maxOccurs
+    /// Internal helper that also propagates parent maxOccurs and choice membership
+    ///
+    /// Each outermost `xs:choice` (with more than one particle) gets the next
+    /// index from `next_choice`; everything nested inside it, including nested
+    /// choices and sequences, belongs to that choice.
     fn extract_group_children_with_full_context(
         group: &Arc<XsdGroup>,
         parent_optional: bool,
         parent_max_occurs: Option<&Cardinality>,
+        parent_choice: Option<usize>,
+        next_choice: &mut usize,
         schema: &RustXsdSchema,
     ) -> Vec<ChildElement> {
         let mut children = Vec::new();
@@ -605,20 +721,33 @@ impl XsdSchema {
         };
 
         // If this group is a reference to another group (xs:group ref="..."),
-        // resolve it and use the referenced group's particles
-        let particles: &Vec<GroupParticle> = if let Some(ref group_ref) = group.group_ref {
+        // resolve it and use the referenced group's particles and compositor
+        let resolved: &XsdGroup = if let Some(ref group_ref) = group.group_ref {
             // Look up the referenced group in the schema
             if let Some((_qname, referenced_group)) = schema.groups().find(|(qname, _)| {
                 // Match by local name (group_ref may or may not have namespace)
                 qname.local_name == group_ref.local_name
             }) {
-                &referenced_group.particles
+                referenced_group
             } else {
                 // Group reference not found, use this group's particles (likely empty)
-                &group.particles
+                group
             }
         } else {
-            &group.particles
+            group
+        };
+        let particles: &Vec<GroupParticle> = &resolved.particles;
+
+        // A choice between several particles starts a new choice index, unless
+        // we are already inside one (a choice within a choice is the same choice)
+        let choice = match parent_choice {
+            Some(index) => Some(index),
+            None if Self::is_choice_group(resolved) && particles.len() > 1 => {
+                let index = *next_choice;
+                *next_choice += 1;
+                Some(index)
+            }
+            None => None,
         };
 
         for particle in particles {
@@ -654,14 +783,18 @@ impl XsdSchema {
                         element_type,
                         min_occurs: Some(effective_min),
                         max_occurs: Some(effective_max),
+                        choice,
                     });
                 }
                 GroupParticle::Group(nested) => {
-                    // Recursively extract children, propagating both optionality and max occurs
+                    // Recursively extract children, propagating optionality, max occurs
+                    // and choice membership
                     children.extend(Self::extract_group_children_with_full_context(
                         nested,
                         is_optional,
                         effective_parent_max.as_ref(),
+                        choice,
+                        next_choice,
                         schema,
                     ));
                 }
@@ -672,6 +805,7 @@ impl XsdSchema {
                         element_type: "xs:anyType".to_string(),
                         min_occurs: Some(0),
                         max_occurs: Some(Cardinality::Unbounded),
+                        choice,
                     });
                 }
             }
@@ -680,6 +814,13 @@ impl XsdSchema {
         children
     }
 
+    /// Whether a model group is an `xs:choice` compositor
+    fn is_choice_group(group: &XsdGroup) -> bool {
+        format!("{:?}", group.model)
+            .to_lowercase()
+            .contains("choice")
+    }
+
     /// Resolve element type from an ElementParticle
     ///
     /// Tries in order:
@@ -884,6 +1025,16 @@ impl XsdSchema {
     }
 }
 
+/// Value of an unqualified attribute on an XSD declaration
+fn xsd_attribute<'e>(decl: &'e Element, name: &str) -> Option<&'e str> {
+    for (qname, value) in &decl.attributes {
+        if qname.local_name == name {
+            return Some(value.as_str());
+        }
+    }
+    None
+}
+
 /// Convert Occurs to our Cardinality type
 fn occurs_to_cardinality(occurs: &Occurs) -> Cardinality {
     match occurs.max {
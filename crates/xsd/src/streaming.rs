@@ -0,0 +1,437 @@
+//! Streaming XML ingestion for large documents
+//!
+//! [`XmlToInstanceParser::xml_element_to_instance`] needs the whole DOM in
+//! memory, which does not scale to multi-GB files. The streaming mode reads the
+//! document with a pull parser instead and only buffers one *record* at a
+//! time: a chunk of the document (by default each child of the root element)
+//! that is parsed into an [`Instance`] and then dropped.
+//!
+//! Records are emitted in batches, through an iterator ([`InstanceStream`]),
+//! a callback ([`XmlToInstanceParser::stream_with`]), or — with the `client`
+//! feature — inserted into TerminusDB with one commit per batch
+//! ([`XmlToInstanceParser::insert_stream`]).
+//!
+//! Input is expected to be UTF-8. Namespace declarations made on ancestors of
+//! a record are copied onto the record, so records parse the same as they
+//! would inside the full document.
+
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use terminusdb_schema::Instance;
+use xmlschema::documents::Document;
+
+use crate::xml_parser::{ParseResult, XmlParseError, XmlToInstanceParser};
+
+/// Default number of instances per batch.
+pub const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// Options for streaming XML ingestion.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Number of instances per emitted batch (at least 1).
+    pub batch_size: usize,
+    /// Element names (case-insensitive) that become instances, at any depth.
+    /// When empty, every child of the root element becomes an instance.
+    pub record_elements: Vec<String>,
+}
+
+impl StreamOptions {
+    pub fn new() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            record_elements: Vec::new(),
+        }
+    }
+
+    /// Set the number of instances per batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the element names that become instances.
+    ///
+    /// Records are not nested: inside a record, matching elements are part of
+    /// the record's instance rather than instances of their own.
+    pub fn with_record_elements(
+        mut self,
+        elements: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.record_elements = elements.into_iter().map(|e| e.into()).collect();
+        self
+    }
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A record being buffered: its serialized XML so far and the depth its
+/// element was opened at.
+struct Record {
+    writer: Writer<Vec<u8>>,
+    depth: usize,
+}
+
+/// Iterator over batches of instances parsed from a streamed XML document.
+///
+/// Created by [`XmlToInstanceParser::stream`]. Each item is a non-empty batch
+/// of at most `batch_size` instances, in document order. The first error ends
+/// the stream.
+pub struct InstanceStream<'p, 'a, R> {
+    parser: &'p XmlToInstanceParser<'a>,
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    options: StreamOptions,
+    /// Number of currently open elements
+    depth: usize,
+    /// Namespace declarations of each open element outside the current record
+    namespaces: Vec<Vec<(Vec<u8>, Vec<u8>)>>,
+    record: Option<Record>,
+    batch: Vec<Instance>,
+    done: bool,
+}
+
+impl<'a> XmlToInstanceParser<'a> {
+    /// Stream an XML document, yielding batches of instances.
+    ///
+    /// Only one record is held in memory at a time, so this works for
+    /// documents far larger than memory.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    /// use terminusdb_xsd::streaming::StreamOptions;
+    /// use terminusdb_xsd::XsdModel;
+    ///
+    /// let model = XsdModel::from_file("catalog.xsd", None::<&str>)?;
+    /// let parser = model.instance_parser();
+    /// let file = BufReader::new(File::open("catalog.xml")?);
+    ///
+    /// for batch in parser.stream(file, StreamOptions::new().with_batch_size(1000)) {
+    ///     let instances = batch?;
+    ///     println!("{} instances", instances.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stream<R: BufRead>(
+        &self,
+        reader: R,
+        options: StreamOptions,
+    ) -> InstanceStream<'_, 'a, R> {
+        InstanceStream {
+            parser: self,
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            options,
+            depth: 0,
+            namespaces: Vec::new(),
+            record: None,
+            batch: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Stream an XML document, passing each batch of instances to `on_batch`.
+    ///
+    /// Returns the total number of instances. Stops at the first error from
+    /// parsing or from `on_batch`.
+    pub fn stream_with<R: BufRead>(
+        &self,
+        reader: R,
+        options: StreamOptions,
+        mut on_batch: impl FnMut(Vec<Instance>) -> ParseResult<()>,
+    ) -> ParseResult<usize> {
+        let mut count = 0;
+        for batch in self.stream(reader, options) {
+            let batch = batch?;
+            count += batch.len();
+            on_batch(batch)?;
+        }
+        Ok(count)
+    }
+
+    /// Stream an XML document into TerminusDB, committing each batch
+    /// separately.
+    ///
+    /// Commit messages are `args.message` with the batch number appended.
+    /// Batches committed before an error stay committed. Reading the document
+    /// blocks the current task between inserts.
+    ///
+    /// Returns the total number of instances inserted.
+    #[cfg(feature = "client")]
+    pub async fn insert_stream<R: BufRead>(
+        &self,
+        reader: R,
+        options: StreamOptions,
+        client: &terminusdb_client::TerminusDBHttpClient,
+        args: terminusdb_client::DocumentInsertArgs,
+    ) -> anyhow::Result<usize> {
+        let mut count = 0;
+        for (n, batch) in self.stream(reader, options).enumerate() {
+            let batch = batch?;
+            let mut batch_args = args.clone();
+            batch_args.message = format!("{} (batch {})", args.message, n + 1);
+
+            client
+                .insert_documents(batch.iter().collect(), batch_args)
+                .await?;
+            count += batch.len();
+            tracing::debug!("Inserted batch {} ({} instances total)", n + 1, count);
+        }
+        Ok(count)
+    }
+}
+
+impl<R: BufRead> InstanceStream<'_, '_, R> {
+    /// Read and handle one XML event.
+    fn advance(&mut self) -> ParseResult<()> {
+        let mut buf = std::mem::take(&mut self.buf);
+        let event = self.reader.read_event_into(&mut buf).map_err(|e| {
+            XmlParseError::parse(format!(
+                "XML read error at byte {}: {}",
+                self.reader.buffer_position(),
+                e
+            ))
+        })?;
+
+        match event {
+            Event::Start(start) => {
+                if let Some(record) = &mut self.record {
+                    write(&mut record.writer, Event::Start(start))?;
+                } else if self.is_record(&start) {
+                    let mut writer = Writer::new(Vec::new());
+                    write(&mut writer, Event::Start(self.record_start(&start)))?;
+                    self.record = Some(Record {
+                        writer,
+                        depth: self.depth,
+                    });
+                } else {
+                    self.namespaces.push(namespace_declarations(&start));
+                }
+                self.depth += 1;
+            }
+            Event::End(end) => {
+                self.depth = self.depth.saturating_sub(1);
+                if let Some(record) = &mut self.record {
+                    write(&mut record.writer, Event::End(end))?;
+                    if record.depth == self.depth {
+                        let record = self.record.take().expect("record is open");
+                        self.finish_record(record.writer)?;
+                    }
+                } else {
+                    self.namespaces.pop();
+                }
+            }
+            Event::Empty(start) => {
+                if let Some(record) = &mut self.record {
+                    write(&mut record.writer, Event::Empty(start))?;
+                } else if self.is_record(&start) {
+                    let mut writer = Writer::new(Vec::new());
+                    write(&mut writer, Event::Empty(self.record_start(&start)))?;
+                    self.finish_record(writer)?;
+                }
+            }
+            Event::Eof => {
+                if self.record.is_some() {
+                    return Err(XmlParseError::parse(
+                        "XML document ended inside an unclosed element",
+                    ));
+                }
+                self.done = true;
+            }
+            // Text, CDATA, comments, entity references and processing
+            // instructions only matter inside a record
+            other => {
+                if let Some(record) = &mut self.record {
+                    write(&mut record.writer, other)?;
+                }
+            }
+        }
+
+        buf.clear();
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Whether an element outside any record starts a new record.
+    fn is_record(&self, start: &BytesStart) -> bool {
+        if self.options.record_elements.is_empty() {
+            return self.depth == 1;
+        }
+        let local_name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+        self.options
+            .record_elements
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&local_name))
+    }
+
+    /// The record's start tag, with the namespace declarations it inherits
+    /// from its ancestors added.
+    fn record_start(&self, start: &BytesStart) -> BytesStart<'static> {
+        let declared = namespace_declarations(start);
+        let mut inherited: Vec<(&[u8], &[u8])> = Vec::new();
+        for (key, value) in self.namespaces.iter().rev().flatten() {
+            let shadowed = declared.iter().any(|(k, _)| k == key)
+                || inherited.iter().any(|(k, _)| *k == key.as_slice());
+            if !shadowed {
+                inherited.push((key.as_slice(), value.as_slice()));
+            }
+        }
+
+        let mut record_start = start.clone().into_owned();
+        for attribute in inherited {
+            record_start.push_attribute(attribute);
+        }
+        record_start
+    }
+
+    /// Parse a complete record into an instance and add it to the batch.
+    fn finish_record(&mut self, writer: Writer<Vec<u8>>) -> ParseResult<()> {
+        let xml = String::from_utf8(writer.into_inner())
+            .map_err(|e| XmlParseError::parse(format!("Record is not valid UTF-8: {}", e)))?;
+        let doc = Document::from_string(&xml)
+            .map_err(|e| XmlParseError::parse(format!("Failed to parse record: {}", e)))?;
+        let root = doc
+            .root
+            .as_ref()
+            .ok_or_else(|| XmlParseError::parse("Record has no root element"))?;
+
+        self.batch.push(self.parser.xml_element_to_instance(root)?);
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for InstanceStream<'_, '_, R> {
+    type Item = ParseResult<Vec<Instance>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch_size = self.options.batch_size.max(1);
+        while !self.done && self.batch.len() < batch_size {
+            if let Err(e) = self.advance() {
+                self.done = true;
+                self.batch.clear();
+                return Some(Err(e));
+            }
+        }
+
+        if self.batch.is_empty() {
+            None
+        } else {
+            Some(Ok(std::mem::take(&mut self.batch)))
+        }
+    }
+}
+
+/// `xmlns` and `xmlns:*` attributes of an element, as raw (key, value) bytes.
+fn namespace_declarations(start: &BytesStart) -> Vec<(Vec<u8>, Vec<u8>)> {
+    start
+        .attributes()
+        .flatten()
+        .filter(|attr| {
+            let key = attr.key.as_ref();
+            key == b"xmlns" || key.starts_with(b"xmlns:")
+        })
+        .map(|attr| (attr.key.as_ref().to_vec(), attr.value.to_vec()))
+        .collect()
+}
+
+fn write(writer: &mut Writer<Vec<u8>>, event: Event<'_>) -> ParseResult<()> {
+    writer
+        .write_event(event)
+        .map_err(|e| XmlParseError::parse(format!("Failed to buffer record: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use terminusdb_schema::{Key, Property, Schema};
+
+    fn item_schema() -> Vec<Schema> {
+        vec![Schema::Class {
+            id: "Item".to_string(),
+            base: None,
+            key: Key::ValueHash,
+            documentation: None,
+            subdocument: false,
+            r#abstract: false,
+            inherits: vec![],
+            unfoldable: false,
+            properties: vec![Property {
+                name: "sku".to_string(),
+                r#type: None,
+                class: "xsd:string".to_string(),
+            }],
+        }]
+    }
+
+    const CATALOG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<catalog xmlns:x="http://example.com/x">
+    <!-- three items -->
+    <item sku="a1">First</item>
+    <item sku="a2"/>
+    <item sku="a3" x:note="last">Third</item>
+</catalog>"#;
+
+    #[test]
+    fn test_stream_batches_root_children() {
+        let schemas = item_schema();
+        let parser = XmlToInstanceParser::new(&schemas);
+
+        let batches: Vec<Vec<Instance>> = parser
+            .stream(CATALOG.as_bytes(), StreamOptions::new().with_batch_size(2))
+            .collect::<ParseResult<_>>()
+            .unwrap();
+
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(batches
+            .iter()
+            .flatten()
+            .all(|inst| inst.has_property("sku")));
+        assert!(batches[1][0].has_property("x:note"));
+    }
+
+    #[test]
+    fn test_stream_with_counts_and_propagates_errors() {
+        let schemas = item_schema();
+        let parser = XmlToInstanceParser::new(&schemas);
+
+        let mut seen = 0;
+        let count = parser
+            .stream_with(CATALOG.as_bytes(), StreamOptions::new(), |batch| {
+                seen += batch.len();
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!((count, seen), (3, 3));
+
+        let truncated = "<catalog><item sku=\"a1\">";
+        assert!(parser
+            .stream_with(truncated.as_bytes(), StreamOptions::new(), |_| Ok(()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_stream_record_elements() {
+        let schemas = item_schema();
+        let parser = XmlToInstanceParser::new(&schemas);
+
+        let xml = "<export><section><item sku=\"b1\"/></section><item sku=\"b2\"/></export>";
+        let options = StreamOptions::new().with_record_elements(["ITEM"]);
+        let instances: Vec<Instance> = parser
+            .stream(xml.as_bytes(), options)
+            .collect::<ParseResult<Vec<_>>>()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        assert_eq!(instances.len(), 2);
+    }
+}
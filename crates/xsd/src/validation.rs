@@ -0,0 +1,1343 @@
+//! Validating XML documents before instance conversion
+//!
+//! [`XmlValidator`] checks a document against the structure extracted from
+//! the XSD ([`XsdSchema`]) and lists every violation it finds, each with the
+//! line and column it occurs at: undeclared and missing elements, elements
+//! occurring too often, missing required attributes, text where only elements
+//! are allowed, and values that do not match their datatype or facets.
+//!
+//! The report is meant for the people who wrote the document, so it is
+//! produced before [`XmlToInstanceParser`](crate::XmlToInstanceParser) turns
+//! the document into instances. Some constraints are not checked here: the
+//! order of child elements, `xs:pattern` facets, identity constraints and
+//! undeclared attributes are left to the full XSD validation, which
+//! [`XsdModel::validate_xml_detailed`](crate::XsdModel::validate_xml_detailed)
+//! falls back to and which reports errors without a position. Elements whose
+//! type cannot be resolved, and the contents of `xs:any` wildcards, are not
+//! checked.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+use serde::Serialize;
+
+use crate::schema_model::{
+    Cardinality, ChildElement, Restriction, SimpleTypeVariety, XsdAttribute, XsdComplexType,
+    XsdElement, XsdSchema, XsdSimpleType,
+};
+
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// How many derivation steps are followed from a type to its bases.
+const MAX_DERIVATION_DEPTH: usize = 32;
+
+/// Values longer than this are shortened in messages.
+const MAX_QUOTED_LENGTH: usize = 40;
+
+/// A line and column in a document, both starting at 1. Columns count
+/// characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The kind of problem a [`Violation`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// The document is not well-formed XML
+    Malformed,
+    /// The root element is not declared in the schema
+    UnknownRoot,
+    /// An element is not allowed where it appears
+    UnexpectedElement,
+    /// A required child element is missing
+    MissingElement,
+    /// An element occurs more often than allowed
+    TooManyElements,
+    /// A required attribute is missing
+    MissingAttribute,
+    /// Text appears in an element that may not contain any
+    UnexpectedText,
+    /// An element's text or an attribute's value does not match its type
+    InvalidValue,
+    /// Reported by the full XSD validation, which has no position
+    Schema,
+}
+
+/// A problem found in an XML document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub message: String,
+    /// Path of the element involved, e.g. `/catalog/product[2]/price`
+    pub path: Option<String>,
+    /// Position of the start tag, attribute or text involved
+    pub position: Option<Position>,
+}
+
+impl Violation {
+    /// A violation reported by the full XSD validation.
+    pub fn schema(message: impl Into<String>) -> Self {
+        Self {
+            kind: ViolationKind::Schema,
+            message: message.into(),
+            path: None,
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{}: {}", position, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// The violations found in an XML document, in document order.
+///
+/// Its `Display` implementation lists them one per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.violations.len() {
+            0 => return writeln!(f, "Valid"),
+            1 => writeln!(f, "1 violation")?,
+            n => writeln!(f, "{} violations", n)?,
+        }
+        for violation in &self.violations {
+            writeln!(f, "  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks XML documents against XSD schemas, reporting violations with their
+/// position.
+///
+/// # Example
+///
+/// ```no_run
+/// use terminusdb_xsd::schema_model::XsdSchema;
+/// use terminusdb_xsd::validation::XmlValidator;
+///
+/// let schema = XsdSchema::from_xsd_file("catalog.xsd", None::<&str>)?;
+/// let schemas = [schema];
+/// let validator = XmlValidator::new(&schemas);
+///
+/// let report = validator.validate(r#"<catalog version="1"><product/></catalog>"#);
+/// for violation in &report.violations {
+///     println!("{}", violation);
+/// }
+/// # Ok::<(), terminusdb_xsd::XsdError>(())
+/// ```
+pub struct XmlValidator<'a> {
+    /// Global elements by local name
+    elements: HashMap<&'a str, &'a XsdElement>,
+    /// Named complex types by local name
+    complex_types: HashMap<&'a str, &'a XsdComplexType>,
+    /// Anonymous complex types by the local name of their element
+    anonymous_types: HashMap<&'a str, &'a XsdComplexType>,
+    /// Simple types by local name
+    simple_types: HashMap<&'a str, &'a XsdSimpleType>,
+    /// Substitution group heads to their direct members
+    substitution_members: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> XmlValidator<'a> {
+    /// Create a validator for documents using the given schemas.
+    ///
+    /// When several schemas declare the same name, the first declaration is
+    /// used.
+    pub fn new(schemas: &'a [XsdSchema]) -> Self {
+        let mut validator = Self {
+            elements: HashMap::new(),
+            complex_types: HashMap::new(),
+            anonymous_types: HashMap::new(),
+            simple_types: HashMap::new(),
+            substitution_members: HashMap::new(),
+        };
+        for schema in schemas {
+            for element in &schema.root_elements {
+                validator
+                    .elements
+                    .entry(local_name(&element.name))
+                    .or_insert(element);
+            }
+            for ct in &schema.complex_types {
+                match (&ct.element_name, ct.is_anonymous) {
+                    (Some(element), true) => validator
+                        .anonymous_types
+                        .entry(local_name(element))
+                        .or_insert(ct),
+                    _ => validator
+                        .complex_types
+                        .entry(local_name(&ct.name))
+                        .or_insert(ct),
+                };
+            }
+            for st in &schema.simple_types {
+                validator
+                    .simple_types
+                    .entry(local_name(&st.name))
+                    .or_insert(st);
+            }
+            for group in &schema.substitution_groups {
+                validator
+                    .substitution_members
+                    .entry(group.head.as_str())
+                    .or_default()
+                    .extend(group.members.iter().map(|m| m.as_str()));
+            }
+        }
+        validator
+    }
+
+    /// Validate an XML document.
+    ///
+    /// Checking stops at the first well-formedness error, which is reported
+    /// as a [`ViolationKind::Malformed`] violation.
+    pub fn validate(&self, xml: &str) -> ValidationReport {
+        let mut walk = Walk {
+            validator: self,
+            locator: Locator::new(xml),
+            stack: Vec::new(),
+            seen_root: false,
+            violations: Vec::new(),
+        };
+        walk.run();
+
+        // Missing children are found at the end tag but reported at the
+        // start tag, after violations inside the element
+        let mut violations = walk.violations;
+        violations.sort_by_key(|v| v.position);
+        ValidationReport { violations }
+    }
+
+    /// The content of a global element.
+    fn element_content(&self, element: &'a XsdElement) -> Content<'a> {
+        let Some(type_info) = &element.type_info else {
+            return Content::Unchecked;
+        };
+        let type_name = type_info
+            .qualified_name
+            .as_deref()
+            .or(type_info.name.as_deref());
+
+        if type_info.is_complex {
+            let complex = match type_name {
+                Some(name) => self.complex_types.get(local_name(name)),
+                None => self.anonymous_types.get(local_name(&element.name)),
+            };
+            match complex {
+                Some(ct) => Content::Complex(self.complex_content(ct)),
+                // Without the type definition, whether text is allowed is unknown
+                None => {
+                    let children = type_info.child_elements.iter().flatten();
+                    Content::Complex(ComplexContent {
+                        attributes: type_info.attributes.iter().flatten().collect(),
+                        children: children.clone().filter(|c| c.name != ANY).collect(),
+                        wildcard: children.clone().any(|c| c.name == ANY),
+                        mixed: true,
+                        simple_content: false,
+                        text_type: None,
+                    })
+                }
+            }
+        } else if type_info.is_simple {
+            type_name.map_or(Content::Unchecked, Content::Simple)
+        } else {
+            Content::Unchecked
+        }
+    }
+
+    /// The content of a child element named `name`, declared by `decl`.
+    fn child_content(&self, decl: &'a ChildElement, name: &str) -> Content<'a> {
+        let type_name = decl.element_type.as_str();
+        if type_name.starts_with("UNRESOLVED:") {
+            return Content::Unchecked;
+        }
+        let local = local_name(type_name);
+
+        // Anonymous types are referenced by their element's name, or by the
+        // base type they extend
+        if let Some(ct) = self.anonymous_types.get(name) {
+            if local == name || ct.base_type.as_deref().map(local_name) == Some(local) {
+                return Content::Complex(self.complex_content(ct));
+            }
+        }
+        if is_builtin(type_name) {
+            return match local {
+                "anyType" => Content::Unchecked,
+                _ => Content::Simple(type_name),
+            };
+        }
+        if let Some(ct) = self.complex_types.get(local) {
+            return Content::Complex(self.complex_content(ct));
+        }
+        if self.simple_types.contains_key(local) {
+            return Content::Simple(type_name);
+        }
+        Content::Unchecked
+    }
+
+    /// The content of a complex type, including what it inherits from its
+    /// base types.
+    fn complex_content(&self, ct: &'a XsdComplexType) -> ComplexContent<'a> {
+        let mut content = ComplexContent {
+            attributes: Vec::new(),
+            children: Vec::new(),
+            wildcard: false,
+            mixed: ct.mixed,
+            simple_content: ct.has_simple_content,
+            text_type: None,
+        };
+
+        let mut current = Some(ct);
+        let mut base = None;
+        for _ in 0..MAX_DERIVATION_DEPTH {
+            let Some(ty) = current else {
+                break;
+            };
+            for attr in ty.attributes.iter().flatten() {
+                if !content
+                    .attributes
+                    .iter()
+                    .any(|a| a.name == attr.name && a.namespace == attr.namespace)
+                {
+                    content.attributes.push(attr);
+                }
+            }
+            for child in ty.child_elements.iter().flatten() {
+                if child.name == ANY {
+                    content.wildcard = true;
+                } else if !content.children.iter().any(|c| c.name == child.name) {
+                    content.children.push(child);
+                }
+            }
+            base = ty.base_type.as_deref();
+            current = base.and_then(|b| self.complex_types.get(local_name(b)).copied());
+        }
+
+        // The text of simple content has the type of the first base that is
+        // not a complex type
+        if content.simple_content && current.is_none() {
+            content.text_type = base;
+        }
+        content
+    }
+
+    /// Whether `member` may appear in place of `head`.
+    fn substitutes(&self, member: &str, head: &str) -> bool {
+        let mut pending = vec![head];
+        let mut seen = HashSet::new();
+        while let Some(head) = pending.pop() {
+            if !seen.insert(head) {
+                continue;
+            }
+            for &candidate in self.substitution_members.get(head).into_iter().flatten() {
+                if candidate == member {
+                    return true;
+                }
+                pending.push(candidate);
+            }
+        }
+        false
+    }
+
+    /// Check a value against a simple type, returning what was expected when
+    /// it does not match.
+    fn check_value(&self, type_name: &str, value: &str, depth: usize) -> Option<String> {
+        if depth > MAX_DERIVATION_DEPTH {
+            return None;
+        }
+        let local = local_name(type_name);
+        if is_builtin(type_name) {
+            return check_builtin(local, value);
+        }
+        let st = self.simple_types.get(local)?;
+
+        match st.variety {
+            Some(SimpleTypeVariety::List) => {
+                let item_type = st.item_type.as_deref()?;
+                value
+                    .split_whitespace()
+                    .find_map(|item| self.check_value(item_type, item, depth + 1))
+            }
+            Some(SimpleTypeVariety::Union) => {
+                let members = st.member_types.as_deref().unwrap_or_default();
+                if members.is_empty()
+                    || members
+                        .iter()
+                        .any(|m| self.check_value(m, value, depth + 1).is_none())
+                {
+                    return None;
+                }
+                let names: Vec<&str> = members.iter().map(|m| local_name(m)).collect();
+                Some(format!("a value of type {}", names.join(" or ")))
+            }
+            _ => {
+                if let Some(base) = &st.base_type {
+                    if let Some(expected) = self.check_value(base, value, depth + 1) {
+                        return Some(expected);
+                    }
+                }
+                st.restrictions
+                    .iter()
+                    .flatten()
+                    .find_map(|r| check_facet(r, value))
+            }
+        }
+    }
+}
+
+/// Name of the child standing for an `xs:any` wildcard.
+const ANY: &str = "##any";
+
+/// What an element may contain.
+enum Content<'a> {
+    Complex(ComplexContent<'a>),
+    /// Text of the named simple type
+    Simple(&'a str),
+    /// Not checked: unresolved types, `xs:anyType` and wildcard contents
+    Unchecked,
+}
+
+struct ComplexContent<'a> {
+    attributes: Vec<&'a XsdAttribute>,
+    children: Vec<&'a ChildElement>,
+    /// Whether an `xs:any` wildcard allows undeclared children
+    wildcard: bool,
+    mixed: bool,
+    simple_content: bool,
+    /// Type of the text of simple content, when known
+    text_type: Option<&'a str>,
+}
+
+/// An element being validated.
+struct Frame<'a> {
+    name: String,
+    path: String,
+    /// Byte offset of the start tag
+    start: usize,
+    content: Content<'a>,
+    /// Set by `xsi:nil="true"`: the element must be empty
+    nil: bool,
+    text: String,
+    /// Byte offset of the first non-whitespace text
+    text_start: Option<usize>,
+    /// Occurrences of each declared child, substitution group members
+    /// counting for their head
+    occurrences: HashMap<&'a str, u32>,
+    /// Occurrences of each child element name, for paths
+    indexes: HashMap<String, usize>,
+}
+
+/// Maps byte offsets in a document to lines and columns.
+struct Locator<'x> {
+    xml: &'x str,
+    line_starts: Vec<usize>,
+}
+
+impl<'x> Locator<'x> {
+    fn new(xml: &'x str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(xml.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { xml, line_starts }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.xml.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let start = self.line_starts[line - 1];
+        let column = String::from_utf8_lossy(&self.xml.as_bytes()[start..offset])
+            .chars()
+            .count()
+            + 1;
+        Position { line, column }
+    }
+}
+
+/// State of a validation pass over one document.
+struct Walk<'v, 'a, 'x> {
+    validator: &'v XmlValidator<'a>,
+    locator: Locator<'x>,
+    stack: Vec<Frame<'a>>,
+    seen_root: bool,
+    violations: Vec<Violation>,
+}
+
+impl<'a> Walk<'_, 'a, '_> {
+    fn run(&mut self) {
+        let mut reader = NsReader::from_str(self.locator.xml);
+        reader.config_mut().expand_empty_elements = true;
+
+        loop {
+            let offset = reader.buffer_position() as usize;
+            let event = match reader.read_event() {
+                Ok(event) => event,
+                Err(e) => {
+                    let offset = reader.error_position() as usize;
+                    self.report(ViolationKind::Malformed, e.to_string(), None, offset);
+                    return;
+                }
+            };
+            match event {
+                Event::Start(start) => self.start(&reader, &start, offset),
+                Event::End(_) => self.end(),
+                Event::Text(text) => {
+                    let leading = text.iter().take_while(|b| b.is_ascii_whitespace()).count();
+                    if let Ok(decoded) = text.decode() {
+                        self.push_text(&decoded, offset + leading, leading < text.len());
+                    }
+                }
+                Event::CData(cdata) => {
+                    if let Ok(decoded) = cdata.decode() {
+                        self.push_text(&decoded, offset + "<![CDATA[".len(), true);
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    let resolved = match reference.resolve_char_ref() {
+                        Ok(Some(c)) => Some(c.to_string()),
+                        _ => reference.decode().ok().map(|name| {
+                            match quick_xml::escape::resolve_predefined_entity(&name) {
+                                Some(value) => value.to_string(),
+                                None => format!("&{};", name),
+                            }
+                        }),
+                    };
+                    if let Some(resolved) = resolved {
+                        self.push_text(&resolved, offset, true);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if let Some(frame) = self.stack.first() {
+            let (name, path, start) = (frame.name.clone(), frame.path.clone(), frame.start);
+            let message = format!("Element <{}> is not closed", name);
+            self.report(ViolationKind::Malformed, message, Some(path), start);
+        } else if !self.seen_root {
+            let message = "Document has no root element".to_string();
+            self.report(ViolationKind::Malformed, message, None, 0);
+        }
+    }
+
+    fn start(&mut self, reader: &NsReader<&[u8]>, start: &BytesStart, offset: usize) {
+        let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+        let validator = self.validator;
+
+        let (path, content, violation) = match self.stack.last_mut() {
+            None if self.seen_root => (
+                format!("/{}", name),
+                Content::Unchecked,
+                Some((
+                    ViolationKind::UnexpectedElement,
+                    format!("Element <{}> follows the root element", name),
+                )),
+            ),
+            None => {
+                self.seen_root = true;
+                match validator.elements.get(name.as_str()) {
+                    Some(element) => (
+                        format!("/{}", name),
+                        validator.element_content(element),
+                        None,
+                    ),
+                    None => (
+                        format!("/{}", name),
+                        Content::Unchecked,
+                        Some((
+                            ViolationKind::UnknownRoot,
+                            format!("Element <{}> is not declared in the schema", name),
+                        )),
+                    ),
+                }
+            }
+            Some(parent) => {
+                let index = parent.indexes.entry(name.clone()).or_insert(0);
+                *index += 1;
+                let path = format!("{}/{}[{}]", parent.path, name, index);
+                let (content, violation) = Self::child(validator, parent, &name);
+                (path, content, violation)
+            }
+        };
+        if let Some((kind, message)) = violation {
+            self.report(kind, message, Some(path.clone()), offset);
+        }
+
+        let nil = self.check_attributes(reader, start, &name, &path, &content, offset);
+        self.stack.push(Frame {
+            name,
+            path,
+            start: offset,
+            content,
+            nil,
+            text: String::new(),
+            text_start: None,
+            occurrences: HashMap::new(),
+            indexes: HashMap::new(),
+        });
+    }
+
+    /// The content of a child element of `parent`, and the violation its
+    /// appearance is, if any.
+    fn child(
+        validator: &XmlValidator<'a>,
+        parent: &mut Frame<'a>,
+        name: &str,
+    ) -> (Content<'a>, Option<(ViolationKind, String)>) {
+        if parent.nil {
+            let message = format!(
+                "Element <{}> is not allowed in <{}>, which is nil",
+                name, parent.name
+            );
+            return (
+                Content::Unchecked,
+                Some((ViolationKind::UnexpectedElement, message)),
+            );
+        }
+        let complex = match &parent.content {
+            Content::Unchecked => return (Content::Unchecked, None),
+            Content::Complex(complex) if !complex.simple_content => complex,
+            _ => {
+                let message = format!(
+                    "Element <{}> is not allowed in <{}>, which only contains text",
+                    name, parent.name
+                );
+                return (
+                    Content::Unchecked,
+                    Some((ViolationKind::UnexpectedElement, message)),
+                );
+            }
+        };
+
+        let declared = complex
+            .children
+            .iter()
+            .find(|decl| local_name(&decl.name) == name);
+        let (decl, content) = match declared {
+            Some(&decl) => (decl, validator.child_content(decl, name)),
+            None => {
+                let head = complex
+                    .children
+                    .iter()
+                    .find(|decl| validator.substitutes(name, local_name(&decl.name)));
+                match head {
+                    Some(&decl) => {
+                        let content = validator
+                            .elements
+                            .get(name)
+                            .map_or(Content::Unchecked, |e| validator.element_content(e));
+                        (decl, content)
+                    }
+                    None if complex.wildcard => return (Content::Unchecked, None),
+                    None => {
+                        let message =
+                            format!("Element <{}> is not allowed in <{}>", name, parent.name);
+                        return (
+                            Content::Unchecked,
+                            Some((ViolationKind::UnexpectedElement, message)),
+                        );
+                    }
+                }
+            }
+        };
+
+        let count = parent.occurrences.entry(decl.name.as_str()).or_insert(0);
+        *count += 1;
+        let max = match decl.max_occurs {
+            Some(Cardinality::Unbounded) => None,
+            Some(Cardinality::Number(n)) => Some(n),
+            None => Some(1),
+        };
+        let violation = match max {
+            Some(max) if *count > max => Some((
+                ViolationKind::TooManyElements,
+                format!(
+                    "Element <{}> occurs more than {} in <{}>",
+                    name,
+                    times(max),
+                    parent.name
+                ),
+            )),
+            _ => None,
+        };
+        (content, violation)
+    }
+
+    /// Check the attributes of a start tag, returning whether the element is
+    /// nil.
+    fn check_attributes(
+        &mut self,
+        reader: &NsReader<&[u8]>,
+        start: &BytesStart,
+        name: &str,
+        path: &str,
+        content: &Content<'a>,
+        offset: usize,
+    ) -> bool {
+        let mut nil = false;
+        let mut present = Vec::new();
+
+        for attr in start.attributes() {
+            let attr = match attr {
+                Ok(attr) => attr,
+                Err(e) => {
+                    let message = format!("Invalid attribute in <{}>: {}", name, e);
+                    self.report(ViolationKind::Malformed, message, Some(path.into()), offset);
+                    break;
+                }
+            };
+            if attr.key.as_namespace_binding().is_some() {
+                continue;
+            }
+            let (namespace, local) = reader.resolve_attribute(attr.key);
+            let namespace = match namespace {
+                ResolveResult::Bound(ns) => Some(String::from_utf8_lossy(ns.as_ref()).into_owned()),
+                _ => None,
+            };
+            let local = String::from_utf8_lossy(local.as_ref()).into_owned();
+            let Ok(value) = attr.decode_and_unescape_value(reader.decoder()) else {
+                continue;
+            };
+
+            if namespace.as_deref() == Some(XSI_NAMESPACE) {
+                if local == "nil" && matches!(value.trim(), "true" | "1") {
+                    nil = true;
+                }
+                continue;
+            }
+            let Content::Complex(complex) = content else {
+                continue;
+            };
+            let Some(decl) = complex
+                .attributes
+                .iter()
+                .find(|a| a.name == local && namespace_matches(&a.namespace, &namespace))
+            else {
+                continue;
+            };
+            present.push(*decl);
+
+            if let Some(expected) = self.validator.check_value(&decl.attr_type, &value, 0) {
+                let key = attr.key.as_ref();
+                let at = offset + attribute_offset(start, key).map_or(0, |i| i + 1);
+                let message = format!(
+                    "Invalid value {} for attribute {} of <{}>: expected {}",
+                    quoted(&value),
+                    String::from_utf8_lossy(key),
+                    name,
+                    expected
+                );
+                self.report(ViolationKind::InvalidValue, message, Some(path.into()), at);
+            }
+        }
+
+        if let Content::Complex(complex) = content {
+            for decl in &complex.attributes {
+                if decl.is_required() && !present.iter().any(|p| std::ptr::eq(*p, *decl)) {
+                    let message = format!("Missing required attribute {} on <{}>", decl.name, name);
+                    self.report(
+                        ViolationKind::MissingAttribute,
+                        message,
+                        Some(path.into()),
+                        offset,
+                    );
+                }
+            }
+        }
+        nil
+    }
+
+    fn push_text(&mut self, text: &str, offset: usize, significant: bool) {
+        let Some(frame) = self.stack.last_mut() else {
+            return;
+        };
+        if significant && frame.text_start.is_none() {
+            frame.text_start = Some(offset);
+        }
+        frame.text.push_str(text);
+    }
+
+    fn end(&mut self) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        if frame.nil {
+            if let Some(text_start) = frame.text_start {
+                let message = format!("Text is not allowed in <{}>, which is nil", frame.name);
+                self.report(
+                    ViolationKind::UnexpectedText,
+                    message,
+                    Some(frame.path.clone()),
+                    text_start,
+                );
+            }
+            return;
+        }
+
+        match &frame.content {
+            Content::Complex(complex) => {
+                self.check_children(&frame, complex);
+                if complex.simple_content {
+                    if let Some(text_type) = complex.text_type {
+                        self.check_text(&frame, text_type);
+                    }
+                } else if !complex.mixed {
+                    if let Some(text_start) = frame.text_start {
+                        let message = format!("Text is not allowed in <{}>", frame.name);
+                        self.report(
+                            ViolationKind::UnexpectedText,
+                            message,
+                            Some(frame.path.clone()),
+                            text_start,
+                        );
+                    }
+                }
+            }
+            Content::Simple(type_name) => self.check_text(&frame, type_name),
+            Content::Unchecked => {}
+        }
+    }
+
+    /// Report required children that did not occur.
+    ///
+    /// The members of an `xs:choice` are reported together: the choice is
+    /// satisfied by any one of them.
+    fn check_children(&mut self, frame: &Frame<'a>, complex: &ComplexContent<'a>) {
+        let occurs = |decl: &ChildElement| frame.occurrences.contains_key(decl.name.as_str());
+        let mut choices: BTreeMap<usize, Vec<&ChildElement>> = BTreeMap::new();
+
+        for decl in &complex.children {
+            match decl.choice {
+                Some(choice) => choices.entry(choice).or_default().push(decl),
+                None if decl.is_required() && !occurs(decl) => {
+                    let message = format!(
+                        "Missing required element <{}> in <{}>",
+                        local_name(&decl.name),
+                        frame.name
+                    );
+                    self.report(
+                        ViolationKind::MissingElement,
+                        message,
+                        Some(frame.path.clone()),
+                        frame.start,
+                    );
+                }
+                None => {}
+            }
+        }
+
+        for members in choices.values() {
+            // A choice with an optional member may be left out
+            if members.iter().all(|decl| decl.is_required()) && !members.iter().any(|d| occurs(d)) {
+                let names: Vec<String> = members
+                    .iter()
+                    .map(|decl| format!("<{}>", local_name(&decl.name)))
+                    .collect();
+                let message = format!("Missing one of {} in <{}>", names.join(", "), frame.name);
+                self.report(
+                    ViolationKind::MissingElement,
+                    message,
+                    Some(frame.path.clone()),
+                    frame.start,
+                );
+            }
+        }
+    }
+
+    fn check_text(&mut self, frame: &Frame<'a>, type_name: &str) {
+        if let Some(expected) = self.validator.check_value(type_name, &frame.text, 0) {
+            let message = format!(
+                "Invalid value {} for <{}>: expected {}",
+                quoted(&frame.text),
+                frame.name,
+                expected
+            );
+            self.report(
+                ViolationKind::InvalidValue,
+                message,
+                Some(frame.path.clone()),
+                frame.text_start.unwrap_or(frame.start),
+            );
+        }
+    }
+
+    fn report(
+        &mut self,
+        kind: ViolationKind,
+        message: String,
+        path: Option<String>,
+        offset: usize,
+    ) {
+        self.violations.push(Violation {
+            kind,
+            message,
+            path,
+            position: Some(self.locator.position(offset)),
+        });
+    }
+}
+
+/// Local part of a type or element name, in Clark notation
+/// (`{namespace}local`) or prefixed (`xs:local`).
+fn local_name(name: &str) -> &str {
+    name.rsplit(['}', ':']).next().unwrap_or(name)
+}
+
+/// Whether a type name refers to an XSD built-in type.
+fn is_builtin(type_name: &str) -> bool {
+    type_name.strip_prefix('{').is_some_and(|rest| {
+        rest.starts_with(XSD_NAMESPACE) && rest[XSD_NAMESPACE.len()..].starts_with('}')
+    }) || type_name.starts_with("xs:")
+        || type_name.starts_with("xsd:")
+}
+
+/// Whether an attribute in `actual` namespace matches a declaration in
+/// `declared` namespace. Unqualified attributes match by local name.
+fn namespace_matches(declared: &Option<String>, actual: &Option<String>) -> bool {
+    match (declared, actual) {
+        (Some(declared), Some(actual)) => declared == actual,
+        _ => true,
+    }
+}
+
+/// Byte offset of the attribute named `key` within a start tag, counted from
+/// after its `<`.
+fn attribute_offset(start: &BytesStart, key: &[u8]) -> Option<usize> {
+    let tag: &[u8] = start;
+    let mut i = start.name().as_ref().len();
+    while i < tag.len() {
+        while tag.get(i).is_some_and(|b| b.is_ascii_whitespace()) {
+            i += 1;
+        }
+        let name_start = i;
+        while tag
+            .get(i)
+            .is_some_and(|&b| b != b'=' && !b.is_ascii_whitespace())
+        {
+            i += 1;
+        }
+        if &tag[name_start..i] == key {
+            return Some(name_start);
+        }
+        // Skip to the end of the quoted value
+        let quote = tag[i..].iter().position(|&b| b == b'"' || b == b'\'')?;
+        i += quote;
+        let close = tag[i + 1..].iter().position(|&b| b == tag[i])?;
+        i += close + 2;
+    }
+    None
+}
+
+/// A value in quotes for messages, shortened when long.
+fn quoted(value: &str) -> String {
+    let value = value.trim();
+    if value.chars().count() > MAX_QUOTED_LENGTH {
+        let short: String = value.chars().take(MAX_QUOTED_LENGTH).collect();
+        format!("'{}…'", short)
+    } else {
+        format!("'{}'", value)
+    }
+}
+
+fn times(n: u32) -> String {
+    match n {
+        1 => "once".to_string(),
+        n => format!("{} times", n),
+    }
+}
+
+/// Check a value against a facet, returning what was expected when it does
+/// not match. Patterns are not checked.
+fn check_facet(restriction: &Restriction, value: &str) -> Option<String> {
+    let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    let length = value.chars().count() as u32;
+    let number = collapsed.parse::<f64>().ok();
+    let bound = |limit: &str| limit.trim().parse::<f64>().ok();
+
+    match restriction {
+        Restriction::Enumeration { values } => {
+            (!values.iter().any(|v| v == value || *v == collapsed))
+                .then(|| format!("one of {}", values.join(", ")))
+        }
+        Restriction::Length { value } => {
+            (length != *value).then(|| format!("exactly {} characters", value))
+        }
+        Restriction::MinLength { value } => {
+            (length < *value).then(|| format!("at least {} characters", value))
+        }
+        Restriction::MaxLength { value } => {
+            (length > *value).then(|| format!("at most {} characters", value))
+        }
+        Restriction::MinInclusive { value: limit } => match (number, bound(limit)) {
+            (Some(n), Some(b)) if n < b => Some(format!("a value of at least {}", limit)),
+            _ => None,
+        },
+        Restriction::MaxInclusive { value: limit } => match (number, bound(limit)) {
+            (Some(n), Some(b)) if n > b => Some(format!("a value of at most {}", limit)),
+            _ => None,
+        },
+        Restriction::MinExclusive { value: limit } => match (number, bound(limit)) {
+            (Some(n), Some(b)) if n <= b => Some(format!("a value greater than {}", limit)),
+            _ => None,
+        },
+        Restriction::MaxExclusive { value: limit } => match (number, bound(limit)) {
+            (Some(n), Some(b)) if n >= b => Some(format!("a value less than {}", limit)),
+            _ => None,
+        },
+        Restriction::Pattern { .. } => None,
+    }
+}
+
+/// Check a value against an XSD built-in type, returning what was expected
+/// when it does not match. Types without a lexical check accept any value.
+fn check_builtin(type_name: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    let valid = match type_name {
+        "boolean" => matches!(value, "true" | "false" | "1" | "0"),
+        "decimal" => is_decimal(value),
+        "float" | "double" => is_float(value),
+        "integer" => return check_integer(value, None, None),
+        "nonNegativeInteger" => return check_integer(value, Some(0), None),
+        "positiveInteger" => return check_integer(value, Some(1), None),
+        "nonPositiveInteger" => return check_integer(value, None, Some(0)),
+        "negativeInteger" => return check_integer(value, None, Some(-1)),
+        "long" => return check_integer(value, Some(i64::MIN.into()), Some(i64::MAX.into())),
+        "int" => return check_integer(value, Some(i32::MIN.into()), Some(i32::MAX.into())),
+        "short" => return check_integer(value, Some(i16::MIN.into()), Some(i16::MAX.into())),
+        "byte" => return check_integer(value, Some(i8::MIN.into()), Some(i8::MAX.into())),
+        "unsignedLong" => return check_integer(value, Some(0), Some(u64::MAX.into())),
+        "unsignedInt" => return check_integer(value, Some(0), Some(u32::MAX.into())),
+        "unsignedShort" => return check_integer(value, Some(0), Some(u16::MAX.into())),
+        "unsignedByte" => return check_integer(value, Some(0), Some(u8::MAX.into())),
+        "date" => strip_timezone(value).is_some_and(is_date),
+        "time" => strip_timezone(value).is_some_and(is_time),
+        "dateTime" => strip_timezone(value)
+            .and_then(|v| v.split_once('T'))
+            .is_some_and(|(date, time)| is_date(date) && is_time(time)),
+        "gYear" => strip_timezone(value).is_some_and(is_year),
+        _ => true,
+    };
+    if valid {
+        return None;
+    }
+    let expected = match type_name {
+        "boolean" => "true, false, 1 or 0",
+        "decimal" => "a decimal number",
+        "float" | "double" => "a number",
+        "date" => "a date (YYYY-MM-DD)",
+        "time" => "a time (hh:mm:ss)",
+        "dateTime" => "a date and time (YYYY-MM-DDThh:mm:ss)",
+        "gYear" => "a year (YYYY)",
+        _ => "a valid value",
+    };
+    Some(expected.to_string())
+}
+
+fn check_integer(value: &str, min: Option<i128>, max: Option<i128>) -> Option<String> {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let lexical = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+    let in_range = match value.parse::<i128>() {
+        Ok(n) => min.is_none_or(|min| n >= min) && max.is_none_or(|max| n <= max),
+        // Too large for i128: only unbounded integers can be that large
+        Err(_) => min.is_none() && max.is_none(),
+    };
+    if lexical && in_range {
+        return None;
+    }
+    Some(match (min, max) {
+        (None, None) => "an integer".to_string(),
+        (Some(0), None) => "a non-negative integer".to_string(),
+        (Some(1), None) => "a positive integer".to_string(),
+        (None, Some(0)) => "a non-positive integer".to_string(),
+        (None, Some(_)) => "a negative integer".to_string(),
+        (Some(min), Some(max)) => format!("an integer from {} to {}", min, max),
+        (Some(min), None) => format!("an integer of at least {}", min),
+    })
+}
+
+fn is_digits(value: &str, len: usize) -> bool {
+    value.len() == len && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_decimal(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    !(whole.is_empty() && fraction.is_empty())
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_float(value: &str) -> bool {
+    if matches!(value, "INF" | "+INF" | "-INF" | "NaN") {
+        return true;
+    }
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    is_decimal(mantissa)
+        && exponent.is_none_or(|e| {
+            let digits = e.strip_prefix(['+', '-']).unwrap_or(e);
+            !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+        })
+}
+
+/// A date or time without its timezone (`Z` or `±hh:mm`), if it is ASCII.
+fn strip_timezone(value: &str) -> Option<&str> {
+    if !value.is_ascii() {
+        return None;
+    }
+    if let Some(rest) = value.strip_suffix('Z') {
+        return Some(rest);
+    }
+    if value.len() > 6 {
+        let (rest, zone) = value.split_at(value.len() - 6);
+        let bytes = zone.as_bytes();
+        if matches!(bytes[0], b'+' | b'-')
+            && bytes[3] == b':'
+            && is_digits(&zone[1..3], 2)
+            && is_digits(&zone[4..6], 2)
+        {
+            return Some(rest);
+        }
+    }
+    Some(value)
+}
+
+fn is_year(value: &str) -> bool {
+    let year = value.strip_prefix('-').unwrap_or(value);
+    year.len() >= 4 && year.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_date(value: &str) -> bool {
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    let mut parts = unsigned.splitn(3, '-');
+    let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    if !is_year(year) || !is_digits(month, 2) || !is_digits(day, 2) {
+        return false;
+    }
+    let year: i64 = year.parse().unwrap_or(0);
+    let month: u32 = month.parse().unwrap_or(0);
+    let day: u32 = day.parse().unwrap_or(0);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+fn is_time(value: &str) -> bool {
+    let (hms, fraction) = value.split_once('.').unwrap_or((value, "0"));
+    let mut parts = hms.split(':');
+    let (Some(h), Some(m), Some(s), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    if ![h, m, s].iter().all(|part| is_digits(part, 2))
+        || fraction.is_empty()
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return false;
+    }
+    let (h, m, s): (u32, u32, u32) = (
+        h.parse().unwrap_or(99),
+        m.parse().unwrap_or(99),
+        s.parse().unwrap_or(99),
+    );
+    (h < 24 && m < 60 && s < 60) || (h == 24 && m == 0 && s == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A schema with a `supplier` element of a named type and a `payment`
+    /// element with an anonymous type containing a choice.
+    fn schema() -> XsdSchema {
+        let json = r#"{
+            "target_namespace": null,
+            "schema_location": null,
+            "element_form_default": null,
+            "root_elements": [
+                {"name": "supplier", "qualified_name": "supplier", "min_occurs": 1,
+                 "max_occurs": 1, "nillable": false, "default": null,
+                 "type": {"name": "supplierType", "qualified_name": "supplierType",
+                          "category": "XsdComplexType", "is_complex": true, "is_simple": false,
+                          "content_model": null, "attributes": null, "child_elements": null}},
+                {"name": "payment", "qualified_name": "payment", "min_occurs": 1,
+                 "max_occurs": 1, "nillable": false, "default": null,
+                 "type": {"name": null, "qualified_name": null,
+                          "category": "XsdComplexType", "is_complex": true, "is_simple": false,
+                          "content_model": null, "attributes": null, "child_elements": null}}
+            ],
+            "complex_types": [
+                {"name": "supplierType", "qualified_name": "supplierType",
+                 "category": "XsdComplexType", "is_complex": true, "is_simple": false,
+                 "content_model": "Sequence", "element_name": null, "base_type": null,
+                 "attributes": [
+                    {"name": "id", "type": "xs:string", "use": "required", "default": null},
+                    {"name": "since", "type": "{http://www.w3.org/2001/XMLSchema}date",
+                     "use": "optional", "default": null}
+                 ],
+                 "child_elements": [
+                    {"name": "phone", "type": "xs:string", "min_occurs": 1, "max_occurs": 2},
+                    {"name": "rating", "type": "{http://www.w3.org/2001/XMLSchema}int",
+                     "min_occurs": 1, "max_occurs": 1},
+                    {"name": "status", "type": "statusType", "min_occurs": 0, "max_occurs": 1}
+                 ]},
+                {"name": "payment", "qualified_name": "payment", "is_anonymous": true,
+                 "element_name": "payment", "category": "XsdComplexType", "is_complex": true,
+                 "is_simple": false, "content_model": "Choice", "base_type": null,
+                 "attributes": [],
+                 "child_elements": [
+                    {"name": "card", "type": "xs:string", "min_occurs": 1, "max_occurs": 1,
+                     "choice": 0},
+                    {"name": "transfer", "type": "xs:string", "min_occurs": 1, "max_occurs": 1,
+                     "choice": 0}
+                 ]}
+            ],
+            "simple_types": [
+                {"name": "statusType", "qualified_name": "statusType", "category": "XsdSimpleType",
+                 "base_type": "xs:string", "variety": "Atomic", "item_type": null,
+                 "member_types": null,
+                 "restrictions": [{"kind": "Enumeration", "values": ["active", "inactive"]}]}
+            ]
+        }"#;
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn validate(xml: &str) -> ValidationReport {
+        let schemas = [schema()];
+        XmlValidator::new(&schemas).validate(xml)
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let report = validate(
+            r#"<supplier id="s1" since="2024-02-29"><phone>555</phone><rating>4</rating><status>active</status></supplier>"#,
+        );
+        assert!(report.is_valid(), "{}", report);
+    }
+
+    #[test]
+    fn test_violations_have_positions() {
+        let xml = "<supplier since=\"2024-02-30\">\n  <phone>1</phone>\n  <phone>2</phone>\n  <phone>3</phone>\n  <rating> four </rating>\n  <status>gone</status>\n  <fax/>\n</supplier>";
+        let report = validate(xml);
+        let found: Vec<(ViolationKind, String)> = report
+            .violations
+            .iter()
+            .map(|v| (v.kind, v.position.unwrap().to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (ViolationKind::MissingAttribute, "1:1".to_string()),
+                (ViolationKind::InvalidValue, "1:11".to_string()),
+                (ViolationKind::TooManyElements, "4:3".to_string()),
+                (ViolationKind::InvalidValue, "5:12".to_string()),
+                (ViolationKind::InvalidValue, "6:11".to_string()),
+                (ViolationKind::UnexpectedElement, "7:3".to_string()),
+            ],
+            "{}",
+            report
+        );
+        assert_eq!(
+            report.violations[3].message,
+            "Invalid value 'four' for <rating>: expected an integer from -2147483648 to 2147483647"
+        );
+        assert_eq!(
+            report.violations[4].path.as_deref(),
+            Some("/supplier/status[1]")
+        );
+    }
+
+    #[test]
+    fn test_missing_elements() {
+        let report = validate(r#"<supplier id="s1"><status>active</status></supplier>"#);
+        let messages: Vec<&str> = report
+            .violations
+            .iter()
+            .map(|v| v.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing required element <phone> in <supplier>",
+                "Missing required element <rating> in <supplier>",
+            ]
+        );
+
+        let report = validate("<payment/>");
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(
+            report.violations[0].message,
+            "Missing one of <card>, <transfer> in <payment>"
+        );
+        assert!(validate("<payment><transfer>x</transfer></payment>").is_valid());
+    }
+
+    #[test]
+    fn test_malformed_and_unknown_root() {
+        let report = validate("<supplier id=\"s1\">\n  <phone>1</rating>");
+        let malformed = report
+            .violations
+            .iter()
+            .find(|v| v.kind == ViolationKind::Malformed)
+            .unwrap();
+        assert_eq!(malformed.position.unwrap().line, 2);
+
+        let report = validate("<invoice/>");
+        assert_eq!(report.violations[0].kind, ViolationKind::UnknownRoot);
+    }
+
+    #[test]
+    fn test_builtin_types() {
+        assert!(check_builtin("decimal", " -1.50 ").is_none());
+        assert!(check_builtin("decimal", "1e3").is_some());
+        assert!(check_builtin("double", "1.5E-3").is_none());
+        assert!(check_builtin("boolean", "yes").is_some());
+        assert!(check_builtin("unsignedByte", "256").is_some());
+        assert!(check_builtin("integer", "123456789012345678901234567890123456789012").is_none());
+        assert!(check_builtin("dateTime", "2024-01-15T10:30:00.5+02:00").is_none());
+        assert!(check_builtin("date", "2023-02-29").is_some());
+        assert!(check_builtin("time", "25:00:00").is_some());
+        assert!(check_builtin("string", "anything").is_none());
+    }
+}
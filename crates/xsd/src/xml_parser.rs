@@ -1081,18 +1081,11 @@ impl<'a> XmlToInstanceParser<'a> {
         element_name: &str,
         element_instance: Instance,
     ) -> RelationValue {
-        let mut child = Instance {
-            schema: union_schema.clone(),
-            id: None,
-            capture: false,
-            ref_props: false,
-            properties: BTreeMap::new(),
-        };
-        child.properties.insert(
-            element_name.to_string(),
+        self.make_union_child(
+            union_schema,
+            element_name,
             InstanceProperty::Relation(RelationValue::One(element_instance)),
-        );
-        RelationValue::One(child)
+        )
     }
 
     /// Create a TaggedUnion child for a simple-type element (xs:string, etc.).
@@ -1106,19 +1099,94 @@ impl<'a> XmlToInstanceParser<'a> {
         element_name: &str,
         text: &str,
     ) -> RelationValue {
-        let mut child = Instance {
-            schema: union_schema.clone(),
-            id: None,
-            capture: false,
-            ref_props: false,
-            properties: BTreeMap::new(),
-        };
-        child.properties.insert(
-            element_name.to_string(),
+        self.make_union_child(
+            union_schema,
+            element_name,
             InstanceProperty::Primitive(PrimitiveValue::String(text.to_string())),
-        );
+        )
+    }
+
+    /// Create a TaggedUnion child holding `value` under the variant for
+    /// `element_name`.
+    ///
+    /// Elements of an `xs:choice` and members of a substitution group are
+    /// variants of a nested union (`{Type}Choice`, `{Head}SubstitutionGroup`),
+    /// so the value is wrapped in each union on the way down. For example,
+    /// `<article>` in a document choice becomes
+    /// `{ "@type": "DocumentTypeChild", "choice": { "@type": "DocumentTypeChoice", "article": ... } }`.
+    fn make_union_child(
+        &self,
+        union_schema: &Schema,
+        element_name: &str,
+        value: InstanceProperty,
+    ) -> RelationValue {
+        let mut path = self
+            .variant_path(union_schema, element_name, MAX_UNION_NESTING)
+            .unwrap_or_else(|| vec![(union_schema, element_name.to_string())]);
+
+        let (schema, variant) = path.pop().expect("variant path is never empty");
+        let innermost = union_instance(schema, variant, value);
+        let child = path
+            .into_iter()
+            .rev()
+            .fold(innermost, |inner, (schema, variant)| {
+                union_instance(
+                    schema,
+                    variant,
+                    InstanceProperty::Relation(RelationValue::One(inner)),
+                )
+            });
         RelationValue::One(child)
     }
+
+    /// Find the chain of (union, variant) pairs leading from `union_schema` to
+    /// the variant named `element_name`, searching nested TaggedUnions up to
+    /// `depth` levels deep. Direct variants win over nested ones.
+    fn variant_path<'s>(
+        &'s self,
+        union_schema: &'s Schema,
+        element_name: &str,
+        depth: usize,
+    ) -> Option<Vec<(&'s Schema, String)>> {
+        let Schema::TaggedUnion { properties, .. } = union_schema else {
+            return None;
+        };
+
+        if properties.iter().any(|p| p.name == element_name) {
+            return Some(vec![(union_schema, element_name.to_string())]);
+        }
+        if depth == 0 {
+            return None;
+        }
+
+        for property in properties {
+            let Some(nested) = self.schemas.get(property.class.as_str()).copied() else {
+                continue;
+            };
+            if let Some(mut path) = self.variant_path(nested, element_name, depth - 1) {
+                path.insert(0, (union_schema, property.name.clone()));
+                return Some(path);
+            }
+        }
+        None
+    }
+}
+
+/// How deep [`XmlToInstanceParser::variant_path`] looks through nested
+/// TaggedUnions (a substitution group inside a choice is two levels).
+const MAX_UNION_NESTING: usize = 3;
+
+/// A TaggedUnion instance with a single variant set
+fn union_instance(union_schema: &Schema, variant: String, value: InstanceProperty) -> Instance {
+    let mut instance = Instance {
+        schema: union_schema.clone(),
+        id: None,
+        capture: false,
+        ref_props: false,
+        properties: BTreeMap::new(),
+    };
+    instance.properties.insert(variant, value);
+    instance
 }
 
 /// Convert a string to PascalCase
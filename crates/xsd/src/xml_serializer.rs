@@ -0,0 +1,494 @@
+//! TerminusDB Instance to XML serializer
+//!
+//! The inverse of [`XmlToInstanceParser::xml_element_to_instance`]: renders
+//! instances of an XSD-derived schema back into XML, for exporting documents
+//! to systems that consume XML.
+//!
+//! Instances are expected in the ordered children model:
+//!
+//! - Primitive properties become attributes (lists are space-separated)
+//! - `_text` becomes the element's text content
+//! - `children: List<{Type}Child>` becomes the element's content, in order.
+//!   Each child union holds one variant: `text` is a text node, a primitive is
+//!   a simple-typed element, and an instance is a complex element. Nested
+//!   unions (`{Type}Choice`, `{Head}SubstitutionGroup`) are unwrapped, so the
+//!   element is named after the innermost variant.
+//!
+//! Other nested instances (from the JSON-based parser) are written as child
+//! elements named after their property, after the ordered children.
+//!
+//! [`XmlToInstanceParser::xml_element_to_instance`]: crate::XmlToInstanceParser::xml_element_to_instance
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use quick_xml::events::{BytesDecl, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use terminusdb_schema::{Instance, InstanceProperty, PrimitiveValue, RelationValue, Schema};
+use thiserror::Error;
+
+/// Prefix for the target namespace when only the root element is qualified
+const ROOT_PREFIX: &str = "tns";
+
+/// Errors that can occur when serializing instances to XML.
+#[derive(Debug, Error)]
+pub enum XmlSerializeError {
+    /// No element name is known for the root instance's class
+    #[error("No root element name for class '{class}'")]
+    NoRootElement { class: String },
+
+    /// An attribute uses a namespace prefix that was not declared
+    #[error("Undeclared namespace prefix '{prefix}' in attribute '{attribute}'")]
+    UndeclaredPrefix { prefix: String, attribute: String },
+
+    /// The instance does not have the shape of a parsed XML document
+    #[error("Invalid instance structure: {message}")]
+    InvalidStructure { message: String },
+
+    /// Writing the XML failed
+    #[error("XML write error: {message}")]
+    Write { message: String },
+}
+
+impl XmlSerializeError {
+    /// Create an invalid structure error
+    pub fn invalid_structure(message: impl Into<String>) -> Self {
+        Self::InvalidStructure {
+            message: message.into(),
+        }
+    }
+
+    fn write(error: impl std::fmt::Display) -> Self {
+        Self::Write {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Result type for XML serialization operations
+pub type SerializeResult<T> = std::result::Result<T, XmlSerializeError>;
+
+/// Serializer for rendering TerminusDB instances as XML
+#[derive(Debug, Clone)]
+pub struct InstanceToXmlSerializer {
+    /// Root element name for each class
+    root_elements: HashMap<String, String>,
+    /// Target namespace, declared on the root element
+    namespace: Option<String>,
+    /// Whether child elements are in the target namespace
+    /// (`elementFormDefault="qualified"`)
+    qualified_children: bool,
+    /// Namespace prefixes used by attributes, by prefix
+    prefixes: BTreeMap<String, String>,
+    /// Spaces per indentation level, if pretty-printing
+    indent: Option<usize>,
+}
+
+impl InstanceToXmlSerializer {
+    /// Create a serializer without namespaces or root element names.
+    pub fn new() -> Self {
+        Self {
+            root_elements: HashMap::new(),
+            namespace: None,
+            qualified_children: true,
+            prefixes: BTreeMap::new(),
+            indent: None,
+        }
+    }
+
+    /// Set the element name used for root instances of `class`.
+    pub fn with_root_element(
+        mut self,
+        class: impl Into<String>,
+        element: impl Into<String>,
+    ) -> Self {
+        self.root_elements.insert(class.into(), element.into());
+        self
+    }
+
+    /// Set the target namespace, declared as the default namespace of the
+    /// document.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Keep child elements out of the target namespace, for schemas with
+    /// `elementFormDefault="unqualified"`. Only the root element is then
+    /// qualified, through a prefix.
+    pub fn with_unqualified_children(mut self) -> Self {
+        self.qualified_children = false;
+        self
+    }
+
+    /// Declare a namespace prefix used by attribute names (`xlink:href`).
+    pub fn with_prefix(mut self, prefix: impl Into<String>, namespace: impl Into<String>) -> Self {
+        self.prefixes.insert(prefix.into(), namespace.into());
+        self
+    }
+
+    /// Pretty-print with the given number of spaces per level.
+    ///
+    /// Indentation adds whitespace around text in mixed content, which the
+    /// parser trims again but which is not the original text.
+    pub fn with_indent(mut self, spaces: usize) -> Self {
+        self.indent = Some(spaces);
+        self
+    }
+
+    /// Serialize an instance as an XML document, naming the root element
+    /// after the instance's class.
+    pub fn to_xml(&self, instance: &Instance) -> SerializeResult<String> {
+        let class = instance.schema.class_name();
+        let element =
+            self.root_elements
+                .get(class)
+                .ok_or_else(|| XmlSerializeError::NoRootElement {
+                    class: class.clone(),
+                })?;
+        self.to_xml_as(instance, element)
+    }
+
+    /// Serialize an instance as an XML document with the given root element.
+    pub fn to_xml_as(&self, instance: &Instance, element: &str) -> SerializeResult<String> {
+        let mut xml = Vec::new();
+        self.write_xml(instance, element, &mut xml)?;
+        String::from_utf8(xml).map_err(XmlSerializeError::write)
+    }
+
+    /// Write an instance as an XML document with the given root element.
+    pub fn write_xml<W: Write>(
+        &self,
+        instance: &Instance,
+        element: &str,
+        writer: W,
+    ) -> SerializeResult<()> {
+        let mut writer = match self.indent {
+            Some(spaces) => Writer::new_with_indent(writer, b' ', spaces),
+            None => Writer::new(writer),
+        };
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+            .map_err(XmlSerializeError::write)?;
+
+        let mut root = BytesStart::new(element.to_string());
+        match &self.namespace {
+            Some(namespace) if !self.qualified_children => {
+                root = BytesStart::new(format!("{}:{}", ROOT_PREFIX, element));
+                root.push_attribute((
+                    format!("xmlns:{}", ROOT_PREFIX).as_str(),
+                    namespace.as_str(),
+                ));
+            }
+            Some(namespace) => root.push_attribute(("xmlns", namespace.as_str())),
+            None => {}
+        }
+        for (prefix, namespace) in &self.prefixes {
+            root.push_attribute((format!("xmlns:{}", prefix).as_str(), namespace.as_str()));
+        }
+
+        self.write_element(&mut writer, root, instance)
+    }
+
+    /// Write an element with the attributes and content of `instance`.
+    fn write_element<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        mut start: BytesStart<'_>,
+        instance: &Instance,
+    ) -> SerializeResult<()> {
+        let mut text = None;
+        let mut children = None;
+        let mut nested = Vec::new();
+
+        for (name, value) in &instance.properties {
+            match (name.as_str(), value) {
+                ("_text", InstanceProperty::Primitive(value)) => text = primitive_text(value),
+                ("children", InstanceProperty::Relations(values)) => children = Some(values),
+                (_, InstanceProperty::Primitive(value)) => {
+                    if let Some(value) = primitive_text(value) {
+                        self.check_prefix(name)?;
+                        start.push_attribute((name.as_str(), value.as_str()));
+                    }
+                }
+                (_, InstanceProperty::Primitives(values)) => {
+                    let value = values
+                        .iter()
+                        .filter_map(primitive_text)
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    self.check_prefix(name)?;
+                    start.push_attribute((name.as_str(), value.as_str()));
+                }
+                (_, InstanceProperty::Relation(value)) => nested.push((name, value)),
+                (_, InstanceProperty::Relations(values)) => {
+                    nested.extend(values.iter().map(|value| (name, value)))
+                }
+                (_, InstanceProperty::Any(_)) => {
+                    return Err(XmlSerializeError::invalid_structure(format!(
+                        "property '{}' has mixed values",
+                        name
+                    )))
+                }
+            }
+        }
+
+        let is_empty = text.is_none() && children.is_none_or(|c| c.is_empty()) && nested.is_empty();
+        if is_empty {
+            return writer
+                .write_event(Event::Empty(start))
+                .map_err(XmlSerializeError::write);
+        }
+
+        let end = start.to_end().into_owned();
+        writer
+            .write_event(Event::Start(start))
+            .map_err(XmlSerializeError::write)?;
+
+        if let Some(text) = text {
+            write_text(writer, &text)?;
+        }
+        for child in children.into_iter().flatten() {
+            match child {
+                RelationValue::One(child) => self.write_union_child(writer, child)?,
+                other => return Err(not_nested("children", other)),
+            }
+        }
+        for (name, value) in nested {
+            match value {
+                RelationValue::One(instance) => {
+                    self.write_element(writer, BytesStart::new(name.as_str()), instance)?
+                }
+                RelationValue::More(instances) => {
+                    for instance in instances {
+                        self.write_element(writer, BytesStart::new(name.as_str()), instance)?;
+                    }
+                }
+                other => return Err(not_nested(name, other)),
+            }
+        }
+
+        writer
+            .write_event(Event::End(end))
+            .map_err(XmlSerializeError::write)
+    }
+
+    /// Write the node held by a `{Type}Child` union instance.
+    fn write_union_child<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        union: &Instance,
+    ) -> SerializeResult<()> {
+        let mut variants = union.properties.iter();
+        let (variant, value) = match (variants.next(), variants.next()) {
+            (Some(variant), None) => variant,
+            _ => {
+                return Err(XmlSerializeError::invalid_structure(format!(
+                    "'{}' must have exactly one variant, found {}",
+                    union.schema.class_name(),
+                    union.properties.len()
+                )))
+            }
+        };
+
+        match value {
+            InstanceProperty::Primitive(value) if variant == "text" => {
+                match primitive_text(value) {
+                    Some(text) => write_text(writer, &text),
+                    None => Ok(()),
+                }
+            }
+            InstanceProperty::Primitive(value) => {
+                let start = BytesStart::new(variant.as_str());
+                match primitive_text(value) {
+                    Some(text) => {
+                        let end = start.to_end().into_owned();
+                        writer
+                            .write_event(Event::Start(start))
+                            .map_err(XmlSerializeError::write)?;
+                        write_text(writer, &text)?;
+                        writer
+                            .write_event(Event::End(end))
+                            .map_err(XmlSerializeError::write)
+                    }
+                    None => writer
+                        .write_event(Event::Empty(start))
+                        .map_err(XmlSerializeError::write),
+                }
+            }
+            InstanceProperty::Relation(RelationValue::One(inner))
+                if matches!(inner.schema, Schema::TaggedUnion { .. }) =>
+            {
+                self.write_union_child(writer, inner)
+            }
+            InstanceProperty::Relation(RelationValue::One(inner)) => {
+                self.write_element(writer, BytesStart::new(variant.as_str()), inner)
+            }
+            _ => Err(XmlSerializeError::invalid_structure(format!(
+                "variant '{}' of '{}' is neither a value nor a nested instance",
+                variant,
+                union.schema.class_name()
+            ))),
+        }
+    }
+
+    /// Check that a prefixed attribute name uses a declared prefix.
+    fn check_prefix(&self, attribute: &str) -> SerializeResult<()> {
+        match attribute.split_once(':') {
+            Some((prefix, _)) if prefix != "xml" && !self.prefixes.contains_key(prefix) => {
+                Err(XmlSerializeError::UndeclaredPrefix {
+                    prefix: prefix.to_string(),
+                    attribute: attribute.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for InstanceToXmlSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn write_text<W: Write>(writer: &mut Writer<W>, text: &str) -> SerializeResult<()> {
+    writer
+        .write_event(Event::Text(BytesText::new(text)))
+        .map_err(XmlSerializeError::write)
+}
+
+fn not_nested(property: &str, value: &RelationValue) -> XmlSerializeError {
+    XmlSerializeError::invalid_structure(format!(
+        "property '{}' holds a reference ({:?}) instead of a nested instance",
+        property, value
+    ))
+}
+
+/// Text of a primitive value, `None` for null and unit values.
+fn primitive_text(value: &PrimitiveValue) -> Option<String> {
+    match value {
+        PrimitiveValue::String(s) => Some(s.clone()),
+        PrimitiveValue::Number(n) => Some(n.to_string()),
+        PrimitiveValue::Bool(b) => Some(b.to_string()),
+        PrimitiveValue::Object(value) => Some(value.to_string()),
+        PrimitiveValue::Unit | PrimitiveValue::Null => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XmlToInstanceParser;
+    use terminusdb_schema::{Key, Property, TypeFamily};
+    use xmlschema::documents::Document;
+
+    fn property(name: &str, r#type: Option<TypeFamily>, class: &str) -> Property {
+        Property {
+            name: name.to_string(),
+            r#type,
+            class: class.to_string(),
+        }
+    }
+
+    fn note_schemas() -> Vec<Schema> {
+        vec![
+            Schema::Class {
+                id: "Note".to_string(),
+                base: None,
+                key: Key::ValueHash,
+                documentation: None,
+                subdocument: false,
+                r#abstract: false,
+                inherits: vec![],
+                unfoldable: false,
+                properties: vec![
+                    property("lang", Some(TypeFamily::Optional), "xsd:string"),
+                    property("children", Some(TypeFamily::List), "NoteChild"),
+                ],
+            },
+            Schema::TaggedUnion {
+                id: "NoteChild".to_string(),
+                base: None,
+                key: Key::Random,
+                r#abstract: false,
+                documentation: None,
+                subdocument: true,
+                properties: vec![
+                    property("text", None, "xsd:string"),
+                    property("b", None, "xsd:string"),
+                    property("note", None, "Note"),
+                ],
+                unfoldable: false,
+            },
+        ]
+    }
+
+    fn parse(schemas: &[Schema], xml: &str) -> Instance {
+        let doc = Document::from_string(xml).unwrap();
+        XmlToInstanceParser::new(schemas)
+            .xml_element_to_instance(doc.root.as_ref().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_mixed_content() {
+        let schemas = note_schemas();
+        let xml = r#"<note lang="en">Say <b>hi &amp; bye</b><note/></note>"#;
+        let instance = parse(&schemas, xml);
+
+        let serializer = InstanceToXmlSerializer::new().with_root_element("Note", "note");
+        let output = serializer.to_xml(&instance).unwrap();
+
+        assert_eq!(
+            output,
+            r#"<?xml version="1.0" encoding="UTF-8"?><note lang="en">Say<b>hi &amp; bye</b><note/></note>"#
+        );
+        assert_eq!(parse(&schemas, &output), instance);
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let schemas = note_schemas();
+        let instance = parse(&schemas, "<note><note/></note>");
+
+        let qualified = InstanceToXmlSerializer::new()
+            .with_namespace("http://example.com/notes")
+            .to_xml_as(&instance, "note")
+            .unwrap();
+        assert!(qualified.ends_with(r#"<note xmlns="http://example.com/notes"><note/></note>"#));
+
+        let unqualified = InstanceToXmlSerializer::new()
+            .with_namespace("http://example.com/notes")
+            .with_unqualified_children()
+            .to_xml_as(&instance, "note")
+            .unwrap();
+        assert!(unqualified
+            .ends_with(r#"<tns:note xmlns:tns="http://example.com/notes"><note/></tns:note>"#));
+    }
+
+    #[test]
+    fn test_errors() {
+        let schemas = note_schemas();
+        let mut instance = parse(&schemas, "<note/>");
+        let serializer = InstanceToXmlSerializer::new();
+
+        assert!(matches!(
+            serializer.to_xml(&instance),
+            Err(XmlSerializeError::NoRootElement { .. })
+        ));
+
+        instance.properties.insert(
+            "xlink:href".to_string(),
+            InstanceProperty::Primitive(PrimitiveValue::String("#a".to_string())),
+        );
+        assert!(matches!(
+            serializer.to_xml_as(&instance, "note"),
+            Err(XmlSerializeError::UndeclaredPrefix { .. })
+        ));
+        assert!(serializer
+            .with_prefix("xlink", "http://www.w3.org/1999/xlink")
+            .to_xml_as(&instance, "note")
+            .is_ok());
+    }
+}
@@ -6,11 +6,17 @@
 //! - Validating XML instances
 //! - Parsing XML to TerminusDB instances
 
+use crate::codegen::{self, RustModels};
+use crate::resolver::{SchemaBundle, SchemaLocation, SchemaResolver};
 use crate::schema_generator::XsdToSchemaGenerator;
 use crate::schema_model::XsdSchema;
+use crate::validation::{ValidationReport, Violation, XmlValidator};
 use crate::xml_parser::{ParseResult, XmlToInstanceParser};
+use crate::xml_serializer::InstanceToXmlSerializer;
 use crate::Result;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
 use terminusdb_schema::{Context, Instance, Schema};
 
 // xmlschema-rs imports for XML parsing and conversion
@@ -58,6 +64,10 @@ pub struct XsdModel {
 
     /// Namespace for generated schemas
     namespace: String,
+
+    /// Directory holding the documents fetched by `from_location`, removed
+    /// when the last clone of the model is dropped
+    resolved_dir: Option<Arc<TempDir>>,
 }
 
 impl XsdModel {
@@ -124,6 +134,7 @@ impl XsdModel {
             tdb_schemas,
             context,
             namespace,
+            resolved_dir: None,
         })
     }
 
@@ -182,6 +193,7 @@ impl XsdModel {
             tdb_schemas,
             context,
             namespace,
+            resolved_dir: None,
         })
     }
 
@@ -193,6 +205,44 @@ impl XsdModel {
         Self::from_entry_points(&[schema_path.as_ref()], catalog_path, None)
     }
 
+    /// Load an XSD model from a schema split across files or URLs.
+    ///
+    /// The schema at `location` (a path or URL) and every document it
+    /// includes or imports is fetched with `resolver` into a temporary
+    /// directory that lives as long as the model. See [`crate::resolver`].
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - Path or URL of the entry-point schema
+    /// * `resolver` - Fetches the schema documents
+    /// * `catalog_path` - Optional XML catalog for URN resolution
+    /// * `namespace` - Optional TDB namespace URI, as for `from_entry_points`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use terminusdb_xsd::{CachingResolver, FileResolver, XsdModel};
+    ///
+    /// let resolver = CachingResolver::new(FileResolver).with_cache_dir(".xsd-cache");
+    /// let model = XsdModel::from_location("schemas/order.xsd", &resolver, None::<&str>, None)?;
+    /// # Ok::<(), terminusdb_xsd::XsdError>(())
+    /// ```
+    pub fn from_location(
+        location: &str,
+        resolver: &dyn SchemaResolver,
+        catalog_path: Option<impl AsRef<Path>>,
+        namespace: Option<&str>,
+    ) -> Result<Self> {
+        let entry = SchemaLocation::parse(location)?;
+        let bundle = SchemaBundle::resolve(&entry, resolver)?;
+
+        let dir = tempfile::tempdir()?;
+        let entry_path = bundle.write_to(dir.path())?;
+        let mut model = Self::from_entry_points(&[entry_path], catalog_path, namespace)?;
+        model.resolved_dir = Some(Arc::new(dir));
+        Ok(model)
+    }
+
     /// Set the schema namespace URI for generated schemas.
     ///
     /// This updates both the internal namespace tracking and the TerminusDB
@@ -331,6 +381,44 @@ impl XsdModel {
         }
     }
 
+    /// Validate XML content, listing every violation with its position.
+    ///
+    /// Runs [`XmlValidator`] over the document, so missing elements, bad
+    /// values and the like are reported with their line and column. When it
+    /// finds nothing but the full XSD validation of [`validate_xml`] fails,
+    /// the errors of the full validation are reported, without a position.
+    ///
+    /// Use this before converting documents to instances, to show authors
+    /// what to fix.
+    ///
+    /// [`validate_xml`]: Self::validate_xml
+    pub fn validate_xml_detailed(&self, xml: &str) -> Result<ValidationReport> {
+        let mut report = self.validator().validate(xml);
+        if !report.is_valid() {
+            return Ok(report);
+        }
+
+        let schema_location = self
+            .xsd_schemas
+            .first()
+            .and_then(|s| s.schema_location.as_ref());
+        if let Some(schema_location) = schema_location {
+            let rust_schema = RustXsdSchema::from_file(std::path::Path::new(schema_location))?;
+            let result = rust_schema.validate_string(xml);
+            if !result.valid {
+                report
+                    .violations
+                    .extend(result.errors.into_iter().map(Violation::schema));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Create a validator reporting violations with their position.
+    pub fn validator(&self) -> XmlValidator<'_> {
+        XmlValidator::new(&self.xsd_schemas)
+    }
+
     /// Parse XML content into TerminusDB instances.
     ///
     /// This parses and validates the XML against the XSD schema, then converts
@@ -383,12 +471,61 @@ impl XsdModel {
                 crate::xml_parser::XmlParseError::parse("XML document has no root element")
             })?;
 
-        let element_map = self.element_to_class_map();
-        let parser = XmlToInstanceParser::with_element_mapping(&self.tdb_schemas, element_map);
-        let instance = parser.xml_element_to_instance(root)?;
+        let instance = self.instance_parser().xml_element_to_instance(root)?;
         Ok(vec![instance])
     }
 
+    /// Create an XML parser for this model's schemas and element mapping.
+    ///
+    /// Use this for repeated parsing, or to stream large documents with
+    /// [`XmlToInstanceParser::stream`].
+    pub fn instance_parser(&self) -> XmlToInstanceParser<'_> {
+        XmlToInstanceParser::with_element_mapping(&self.tdb_schemas, self.element_to_class_map())
+    }
+
+    /// Create an XML serializer for instances of this model's schemas.
+    ///
+    /// Root instances are named after the global element declared with their
+    /// class (the first one, when several share a type), and the document is
+    /// in the XSD's target namespace.
+    pub fn instance_serializer(&self) -> InstanceToXmlSerializer {
+        let element_map = self.element_to_class_map();
+        let mut root_elements = std::collections::HashMap::new();
+        for xsd in &self.xsd_schemas {
+            for elem in &xsd.root_elements {
+                let local_name = elem.name.split('}').last().unwrap_or(&elem.name);
+                if let Some(class) = element_map.get(&local_name.to_lowercase()) {
+                    root_elements
+                        .entry(class.clone())
+                        .or_insert_with(|| local_name.to_string());
+                }
+            }
+        }
+
+        let mut serializer = InstanceToXmlSerializer::new();
+        for (class, element) in root_elements {
+            serializer = serializer.with_root_element(class, element);
+        }
+        // Like the context, the namespace comes from the first XSD
+        if let Some(xsd) = self.xsd_schemas.first() {
+            if let Some(namespace) = &xsd.target_namespace {
+                serializer = serializer.with_namespace(namespace.clone());
+                if xsd.element_form_default.as_deref() == Some("unqualified") {
+                    serializer = serializer.with_unqualified_children();
+                }
+            }
+        }
+        serializer
+    }
+
+    /// Generate Rust `#[derive(TerminusDBModel)]` types for this model's
+    /// schemas.
+    ///
+    /// See [`crate::codegen`] for using the result from a build script.
+    pub fn generate_rust_models(&self) -> RustModels {
+        codegen::generate_rust_models(&self.tdb_schemas)
+    }
+
     /// Get statistics about the model.
     pub fn stats(&self) -> XsdModelStats {
         let total_complex_types: usize =
@@ -136,25 +136,47 @@ fn test_choice_generates_tagged_union() {
         }
     }
 
-    // DocumentType should have a property that references a TaggedUnion for the choice
-    let doc_type = find_class(&schemas, "DocumentType").expect("DocumentType not found");
-
-    if let Schema::Class { properties, .. } = doc_type {
-        // Should have title, footer, id, and a choice property
-        let prop_names: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
-        eprintln!("\nDocumentType properties: {:?}", prop_names);
-
-        // The choice elements should be represented somehow
-        // Either as separate optional properties or as a union type
-        let has_article = prop_names.contains(&"article");
-        let has_report = prop_names.contains(&"report");
-        let has_memo = prop_names.contains(&"memo");
-
-        // At least one representation of choice should exist
+    // The choice inside DocumentType's sequence becomes its own TaggedUnion,
+    // held by a single `choice` variant of the children union
+    let child_union =
+        find_tagged_union(&schemas, "DocumentTypeChild").expect("DocumentTypeChild not found");
+    let choice_union =
+        find_tagged_union(&schemas, "DocumentTypeChoice").expect("DocumentTypeChoice not found");
+
+    if let Schema::TaggedUnion { properties, .. } = child_union {
+        let variants: Vec<(&str, &str)> = properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.class.as_str()))
+            .collect();
         assert!(
-            has_article || has_report || has_memo,
-            "Choice elements not found in DocumentType. Properties: {:?}",
-            prop_names
+            variants.contains(&("title", "xsd:string")),
+            "{:?}",
+            variants
+        );
+        assert!(
+            variants.contains(&("choice", "DocumentTypeChoice")),
+            "{:?}",
+            variants
+        );
+        assert!(
+            !variants.iter().any(|(name, _)| *name == "article"),
+            "choice elements should not be flattened into the children union: {:?}",
+            variants
+        );
+    }
+
+    if let Schema::TaggedUnion { properties, .. } = choice_union {
+        let variants: Vec<(&str, &str)> = properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.class.as_str()))
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                ("article", "ArticleType"),
+                ("report", "ReportType"),
+                ("memo", "MemoType"),
+            ]
         );
     }
 }
@@ -229,29 +251,55 @@ fn test_payment_type_has_payment_method_choice() {
         .generate(&xsd_schema)
         .expect("Failed to generate schemas");
 
-    let payment_type = find_class(&schemas, "PaymentType").expect("PaymentType not found");
+    let payment_child =
+        find_tagged_union(&schemas, "PaymentTypeChild").expect("PaymentTypeChild not found");
 
-    if let Schema::Class { properties, .. } = payment_type {
-        let prop_names: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
-        eprintln!("\nPaymentType properties: {:?}", prop_names);
+    if let Schema::TaggedUnion { properties, .. } = payment_child {
+        let variant_names: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
+        eprintln!("\nPaymentTypeChild variants: {:?}", variant_names);
 
-        // Should have amount, currency, and payment method choice options
-        assert!(prop_names.contains(&"amount"), "amount not found");
-        assert!(prop_names.contains(&"currency"), "currency not found");
+        assert!(variant_names.contains(&"amount"), "amount not found");
+        assert!(variant_names.contains(&"currency"), "currency not found");
+        assert!(
+            variant_names.contains(&"choice"),
+            "payment method choice not found"
+        );
+    }
 
-        // Check for payment method choice elements
-        let has_credit_card = prop_names.contains(&"creditCard");
-        let has_bank_transfer = prop_names.contains(&"bankTransfer");
-        let has_digital_wallet = prop_names.contains(&"digitalWallet");
+    let payment_choice =
+        find_tagged_union(&schemas, "PaymentTypeChoice").expect("PaymentTypeChoice not found");
 
-        assert!(
-            has_credit_card || has_bank_transfer || has_digital_wallet,
-            "Payment method choice not found. Properties: {:?}",
-            prop_names
+    if let Schema::TaggedUnion { properties, .. } = payment_choice {
+        let variant_names: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            variant_names,
+            vec!["creditCard", "bankTransfer", "digitalWallet"]
         );
     }
 }
 
+#[test]
+fn test_whole_choice_content_stays_flat() {
+    let xsd_schema = XsdSchema::from_xsd_file(choice_xsd_path(), None::<&str>)
+        .expect("Failed to parse choice_types.xsd");
+
+    let mut generator = XsdToSchemaGenerator::new();
+    let schemas = generator
+        .generate(&xsd_schema)
+        .expect("Failed to generate schemas");
+
+    // MixedContentType's content model is a single repeating choice, so its
+    // children union already is the choice
+    assert!(find_tagged_union(&schemas, "MixedContentTypeChoice").is_none());
+
+    let child_union = find_tagged_union(&schemas, "MixedContentTypeChild")
+        .expect("MixedContentTypeChild not found");
+    if let Schema::TaggedUnion { properties, .. } = child_union {
+        let variant_names: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(variant_names, vec!["paragraph", "image", "code"]);
+    }
+}
+
 // ============================================================================
 // XML Parsing Tests
 // ============================================================================
@@ -497,6 +545,42 @@ fn test_parse_contact_with_optional_choice() {
     }
 }
 
+#[test]
+fn test_parse_ordered_choice_wraps_in_choice_union() {
+    let model =
+        XsdModel::from_file(choice_xsd_path(), None::<&str>).expect("Failed to load XSD model");
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<payment xmlns="http://example.com/choice">
+    <amount>150.00</amount>
+    <currency>USD</currency>
+    <bankTransfer>
+        <accountNumber>GB82WEST12345698765432</accountNumber>
+        <routingNumber>WESTGB2L</routingNumber>
+    </bankTransfer>
+</payment>"#;
+
+    let instances = model
+        .parse_xml_to_instances_ordered(xml)
+        .expect("Failed to parse payment");
+    let json = serde_json::to_value(&instances[0]).unwrap();
+    eprintln!("{}", serde_json::to_string_pretty(&json).unwrap());
+
+    let children = json["children"]
+        .as_array()
+        .expect("children should be a list");
+    assert_eq!(children.len(), 3);
+    assert_eq!(children[0]["amount"], "150.00");
+
+    let method = &children[2];
+    assert_eq!(method["@type"], "PaymentTypeChild");
+    assert_eq!(method["choice"]["@type"], "PaymentTypeChoice");
+    assert_eq!(
+        method["choice"]["bankTransfer"]["@type"],
+        "BankTransferType"
+    );
+}
+
 // ============================================================================
 // Full Integration Tests (Schema + Instance + DB)
 // ============================================================================
@@ -0,0 +1,25 @@
+//! Tests for Rust code generation from XSD
+//!
+//! The generated code is checked as text (with whitespace removed); compiling
+//! it would need a build script.
+
+use terminusdb_xsd::codegen::generate_rust_models_from_xsd;
+
+#[test]
+fn test_generate_models_from_substitution_groups() {
+    let xsd = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/substitution_groups.xsd"
+    );
+    let models =
+        generate_rust_models_from_xsd(xsd, None::<&str>).expect("Failed to generate Rust models");
+    let code: String = models.code.split_whitespace().collect();
+    eprintln!("{}", models.code);
+
+    assert!(code.contains("pubstructDrawingType{"));
+    assert!(code.contains("pubchildren:Vec<DrawingTypeChild>"));
+    assert!(code.contains("pubenumDrawingTypeChild{"));
+    assert!(code.contains("Shape(ShapeSubstitutionGroup)"));
+    assert!(code.contains("pubenumShapeSubstitutionGroup{Circle(CircleType),Rectangle(RectangleType),Square(RectangleType)}"));
+    assert!(models.warnings.is_empty(), "{:?}", models.warnings);
+}
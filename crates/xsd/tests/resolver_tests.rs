@@ -0,0 +1,68 @@
+//! Tests for loading schemas split across files
+//!
+//! The `multi_file` fixture has an entry point in `orders/` that includes and
+//! imports documents from `common/`.
+
+use terminusdb_xsd::resolver::{CachingResolver, FileResolver, SchemaBundle, SchemaLocation};
+use terminusdb_xsd::XsdModel;
+
+fn order_xsd_path() -> &'static str {
+    concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/multi_file/orders/order.xsd"
+    )
+}
+
+#[test]
+fn test_bundle_follows_include_and_import() {
+    let entry = SchemaLocation::parse(order_xsd_path()).unwrap();
+    let bundle = SchemaBundle::resolve(&entry, &FileResolver).expect("Failed to resolve bundle");
+
+    let names: Vec<&str> = bundle
+        .documents()
+        .iter()
+        .map(|d| d.file_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["order.xsd", "address.xsd", "party.xsd"]);
+    assert!(bundle
+        .entry()
+        .content
+        .contains(r#"<xs:include schemaLocation="address.xsd"/>"#));
+}
+
+#[test]
+fn test_model_from_location() {
+    let resolver = CachingResolver::new(FileResolver);
+    let model = XsdModel::from_location(order_xsd_path(), &resolver, None::<&str>, None)
+        .expect("Failed to load multi-file schema");
+
+    // Types from the entry point, the included and the imported document
+    for class in ["OrderType", "AddressType", "PartyType"] {
+        assert!(
+            model.find_schema(class).is_some(),
+            "{} not generated, got {:?}",
+            class,
+            model.class_names()
+        );
+    }
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<order xmlns="http://example.com/orders" xmlns:pty="http://example.com/parties" id="o1">
+    <customer><pty:name>Ada</pty:name></customer>
+    <shipTo><street>1 Main St</street><city>Springfield</city></shipTo>
+</order>"#;
+    model
+        .validate_xml(xml)
+        .expect("Order should validate against the resolved schema");
+}
+
+#[test]
+fn test_remote_location_needs_http_resolver() {
+    let result = XsdModel::from_location(
+        "https://example.com/schemas/order.xsd",
+        &FileResolver,
+        None::<&str>,
+        None,
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,145 @@
+//! Tests for diffing two versions of an XSD schema
+//!
+//! `catalog_v2.xsd` renames an element and a type, adds and renames members,
+//! changes a member's type and occurrence, and replaces an enumeration value.
+
+use terminusdb_xsd::schema_diff::{MemberKind, Occurs, XsdChange};
+use terminusdb_xsd::schema_model::XsdSchema;
+
+fn fixture(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/evolution/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn load(name: &str) -> XsdSchema {
+    XsdSchema::from_xsd_file(fixture(name), None::<&str>).expect("Failed to parse XSD")
+}
+
+#[test]
+fn test_diff_catalog_versions() {
+    let diff = XsdSchema::diff(&load("catalog_v1.xsd"), &load("catalog_v2.xsd"));
+    eprint!("{}", diff);
+
+    let expected = [
+        XsdChange::ElementRenamed {
+            from: "supplier".to_string(),
+            to: "vendor".to_string(),
+        },
+        XsdChange::TypeRenamed {
+            from: "productType".to_string(),
+            to: "itemType".to_string(),
+        },
+        XsdChange::MemberAdded {
+            type_name: "catalogType".to_string(),
+            kind: MemberKind::Attribute,
+            name: "region".to_string(),
+            required: false,
+        },
+        XsdChange::MemberAdded {
+            type_name: "supplierType".to_string(),
+            kind: MemberKind::Attribute,
+            name: "country".to_string(),
+            required: true,
+        },
+        XsdChange::MemberOccursChanged {
+            type_name: "supplierType".to_string(),
+            kind: MemberKind::Attribute,
+            name: "id".to_string(),
+            from: Occurs {
+                min: 0,
+                max: Some(1),
+            },
+            to: Occurs {
+                min: 1,
+                max: Some(1),
+            },
+        },
+        XsdChange::MemberAdded {
+            type_name: "supplierType".to_string(),
+            kind: MemberKind::Element,
+            name: "email".to_string(),
+            required: false,
+        },
+        XsdChange::MemberRenamed {
+            type_name: "supplierType".to_string(),
+            kind: MemberKind::Element,
+            from: "phone".to_string(),
+            to: "telephone".to_string(),
+        },
+        XsdChange::MemberTypeChanged {
+            type_name: "supplierType".to_string(),
+            kind: MemberKind::Element,
+            name: "rating".to_string(),
+            from: "int".to_string(),
+            to: "decimal".to_string(),
+        },
+        XsdChange::EnumValuesAdded {
+            type_name: "statusType".to_string(),
+            values: vec!["preorder".to_string()],
+        },
+        XsdChange::EnumValuesRemoved {
+            type_name: "statusType".to_string(),
+            values: vec!["discontinued".to_string()],
+        },
+    ];
+    for change in &expected {
+        assert!(diff.changes.contains(change), "missing change: {}", change);
+    }
+    assert_eq!(diff.changes.len(), expected.len(), "{:#?}", diff.changes);
+
+    // The renamed element, required attribute, tightened occurrence, renamed
+    // and retyped elements and removed enumeration value are breaking
+    assert!(!diff.is_compatible());
+    assert_eq!(diff.breaking_changes().count(), 6);
+}
+
+#[test]
+fn test_diff_identical_schemas() {
+    let schema = load("catalog_v1.xsd");
+    let diff = XsdSchema::diff(&schema, &schema);
+
+    assert!(diff.is_empty());
+    assert!(diff.is_compatible());
+    assert_eq!(diff.to_string(), "No changes\n");
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_migration_plan() {
+    use terminusdb_client::MigrationOperation;
+    use terminusdb_xsd::XsdModel;
+
+    let old = XsdModel::from_file(fixture("catalog_v1.xsd"), None::<&str>).unwrap();
+    let new = XsdModel::from_file(fixture("catalog_v2.xsd"), None::<&str>).unwrap();
+
+    let diff = XsdSchema::diff(&old.xsd_schemas()[0], &new.xsd_schemas()[0]);
+    let plan = diff.migration_plan(old.schemas(), new.schemas());
+    eprintln!("{}", serde_json::to_string_pretty(&plan).unwrap());
+
+    // Renames come first and keep instance data
+    assert!(matches!(
+        &plan[0],
+        MigrationOperation::MoveClass { from, to } if from == "ProductType" && to == "ItemType"
+    ));
+    assert!(plan.iter().any(|op| matches!(
+        op,
+        MigrationOperation::MoveClassProperty { from, to, .. } if from == "phone" && to == "telephone"
+    )));
+    assert!(plan.iter().any(|op| matches!(
+        op,
+        MigrationOperation::CreateClassProperty { class, property, .. }
+            if class == "SupplierType" && property == "country"
+    )));
+    assert!(plan.iter().any(|op| matches!(
+        op,
+        MigrationOperation::ExpandEnum { enum_name, .. } if enum_name == "StatusType"
+    )));
+    // Nothing is deleted: every removal was detected as a rename
+    assert!(!plan.iter().any(|op| matches!(
+        op,
+        MigrationOperation::DeleteClass { .. } | MigrationOperation::DeleteClassProperty { .. }
+    )));
+}
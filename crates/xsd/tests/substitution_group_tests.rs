@@ -0,0 +1,159 @@
+//! Tests for substitution group handling in XSD to TerminusDB conversion
+//!
+//! Elements declaring `substitutionGroup="head"` may appear wherever the head
+//! element is referenced. In TerminusDB schema, each head maps to a
+//! `{Head}SubstitutionGroup` TaggedUnion with one variant per element that can
+//! appear in its place.
+
+use terminusdb_schema::Schema;
+use terminusdb_xsd::schema_generator::XsdToSchemaGenerator;
+use terminusdb_xsd::schema_model::XsdSchema;
+use terminusdb_xsd::XsdModel;
+
+/// Path to the substitution_groups.xsd fixture
+fn substitution_xsd_path() -> &'static str {
+    concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/substitution_groups.xsd"
+    )
+}
+
+/// Variant (name, class) pairs of a TaggedUnion
+fn union_variants<'a>(schemas: &'a [Schema], name: &str) -> Vec<(&'a str, &'a str)> {
+    match schemas
+        .iter()
+        .find(|s| matches!(s, Schema::TaggedUnion { id, .. } if id == name))
+    {
+        Some(Schema::TaggedUnion { properties, .. }) => properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.class.as_str()))
+            .collect(),
+        _ => panic!("TaggedUnion {} not found", name),
+    }
+}
+
+#[test]
+fn test_substitution_groups_extracted() {
+    let xsd_schema = XsdSchema::from_xsd_file(substitution_xsd_path(), None::<&str>)
+        .expect("Failed to parse substitution_groups.xsd");
+
+    let shape = xsd_schema
+        .substitution_groups
+        .iter()
+        .find(|g| g.head == "shape")
+        .expect("shape substitution group not found");
+    assert!(shape.is_abstract);
+    assert_eq!(shape.members, vec!["circle", "rectangle"]);
+
+    let rectangle = xsd_schema
+        .substitution_groups
+        .iter()
+        .find(|g| g.head == "rectangle")
+        .expect("rectangle substitution group not found");
+    assert!(!rectangle.is_abstract);
+    assert_eq!(rectangle.members, vec!["square"]);
+}
+
+#[test]
+fn test_substitution_group_generates_tagged_union() {
+    let xsd_schema = XsdSchema::from_xsd_file(substitution_xsd_path(), None::<&str>)
+        .expect("Failed to parse substitution_groups.xsd");
+
+    let mut generator = XsdToSchemaGenerator::new();
+    let schemas = generator
+        .generate(&xsd_schema)
+        .expect("Failed to generate schemas");
+
+    // The abstract head is left out; members of members are included
+    assert_eq!(
+        union_variants(&schemas, "ShapeSubstitutionGroup"),
+        vec![
+            ("circle", "CircleType"),
+            ("rectangle", "RectangleType"),
+            ("square", "RectangleType"),
+        ]
+    );
+
+    // A non-abstract head is a variant of its own group
+    assert_eq!(
+        union_variants(&schemas, "RectangleSubstitutionGroup"),
+        vec![("rectangle", "RectangleType"), ("square", "RectangleType")]
+    );
+
+    // The reference to the head resolves to the substitution group union
+    let children = union_variants(&schemas, "DrawingTypeChild");
+    assert!(
+        children.contains(&("shape", "ShapeSubstitutionGroup")),
+        "{:?}",
+        children
+    );
+}
+
+#[test]
+fn test_parse_substitution_group_members() {
+    let model = XsdModel::from_file(substitution_xsd_path(), None::<&str>)
+        .expect("Failed to load XSD model");
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<drawing xmlns="http://example.com/shapes">
+    <name>Shapes</name>
+    <circle color="red" radius="2.5"/>
+    <square width="4" height="4"/>
+</drawing>"#;
+
+    let instances = model
+        .parse_xml_to_instances_ordered(xml)
+        .expect("Failed to parse drawing");
+    let json = serde_json::to_value(&instances[0]).unwrap();
+    eprintln!("{}", serde_json::to_string_pretty(&json).unwrap());
+
+    let children = json["children"]
+        .as_array()
+        .expect("children should be a list");
+    assert_eq!(children.len(), 3);
+
+    // Each substitute is wrapped in the head's union under the head's variant
+    let circle = &children[1]["shape"];
+    assert_eq!(circle["@type"], "ShapeSubstitutionGroup");
+    assert_eq!(circle["circle"]["@type"], "CircleType");
+    assert_eq!(circle["circle"]["radius"], "2.5");
+
+    let square = &children[2]["shape"];
+    assert_eq!(square["@type"], "ShapeSubstitutionGroup");
+    assert_eq!(square["square"]["@type"], "RectangleType");
+}
+
+#[test]
+fn test_stream_substitution_group_members() {
+    use terminusdb_xsd::StreamOptions;
+
+    let model = XsdModel::from_file(substitution_xsd_path(), None::<&str>)
+        .expect("Failed to load XSD model");
+
+    let shapes: String = (0..25)
+        .map(|i| format!(r#"<circle radius="{i}"/><square width="{i}" height="{i}"/>"#))
+        .collect();
+    let xml = format!(
+        r#"<drawing xmlns="http://example.com/shapes"><name>Many</name>{}</drawing>"#,
+        shapes
+    );
+
+    let parser = model.instance_parser();
+    let options = StreamOptions::new()
+        .with_batch_size(20)
+        .with_record_elements(["circle", "rectangle", "square"]);
+    let batches = parser
+        .stream(xml.as_bytes(), options)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to stream drawing");
+
+    // Records are taken from inside the root, which itself is never buffered
+    assert_eq!(
+        batches.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![20, 20, 10]
+    );
+
+    let json = serde_json::to_value(&batches[2][9]).unwrap();
+    assert_eq!(json["@type"], "RectangleType");
+    assert_eq!(json["width"], "24");
+}
@@ -0,0 +1,115 @@
+//! Tests for validating XML documents with line/column diagnostics
+
+use terminusdb_xsd::{ViolationKind, XsdModel};
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_catalog_violations() {
+    let model =
+        XsdModel::from_file(fixture("catalog.xsd"), None::<&str>).expect("Failed to load XSD");
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<catalog xmlns="http://example.com/catalog" version="1.0">
+    <product id="p1" sku="A-1">
+        <name>Widget</name>
+        <price>9.99</price>
+        <quantity>many</quantity>
+        <inStock>true</inStock>
+    </product>
+    <product id="p2">
+        <name>Gadget</name>
+        <quantity>3</quantity>
+        <inStock>maybe</inStock>
+    </product>
+    <category id="c1"><name>Tools</name></category>
+</catalog>"#;
+    let report = model
+        .validate_xml_detailed(xml)
+        .expect("Failed to validate");
+    eprintln!("{}", report);
+
+    let found: Vec<(ViolationKind, String, &str)> = report
+        .violations
+        .iter()
+        .map(|v| {
+            (
+                v.kind,
+                v.position.expect("violation has a position").to_string(),
+                v.path.as_deref().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                ViolationKind::InvalidValue,
+                "6:19".to_string(),
+                "/catalog/product[1]/quantity[1]"
+            ),
+            (
+                ViolationKind::MissingAttribute,
+                "9:5".to_string(),
+                "/catalog/product[2]"
+            ),
+            (
+                ViolationKind::MissingElement,
+                "9:5".to_string(),
+                "/catalog/product[2]"
+            ),
+            (
+                ViolationKind::InvalidValue,
+                "12:18".to_string(),
+                "/catalog/product[2]/inStock[1]"
+            ),
+        ]
+    );
+    assert_eq!(
+        report.violations[2].message,
+        "Missing required element <price> in <product>"
+    );
+    assert_eq!(
+        report.violations[3].message,
+        "Invalid value 'maybe' for <inStock>: expected true, false, 1 or 0"
+    );
+}
+
+#[test]
+fn test_valid_document_has_no_violations() {
+    let model =
+        XsdModel::from_file(fixture("catalog.xsd"), None::<&str>).expect("Failed to load XSD");
+
+    let xml = r#"<catalog xmlns="http://example.com/catalog" version="1.0">
+    <product id="p1" sku="A-1">
+        <name>Widget</name>
+        <price>9.99</price>
+        <quantity>3</quantity>
+        <inStock>1</inStock>
+    </product>
+    <category id="c1"><name>Tools</name></category>
+</catalog>"#;
+    let report = model
+        .validate_xml_detailed(xml)
+        .expect("Failed to validate");
+    assert!(report.is_valid(), "{}", report);
+}
+
+#[test]
+fn test_missing_choice() {
+    let model =
+        XsdModel::from_file(fixture("choice_types.xsd"), None::<&str>).expect("Failed to load XSD");
+
+    let xml = r#"<document xmlns="http://example.com/choice" id="d1">
+    <title>Quarterly</title>
+    <footer>End</footer>
+</document>"#;
+    let report = model.validator().validate(xml);
+    assert_eq!(report.violations.len(), 1, "{}", report);
+    assert_eq!(
+        report.violations[0].to_string(),
+        "1:1: Missing one of <article>, <report>, <memo> in <document>"
+    );
+}
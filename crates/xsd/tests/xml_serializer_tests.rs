@@ -0,0 +1,83 @@
+//! Tests for serializing TerminusDB instances back to XML
+//!
+//! Round trips parse XML into instances, serialize them, and parse the output
+//! again: the instances must be unchanged.
+
+use terminusdb_xsd::XsdModel;
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_round_trip_substitution_groups() {
+    let model = XsdModel::from_file(fixture("substitution_groups.xsd"), None::<&str>)
+        .expect("Failed to load XSD model");
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<drawing xmlns="http://example.com/shapes">
+    <name>Shapes</name>
+    <circle color="red" radius="2.5"/>
+    <square width="4" height="4"/>
+</drawing>"#;
+    let instances = model
+        .parse_xml_to_instances_ordered(xml)
+        .expect("Failed to parse drawing");
+
+    let output = model
+        .instance_serializer()
+        .to_xml(&instances[0])
+        .expect("Failed to serialize drawing");
+    eprintln!("{}", output);
+
+    // Substitution group members keep their own element names
+    assert!(output.contains(r#"<drawing xmlns="http://example.com/shapes">"#));
+    assert!(output.contains("<name>Shapes</name>"));
+    assert!(output.contains(r#"<circle color="red" radius="2.5"/>"#));
+    assert!(output.contains(r#"<square height="4" width="4"/>"#));
+
+    let reparsed = model
+        .parse_xml_to_instances_ordered(&output)
+        .expect("Failed to parse serialized drawing");
+    assert_eq!(reparsed, instances);
+
+    // The output is valid against the schema
+    model
+        .validate_xml(&output)
+        .expect("Serialized drawing should be valid");
+}
+
+#[test]
+fn test_round_trip_choice() {
+    let model = XsdModel::from_file(fixture("choice_types.xsd"), None::<&str>)
+        .expect("Failed to load XSD model");
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<payment xmlns="http://example.com/choice">
+    <amount>150.00</amount>
+    <currency>USD</currency>
+    <bankTransfer>
+        <accountNumber>GB82WEST12345698765432</accountNumber>
+        <routingNumber>WESTGB2L</routingNumber>
+    </bankTransfer>
+</payment>"#;
+    let instances = model
+        .parse_xml_to_instances_ordered(xml)
+        .expect("Failed to parse payment");
+
+    let output = model
+        .instance_serializer()
+        .with_indent(2)
+        .to_xml(&instances[0])
+        .expect("Failed to serialize payment");
+    eprintln!("{}", output);
+
+    // The choice union is unwrapped to the chosen element
+    assert!(output.contains("<bankTransfer>"));
+    assert!(!output.contains("<choice>"));
+
+    let reparsed = model
+        .parse_xml_to_instances_ordered(&output)
+        .expect("Failed to parse serialized payment");
+    assert_eq!(reparsed, instances);
+}